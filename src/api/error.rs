@@ -0,0 +1,135 @@
+use std::fmt;
+
+/// Longest part of a non-JSON error body kept in the message.
+const RAW_BODY_PREVIEW: usize = 200;
+
+/// One entry of `data.failed_items`: the agents (or other IDs) an error applies to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedItem {
+    pub ids: Vec<String>,
+    pub code: Option<i64>,
+    pub message: String,
+    pub remediation: Option<String>,
+}
+
+/// A Wazuh API error answer: the problem fields of a failed request (`title`, `detail`,
+/// `error`, `remediation`), or a request where every item failed (`failed_items`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiError {
+    pub status: u16,
+    pub code: Option<i64>,
+    pub title: Option<String>,
+    pub detail: String,
+    pub remediation: Option<String>,
+    pub failed_items: Vec<FailedItem>,
+}
+
+fn str_field(value: &serde_json::Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()).map(String::from)
+}
+
+fn failed_items(body: &serde_json::Value) -> Vec<FailedItem> {
+    let Some(items) = body.pointer("/data/failed_items").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+    items.iter().map(|item| {
+        let error = item.get("error").unwrap_or(&serde_json::Value::Null);
+        FailedItem {
+            ids: item.get("id").and_then(|v| v.as_array())
+                .map(|ids| ids.iter().map(|id| id.as_str().map(String::from).unwrap_or_else(|| id.to_string())).collect())
+                .unwrap_or_default(),
+            code: error.get("code").and_then(|c| c.as_i64()),
+            message: str_field(error, "message").unwrap_or_else(|| "failed".to_string()),
+            remediation: str_field(error, "remediation"),
+        }
+    }).collect()
+}
+
+impl ApiError {
+    /// Parses the body of a non-2xx answer; a body that is not Wazuh JSON is kept, shortened, as the detail.
+    pub fn parse(status: u16, body: &str) -> Self {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(body) else {
+            let mut detail: String = body.trim().chars().take(RAW_BODY_PREVIEW).collect();
+            if body.trim().chars().count() > RAW_BODY_PREVIEW {
+                detail.push('…');
+            }
+            return ApiError { status, code: None, title: None, detail, remediation: None, failed_items: Vec::new() };
+        };
+        ApiError {
+            status,
+            code: json.get("error").and_then(|c| c.as_i64()),
+            title: str_field(&json, "title"),
+            detail: str_field(&json, "detail").or_else(|| str_field(&json, "message")).unwrap_or_default(),
+            remediation: str_field(&json, "remediation"),
+            failed_items: failed_items(&json),
+        }
+    }
+
+    /// A 2xx answer where every item failed (`"error": 2`); partial failures stay successes.
+    pub fn from_all_failed(status: u16, body: &serde_json::Value) -> Option<Self> {
+        if body.get("error").and_then(|c| c.as_i64()) != Some(2) {
+            return None;
+        }
+        let failed_items = failed_items(body);
+        if failed_items.is_empty() {
+            return None;
+        }
+        Some(ApiError {
+            status,
+            code: Some(2),
+            title: None,
+            detail: str_field(body, "message").unwrap_or_else(|| "Every item failed".to_string()),
+            remediation: None,
+            failed_items,
+        })
+    }
+
+    /// Everything past the one-line message: each failed item with its IDs, then the remediation
+    /// texts, one per line. Empty when there is nothing more to say.
+    pub fn details(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.failed_items.iter().map(|item| {
+            let code = item.code.map(|c| format!(" ({})", c)).unwrap_or_default();
+            if item.ids.is_empty() {
+                format!("• {}{}", item.message, code)
+            } else {
+                format!("• {}: {}{}", item.ids.join(", "), item.message, code)
+            }
+        }).collect();
+        let mut remediations: Vec<&str> = self.remediation.iter().map(String::as_str)
+            .chain(self.failed_items.iter().filter_map(|i| i.remediation.as_deref()))
+            .collect();
+        remediations.dedup();
+        lines.extend(remediations.into_iter().map(|r| format!("Remediation: {}", r)));
+        lines
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match (self.detail.is_empty(), &self.title) {
+            (false, _) => self.detail.as_str(),
+            (true, Some(title)) => title.as_str(),
+            (true, None) => "no details",
+        };
+        // The code of an all-failed answer is only the overall status; each item has its own
+        let code = self.code.filter(|_| self.failed_items.is_empty()).map(|c| format!("error {}", c));
+        let status = (self.status >= 400).then(|| format!("HTTP {}", self.status));
+        let context: Vec<String> = code.into_iter().chain(status).collect();
+        if context.is_empty() {
+            write!(f, "{}", message)
+        } else {
+            write!(f, "{} ({})", message, context.join(", "))
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// The error as a message whose first line is the summary and whose further lines are the
+/// per-item failures and remediation of a Wazuh API error.
+pub fn describe(error: &anyhow::Error) -> String {
+    match error.downcast_ref::<ApiError>() {
+        Some(api_error) => std::iter::once(api_error.to_string()).chain(api_error.details()).collect::<Vec<_>>().join("\n"),
+        None => error.to_string(),
+    }
+}
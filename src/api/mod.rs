@@ -1,41 +1,292 @@
 use reqwest::Client;
 use crate::models::{Config, AuthResponse, WazuhAgentsResponse, WazuhGroupsResponse};
+use crate::app::rules::RulesetKind;
 use anyhow::{Result, anyhow};
+use futures_util::future::{BoxFuture, FutureExt, Shared};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+const DEFAULT_API_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_INDEXER_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_RETRY_BASE_DELAY_MS: u64 = 250;
+const MAX_RETRY_DELAY_MS: u64 = 10_000;
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+/// Requests kept for the request inspector popup.
+pub const REQUEST_LOG_SIZE: usize = 50;
+/// Request bodies longer than this are cut in the request inspector.
+pub const REQUEST_BODY_PREVIEW: usize = 4000;
+
+pub mod error;
+pub mod tls;
+pub use error::ApiError;
+
+/// Exponential backoff: `base * 2^(attempt-1)`, capped at 10s.
+pub fn backoff_delay(base_ms: u64, attempt: u32) -> Duration {
+    let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+    Duration::from_millis(base_ms.saturating_mul(factor).min(MAX_RETRY_DELAY_MS))
+}
+
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::BAD_GATEWAY
+        || status == reqwest::StatusCode::SERVICE_UNAVAILABLE
+        || status == reqwest::StatusCode::GATEWAY_TIMEOUT
+}
+
+/// Whether a request can be sent again after it reached the server. Anything else (a login,
+/// an agent restart, a group change) is only retried when the connection was never made.
+fn is_idempotent(method: &reqwest::Method) -> bool {
+    *method == reqwest::Method::GET || *method == reqwest::Method::HEAD
+}
+
+/// Every caller of a shared GET gets its own copy of the error; an `ApiError` stays one, so
+/// `error::describe` can still show its details.
+fn unshare_error(error: &anyhow::Error) -> anyhow::Error {
+    match error.downcast_ref::<ApiError>() {
+        Some(api_error) => api_error.clone().into(),
+        None => anyhow!("{:#}", error),
+    }
+}
+
+/// Alert count per agent id.
+pub type AgentAlertCounts = std::collections::HashMap<String, usize>;
+/// CVE counts by severity per agent id.
+pub type AgentVulnCountMap = std::collections::HashMap<String, crate::models::AgentVulnCounts>;
+
+/// Status and body of a GET, shared by every caller that asked for the same URL meanwhile.
+type SharedGet = Shared<BoxFuture<'static, Result<(u16, Arc<Vec<u8>>), Arc<anyhow::Error>>>>;
+
+/// Running totals of request round-trip times.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct LatencyStats {
+    pub count: u64,
+    pub total_secs: f64,
+    pub last_secs: f64,
+}
+
+/// One attempt made by `send_with_retry`, for the request inspector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestRecord {
+    pub time: chrono::DateTime<chrono::Utc>,
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub elapsed_ms: u64,
+    pub attempt: u32,
+    pub body: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Request body as shown in the inspector: `password` fields masked, long bodies cut.
+pub(crate) fn body_preview(bytes: &[u8]) -> String {
+    let text = match serde_json::from_slice::<serde_json::Value>(bytes) {
+        Ok(mut json) => {
+            if let Some(obj) = json.as_object_mut() {
+                for (key, value) in obj.iter_mut() {
+                    if key.eq_ignore_ascii_case("password") {
+                        *value = serde_json::json!("********");
+                    }
+                }
+            }
+            serde_json::to_string_pretty(&json).unwrap_or_default()
+        }
+        Err(_) => String::from_utf8_lossy(bytes).into_owned(),
+    };
+    match text.char_indices().nth(REQUEST_BODY_PREVIEW) {
+        Some((cut, _)) => format!("{}\n… ({} bytes total)", &text[..cut], text.len()),
+        None => text,
+    }
+}
+
 #[derive(Clone)]
 pub struct WazuhApi {
     pub client: Client,
     pub config: Config,
     pub token: Arc<RwLock<Option<String>>>,
+    /// Human readable retry activity, shown in the status bar while set
+    pub retry_status: Arc<std::sync::Mutex<Option<String>>>,
+    pub latency: Arc<std::sync::Mutex<LatencyStats>>,
+    /// Last `REQUEST_LOG_SIZE` attempts, oldest first
+    pub request_log: Arc<std::sync::Mutex<std::collections::VecDeque<RequestRecord>>>,
+    /// Caps requests on the wire at `network.max_concurrent_requests`; waiters are served in order
+    pub limiter: Arc<tokio::sync::Semaphore>,
+    /// GETs in flight by URL, so repeated key presses join the running request
+    inflight: Arc<std::sync::Mutex<std::collections::HashMap<String, SharedGet>>>,
 }
 
 impl WazuhApi {
     pub fn new(config: Config) -> Self {
         let client = Client::builder()
             .danger_accept_invalid_certs(true)
-            .timeout(std::time::Duration::from_secs(10))
+            .connect_timeout(Duration::from_secs(DEFAULT_API_TIMEOUT_SECS))
             .build()
             .unwrap();
+        let max_concurrent = config.network.max_concurrent_requests.unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS).max(1);
         
         Self {
             client,
             config,
             token: Arc::new(RwLock::new(None)),
+            retry_status: Arc::new(std::sync::Mutex::new(None)),
+            latency: Arc::new(std::sync::Mutex::new(LatencyStats::default())),
+            request_log: Arc::new(std::sync::Mutex::new(std::collections::VecDeque::new())),
+            limiter: Arc::new(tokio::sync::Semaphore::new(max_concurrent)),
+            inflight: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+        }
+    }
+
+    /// Alert severity bands from the `[severity]` config section.
+    pub fn severity(&self) -> crate::app::severity::SeverityScale {
+        crate::app::severity::SeverityScale::from_config(&self.config)
+    }
+
+    pub fn retry_status(&self) -> Option<String> {
+        self.retry_status.lock().ok().and_then(|s| s.clone())
+    }
+
+    pub fn latency(&self) -> LatencyStats {
+        self.latency.lock().map(|l| *l).unwrap_or_default()
+    }
+
+    fn record_latency(&self, elapsed: Duration) {
+        if let Ok(mut l) = self.latency.lock() {
+            l.count += 1;
+            l.total_secs += elapsed.as_secs_f64();
+            l.last_secs = elapsed.as_secs_f64();
+        }
+    }
+
+    /// Recorded requests, newest first.
+    pub fn request_log(&self) -> Vec<RequestRecord> {
+        self.request_log.lock().map(|log| log.iter().rev().cloned().collect()).unwrap_or_default()
+    }
+
+    fn record_request(&self, record: RequestRecord) {
+        if let Ok(mut log) = self.request_log.lock() {
+            log.push_back(record);
+            while log.len() > REQUEST_LOG_SIZE {
+                log.pop_front();
+            }
+        }
+    }
+
+    fn set_retry_status(&self, status: Option<String>) {
+        if let Ok(mut s) = self.retry_status.lock() {
+            *s = status;
+        }
+    }
+
+    /// Picks the timeout for a URL: endpoint override, then indexer/API default.
+    pub fn timeout_for(&self, url: &str) -> Duration {
+        let net = &self.config.network;
+        let override_secs = net.endpoint_timeouts.iter()
+            .filter(|(fragment, _)| url.contains(fragment.as_str()))
+            .max_by_key(|(fragment, _)| fragment.len())
+            .map(|(_, secs)| *secs);
+        if let Some(secs) = override_secs {
+            return Duration::from_secs(secs);
+        }
+
+        let is_indexer = self.config.os_url.as_ref().map(|os| url.starts_with(os.as_str())).unwrap_or(false);
+        if is_indexer {
+            Duration::from_secs(net.indexer_timeout_secs.unwrap_or(DEFAULT_INDEXER_TIMEOUT_SECS))
+        } else {
+            Duration::from_secs(net.api_timeout_secs.unwrap_or(DEFAULT_API_TIMEOUT_SECS))
+        }
+    }
+
+    /// Sends a request, retrying timeouts, connection failures and 429/502/503/504
+    /// responses with exponential backoff. Only GET, HEAD and `retry_safe` requests (read-only
+    /// indexer queries sent as POST) are retried once sent; anything else is retried on
+    /// connection failures alone, so a write never runs twice.
+    async fn send_with_retry(&self, url: &str, rb: reqwest::RequestBuilder, retry_safe: bool) -> Result<reqwest::Response> {
+        let net = &self.config.network;
+        let max_retries = net.max_retries.unwrap_or(DEFAULT_MAX_RETRIES);
+        let base_delay = net.retry_base_delay_ms.unwrap_or(DEFAULT_RETRY_BASE_DELAY_MS);
+        let timeout = self.timeout_for(url);
+        let mut attempt = 0;
+
+        loop {
+            let req = rb.try_clone()
+                .ok_or_else(|| anyhow!("Request cannot be retried"))?
+                .timeout(timeout);
+
+            let built = rb.try_clone().and_then(|r| r.build().ok());
+            let method = built.as_ref().map(|r| r.method().to_string()).unwrap_or_default();
+            let idempotent = retry_safe || built.as_ref().is_some_and(|r| is_idempotent(r.method()));
+            let body = built.as_ref().and_then(|r| r.body()).and_then(|b| b.as_bytes()).map(body_preview);
+            let permit = self.limiter.acquire().await?;
+            let started = std::time::Instant::now();
+            let result = req.send().await;
+            let elapsed = started.elapsed();
+            drop(permit);
+            self.record_latency(elapsed);
+            self.record_request(RequestRecord {
+                time: chrono::Utc::now(),
+                method: method.clone(),
+                url: url.to_string(),
+                status: result.as_ref().ok().map(|r| r.status().as_u16()),
+                elapsed_ms: elapsed.as_millis() as u64,
+                attempt,
+                body,
+                error: result.as_ref().err().map(|e| e.to_string()),
+            });
+
+            let reason = match result {
+                Ok(resp) if !idempotent || !is_retryable_status(resp.status()) || attempt >= max_retries => {
+                    tracing::debug!(%method, url, status = resp.status().as_u16(), elapsed_ms = elapsed.as_millis() as u64, attempt, "request");
+                    self.set_retry_status(None);
+                    return Ok(resp);
+                }
+                Ok(resp) => format!("HTTP {}", resp.status().as_u16()),
+                // A connection failure means the request never left; a timeout may come after it was handled
+                Err(e) if (e.is_connect() || (idempotent && e.is_timeout())) && attempt < max_retries => {
+                    if e.is_timeout() { "timeout".to_string() } else { "connection error".to_string() }
+                }
+                Err(e) => {
+                    tracing::error!(%method, url, elapsed_ms = elapsed.as_millis() as u64, error = %e, "request failed");
+                    self.set_retry_status(None);
+                    return Err(e.into());
+                }
+            };
+
+            attempt += 1;
+            tracing::warn!(%method, url, elapsed_ms = elapsed.as_millis() as u64, attempt, reason = %reason, "retrying request");
+            self.set_retry_status(Some(format!("Retrying ({}/{}) after {}", attempt, max_retries, reason)));
+            tokio::time::sleep(backoff_delay(base_delay, attempt)).await;
+        }
+    }
+
+    /// Login URL and, with `auth_context` set, the context to send to the `run_as` endpoint.
+    pub fn auth_request(&self) -> Result<(String, Option<serde_json::Value>)> {
+        let Some(context) = self.config.auth_context.as_deref().map(str::trim).filter(|c| !c.is_empty()) else {
+            return Ok((format!("{}/security/user/authenticate", self.config.url), None));
+        };
+        let context: serde_json::Value = serde_json::from_str(context)
+            .map_err(|e| anyhow!("auth_context is not valid JSON: {}", e))?;
+        if !context.is_object() {
+            return Err(anyhow!("auth_context must be a JSON object"));
         }
+        Ok((format!("{}/security/user/authenticate/run_as", self.config.url), Some(context)))
     }
 
     pub async fn authenticate(&self) -> Result<String> {
-        let url = format!("{}/security/user/authenticate", self.config.url);
+        let (url, context) = self.auth_request()?;
         
-        let response = self.client
+        let mut rb = self.client
             .post(&url)
-            .basic_auth(&self.config.username, Some(&self.config.password))
-            .send()
-            .await?;
+            .basic_auth(&self.config.username, Some(&self.config.password));
+        if let Some(context) = &context {
+            rb = rb.json(context);
+        }
+        let response = self.send_with_retry(&url, rb, false).await?;
 
         if !response.status().is_success() {
+            if context.is_some() {
+                return Err(anyhow!("run_as authentication failed with status: {} (the API user needs allow_run_as)", response.status()));
+            }
             return Err(anyhow!("Authentication failed with status: {}", response.status()));
         }
 
@@ -48,6 +299,32 @@ impl WazuhApi {
         Ok(token)
     }
 
+    /// Uses another API user from now on; the next `authenticate` logs in as them.
+    pub fn set_credentials(&mut self, username: &str, password: &str) {
+        self.config.username = username.to_string();
+        self.config.password = password.to_string();
+    }
+
+    /// Heartbeat: one `GET /manager/info` without retries, returning its latency in ms.
+    /// With `reconnect` the cached token is dropped and a new one requested first.
+    pub async fn ping(&self, reconnect: bool) -> Result<u64> {
+        if reconnect {
+            *self.token.write().await = None;
+        }
+        let url = format!("{}/manager/info", self.config.url);
+        let timeout = self.timeout_for(&url);
+        let started = std::time::Instant::now();
+        let mut response = self.client.get(&url).bearer_auth(self.get_token().await?).timeout(timeout).send().await?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            let token = self.authenticate().await?;
+            response = self.client.get(&url).bearer_auth(token).timeout(timeout).send().await?;
+        }
+        if !response.status().is_success() {
+            return Err(anyhow!("HTTP {}", response.status().as_u16()));
+        }
+        Ok(started.elapsed().as_millis() as u64)
+    }
+
     async fn get_token(&self) -> Result<String> {
         {
             let token_lock = self.token.read().await;
@@ -58,7 +335,45 @@ impl WazuhApi {
         self.authenticate().await
     }
 
+    /// Authenticated API call. GETs for a URL already being fetched wait for that response
+    /// instead of sending another request.
     async fn request(&self, method: reqwest::Method, url: &str, body: Option<serde_json::Value>) -> Result<reqwest::Response> {
+        if method != reqwest::Method::GET {
+            return self.send_request(method, url, body).await;
+        }
+
+        let shared = {
+            let mut inflight = self.inflight.lock().map_err(|_| anyhow!("request table poisoned"))?;
+            match inflight.get(url) {
+                Some(pending) => {
+                    tracing::debug!(url, "joined in-flight request");
+                    pending.clone()
+                }
+                None => {
+                    let api = self.clone();
+                    let key = url.to_string();
+                    let pending = async move {
+                        let result = async {
+                            let response = api.send_request(reqwest::Method::GET, &key, None).await?;
+                            let status = response.status().as_u16();
+                            Ok::<_, anyhow::Error>((status, Arc::new(response.bytes().await?.to_vec())))
+                        }.await;
+                        if let Ok(mut inflight) = api.inflight.lock() {
+                            inflight.remove(&key);
+                        }
+                        result.map_err(Arc::new)
+                    }.boxed().shared();
+                    inflight.insert(url.to_string(), pending.clone());
+                    pending
+                }
+            }
+        };
+
+        let (status, body) = shared.await.map_err(|e| unshare_error(&e))?;
+        Ok(http::Response::builder().status(status).body(body.to_vec())?.into())
+    }
+
+    async fn send_request(&self, method: reqwest::Method, url: &str, body: Option<serde_json::Value>) -> Result<reqwest::Response> {
         let token = self.get_token().await?;
         let mut rb = self.client.request(method.clone(), url).bearer_auth(&token);
         
@@ -66,29 +381,105 @@ impl WazuhApi {
             rb = rb.json(&b);
         }
 
-        let response = rb.send().await?;
+        let response = self.send_with_retry(url, rb, false).await?;
         let status = response.status();
 
         if status == reqwest::StatusCode::UNAUTHORIZED {
             let token = self.authenticate().await?;
-            let mut rb = self.client.request(method, url).bearer_auth(token);
+            let mut rb = self.client.request(method.clone(), url).bearer_auth(token);
             if let Some(b) = body {
                 rb = rb.json(&b);
             }
-            let response = rb.send().await?;
-            if !response.status().is_success() {
-                let error_text = response.text().await?;
-                return Err(anyhow!("Request failed with status {}: {}", status, error_text));
-            }
-            return Ok(response);
+            let response = self.send_with_retry(url, rb, false).await?;
+            return Self::check_response(method, response).await;
         }
 
+        Self::check_response(method, response).await
+    }
+
+    /// Turns a Wazuh error answer into an `ApiError`: any non-2xx status, and a change
+    /// (non-GET) the manager applied to none of the items.
+    async fn check_response(method: reqwest::Method, response: reqwest::Response) -> Result<reqwest::Response> {
+        let status = response.status();
         if !status.is_success() {
             let error_text = response.text().await?;
-            return Err(anyhow!("Request failed with status {}: {}", status, error_text));
+            return Err(ApiError::parse(status.as_u16(), &error_text).into());
+        }
+        if method == reqwest::Method::GET {
+            return Ok(response);
         }
+        let body = response.bytes().await?.to_vec();
+        if let Some(error) = serde_json::from_slice(&body).ok().and_then(|json| ApiError::from_all_failed(status.as_u16(), &json)) {
+            return Err(error.into());
+        }
+        Ok(http::Response::builder().status(status.as_u16()).body(body)?.into())
+    }
+
+    pub async fn get_permissions(&self) -> Result<crate::app::Permissions> {
+        let url = format!("{}/security/users/me", self.config.url);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        let me: serde_json::Value = response.json().await?;
+        let user = me.get("data")
+            .and_then(|d| d.get("affected_items"))
+            .and_then(|items| items.get(0));
+        let username = user.and_then(|u| u.get("username")).and_then(|u| u.as_str())
+            .unwrap_or(&self.config.username)
+            .to_string();
+        let roles = user.and_then(|u| u.get("roles")).and_then(|r| r.as_array())
+            .map(|arr| arr.iter().map(|r| r.to_string().trim_matches('"').to_string()).collect())
+            .unwrap_or_default();
+
+        let url = format!("{}/security/users/me/policies", self.config.url);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        let policies: serde_json::Value = response.json().await?;
+        let data = policies.get("data").cloned().unwrap_or(serde_json::Value::Null);
+
+        Ok(crate::app::Permissions::from_policies(&username, roles, &data))
+    }
+
+    pub async fn list_users(&self) -> Result<crate::models::WazuhUsersResponse> {
+        let url = format!("{}/security/users?limit=500", self.config.url);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn list_roles(&self) -> Result<crate::models::WazuhRolesResponse> {
+        let url = format!("{}/security/roles?limit=500", self.config.url);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn list_policies(&self) -> Result<crate::models::WazuhPoliciesResponse> {
+        let url = format!("{}/security/policies?limit=500", self.config.url);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
 
-        Ok(response)
+    pub async fn create_user(&self, username: &str, password: &str) -> Result<serde_json::Value> {
+        let url = format!("{}/security/users", self.config.url);
+        let body = serde_json::json!({ "username": username, "password": password });
+        let response = self.request(reqwest::Method::POST, &url, Some(body)).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn delete_user(&self, user_id: u32) -> Result<serde_json::Value> {
+        let url = format!("{}/security/users?user_ids={}", self.config.url, user_id);
+        let response = self.request(reqwest::Method::DELETE, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn assign_roles_to_user(&self, user_id: u32, role_ids: &[u32]) -> Result<serde_json::Value> {
+        let ids: Vec<String> = role_ids.iter().map(|id| id.to_string()).collect();
+        let url = format!("{}/security/users/{}/roles?role_ids={}", self.config.url, user_id, ids.join(","));
+        let response = self.request(reqwest::Method::POST, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn remove_roles_from_user(&self, user_id: u32, role_ids: &[u32]) -> Result<serde_json::Value> {
+        let ids: Vec<String> = role_ids.iter().map(|id| id.to_string()).collect();
+        let url = format!("{}/security/users/{}/roles?role_ids={}", self.config.url, user_id, ids.join(","));
+        let response = self.request(reqwest::Method::DELETE, &url, None).await?;
+        Ok(response.json().await?)
     }
 
     pub async fn list_agents(&self, group: Option<&str>, offset: u32, limit: u32) -> Result<WazuhAgentsResponse> {
@@ -103,12 +494,157 @@ impl WazuhApi {
         Ok(response.json().await?)
     }
 
+    /// Agents running an older version than the manager.
+    pub async fn get_outdated_agents(&self) -> Result<crate::models::WazuhOutdatedAgentsResponse> {
+        let url = format!("{}/agents/outdated?limit=500", self.config.url);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
     pub async fn get_groups(&self) -> Result<WazuhGroupsResponse> {
         let url = format!("{}/groups", self.config.url);
         let response = self.request(reqwest::Method::GET, &url, None).await?;
         Ok(response.json().await?)
     }
 
+    /// Files shared with a group (agent.conf, merged.mg, CDB lists, ...).
+    pub async fn get_group_files(&self, group_id: &str) -> Result<crate::models::WazuhGroupFilesResponse> {
+        let url = format!("{}/groups/{}/files", self.config.url, group_id);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Content of one group file as plain text.
+    pub async fn get_group_file(&self, group_id: &str, filename: &str) -> Result<String> {
+        let url = format!("{}/groups/{}/files/{}?raw=true", self.config.url, group_id, filename);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.text().await?)
+    }
+
+    pub async fn get_cdb_lists(&self) -> Result<crate::models::WazuhCdbListsResponse> {
+        let url = format!("{}/lists?limit=500", self.config.url);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Raw `key:value` content of a CDB list file.
+    pub async fn get_cdb_list_file(&self, filename: &str) -> Result<String> {
+        let url = format!("{}/lists/files/{}?raw=true", self.config.url, filename);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.text().await?)
+    }
+
+    /// Replaces a CDB list file with `content`; the manager recompiles it on its own.
+    pub async fn put_cdb_list_file(&self, filename: &str, content: String) -> Result<serde_json::Value> {
+        let url = format!("{}/lists/files/{}?overwrite=true", self.config.url, filename);
+        self.upload(&url, content).await
+    }
+
+    /// Local rule or decoder files only; the ruleset shipped with Wazuh is overwritten on upgrade.
+    pub async fn get_ruleset_files(&self, kind: RulesetKind) -> Result<crate::models::WazuhRuleFilesResponse> {
+        let url = format!("{}/{}/files?relative_dirname={}&limit=500", self.config.url, kind.endpoint(), kind.dirname());
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn get_ruleset_file(&self, kind: RulesetKind, filename: &str) -> Result<String> {
+        let url = format!("{}/{}/files/{}?raw=true", self.config.url, kind.endpoint(), filename);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.text().await?)
+    }
+
+    pub async fn put_ruleset_file(&self, kind: RulesetKind, filename: &str, content: String) -> Result<serde_json::Value> {
+        let url = format!("{}/{}/files/{}?overwrite=true", self.config.url, kind.endpoint(), filename);
+        self.upload(&url, content).await
+    }
+
+    pub async fn delete_ruleset_file(&self, kind: RulesetKind, filename: &str) -> Result<serde_json::Value> {
+        let url = format!("{}/{}/files/{}", self.config.url, kind.endpoint(), filename);
+        let response = self.request(reqwest::Method::DELETE, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Errors reported by the manager's configuration check (ossec.conf, rules, decoders).
+    pub async fn validate_manager_config(&self) -> Result<Vec<String>> {
+        let url = format!("{}/manager/configuration/validation", self.config.url);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        let json: serde_json::Value = response.json().await?;
+        Ok(parse_config_validation(&json))
+    }
+
+    /// State of each manager daemon, the equivalent of agent status for agent 000.
+    pub async fn get_manager_status(&self) -> Result<Vec<(String, String)>> {
+        let url = format!("{}/manager/status", self.config.url);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        let json: serde_json::Value = response.json().await?;
+        Ok(parse_manager_status(&json))
+    }
+
+    /// The newest `limit` lines of the manager's `ossec.log`.
+    pub async fn get_manager_logs(&self, limit: u32) -> Result<Vec<crate::models::ManagerLogEntry>> {
+        let url = format!("{}/manager/logs?limit={}&sort=-timestamp", self.config.url, limit);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        let json: serde_json::Value = response.json().await?;
+        let items = json.pointer("/data/affected_items").cloned().unwrap_or_default();
+        Ok(serde_json::from_value(items).unwrap_or_default())
+    }
+
+    /// Certificate chains of the Wazuh API and, when configured, the indexer.
+    pub async fn certificate_chains(&self) -> Vec<crate::app::certs::CertChain> {
+        let timeout = Duration::from_secs(self.config.network.api_timeout_secs.unwrap_or(DEFAULT_API_TIMEOUT_SECS));
+        let endpoints = std::iter::once(("Wazuh API", self.config.url.clone()))
+            .chain(self.config.os_url.clone().map(|url| ("Indexer", url)));
+        let mut chains = Vec::new();
+        for (endpoint, url) in endpoints {
+            let result = tls::peer_certificates(&url, timeout).await
+                .map_err(|e| e.to_string())
+                .and_then(|ders| match ders.iter().map(|der| crate::app::certs::parse_certificate(der)).collect::<Option<Vec<_>>>() {
+                    Some(certs) if !certs.is_empty() => Ok(certs),
+                    Some(_) => Err("The server sent no certificate".to_string()),
+                    None => Err("Could not read the certificate".to_string()),
+                });
+            chains.push(crate::app::certs::CertChain { endpoint: endpoint.to_string(), url, result });
+        }
+        chains
+    }
+
+    pub async fn restart_manager(&self) -> Result<serde_json::Value> {
+        let url = format!("{}/manager/restart", self.config.url);
+        let response = self.request(reqwest::Method::PUT, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    /// PUTs a raw file body, as the `/lists/files` and `/rules/files` endpoints expect.
+    async fn upload(&self, url: &str, content: String) -> Result<serde_json::Value> {
+        let upload = |token: String| self.client.put(url)
+            .bearer_auth(token)
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(content.clone());
+
+        let mut response = self.send_with_retry(url, upload(self.get_token().await?), false).await?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            response = self.send_with_retry(url, upload(self.authenticate().await?), false).await?;
+        }
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await?;
+            return Err(ApiError::parse(status.as_u16(), &error_text).into());
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Decodes and matches one raw log line against the manager's rules, like `wazuh-logtest`.
+    pub async fn logtest(&self, event: &str, log_format: &str, location: &str, token: Option<&str>) -> Result<crate::models::LogtestResult> {
+        let url = format!("{}/logtest", self.config.url);
+        let mut body = serde_json::json!({ "event": event, "log_format": log_format, "location": location });
+        if let Some(token) = token {
+            body["token"] = serde_json::json!(token);
+        }
+        let response = self.request(reqwest::Method::PUT, &url, Some(body)).await?;
+        let json: serde_json::Value = response.json().await?;
+        Ok(parse_logtest(&json))
+    }
+
     pub async fn create_group(&self, group_id: &str) -> Result<serde_json::Value> {
         let url = format!("{}/groups", self.config.url);
         let body = serde_json::json!({ "group_id": group_id });
@@ -140,14 +676,16 @@ impl WazuhApi {
         Ok(response.json().await?)
     }
 
-    pub async fn get_processes(&self, agent_id: &str) -> Result<crate::models::WazuhProcessesResponse> {
-        let url = format!("{}/syscollector/{}/processes", self.config.url, agent_id);
+    /// One page of the agent's processes; `total_affected_items` tells how many there are.
+    pub async fn get_processes(&self, agent_id: &str, offset: u32, limit: u32) -> Result<crate::models::WazuhProcessesResponse> {
+        let url = format!("{}/syscollector/{}/processes?offset={}&limit={}", self.config.url, agent_id, offset, limit);
         let response = self.request(reqwest::Method::GET, &url, None).await?;
         Ok(response.json().await?)
     }
 
-    pub async fn get_programs(&self, agent_id: &str) -> Result<crate::models::WazuhProgramsResponse> {
-        let url = format!("{}/syscollector/{}/packages", self.config.url, agent_id);
+    /// One page of the agent's installed packages.
+    pub async fn get_programs(&self, agent_id: &str, offset: u32, limit: u32) -> Result<crate::models::WazuhProgramsResponse> {
+        let url = format!("{}/syscollector/{}/packages?offset={}&limit={}", self.config.url, agent_id, offset, limit);
         let response = self.request(reqwest::Method::GET, &url, None).await?;
         Ok(response.json().await?)
     }
@@ -170,12 +708,13 @@ impl WazuhApi {
             ]
         });
 
-        let mut rb = self.client.post(format!("{}/wazuh-states-vulnerabilities*/_search", os_url));
+        let search_url = format!("{}/{}/_search", os_url, self.config.indices.vulnerabilities());
+        let mut rb = self.client.post(&search_url);
         if let (Some(u), Some(p)) = (&self.config.os_username, &self.config.os_password) {
             rb = rb.basic_auth(u, Some(p));
         }
 
-        let response = rb.json(&query).send().await?;
+        let response = self.send_with_retry(&search_url, rb.json(&query), true).await?;
         
         if !response.status().is_success() {
             let error_text = response.text().await?;
@@ -201,6 +740,7 @@ impl WazuhApi {
                 }),
                 name: pkg.and_then(|p| p.name.clone()),
                 version: pkg.and_then(|p| p.version.clone()),
+                cvss: src.vulnerability.score.as_ref().map(|s| s.base),
             }
         }).collect();
         
@@ -214,6 +754,52 @@ impl WazuhApi {
         })
     }
 
+    pub async fn get_rootcheck(&self, agent_id: &str) -> Result<crate::models::WazuhRootcheckResponse> {
+        let url = format!("{}/rootcheck/{}?limit=500&sort=-date_last", self.config.url, agent_id);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn get_rootcheck_last_scan(&self, agent_id: &str) -> Result<crate::models::WazuhLastScanResponse> {
+        let url = format!("{}/rootcheck/{}/last_scan", self.config.url, agent_id);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Asks the agents to run a rootcheck (and syscheck) scan now.
+    pub async fn run_rootcheck(&self, agent_ids: &[&str]) -> Result<serde_json::Value> {
+        let url = format!("{}/rootcheck?agents_list={}", self.config.url, agent_ids.join(","));
+        let response = self.request(reqwest::Method::PUT, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Asks the agents to run a syscheck (FIM) scan now instead of at the next scheduled run.
+    pub async fn run_syscheck(&self, agent_ids: &[&str]) -> Result<serde_json::Value> {
+        let url = format!("{}/syscheck?agents_list={}", self.config.url, agent_ids.join(","));
+        let response = self.request(reqwest::Method::PUT, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Runs a custom active-response script (from the agent's `active-response/bin`) with `arguments`.
+    pub async fn run_active_response(&self, agent_ids: &[&str], script: &str, arguments: &[String]) -> Result<serde_json::Value> {
+        let url = format!("{}/active-response?agents_list={}", self.config.url, agent_ids.join(","));
+        let body = serde_json::json!({ "command": format!("!{}", script), "arguments": arguments });
+        let response = self.request(reqwest::Method::PUT, &url, Some(body)).await?;
+        Ok(response.json().await?)
+    }
+
+    /// The alert carrying the output of remote command `id`, once the agent reported it.
+    pub async fn get_remote_command_result(&self, id: &str) -> Result<Option<serde_json::Value>> {
+        let body = self.search_alerts(&remote_command_query(id)).await?;
+        Ok(body.pointer("/hits/hits/0/_source").cloned())
+    }
+
+    pub async fn get_syscheck_last_scan(&self, agent_id: &str) -> Result<crate::models::WazuhLastScanResponse> {
+        let url = format!("{}/syscheck/{}/last_scan", self.config.url, agent_id);
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
     pub async fn upgrade_agents(&self, agent_ids: &[&str]) -> Result<serde_json::Value> {
         let url = format!("{}/agents/upgrade?agents_list={}", self.config.url, agent_ids.join(","));
         let response = self.request(reqwest::Method::PUT, &url, None).await?;
@@ -234,6 +820,15 @@ impl WazuhApi {
             _ => component,  // For syscheck, auth, etc. section = component
         };
         
+        // The manager answers on its own endpoint, one item holding the section
+        if agent_id == crate::models::MANAGER_AGENT_ID {
+            let url = format!("{}/manager/configuration?section={}", self.config.url, section);
+            let response = self.request(reqwest::Method::GET, &url, None).await?;
+            let json: serde_json::Value = response.json().await?;
+            let item = json.pointer("/data/affected_items/0").ok_or_else(|| anyhow!("manager has no {} section", section))?;
+            return Ok(item.get(section).unwrap_or(item).clone());
+        }
+
         let url = format!("{}/agents/{}/config/{}/{}", self.config.url, agent_id, component, section);
         let response = self.request(reqwest::Method::GET, &url, None).await?;
         let json: serde_json::Value = response.json().await?;
@@ -273,6 +868,20 @@ impl WazuhApi {
         Ok(summary)
     }
 
+    /// ID, name and status of the given agents.
+    pub async fn get_agents_status(&self, agent_ids: &[&str]) -> Result<WazuhAgentsResponse> {
+        let url = format!("{}/agents?agents_list={}&select=id,name,status&limit={}", self.config.url, agent_ids.join(","), agent_ids.len().max(1));
+        let response = self.request(reqwest::Method::GET, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn delete_agents(&self, agent_ids: &[&str]) -> Result<serde_json::Value> {
+        // older_than=0s: the caller already decided which agents are stale
+        let url = format!("{}/agents?agents_list={}&status=all&older_than=0s", self.config.url, agent_ids.join(","));
+        let response = self.request(reqwest::Method::DELETE, &url, None).await?;
+        Ok(response.json().await?)
+    }
+
     pub async fn restart_agents(&self, agent_ids: &[&str]) -> Result<serde_json::Value> {
         let url = format!("{}/agents/restart?agents_list={}", self.config.url, agent_ids.join(","));
         let response = self.request(reqwest::Method::PUT, &url, None).await?;
@@ -294,121 +903,855 @@ impl WazuhApi {
     }
 
     pub async fn get_logs(&self, agent_id: Option<&str>, minutes: u32, offset: u32, limit: u32, filter: Option<&crate::app::LogFilter>) -> Result<serde_json::Value> {
+        self.search_alerts(&log_search_query(agent_id, minutes, offset, limit, filter, &self.severity())).await
+    }
+
+    /// Alerts from the same agent within `minutes` either side of `timestamp`, oldest first.
+    pub async fn get_alert_context(&self, agent_id: &str, timestamp: &str, minutes: u32, limit: u32) -> Result<serde_json::Value> {
+        self.search_alerts(&alert_context_query(agent_id, timestamp, minutes, limit)).await
+    }
+
+    /// Alerts from any agent with the given `data.srcip` in the last `minutes`, newest first.
+    pub async fn get_alerts_by_srcip(&self, srcip: &str, minutes: u32, limit: u32) -> Result<serde_json::Value> {
+        self.search_alerts(&srcip_pivot_query(srcip, minutes, limit)).await
+    }
+
+    /// Distinct connections in the agent's network alerts over the last `minutes`, busiest first.
+    pub async fn get_agent_connections(&self, agent_id: &str, minutes: u32) -> Result<Vec<crate::models::NetworkConnection>> {
+        let body = self.search_alerts(&connections_query(agent_id, minutes)).await?;
+        Ok(parse_connections(&body))
+    }
+
+    /// The agent's alerts per hour over the last `models::ALERT_HOURS` hours, in one aggregation.
+    pub async fn get_agent_alert_hours(&self, agent_id: &str) -> Result<crate::models::HourlyAlerts> {
+        let body = self.search_alerts(&agent_alert_hours_query(agent_id)).await?;
+        Ok(parse_agent_alert_hours(&body))
+    }
+
+    /// Runs one of the prebuilt hunts over the last `minutes`.
+    pub async fn get_hunt(&self, hunt: crate::app::hunts::Hunt, minutes: u32) -> Result<Vec<crate::app::hunts::HuntRow>> {
+        let body = self.search_alerts(&hunt_query(hunt, minutes)).await?;
+        Ok(parse_hunt(&body))
+    }
+
+    /// Fleet-wide CVE counts by severity from the vulnerability states index.
+    pub async fn get_vuln_summary(&self) -> Result<crate::models::VulnerabilitySummary> {
+        let body = self.search_vulnerabilities(&vuln_summary_query()).await?;
+        Ok(parse_vuln_summary(&body))
+    }
+
+    /// CVE counts per agent and severity in a single aggregation over the vulnerability states index.
+    pub async fn get_agent_vuln_counts(&self, max_agents: u32) -> Result<std::collections::HashMap<String, crate::models::AgentVulnCounts>> {
+        let body = self.search_vulnerabilities(&agent_vuln_counts_query(max_agents)).await?;
+        Ok(parse_agent_vuln_counts(&body))
+    }
+
+    /// What the health scores need for every agent, in one `_msearch`: alerts in the high band
+    /// or above over the last `minutes`, and CVE counts. Each search fails on its own.
+    pub async fn get_health_inputs(&self, minutes: u32, max_agents: u32) -> Result<(Result<AgentAlertCounts>, Result<AgentVulnCountMap>)> {
+        let mut responses = self.msearch(&[
+            (self.config.indices.alerts(), agent_high_alerts_query(minutes, self.severity().high, max_agents)),
+            (self.config.indices.vulnerabilities(), agent_vuln_counts_query(max_agents)),
+        ]).await?.into_iter();
+        let alerts = responses.next().unwrap_or_else(|| Err(anyhow!("No response for the alert count search")));
+        let vulns = responses.next().unwrap_or_else(|| Err(anyhow!("No response for the vulnerability count search")));
+        Ok((alerts.map(|body| parse_agent_alert_counts(&body)), vulns.map(|body| parse_agent_vuln_counts(&body))))
+    }
+
+    /// Alert counts by severity band over the last `minutes`, across all matching alerts.
+    pub async fn get_alert_severity_counts(&self, minutes: u32) -> Result<crate::app::ThreatStats> {
+        let body = self.search_alerts(&alert_severity_query(minutes, &self.severity())).await?;
+        Ok(parse_severity_counts(&body))
+    }
+
+    /// Alert counts by severity, top rules and CVE counts across `agent_ids`.
+    pub async fn get_group_posture(&self, agent_ids: &[String], minutes: u32) -> Result<crate::app::GroupPosture> {
+        if agent_ids.is_empty() {
+            return Ok(crate::app::GroupPosture::default());
+        }
+        let alerts = self.search_alerts(&group_alerts_query(agent_ids, minutes, &self.severity())).await?;
+        let vulns = self.search_vulnerabilities(&group_vulns_query(agent_ids)).await?;
+        let (stats, top_rules) = parse_group_alerts(&alerts);
+        Ok(crate::app::GroupPosture {
+            agents: agent_ids.len(),
+            alerts: stats,
+            top_rules,
+            vulns: parse_vuln_summary(&vulns),
+        })
+    }
+
+    /// Alert counts per day and severity over the last `days` days, with days starting at
+    /// midnight in `time_zone` (an offset such as `+02:00`).
+    pub async fn get_alert_trend(&self, days: u32, time_zone: &str) -> Result<Vec<(String, crate::app::ThreatStats)>> {
+        let body = self.search_alerts(&alert_trend_query(days, time_zone)).await?;
+        Ok(parse_alert_trend(&body, &self.severity()))
+    }
+
+    async fn search_vulnerabilities(&self, query: &serde_json::Value) -> Result<serde_json::Value> {
+        let os_url = self.config.os_url.as_ref().ok_or_else(|| anyhow!("OpenSearch URL not configured"))?;
+        let search_url = format!("{}/{}/_search", os_url, self.config.indices.vulnerabilities());
+        let mut rb = self.client.post(&search_url);
+        if let (Some(u), Some(p)) = (&self.config.os_username, &self.config.os_password) {
+            rb = rb.basic_auth(u, Some(p));
+        }
+
+        let response = self.send_with_retry(&search_url, rb.json(query), true).await?;
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("OpenSearch query failed: {}", error_text));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// The dashboard's alert hits and fleet CVE summary in one `_msearch` round trip. Each
+    /// search fails on its own, so a missing vulnerability index still leaves the alerts.
+    pub async fn get_dashboard_searches(&self, minutes: u32, limit: u32) -> Result<(Result<serde_json::Value>, Result<crate::models::VulnerabilitySummary>)> {
+        let mut responses = self.msearch(&[
+            (self.config.indices.alerts(), log_search_query(None, minutes, 0, limit, None, &self.severity())),
+            (self.config.indices.vulnerabilities(), vuln_summary_query()),
+        ]).await?.into_iter();
+        let alerts = responses.next().unwrap_or_else(|| Err(anyhow!("No response for the alert search")));
+        let vulns = responses.next().unwrap_or_else(|| Err(anyhow!("No response for the vulnerability search")));
+        Ok((alerts, vulns.map(|body| parse_vuln_summary(&body))))
+    }
+
+    /// Runs several searches in one request; responses come back in the same order.
+    async fn msearch(&self, searches: &[(&str, serde_json::Value)]) -> Result<Vec<Result<serde_json::Value>>> {
+        let os_url = self.config.os_url.as_ref().ok_or_else(|| anyhow!("OpenSearch URL not configured"))?;
+        let url = format!("{}/_msearch", os_url);
+        let mut rb = self.client.post(&url)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(msearch_body(searches));
+        if let (Some(u), Some(p)) = (&self.config.os_username, &self.config.os_password) {
+            rb = rb.basic_auth(u, Some(p));
+        }
+
+        let response = self.send_with_retry(&url, rb, true).await?;
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("OpenSearch query failed: {}", error_text));
+        }
+        let body: serde_json::Value = response.json().await?;
+        Ok(parse_msearch(&body))
+    }
+
+    /// Cluster status, Wazuh index sizes and per-node disk usage of the indexer.
+    pub async fn get_indexer_health(&self) -> Result<crate::models::IndexerHealth> {
+        let cluster = self.indexer_get("_cluster/health").await?;
+        let indices = self.indexer_get(&format!("_cat/indices/{}?format=json&bytes=b&s=index:desc&h=index,health,status,docs.count,store.size", self.config.indices.health())).await?;
+        let disks = self.indexer_get("_cat/allocation?format=json&bytes=b&h=node,disk.percent,disk.used,disk.avail").await?;
+        Ok(crate::models::IndexerHealth {
+            cluster: serde_json::from_value(cluster)?,
+            indices: serde_json::from_value(indices)?,
+            // Unassigned shards show up as a row without a node
+            disks: serde_json::from_value::<Vec<crate::models::IndexerAllocation>>(disks)?
+                .into_iter().filter(|d| d.disk_percent.is_some()).collect(),
+        })
+    }
+
+    async fn indexer_get(&self, path: &str) -> Result<serde_json::Value> {
+        let os_url = self.config.os_url.as_ref().ok_or_else(|| anyhow!("OpenSearch URL not configured"))?;
+        let url = format!("{}/{}", os_url.trim_end_matches('/'), path);
+        let mut rb = self.client.get(&url);
+        if let (Some(u), Some(p)) = (&self.config.os_username, &self.config.os_password) {
+            rb = rb.basic_auth(u, Some(p));
+        }
+
+        let response = self.send_with_retry(&url, rb, false).await?;
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("OpenSearch request failed: {}", error_text));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// First page of a scrolled alert search; `_scroll_id` in the response continues it.
+    pub async fn start_alert_scroll(&self, query: &serde_json::Value) -> Result<serde_json::Value> {
         let os_url = self.config.os_url.as_ref().ok_or_else(|| anyhow!("OpenSearch URL not configured"))?;
+        let url = format!("{}/{}/_search?scroll={}", os_url, self.config.indices.alerts(), SCROLL_KEEPALIVE);
+        self.indexer_post(&url, query).await
+    }
+
+    pub async fn scroll_alerts(&self, scroll_id: &str) -> Result<serde_json::Value> {
+        let os_url = self.config.os_url.as_ref().ok_or_else(|| anyhow!("OpenSearch URL not configured"))?;
+        let url = format!("{}/_search/scroll", os_url);
+        self.indexer_post(&url, &serde_json::json!({ "scroll": SCROLL_KEEPALIVE, "scroll_id": scroll_id })).await
+    }
+
+    /// Frees the scroll context instead of leaving it to expire.
+    pub async fn clear_alert_scroll(&self, scroll_id: &str) -> Result<()> {
+        let os_url = self.config.os_url.as_ref().ok_or_else(|| anyhow!("OpenSearch URL not configured"))?;
+        let url = format!("{}/_search/scroll", os_url);
+        let mut rb = self.client.delete(&url);
+        if let (Some(u), Some(p)) = (&self.config.os_username, &self.config.os_password) {
+            rb = rb.basic_auth(u, Some(p));
+        }
+        self.send_with_retry(&url, rb.json(&serde_json::json!({ "scroll_id": scroll_id })), false).await?;
+        Ok(())
+    }
+
+    async fn indexer_post(&self, url: &str, body: &serde_json::Value) -> Result<serde_json::Value> {
+        let mut rb = self.client.post(url);
+        if let (Some(u), Some(p)) = (&self.config.os_username, &self.config.os_password) {
+            rb = rb.basic_auth(u, Some(p));
+        }
+        let response = self.send_with_retry(url, rb.json(body), true).await?;
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("OpenSearch query failed: {}", error_text));
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn search_alerts(&self, query: &serde_json::Value) -> Result<serde_json::Value> {
+        let os_url = self.config.os_url.as_ref().ok_or_else(|| anyhow!("OpenSearch URL not configured"))?;
+        let search_url = format!("{}/{}/_search", os_url, self.config.indices.alerts());
+        let mut rb = self.client.post(&search_url);
+        if let (Some(u), Some(p)) = (&self.config.os_username, &self.config.os_password) {
+            rb = rb.basic_auth(u, Some(p));
+        }
+
+        let response = self.send_with_retry(&search_url, rb.json(query), true).await?;
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow!("OpenSearch query failed: {}", error_text));
+        }
+        Ok(response.json().await?)
+    }
+}
+
+/// NDJSON for `_msearch`: a header line naming the index, then the query, per search.
+pub(crate) fn msearch_body(searches: &[(&str, serde_json::Value)]) -> String {
+    let mut body = String::new();
+    for (index, query) in searches {
+        body.push_str(&serde_json::json!({ "index": index }).to_string());
+        body.push('\n');
+        body.push_str(&query.to_string());
+        body.push('\n');
+    }
+    body
+}
+
+pub(crate) fn parse_msearch(body: &serde_json::Value) -> Vec<Result<serde_json::Value>> {
+    let responses = body.get("responses").and_then(|r| r.as_array());
+    responses.into_iter().flatten().map(|response| match response.get("error") {
+        Some(error) => {
+            let reason = error.get("reason").and_then(|r| r.as_str()).map(String::from).unwrap_or_else(|| error.to_string());
+            Err(anyhow!("OpenSearch query failed: {}", reason))
+        }
+        None => Ok(response.clone()),
+    }).collect()
+}
+
+/// The alert search behind Security Events and the inspector log tab; also shown in the
+/// filter popup's query preview.
+pub(crate) fn log_search_query(agent_id: Option<&str>, minutes: u32, offset: u32, limit: u32, filter: Option<&crate::app::LogFilter>, scale: &crate::app::severity::SeverityScale) -> serde_json::Value {
+    let mut must = vec![
+        serde_json::json!({
+            "range": {
+                "@timestamp": {
+                    "gte": format!("now-{}m", minutes),
+                    "lte": "now"
+                }
+            }
+        })
+    ];
+
+    if let Some(f) = filter {
+        // Severity filter
+        let severity_query = match f.mode {
+            crate::app::SeverityFilterMode::Min => serde_json::json!({ "range": { "rule.level": { "gte": f.val1 } } }),
+            crate::app::SeverityFilterMode::Max => serde_json::json!({ "range": { "rule.level": { "lte": f.val1 } } }),
+            crate::app::SeverityFilterMode::Exact => serde_json::json!({ "term": { "rule.level": f.val1 } }),
+            crate::app::SeverityFilterMode::Range => serde_json::json!({ "range": { "rule.level": { "gte": f.val1, "lte": f.val2 } } }),
+        };
+        must.push(severity_query);
         
-        let mut must = vec![
-            serde_json::json!({
-                "range": {
-                    "@timestamp": {
-                        "gte": format!("now-{}m", minutes),
-                        "lte": "now"
+        // Agent name filter (wildcard search)
+        if !f.agent_filter.is_empty() {
+            must.push(serde_json::json!({
+                "wildcard": {
+                    "agent.name": {
+                        "value": format!("*{}*", f.agent_filter.to_lowercase()),
+                        "case_insensitive": true
                     }
                 }
-            })
-        ];
-
-        if let Some(f) = filter {
-            // Severity filter
-            let severity_query = match f.mode {
-                crate::app::SeverityFilterMode::Min => serde_json::json!({ "range": { "rule.level": { "gte": f.val1 } } }),
-                crate::app::SeverityFilterMode::Max => serde_json::json!({ "range": { "rule.level": { "lte": f.val1 } } }),
-                crate::app::SeverityFilterMode::Exact => serde_json::json!({ "term": { "rule.level": f.val1 } }),
-                crate::app::SeverityFilterMode::Range => serde_json::json!({ "range": { "rule.level": { "gte": f.val1, "lte": f.val2 } } }),
-            };
-            must.push(severity_query);
-            
-            // Agent name filter (wildcard search)
-            if !f.agent_filter.is_empty() {
+            }));
+        }
+        
+        // Rule ID filter (supports comma-separated list and wildcards)
+        if !f.rule_id_filter.is_empty() {
+            if f.rule_id_filter.contains(',') {
+                // Multiple rule IDs
+                let rule_ids: Vec<&str> = f.rule_id_filter.split(',').map(|s| s.trim()).collect();
                 must.push(serde_json::json!({
-                    "wildcard": {
-                        "agent.name": {
-                            "value": format!("*{}*", f.agent_filter.to_lowercase()),
-                            "case_insensitive": true
-                        }
+                    "terms": {
+                        "rule.id": rule_ids
                     }
                 }));
-            }
-            
-            // Rule ID filter (supports comma-separated list and wildcards)
-            if !f.rule_id_filter.is_empty() {
-                if f.rule_id_filter.contains(',') {
-                    // Multiple rule IDs
-                    let rule_ids: Vec<&str> = f.rule_id_filter.split(',').map(|s| s.trim()).collect();
-                    must.push(serde_json::json!({
-                        "terms": {
-                            "rule.id": rule_ids
-                        }
-                    }));
-                } else if f.rule_id_filter.contains('*') {
-                    // Wildcard search
-                    must.push(serde_json::json!({
-                        "wildcard": {
-                            "rule.id": {
-                                "value": f.rule_id_filter.clone()
-                            }
-                        }
-                    }));
-                } else {
-                    // Exact match
-                    must.push(serde_json::json!({
-                        "term": {
-                            "rule.id": f.rule_id_filter.clone()
-                        }
-                    }));
-                }
-            }
-            
-            // Description filter (full-text search)
-            if !f.description_filter.is_empty() {
+            } else if f.rule_id_filter.contains('*') {
+                // Wildcard search
                 must.push(serde_json::json!({
-                    "match": {
-                        "rule.description": {
-                            "query": f.description_filter.clone(),
-                            "operator": "and"
+                    "wildcard": {
+                        "rule.id": {
+                            "value": f.rule_id_filter.clone()
                         }
                     }
                 }));
-            }
-            
-            // MITRE filter (ID or tactic)
-            if !f.mitre_filter.is_empty() {
-                let mitre_lower = f.mitre_filter.to_lowercase();
+            } else {
+                // Exact match
                 must.push(serde_json::json!({
-                    "bool": {
-                        "should": [
-                            { "wildcard": { "rule.mitre.id": { "value": format!("*{}*", mitre_lower), "case_insensitive": true } } },
-                            { "wildcard": { "rule.mitre.tactic": { "value": format!("*{}*", mitre_lower), "case_insensitive": true } } },
-                            { "wildcard": { "rule.mitre.technique": { "value": format!("*{}*", mitre_lower), "case_insensitive": true } } }
-                        ],
-                        "minimum_should_match": 1
+                    "term": {
+                        "rule.id": f.rule_id_filter.clone()
                     }
                 }));
             }
         }
-
-        if let Some(id) = agent_id {
-            must.push(serde_json::json!({ "term": { "agent.id": id } }));
+        
+        // Description filter (full-text search)
+        if !f.description_filter.is_empty() {
+            must.push(serde_json::json!({
+                "match": {
+                    "rule.description": {
+                        "query": f.description_filter.clone(),
+                        "operator": "and"
+                    }
+                }
+            }));
+        }
+        
+        // Severity chips combine with OR, and with the level filter above with AND
+        let ranges: Vec<serde_json::Value> = f.severities.iter().filter_map(|s| severity_level_range(s, scale)).collect();
+        if !ranges.is_empty() {
+            must.push(serde_json::json!({ "bool": { "should": ranges, "minimum_should_match": 1 } }));
         }
 
-        let query = serde_json::json!({
-            "from": offset,
-            "size": limit,
-            "sort": [{ "@timestamp": { "order": "desc" } }],
-            "query": {
+        // MITRE filter (ID or tactic)
+        if !f.mitre_filter.is_empty() {
+            let mitre_lower = f.mitre_filter.to_lowercase();
+            must.push(serde_json::json!({
                 "bool": {
-                    "must": must
+                    "should": [
+                        { "wildcard": { "rule.mitre.id": { "value": format!("*{}*", mitre_lower), "case_insensitive": true } } },
+                        { "wildcard": { "rule.mitre.tactic": { "value": format!("*{}*", mitre_lower), "case_insensitive": true } } },
+                        { "wildcard": { "rule.mitre.technique": { "value": format!("*{}*", mitre_lower), "case_insensitive": true } } }
+                    ],
+                    "minimum_should_match": 1
                 }
+            }));
+        }
+    }
+
+    if let Some(id) = agent_id {
+        must.push(serde_json::json!({ "term": { "agent.id": id } }));
+    }
+
+    let mut sort = vec![serde_json::json!({ "@timestamp": { "order": "desc" } })];
+    if let Some(f) = filter {
+        let order = if f.sort_order == crate::app::SortOrder::Asc { "asc" } else { "desc" };
+        let key = serde_json::json!({ f.sort.field(): { "order": order } });
+        // Newest first among equal keys
+        if f.sort == crate::app::LogSortColumn::Time {
+            sort[0] = key;
+        } else {
+            sort.insert(0, key);
+        }
+    }
+
+    serde_json::json!({
+        "from": offset,
+        "size": limit,
+        "sort": sort,
+        "query": {
+            "bool": {
+                "must": must
             }
-        });
+        }
+    })
+}
 
-        let mut rb = self.client.post(format!("{}/wazuh-alerts-*/_search", os_url));
-        if let (Some(u), Some(p)) = (&self.config.os_username, &self.config.os_password) {
-            rb = rb.basic_auth(u, Some(p));
+/// How long the indexer keeps a scroll context between pages of a full export.
+const SCROLL_KEEPALIVE: &str = "2m";
+
+/// The log search for a full export: same filter and order, `page_size` alerts per scroll page.
+pub(crate) fn export_query(agent_id: Option<&str>, minutes: u32, filter: Option<&crate::app::LogFilter>, page_size: u32, scale: &crate::app::severity::SeverityScale) -> serde_json::Value {
+    let mut query = log_search_query(agent_id, minutes, 0, page_size, filter, scale);
+    if let Some(q) = query.as_object_mut() {
+        // Scrolls cannot start at an offset
+        q.remove("from");
+    }
+    query
+}
+
+/// Rule level range of an alert severity band, matching the dashboard counts.
+fn severity_level_range(severity: &str, scale: &crate::app::severity::SeverityScale) -> Option<serde_json::Value> {
+    let range = match scale.bounds(crate::app::severity::Severity::from_name(severity)?) {
+        (0, Some(high)) => serde_json::json!({ "lte": high }),
+        (low, Some(high)) => serde_json::json!({ "gte": low, "lte": high }),
+        (low, None) => serde_json::json!({ "gte": low }),
+    };
+    Some(serde_json::json!({ "range": { "rule.level": range } }))
+}
+
+pub(crate) fn alert_context_query(agent_id: &str, timestamp: &str, minutes: u32, limit: u32) -> serde_json::Value {
+    serde_json::json!({
+        "size": limit,
+        "sort": [{ "@timestamp": { "order": "asc" } }],
+        "query": {
+            "bool": {
+                "must": [
+                    { "term": { "agent.id": agent_id } },
+                    {
+                        "range": {
+                            "@timestamp": {
+                                "gte": format!("{}||-{}m", timestamp, minutes),
+                                "lte": format!("{}||+{}m", timestamp, minutes)
+                            }
+                        }
+                    }
+                ]
+            }
         }
+    })
+}
 
-        let response = rb.json(&query).send().await?;
-        Ok(response.json().await?)
+pub(crate) fn vuln_summary_query() -> serde_json::Value {
+    serde_json::json!({
+        "size": 0,
+        "aggs": {
+            "severity": { "terms": { "field": "vulnerability.severity", "size": 10, "missing": "-" } }
+        }
+    })
+}
+
+/// Severities other than Critical/High/Medium/Low (including missing ones) count as untriaged.
+pub(crate) fn parse_vuln_summary(body: &serde_json::Value) -> crate::models::VulnerabilitySummary {
+    let mut summary = crate::models::VulnerabilitySummary { critical: 0, high: 0, medium: 0, low: 0, untriaged: 0 };
+    let buckets = body.pointer("/aggregations/severity/buckets").and_then(|b| b.as_array());
+    for sev in buckets.into_iter().flatten() {
+        let n = sev.get("doc_count").and_then(|c| c.as_u64()).unwrap_or(0) as u32;
+        match sev.get("key").and_then(|k| k.as_str()).unwrap_or("").to_lowercase().as_str() {
+            "critical" => summary.critical += n,
+            "high" => summary.high += n,
+            "medium" => summary.medium += n,
+            "low" => summary.low += n,
+            _ => summary.untriaged += n,
+        }
     }
+    summary
+}
+
+pub(crate) fn parse_manager_status(body: &serde_json::Value) -> Vec<(String, String)> {
+    let mut daemons: Vec<(String, String)> = body.pointer("/data/affected_items/0")
+        .and_then(|i| i.as_object())
+        .map(|item| item.iter().map(|(name, state)| (name.clone(), state.as_str().unwrap_or("unknown").to_string())).collect())
+        .unwrap_or_default();
+    daemons.sort();
+    daemons
+}
+
+pub(crate) fn parse_config_validation(body: &serde_json::Value) -> Vec<String> {
+    let items = body.pointer("/data/affected_items").and_then(|i| i.as_array());
+    let mut errors: Vec<String> = items.into_iter().flatten()
+        .filter(|item| item.get("status").and_then(|s| s.as_str()) != Some("OK"))
+        .map(|item| {
+            let node = item.get("name").and_then(|n| n.as_str()).unwrap_or("manager");
+            let details = item.get("details").and_then(|d| d.as_str()).unwrap_or("configuration check failed");
+            format!("{}: {}", node, details)
+        })
+        .collect();
+    let failed = body.pointer("/data/failed_items").and_then(|i| i.as_array());
+    for item in failed.into_iter().flatten() {
+        let message = item.pointer("/error/message").and_then(|m| m.as_str()).unwrap_or("configuration check failed");
+        errors.push(message.to_string());
+    }
+    errors
+}
+
+pub(crate) fn parse_logtest(body: &serde_json::Value) -> crate::models::LogtestResult {
+    let text = |path: &str| body.pointer(path).and_then(|v| match v {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Null => None,
+        other => Some(other.to_string()),
+    });
+    let fields = body.pointer("/data/output/data")
+        .and_then(|d| d.as_object())
+        .map(|obj| obj.iter()
+            .map(|(k, v)| (k.clone(), v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string())))
+            .collect())
+        .unwrap_or_default();
+    let messages = body.pointer("/data/messages")
+        .and_then(|m| m.as_array())
+        .map(|arr| arr.iter().filter_map(|m| m.as_str().map(|s| s.to_string())).collect())
+        .unwrap_or_default();
+
+    crate::models::LogtestResult {
+        token: text("/data/token"),
+        alert: body.pointer("/data/alert").and_then(|a| a.as_bool()).unwrap_or(false),
+        decoder: text("/data/output/decoder/name"),
+        parent_decoder: text("/data/output/decoder/parent"),
+        program_name: text("/data/output/predecoder/program_name"),
+        rule_id: text("/data/output/rule/id"),
+        rule_level: body.pointer("/data/output/rule/level").and_then(|l| l.as_u64()),
+        rule_description: text("/data/output/rule/description"),
+        fields,
+        messages,
+    }
+}
+
+pub(crate) fn agent_vuln_counts_query(max_agents: u32) -> serde_json::Value {
+    serde_json::json!({
+        "size": 0,
+        "aggs": {
+            "agents": {
+                "terms": { "field": "agent.id", "size": max_agents },
+                "aggs": {
+                    "severity": { "terms": { "field": "vulnerability.severity", "size": 10 } }
+                }
+            }
+        }
+    })
+}
+
+pub(crate) fn parse_agent_vuln_counts(body: &serde_json::Value) -> std::collections::HashMap<String, crate::models::AgentVulnCounts> {
+    let mut out = std::collections::HashMap::new();
+    let buckets = body.pointer("/aggregations/agents/buckets").and_then(|b| b.as_array());
+    for agent in buckets.into_iter().flatten() {
+        let Some(id) = agent.get("key").and_then(|k| k.as_str()) else { continue };
+        let mut counts = crate::models::AgentVulnCounts::default();
+        let severities = agent.pointer("/severity/buckets").and_then(|b| b.as_array());
+        for sev in severities.into_iter().flatten() {
+            let n = sev.get("doc_count").and_then(|c| c.as_u64()).unwrap_or(0) as u32;
+            match sev.get("key").and_then(|k| k.as_str()).unwrap_or("").to_lowercase().as_str() {
+                "critical" => counts.critical += n,
+                "high" => counts.high += n,
+                "medium" => counts.medium += n,
+                "low" => counts.low += n,
+                _ => {}
+            }
+        }
+        out.insert(id.to_string(), counts);
+    }
+    out
+}
+
+/// Alerts at `min_level` or above per agent over the last `minutes`.
+pub(crate) fn agent_high_alerts_query(minutes: u32, min_level: u64, max_agents: u32) -> serde_json::Value {
+    serde_json::json!({
+        "size": 0,
+        "query": {
+            "bool": {
+                "filter": [
+                    { "range": { "@timestamp": { "gte": format!("now-{}m", minutes), "lte": "now" } } },
+                    { "range": { "rule.level": { "gte": min_level } } }
+                ]
+            }
+        },
+        "aggs": {
+            "agents": { "terms": { "field": "agent.id", "size": max_agents } }
+        }
+    })
+}
+
+pub(crate) fn parse_agent_alert_counts(body: &serde_json::Value) -> AgentAlertCounts {
+    let buckets = body.pointer("/aggregations/agents/buckets").and_then(|b| b.as_array());
+    buckets.into_iter().flatten().filter_map(|bucket| {
+        let id = bucket.get("key")?.as_str()?;
+        Some((id.to_string(), bucket.get("doc_count").and_then(|c| c.as_u64()).unwrap_or(0) as usize))
+    }).collect()
+}
+
+/// Alert counts per severity band over the window, taken from every matching alert rather
+/// than from a page of hits.
+pub(crate) fn alert_severity_query(minutes: u32, scale: &crate::app::severity::SeverityScale) -> serde_json::Value {
+    serde_json::json!({
+        "size": 0,
+        "query": {
+            "range": { "@timestamp": { "gte": format!("now-{}m", minutes), "lte": "now" } }
+        },
+        "aggs": { "severity": severity_bands_agg(scale) }
+    })
+}
+
+/// Severity buckets use the same level bands as the dashboard cards.
+fn severity_bands_agg(scale: &crate::app::severity::SeverityScale) -> serde_json::Value {
+    serde_json::json!({
+        "range": {
+            "field": "rule.level",
+            "ranges": [
+                { "key": "low", "to": scale.medium },
+                { "key": "medium", "from": scale.medium, "to": scale.high },
+                { "key": "high", "from": scale.high, "to": scale.critical },
+                { "key": "critical", "from": scale.critical }
+            ]
+        }
+    })
+}
+
+pub(crate) fn group_alerts_query(agent_ids: &[String], minutes: u32, scale: &crate::app::severity::SeverityScale) -> serde_json::Value {
+    serde_json::json!({
+        "size": 0,
+        "query": {
+            "bool": {
+                "filter": [
+                    { "terms": { "agent.id": agent_ids } },
+                    { "range": { "@timestamp": { "gte": format!("now-{}m", minutes), "lte": "now" } } }
+                ]
+            }
+        },
+        "aggs": {
+            "severity": severity_bands_agg(scale),
+            "rules": {
+                "terms": { "field": "rule.id", "size": 5 },
+                "aggs": { "description": { "terms": { "field": "rule.description", "size": 1 } } }
+            }
+        }
+    })
+}
+
+pub(crate) fn alert_trend_query(days: u32, time_zone: &str) -> serde_json::Value {
+    let start = format!("now-{}d/d", days.saturating_sub(1));
+    serde_json::json!({
+        "size": 0,
+        "query": {
+            "range": { "@timestamp": { "gte": start, "lte": "now", "time_zone": time_zone } }
+        },
+        "aggs": {
+            "days": {
+                "date_histogram": {
+                    "field": "@timestamp",
+                    "calendar_interval": "1d",
+                    "time_zone": time_zone,
+                    "format": "yyyy-MM-dd",
+                    // Quiet days still get a (zero) column
+                    "min_doc_count": 0,
+                    "extended_bounds": { "min": start, "max": "now/d" }
+                },
+                "aggs": { "levels": { "terms": { "field": "rule.level", "size": 20 } } }
+            }
+        }
+    })
+}
+
+/// Oldest day first, labelled `yyyy-MM-dd`.
+pub(crate) fn parse_alert_trend(body: &serde_json::Value, scale: &crate::app::severity::SeverityScale) -> Vec<(String, crate::app::ThreatStats)> {
+    let buckets = body.pointer("/aggregations/days/buckets").and_then(|b| b.as_array());
+    buckets.into_iter().flatten().map(|day| {
+        let label = day.get("key_as_string").and_then(|k| k.as_str()).unwrap_or("").to_string();
+        let mut stats = crate::app::ThreatStats::default();
+        let levels = day.pointer("/levels/buckets").and_then(|b| b.as_array());
+        for level in levels.into_iter().flatten() {
+            let key = level.get("key").and_then(|k| k.as_u64()).unwrap_or(0);
+            let n = level.get("doc_count").and_then(|c| c.as_u64()).unwrap_or(0) as u32;
+            stats.add_level(scale, key, n);
+        }
+        (label, stats)
+    }).collect()
+}
+
+/// Reads the `severity` buckets of `alert_severity_query` and `group_alerts_query`.
+pub(crate) fn parse_severity_counts(body: &serde_json::Value) -> crate::app::ThreatStats {
+    let mut stats = crate::app::ThreatStats::default();
+    let buckets = body.pointer("/aggregations/severity/buckets").and_then(|b| b.as_array());
+    for bucket in buckets.into_iter().flatten() {
+        let n = bucket.get("doc_count").and_then(|c| c.as_u64()).unwrap_or(0) as u32;
+        match bucket.get("key").and_then(|k| k.as_str()).unwrap_or("") {
+            "critical" => stats.critical += n,
+            "high" => stats.high += n,
+            "medium" => stats.medium += n,
+            _ => stats.low += n,
+        }
+    }
+    stats
+}
+
+pub(crate) fn parse_group_alerts(body: &serde_json::Value) -> (crate::app::ThreatStats, Vec<crate::app::report::TopRule>) {
+    let stats = parse_severity_counts(body);
+    let rules = body.pointer("/aggregations/rules/buckets").and_then(|b| b.as_array());
+    let top_rules = rules.into_iter().flatten().filter_map(|bucket| {
+        let id = match bucket.get("key")? {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        let description = bucket.pointer("/description/buckets/0/key").and_then(|d| d.as_str()).unwrap_or("").to_string();
+        let count = bucket.get("doc_count").and_then(|c| c.as_u64()).unwrap_or(0);
+        Some(crate::app::report::TopRule { id, description, count })
+    }).collect();
+
+    (stats, top_rules)
+}
+
+pub(crate) fn group_vulns_query(agent_ids: &[String]) -> serde_json::Value {
+    let mut query = vuln_summary_query();
+    query["query"] = serde_json::json!({ "terms": { "agent.id": agent_ids } });
+    query
+}
+
+pub(crate) fn remote_command_query(id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "size": 1,
+        "query": {
+            "bool": {
+                "must": [
+                    { "term": { "data.wazuh_tui_exec.id": id } },
+                    { "range": { "@timestamp": { "gte": "now-1h" } } }
+                ]
+            }
+        }
+    })
+}
+
+pub(crate) fn hunt_query(hunt: crate::app::hunts::Hunt, minutes: u32) -> serde_json::Value {
+    let mut filter = hunt.filters();
+    filter.push(serde_json::json!({ "range": { "@timestamp": { "gte": format!("now-{}m", minutes), "lte": "now" } } }));
+    serde_json::json!({
+        "size": 0,
+        "query": { "bool": { "filter": filter } },
+        "aggs": {
+            "buckets": {
+                "terms": { "field": hunt.key_field().0, "size": crate::app::hunts::HUNT_ROWS, "min_doc_count": hunt.min_count() },
+                "aggs": {
+                    "agents": { "terms": { "field": "agent.name", "size": 5 } },
+                    "details": { "terms": { "field": hunt.detail_field().0, "size": 3 } },
+                    "last_seen": { "max": { "field": "@timestamp" } }
+                }
+            }
+        }
+    })
+}
+
+pub(crate) fn parse_hunt(body: &serde_json::Value) -> Vec<crate::app::hunts::HuntRow> {
+    let keys = |bucket: &serde_json::Value, name: &str| -> Vec<String> {
+        let buckets = bucket.pointer(&format!("/{}/buckets", name)).and_then(|b| b.as_array());
+        buckets.into_iter().flatten().filter_map(|b| match b.get("key")? {
+            serde_json::Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        }).collect()
+    };
+    let buckets = body.pointer("/aggregations/buckets/buckets").and_then(|b| b.as_array());
+    buckets.into_iter().flatten().filter_map(|bucket| {
+        let key = match bucket.get("key")? {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        Some(crate::app::hunts::HuntRow {
+            key,
+            count: bucket.get("doc_count").and_then(|c| c.as_u64()).unwrap_or(0),
+            agents: keys(bucket, "agents"),
+            details: keys(bucket, "details"),
+            last_seen: bucket.pointer("/last_seen/value_as_string").and_then(|t| t.as_str()).map(String::from),
+        })
+    }).collect()
+}
+
+/// Most distinct connections kept for one agent; a single composite page, so a host with more
+/// is cut off rather than paged through.
+pub const CONNECTIONS_LIMIT: u32 = 500;
+
+/// Alerts of one agent per hour over the last `ALERT_HOURS` hours; empty hours still get a
+/// bucket so the sparkline keeps its width.
+pub(crate) fn agent_alert_hours_query(agent_id: &str) -> serde_json::Value {
+    // Whole hours, the current one last
+    let start = format!("now-{}h/h", crate::models::ALERT_HOURS - 1);
+    serde_json::json!({
+        "size": 0,
+        "query": {
+            "bool": {
+                "filter": [
+                    { "term": { "agent.id": agent_id } },
+                    { "range": { "@timestamp": { "gte": start, "lte": "now" } } }
+                ]
+            }
+        },
+        "aggs": {
+            "hours": {
+                "date_histogram": {
+                    "field": "@timestamp",
+                    "fixed_interval": "1h",
+                    "min_doc_count": 0,
+                    "extended_bounds": { "min": start, "max": "now/h" }
+                }
+            }
+        }
+    })
+}
+
+pub(crate) fn parse_agent_alert_hours(body: &serde_json::Value) -> crate::models::HourlyAlerts {
+    let buckets = body.pointer("/aggregations/hours/buckets").and_then(|b| b.as_array());
+    let hours = buckets.into_iter().flatten().filter_map(|bucket| {
+        let start = chrono::DateTime::from_timestamp_millis(bucket.get("key")?.as_i64()?)?;
+        Some((start, bucket.get("doc_count").and_then(|c| c.as_u64()).unwrap_or(0)))
+    }).collect();
+    crate::models::HourlyAlerts { hours }
+}
+
+/// Alerts carrying a source or destination address, bucketed by (srcip, dstip, dstport).
+/// `missing_bucket` keeps alerts that only have some of the three fields.
+pub(crate) fn connections_query(agent_id: &str, minutes: u32) -> serde_json::Value {
+    let source = |name: &str, field: &str| serde_json::json!({ name: { "terms": { "field": field, "missing_bucket": true } } });
+    serde_json::json!({
+        "size": 0,
+        "query": {
+            "bool": {
+                "filter": [
+                    { "term": { "agent.id": agent_id } },
+                    { "range": { "@timestamp": { "gte": format!("now-{}m", minutes), "lte": "now" } } }
+                ],
+                "should": [
+                    { "exists": { "field": "data.srcip" } },
+                    { "exists": { "field": "data.dstip" } }
+                ],
+                "minimum_should_match": 1
+            }
+        },
+        "aggs": {
+            "connections": {
+                "composite": {
+                    "size": CONNECTIONS_LIMIT,
+                    "sources": [
+                        source("srcip", "data.srcip"),
+                        source("dstip", "data.dstip"),
+                        source("dstport", "data.dstport")
+                    ]
+                },
+                "aggs": { "last_seen": { "max": { "field": "@timestamp" } } }
+            }
+        }
+    })
+}
+
+/// Busiest first, ties broken by the most recent.
+pub(crate) fn parse_connections(body: &serde_json::Value) -> Vec<crate::models::NetworkConnection> {
+    let key = |bucket: &serde_json::Value, name: &str| match bucket.pointer(&format!("/key/{}", name)) {
+        Some(serde_json::Value::String(s)) if !s.is_empty() => Some(s.clone()),
+        Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    };
+    let buckets = body.pointer("/aggregations/connections/buckets").and_then(|b| b.as_array());
+    let mut connections: Vec<_> = buckets.into_iter().flatten().map(|bucket| crate::models::NetworkConnection {
+        srcip: key(bucket, "srcip"),
+        dstip: key(bucket, "dstip"),
+        dstport: key(bucket, "dstport"),
+        count: bucket.get("doc_count").and_then(|c| c.as_u64()).unwrap_or(0),
+        last_seen: bucket.pointer("/last_seen/value_as_string").and_then(|t| t.as_str()).map(String::from),
+    }).collect();
+    connections.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| b.last_seen.cmp(&a.last_seen)));
+    connections
+}
+
+pub(crate) fn srcip_pivot_query(srcip: &str, minutes: u32, limit: u32) -> serde_json::Value {
+    serde_json::json!({
+        "size": limit,
+        "sort": [{ "@timestamp": { "order": "desc" } }],
+        "query": {
+            "bool": {
+                "must": [
+                    { "term": { "data.srcip": srcip } },
+                    { "range": { "@timestamp": { "gte": format!("now-{}m", minutes), "lte": "now" } } }
+                ]
+            }
+        }
+    })
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 use crate::api::WazuhApi;
 use crate::models::{Config, WazuhAgentsResponse};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_auth_failure() {
@@ -10,6 +12,7 @@ async fn test_auth_failure() {
         os_url: None,
         os_username: None,
         os_password: None,
+        ..Default::default()
     };
     let api = WazuhApi::new(config);
     let result = api.authenticate().await;
@@ -86,6 +89,36 @@ async fn test_deserialization_processes() {
     assert_eq!(res.data.affected_items[0].pid, "1");
 }
 
+#[tokio::test]
+async fn test_deserialization_processes_resource_fields() {
+    let json = r#"{
+        "data": {
+            "affected_items": [
+                {
+                    "name": "nginx",
+                    "cmd": "/usr/sbin/nginx",
+                    "pid": "812",
+                    "ppid": 1,
+                    "euser": "www-data",
+                    "vm_size": 56320,
+                    "utime": 120,
+                    "stime": 30,
+                    "state": "S",
+                    "agent_id": "001"
+                }
+            ],
+            "total_affected_items": 1
+        }
+    }"#;
+    let res: crate::models::WazuhProcessesResponse = serde_json::from_str(json).unwrap();
+    let p = &res.data.affected_items[0];
+    assert_eq!(p.ppid, Some(1));
+    assert_eq!(p.euser.as_deref(), Some("www-data"));
+    assert_eq!(p.cpu_time(), Some(150));
+    assert_eq!(p.kill_command(Some("ubuntu")), "kill -9 812");
+    assert_eq!(p.kill_command(Some("windows")), "taskkill /PID 812 /F");
+}
+
 #[tokio::test]
 async fn test_deserialization_programs() {
     let json = r#"{
@@ -216,6 +249,219 @@ fn test_log_filter_query_generation() {
     assert_eq!(query["range"]["rule.level"]["lte"], 12);
 }
 
+#[test]
+fn test_log_search_query() {
+    use crate::app::{LogFilter, SeverityFilterMode};
+
+    let filter = LogFilter { mode: SeverityFilterMode::Range, val1: 7, val2: 11, rule_id_filter: "5710,5712".to_string(), ..Default::default() };
+    let query = crate::api::log_search_query(Some("004"), 60, 50, 50, Some(&filter), &crate::app::severity::SeverityScale::default());
+    let must = query["query"]["bool"]["must"].as_array().unwrap();
+    assert_eq!(must[0]["range"]["@timestamp"]["gte"], "now-60m");
+    assert_eq!(must[1]["range"]["rule.level"]["gte"], 7);
+    assert_eq!(must[1]["range"]["rule.level"]["lte"], 11);
+    assert_eq!(must[2]["terms"]["rule.id"], serde_json::json!(["5710", "5712"]));
+    assert_eq!(must[3]["term"]["agent.id"], "004");
+    assert_eq!(query["from"], 50);
+}
+
+#[test]
+fn test_filter_query_preview_uses_typed_levels() {
+    let mut app = crate::app::App::new();
+    app.log_filter.mode = crate::app::SeverityFilterMode::Min;
+    app.filter_input_1 = "12".to_string();
+
+    let preview: serde_json::Value = serde_json::from_str(&app.filter_query_preview()).unwrap();
+    assert_eq!(preview["query"]["bool"]["must"][1]["range"]["rule.level"]["gte"], 12);
+    // Nothing is applied until Enter
+    assert_eq!(app.log_filter.val1, 0);
+}
+
+#[test]
+fn test_alert_context_query() {
+    let query = crate::api::alert_context_query("003", "2024-05-01T10:00:00.000Z", 5, 200);
+    let must = query["query"]["bool"]["must"].as_array().unwrap();
+    assert_eq!(must[0]["term"]["agent.id"], "003");
+    assert_eq!(must[1]["range"]["@timestamp"]["gte"], "2024-05-01T10:00:00.000Z||-5m");
+    assert_eq!(must[1]["range"]["@timestamp"]["lte"], "2024-05-01T10:00:00.000Z||+5m");
+    assert_eq!(query["sort"][0]["@timestamp"]["order"], "asc");
+}
+
+#[test]
+fn test_srcip_pivot_query() {
+    let query = crate::api::srcip_pivot_query("10.0.0.5", 60, 500);
+    let must = query["query"]["bool"]["must"].as_array().unwrap();
+    assert_eq!(must[0]["term"]["data.srcip"], "10.0.0.5");
+    assert_eq!(must[1]["range"]["@timestamp"]["gte"], "now-60m");
+    assert_eq!(query["size"], 500);
+}
+
+#[test]
+fn test_remote_command_query() {
+    let query = crate::api::remote_command_query("003-1714557600000");
+    let must = query["query"]["bool"]["must"].as_array().unwrap();
+    assert_eq!(must[0]["term"]["data.wazuh_tui_exec.id"], "003-1714557600000");
+    assert_eq!(query["size"], 1);
+}
+
+#[test]
+fn test_parse_vuln_summary() {
+    let query = crate::api::vuln_summary_query();
+    assert_eq!(query["aggs"]["severity"]["terms"]["field"], "vulnerability.severity");
+
+    let body = serde_json::json!({
+        "aggregations": { "severity": { "buckets": [
+            { "key": "Medium", "doc_count": 120 },
+            { "key": "High", "doc_count": 40 },
+            { "key": "Critical", "doc_count": 3 },
+            { "key": "Low", "doc_count": 9 },
+            { "key": "-", "doc_count": 5 }
+        ] } }
+    });
+    let summary = crate::api::parse_vuln_summary(&body);
+    assert_eq!((summary.critical, summary.high, summary.medium, summary.low, summary.untriaged), (3, 40, 120, 9, 5));
+}
+
+#[test]
+fn test_group_posture_queries() {
+    let ids = vec!["001".to_string(), "004".to_string()];
+    let query = crate::api::group_alerts_query(&ids, 1440, &crate::app::severity::SeverityScale::default());
+    assert_eq!(query["query"]["bool"]["filter"][0]["terms"]["agent.id"], serde_json::json!(["001", "004"]));
+    assert_eq!(query["aggs"]["severity"]["range"]["ranges"][3]["from"], 15);
+    let vulns = crate::api::group_vulns_query(&ids);
+    assert_eq!(vulns["query"]["terms"]["agent.id"][1], "004");
+    assert_eq!(vulns["aggs"]["severity"]["terms"]["field"], "vulnerability.severity");
+
+    let body = serde_json::json!({
+        "aggregations": {
+            "severity": { "buckets": [
+                { "key": "low", "doc_count": 50 },
+                { "key": "medium", "doc_count": 20 },
+                { "key": "high", "doc_count": 4 },
+                { "key": "critical", "doc_count": 1 }
+            ] },
+            "rules": { "buckets": [
+                { "key": "5710", "doc_count": 30, "description": { "buckets": [{ "key": "sshd: Attempt to login using a non-existent user", "doc_count": 30 }] } },
+                { "key": "31101", "doc_count": 12, "description": { "buckets": [] } }
+            ] }
+        }
+    });
+    let (stats, rules) = crate::api::parse_group_alerts(&body);
+    assert_eq!((stats.critical, stats.high, stats.medium, stats.low), (1, 4, 20, 50));
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].id, "5710");
+    assert_eq!(rules[0].count, 30);
+    assert!(rules[1].description.is_empty());
+}
+
+#[test]
+fn test_alert_severity_counts_cover_the_whole_window() {
+    let query = crate::api::alert_severity_query(1440, &crate::app::severity::SeverityScale::default());
+    assert_eq!(query["size"], 0);
+    assert_eq!(query["query"]["range"]["@timestamp"]["gte"], "now-1440m");
+    assert_eq!(query["aggs"]["severity"]["range"]["ranges"][3]["from"], 15);
+
+    let body = serde_json::json!({
+        "hits": { "total": { "value": 10000, "relation": "gte" }, "hits": [] },
+        "aggregations": { "severity": { "buckets": [
+            { "key": "low", "doc_count": 52000 },
+            { "key": "medium", "doc_count": 3100 },
+            { "key": "high", "doc_count": 1800 },
+            { "key": "critical", "doc_count": 7 }
+        ] } }
+    });
+    let stats = crate::api::parse_severity_counts(&body);
+    assert_eq!((stats.critical, stats.high, stats.medium, stats.low), (7, 1800, 3100, 52000));
+}
+
+#[test]
+fn test_parse_config_validation() {
+    let ok = serde_json::json!({ "data": { "affected_items": [{ "name": "wazuh-manager", "status": "OK" }], "failed_items": [] } });
+    assert!(crate::api::parse_config_validation(&ok).is_empty());
+
+    let ko = serde_json::json!({ "data": {
+        "affected_items": [{ "name": "master-node", "status": "KO", "details": "Error loading rules: etc/rules/local_rules.xml" }],
+        "failed_items": [{ "error": { "code": 1908, "message": "Error validating configuration" } }]
+    } });
+    assert_eq!(crate::api::parse_config_validation(&ko), vec![
+        "master-node: Error loading rules: etc/rules/local_rules.xml".to_string(),
+        "Error validating configuration".to_string(),
+    ]);
+}
+
+#[test]
+fn test_parse_logtest() {
+    let body = serde_json::json!({
+        "error": 0,
+        "data": {
+            "token": "1c4b9a2f",
+            "messages": ["WARNING: (7309): 'stdin' is not a valid location"],
+            "alert": true,
+            "output": {
+                "predecoder": { "program_name": "sshd", "hostname": "web01" },
+                "decoder": { "parent": "sshd", "name": "sshd" },
+                "data": { "srcip": "10.0.0.5", "srcport": 52814 },
+                "rule": { "id": "5710", "level": 5, "description": "sshd: Attempt to login using a non-existent user" }
+            }
+        }
+    });
+    let res = crate::api::parse_logtest(&body);
+    assert_eq!(res.token.as_deref(), Some("1c4b9a2f"));
+    assert!(res.alert);
+    assert_eq!(res.decoder.as_deref(), Some("sshd"));
+    assert_eq!(res.program_name.as_deref(), Some("sshd"));
+    assert_eq!(res.rule_id.as_deref(), Some("5710"));
+    assert_eq!(res.rule_level, Some(5));
+    assert_eq!(res.fields, vec![("srcip".to_string(), "10.0.0.5".to_string()), ("srcport".to_string(), "52814".to_string())]);
+    assert_eq!(res.messages.len(), 1);
+
+    // No decoder matched: only the predecoder ran
+    let body = serde_json::json!({ "data": { "token": "x", "alert": false, "output": { "decoder": {} } } });
+    let res = crate::api::parse_logtest(&body);
+    assert!(!res.alert);
+    assert!(res.decoder.is_none() && res.rule_id.is_none() && res.fields.is_empty());
+}
+
+#[test]
+fn test_parse_agent_vuln_counts() {
+    let query = crate::api::agent_vuln_counts_query(5000);
+    assert_eq!(query["size"], 0);
+    assert_eq!(query["aggs"]["agents"]["terms"]["size"], 5000);
+
+    let body = serde_json::json!({
+        "aggregations": { "agents": { "buckets": [
+            { "key": "001", "doc_count": 7, "severity": { "buckets": [
+                { "key": "High", "doc_count": 4 },
+                { "key": "Critical", "doc_count": 2 },
+                { "key": "Medium", "doc_count": 1 }
+            ] } },
+            { "key": "002", "doc_count": 1, "severity": { "buckets": [
+                { "key": "Low", "doc_count": 1 }
+            ] } }
+        ] } }
+    });
+    let counts = crate::api::parse_agent_vuln_counts(&body);
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts["001"], crate::models::AgentVulnCounts { critical: 2, high: 4, medium: 1, low: 0 });
+    assert_eq!(counts["002"].count("low"), 1);
+    assert_eq!(counts["002"].count("critical"), 0);
+}
+
+#[test]
+fn test_agent_high_alerts_query_and_parse() {
+    let query = crate::api::agent_high_alerts_query(60, 12, 5000);
+    assert_eq!(query["query"]["bool"]["filter"][1]["range"]["rule.level"]["gte"], 12);
+    assert_eq!(query["aggs"]["agents"]["terms"]["size"], 5000);
+
+    let counts = crate::api::parse_agent_alert_counts(&serde_json::json!({
+        "aggregations": { "agents": { "buckets": [
+            { "key": "001", "doc_count": 3 },
+            { "key": "004", "doc_count": 1 }
+        ] } }
+    }));
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts["001"], 3);
+}
+
 // OpenSearch vulnerability response deserialization test
 #[tokio::test]
 async fn test_deserialization_os_vulnerabilities() {
@@ -258,6 +504,626 @@ async fn test_deserialization_os_vulnerabilities() {
     assert_eq!(res.hits.hits[0].source.package.as_ref().unwrap().name, Some("pip".to_string()));
 }
 
+#[test]
+fn test_backoff_delay_grows_and_caps() {
+    use crate::api::backoff_delay;
+    use std::time::Duration;
+
+    assert_eq!(backoff_delay(250, 1), Duration::from_millis(250));
+    assert_eq!(backoff_delay(250, 2), Duration::from_millis(500));
+    assert_eq!(backoff_delay(250, 3), Duration::from_millis(1000));
+    assert_eq!(backoff_delay(250, 20), Duration::from_secs(10));
+}
+
+#[test]
+fn test_endpoint_timeout_override() {
+    let mut config = Config {
+        url: "https://localhost:55000".to_string(),
+        ..Default::default()
+    };
+    config.network.endpoint_timeouts.insert("/syscollector".to_string(), 45);
+    let api = WazuhApi::new(config);
+
+    assert_eq!(api.timeout_for("https://localhost:55000/syscollector/001/packages").as_secs(), 45);
+    assert_eq!(api.timeout_for("https://localhost:55000/agents?limit=10").as_secs(), 10);
+}
+
+#[test]
+fn test_body_preview_masks_password_and_truncates() {
+    use crate::api::{body_preview, REQUEST_BODY_PREVIEW};
+
+    let preview = body_preview(br#"{"username":"analyst","password":"S3cret!"}"#);
+    assert!(preview.contains("analyst"));
+    assert!(!preview.contains("S3cret!"));
+
+    let long = "x".repeat(REQUEST_BODY_PREVIEW + 10);
+    let preview = body_preview(long.as_bytes());
+    assert!(preview.ends_with(&format!("({} bytes total)", long.len())));
+}
+
+#[tokio::test]
+async fn test_failed_request_is_recorded() {
+    let config = Config {
+        url: "https://127.0.0.1:1".to_string(),
+        network: crate::models::NetworkConfig { max_retries: Some(0), ..Default::default() },
+        ..Default::default()
+    };
+    let api = WazuhApi::new(config);
+    assert!(api.authenticate().await.is_err());
+
+    let log = api.request_log();
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].method, "POST");
+    assert_eq!(log[0].url, "https://127.0.0.1:1/security/user/authenticate");
+    assert!(log[0].status.is_none());
+    assert!(log[0].error.is_some());
+}
+
+#[tokio::test]
+async fn test_deserialization_rootcheck() {
+    let json = r#"{
+        "data": {
+            "affected_items": [
+                { "log": "Trojaned version of file '/bin/ls' detected. Signature used: 'bash|^/bin/sh' (Generic).", "status": "outstanding", "date_last": "2024-05-01T10:00:00Z" },
+                { "log": "File '/tmp/x' is owned by root and has written permissions to anyone.", "status": "solved", "cis": "1.1.1" },
+                { "log": "Process '1234' hidden from /proc." }
+            ],
+            "total_affected_items": 3
+        }
+    }"#;
+    let res: crate::models::WazuhRootcheckResponse = serde_json::from_str(json).unwrap();
+    let kinds: Vec<_> = res.data.affected_items.iter().map(|r| r.category()).collect();
+    assert_eq!(kinds, vec!["Trojan", "File", "Process"]);
+    assert!(res.data.affected_items[0].is_outstanding());
+    assert!(!res.data.affected_items[1].is_outstanding());
+    assert!(res.data.affected_items[2].is_outstanding());
+
+    let scan: crate::models::WazuhLastScanResponse = serde_json::from_str(
+        r#"{ "data": { "affected_items": [{ "start": "2024-05-01T09:58:00Z", "end": null }] } }"#
+    ).unwrap();
+    assert!(scan.data.affected_items[0].end.is_none());
+}
+
+#[test]
+fn test_permissions_from_policies() {
+    let policies = serde_json::json!({
+        "agent:read": { "agent:id:*": "allow" },
+        "agent:restart": { "agent:id:*": "allow" },
+        "agent:upgrade": { "agent:id:*": "deny" },
+        "agent:modify_group": { "agent:id:001": "deny", "agent:id:002": "allow" }
+    });
+    let perms = crate::app::Permissions::from_policies("readonly", vec!["2".to_string()], &policies);
+
+    assert_eq!(perms.username, "readonly");
+    assert!(perms.can("agent:restart"));
+    assert!(perms.can("agent:modify_group"));
+    assert!(!perms.can("agent:upgrade"));
+    assert!(!perms.can("agent:reconfigure"));
+}
+
+#[test]
+fn test_permissions_black_mode() {
+    let policies = serde_json::json!({
+        "agent:upgrade": { "agent:id:*": "deny" },
+        "agent:modify_group": { "agent:id:001": "deny", "agent:id:002": "allow" },
+        "rbac_mode": "black"
+    });
+    let perms = crate::app::Permissions::from_policies("operator", vec![], &policies);
+
+    // Unlisted actions are allowed; only a plain deny takes an action away
+    assert!(perms.black_mode);
+    assert!(perms.can("agent:restart"));
+    assert!(perms.can("agent:modify_group"));
+    assert!(!perms.can("agent:upgrade"));
+
+    let white = crate::app::Permissions::from_policies("operator", vec![], &serde_json::json!({ "rbac_mode": "white" }));
+    assert!(!white.can("agent:restart"));
+}
+
+#[tokio::test]
+async fn test_deserialization_security_users() {
+    let json = r#"{
+        "data": {
+            "affected_items": [
+                { "id": 1, "username": "wazuh", "allow_run_as": true, "roles": [1] },
+                { "id": 3, "username": "analyst", "roles": [] }
+            ],
+            "total_affected_items": 2
+        }
+    }"#;
+    let res: crate::models::WazuhUsersResponse = serde_json::from_str(json).unwrap();
+    assert_eq!(res.data.affected_items[0].username, "wazuh");
+    assert!(res.data.affected_items[0].allow_run_as);
+    assert!(!res.data.affected_items[1].allow_run_as);
+}
+
+#[tokio::test]
+async fn test_deserialization_outdated_agents() {
+    let json = r#"{
+        "data": {
+            "affected_items": [
+                { "id": "003", "name": "legacy-db", "version": "Wazuh v4.3.10" }
+            ],
+            "total_affected_items": 1
+        }
+    }"#;
+    let res: crate::models::WazuhOutdatedAgentsResponse = serde_json::from_str(json).unwrap();
+    assert_eq!(res.data.affected_items[0].id, "003");
+    assert_eq!(res.data.affected_items[0].version.as_deref(), Some("Wazuh v4.3.10"));
+}
+
+#[test]
+fn test_alert_trend_query_and_parse() {
+    let query = crate::api::alert_trend_query(7, "+02:00");
+    assert_eq!(query["query"]["range"]["@timestamp"]["gte"], "now-6d/d");
+    let histogram = &query["aggs"]["days"]["date_histogram"];
+    assert_eq!(histogram["calendar_interval"], "1d");
+    assert_eq!(histogram["time_zone"], "+02:00");
+    assert_eq!(histogram["min_doc_count"], 0);
+    assert_eq!(query["aggs"]["days"]["aggs"]["levels"]["terms"]["field"], "rule.level");
+
+    let body = serde_json::json!({
+        "aggregations": { "days": { "buckets": [
+            { "key_as_string": "2024-05-01", "doc_count": 0, "levels": { "buckets": [] } },
+            { "key_as_string": "2024-05-02", "doc_count": 9, "levels": { "buckets": [
+                { "key": 3, "doc_count": 5 },
+                { "key": 10, "doc_count": 2 },
+                { "key": 12, "doc_count": 1 },
+                { "key": 15, "doc_count": 1 }
+            ] } }
+        ] } }
+    });
+    let trend = crate::api::parse_alert_trend(&body, &crate::app::severity::SeverityScale::default());
+    assert_eq!(trend.len(), 2);
+    assert_eq!(trend[0].0, "2024-05-01");
+    assert_eq!(trend[0].1.total(), 0);
+    let day = &trend[1].1;
+    assert_eq!((day.critical, day.high, day.medium, day.low), (1, 1, 2, 5));
+}
+
+#[test]
+fn test_agent_alert_hours_query_and_parse() {
+    let query = crate::api::agent_alert_hours_query("007");
+    assert_eq!(query["size"], 0);
+    assert_eq!(query["query"]["bool"]["filter"][0]["term"]["agent.id"], "007");
+    assert_eq!(query["query"]["bool"]["filter"][1]["range"]["@timestamp"]["gte"], "now-23h/h");
+    let histogram = &query["aggs"]["hours"]["date_histogram"];
+    assert_eq!(histogram["fixed_interval"], "1h");
+    assert_eq!(histogram["min_doc_count"], 0);
+    assert_eq!(histogram["extended_bounds"]["max"], "now/h");
+
+    // 2024-05-01 08:00, 09:00 and 10:00 UTC
+    let body = serde_json::json!({
+        "aggregations": { "hours": { "buckets": [
+            { "key": 1714550400000i64, "doc_count": 3 },
+            { "key": 1714554000000i64, "doc_count": 0 },
+            { "key": 1714557600000i64, "doc_count": 41 }
+        ] } }
+    });
+    let hours = crate::api::parse_agent_alert_hours(&body);
+    assert_eq!(hours.counts(), vec![3, 0, 41]);
+    assert_eq!(hours.total(), 44);
+    let (peak_time, peak) = hours.peak().unwrap();
+    assert_eq!(peak, 41);
+    assert_eq!(peak_time.to_rfc3339(), "2024-05-01T10:00:00+00:00");
+
+    let quiet = crate::api::parse_agent_alert_hours(&serde_json::json!({ "aggregations": { "hours": { "buckets": [
+        { "key": 1714550400000i64, "doc_count": 0 }
+    ] } } }));
+    assert_eq!(quiet.peak(), None);
+    assert!(crate::api::parse_agent_alert_hours(&serde_json::json!({})).hours.is_empty());
+}
+
+#[test]
+fn test_deserialization_indexer_health() {
+    let cluster: crate::models::IndexerClusterHealth = serde_json::from_value(serde_json::json!({
+        "cluster_name": "wazuh-cluster", "status": "yellow", "timed_out": false,
+        "number_of_nodes": 1, "active_shards": 42, "unassigned_shards": 3
+    })).unwrap();
+    assert_eq!(cluster.status, "yellow");
+    assert_eq!(cluster.unassigned_shards, 3);
+
+    let indices: Vec<crate::models::IndexerIndex> = serde_json::from_value(serde_json::json!([
+        { "index": "wazuh-alerts-4.x-2024.05.02", "health": "green", "status": "open", "docs.count": "18234", "store.size": "52428800" },
+        { "index": "wazuh-states-vulnerabilities-manager", "health": "yellow", "status": "open", "docs.count": null, "store.size": null }
+    ])).unwrap();
+    assert_eq!(indices[0].docs_count.as_deref(), Some("18234"));
+    assert_eq!(indices[0].store_size.as_deref(), Some("52428800"));
+    assert!(indices[1].docs_count.is_none());
+
+    let disks: Vec<crate::models::IndexerAllocation> = serde_json::from_value(serde_json::json!([
+        { "node": "node-1", "disk.percent": "91", "disk.used": "96636764160", "disk.avail": "9556302848" }
+    ])).unwrap();
+    assert_eq!(disks[0].disk_percent(), Some(91));
+}
+
+#[test]
+fn test_msearch_body_and_responses() {
+    let body = crate::api::msearch_body(&[
+        ("wazuh-alerts-*", serde_json::json!({ "size": 10 })),
+        ("wazuh-states-vulnerabilities*", serde_json::json!({ "size": 0 })),
+    ]);
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(body.ends_with('\n'));
+    assert_eq!(serde_json::from_str::<serde_json::Value>(lines[0]).unwrap()["index"], "wazuh-alerts-*");
+    assert_eq!(serde_json::from_str::<serde_json::Value>(lines[3]).unwrap()["size"], 0);
+
+    let responses = crate::api::parse_msearch(&serde_json::json!({ "responses": [
+        { "hits": { "hits": [{ "_id": "1" }] }, "status": 200 },
+        { "error": { "type": "index_not_found_exception", "reason": "no such index [wazuh-states-vulnerabilities*]" }, "status": 404 }
+    ] }));
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0].as_ref().unwrap()["hits"]["hits"][0]["_id"], "1");
+    assert!(responses[1].as_ref().unwrap_err().to_string().contains("no such index"));
+}
+
+#[test]
+fn test_log_search_query_sort() {
+    let default = crate::api::log_search_query(None, 60, 0, 50, None, &crate::app::severity::SeverityScale::default());
+    assert_eq!(default["sort"], serde_json::json!([{ "@timestamp": { "order": "desc" } }]));
+
+    let mut app = crate::app::App::new();
+    app.cycle_log_sort();
+    let query = crate::api::log_search_query(None, 60, 0, 50, Some(&app.log_filter), &crate::app::severity::SeverityScale::default());
+    assert_eq!(query["sort"], serde_json::json!([{ "@timestamp": { "order": "asc" } }]));
+
+    app.log_offset = 100;
+    app.cycle_log_sort();
+    assert_eq!(app.log_offset, 0);
+    let query = crate::api::log_search_query(None, 60, 0, 50, Some(&app.log_filter), &crate::app::severity::SeverityScale::default());
+    // Equal levels stay newest first
+    assert_eq!(query["sort"], serde_json::json!([
+        { "rule.level": { "order": "desc" } },
+        { "@timestamp": { "order": "desc" } }
+    ]));
+    assert_eq!(app.log_filter.sort.column(), crate::app::LogColumn::Level);
+}
+
+#[test]
+fn test_log_search_query_severity_chips() {
+    let mut app = crate::app::App::new();
+    app.toggle_log_severity("low");
+    app.toggle_log_severity("critical");
+    assert_eq!(app.log_filter.severities, vec!["critical", "low"]);
+
+    let query = crate::api::log_search_query(None, 60, 0, 50, Some(&app.log_filter), &crate::app::severity::SeverityScale::default());
+    let must = query["query"]["bool"]["must"].as_array().unwrap();
+    let chips = must.iter().find(|m| m["bool"]["minimum_should_match"] == 1 && m["bool"]["should"][0]["range"].is_object()).unwrap();
+    assert_eq!(chips["bool"]["should"], serde_json::json!([
+        { "range": { "rule.level": { "gte": 15 } } },
+        { "range": { "rule.level": { "lte": 6 } } }
+    ]));
+
+    app.toggle_log_severity("critical");
+    app.toggle_log_severity("low");
+    let query = crate::api::log_search_query(None, 60, 0, 50, Some(&app.log_filter), &crate::app::severity::SeverityScale::default());
+    assert_eq!(query["query"]["bool"]["must"].as_array().unwrap().len(), must.len() - 1);
+}
+
+/// Plain-HTTP stand-in for the manager: answers every request with `{}` after `delay`,
+/// counting requests and the most that were open at once.
+async fn mock_manager(delay: std::time::Duration) -> (String, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (total, open, peak) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let (total_srv, peak_srv) = (total.clone(), peak.clone());
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else { return };
+            let (total, open, peak) = (total_srv.clone(), open.clone(), peak_srv.clone());
+            tokio::spawn(async move {
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                total.fetch_add(1, Ordering::SeqCst);
+                let now_open = open.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now_open, Ordering::SeqCst);
+                tokio::time::sleep(delay).await;
+                open.fetch_sub(1, Ordering::SeqCst);
+                let _ = socket.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}").await;
+            });
+        }
+    });
+    (url, total, peak)
+}
+
+async fn mock_api(url: String, max_concurrent: usize) -> WazuhApi {
+    let api = WazuhApi::new(Config {
+        url,
+        network: crate::models::NetworkConfig { max_concurrent_requests: Some(max_concurrent), ..Default::default() },
+        ..Default::default()
+    });
+    *api.token.write().await = Some("token".to_string());
+    api
+}
+
+#[tokio::test]
+async fn test_only_reads_and_searches_are_retried_after_sending() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let total = Arc::new(AtomicUsize::new(0));
+    let total_srv = total.clone();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            total_srv.fetch_add(1, Ordering::SeqCst);
+            let _ = socket.write_all(b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").await;
+        }
+    });
+    let api = WazuhApi::new(Config {
+        url: url.clone(),
+        os_url: Some(url),
+        network: crate::models::NetworkConfig { max_retries: Some(2), retry_base_delay_ms: Some(1), ..Default::default() },
+        ..Default::default()
+    });
+    *api.token.write().await = Some("token".to_string());
+
+    assert!(api.get_group_file("default", "agent.conf").await.is_err());
+    assert_eq!(total.load(Ordering::SeqCst), 3);
+
+    // A login may have gone through; it is not sent again
+    assert!(api.authenticate().await.is_err());
+    assert_eq!(total.load(Ordering::SeqCst), 4);
+
+    // Indexer searches are POSTs but read-only, so they are retried like a GET
+    assert!(api.start_alert_scroll(&serde_json::json!({ "size": 1 })).await.is_err());
+    assert_eq!(total.load(Ordering::SeqCst), 7);
+    assert!(api.get_dashboard_searches(60, 10).await.is_err());
+    assert_eq!(total.load(Ordering::SeqCst), 10);
+}
+
+#[tokio::test]
+async fn test_identical_gets_share_one_request() {
+    let (url, total, _) = mock_manager(std::time::Duration::from_millis(100)).await;
+    let api = mock_api(url, 4).await;
+
+    let results = futures_util::future::join_all((0..3).map(|_| api.get_group_file("default", "agent.conf"))).await;
+    assert!(results.iter().all(|r| matches!(r.as_deref(), Ok("{}"))));
+    assert_eq!(total.load(Ordering::SeqCst), 1);
+
+    // Finished requests are not cached
+    api.get_group_file("default", "agent.conf").await.unwrap();
+    assert_eq!(total.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_shared_get_keeps_api_errors() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            let body = r#"{"title": "Permission Denied", "detail": "No permission to read group files", "remediation": "Ask for group:read", "error": 4000}"#;
+            let response = format!("HTTP/1.1 403 Forbidden\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body);
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+    let api = mock_api(url, 4).await;
+
+    let results = futures_util::future::join_all((0..2).map(|_| api.get_group_file("default", "agent.conf"))).await;
+    for result in results {
+        let error = result.unwrap_err();
+        assert_eq!(error.downcast_ref::<crate::api::ApiError>().map(|e| e.code), Some(Some(4000)));
+        assert!(crate::api::error::describe(&error).contains("Remediation: Ask for group:read"));
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_requests_are_capped() {
+    let (url, total, peak) = mock_manager(std::time::Duration::from_millis(50)).await;
+    let api = mock_api(url, 2).await;
+
+    let files = ["a", "b", "c", "d", "e"];
+    let results = futures_util::future::join_all(files.iter().map(|f| api.get_group_file("default", f))).await;
+    assert!(results.iter().all(|r| r.is_ok()));
+    assert_eq!(total.load(Ordering::SeqCst), 5);
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_syscollector_pages_by_offset() {
+    let (url, total, _) = mock_manager(std::time::Duration::from_millis(0)).await;
+    let api = mock_api(url, 4).await;
+    // The mock answers `{}`, so only the request itself is checked
+    assert!(api.get_programs("003", 1000, 500).await.is_err());
+    assert_eq!(total.load(Ordering::SeqCst), 1);
+    assert!(api.request_log()[0].url.ends_with("/syscollector/003/packages?offset=1000&limit=500"));
+}
+
+#[test]
+fn test_parse_manager_status() {
+    let body = serde_json::json!({
+        "data": { "affected_items": [{ "wazuh-remoted": "running", "wazuh-analysisd": "running", "wazuh-clusterd": "stopped" }] }
+    });
+    assert_eq!(crate::api::parse_manager_status(&body), vec![
+        ("wazuh-analysisd".to_string(), "running".to_string()),
+        ("wazuh-clusterd".to_string(), "stopped".to_string()),
+        ("wazuh-remoted".to_string(), "running".to_string()),
+    ]);
+    assert!(crate::api::parse_manager_status(&serde_json::json!({ "error": 1 })).is_empty());
+}
+
+#[tokio::test]
+async fn test_manager_config_uses_manager_endpoint() {
+    let (url, _, _) = mock_manager(std::time::Duration::from_millis(0)).await;
+    let api = mock_api(url, 4).await;
+    // The mock answers `{}`, which has no section to return
+    assert!(api.get_agent_config("000", "logcollector").await.is_err());
+    assert!(api.request_log()[0].url.ends_with("/manager/configuration?section=localfile"));
+}
+
+#[test]
+fn test_connections_query_and_parse() {
+    let query = crate::api::connections_query("003", 1440);
+    let filter = query["query"]["bool"]["filter"].as_array().unwrap();
+    assert_eq!(filter[0]["term"]["agent.id"], "003");
+    assert_eq!(filter[1]["range"]["@timestamp"]["gte"], "now-1440m");
+    assert_eq!(query["query"]["bool"]["minimum_should_match"], 1);
+    let sources = query["aggs"]["connections"]["composite"]["sources"].as_array().unwrap();
+    assert_eq!(sources[2]["dstport"]["terms"]["field"], "data.dstport");
+    assert_eq!(sources[2]["dstport"]["terms"]["missing_bucket"], true);
+
+    let body = serde_json::json!({
+        "aggregations": { "connections": { "buckets": [
+            { "key": { "srcip": "10.0.0.5", "dstip": null, "dstport": null }, "doc_count": 3,
+              "last_seen": { "value_as_string": "2024-05-01T10:00:00.000Z" } },
+            { "key": { "srcip": "10.0.0.9", "dstip": "10.0.0.1", "dstport": "22" }, "doc_count": 12,
+              "last_seen": { "value_as_string": "2024-05-01T09:00:00.000Z" } },
+            { "key": { "srcip": null, "dstip": "8.8.8.8", "dstport": 53 }, "doc_count": 3,
+              "last_seen": { "value_as_string": "2024-05-01T11:00:00.000Z" } }
+        ] } }
+    });
+    let connections = crate::api::parse_connections(&body);
+    assert_eq!(connections.len(), 3);
+    assert_eq!(connections[0].count, 12);
+    assert_eq!(connections[0].dstport.as_deref(), Some("22"));
+    // Equal counts: most recent first; numeric ports read as text
+    assert_eq!(connections[1].dstip.as_deref(), Some("8.8.8.8"));
+    assert_eq!(connections[1].srcip, None);
+    assert_eq!(connections[1].dstport.as_deref(), Some("53"));
+    assert_eq!(connections[2].srcip.as_deref(), Some("10.0.0.5"));
+    assert_eq!(connections[2].dstip, None);
+}
+
+#[test]
+fn test_hunt_query_and_parse() {
+    use crate::app::hunts::Hunt;
+
+    let query = crate::api::hunt_query(Hunt::SshBruteForce, 60);
+    let filter = query["query"]["bool"]["filter"].as_array().unwrap();
+    assert_eq!(filter[0]["term"]["rule.groups"], "sshd");
+    assert_eq!(filter.last().unwrap()["range"]["@timestamp"]["gte"], "now-60m");
+    let terms = &query["aggs"]["buckets"]["terms"];
+    assert_eq!(terms["field"], "data.srcip");
+    assert_eq!(terms["min_doc_count"], 5);
+    assert_eq!(query["aggs"]["buckets"]["aggs"]["details"]["terms"]["field"], "data.dstuser");
+
+    let query = crate::api::hunt_query(Hunt::WindowsFailedLogons, 60);
+    assert_eq!(query["query"]["bool"]["filter"][0]["term"]["data.win.system.eventID"], "4625");
+    assert_eq!(query["aggs"]["buckets"]["terms"]["field"], "data.win.eventdata.targetUserName");
+
+    let body = serde_json::json!({
+        "aggregations": { "buckets": { "buckets": [
+            { "key": "203.0.113.7", "doc_count": 41,
+              "agents": { "buckets": [{ "key": "web-01", "doc_count": 30 }, { "key": "web-02", "doc_count": 11 }] },
+              "details": { "buckets": [{ "key": "root", "doc_count": 20 }, { "key": "admin", "doc_count": 21 }] },
+              "last_seen": { "value": 1714557600000.0, "value_as_string": "2024-05-01T10:00:00.000Z" } },
+            { "key": "198.51.100.2", "doc_count": 6,
+              "agents": { "buckets": [] }, "details": { "buckets": [] }, "last_seen": { "value": null } }
+        ] } }
+    });
+    let rows = crate::api::parse_hunt(&body);
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].key, "203.0.113.7");
+    assert_eq!(rows[0].count, 41);
+    assert_eq!(rows[0].agents, vec!["web-01", "web-02"]);
+    assert_eq!(rows[0].details, vec!["root", "admin"]);
+    assert_eq!(rows[0].last_seen.as_deref(), Some("2024-05-01T10:00:00.000Z"));
+    assert_eq!(rows[1].last_seen, None);
+    assert!(Hunt::SshBruteForce.keyed_by_srcip());
+    assert!(!Hunt::NewSudoUsers.keyed_by_srcip());
+}
+
+#[test]
+fn test_export_query_pages_the_log_search() {
+    let mut filter = crate::app::LogFilter { val1: 12, ..Default::default() };
+    filter.rule_id_filter = "5710".to_string();
+    let query = crate::api::export_query(None, 1440, Some(&filter), 1000, &crate::app::severity::SeverityScale::default());
+    let search = crate::api::log_search_query(None, 1440, 0, 1000, Some(&filter), &crate::app::severity::SeverityScale::default());
+    assert!(query.get("from").is_none());
+    assert_eq!(query["size"], 1000);
+    assert_eq!(query["query"], search["query"]);
+    assert_eq!(query["sort"], search["sort"]);
+}
+
+#[test]
+fn test_api_error_parse() {
+    use crate::api::ApiError;
+    let body = r#"{"title": "Bad Request", "detail": "Invalid field found {'agents_lst'}", "remediation": "Please, check the API reference", "error": 1400}"#;
+    let error = ApiError::parse(400, body);
+    assert_eq!(error.to_string(), "Invalid field found {'agents_lst'} (error 1400, HTTP 400)");
+    assert_eq!(error.details(), vec!["Remediation: Please, check the API reference".to_string()]);
+
+    // Not JSON: the body, cut short
+    let error = ApiError::parse(502, &"x".repeat(500));
+    assert_eq!(error.detail.chars().count(), 201);
+    assert!(error.to_string().ends_with("(HTTP 502)"));
+}
+
+#[test]
+fn test_api_error_all_failed() {
+    use crate::api::ApiError;
+    let body = serde_json::json!({
+        "data": {
+            "affected_items": [],
+            "total_affected_items": 0,
+            "failed_items": [{
+                "error": { "code": 1701, "message": "Agent does not exist", "remediation": "Please, use `GET /agents?select=id,name` to find all available agents" },
+                "id": ["998", "999"]
+            }],
+            "total_failed_items": 2
+        },
+        "message": "No agent was restarted",
+        "error": 2
+    });
+    let error = ApiError::from_all_failed(200, &body).unwrap();
+    assert_eq!(error.to_string(), "No agent was restarted");
+    assert_eq!(crate::api::error::describe(&anyhow::Error::new(error)), [
+        "No agent was restarted",
+        "• 998, 999: Agent does not exist (1701)",
+        "Remediation: Please, use `GET /agents?select=id,name` to find all available agents",
+    ].join("\n"));
+
+    // Some items went through: still a success
+    let mut partial = body.clone();
+    partial["error"] = serde_json::json!(1);
+    assert_eq!(ApiError::from_all_failed(200, &partial), None);
+    assert_eq!(crate::api::error::describe(&anyhow::anyhow!("timed out")), "timed out");
+}
+
+#[test]
+fn test_auth_request_run_as() {
+    let mut config = Config {
+        url: "https://localhost:55000".to_string(),
+        ..Default::default()
+    };
+    let (url, context) = WazuhApi::new(config.clone()).auth_request().unwrap();
+    assert_eq!(url, "https://localhost:55000/security/user/authenticate");
+    assert!(context.is_none());
+
+    config.auth_context = Some(r#"{"name": "jdoe", "groups": ["soc-analysts"]}"#.to_string());
+    let (url, context) = WazuhApi::new(config.clone()).auth_request().unwrap();
+    assert_eq!(url, "https://localhost:55000/security/user/authenticate/run_as");
+    assert_eq!(context.unwrap()["groups"][0], "soc-analysts");
+
+    config.auth_context = Some("{not json".to_string());
+    assert!(WazuhApi::new(config.clone()).auth_request().unwrap_err().to_string().contains("not valid JSON"));
+    config.auth_context = Some(r#"["soc"]"#.to_string());
+    assert!(WazuhApi::new(config).auth_request().is_err());
+}
+
+#[tokio::test]
+async fn test_run_as_request_is_recorded() {
+    let config = Config {
+        url: "https://127.0.0.1:1".to_string(),
+        auth_context: Some(r#"{"name": "jdoe"}"#.to_string()),
+        network: crate::models::NetworkConfig { max_retries: Some(0), ..Default::default() },
+        ..Default::default()
+    };
+    let api = WazuhApi::new(config);
+    assert!(api.authenticate().await.is_err());
+
+    let log = api.request_log();
+    assert_eq!(log[0].url, "https://127.0.0.1:1/security/user/authenticate/run_as");
+    assert!(log[0].body.as_deref().unwrap().contains("jdoe"));
+}
+
 // ============================================================================
 // INTEGRATION TESTS - These tests require a live Wazuh API
 // Run with: cargo test --features integration -- --ignored
@@ -272,6 +1138,7 @@ fn get_integration_config() -> Config {
         os_url: Some("https://192.168.0.113:9200".to_string()),
         os_username: Some("wazuh".to_string()),
         os_password: Some("xxxxxxx".to_string()),
+        ..Default::default()
     }
 }
 
@@ -0,0 +1,47 @@
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Accepts any certificate: the viewer is there to show what the server presents, including
+/// the self-signed certificates Wazuh installs by default.
+struct AcceptAnyCertificate;
+
+impl rustls::client::ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: std::time::SystemTime,
+    ) -> std::result::Result<rustls::client::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+/// DER certificates the server at `url` presents in the TLS handshake, leaf first.
+pub async fn peer_certificates(url: &str, timeout: Duration) -> Result<Vec<Vec<u8>>> {
+    let parsed = reqwest::Url::parse(url)?;
+    if parsed.scheme() != "https" {
+        return Err(anyhow!("{} does not use TLS", url));
+    }
+    let host = parsed.host_str().ok_or_else(|| anyhow!("No host in {}", url))?
+        .trim_start_matches('[').trim_end_matches(']').to_string();
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate))
+        .with_no_client_auth();
+    let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+    let server_name = rustls::ServerName::try_from(host.as_str()).map_err(|_| anyhow!("Invalid host name {}", host))?;
+
+    let handshake = async {
+        let stream = tokio::net::TcpStream::connect((host.as_str(), port)).await?;
+        let tls = connector.connect(server_name, stream).await?;
+        let (_, connection) = tls.get_ref();
+        Ok::<_, anyhow::Error>(connection.peer_certificates().unwrap_or_default().iter().map(|c| c.0.clone()).collect())
+    };
+    tokio::time::timeout(timeout, handshake).await.map_err(|_| anyhow!("TLS handshake with {}:{} timed out", host, port))?
+}
@@ -0,0 +1,76 @@
+use crate::models::WazuhAgent;
+use serde::{Deserialize, Serialize};
+
+/// Operation on one or more agents that goes through the confirmation popup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AgentAction {
+    Upgrade,
+    Restart,
+    FimScan,
+    /// Deleting never-connected or pending agents (Cleanup Stale Agents)
+    DeleteStale,
+}
+
+impl AgentAction {
+    pub fn title(&self) -> &'static str {
+        match self {
+            AgentAction::Upgrade => "Upgrade Agents",
+            AgentAction::Restart => "Restart Agents",
+            AgentAction::FimScan => "FIM Scan",
+            AgentAction::DeleteStale => "Cleanup Stale Agents",
+        }
+    }
+
+    /// Label of the key that confirms it, e.g. "[Enter] Restart".
+    pub fn verb(&self) -> &'static str {
+        match self {
+            AgentAction::Upgrade => "Upgrade",
+            AgentAction::Restart => "Restart",
+            AgentAction::FimScan => "Scan",
+            AgentAction::DeleteStale => "Delete all",
+        }
+    }
+
+    /// RBAC action it needs and the label the refusal names it by.
+    pub fn permission(&self) -> (&'static str, &'static str) {
+        match self {
+            AgentAction::Upgrade => (super::ACTION_AGENT_UPGRADE, "Upgrade"),
+            AgentAction::Restart => (super::ACTION_AGENT_RESTART, "Restart"),
+            AgentAction::FimScan => (super::ACTION_SYSCHECK_RUN, "FIM scan"),
+            AgentAction::DeleteStale => (super::ACTION_AGENT_DELETE, "Agent deletion"),
+        }
+    }
+
+    /// Drawn in red: the agents are gone or offline afterwards.
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, AgentAction::DeleteStale)
+    }
+
+    /// Needs the agent connected; can be queued for a disconnected one.
+    pub fn needs_active_agent(&self) -> bool {
+        !matches!(self, AgentAction::DeleteStale)
+    }
+
+    /// Whether to ask before running it on `count` agents. Deletions are always confirmed;
+    /// the rest only while `[confirm] agent_actions` is on, and a FIM scan only in bulk.
+    pub fn needs_confirmation(&self, count: usize, enabled: bool) -> bool {
+        match self {
+            AgentAction::DeleteStale => true,
+            AgentAction::FimScan => enabled && count > 1,
+            AgentAction::Upgrade | AgentAction::Restart => enabled,
+        }
+    }
+}
+
+/// Number of agents per status, most common first.
+pub fn status_counts(agents: &[&WazuhAgent]) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for agent in agents {
+        match counts.iter_mut().find(|(status, _)| *status == agent.status) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((agent.status.clone(), 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
@@ -0,0 +1,73 @@
+use crate::app::actions::{status_counts, AgentAction};
+use crate::app::{App, PopupMode};
+use crate::models::WazuhAgent;
+
+fn agent(id: &str, status: &str) -> WazuhAgent {
+    WazuhAgent {
+        id: id.to_string(),
+        name: format!("host-{}", id),
+        status: status.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_needs_confirmation() {
+    assert!(AgentAction::Restart.needs_confirmation(1, true));
+    assert!(!AgentAction::Restart.needs_confirmation(5, false));
+    assert!(!AgentAction::FimScan.needs_confirmation(1, true));
+    assert!(AgentAction::FimScan.needs_confirmation(2, true));
+    // Deletions ask even with confirmations turned off
+    assert!(AgentAction::DeleteStale.needs_confirmation(1, false));
+}
+
+#[test]
+fn test_status_counts() {
+    let agents = [agent("001", "active"), agent("002", "disconnected"), agent("003", "active"), agent("004", "pending")];
+    let refs: Vec<&WazuhAgent> = agents.iter().collect();
+    assert_eq!(status_counts(&refs), vec![
+        ("active".to_string(), 2),
+        ("disconnected".to_string(), 1),
+        ("pending".to_string(), 1),
+    ]);
+}
+
+#[test]
+fn test_request_agent_action() {
+    let mut app = App::new();
+    app.agents = vec![agent("000", "active"), agent("002", "active"), agent("001", "active")];
+    app.selected_agents = ["002", "000", "001"].iter().map(|s| s.to_string()).collect();
+
+    // The manager is left out and the rest wait for confirmation, in id order
+    assert_eq!(app.request_agent_action(AgentAction::Restart, app.action_targets()), None);
+    assert_eq!(app.popup_mode, PopupMode::ConfirmAgentAction {
+        action: AgentAction::Restart,
+        agent_ids: vec!["001".to_string(), "002".to_string()],
+    });
+
+    app.popup_mode = PopupMode::None;
+    app.confirm_agent_actions = false;
+    assert_eq!(app.request_agent_action(AgentAction::Upgrade, vec!["001".to_string()]), Some(vec!["001".to_string()]));
+    assert_eq!(app.popup_mode, PopupMode::None);
+    assert_eq!(app.request_agent_action(AgentAction::Upgrade, vec!["000".to_string()]), None);
+}
+
+#[test]
+fn test_other_profile_agents_are_never_action_targets() {
+    let mut app = App::new();
+    let added = Some((chrono::Utc::now() - chrono::Duration::days(30)).to_rfc3339());
+    // Agent IDs are per manager: 005 on site-b is not this manager's 005
+    app.agents = vec![
+        WazuhAgent { date_add: added.clone(), profile: Some("site-b".to_string()), ..agent("005", "never_connected") },
+        WazuhAgent { date_add: added, ..agent("005", "active") },
+    ];
+    assert!(app.stale_agents().is_empty());
+
+    app.selected_agent_index = 0;
+    assert!(app.action_targets().is_empty());
+    app.toggle_selection();
+    assert!(app.selected_agents.is_empty());
+
+    app.selected_agent_index = 1;
+    assert_eq!(app.action_targets(), vec!["005".to_string()]);
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Entries kept in memory for the Activity popup; the file keeps everything.
+pub const AUDIT_HISTORY: usize = 200;
+
+/// One mutating action (restart, upgrade, group change, config push, ...) and its outcome.
+/// Stored as one JSON object per line in `audit.log` next to `config.toml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: String,
+    /// API user the action ran as
+    #[serde(default)]
+    pub user: String,
+    pub action: String,
+    /// Agent IDs, group, user or role names the action applied to
+    #[serde(default)]
+    pub targets: Vec<String>,
+    pub success: bool,
+    #[serde(default)]
+    pub detail: String,
+}
+
+impl AuditEntry {
+    pub fn new(action: &str, targets: &[String], success: bool, detail: &str) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            user: String::new(),
+            action: action.to_string(),
+            targets: targets.to_vec(),
+            success,
+            detail: detail.to_string(),
+        }
+    }
+}
+
+pub fn audit_path() -> PathBuf {
+    crate::config::ConfigManager::get_config_path().with_file_name("audit.log")
+}
+
+pub fn append(path: &Path, entry: &AuditEntry) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    let line = serde_json::to_string(entry)?;
+    writeln!(file, "{}", line)
+}
+
+/// The last `limit` entries in file order; unreadable lines are skipped.
+pub fn load_recent(path: &Path, limit: usize) -> Vec<AuditEntry> {
+    let Ok(content) = std::fs::read_to_string(path) else { return Vec::new() };
+    let entries: Vec<AuditEntry> = content.lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let skip = entries.len().saturating_sub(limit);
+    entries.into_iter().skip(skip).collect()
+}
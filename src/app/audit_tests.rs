@@ -0,0 +1,24 @@
+use crate::app::audit::{append, load_recent, AuditEntry};
+
+#[test]
+fn test_audit_append_and_load_recent() {
+    let path = std::env::temp_dir().join(format!("wazuh-tui-audit-{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let restart = AuditEntry::new("restart", &["001".to_string(), "002".to_string()], true, "Restart signal sent to 2 agents");
+    let upgrade = AuditEntry::new("upgrade", &["003".to_string()], false, "Upgrade failed: 403");
+    append(&path, &restart).unwrap();
+    std::fs::OpenOptions::new().append(true).open(&path).and_then(|mut f| std::io::Write::write_all(&mut f, b"not json\n")).unwrap();
+    append(&path, &upgrade).unwrap();
+
+    let all = load_recent(&path, 10);
+    assert_eq!(all, vec![restart, upgrade.clone()]);
+    assert_eq!(load_recent(&path, 1), vec![upgrade]);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_audit_missing_file() {
+    assert!(load_recent(std::path::Path::new("/nonexistent/audit.log"), 10).is_empty());
+}
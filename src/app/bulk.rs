@@ -0,0 +1,95 @@
+use super::actions::AgentAction;
+
+/// Agents per `agents_list` request of a bulk restart or upgrade.
+pub const BATCH_SIZE: usize = 25;
+
+/// Outcome of one batch of a bulk operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResult {
+    pub agent_ids: Vec<String>,
+    /// Why the whole request failed
+    pub error: Option<String>,
+    /// Agents the manager refused (`failed_items`), with its reason
+    pub failed: Vec<(String, String)>,
+}
+
+impl BatchResult {
+    /// Result of a request the API answered; agents listed in `failed_items` count as failed.
+    pub fn from_response(agent_ids: Vec<String>, response: &serde_json::Value) -> Self {
+        BatchResult { agent_ids, error: None, failed: failed_items(response) }
+    }
+
+    pub fn from_error(agent_ids: Vec<String>, error: String) -> Self {
+        BatchResult { agent_ids, error: Some(error), failed: Vec::new() }
+    }
+
+    pub fn failed_count(&self) -> usize {
+        if self.error.is_some() { self.agent_ids.len() } else { self.failed.len() }
+    }
+}
+
+/// A restart or upgrade sent in batches, followed by the progress popup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkOp {
+    pub action: AgentAction,
+    pub total: usize,
+    pub batch_count: usize,
+    pub batches: Vec<BatchResult>,
+}
+
+impl BulkOp {
+    pub fn new(action: AgentAction, total: usize) -> Self {
+        BulkOp { action, total, batch_count: total.div_ceil(BATCH_SIZE), batches: Vec::new() }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.batches.len() >= self.batch_count
+    }
+
+    /// Agents the batches done so far covered.
+    pub fn processed(&self) -> usize {
+        self.batches.iter().map(|b| b.agent_ids.len()).sum()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.batches.iter().map(|b| b.failed_count()).sum()
+    }
+
+    /// Share of the agents processed, for the gauge.
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 { 1.0 } else { (self.processed() as f64 / self.total as f64).min(1.0) }
+    }
+
+    /// One line per failed batch or agent, in the order they happened.
+    pub fn failures(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (i, batch) in self.batches.iter().enumerate() {
+            if let Some(error) = &batch.error {
+                lines.push(format!("Batch {} ({} agents): {}", i + 1, batch.agent_ids.len(), error));
+            }
+            for (id, reason) in &batch.failed {
+                lines.push(format!("Agent {}: {}", id, reason));
+            }
+        }
+        lines
+    }
+}
+
+/// Splits the agents into the batches sent one request at a time.
+pub fn batches(agent_ids: &[String]) -> Vec<Vec<String>> {
+    agent_ids.chunks(BATCH_SIZE).map(|chunk| chunk.to_vec()).collect()
+}
+
+/// Agents of `data.failed_items` in a Wazuh API answer, each with the error message.
+pub fn failed_items(response: &serde_json::Value) -> Vec<(String, String)> {
+    let Some(items) = response.pointer("/data/failed_items").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+    items.iter().flat_map(|item| {
+        let reason = item.pointer("/error/message").and_then(|m| m.as_str()).unwrap_or("failed").to_string();
+        let ids: Vec<String> = item.get("id").and_then(|v| v.as_array())
+            .map(|ids| ids.iter().map(|id| id.as_str().map(String::from).unwrap_or_else(|| id.to_string())).collect())
+            .unwrap_or_default();
+        ids.into_iter().map(move |id| (id, reason.clone()))
+    }).collect()
+}
@@ -0,0 +1,57 @@
+use crate::app::actions::AgentAction;
+use crate::app::bulk::{batches, failed_items, BatchResult, BulkOp, BATCH_SIZE};
+use crate::app::{App, PopupMode};
+
+fn ids(n: usize) -> Vec<String> {
+    (1..=n).map(|i| format!("{:03}", i)).collect()
+}
+
+#[test]
+fn test_batches() {
+    let chunks = batches(&ids(BATCH_SIZE * 2 + 3));
+    assert_eq!(chunks.iter().map(|c| c.len()).collect::<Vec<_>>(), vec![BATCH_SIZE, BATCH_SIZE, 3]);
+    assert_eq!(BulkOp::new(AgentAction::Restart, BATCH_SIZE * 2 + 3).batch_count, 3);
+    assert!(batches(&[]).is_empty());
+}
+
+#[test]
+fn test_failed_items() {
+    let response = serde_json::json!({
+        "data": {
+            "affected_items": ["001"],
+            "failed_items": [
+                { "error": { "code": 1707, "message": "Cannot send request, agent is not active" }, "id": ["002", "003"] }
+            ],
+            "total_failed_items": 2
+        }
+    });
+    assert_eq!(failed_items(&response), vec![
+        ("002".to_string(), "Cannot send request, agent is not active".to_string()),
+        ("003".to_string(), "Cannot send request, agent is not active".to_string()),
+    ]);
+    assert!(failed_items(&serde_json::json!({ "data": { "affected_items": [] } })).is_empty());
+}
+
+#[test]
+fn test_bulk_progress() {
+    let mut app = App::new();
+    let agents = ids(BATCH_SIZE + 1);
+    assert!(app.start_bulk_op(AgentAction::Upgrade, agents.len()));
+    assert_eq!(app.popup_mode, PopupMode::BulkProgress);
+    // Only one at a time
+    assert!(!app.start_bulk_op(AgentAction::Restart, 1));
+
+    let response = serde_json::json!({ "data": { "failed_items": [{ "error": { "message": "Upgrade procedure could not start" }, "id": ["004"] }] } });
+    app.record_bulk_batch(BatchResult::from_response(agents[..BATCH_SIZE].to_vec(), &response));
+    app.record_bulk_batch(BatchResult::from_error(agents[BATCH_SIZE..].to_vec(), "timed out".to_string()));
+
+    let op = app.bulk_op.as_ref().unwrap();
+    assert!(op.is_finished());
+    assert_eq!((op.processed(), op.failed()), (BATCH_SIZE + 1, 2));
+    assert_eq!(op.failures(), vec![
+        "Agent 004: Upgrade procedure could not start".to_string(),
+        "Batch 2 (1 agents): timed out".to_string(),
+    ]);
+    assert_eq!(app.notifications.last().unwrap().message, format!("Upgrade: 2 of {} agents failed (see the progress popup)", BATCH_SIZE + 1));
+    assert!(app.start_bulk_op(AgentAction::Restart, 1));
+}
@@ -0,0 +1,111 @@
+use super::state::UiState;
+use super::{App, LogColumn, LogFilter};
+use crate::models::{Config, ConfirmConfig, DisplayConfig, ProfileConfig, SeverityConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// File name of an exported setup, written to the export directory.
+pub const BUNDLE_FILENAME: &str = "wazuh-tui-setup.toml";
+
+/// A profile as shared: whoever imports it adds the password in their own `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BundleProfile {
+    pub name: String,
+    pub url: String,
+    pub username: String,
+}
+
+/// The TUI setup a team shares in one TOML file: display and severity settings, key bindings,
+/// profiles without passwords, log columns and saved filters. Connection credentials, the
+/// tour flag and session state such as the active view stay on each machine.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SetupBundle {
+    pub log_columns: Option<Vec<LogColumn>>,
+    /// Paths kept in JSON alert exports
+    pub export_fields: Option<Vec<String>>,
+    // Tables last: TOML needs plain values before them
+    pub display: DisplayConfig,
+    pub severity: SeverityConfig,
+    pub confirm: ConfirmConfig,
+    pub keys: BTreeMap<String, String>,
+    pub log_filter: Option<LogFilter>,
+    pub saved_agent_searches: Option<BTreeMap<String, String>>,
+    pub profiles: Vec<BundleProfile>,
+}
+
+impl SetupBundle {
+    pub fn capture(app: &App) -> Self {
+        let ui = UiState::capture(app);
+        let mut bundle = SetupBundle {
+            log_columns: ui.log_columns,
+            export_fields: ui.export_fields,
+            log_filter: ui.log_filter,
+            saved_agent_searches: ui.saved_agent_searches,
+            ..Default::default()
+        };
+        if let Some(config) = app.api.as_ref().map(|api| &api.config) {
+            bundle.display = DisplayConfig { tour_seen: None, ..config.display.clone() };
+            bundle.severity = config.severity.clone();
+            bundle.confirm = config.confirm.clone();
+            bundle.keys = config.keys.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            bundle.profiles = config.profiles.iter()
+                .map(|p| BundleProfile { name: p.name.clone(), url: p.url.clone(), username: p.username.clone() })
+                .collect();
+        }
+        bundle
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Merges the shared settings into `config`. Key bindings are added or replaced by name;
+    /// profiles are only added, so a bundle can never point a saved password at another URL.
+    /// Returns the profiles that still need a password.
+    pub fn apply_to_config(&self, config: &mut Config) -> Vec<String> {
+        config.display = DisplayConfig { tour_seen: config.display.tour_seen, ..self.display.clone() };
+        config.severity = self.severity.clone();
+        config.confirm = self.confirm.clone();
+        config.keys.extend(self.keys.iter().map(|(k, v)| (k.clone(), v.clone())));
+        for shared in &self.profiles {
+            if config.profiles.iter().any(|p| p.name == shared.name) {
+                continue;
+            }
+            config.profiles.push(ProfileConfig {
+                name: shared.name.clone(),
+                url: shared.url.clone(),
+                username: shared.username.clone(),
+                password: String::new(),
+            });
+        }
+        config.profiles.iter().filter(|p| p.password.is_empty()).map(|p| p.name.clone()).collect()
+    }
+
+    /// Log columns, export fields and saved filters, as the session state would restore them.
+    pub fn apply_ui(&self, app: &mut App) {
+        UiState {
+            log_columns: self.log_columns.clone(),
+            export_fields: self.export_fields.clone(),
+            log_filter: self.log_filter.clone(),
+            saved_agent_searches: self.saved_agent_searches.clone(),
+            ..Default::default()
+        }.apply(app);
+    }
+}
+
+/// Where Export Setup writes and Import Setup looks first: the export directory, or the
+/// working directory when none is configured.
+pub fn default_path(export_dir: Option<&str>) -> PathBuf {
+    match export_dir.map(str::trim).filter(|d| !d.is_empty()) {
+        Some(dir) => super::export::expand_home(dir).join(BUNDLE_FILENAME),
+        None => PathBuf::from(BUNDLE_FILENAME),
+    }
+}
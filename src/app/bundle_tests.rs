@@ -0,0 +1,95 @@
+use crate::app::bundle::{default_path, SetupBundle, BUNDLE_FILENAME};
+use crate::app::{App, LogColumn};
+use crate::models::{Config, DisplayConfig, ProfileConfig};
+
+fn config() -> Config {
+    let mut config = Config {
+        url: "https://hq:55000".to_string(),
+        username: "wazuh".to_string(),
+        password: "secret".to_string(),
+        display: DisplayConfig { timezone: Some("utc".to_string()), icons: Some("ascii".to_string()), tour_seen: Some(true), ..Default::default() },
+        profiles: vec![ProfileConfig {
+            name: "branch".to_string(),
+            url: "https://branch:55000".to_string(),
+            username: "viewer".to_string(),
+            password: "viewer-pass".to_string(),
+        }],
+        ..Default::default()
+    };
+    config.keys.insert("F5".to_string(), "r".to_string());
+    config
+}
+
+#[test]
+fn test_capture_leaves_out_secrets() {
+    let mut app = App::new();
+    app.set_api(crate::api::WazuhApi::new(config()));
+    app.visible_log_columns = vec![LogColumn::Timestamp, LogColumn::SrcIp];
+    app.saved_agent_searches.insert(1, "st:disconnected".to_string());
+
+    let text = toml::to_string_pretty(&SetupBundle::capture(&app)).unwrap();
+    assert!(!text.contains("secret") && !text.contains("viewer-pass"));
+    assert!(!text.contains("https://hq:55000"));
+    assert!(!text.contains("tour_seen"));
+
+    let bundle: SetupBundle = toml::from_str(&text).unwrap();
+    assert_eq!(bundle.display.icons.as_deref(), Some("ascii"));
+    assert_eq!(bundle.keys.get("F5").map(String::as_str), Some("r"));
+    assert_eq!(bundle.profiles[0].username, "viewer");
+    assert_eq!(bundle.log_columns, Some(vec![LogColumn::Timestamp, LogColumn::SrcIp]));
+}
+
+#[test]
+fn test_apply_to_config_keeps_local_secrets() {
+    let mut app = App::new();
+    app.set_api(crate::api::WazuhApi::new(config()));
+    let mut bundle = SetupBundle::capture(&app);
+    bundle.display.timezone = Some("+02:00".to_string());
+    bundle.profiles[0].url = "https://branch-new:55000".to_string();
+    bundle.profiles.push(crate::app::bundle::BundleProfile {
+        name: "lab".to_string(),
+        url: "https://lab:55000".to_string(),
+        username: "viewer".to_string(),
+    });
+
+    let mut local = Config { display: DisplayConfig { tour_seen: Some(false), ..Default::default() }, ..config() };
+    local.keys.insert("F6".to_string(), "u".to_string());
+    let missing = bundle.apply_to_config(&mut local);
+
+    assert_eq!(missing, vec!["lab"]);
+    assert_eq!(local.password, "secret");
+    assert_eq!(local.display.timezone.as_deref(), Some("+02:00"));
+    assert_eq!(local.display.tour_seen, Some(false));
+    // Profiles configured here are left alone, so their password never goes to the bundle's URL
+    assert_eq!((local.profiles[0].url.as_str(), local.profiles[0].password.as_str()), ("https://branch:55000", "viewer-pass"));
+    assert_eq!(local.profiles[1].url, "https://lab:55000");
+    // Local bindings the bundle does not mention stay
+    assert_eq!(local.keys.len(), 2);
+}
+
+#[test]
+fn test_import_setup() {
+    let bundle: SetupBundle = toml::from_str(r#"
+        log_columns = ["Timestamp", "Level"]
+
+        [display]
+        relative_time = true
+
+        [saved_agent_searches]
+        4 = "os:windows"
+    "#).unwrap();
+
+    let mut app = App::new();
+    app.set_api(crate::api::WazuhApi::new(config()));
+    let missing = app.import_setup(&bundle);
+    assert!(missing.is_empty());
+    assert_eq!(app.visible_log_columns, vec![LogColumn::Timestamp, LogColumn::Level]);
+    assert!(app.relative_time);
+    assert_eq!(app.saved_agent_searches.get(&4).map(String::as_str), Some("os:windows"));
+}
+
+#[test]
+fn test_default_path() {
+    assert_eq!(default_path(None), std::path::PathBuf::from(BUNDLE_FILENAME));
+    assert_eq!(default_path(Some("/srv/exports")), std::path::PathBuf::from("/srv/exports").join(BUNDLE_FILENAME));
+}
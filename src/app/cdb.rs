@@ -0,0 +1,58 @@
+//! Line-level edits of CDB list files (`key:value` per line, value optional).
+//! Edits work on the raw file so comments and ordering survive the round trip.
+
+/// Formats one entry; keys containing `:` have to be quoted.
+pub fn format_entry(key: &str, value: &str) -> String {
+    let key = if key.contains(':') { format!("\"{}\"", key) } else { key.to_string() };
+    if value.is_empty() { format!("{}:", key) } else { format!("{}:{}", key, value) }
+}
+
+/// Splits `key:value` as typed in the add popup.
+pub fn parse_entry(input: &str) -> Option<(String, String)> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    let (key, value) = line_key(input);
+    Some((key.to_string(), value.trim().to_string()))
+}
+
+fn line_key(line: &str) -> (&str, &str) {
+    if let Some(rest) = line.strip_prefix('"') {
+        if let Some(end) = rest.find('"') {
+            let value = rest[end + 1..].strip_prefix(':').unwrap_or("");
+            return (&rest[..end], value);
+        }
+    }
+    match line.split_once(':') {
+        Some((key, value)) => (key, value),
+        None => (line, ""),
+    }
+}
+
+/// Appends an entry, or `None` when the key is already present.
+pub fn append_entry(content: &str, key: &str, value: &str) -> Option<String> {
+    if content.lines().any(|l| line_key(l.trim()).0 == key) {
+        return None;
+    }
+    let mut out = content.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(&format_entry(key, value));
+    out.push('\n');
+    Some(out)
+}
+
+/// Drops the lines with `key`, or `None` when the key is not in the file.
+pub fn remove_entry(content: &str, key: &str) -> Option<String> {
+    let kept: Vec<&str> = content.lines().filter(|l| line_key(l.trim()).0 != key).collect();
+    if kept.len() == content.lines().count() {
+        return None;
+    }
+    let mut out = kept.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    Some(out)
+}
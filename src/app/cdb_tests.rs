@@ -0,0 +1,30 @@
+use crate::app::cdb::{append_entry, format_entry, parse_entry, remove_entry};
+
+#[test]
+fn test_cdb_format_and_parse_entry() {
+    assert_eq!(format_entry("10.0.0.1", "malicious"), "10.0.0.1:malicious");
+    assert_eq!(format_entry("admin", ""), "admin:");
+    assert_eq!(format_entry("fe80::1", "ipv6"), "\"fe80::1\":ipv6");
+
+    assert_eq!(parse_entry(" 10.0.0.1:malicious "), Some(("10.0.0.1".to_string(), "malicious".to_string())));
+    assert_eq!(parse_entry("root"), Some(("root".to_string(), String::new())));
+    assert_eq!(parse_entry("\"fe80::1\":ipv6"), Some(("fe80::1".to_string(), "ipv6".to_string())));
+    assert_eq!(parse_entry("   "), None);
+}
+
+#[test]
+fn test_cdb_append_entry() {
+    let content = "10.0.0.1:malicious\n10.0.0.2:";
+    assert_eq!(append_entry(content, "10.0.0.3", "scanner").unwrap(), "10.0.0.1:malicious\n10.0.0.2:\n10.0.0.3:scanner\n");
+    assert_eq!(append_entry("", "root", "").unwrap(), "root:\n");
+    assert!(append_entry(content, "10.0.0.2", "again").is_none());
+}
+
+#[test]
+fn test_cdb_remove_entry() {
+    let content = "10.0.0.1:malicious\n\"fe80::1\":ipv6\n10.0.0.2:\n";
+    assert_eq!(remove_entry(content, "10.0.0.1").unwrap(), "\"fe80::1\":ipv6\n10.0.0.2:\n");
+    assert_eq!(remove_entry(content, "fe80::1").unwrap(), "10.0.0.1:malicious\n10.0.0.2:\n");
+    assert_eq!(remove_entry("root:\n", "root").unwrap(), "");
+    assert!(remove_entry(content, "10.0.0.9").is_none());
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+
+/// Days before expiry from which a certificate is flagged in the status bar.
+pub const EXPIRY_WARN_DAYS: i64 = 30;
+
+/// The fields of an X.509 certificate the certificate viewer shows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CertInfo {
+    /// Distinguished name, e.g. "O=Wazuh, CN=wazuh-manager"
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+    /// DNS names and IP addresses of the subjectAltName extension
+    pub san: Vec<String>,
+}
+
+impl CertInfo {
+    pub fn days_left(&self, now: DateTime<Utc>) -> i64 {
+        (self.not_after - now).num_days()
+    }
+
+    pub fn is_self_signed(&self) -> bool {
+        self.subject == self.issuer
+    }
+}
+
+/// Certificate chain an endpoint presented, leaf first, or why it could not be read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CertChain {
+    /// "Wazuh API" or "Indexer"
+    pub endpoint: String,
+    pub url: String,
+    pub result: Result<Vec<CertInfo>, String>,
+}
+
+impl CertChain {
+    /// The certificate of the chain that expires first, with its days left.
+    pub fn soonest_expiry(&self, now: DateTime<Utc>) -> Option<(&CertInfo, i64)> {
+        self.result.as_ref().ok()?.iter()
+            .map(|cert| (cert, cert.days_left(now)))
+            .min_by_key(|(_, days)| *days)
+    }
+}
+
+/// Reads the subject, issuer, validity and SAN of a DER-encoded X.509 certificate.
+pub fn parse_certificate(der: &[u8]) -> Option<CertInfo> {
+    let (_, cert) = X509Certificate::from_der(der).ok()?;
+    let san = match cert.subject_alternative_name() {
+        Ok(Some(ext)) => ext.value.general_names.iter().filter_map(|name| match name {
+            GeneralName::DNSName(dns) => Some(dns.to_string()),
+            GeneralName::IPAddress(&[a, b, c, d]) => Some(std::net::Ipv4Addr::new(a, b, c, d).to_string()),
+            GeneralName::IPAddress(ip) => <[u8; 16]>::try_from(*ip).ok().map(|ip| std::net::Ipv6Addr::from(ip).to_string()),
+            _ => None,
+        }).collect(),
+        _ => Vec::new(),
+    };
+    let validity = cert.validity();
+    Some(CertInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        not_before: DateTime::from_timestamp(validity.not_before.timestamp(), 0)?,
+        not_after: DateTime::from_timestamp(validity.not_after.timestamp(), 0)?,
+        san,
+    })
+}
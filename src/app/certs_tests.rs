@@ -0,0 +1,88 @@
+use crate::app::certs::{parse_certificate, CertChain, CertInfo};
+use crate::app::App;
+use chrono::{TimeZone, Utc};
+
+/// Self-signed EC certificate for CN=wazuh-manager with SAN wazuh.example.com, manager and
+/// 10.0.0.5, valid 2026-10-16 09:03:16 to 2036-10-13 09:03:16 UTC.
+const MANAGER_CERT: &[&str] = &[
+    "308201d330820179a003020102021467832827c85bc5730d1eb028029afa1a9c6b82d4300a06082a8648ce3d040302302831",
+    "0e300c060355040a0c0557617a75683116301406035504030c0d77617a75682d6d616e61676572301e170d32363130313630",
+    "39303331365a170d3336313031333039303331365a3028310e300c060355040a0c0557617a75683116301406035504030c0d",
+    "77617a75682d6d616e616765723059301306072a8648ce3d020106082a8648ce3d03010703420004f38ab53e8998971eb171",
+    "8649b38f3207d5553aaef04ccb55c20643e6adb76f29f1b910726090dc1c13028335b9852bb8036c17220471bb974ae59458",
+    "6d51600ca38180307e301d0603551d0e04160414a1c439565db11e72d6be631a52386a8d0f92f6af301f0603551d23041830",
+    "168014a1c439565db11e72d6be631a52386a8d0f92f6af300f0603551d130101ff040530030101ff302b0603551d11042430",
+    "22821177617a75682e6578616d706c652e636f6d82076d616e6167657287040a000005300a06082a8648ce3d040302034800",
+    "3045022100863f8d4df9789f0cc984a556c34c7438de0ed5a96a097482e12fbc2ce7a630e30220673190255c2a81b609168a",
+    "74d156f23545e5f59ed6b27ec726db13484495a148",
+];
+
+fn der() -> Vec<u8> {
+    let hex = MANAGER_CERT.concat();
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+fn cert(subject: &str, not_after: chrono::DateTime<Utc>) -> CertInfo {
+    CertInfo {
+        subject: subject.to_string(),
+        issuer: "CN=Wazuh Root CA".to_string(),
+        not_before: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+        not_after,
+        san: Vec::new(),
+    }
+}
+
+fn chain(endpoint: &str, result: Result<Vec<CertInfo>, String>) -> CertChain {
+    CertChain { endpoint: endpoint.to_string(), url: "https://wazuh:55000".to_string(), result }
+}
+
+#[test]
+fn test_parse_certificate() {
+    let cert = parse_certificate(&der()).unwrap();
+    assert_eq!(cert.subject, "O=Wazuh, CN=wazuh-manager");
+    assert_eq!(cert.issuer, "O=Wazuh, CN=wazuh-manager");
+    assert!(cert.is_self_signed());
+    assert_eq!(cert.not_before, Utc.with_ymd_and_hms(2026, 10, 16, 9, 3, 16).unwrap());
+    assert_eq!(cert.not_after, Utc.with_ymd_and_hms(2036, 10, 13, 9, 3, 16).unwrap());
+    assert_eq!(cert.san, vec!["wazuh.example.com", "manager", "10.0.0.5"]);
+}
+
+#[test]
+fn test_parse_certificate_rejects_garbage() {
+    assert_eq!(parse_certificate(&[]), None);
+    assert_eq!(parse_certificate(b"not a certificate"), None);
+    // Cut off in the middle of the to-be-signed part
+    assert_eq!(parse_certificate(&der()[..120]), None);
+}
+
+#[test]
+fn test_soonest_expiry() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    let leaf = cert("CN=manager", Utc.with_ymd_and_hms(2026, 11, 5, 0, 0, 0).unwrap());
+    let root = cert("CN=Wazuh Root CA", Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap());
+    let ok = chain("Wazuh API", Ok(vec![root, leaf]));
+    let (soonest, days) = ok.soonest_expiry(now).unwrap();
+    assert_eq!(soonest.subject, "CN=manager");
+    assert_eq!(days, 20);
+    assert_eq!(chain("Indexer", Err("connection refused".to_string())).soonest_expiry(now), None);
+}
+
+#[test]
+fn test_expiring_certificate() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    let mut app = App::new();
+    assert_eq!(app.expiring_certificate(now), None);
+
+    app.certificates = Some(vec![
+        chain("Wazuh API", Ok(vec![cert("CN=manager", Utc.with_ymd_and_hms(2027, 6, 1, 0, 0, 0).unwrap())])),
+        chain("Indexer", Err("timed out".to_string())),
+    ]);
+    assert_eq!(app.expiring_certificate(now), None);
+
+    app.certificates.as_mut().unwrap().push(chain("Indexer", Ok(vec![cert("CN=indexer", Utc.with_ymd_and_hms(2026, 10, 26, 0, 0, 0).unwrap())])));
+    assert_eq!(app.expiring_certificate(now), Some(("Indexer", 10)));
+
+    // An expired certificate is reported with negative days
+    app.certificates.as_mut().unwrap()[0] = chain("Wazuh API", Ok(vec![cert("CN=manager", Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap())]));
+    assert_eq!(app.expiring_certificate(now), Some(("Wazuh API", -15)));
+}
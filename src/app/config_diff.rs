@@ -0,0 +1,221 @@
+use serde_json::Value;
+
+/// An element of a group's `agent.conf`; attributes are kept for the
+/// `<agent_config>` filters and `<wodle name="...">`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmlNode {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+    pub text: String,
+    pub children: Vec<XmlNode>,
+}
+
+impl XmlNode {
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
+
+/// Minimal parser for the element tree of `agent.conf`: comments, CDATA and the XML
+/// declaration are skipped, entities are left as written.
+pub fn parse_xml(content: &str) -> Result<Vec<XmlNode>, String> {
+    let mut stack = vec![XmlNode::default()];
+    let mut rest = content;
+
+    while let Some(start) = rest.find('<') {
+        let text = rest[..start].trim();
+        if !text.is_empty() {
+            let top = stack.last_mut().expect("root stays on the stack");
+            if !top.text.is_empty() {
+                top.text.push(' ');
+            }
+            top.text.push_str(text);
+        }
+        rest = &rest[start..];
+
+        let end_marker = if rest.starts_with("<!--") {
+            "-->"
+        } else if rest.starts_with("<![CDATA[") {
+            "]]>"
+        } else if rest.starts_with("<?") {
+            "?>"
+        } else {
+            ">"
+        };
+        let end = rest.find(end_marker).ok_or("unterminated tag")?;
+        let tag = &rest[1..end];
+        rest = &rest[end + end_marker.len()..];
+        if end_marker != ">" {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let node = stack.pop().filter(|n| n.name == name.trim() && !n.name.is_empty())
+                .ok_or_else(|| format!("unexpected </{}>", name.trim()))?;
+            stack.last_mut().ok_or("unbalanced tags")?.children.push(node);
+        } else {
+            let self_closing = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let name = tag.split_whitespace().next().unwrap_or("");
+            if name.is_empty() {
+                return Err("empty tag".to_string());
+            }
+            let node = XmlNode { name: name.to_string(), attrs: parse_attrs(&tag[name.len()..]), ..Default::default() };
+            if self_closing {
+                stack.last_mut().expect("root stays on the stack").children.push(node);
+            } else {
+                stack.push(node);
+            }
+        }
+    }
+
+    match stack.pop() {
+        Some(root) if stack.is_empty() => Ok(root.children),
+        Some(open) => Err(format!("<{}> is never closed", open.name)),
+        None => Err("unbalanced tags".to_string()),
+    }
+}
+
+/// `key="value"` pairs; single quotes work too.
+fn parse_attrs(mut rest: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim().to_string();
+        let value_part = rest[eq + 1..].trim_start();
+        let Some(quote) = value_part.chars().next().filter(|c| *c == '"' || *c == '\'') else { break };
+        let Some(close) = value_part[1..].find(quote) else { break };
+        attrs.push((key, value_part[1..close + 1].to_string()));
+        rest = &value_part[close + 2..];
+    }
+    attrs
+}
+
+/// Whether an `<agent_config>` block targets this agent. `name` and `os` are `|`-separated
+/// alternatives; `profile` blocks depend on agent-side settings and are left out.
+fn block_applies(block: &XmlNode, agent_name: &str, agent_os: &str) -> bool {
+    let os = agent_os.to_lowercase();
+    let name_ok = block.attr("name").map(|n| n.split('|').any(|n| n.trim() == agent_name)).unwrap_or(true);
+    let os_ok = block.attr("os").map(|o| o.split('|').any(|o| os.contains(&o.trim().to_lowercase()))).unwrap_or(true);
+    name_ok && os_ok && block.attr("profile").is_none()
+}
+
+/// Elements of `agent.conf` that configure a config component, see `WazuhApi::get_agent_config`.
+pub fn xml_sections(component: &str) -> &'static [&'static str] {
+    match component {
+        "syscheck" => &["syscheck"],
+        "logcollector" => &["localfile"],
+        "agent" => &["client"],
+        "wmodules" => &["wodle", "sca"],
+        _ => &[],
+    }
+}
+
+/// A value the group's `agent.conf` sets, as a dotted path below the component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineSetting {
+    pub group: String,
+    pub key: String,
+    pub expected: String,
+}
+
+/// Leaf values set for `component` by the blocks of one group file that apply to the agent.
+/// Attributes other than a wodle's name are not compared.
+pub fn baseline_settings(group: &str, agent_conf: &str, component: &str, agent_name: &str, agent_os: &str) -> Result<Vec<BaselineSetting>, String> {
+    let sections = xml_sections(component);
+    let mut settings = Vec::new();
+    for block in parse_xml(agent_conf)?.iter().filter(|n| n.name == "agent_config") {
+        if !block_applies(block, agent_name, agent_os) {
+            continue;
+        }
+        for section in block.children.iter().filter(|c| sections.contains(&c.name.as_str())) {
+            // Wodles are keyed by name in the API output, other sections by their settings
+            let prefix = match (section.name.as_str(), section.attr("name")) {
+                ("wodle", Some(name)) => name.to_string(),
+                ("sca", _) => "sca".to_string(),
+                _ => String::new(),
+            };
+            collect_leaves(section, &prefix, group, &mut settings);
+        }
+    }
+    Ok(settings)
+}
+
+fn collect_leaves(node: &XmlNode, prefix: &str, group: &str, out: &mut Vec<BaselineSetting>) {
+    if node.children.is_empty() {
+        if !prefix.is_empty() && !node.text.is_empty() {
+            out.push(BaselineSetting { group: group.to_string(), key: prefix.to_string(), expected: node.text.clone() });
+        }
+        return;
+    }
+    for child in &node.children {
+        let key = if prefix.is_empty() { child.name.clone() } else { format!("{}.{}", prefix, child.name) };
+        collect_leaves(child, &key, group, out);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Drift {
+    Match,
+    /// The agent has the setting with other values
+    Differs(String),
+    /// The agent reports nothing under this key
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDrift {
+    pub setting: BaselineSetting,
+    pub drift: Drift,
+}
+
+/// Checks each baseline setting against the agent's running config. Arrays are searched
+/// element by element and a comma-separated expectation (`/etc,/usr/bin`) needs every part,
+/// so lists match regardless of how the API splits them. Settings only the agent has are
+/// defaults or local overrides and are not reported.
+pub fn compare(baseline: &[BaselineSetting], config: &Value) -> Vec<ConfigDrift> {
+    baseline.iter().map(|setting| {
+        let path: Vec<&str> = setting.key.split('.').collect();
+        let mut found = Vec::new();
+        lookup(config, &path, &mut found);
+        let drift = if found.is_empty() {
+            Drift::Missing
+        } else {
+            let mut actual = Vec::new();
+            for value in &found {
+                scalars(value, &mut actual);
+            }
+            let wanted = setting.expected.split(',').map(str::trim).filter(|v| !v.is_empty());
+            if wanted.clone().all(|w| actual.iter().any(|a| a.eq_ignore_ascii_case(w))) {
+                Drift::Match
+            } else {
+                actual.sort();
+                actual.dedup();
+                Drift::Differs(actual.join(", "))
+            }
+        };
+        ConfigDrift { setting: setting.clone(), drift }
+    }).collect()
+}
+
+fn lookup<'a>(value: &'a Value, path: &[&str], out: &mut Vec<&'a Value>) {
+    match (value, path.split_first()) {
+        (Value::Array(items), Some(_)) => items.iter().for_each(|i| lookup(i, path, out)),
+        (Value::Object(obj), Some((key, rest))) => {
+            if let Some(child) = obj.get(*key) {
+                lookup(child, rest, out);
+            }
+        }
+        (_, None) => out.push(value),
+        _ => {}
+    }
+}
+
+fn scalars(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::Array(items) => items.iter().for_each(|i| scalars(i, out)),
+        Value::Object(obj) => obj.values().for_each(|v| scalars(v, out)),
+        Value::String(s) => out.push(s.trim().to_string()),
+        Value::Null => {}
+        other => out.push(other.to_string()),
+    }
+}
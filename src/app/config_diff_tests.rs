@@ -0,0 +1,65 @@
+use crate::app::config_diff::{self, Drift};
+
+const AGENT_CONF: &str = r#"<!-- Shared agent configuration -->
+<agent_config>
+  <syscheck>
+    <frequency>43200</frequency>
+    <directories check_all="yes">/etc,/usr/bin</directories>
+    <skip_nfs>yes</skip_nfs>
+  </syscheck>
+  <wodle name="docker-listener">
+    <disabled>no</disabled>
+  </wodle>
+</agent_config>
+
+<agent_config os="Windows">
+  <syscheck>
+    <windows_registry>HKEY_LOCAL_MACHINE\Software</windows_registry>
+  </syscheck>
+</agent_config>
+"#;
+
+#[test]
+fn test_parse_xml_tree() {
+    let nodes = config_diff::parse_xml(AGENT_CONF).unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[1].attr("os"), Some("Windows"));
+    let syscheck = &nodes[0].children[0];
+    assert_eq!(syscheck.children[1].attr("check_all"), Some("yes"));
+    assert_eq!(syscheck.children[1].text, "/etc,/usr/bin");
+
+    assert!(config_diff::parse_xml("<agent_config><syscheck></agent_config>").is_err());
+    assert!(config_diff::parse_xml("<agent_config>").is_err());
+}
+
+#[test]
+fn test_baseline_settings_follow_block_filters() {
+    let linux = config_diff::baseline_settings("web", AGENT_CONF, "syscheck", "web-01", "Ubuntu ubuntu").unwrap();
+    let keys: Vec<&str> = linux.iter().map(|s| s.key.as_str()).collect();
+    assert_eq!(keys, ["frequency", "directories", "skip_nfs"]);
+    assert_eq!(linux[0].group, "web");
+
+    let windows = config_diff::baseline_settings("web", AGENT_CONF, "syscheck", "win-01", "Microsoft Windows Server 2019").unwrap();
+    assert_eq!(windows.len(), 4);
+
+    let wodles = config_diff::baseline_settings("web", AGENT_CONF, "wmodules", "web-01", "ubuntu").unwrap();
+    assert_eq!(wodles[0].key, "docker-listener.disabled");
+}
+
+#[test]
+fn test_compare_against_agent_config() {
+    let baseline = config_diff::baseline_settings("web", AGENT_CONF, "syscheck", "web-01", "ubuntu").unwrap();
+    let config = serde_json::json!({
+        "frequency": 3600,
+        "directories": [
+            { "dir": "/etc", "opts": ["check_md5sum"] },
+            { "dir": "/usr/bin", "opts": ["check_md5sum"] }
+        ]
+    });
+    let drift: Vec<Drift> = config_diff::compare(&baseline, &config).into_iter().map(|d| d.drift).collect();
+    assert_eq!(drift, [Drift::Differs("3600".to_string()), Drift::Match, Drift::Missing]);
+
+    let wodles = config_diff::baseline_settings("web", AGENT_CONF, "wmodules", "web-01", "ubuntu").unwrap();
+    let config = serde_json::json!([{ "syscollector": { "disabled": "no" } }, { "docker-listener": { "disabled": "yes" } }]);
+    assert_eq!(config_diff::compare(&wodles, &config)[0].drift, Drift::Differs("yes".to_string()));
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use std::time::{Duration, Instant};
+
+/// Seconds between `/manager/info` pings while the API answers.
+pub const HEARTBEAT_SECS: u64 = 30;
+/// Pings are closer together while the manager is unreachable, so it is picked up soon after it returns.
+pub const RECONNECT_SECS: u64 = 5;
+/// A ping slower than this marks the connection as degraded.
+pub const SLOW_PING_MS: u64 = 2000;
+/// Consecutive failed pings before the connection counts as down.
+pub const DOWN_AFTER_FAILURES: u32 = 2;
+
+/// Header dot: green, yellow or red.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConnectionState {
+    /// No ping answered yet
+    Unknown,
+    Up,
+    /// Slow, or one ping failed
+    Degraded,
+    Down,
+}
+
+/// What a ping result changed, for the caller to notify and reload on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transition {
+    Lost,
+    Reconnected,
+}
+
+#[derive(Debug, Clone)]
+pub struct Heartbeat {
+    pub state: ConnectionState,
+    pub last_ok: Option<DateTime<Utc>>,
+    pub last_latency_ms: Option<u64>,
+    pub last_error: Option<String>,
+    pub failures: u32,
+    pub next_check: Instant,
+    pub in_flight: bool,
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self {
+            state: ConnectionState::Unknown,
+            last_ok: None,
+            last_latency_ms: None,
+            last_error: None,
+            failures: 0,
+            // The startup load already tells whether the API is reachable
+            next_check: Instant::now() + Duration::from_secs(HEARTBEAT_SECS),
+            in_flight: false,
+        }
+    }
+}
+
+impl Heartbeat {
+    /// True when a ping should be sent now; marks it in flight until `record` is called.
+    pub fn due(&mut self, now: Instant) -> bool {
+        if self.in_flight || now < self.next_check {
+            return false;
+        }
+        self.in_flight = true;
+        true
+    }
+
+    /// A ping sent now should re-authenticate first, as the token may not have survived the outage.
+    pub fn needs_reconnect(&self) -> bool {
+        self.state == ConnectionState::Down
+    }
+
+    /// Updates the state from a ping: its latency in ms, or the error.
+    pub fn record(&mut self, result: Result<u64, String>, now: DateTime<Utc>, instant: Instant) -> Option<Transition> {
+        let was_down = self.state == ConnectionState::Down;
+        self.in_flight = false;
+        match result {
+            Ok(ms) => {
+                self.failures = 0;
+                self.last_ok = Some(now);
+                self.last_latency_ms = Some(ms);
+                self.last_error = None;
+                self.state = if ms > SLOW_PING_MS { ConnectionState::Degraded } else { ConnectionState::Up };
+            }
+            Err(e) => {
+                self.failures += 1;
+                self.last_error = Some(e);
+                self.state = if self.failures >= DOWN_AFTER_FAILURES { ConnectionState::Down } else { ConnectionState::Degraded };
+            }
+        }
+        let is_down = self.state == ConnectionState::Down;
+        let wait = if self.failures > 0 { RECONNECT_SECS } else { HEARTBEAT_SECS };
+        self.next_check = instant + Duration::from_secs(wait);
+        match (was_down, is_down) {
+            (false, true) => Some(Transition::Lost),
+            (true, false) => Some(Transition::Reconnected),
+            _ => None,
+        }
+    }
+
+    /// Header tooltip-style text next to the dot.
+    pub fn label(&self, now: DateTime<Utc>) -> String {
+        match self.state {
+            ConnectionState::Unknown => "API".to_string(),
+            ConnectionState::Up => format!("API {}ms", self.last_latency_ms.unwrap_or(0)),
+            ConnectionState::Degraded if self.failures == 0 => format!("API slow {}ms", self.last_latency_ms.unwrap_or(0)),
+            ConnectionState::Degraded => "API retrying".to_string(),
+            ConnectionState::Down => match self.last_ok {
+                Some(t) => format!("API down {}s", (now - t).num_seconds().max(0)),
+                None => "API down".to_string(),
+            },
+        }
+    }
+}
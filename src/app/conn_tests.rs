@@ -0,0 +1,36 @@
+use crate::app::conn::{ConnectionState, Heartbeat, Transition, HEARTBEAT_SECS, RECONNECT_SECS};
+use chrono::{Duration, TimeZone, Utc};
+use std::time::Instant;
+
+#[test]
+fn test_heartbeat_goes_down_and_reconnects() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap();
+    let instant = Instant::now();
+    let mut hb = Heartbeat::default();
+    assert!(!hb.due(instant));
+
+    assert_eq!(hb.record(Ok(40), now, instant), None);
+    assert_eq!((hb.state, hb.label(now).as_str()), (ConnectionState::Up, "API 40ms"));
+    assert_eq!(hb.next_check, instant + std::time::Duration::from_secs(HEARTBEAT_SECS));
+
+    // One miss is only a warning; the second one means the manager is gone
+    assert_eq!(hb.record(Err("connection refused".into()), now, instant), None);
+    assert_eq!(hb.state, ConnectionState::Degraded);
+    assert_eq!(hb.record(Err("connection refused".into()), now, instant), Some(Transition::Lost));
+    assert!(hb.needs_reconnect());
+    assert_eq!(hb.next_check, instant + std::time::Duration::from_secs(RECONNECT_SECS));
+    assert_eq!(hb.label(now + Duration::seconds(75)), "API down 75s");
+
+    assert_eq!(hb.record(Ok(3500), now, instant), Some(Transition::Reconnected));
+    assert_eq!((hb.state, hb.label(now).as_str()), (ConnectionState::Degraded, "API slow 3500ms"));
+}
+
+#[test]
+fn test_heartbeat_single_ping_in_flight() {
+    let mut hb = Heartbeat::default();
+    let later = hb.next_check;
+    assert!(hb.due(later));
+    assert!(!hb.due(later));
+    hb.record(Ok(10), Utc::now(), later);
+    assert!(!hb.due(later));
+}
@@ -0,0 +1,600 @@
+use serde_json::{json, Value};
+use crate::models::WazuhVulnerabilityItem;
+use flate2::write::GzEncoder;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Output format for exported alerts. Everything except JSON is one line per alert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Cef,
+    Leef,
+    Syslog,
+}
+
+impl ExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Cef => "CEF",
+            ExportFormat::Leef => "LEEF",
+            ExportFormat::Syslog => "Syslog",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Cef => "cef",
+            ExportFormat::Leef => "leef",
+            ExportFormat::Syslog => "log",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ExportFormat::Json => ExportFormat::Cef,
+            ExportFormat::Cef => ExportFormat::Leef,
+            ExportFormat::Leef => ExportFormat::Syslog,
+            ExportFormat::Syslog => ExportFormat::Json,
+        }
+    }
+
+    /// With `fields`, JSON output keeps only those paths of each alert; the line formats have
+    /// fixed fields of their own and ignore them.
+    pub fn render(&self, logs: &[Value], fields: &[String]) -> Result<String, String> {
+        let mut out = Vec::new();
+        for (i, log) in logs.iter().enumerate() {
+            self.write_alert(&mut out, log, fields, i == 0).map_err(|e| format!("Write error: {}", e))?;
+        }
+        self.write_end(&mut out, logs.is_empty()).map_err(|e| format!("Write error: {}", e))?;
+        String::from_utf8(out).map_err(|e| format!("Encoding error: {}", e))
+    }
+
+    /// One alert of an export. JSON alerts are elements of a pretty-printed array that the first
+    /// one opens.
+    fn write_alert(&self, out: &mut dyn Write, log: &Value, fields: &[String], first: bool) -> io::Result<()> {
+        let source = log.get("_source").unwrap_or(log);
+        let line_fn: fn(&Value) -> String = match self {
+            ExportFormat::Json => {
+                let element = if fields.is_empty() {
+                    serde_json::to_string_pretty(log)?
+                } else {
+                    serde_json::to_string_pretty(&project(source, fields))?
+                };
+                out.write_all(if first { b"[\n  " } else { b",\n  " })?;
+                return out.write_all(element.replace('\n', "\n  ").as_bytes());
+            }
+            ExportFormat::Cef => cef_line,
+            ExportFormat::Leef => leef_line,
+            ExportFormat::Syslog => syslog_line,
+        };
+        out.write_all(line_fn(source).as_bytes())?;
+        out.write_all(b"\n")
+    }
+
+    /// Closes the JSON array; the line formats need nothing.
+    fn write_end(&self, out: &mut dyn Write, empty: bool) -> io::Result<()> {
+        match self {
+            ExportFormat::Json => out.write_all(if empty { b"[]" } else { b"\n]" }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Compression for alert exports, to keep large dumps manageable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportCompression {
+    #[default]
+    None,
+    Gzip,
+    /// A `.zip` bundle holding the export file
+    Zip,
+}
+
+impl ExportCompression {
+    /// `none`, `gzip` (or `gz`) or `zip`, as in `[export] compress`.
+    pub fn from_config(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "none" | "" => Some(ExportCompression::None),
+            "gzip" | "gz" => Some(ExportCompression::Gzip),
+            "zip" => Some(ExportCompression::Zip),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportCompression::None => "none",
+            ExportCompression::Gzip => "gzip",
+            ExportCompression::Zip => "zip",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ExportCompression::None => ExportCompression::Gzip,
+            ExportCompression::Gzip => ExportCompression::Zip,
+            ExportCompression::Zip => ExportCompression::None,
+        }
+    }
+
+    /// File extension of a `format` export: `json`, `json.gz` or `zip`.
+    pub fn extension(&self, format: ExportFormat) -> String {
+        match self {
+            ExportCompression::None => format.extension().to_string(),
+            ExportCompression::Gzip => format!("{}.gz", format.extension()),
+            ExportCompression::Zip => "zip".to_string(),
+        }
+    }
+}
+
+/// Writes alerts to `path`, creating its directory if needed.
+pub fn write_alerts(logs: &[Value], format: ExportFormat, compression: ExportCompression, fields: &[String], path: &Path) -> Result<(), String> {
+    let mut writer = AlertWriter::create(path, format, compression, fields)?;
+    writer.write(logs)?;
+    writer.finish().map(|_| ())
+}
+
+/// Writes an export file, creating its directory if needed.
+pub fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    create_parent(path)?;
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn create_parent(path: &Path) -> Result<(), String> {
+    match path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        Some(dir) => std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e)),
+        None => Ok(()),
+    }
+}
+
+enum Sink {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zip(ZipWriter<BufWriter<File>>),
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Sink::Plain(w) => w.write(buf),
+            Sink::Gzip(w) => w.write(buf),
+            Sink::Zip(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Sink::Plain(w) => w.flush(),
+            Sink::Gzip(w) => w.flush(),
+            Sink::Zip(w) => w.flush(),
+        }
+    }
+}
+
+impl Sink {
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Sink::Plain(mut w) => w.flush(),
+            Sink::Gzip(w) => w.finish()?.flush(),
+            Sink::Zip(mut w) => w.finish()?.flush(),
+        }
+    }
+}
+
+/// Streams alerts into an export file a page at a time, so memory stays flat however many
+/// alerts a full export pages through. Dropped before `finish` (an error or a cancelled export),
+/// it deletes the partial file.
+pub struct AlertWriter {
+    sink: Option<Sink>,
+    path: PathBuf,
+    format: ExportFormat,
+    fields: Vec<String>,
+    written: usize,
+}
+
+impl AlertWriter {
+    pub fn create(path: &Path, format: ExportFormat, compression: ExportCompression, fields: &[String]) -> Result<Self, String> {
+        create_parent(path)?;
+        let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        let mut writer = AlertWriter { sink: None, path: path.to_path_buf(), format, fields: fields.to_vec(), written: 0 };
+        let out = BufWriter::new(file);
+        writer.sink = Some(match compression {
+            ExportCompression::None => Sink::Plain(out),
+            ExportCompression::Gzip => Sink::Gzip(GzEncoder::new(out, flate2::Compression::default())),
+            ExportCompression::Zip => {
+                let mut zip = ZipWriter::new(out);
+                let options = FileOptions::default().compression_method(CompressionMethod::Deflated).large_file(true);
+                zip.start_file(zip_entry_name(path, format), options).map_err(|e| format!("Zip error: {}", e))?;
+                Sink::Zip(zip)
+            }
+        });
+        Ok(writer)
+    }
+
+    pub fn write(&mut self, logs: &[Value]) -> Result<(), String> {
+        let Some(sink) = self.sink.as_mut() else { return Ok(()) };
+        for log in logs {
+            self.format.write_alert(sink, log, &self.fields, self.written == 0)
+                .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))?;
+            self.written += 1;
+        }
+        Ok(())
+    }
+
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Completes the file and returns how many alerts it holds.
+    pub fn finish(mut self) -> Result<usize, String> {
+        let Some(mut sink) = self.sink.take() else { return Ok(self.written) };
+        let result = self.format.write_end(&mut sink, self.written == 0).and_then(|_| sink.finish());
+        match result {
+            Ok(()) => Ok(self.written),
+            Err(e) => {
+                let _ = std::fs::remove_file(&self.path);
+                Err(format!("Failed to write {}: {}", self.path.display(), e))
+            }
+        }
+    }
+}
+
+impl Drop for AlertWriter {
+    fn drop(&mut self) {
+        if self.sink.take().is_some() {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Name of the export inside a `.zip` bundle: the bundle's name with the format's extension.
+fn zip_entry_name(path: &Path, format: ExportFormat) -> String {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let stem = name.strip_suffix(".zip").unwrap_or(&name);
+    let suffix = format!(".{}", format.extension());
+    if stem.ends_with(&suffix) { stem.to_string() } else { format!("{}{}", stem, suffix) }
+}
+
+/// What an export file name template is filled in with.
+pub struct ExportName<'a> {
+    /// `security-events`, `agent-logs`, `vulnerabilities` or `report`
+    pub view: &'a str,
+    /// Agent name, `all` when the export is not about one agent
+    pub agent: &'a str,
+    pub from: chrono::DateTime<chrono::Local>,
+    pub to: chrono::DateTime<chrono::Local>,
+    pub extension: &'a str,
+}
+
+/// Where exports go, from `dir` and `filename` in the `[export]` config section.
+#[derive(Debug, Clone, Default)]
+pub struct ExportNaming {
+    pub dir: Option<String>,
+    pub filename: Option<String>,
+}
+
+impl ExportNaming {
+    /// Absolute path for an export; `default` is the file name template used when none is
+    /// configured.
+    pub fn path(&self, default: &str, name: &ExportName) -> Result<PathBuf, String> {
+        let template = self.filename.as_deref().map(str::trim).filter(|t| !t.is_empty()).unwrap_or(default);
+        let file = fill_template(template, name);
+        let path = match self.dir.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+            Some(dir) => expand_home(dir).join(file),
+            None => PathBuf::from(file),
+        };
+        std::path::absolute(&path).map_err(|e| format!("Invalid export path {}: {}", path.display(), e))
+    }
+}
+
+/// Replaces `{view}`, `{agent}`, `{from}` and `{to}` in a file name template and appends the
+/// extension unless the template already ends with it. Values are reduced to characters safe in
+/// file names; unknown placeholders are kept as written.
+pub fn fill_template(template: &str, name: &ExportName) -> String {
+    let stamp = |t: &chrono::DateTime<chrono::Local>| t.format("%Y%m%d_%H%M%S").to_string();
+    let mut file = template
+        .replace("{view}", &file_safe(name.view))
+        .replace("{agent}", &file_safe(name.agent))
+        .replace("{from}", &stamp(&name.from))
+        .replace("{to}", &stamp(&name.to));
+    let suffix = format!(".{}", name.extension);
+    if !file.ends_with(&suffix) {
+        file.push_str(&suffix);
+    }
+    file
+}
+
+fn file_safe(value: &str) -> String {
+    value.chars().map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '_' }).collect()
+}
+
+/// `~/exports` relative to the home directory; other paths as given.
+pub fn expand_home(dir: &str) -> PathBuf {
+    let home = || directories::BaseDirs::new().map(|d| d.home_dir().to_path_buf());
+    match dir.strip_prefix("~/").or(if dir == "~" { Some("") } else { None }) {
+        Some(rest) => home().map(|h| h.join(rest)).unwrap_or_else(|| PathBuf::from(dir)),
+        None => PathBuf::from(dir),
+    }
+}
+
+/// Splits a list of jq-style paths (`.rule.id, agent.name data.win.eventdata.ipAddress`) on
+/// commas and spaces. The leading dot is optional; `[n]` or `.n` picks an array element.
+pub fn parse_field_paths(input: &str) -> Result<Vec<String>, String> {
+    let mut paths: Vec<String> = Vec::new();
+    for raw in input.split([',', ' ']).map(str::trim).filter(|p| !p.is_empty()) {
+        let path = raw.strip_prefix('.').unwrap_or(raw);
+        if path_segments(path).is_none() {
+            return Err(format!("Invalid field path: {}", raw));
+        }
+        if !paths.iter().any(|p| p == path) {
+            paths.push(path.to_string());
+        }
+    }
+    Ok(paths)
+}
+
+/// `rule.groups[0]` as `["rule", "groups", "0"]`; `None` for empty segments or unclosed brackets.
+fn path_segments(path: &str) -> Option<Vec<&str>> {
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let (key, mut rest) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if !key.is_empty() {
+            segments.push(key);
+        } else if rest.is_empty() {
+            return None;
+        }
+        while let Some(inner) = rest.strip_prefix('[') {
+            let end = inner.find(']')?;
+            let index = &inner[..end];
+            if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            segments.push(index);
+            rest = &inner[end + 1..];
+        }
+        if !rest.is_empty() {
+            return None;
+        }
+    }
+    Some(segments)
+}
+
+/// The value at `path` in an alert, following array indexes.
+pub fn extract_path<'a>(source: &'a Value, path: &str) -> Option<&'a Value> {
+    path_segments(path)?.into_iter().try_fold(source, |v, segment| match v {
+        Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+        _ => v.get(segment),
+    })
+}
+
+/// An exported alert cut down to the chosen paths, serialized as an object with the fields in
+/// the order they were given (a `serde_json` object would sort them).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection(pub Vec<(String, Value)>);
+
+impl serde::Serialize for Projection {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// One entry per path, keyed by the path; fields the alert lacks are null so every row has
+/// the same keys.
+pub fn project(source: &Value, paths: &[String]) -> Projection {
+    Projection(paths.iter()
+        .map(|p| (p.clone(), extract_path(source, p).cloned().unwrap_or(Value::Null)))
+        .collect())
+}
+
+/// Common alert fields pulled out once for the line formats.
+struct AlertFields {
+    timestamp: String,
+    rule_id: String,
+    description: String,
+    level: u64,
+    agent_name: String,
+    agent_ip: String,
+    srcip: Option<String>,
+    dstip: Option<String>,
+    user: Option<String>,
+    groups: String,
+}
+
+fn str_at(source: &Value, path: &[&str]) -> Option<String> {
+    let mut v = source;
+    for key in path {
+        v = v.get(key)?;
+    }
+    v.as_str().map(|s| s.to_string())
+}
+
+fn fields(source: &Value) -> AlertFields {
+    AlertFields {
+        timestamp: str_at(source, &["@timestamp"]).or_else(|| str_at(source, &["timestamp"])).unwrap_or_default(),
+        rule_id: str_at(source, &["rule", "id"]).unwrap_or_else(|| "0".to_string()),
+        description: str_at(source, &["rule", "description"]).unwrap_or_default(),
+        level: source.get("rule").and_then(|r| r.get("level")).and_then(|l| l.as_u64()).unwrap_or(0),
+        agent_name: str_at(source, &["agent", "name"]).unwrap_or_else(|| "-".to_string()),
+        agent_ip: str_at(source, &["agent", "ip"]).unwrap_or_default(),
+        srcip: str_at(source, &["data", "srcip"]),
+        dstip: str_at(source, &["data", "dstip"]),
+        user: str_at(source, &["data", "srcuser"]).or_else(|| str_at(source, &["data", "dstuser"])),
+        groups: source.get("rule").and_then(|r| r.get("groups")).and_then(|g| g.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join(","))
+            .unwrap_or_default(),
+    }
+}
+
+fn cef_header_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn cef_value_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('=', "\\=").replace('\n', "\\n").replace('\r', "")
+}
+
+/// Wazuh levels run 0-15, CEF severities 0-10.
+pub fn cef_severity(level: u64) -> u64 {
+    (level.min(15) * 10 + 7) / 15
+}
+
+pub fn cef_line(source: &Value) -> String {
+    let f = fields(source);
+    let mut ext = vec![
+        format!("rt={}", cef_value_escape(&f.timestamp)),
+        format!("dvchost={}", cef_value_escape(&f.agent_name)),
+    ];
+    if !f.agent_ip.is_empty() {
+        ext.push(format!("dvc={}", cef_value_escape(&f.agent_ip)));
+    }
+    if let Some(ip) = &f.srcip {
+        ext.push(format!("src={}", cef_value_escape(ip)));
+    }
+    if let Some(ip) = &f.dstip {
+        ext.push(format!("dst={}", cef_value_escape(ip)));
+    }
+    if let Some(user) = &f.user {
+        ext.push(format!("suser={}", cef_value_escape(user)));
+    }
+    if !f.groups.is_empty() {
+        ext.push(format!("cs1Label=groups cs1={}", cef_value_escape(&f.groups)));
+    }
+    format!(
+        "CEF:0|Wazuh|Wazuh|4|{}|{}|{}|{}",
+        cef_header_escape(&f.rule_id),
+        cef_header_escape(&f.description),
+        cef_severity(f.level),
+        ext.join(" ")
+    )
+}
+
+pub fn leef_line(source: &Value) -> String {
+    let f = fields(source);
+    let clean = |s: &str| s.replace(['\t', '\n', '\r'], " ");
+    let mut attrs = vec![
+        format!("devTime={}", clean(&f.timestamp)),
+        format!("sev={}", cef_severity(f.level)),
+        format!("identHostName={}", clean(&f.agent_name)),
+        format!("msg={}", clean(&f.description)),
+    ];
+    if let Some(ip) = &f.srcip {
+        attrs.push(format!("src={}", clean(ip)));
+    }
+    if let Some(ip) = &f.dstip {
+        attrs.push(format!("dst={}", clean(ip)));
+    }
+    if let Some(user) = &f.user {
+        attrs.push(format!("usrName={}", clean(user)));
+    }
+    format!("LEEF:1.0|Wazuh|Wazuh|4|{}|{}", f.rule_id.replace('|', " "), attrs.join("\t"))
+}
+
+/// RFC 5424 line on facility local0 with the rule level mapped to a syslog severity.
+pub fn syslog_line(source: &Value) -> String {
+    let f = fields(source);
+    let severity = match f.level {
+        15..=u64::MAX => 2, // critical
+        12..=14 => 3,       // error
+        7..=11 => 4,        // warning
+        _ => 6,             // informational
+    };
+    let pri = 16 * 8 + severity;
+    let timestamp = if f.timestamp.is_empty() { "-".to_string() } else { f.timestamp.clone() };
+    let mut msg = format!("level={} rule={} {}", f.level, f.rule_id, f.description.replace('\n', " "));
+    if let Some(ip) = &f.srcip {
+        msg.push_str(&format!(" srcip={}", ip));
+    }
+    format!("<{}>1 {} {} wazuh - {} - {}", pri, timestamp, f.agent_name.replace(' ', "_"), f.rule_id, msg)
+}
+
+/// Output format for an agent's vulnerability list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VulnExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl VulnExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            VulnExportFormat::Csv => "CSV",
+            VulnExportFormat::Json => "JSON",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            VulnExportFormat::Csv => "csv",
+            VulnExportFormat::Json => "json",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            VulnExportFormat::Csv => VulnExportFormat::Json,
+            VulnExportFormat::Json => VulnExportFormat::Csv,
+        }
+    }
+
+    /// One row per CVE with the agent, severity, CVSS score and affected package version.
+    pub fn render(&self, agent_id: &str, agent_name: &str, vulns: &[&WazuhVulnerabilityItem]) -> Result<String, String> {
+        match self {
+            VulnExportFormat::Json => {
+                let rows: Vec<Value> = vulns.iter().map(|v| {
+                    let (package, version) = vuln_package(v);
+                    json!({
+                        "agent_id": agent_id,
+                        "agent_name": agent_name,
+                        "cve": v.cve,
+                        "severity": v.severity,
+                        "cvss": v.cvss,
+                        "package": package,
+                        "version": version,
+                        "title": v.title,
+                    })
+                }).collect();
+                serde_json::to_string_pretty(&rows).map_err(|e| format!("JSON error: {}", e))
+            }
+            VulnExportFormat::Csv => {
+                let mut out = String::from("agent_id,agent_name,cve,severity,cvss,package,version,title\n");
+                for v in vulns {
+                    let (package, version) = vuln_package(v);
+                    let cvss = v.cvss.map(|s| format!("{:.1}", s)).unwrap_or_default();
+                    let fields: [&str; 8] = [agent_id, agent_name, &v.cve, &v.severity, &cvss, &package, &version, v.title.as_deref().unwrap_or("")];
+                    let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                    out.push_str(&line.join(","));
+                    out.push('\n');
+                }
+                Ok(out)
+            }
+        }
+    }
+}
+
+fn vuln_package(v: &WazuhVulnerabilityItem) -> (String, String) {
+    let name = v.package.as_ref().map(|p| p.name.clone()).or_else(|| v.name.clone()).unwrap_or_default();
+    let version = v.package.as_ref().map(|p| p.version.clone()).or_else(|| v.version.clone()).unwrap_or_default();
+    (name, version)
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break (RFC 4180).
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,169 @@
+use crate::app::export::{cef_line, cef_severity, csv_field, fill_template, leef_line, syslog_line, write_alerts, AlertWriter, ExportCompression, ExportFormat, ExportName, ExportNaming, VulnExportFormat};
+use crate::models::{WazuhVulnerabilityItem, WazuhVulnerabilityPackage};
+use serde_json::json;
+
+fn alert() -> serde_json::Value {
+    json!({
+        "@timestamp": "2024-05-01T10:00:00.000Z",
+        "agent": { "id": "003", "name": "web-01", "ip": "10.0.0.3" },
+        "rule": { "id": "5710", "level": 12, "description": "sshd: attempt|to=login", "groups": ["sshd", "authentication_failed"] },
+        "data": { "srcip": "203.0.113.7", "srcuser": "root" }
+    })
+}
+
+#[test]
+fn test_cef_line_escapes_and_maps_severity() {
+    let line = cef_line(&alert());
+    assert!(line.starts_with("CEF:0|Wazuh|Wazuh|4|5710|sshd: attempt\\|to=login|8|"));
+    assert!(line.contains("src=203.0.113.7"));
+    assert!(line.contains("suser=root"));
+    assert!(line.contains("cs1=sshd,authentication_failed"));
+    assert_eq!(cef_severity(0), 0);
+    assert_eq!(cef_severity(15), 10);
+}
+
+#[test]
+fn test_leef_and_syslog_lines() {
+    let leef = leef_line(&alert());
+    assert!(leef.starts_with("LEEF:1.0|Wazuh|Wazuh|4|5710|devTime=2024-05-01T10:00:00.000Z\t"));
+    assert!(leef.contains("\tsrc=203.0.113.7"));
+
+    let syslog = syslog_line(&alert());
+    assert!(syslog.starts_with("<131>1 2024-05-01T10:00:00.000Z web-01 wazuh - 5710 - level=12"));
+}
+
+#[test]
+fn test_render_one_line_per_alert() {
+    let logs = vec![json!({ "_source": alert() }), json!({ "_source": alert() })];
+    let out = ExportFormat::Cef.render(&logs, &[]).unwrap();
+    assert_eq!(out.lines().count(), 2);
+    assert_eq!(ExportFormat::Syslog.next(), ExportFormat::Json);
+}
+
+fn vuln() -> WazuhVulnerabilityItem {
+    WazuhVulnerabilityItem {
+        cve: "CVE-2024-6387".to_string(),
+        severity: "High".to_string(),
+        status: None,
+        title: Some("regreSSHion, signal handler race".to_string()),
+        package: Some(WazuhVulnerabilityPackage {
+            name: "openssh-server".to_string(),
+            version: "1:8.9p1-3".to_string(),
+            architecture: None,
+        }),
+        name: None,
+        version: None,
+        cvss: Some(8.1),
+    }
+}
+
+#[test]
+fn test_csv_field_quoting() {
+    assert_eq!(csv_field("plain"), "plain");
+    assert_eq!(csv_field("a,b"), "\"a,b\"");
+    assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+}
+
+#[test]
+fn test_vuln_export_csv_and_json() {
+    let v = vuln();
+    let csv = VulnExportFormat::Csv.render("001", "web-01", &[&v]).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "agent_id,agent_name,cve,severity,cvss,package,version,title");
+    assert_eq!(lines[1], "001,web-01,CVE-2024-6387,High,8.1,openssh-server,1:8.9p1-3,\"regreSSHion, signal handler race\"");
+
+    let json: serde_json::Value = serde_json::from_str(&VulnExportFormat::Json.render("001", "web-01", &[&v]).unwrap()).unwrap();
+    assert_eq!(json[0]["cvss"], 8.1);
+    assert_eq!(json[0]["package"], "openssh-server");
+    assert_eq!(json[0]["version"], "1:8.9p1-3");
+}
+
+#[test]
+fn test_export_field_paths() {
+    use crate::app::export::{extract_path, parse_field_paths};
+
+    let paths = parse_field_paths(".@timestamp, agent.name  rule.id,data.srcip rule.groups[1] rule.id").unwrap();
+    assert_eq!(paths, vec!["@timestamp", "agent.name", "rule.id", "data.srcip", "rule.groups[1]"]);
+    assert!(parse_field_paths("").unwrap().is_empty());
+    assert!(parse_field_paths("rule..id").is_err());
+    assert!(parse_field_paths("rule.groups[x]").is_err());
+    assert!(parse_field_paths("rule.groups[0").is_err());
+
+    let source = alert();
+    assert_eq!(extract_path(&source, "rule.groups[1]"), Some(&json!("authentication_failed")));
+    assert_eq!(extract_path(&source, "rule.groups.0"), Some(&json!("sshd")));
+    assert_eq!(extract_path(&source, "data.dstip"), None);
+
+    // Keys follow the given order, missing fields are null
+    let logs = vec![json!({ "_source": alert() })];
+    let fields: Vec<String> = ["rule.id", "@timestamp", "data.dstip"].iter().map(|s| s.to_string()).collect();
+    let out = ExportFormat::Json.render(&logs, &fields).unwrap();
+    let rule = out.find("\"rule.id\"").unwrap();
+    assert!(rule < out.find("\"@timestamp\"").unwrap());
+    let rows: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(rows[0], json!({ "rule.id": "5710", "@timestamp": "2024-05-01T10:00:00.000Z", "data.dstip": null }));
+    // Line formats ignore the selection
+    assert!(ExportFormat::Cef.render(&logs, &fields).unwrap().starts_with("CEF:0|"));
+}
+
+#[test]
+fn test_export_file_names() {
+    use chrono::TimeZone;
+    let name = ExportName {
+        view: "agent-logs",
+        agent: "web 01/prod",
+        from: chrono::Local.with_ymd_and_hms(2024, 5, 1, 9, 45, 0).unwrap(),
+        to: chrono::Local.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap(),
+        extension: "json",
+    };
+    assert_eq!(fill_template("{view}_{agent}_{from}_{to}", &name), "agent-logs_web_01_prod_20240501_094500_20240501_100000.json");
+    assert_eq!(fill_template("alerts-{to}.json", &name), "alerts-20240501_100000.json");
+    assert_eq!(fill_template("{unknown}", &name), "{unknown}.json");
+
+    let default = ExportNaming::default().path("wazuh_export_{to}", &name).unwrap();
+    assert!(default.is_absolute());
+    assert_eq!(default, std::env::current_dir().unwrap().join("wazuh_export_20240501_100000.json"));
+
+    let naming = ExportNaming { dir: Some("/tmp/exports".to_string()), filename: Some("{view}".to_string()) };
+    assert_eq!(naming.path("wazuh_export_{to}", &name).unwrap(), std::path::PathBuf::from("/tmp/exports/agent-logs.json"));
+}
+
+#[test]
+fn test_compressed_alert_exports() {
+    use std::io::Read;
+    let logs = vec![json!({ "_id": "a1", "_source": alert() }), json!({ "_id": "a2", "_source": alert() })];
+    // Streaming keeps the pretty-printed array byte for byte
+    assert_eq!(ExportFormat::Json.render(&logs, &[]).unwrap(), serde_json::to_string_pretty(&logs).unwrap());
+    assert_eq!(ExportFormat::Json.render(&[], &[]).unwrap(), "[]");
+    assert_eq!(ExportCompression::Gzip.extension(ExportFormat::Json), "json.gz");
+    assert_eq!(ExportCompression::from_config("GZ"), Some(ExportCompression::Gzip));
+    assert_eq!(ExportCompression::from_config("bz2"), None);
+
+    let dir = std::env::temp_dir().join(format!("wazuh-tui-export-{}", std::process::id()));
+    let expected = ExportFormat::Cef.render(&logs, &[]).unwrap();
+
+    let gz = dir.join("alerts.cef.gz");
+    write_alerts(&logs, ExportFormat::Cef, ExportCompression::Gzip, &[], &gz).unwrap();
+    let mut text = String::new();
+    flate2::read::GzDecoder::new(std::fs::File::open(&gz).unwrap()).read_to_string(&mut text).unwrap();
+    assert_eq!(text, expected);
+
+    let zip_path = dir.join("alerts.zip");
+    let mut writer = AlertWriter::create(&zip_path, ExportFormat::Cef, ExportCompression::Zip, &[]).unwrap();
+    writer.write(&logs[..1]).unwrap();
+    writer.write(&logs[1..]).unwrap();
+    assert_eq!(writer.finish().unwrap(), 2);
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&zip_path).unwrap()).unwrap();
+    let mut text = String::new();
+    archive.by_name("alerts.cef").unwrap().read_to_string(&mut text).unwrap();
+    assert_eq!(text, expected);
+
+    // A writer dropped before finishing (a cancelled export) leaves nothing behind
+    let partial = dir.join("partial.json");
+    let mut writer = AlertWriter::create(&partial, ExportFormat::Json, ExportCompression::None, &[]).unwrap();
+    writer.write(&logs).unwrap();
+    drop(writer);
+    assert!(!partial.exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
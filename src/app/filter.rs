@@ -8,9 +8,54 @@ pub enum FilterPredicate {
     Status(String),
     Os(String),
     Severity(u32), // For severity filtering
+    /// Member of the group (exact name, case-insensitive)
+    Group(String),
+    /// `ver:<4.8`, `ver:>=4.7.2`
+    Version(Cmp, (u32, u32, u32)),
+    /// `ver:4.7` matches 4.7.x
+    VersionPrefix(String),
+    /// `ka:>2h`: keep-alive age in minutes; agents that never checked in count as older than anything
+    KeepAliveAge(Cmp, u32),
+    /// `id:10-50`, inclusive
+    IdRange(u32, u32),
+    /// Local tag (see `labels.rs`), substring match
+    Tag(String),
+    /// Cluster node the agent reports to (exact name, case-insensitive)
+    Node(String),
     Global(String),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cmp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl Cmp {
+    /// Splits a leading `<`, `<=`, `>`, `>=` or `=` off `value`.
+    fn split(value: &str) -> (Option<Cmp>, &str) {
+        for (prefix, cmp) in [(">=", Cmp::Ge), ("<=", Cmp::Le), (">", Cmp::Gt), ("<", Cmp::Lt), ("=", Cmp::Eq)] {
+            if let Some(rest) = value.strip_prefix(prefix) {
+                return (Some(cmp), rest);
+            }
+        }
+        (None, value)
+    }
+
+    fn holds<T: PartialOrd>(self, left: T, right: T) -> bool {
+        match self {
+            Cmp::Lt => left < right,
+            Cmp::Le => left <= right,
+            Cmp::Gt => left > right,
+            Cmp::Ge => left >= right,
+            Cmp::Eq => left == right,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct AgentFilter {
     pub predicates: Vec<FilterPredicate>,
@@ -26,7 +71,27 @@ impl AgentFilter {
             if let Some((field, value)) = part.split_once(':') {
                 match field.to_lowercase().as_str() {
                     "name" | "n" => predicates.push(FilterPredicate::Name(value.to_lowercase())),
-                    "id" => predicates.push(FilterPredicate::Id(value.to_lowercase())),
+                    "id" => match value.split_once('-').and_then(|(a, b)| Some((a.parse::<u32>().ok()?, b.parse::<u32>().ok()?))) {
+                        Some((from, to)) => predicates.push(FilterPredicate::IdRange(from.min(to), from.max(to))),
+                        None => predicates.push(FilterPredicate::Id(value.to_lowercase())),
+                    },
+                    "group" | "g" => predicates.push(FilterPredicate::Group(value.to_lowercase())),
+                    "tag" | "t" => predicates.push(FilterPredicate::Tag(value.to_lowercase())),
+                    "node" => predicates.push(FilterPredicate::Node(value.to_lowercase())),
+                    "ver" | "version" => match Cmp::split(value) {
+                        (Some(cmp), v) => match crate::app::health::parse_version(v) {
+                            Some(version) => predicates.push(FilterPredicate::Version(cmp, version)),
+                            None => predicates.push(FilterPredicate::Global(part.to_lowercase())),
+                        },
+                        (None, v) => predicates.push(FilterPredicate::VersionPrefix(v.trim_start_matches('v').to_string())),
+                    },
+                    "ka" | "keepalive" => {
+                        let (cmp, age) = Cmp::split(value);
+                        match crate::app::parse_interval(age) {
+                            Ok(minutes) => predicates.push(FilterPredicate::KeepAliveAge(cmp.unwrap_or(Cmp::Gt), minutes)),
+                            Err(_) => predicates.push(FilterPredicate::Global(part.to_lowercase())),
+                        }
+                    }
                     "ip" => predicates.push(FilterPredicate::Ip(value.to_lowercase())),
                     "status" | "st" => predicates.push(FilterPredicate::Status(value.to_lowercase())),
                     "os" => predicates.push(FilterPredicate::Os(value.to_lowercase())),
@@ -57,6 +122,11 @@ impl AgentFilter {
     }
 
     pub fn matches(&self, agent: &WazuhAgent) -> bool {
+        self.matches_with_tags(agent, &[])
+    }
+
+    /// Like `matches`, with the agent's local tags for `tag:` tokens.
+    pub fn matches_with_tags(&self, agent: &WazuhAgent, tags: &[String]) -> bool {
         if self.predicates.is_empty() {
             return true;
         }
@@ -71,6 +141,21 @@ impl AgentFilter {
                 os.name.as_ref().map(|n| n.to_lowercase().contains(val)).unwrap_or(false)
             }).unwrap_or(false),
             FilterPredicate::Severity(_) => true, // Severity might need access to vulnerabilities or rule stats, which aren't in WazuhAgent directly
+            FilterPredicate::Group(val) => agent.group.as_ref().map(|g| g.iter().any(|name| name.to_lowercase() == *val)).unwrap_or(false),
+            FilterPredicate::Version(cmp, version) => agent.version.as_deref()
+                .and_then(crate::app::health::parse_version)
+                .map(|v| cmp.holds(v, *version))
+                .unwrap_or(false),
+            FilterPredicate::VersionPrefix(val) => agent.version.as_deref()
+                .map(|v| v.trim().trim_start_matches("Wazuh").trim().trim_start_matches('v').starts_with(val.as_str()))
+                .unwrap_or(false),
+            FilterPredicate::KeepAliveAge(cmp, minutes) => match agent.last_keep_alive_time() {
+                Some(t) => cmp.holds((chrono::Utc::now() - t).num_minutes(), *minutes as i64),
+                None => matches!(cmp, Cmp::Gt | Cmp::Ge),
+            },
+            FilterPredicate::IdRange(from, to) => agent.id.parse::<u32>().map(|id| (*from..=*to).contains(&id)).unwrap_or(false),
+            FilterPredicate::Tag(val) => tags.iter().any(|t| t.contains(val.as_str())),
+            FilterPredicate::Node(val) => agent.node_name.as_ref().map(|n| n.to_lowercase() == *val).unwrap_or(false),
             FilterPredicate::Global(val) => {
                 agent.name.to_lowercase().contains(val) ||
                 agent.id.to_lowercase().contains(val) ||
@@ -31,6 +31,7 @@ fn test_agent_filter_matches() {
             arch: None,
         }),
         manager: None,
+        profile: None,
     };
 
     // Test name match
@@ -61,3 +62,52 @@ fn test_agent_filter_named_severity() {
     assert_eq!(AgentFilter::parse("sev:medium").predicates[0], FilterPredicate::Severity(4));
     assert_eq!(AgentFilter::parse("sev:low").predicates[0], FilterPredicate::Severity(0));
 }
+
+#[test]
+fn test_agent_filter_parse_fleet_tokens() {
+    use crate::app::filter::Cmp;
+    let filter = AgentFilter::parse("g:Web ver:<4.8 ver:4.7 ka:>2h ka:<=30 id:50-10 id:00");
+    assert_eq!(filter.predicates, vec![
+        FilterPredicate::Group("web".to_string()),
+        FilterPredicate::Version(Cmp::Lt, (4, 8, 0)),
+        FilterPredicate::VersionPrefix("4.7".to_string()),
+        FilterPredicate::KeepAliveAge(Cmp::Gt, 120),
+        FilterPredicate::KeepAliveAge(Cmp::Le, 30),
+        FilterPredicate::IdRange(10, 50),
+        FilterPredicate::Id("00".to_string()),
+    ]);
+    // Unparseable values fall back to a plain text search
+    assert_eq!(AgentFilter::parse("ka:>soon").predicates[0], FilterPredicate::Global("ka:>soon".to_string()));
+}
+
+#[test]
+fn test_agent_filter_fleet_tokens_match() {
+    let agent = |id: &str, version: &str, group: &str, keepalive_mins: Option<i64>| WazuhAgent {
+        id: id.to_string(),
+        name: format!("agent-{}", id),
+        ip: None,
+        status: "active".to_string(),
+        version: Some(version.to_string()),
+        node_name: None,
+        group: Some(vec!["default".to_string(), group.to_string()]),
+        date_add: None,
+        last_keep_alive: keepalive_mins.map(|m| (chrono::Utc::now() - chrono::Duration::minutes(m)).to_rfc3339()),
+        os: None,
+        manager: None,
+        profile: None,
+    };
+    let fresh = agent("012", "Wazuh v4.7.2", "web", Some(1));
+    let stale = agent("120", "Wazuh v4.8.1", "db", Some(300));
+    let never = agent("121", "Wazuh v4.8.1", "db", None);
+
+    let hits = |q: &str| [&fresh, &stale, &never].iter().filter(|a| AgentFilter::parse(q).matches(a)).map(|a| a.id.as_str()).collect::<Vec<_>>();
+    assert_eq!(hits("g:web"), vec!["012"]);
+    assert_eq!(hits("group:DEFAULT"), vec!["012", "120", "121"]);
+    assert_eq!(hits("ver:<4.8"), vec!["012"]);
+    assert_eq!(hits("ver:>=4.8.1"), vec!["120", "121"]);
+    assert_eq!(hits("ver:4.7"), vec!["012"]);
+    assert_eq!(hits("ka:>2h"), vec!["120", "121"]);
+    assert_eq!(hits("ka:<10m"), vec!["012"]);
+    assert_eq!(hits("id:1-100"), vec!["012"]);
+    assert_eq!(hits("id:100-200 ka:>2h g:db"), vec!["120", "121"]);
+}
@@ -0,0 +1,71 @@
+use crate::models::{parse_timestamp, WazuhLastScan};
+use chrono::{DateTime, Utc};
+use std::time::{Duration, Instant};
+
+/// Seconds between `last_scan` polls while a requested scan has not finished.
+pub const FIM_POLL_SECS: u64 = 5;
+/// Polling stops after this long; the scan may still finish on the agent.
+pub const FIM_POLL_TIMEOUT_MINS: i64 = 30;
+/// Manager and local clocks may disagree by this much when matching a scan to a request.
+const CLOCK_SKEW_SECS: i64 = 30;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FimScanState {
+    /// The agent has not reported a scan start since the request yet
+    Requested,
+    Running { start: DateTime<Utc> },
+    Finished { start: DateTime<Utc>, end: DateTime<Utc> },
+    TimedOut,
+}
+
+/// An on-demand syscheck scan started from the TUI, tracked until the agent reports it finished.
+#[derive(Debug, Clone)]
+pub struct FimScan {
+    pub requested_at: DateTime<Utc>,
+    pub last_scan: Option<WazuhLastScan>,
+    pub next_poll: Instant,
+}
+
+impl FimScan {
+    pub fn new(requested_at: DateTime<Utc>) -> Self {
+        Self { requested_at, last_scan: None, next_poll: Instant::now() + Duration::from_secs(FIM_POLL_SECS) }
+    }
+
+    /// Only a scan that started after the request counts; an older `last_scan` is the previous run.
+    pub fn state(&self, now: DateTime<Utc>) -> FimScanState {
+        let threshold = self.requested_at - chrono::Duration::seconds(CLOCK_SKEW_SECS);
+        let scan = self.last_scan.as_ref();
+        let start = scan.and_then(|s| s.start.as_deref()).and_then(parse_timestamp).filter(|t| *t >= threshold);
+        let end = scan.and_then(|s| s.end.as_deref()).and_then(parse_timestamp);
+        let timed_out = now - self.requested_at > chrono::Duration::minutes(FIM_POLL_TIMEOUT_MINS);
+        match (start, end) {
+            (Some(start), Some(end)) if end >= start => FimScanState::Finished { start, end },
+            _ if timed_out => FimScanState::TimedOut,
+            (Some(start), _) => FimScanState::Running { start },
+            (None, _) => FimScanState::Requested,
+        }
+    }
+
+    pub fn is_pending(&self, now: DateTime<Utc>) -> bool {
+        matches!(self.state(now), FimScanState::Requested | FimScanState::Running { .. })
+    }
+
+    /// Short status for the inspector header and footer.
+    pub fn label(&self, now: DateTime<Utc>) -> String {
+        match self.state(now) {
+            FimScanState::Requested => "FIM scan requested".to_string(),
+            FimScanState::Running { start } => format!("FIM scan running for {}", format_secs((now - start).num_seconds())),
+            FimScanState::Finished { start, end } => format!("FIM scan finished in {}", format_secs((end - start).num_seconds())),
+            FimScanState::TimedOut => "FIM scan not reported after 30m".to_string(),
+        }
+    }
+}
+
+fn format_secs(secs: i64) -> String {
+    let secs = secs.max(0);
+    if secs < 60 {
+        format!("{}s", secs)
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
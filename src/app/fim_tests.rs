@@ -0,0 +1,36 @@
+use crate::app::fim::{FimScan, FimScanState};
+use crate::models::WazuhLastScan;
+use chrono::{Duration, TimeZone, Utc};
+
+fn scan(start: Option<&str>, end: Option<&str>) -> Option<WazuhLastScan> {
+    Some(WazuhLastScan { start: start.map(String::from), end: end.map(String::from) })
+}
+
+#[test]
+fn test_fim_scan_progress() {
+    let requested = Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap();
+    let now = requested + Duration::seconds(90);
+    let mut fim = FimScan::new(requested);
+    assert_eq!(fim.state(now), FimScanState::Requested);
+
+    // The previous scheduled run does not count
+    fim.last_scan = scan(Some("2024-05-01T08:00:00Z"), Some("2024-05-01T08:03:00Z"));
+    assert_eq!(fim.state(now), FimScanState::Requested);
+
+    fim.last_scan = scan(Some("2024-05-01T10:00:05Z"), Some("2024-05-01T08:03:00Z"));
+    assert!(matches!(fim.state(now), FimScanState::Running { .. }));
+    assert_eq!(fim.label(now), "FIM scan running for 1m 25s");
+    assert!(fim.is_pending(now));
+
+    fim.last_scan = scan(Some("2024-05-01T10:00:05Z"), Some("2024-05-01T10:00:47Z"));
+    assert_eq!(fim.label(now), "FIM scan finished in 42s");
+    assert!(!fim.is_pending(now));
+}
+
+#[test]
+fn test_fim_scan_times_out() {
+    let requested = Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap();
+    let fim = FimScan::new(requested);
+    assert_eq!(fim.state(requested + Duration::minutes(31)), FimScanState::TimedOut);
+    assert!(!fim.is_pending(requested + Duration::minutes(31)));
+}
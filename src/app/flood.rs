@@ -0,0 +1,50 @@
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// A row of the Security Events table: one alert, or the first alert of a flood standing in
+/// for the whole run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlertRow {
+    /// Index into `App::logs`
+    pub log: usize,
+    /// Alerts the row stands for; 1 unless it heads a collapsed flood
+    pub count: usize,
+    /// Part of an expanded flood, drawn indented below its head
+    pub member: bool,
+}
+
+/// Rule and agent of an alert; consecutive alerts with the same pair make a flood.
+pub fn flood_key(log: &Value) -> Option<(&str, &str)> {
+    let source = log.get("_source").unwrap_or(log);
+    let rule = source.get("rule").and_then(|r| r.get("id")).and_then(|i| i.as_str())?;
+    let agent = source.get("agent").and_then(|a| a.get("id")).and_then(|i| i.as_str()).unwrap_or("000");
+    Some((rule, agent))
+}
+
+/// Collapses runs of consecutive alerts from the same rule and agent into their first alert.
+/// Runs whose first alert id is in `expanded` keep the head row with its count and list every
+/// alert below it, so the selection can move through them.
+pub fn group_floods(logs: &[Value], expanded: &HashSet<String>) -> Vec<AlertRow> {
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while start < logs.len() {
+        let key = flood_key(&logs[start]);
+        let mut end = start + 1;
+        while key.is_some() && end < logs.len() && flood_key(&logs[end]) == key {
+            end += 1;
+        }
+        let count = end - start;
+        rows.push(AlertRow { log: start, count, member: false });
+        let open = count > 1 && crate::app::alert_id(&logs[start]).map(|id| expanded.contains(id)).unwrap_or(false);
+        if open {
+            rows.extend((start + 1..end).map(|log| AlertRow { log, count: 1, member: true }));
+        }
+        start = end;
+    }
+    rows
+}
+
+/// One row per alert, for when grouping is off or the raw view is shown.
+pub fn ungrouped(len: usize) -> Vec<AlertRow> {
+    (0..len).map(|log| AlertRow { log, count: 1, member: false }).collect()
+}
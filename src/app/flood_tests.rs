@@ -0,0 +1,73 @@
+use crate::app::flood::{group_floods, ungrouped, AlertRow};
+use crate::app::{ActiveView, App, LogViewMode};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+fn alert(id: &str, rule: &str, agent: &str) -> Value {
+    json!({ "_id": id, "_source": { "rule": { "id": rule }, "agent": { "id": agent } } })
+}
+
+fn storm() -> Vec<Value> {
+    vec![
+        alert("a", "5710", "001"),
+        alert("b", "5710", "001"),
+        alert("c", "5710", "001"),
+        alert("d", "5710", "002"),
+        alert("e", "5502", "002"),
+        alert("f", "5502", "002"),
+    ]
+}
+
+#[test]
+fn test_consecutive_rule_and_agent_collapse() {
+    let rows = group_floods(&storm(), &HashSet::new());
+    assert_eq!(rows, vec![
+        AlertRow { log: 0, count: 3, member: false },
+        AlertRow { log: 3, count: 1, member: false },
+        AlertRow { log: 4, count: 2, member: false },
+    ]);
+
+    // Only consecutive alerts fold; the same pair later on starts a new row
+    let mut logs = storm();
+    logs.push(alert("g", "5710", "001"));
+    assert_eq!(group_floods(&logs, &HashSet::new()).last(), Some(&AlertRow { log: 6, count: 1, member: false }));
+    assert_eq!(ungrouped(2).len(), 2);
+}
+
+#[test]
+fn test_expanded_flood_lists_its_alerts() {
+    let expanded: HashSet<String> = ["a".to_string()].into();
+    let rows = group_floods(&storm(), &expanded);
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0].count, 3);
+    assert!(rows[1].member && rows[2].member);
+    assert_eq!((rows[1].log, rows[2].log), (1, 2));
+}
+
+#[test]
+fn test_selection_follows_the_alert_across_grouping() {
+    let mut app = App::new();
+    app.active_view = ActiveView::SecurityEvents;
+    app.group_floods = false;
+    app.set_logs(storm());
+    app.table_state.select(Some(2));
+
+    app.toggle_flood_grouping();
+    assert_eq!(app.alert_rows.len(), 3);
+    assert_eq!(app.table_state.selected(), Some(0), "folded into its flood's head");
+
+    assert!(app.expand_selected_flood(true));
+    app.scroll_down(2);
+    assert_eq!(app.selected_alert().and_then(|l| l.get("_id")), Some(&json!("c")));
+    // Folding from a listed alert closes its flood and returns to the head
+    assert!(app.expand_selected_flood(false));
+    assert_eq!(app.table_state.selected(), Some(0));
+
+    app.table_state.select(Some(1));
+    assert!(!app.expand_selected_flood(true), "a single alert has nothing to list");
+
+    app.log_view_mode = LogViewMode::Raw;
+    app.regroup_alerts();
+    assert_eq!(app.alert_rows.len(), 6);
+    assert_eq!(app.table_state.selected(), Some(3));
+}
@@ -0,0 +1,30 @@
+use serde_json::Value;
+
+/// "City, Country" from the `GeoLocation` block Wazuh attaches to alerts with a
+/// public source IP. Falls back to whichever part is present.
+pub fn geo_label(source: &Value) -> Option<String> {
+    let geo = source.get("GeoLocation")?;
+    let country = geo.get("country_name").and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+    let city = geo.get("city_name").and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+    match (city, country) {
+        (Some(city), Some(country)) => Some(format!("{}, {}", city, country)),
+        (None, Some(country)) => Some(country.to_string()),
+        (Some(city), None) => Some(city.to_string()),
+        (None, None) => None,
+    }
+}
+
+/// Alert counts per source country, highest first, ties broken by name.
+pub fn top_countries(hits: &[Value], limit: usize) -> Vec<(String, u64)> {
+    let mut counts: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
+    for hit in hits {
+        let source = hit.get("_source").unwrap_or(hit);
+        if let Some(country) = source.get("GeoLocation").and_then(|g| g.get("country_name")).and_then(|v| v.as_str()) {
+            *counts.entry(country.to_string()).or_insert(0) += 1;
+        }
+    }
+    let mut top: Vec<(String, u64)> = counts.into_iter().collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top.truncate(limit);
+    top
+}
@@ -0,0 +1,33 @@
+use crate::app::geo::{geo_label, top_countries};
+use serde_json::json;
+
+fn hit(country: Option<&str>, city: Option<&str>) -> serde_json::Value {
+    let mut geo = serde_json::Map::new();
+    if let Some(c) = country {
+        geo.insert("country_name".to_string(), json!(c));
+    }
+    if let Some(c) = city {
+        geo.insert("city_name".to_string(), json!(c));
+    }
+    json!({ "_source": { "GeoLocation": geo, "data": { "srcip": "203.0.113.7" } } })
+}
+
+#[test]
+fn test_geo_label() {
+    assert_eq!(geo_label(&hit(Some("Germany"), Some("Berlin"))["_source"]), Some("Berlin, Germany".to_string()));
+    assert_eq!(geo_label(&hit(Some("Germany"), None)["_source"]), Some("Germany".to_string()));
+    assert_eq!(geo_label(&json!({ "data": {} })), None);
+}
+
+#[test]
+fn test_top_countries() {
+    let hits = vec![
+        hit(Some("Germany"), None),
+        hit(Some("Brazil"), None),
+        hit(Some("Germany"), Some("Berlin")),
+        hit(Some("China"), None),
+        json!({ "_source": { "rule": { "level": 3 } } }),
+    ];
+    let top = top_countries(&hits, 2);
+    assert_eq!(top, vec![("Germany".to_string(), 2), ("Brazil".to_string(), 1)]);
+}
@@ -0,0 +1,54 @@
+use crate::models::WazuhAgent;
+
+/// Scores an agent from 0 (unhealthy) to 100 (healthy) based on its connection
+/// state, keep-alive recency, recent alerts in the high band or above, known critical CVEs
+/// and whether it runs an older version than the manager.
+pub fn health_score(agent: &WazuhAgent, manager_version: Option<&str>, high_alerts: usize, critical_cves: usize) -> u8 {
+    let mut score: i64 = 100;
+
+    score -= match agent.status.as_str() {
+        "active" => 0,
+        "pending" => 20,
+        "disconnected" => 40,
+        _ => 50, // never_connected
+    };
+
+    match agent.last_keep_alive_time() {
+        Some(t) => {
+            let age = chrono::Utc::now() - t;
+            if age > chrono::Duration::hours(24) {
+                score -= 20;
+            } else if age > chrono::Duration::hours(1) {
+                score -= 10;
+            }
+        }
+        None => score -= 10,
+    }
+
+    score -= (high_alerts as i64 * 5).min(30);
+    score -= (critical_cves as i64 * 10).min(30);
+
+    if is_outdated(agent.version.as_deref(), manager_version) {
+        score -= 10;
+    }
+
+    score.clamp(0, 100) as u8
+}
+
+/// Parses versions as reported by Wazuh ("Wazuh v4.7.2", "v4.7.2", "4.7.2").
+pub fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let v = version.trim().trim_start_matches("Wazuh").trim().trim_start_matches('v');
+    let mut parts = v.split('.').map(|p| p.trim().parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+/// True when both versions are known and the agent's is older than the manager's.
+pub fn is_outdated(agent_version: Option<&str>, manager_version: Option<&str>) -> bool {
+    match (agent_version.and_then(parse_version), manager_version.and_then(parse_version)) {
+        (Some(agent), Some(manager)) => agent < manager,
+        _ => false,
+    }
+}
@@ -0,0 +1,86 @@
+use crate::app::health::{health_score, is_outdated, parse_version};
+use crate::models::WazuhAgent;
+
+fn agent(status: &str, version: Option<&str>, last_keep_alive: Option<String>) -> WazuhAgent {
+    WazuhAgent {
+        id: "001".to_string(),
+        name: "web-01".to_string(),
+        status: status.to_string(),
+        version: version.map(String::from),
+        last_keep_alive,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_version() {
+    assert_eq!(parse_version("Wazuh v4.7.2"), Some((4, 7, 2)));
+    assert_eq!(parse_version("v4.8"), Some((4, 8, 0)));
+    assert_eq!(parse_version("garbage"), None);
+    assert!(is_outdated(Some("Wazuh v4.5.0"), Some("Wazuh v4.7.2")));
+    assert!(!is_outdated(Some("Wazuh v4.7.2"), Some("Wazuh v4.7.2")));
+    assert!(!is_outdated(None, Some("Wazuh v4.7.2")));
+}
+
+#[test]
+fn test_health_score() {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let healthy = agent("active", Some("Wazuh v4.7.2"), Some(now.clone()));
+    assert_eq!(health_score(&healthy, Some("Wazuh v4.7.2"), 0, 0), 100);
+
+    // 2 high alerts (-10), 1 critical CVE (-10), outdated (-10)
+    let busy = agent("active", Some("Wazuh v4.5.0"), Some(now));
+    assert_eq!(health_score(&busy, Some("Wazuh v4.7.2"), 2, 1), 70);
+
+    // Penalties are capped and the score never goes below zero
+    let lost = agent("never_connected", None, None);
+    assert_eq!(health_score(&lost, None, 100, 100), 0);
+}
+
+#[test]
+fn test_oldest_keepalives_flags_stale_active_agents() {
+    let ago = |mins: i64| Some((chrono::Utc::now() - chrono::Duration::minutes(mins)).to_rfc3339());
+    let mut app = crate::app::App::new();
+    app.agents = vec![
+        WazuhAgent { id: "000".to_string(), ..agent("active", None, ago(500)) },
+        WazuhAgent { id: "001".to_string(), ..agent("active", None, ago(1)) },
+        WazuhAgent { id: "002".to_string(), ..agent("active", None, ago(30)) },
+        WazuhAgent { id: "003".to_string(), ..agent("disconnected", None, ago(90)) },
+    ];
+
+    let list: Vec<_> = app.oldest_keepalives().into_iter().map(|(a, stale)| (a.id.as_str(), stale)).collect();
+    assert_eq!(list, vec![("002", true), ("001", false)]);
+}
+
+#[test]
+fn test_health_scores_use_fleet_counts() {
+    let now = Some(chrono::Utc::now().to_rfc3339());
+    let mut app = crate::app::App::new();
+    app.agents = vec![agent("active", None, now)];
+    assert_eq!(app.health_scores()["001"], 100);
+    assert!(app.health_scores_partial());
+
+    // Alerts outside the loaded page and CVEs of an agent never inspected still count
+    app.agent_high_alerts = Some([("001".to_string(), 2)].into());
+    app.set_agent_vuln_counts([("001".to_string(), crate::models::AgentVulnCounts { critical: 1, ..Default::default() })].into());
+    assert_eq!(app.health_scores()["001"], 80);
+    assert!(!app.health_scores_partial());
+}
+
+#[test]
+fn test_recently_disconnected_follows_the_configured_window() {
+    let ago = |hours: i64| Some((chrono::Utc::now() - chrono::Duration::hours(hours)).to_rfc3339());
+    let mut app = crate::app::App::new();
+    app.agents = vec![
+        WazuhAgent { id: "001".to_string(), ..agent("disconnected", None, ago(2)) },
+        WazuhAgent { id: "002".to_string(), ..agent("disconnected", None, ago(48)) },
+    ];
+    let ids = |app: &crate::app::App| app.recently_disconnected().iter().map(|a| a.id.clone()).collect::<Vec<_>>();
+    assert_eq!(ids(&app), vec!["001"]);
+
+    let mut config = crate::models::Config::default();
+    config.maintenance.disconnected_window_hours = Some(72);
+    app.set_api(crate::api::WazuhApi::new(config));
+    assert_eq!(ids(&app), vec!["001", "002"]);
+}
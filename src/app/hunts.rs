@@ -0,0 +1,103 @@
+use serde_json::{json, Value};
+
+/// Prebuilt threat hunts: each buckets the matching alerts of the time interval by one field
+/// (an attacker address or an account) and lists the busiest buckets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hunt {
+    SshBruteForce,
+    WindowsFailedLogons,
+    NewSudoUsers,
+    WebErrorFlood,
+}
+
+pub const HUNTS: [Hunt; 4] = [Hunt::SshBruteForce, Hunt::WindowsFailedLogons, Hunt::NewSudoUsers, Hunt::WebErrorFlood];
+
+/// Buckets shown per hunt, busiest first.
+pub const HUNT_ROWS: u32 = 100;
+
+impl Hunt {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Hunt::SshBruteForce => "SSH brute force",
+            Hunt::WindowsFailedLogons => "Windows failed logons",
+            Hunt::NewSudoUsers => "New sudo users",
+            Hunt::WebErrorFlood => "Web 4xx floods",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Hunt::SshBruteForce => "Source IPs with 5+ failed sshd logins",
+            Hunt::WindowsFailedLogons => "Accounts with 3+ failed logons (event 4625)",
+            Hunt::NewSudoUsers => "Users running sudo for the first time (rule 5403)",
+            Hunt::WebErrorFlood => "Source IPs with 20+ HTTP 4xx responses",
+        }
+    }
+
+    /// Field the alerts are bucketed by, and its column heading.
+    pub fn key_field(&self) -> (&'static str, &'static str) {
+        match self {
+            Hunt::SshBruteForce => ("data.srcip", "Source IP"),
+            Hunt::WindowsFailedLogons => ("data.win.eventdata.targetUserName", "Account"),
+            Hunt::NewSudoUsers => ("data.srcuser", "User"),
+            Hunt::WebErrorFlood => ("data.srcip", "Source IP"),
+        }
+    }
+
+    /// Field whose most common values are listed with each bucket, and its column heading.
+    pub fn detail_field(&self) -> (&'static str, &'static str) {
+        match self {
+            Hunt::SshBruteForce => ("data.dstuser", "Users tried"),
+            Hunt::WindowsFailedLogons => ("data.win.eventdata.ipAddress", "From"),
+            Hunt::NewSudoUsers => ("data.command", "Commands"),
+            Hunt::WebErrorFlood => ("data.url", "URLs"),
+        }
+    }
+
+    /// Buckets with fewer alerts are left out.
+    pub fn min_count(&self) -> u64 {
+        match self {
+            Hunt::SshBruteForce => 5,
+            Hunt::WindowsFailedLogons => 3,
+            Hunt::NewSudoUsers => 1,
+            Hunt::WebErrorFlood => 20,
+        }
+    }
+
+    /// Whether the bucket key is an address the source IP pivot can follow.
+    pub fn keyed_by_srcip(&self) -> bool {
+        self.key_field().0 == "data.srcip"
+    }
+
+    /// Conditions selecting the hunt's alerts, going by the stock Wazuh ruleset.
+    pub fn filters(&self) -> Vec<Value> {
+        match self {
+            Hunt::SshBruteForce => vec![
+                json!({ "term": { "rule.groups": "sshd" } }),
+                json!({ "terms": { "rule.groups": ["authentication_failed", "authentication_failures", "invalid_login"] } }),
+            ],
+            Hunt::WindowsFailedLogons => vec![
+                json!({ "term": { "data.win.system.eventID": "4625" } }),
+            ],
+            Hunt::NewSudoUsers => vec![
+                json!({ "term": { "rule.id": "5403" } }),
+            ],
+            Hunt::WebErrorFlood => vec![
+                json!({ "term": { "rule.groups": "web" } }),
+                json!({ "prefix": { "data.id": "4" } }),
+            ],
+        }
+    }
+}
+
+/// One bucket of a hunt's results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HuntRow {
+    pub key: String,
+    pub count: u64,
+    /// Most affected agent names first
+    pub agents: Vec<String>,
+    /// Most common values of the hunt's detail field
+    pub details: Vec<String>,
+    pub last_seen: Option<String>,
+}
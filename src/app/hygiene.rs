@@ -0,0 +1,67 @@
+use crate::models::WazuhAgent;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DuplicateKind {
+    Name,
+    Ip,
+}
+
+impl DuplicateKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DuplicateKind::Name => "name",
+            DuplicateKind::Ip => "IP",
+        }
+    }
+}
+
+/// Agents sharing one name or address, usually an old registration left behind when a host
+/// was re-imaged and enrolled again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateSet {
+    pub kind: DuplicateKind,
+    /// The shared name (as the first agent spells it) or address
+    pub value: String,
+    /// In list order
+    pub agent_ids: Vec<String>,
+}
+
+/// Addresses many unrelated agents report, so sharing them says nothing about the host.
+fn ignored_ip(ip: &str) -> bool {
+    ip.is_empty() || ip.eq_ignore_ascii_case("any") || ip == "127.0.0.1" || ip == "::1"
+}
+
+/// Duplicate names (compared case-insensitively) and shared IPs among `agents`, names first,
+/// larger sets first within each kind. Agents listed from other profiles are left out: the same
+/// host enrolled with two managers is not a leftover.
+pub fn find_duplicates(agents: &[WazuhAgent]) -> Vec<DuplicateSet> {
+    let mut sets = Vec::new();
+    let mut collect = |kind: DuplicateKind, key: &dyn Fn(&WazuhAgent) -> Option<String>| {
+        let mut groups: HashMap<String, DuplicateSet> = HashMap::new();
+        let mut order = Vec::new();
+        for agent in agents.iter().filter(|a| a.profile.is_none()) {
+            let Some(k) = key(agent) else { continue };
+            let set = groups.entry(k.clone()).or_insert_with(|| {
+                order.push(k);
+                DuplicateSet { kind, value: String::new(), agent_ids: Vec::new() }
+            });
+            if set.value.is_empty() {
+                set.value = match kind {
+                    DuplicateKind::Name => agent.name.clone(),
+                    DuplicateKind::Ip => agent.ip.clone().unwrap_or_default(),
+                };
+            }
+            set.agent_ids.push(agent.id.clone());
+        }
+        let mut found: Vec<DuplicateSet> = order.into_iter()
+            .filter_map(|k| groups.remove(&k))
+            .filter(|s| s.agent_ids.len() > 1)
+            .collect();
+        found.sort_by_key(|s| std::cmp::Reverse(s.agent_ids.len()));
+        sets.extend(found);
+    };
+    collect(DuplicateKind::Name, &|a| Some(a.name.to_lowercase()).filter(|n| !n.is_empty()));
+    collect(DuplicateKind::Ip, &|a| a.ip.clone().filter(|ip| !ignored_ip(ip)));
+    sets
+}
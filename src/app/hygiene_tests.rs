@@ -0,0 +1,43 @@
+use crate::app::hygiene::{find_duplicates, DuplicateKind};
+use crate::models::WazuhAgent;
+
+fn agent(id: &str, name: &str, ip: Option<&str>) -> WazuhAgent {
+    WazuhAgent {
+        id: id.to_string(),
+        name: name.to_string(),
+        ip: ip.map(String::from),
+        status: "active".to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_find_duplicates() {
+    let mut remote = agent("009", "web-01", Some("10.0.0.5"));
+    remote.profile = Some("staging".to_string());
+    let agents = vec![
+        agent("000", "wazuh-manager", Some("127.0.0.1")),
+        agent("001", "web-01", Some("10.0.0.5")),
+        agent("002", "db-01", Some("10.0.0.6")),
+        agent("003", "WEB-01", Some("10.0.0.7")),
+        agent("004", "mail", Some("10.0.0.6")),
+        agent("005", "mail-2", Some("10.0.0.6")),
+        agent("006", "laptop", Some("any")),
+        agent("007", "laptop-2", Some("any")),
+        agent("008", "build", Some("127.0.0.1")),
+        remote,
+    ];
+
+    let sets = find_duplicates(&agents);
+    assert_eq!(sets.len(), 2);
+    // Names first, spelled as the first agent has it
+    assert_eq!(sets[0].kind, DuplicateKind::Name);
+    assert_eq!(sets[0].value, "web-01");
+    assert_eq!(sets[0].agent_ids, vec!["001", "003"]);
+    // Loopback, "any" and agents from other profiles never count as shared
+    assert_eq!(sets[1].kind, DuplicateKind::Ip);
+    assert_eq!(sets[1].value, "10.0.0.6");
+    assert_eq!(sets[1].agent_ids, vec!["002", "004", "005"]);
+
+    assert!(find_duplicates(&agents[..3]).is_empty());
+}
@@ -0,0 +1,138 @@
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// Expand/collapse state and cursor for the interactive JSON viewer.
+/// Nodes are addressed by path (`$`, `$.rule.level`, `$.rule.groups[0]`).
+#[derive(Debug, Default, Clone)]
+pub struct JsonTreeState {
+    pub collapsed: HashSet<String>,
+    pub cursor: usize,
+}
+
+/// One visible line of the tree.
+#[derive(Debug, Clone)]
+pub struct TreeRow<'a> {
+    pub path: String,
+    pub depth: usize,
+    pub key: Option<String>,
+    pub value: &'a Value,
+    pub collapsed: bool,
+}
+
+impl TreeRow<'_> {
+    pub fn is_container(&self) -> bool {
+        matches!(self.value, Value::Object(_) | Value::Array(_))
+    }
+}
+
+impl JsonTreeState {
+    pub fn toggle(&mut self, path: &str) {
+        if !self.collapsed.remove(path) {
+            self.collapsed.insert(path.to_string());
+        }
+    }
+
+    /// Expands every collapsed ancestor of `path` so it becomes visible.
+    pub fn reveal(&mut self, path: &str) {
+        self.collapsed.retain(|c| !is_ancestor(c, path));
+    }
+
+    /// Moves the cursor onto `path`, expanding its ancestors first.
+    pub fn focus(&mut self, root: &Value, path: &str) {
+        self.reveal(path);
+        if let Some(idx) = visible_rows(root, &self.collapsed).iter().position(|r| r.path == path) {
+            self.cursor = idx;
+        }
+    }
+}
+
+/// True if `ancestor` is a strict ancestor path of `path`.
+pub fn is_ancestor(ancestor: &str, path: &str) -> bool {
+    path.len() > ancestor.len()
+        && path.starts_with(ancestor)
+        && matches!(path.as_bytes()[ancestor.len()], b'.' | b'[')
+}
+
+/// Flattens the document into the rows currently visible given the collapsed set.
+pub fn visible_rows<'a>(root: &'a Value, collapsed: &HashSet<String>) -> Vec<TreeRow<'a>> {
+    let mut rows = Vec::new();
+    push_rows(root, "$".to_string(), None, 0, collapsed, &mut rows);
+    rows
+}
+
+fn push_rows<'a>(value: &'a Value, path: String, key: Option<String>, depth: usize, collapsed: &HashSet<String>, rows: &mut Vec<TreeRow<'a>>) {
+    let is_collapsed = collapsed.contains(&path);
+    rows.push(TreeRow { path: path.clone(), depth, key, value, collapsed: is_collapsed });
+    if is_collapsed {
+        return;
+    }
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map {
+                push_rows(v, format!("{}.{}", path, k), Some(k.clone()), depth + 1, collapsed, rows);
+            }
+        }
+        Value::Array(arr) => {
+            for (i, v) in arr.iter().enumerate() {
+                push_rows(v, format!("{}[{}]", path, i), Some(format!("[{}]", i)), depth + 1, collapsed, rows);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Paths of every node (visible or not) whose key or scalar value contains `query`, in document order.
+pub fn search_paths(root: &Value, query: &str) -> Vec<String> {
+    let mut matches = Vec::new();
+    if !query.is_empty() {
+        let all = visible_rows(root, &HashSet::new());
+        let query = query.to_lowercase();
+        for row in all {
+            let key_hit = row.key.as_ref().map(|k| k.to_lowercase().contains(&query)).unwrap_or(false);
+            let value_hit = !row.is_container() && scalar_text(row.value).to_lowercase().contains(&query);
+            if key_hit || value_hit {
+                matches.push(row.path);
+            }
+        }
+    }
+    matches
+}
+
+/// Display text for a scalar (strings without quotes).
+pub fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Dotted keys of the flattened field view in display order: nested objects are
+/// expanded, arrays stay a single entry.
+pub fn flat_fields<'a>(obj: &'a Map<String, Value>, prefix: &str) -> Vec<(String, &'a Value)> {
+    let mut fields = Vec::new();
+    for (k, v) in obj {
+        let key = if prefix.is_empty() { k.clone() } else { format!("{}.{}", prefix, k) };
+        match v {
+            Value::Object(inner) => fields.extend(flat_fields(inner, &key)),
+            _ => fields.push((key, v)),
+        }
+    }
+    fields
+}
+
+/// Indices into `flat_fields` whose key or scalar value contains `query` (case-insensitive).
+pub fn flat_field_matches(obj: &Map<String, Value>, query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query = query.to_lowercase();
+    flat_fields(obj, "")
+        .iter()
+        .enumerate()
+        .filter(|(_, (key, value))| {
+            key.to_lowercase().contains(&query)
+                || (!value.is_array() && scalar_text(value).to_lowercase().contains(&query))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
@@ -0,0 +1,79 @@
+use crate::app::json_tree::{flat_field_matches, flat_fields, is_ancestor, search_paths, visible_rows, JsonTreeState};
+use serde_json::json;
+
+fn sample() -> serde_json::Value {
+    json!({
+        "rule": { "level": 12, "groups": ["sysmon", "process"] },
+        "data": { "win": { "eventdata": { "image": "C:\\Windows\\powershell.exe" } } }
+    })
+}
+
+#[test]
+fn test_visible_rows_and_collapse() {
+    let doc = sample();
+    let mut state = JsonTreeState::default();
+
+    // $, data, win, eventdata, image, rule, groups, [0], [1], level
+    assert_eq!(visible_rows(&doc, &state.collapsed).len(), 10);
+
+    state.toggle("$.rule");
+    let rows = visible_rows(&doc, &state.collapsed);
+    assert_eq!(rows.len(), 6);
+    assert!(rows.iter().find(|r| r.path == "$.rule").unwrap().collapsed);
+
+    state.toggle("$.rule");
+    assert_eq!(visible_rows(&doc, &state.collapsed).len(), 10);
+}
+
+#[test]
+fn test_search_reveals_collapsed_match() {
+    let doc = sample();
+    let mut state = JsonTreeState::default();
+    state.toggle("$.data");
+
+    let matches = search_paths(&doc, "POWERSHELL");
+    assert_eq!(matches, vec!["$.data.win.eventdata.image".to_string()]);
+
+    state.focus(&doc, &matches[0]);
+    let rows = visible_rows(&doc, &state.collapsed);
+    assert_eq!(rows[state.cursor].path, "$.data.win.eventdata.image");
+}
+
+#[test]
+fn test_is_ancestor() {
+    assert!(is_ancestor("$.rule", "$.rule.level"));
+    assert!(is_ancestor("$.rule.groups", "$.rule.groups[1]"));
+    assert!(!is_ancestor("$.rule", "$.rules.level"));
+    assert!(!is_ancestor("$.rule", "$.rule"));
+}
+
+#[test]
+fn test_flat_field_matches() {
+    let doc = sample();
+    let obj = doc.as_object().unwrap();
+
+    let keys: Vec<String> = flat_fields(obj, "").into_iter().map(|(k, _)| k).collect();
+    assert_eq!(keys, vec!["data.win.eventdata.image", "rule.groups", "rule.level"]);
+
+    assert_eq!(flat_field_matches(obj, "Level"), vec![2]);
+    assert_eq!(flat_field_matches(obj, "powershell"), vec![0]);
+    // Array entries only match on their key
+    assert!(flat_field_matches(obj, "sysmon").is_empty());
+    assert!(flat_field_matches(obj, "").is_empty());
+}
+
+#[test]
+fn test_detail_document_reference() {
+    let mut app = crate::app::App::new();
+    assert_eq!(app.detail_document(), None);
+    app.selected_log = Some(json!({
+        "_index": "wazuh-alerts-4.x-2024.05.01",
+        "_id": "Xk3pR48BmTq1",
+        "_source": { "agent": { "name": "web-01" }, "rule": { "id": "5710" } }
+    }));
+    assert_eq!(app.detail_document(), Some(("wazuh-alerts-4.x-2024.05.01", "Xk3pR48BmTq1")));
+
+    // Field matches sit below the title and document lines
+    app.detail_search_query = "rule".to_string();
+    assert_eq!(app.detail_field_matches(), vec![1 + crate::app::LOG_DETAIL_HEADER_LINES]);
+}
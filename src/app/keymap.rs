@@ -0,0 +1,305 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::ActiveView;
+
+/// Where a key binding applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    Global,
+    Dashboard,
+    AgentList,
+    AgentInspector,
+    SecurityEvents,
+    EventDetail,
+    Groups,
+    Users,
+    CdbLists,
+    Rules,
+}
+
+impl KeyContext {
+    pub const ALL: [KeyContext; 10] = [
+        KeyContext::Global,
+        KeyContext::Dashboard,
+        KeyContext::AgentList,
+        KeyContext::AgentInspector,
+        KeyContext::SecurityEvents,
+        KeyContext::EventDetail,
+        KeyContext::Groups,
+        KeyContext::Users,
+        KeyContext::CdbLists,
+        KeyContext::Rules,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            KeyContext::Global => "GLOBAL KEYS",
+            KeyContext::Dashboard => "DASHBOARD",
+            KeyContext::AgentList => "AGENTS LIST",
+            KeyContext::AgentInspector => "AGENT INSPECTOR",
+            KeyContext::SecurityEvents => "SECURITY EVENTS",
+            KeyContext::EventDetail => "EVENT DETAIL",
+            KeyContext::Groups => "GROUPS",
+            KeyContext::Users => "USERS & ROLES",
+            KeyContext::CdbLists => "CDB LISTS",
+            KeyContext::Rules => "RULE & DECODER FILES",
+        }
+    }
+
+    pub fn of_view(view: &ActiveView) -> Self {
+        match view {
+            ActiveView::Dashboard => KeyContext::Dashboard,
+            ActiveView::AgentList => KeyContext::AgentList,
+            ActiveView::AgentInspector => KeyContext::AgentInspector,
+            ActiveView::SecurityEvents => KeyContext::SecurityEvents,
+            ActiveView::GroupManagement => KeyContext::Groups,
+            ActiveView::UserManagement => KeyContext::Users,
+            ActiveView::CdbLists => KeyContext::CdbLists,
+            ActiveView::Rules => KeyContext::Rules,
+        }
+    }
+}
+
+/// One line of the help: the keys, shown joined by `/`, and what they do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binding {
+    pub context: KeyContext,
+    pub keys: &'static [&'static str],
+    pub action: &'static str,
+}
+
+const fn bind(context: KeyContext, keys: &'static [&'static str], action: &'static str) -> Binding {
+    Binding { context, keys, action }
+}
+
+/// Every key binding, in help order; update it along with the key handling in `main.rs`.
+pub const BINDINGS: &[Binding] = &[
+    bind(KeyContext::Global, &["?"], "Toggle this help (type to search it)"),
+    bind(KeyContext::Global, &["Ctrl+p"], "Command palette"),
+    bind(KeyContext::Global, &["Tab"], "Switch view"),
+    bind(KeyContext::Global, &["q"], "Quit / Go back"),
+    bind(KeyContext::Global, &["Esc"], "Cancel / Close popup / Go back"),
+    bind(KeyContext::Global, &["Backspace"], "Back to the previous view"),
+    bind(KeyContext::Global, &["r"], "Refresh data"),
+    bind(KeyContext::Global, &["↑", "↓", "k", "j"], "Move the selection"),
+    bind(KeyContext::Global, &["PgUp", "PgDn"], "Scroll a page"),
+    bind(KeyContext::Global, &["/"], "Start search/filter"),
+    bind(KeyContext::Global, &["Alt+1-9"], "Save the agent search being typed / recall a saved one"),
+    bind(KeyContext::Global, &["i"], "Set time interval"),
+    bind(KeyContext::Global, &["+", "-"], "Adjust interval (+/- 15m)"),
+    bind(KeyContext::Global, &["T"], "Toggle relative timestamps"),
+    bind(KeyContext::Global, &["L"], "Activity log (restarts, upgrades, changes)"),
+    bind(KeyContext::Global, &["M"], "Notification history"),
+    bind(KeyContext::Global, &["B"], "Hunts (brute force, failed logons, ...)"),
+    bind(KeyContext::Global, &["Y"], "Copy the last export's path"),
+    bind(KeyContext::Global, &["u"], "Undo the last group change (for 10 s)"),
+    bind(KeyContext::Dashboard, &["J"], "Quick jump to agent"),
+    bind(KeyContext::Dashboard, &["1-4"], "Filter by severity (1=Critical, 4=Low)"),
+    bind(KeyContext::Dashboard, &["H"], "Alert trend: recent minutes / 7 days / 30 days"),
+    bind(KeyContext::Dashboard, &["d"], "Agents sharing a name or IP"),
+    bind(KeyContext::Dashboard, &["e"], "Write a report of the interval"),
+    bind(KeyContext::Dashboard, &["↑", "↓"], "Select recently disconnected agent"),
+    bind(KeyContext::Dashboard, &["Enter"], "Inspect selected disconnected agent"),
+    bind(KeyContext::AgentList, &["Enter"], "Inspect selected agent"),
+    bind(KeyContext::AgentList, &["Space"], "Toggle selection (multi-select)"),
+    bind(KeyContext::AgentList, &["s"], "Cycle sort column/order"),
+    bind(KeyContext::AgentList, &["/"], "Search by n: st: ip: os: sev: g: ver: ka: id: tag:"),
+    bind(KeyContext::AgentList, &["1-4"], "Only agents with Critical/High/Medium/Low CVEs"),
+    bind(KeyContext::AgentList, &["P"], "Preview pane of the highlighted agent"),
+    bind(KeyContext::AgentList, &["U"], "Upgrade selected agents"),
+    bind(KeyContext::AgentList, &["O"], "Show only agents older than the manager"),
+    bind(KeyContext::AgentList, &["A"], "Outdated only: select and upgrade all outdated agents"),
+    bind(KeyContext::AgentList, &["R"], "Restart selected agents"),
+    bind(KeyContext::AgentList, &["G"], "Assign to group"),
+    bind(KeyContext::AgentList, &["t"], "Tag selected agents locally (search with tag:)"),
+    bind(KeyContext::AgentList, &["F"], "Run a FIM (syscheck) scan on the selected agents"),
+    bind(KeyContext::AgentList, &["X"], "Run a whitelisted diagnostic command"),
+    bind(KeyContext::AgentList, &["h"], "SSH to agent (^E in the prompt: inside the TUI, ^] closes)"),
+    bind(KeyContext::AgentList, &["o"], "Open in browser"),
+    bind(KeyContext::AgentList, &["C"], "All profiles: merge the agents of every configured manager"),
+    bind(KeyContext::AgentInspector, &["Tab"], "Switch category tab"),
+    bind(KeyContext::AgentInspector, &["Enter"], "View log/process detail / Cycle config"),
+    bind(KeyContext::AgentInspector, &["s"], "Cycle process / vulnerability sort column/order"),
+    bind(KeyContext::AgentInspector, &["G"], "Edit group membership (Space toggles, Enter reviews)"),
+    bind(KeyContext::AgentInspector, &["1-4"], "Vulnerabilities: show one severity (again to clear)"),
+    bind(KeyContext::AgentInspector, &["v"], "Programs: show only packages with CVEs"),
+    bind(KeyContext::AgentInspector, &["S"], "Rootcheck: run a scan on the agent now"),
+    bind(KeyContext::AgentInspector, &["d"], "Config: compare with the groups' agent.conf"),
+    bind(KeyContext::AgentInspector, &["F"], "Run a FIM (syscheck) scan on the agent now"),
+    bind(KeyContext::AgentInspector, &["X"], "Run a whitelisted diagnostic command on the agent"),
+    bind(KeyContext::AgentInspector, &["R"], "Restart the agent"),
+    bind(KeyContext::AgentInspector, &["f"], "Filter logs by severity"),
+    bind(KeyContext::AgentInspector, &["e"], "Export logs / CVE list; Config: push the configuration"),
+    bind(KeyContext::AgentInspector, &["E"], "Cycle export format"),
+    bind(KeyContext::AgentInspector, &["Z"], "Compress alert exports: none, gzip, zip"),
+    bind(KeyContext::AgentInspector, &["W"], "Logs: export every matching alert (again to cancel)"),
+    bind(KeyContext::AgentInspector, &["x"], "Logs: fields kept in JSON exports"),
+    bind(KeyContext::AgentInspector, &["h"], "SSH to agent (^E in the prompt: inside the TUI, ^] closes)"),
+    bind(KeyContext::SecurityEvents, &["Enter"], "View event detail"),
+    bind(KeyContext::SecurityEvents, &["f"], "Filter by severity"),
+    bind(KeyContext::SecurityEvents, &["s"], "Sort by time, level, agent or rule (desc, then asc)"),
+    bind(KeyContext::SecurityEvents, &["1-4"], "Toggle Critical/High/Medium/Low chips (combine)"),
+    bind(KeyContext::SecurityEvents, &["g"], "Group repeated rule+agent alerts into ×N rows"),
+    bind(KeyContext::SecurityEvents, &["→", "←"], "Unfold / fold a grouped flood"),
+    bind(KeyContext::SecurityEvents, &["v"], "Switch between the table and raw JSON"),
+    bind(KeyContext::SecurityEvents, &["w"], "Raw: wrap long lines"),
+    bind(KeyContext::SecurityEvents, &["h", "l"], "Raw: scroll sideways"),
+    bind(KeyContext::SecurityEvents, &["e"], "Export (JSON/CEF/LEEF/Syslog), selection only if any"),
+    bind(KeyContext::SecurityEvents, &["x"], "Fields kept in JSON exports"),
+    bind(KeyContext::SecurityEvents, &["W"], "Export every matching alert (again to cancel)"),
+    bind(KeyContext::SecurityEvents, &["Space"], "Select alert"),
+    bind(KeyContext::SecurityEvents, &["a"], "Acknowledge the selected alerts"),
+    bind(KeyContext::SecurityEvents, &["y"], "Copy the selected alerts' IDs"),
+    bind(KeyContext::SecurityEvents, &["E"], "Cycle export format"),
+    bind(KeyContext::SecurityEvents, &["Z"], "Compress alert exports: none, gzip, zip"),
+    bind(KeyContext::SecurityEvents, &["PgUp", "PgDn"], "Previous / next page"),
+    bind(KeyContext::EventDetail, &["k", "j"], "Scroll"),
+    bind(KeyContext::EventDetail, &["/"], "Search the fields"),
+    bind(KeyContext::EventDetail, &["n", "N"], "Next / previous match"),
+    bind(KeyContext::EventDetail, &["c"], "Alerts around this one on the agent"),
+    bind(KeyContext::EventDetail, &["p"], "Pivot on the source IP"),
+    bind(KeyContext::EventDetail, &["o"], "Open in Discover"),
+    bind(KeyContext::EventDetail, &["y"], "Copy the document ID"),
+    bind(KeyContext::EventDetail, &["u"], "Copy a GET request for the document"),
+    bind(KeyContext::Groups, &["Enter"], "View agents and shared files in group"),
+    bind(KeyContext::Groups, &["→", "←"], "Focus / leave the file list (Enter views a file)"),
+    bind(KeyContext::Groups, &["P"], "Posture of the group's agents"),
+    bind(KeyContext::Users, &["n"], "Create API user"),
+    bind(KeyContext::Users, &["a"], "Assign / remove roles"),
+    bind(KeyContext::Users, &["D"], "Delete selected user"),
+    bind(KeyContext::CdbLists, &["→", "←"], "Focus entries / back to lists"),
+    bind(KeyContext::CdbLists, &["n"], "Append key[:value] to the selected list"),
+    bind(KeyContext::CdbLists, &["D"], "Remove the selected entry"),
+    bind(KeyContext::Rules, &["Enter"], "Edit in $EDITOR, then check and upload"),
+    bind(KeyContext::Rules, &["n"], "New file from a template"),
+    bind(KeyContext::Rules, &["v"], "Switch between rule and decoder files"),
+    bind(KeyContext::Rules, &["D"], "Delete selected file"),
+    bind(KeyContext::Rules, &["R"], "Restart the manager to load the ruleset"),
+];
+
+/// A key with its modifiers; Shift is left out of characters since it is part of the character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// Accepts a character or key name with optional `Ctrl+`/`Alt+`/`Shift+` prefixes,
+    /// e.g. `r`, `F5`, `PgDn`, `Ctrl+r` or `Alt+Enter`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let (prefix, name) = match s.rfind('+') {
+            Some(i) if i + 1 < s.len() => (&s[..i], &s[i + 1..]),
+            _ => ("", s),
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for m in prefix.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match m.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+        }
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => match c {
+                '↑' => KeyCode::Up,
+                '↓' => KeyCode::Down,
+                '←' => KeyCode::Left,
+                '→' => KeyCode::Right,
+                c => KeyCode::Char(c),
+            },
+            _ => match name.to_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                f => KeyCode::F(f.strip_prefix('f')?.parse().ok().filter(|n| (1..=24).contains(n))?),
+            },
+        };
+        Some(Key::from_event(&KeyEvent::new(code, modifiers)))
+    }
+
+    pub fn from_event(key: &KeyEvent) -> Self {
+        let mut modifiers = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        if matches!(key.code, KeyCode::Char(_)) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Key { code: key.code, modifiers }
+    }
+}
+
+/// Extra keys from the `[keys]` config section, each standing in for a built-in one.
+#[derive(Debug, Clone, Default)]
+pub struct KeyMap {
+    /// (alias as written in the config, alias, built-in key it presses)
+    aliases: Vec<(String, Key, Key)>,
+}
+
+impl KeyMap {
+    /// `[keys]` entries such as `"F5" = "r"`; entries either side of which is not a key are skipped.
+    pub fn from_config(config: &crate::models::Config) -> Self {
+        let mut aliases: Vec<(String, Key, Key)> = config.keys.iter()
+            .filter_map(|(alias, target)| Some((alias.trim().to_string(), Key::parse(alias)?, Key::parse(target)?)))
+            .collect();
+        aliases.sort_by(|a, b| a.0.cmp(&b.0));
+        KeyMap { aliases }
+    }
+
+    /// The built-in key an alias stands for, or the key itself.
+    pub fn translate(&self, key: KeyEvent) -> KeyEvent {
+        let pressed = Key::from_event(&key);
+        match self.aliases.iter().find(|(_, alias, _)| *alias == pressed) {
+            Some((_, _, target)) => KeyEvent::new(target.code, target.modifiers),
+            None => key,
+        }
+    }
+
+    /// Keys of a binding as shown in the help, followed by the aliases configured for them.
+    pub fn keys_label(&self, binding: &Binding) -> String {
+        let mut label = binding.keys.join("/");
+        let targets: Vec<Key> = binding.keys.iter().filter_map(|k| Key::parse(k)).collect();
+        for (name, _, target) in &self.aliases {
+            if targets.contains(target) {
+                label.push_str(", ");
+                label.push_str(name);
+            }
+        }
+        label
+    }
+
+    /// Help sections: the bindings of `contexts`, or with a query every binding whose keys,
+    /// action or section matches it.
+    pub fn help_sections(&self, contexts: &[KeyContext], query: &str) -> Vec<(KeyContext, Vec<(String, &'static str)>)> {
+        let query = query.trim().to_lowercase();
+        KeyContext::ALL.iter()
+            .filter(|context| !query.is_empty() || contexts.contains(context))
+            .map(|context| {
+                let rows = BINDINGS.iter()
+                    .filter(|b| b.context == *context)
+                    .map(|b| (self.keys_label(b), b.action))
+                    .filter(|(keys, action)| query.is_empty()
+                        || keys.to_lowercase().contains(&query)
+                        || action.to_lowercase().contains(&query)
+                        || context.title().to_lowercase().contains(&query))
+                    .collect::<Vec<_>>();
+                (*context, rows)
+            })
+            .filter(|(_, rows)| !rows.is_empty())
+            .collect()
+    }
+}
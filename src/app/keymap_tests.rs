@@ -0,0 +1,65 @@
+use crate::app::keymap::{Key, KeyContext, KeyMap, BINDINGS};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+fn keymap() -> KeyMap {
+    let config: crate::models::Config = toml::from_str(r#"
+        url = "https://hq:55000"
+        username = "wazuh"
+        password = "secret"
+
+        [keys]
+        "F5" = "r"
+        "Ctrl+n" = "Down"
+        "x" = "not a key"
+    "#).unwrap();
+    KeyMap::from_config(&config)
+}
+
+#[test]
+fn test_key_parse() {
+    assert_eq!(Key::parse("r"), Some(Key { code: KeyCode::Char('r'), modifiers: KeyModifiers::NONE }));
+    assert_eq!(Key::parse("+"), Some(Key { code: KeyCode::Char('+'), modifiers: KeyModifiers::NONE }));
+    assert_eq!(Key::parse("Ctrl+p"), Some(Key { code: KeyCode::Char('p'), modifiers: KeyModifiers::CONTROL }));
+    assert_eq!(Key::parse("F5"), Some(Key { code: KeyCode::F(5), modifiers: KeyModifiers::NONE }));
+    assert_eq!(Key::parse("pgdn"), Key::parse("PageDown"));
+    assert_eq!(Key::parse("↑"), Key::parse("Up"));
+    assert_eq!(Key::parse("Alt+1-9"), None);
+    assert_eq!(Key::parse("Hyper+x"), None);
+    // Shift is part of an upper case character
+    assert_eq!(Key::from_event(&KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT)), Key::parse("R").unwrap());
+}
+
+#[test]
+fn test_keymap_aliases() {
+    let keymap = keymap();
+    let refresh = keymap.translate(KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE));
+    assert_eq!((refresh.code, refresh.modifiers), (KeyCode::Char('r'), KeyModifiers::NONE));
+    let down = keymap.translate(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+    assert_eq!(down.code, KeyCode::Down);
+    // Keys without an alias, and the invalid entry, pass through
+    assert_eq!(keymap.translate(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)).code, KeyCode::Char('x'));
+
+    let refresh = BINDINGS.iter().find(|b| b.context == KeyContext::Global && b.keys == ["r"]).unwrap();
+    assert_eq!(keymap.keys_label(refresh), "r, F5");
+}
+
+#[test]
+fn test_help_sections() {
+    let keymap = KeyMap::default();
+    let sections = keymap.help_sections(&[KeyContext::Global, KeyContext::Rules], "");
+    let contexts: Vec<KeyContext> = sections.iter().map(|(c, _)| *c).collect();
+    assert_eq!(contexts, vec![KeyContext::Global, KeyContext::Rules]);
+
+    // A search covers every view
+    let sections = keymap.help_sections(&[KeyContext::Global], "discover");
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].0, KeyContext::EventDetail);
+    assert_eq!(sections[0].1, vec![("o".to_string(), "Open in Discover")]);
+
+    // Matching a section title lists the whole section
+    let sections = keymap.help_sections(&[], "users & roles");
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].1.len(), 3);
+
+    assert!(keymap.help_sections(&[], "no such key").is_empty());
+}
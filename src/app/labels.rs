@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Free-form agent tags ("pending-decommission", "patch-wave-2") kept only on this machine,
+/// in `labels.toml` next to `config.toml`. Wazuh groups are not touched.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentLabels {
+    /// Sorted tags keyed by agent ID
+    pub agents: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelEdit {
+    Add(String),
+    Remove(String),
+}
+
+/// Lowercases and joins inner whitespace with `-` so a tag stays one search token.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.split_whitespace().collect::<Vec<_>>().join("-").to_lowercase();
+    Some(tag).filter(|t| !t.is_empty())
+}
+
+/// Comma-separated tags; a leading `-` removes the tag instead: `patch wave 2, -pending`.
+pub fn parse_edits(input: &str) -> Vec<LabelEdit> {
+    input.split(',').filter_map(|part| {
+        let part = part.trim();
+        match part.strip_prefix('-') {
+            Some(tag) => normalize_tag(tag).map(LabelEdit::Remove),
+            None => normalize_tag(part).map(LabelEdit::Add),
+        }
+    }).collect()
+}
+
+impl AgentLabels {
+    pub fn path() -> PathBuf {
+        crate::config::ConfigManager::get_config_path().with_file_name("labels.toml")
+    }
+
+    /// A missing or unreadable file means no tags yet.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn tags(&self, agent_id: &str) -> &[String] {
+        self.agents.get(agent_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Every tag in use, sorted, for the tagging popup.
+    pub fn all_tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self.agents.values().flatten().map(String::as_str).collect();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    /// Applies the edits to every agent; returns how many agents changed.
+    pub fn apply(&mut self, agent_ids: &[String], edits: &[LabelEdit]) -> usize {
+        let mut changed = 0;
+        for id in agent_ids {
+            let tags = self.agents.entry(id.clone()).or_default();
+            let before = tags.clone();
+            for edit in edits {
+                match edit {
+                    LabelEdit::Add(tag) if !tags.contains(tag) => tags.push(tag.clone()),
+                    LabelEdit::Remove(tag) => tags.retain(|t| t != tag),
+                    _ => {}
+                }
+            }
+            tags.sort();
+            if *tags != before {
+                changed += 1;
+            }
+            if tags.is_empty() {
+                self.agents.remove(id);
+            }
+        }
+        changed
+    }
+}
@@ -0,0 +1,52 @@
+use crate::app::labels::{parse_edits, AgentLabels, LabelEdit};
+use crate::app::filter::AgentFilter;
+use crate::models::WazuhAgent;
+
+#[test]
+fn test_parse_label_edits() {
+    assert_eq!(parse_edits(" Patch Wave 2, -pending-decommission ,, - "), vec![
+        LabelEdit::Add("patch-wave-2".to_string()),
+        LabelEdit::Remove("pending-decommission".to_string()),
+    ]);
+}
+
+#[test]
+fn test_agent_labels_bulk_apply_and_round_trip() {
+    let mut labels = AgentLabels::default();
+    let ids = vec!["001".to_string(), "002".to_string()];
+    assert_eq!(labels.apply(&ids, &parse_edits("wave-2, decom")), 2);
+    assert_eq!(labels.tags("001"), ["decom".to_string(), "wave-2".to_string()]);
+    // Adding a tag the agent already has is not a change
+    assert_eq!(labels.apply(&ids[..1], &parse_edits("wave-2")), 0);
+
+    assert_eq!(labels.apply(&ids[..1], &parse_edits("-decom, -wave-2")), 1);
+    assert!(labels.tags("001").is_empty());
+    assert!(!labels.agents.contains_key("001"));
+    assert_eq!(labels.all_tags(), vec!["decom", "wave-2"]);
+
+    let saved = toml::to_string_pretty(&labels).unwrap();
+    assert_eq!(toml::from_str::<AgentLabels>(&saved).unwrap(), labels);
+}
+
+#[test]
+fn test_agent_filter_tag_token() {
+    let agent = WazuhAgent {
+        id: "001".to_string(),
+        name: "web-01".to_string(),
+        ip: None,
+        status: "active".to_string(),
+        version: None,
+        node_name: None,
+        group: None,
+        date_add: None,
+        last_keep_alive: None,
+        os: None,
+        manager: None,
+        profile: None,
+    };
+    let tags = vec!["patch-wave-2".to_string()];
+    assert!(AgentFilter::parse("tag:WAVE").matches_with_tags(&agent, &tags));
+    assert!(AgentFilter::parse("t:wave-2 n:web").matches_with_tags(&agent, &tags));
+    assert!(!AgentFilter::parse("tag:decom").matches_with_tags(&agent, &tags));
+    assert!(!AgentFilter::parse("tag:wave").matches(&agent));
+}
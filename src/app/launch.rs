@@ -0,0 +1,127 @@
+/// Desktop platform, picked at runtime to choose the browser and terminal launchers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Platform {
+    Linux,
+    MacOs,
+    Windows,
+}
+
+impl Platform {
+    pub fn current() -> Self {
+        match std::env::consts::OS {
+            "macos" => Platform::MacOs,
+            "windows" => Platform::Windows,
+            _ => Platform::Linux,
+        }
+    }
+}
+
+/// A program and its arguments, tried in order until one starts.
+pub type Launcher = (String, Vec<String>);
+
+fn launcher(program: &str, args: &[&str]) -> Launcher {
+    (program.to_string(), args.iter().map(|a| a.to_string()).collect())
+}
+
+/// Opens `url` in the default browser.
+pub fn browser_command(platform: Platform, url: &str) -> Launcher {
+    match platform {
+        Platform::Linux => launcher("xdg-open", &[url]),
+        Platform::MacOs => launcher("open", &[url]),
+        // `cmd /C start` would split the URL at `&`; the URL handler takes it verbatim
+        Platform::Windows => launcher("rundll32", &["url.dll,FileProtocolHandler", url]),
+    }
+}
+
+/// Minutes either side of an alert in its Discover link.
+pub const DISCOVER_WINDOW_MINUTES: i64 = 5;
+
+/// Wazuh Dashboard Discover link showing one alert: the alerts index pattern queried for the
+/// document `_id`, around its timestamp. The dashboard is assumed on the API host's default
+/// HTTPS port, as for the agent page behind `o`.
+pub fn discover_url(api_url: &str, index_pattern: &str, log: &serde_json::Value) -> Option<String> {
+    let id = log.get("_id").and_then(|v| v.as_str())?;
+    let source = log.get("_source").unwrap_or(log);
+    let time = source.get("@timestamp").or_else(|| source.get("timestamp"))
+        .and_then(|v| v.as_str())
+        .and_then(crate::models::parse_timestamp)?;
+    let window = chrono::Duration::minutes(DISCOVER_WINDOW_MINUTES);
+    let stamp = |t: chrono::DateTime<chrono::Utc>| t.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+
+    let api = reqwest::Url::parse(api_url).ok()?;
+    let mut url = reqwest::Url::parse(&format!("{}://{}/app/data-explorer/discover", api.scheme(), api.host_str()?)).ok()?;
+    // Rison state; `!` and `'` are escaped with `!` inside its strings
+    let rison = |s: &str| s.replace('!', "!!").replace('\'', "!'");
+    let query = rison(&format!("_id:\"{}\"", id));
+    url.set_fragment(Some(&format!(
+        "?_a=(discover:(columns:!(_source),sort:!()),metadata:(indexPattern:'{}',view:discover))\
+         &_g=(filters:!(),refreshInterval:(pause:!t,value:0),time:(from:'{}',to:'{}'))\
+         &_q=(filters:!(),query:(language:kuery,query:'{}'))",
+        rison(index_pattern), stamp(time - window), stamp(time + window), query,
+    )));
+    Some(url.to_string())
+}
+
+/// Quotes `s` as an AppleScript string literal.
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Terminal emulators that can run `command` in a new window, most preferred first.
+/// On macOS iTerm is only offered when `iterm` says it is installed.
+pub fn terminal_commands(platform: Platform, command: &str, iterm: bool) -> Vec<Launcher> {
+    let keep_open = format!("{}; exec bash", command);
+    match platform {
+        Platform::Linux => vec![
+            launcher("xdg-terminal", &[command]),
+            launcher("gnome-terminal", &["--", "bash", "-c", &keep_open]),
+            launcher("konsole", &["-e", command]),
+            launcher("wezterm", &["start", "--", "bash", "-c", &keep_open]),
+            launcher("alacritty", &["-e", "bash", "-c", &keep_open]),
+            launcher("kitty", &["bash", "-c", &keep_open]),
+            launcher("foot", &["bash", "-c", &keep_open]),
+            launcher("xterm", &["-e", command]),
+        ],
+        Platform::MacOs => {
+            let script = applescript_string(command);
+            let mut launchers = Vec::new();
+            if iterm {
+                launchers.push(launcher("osascript", &[
+                    "-e", &format!("tell application \"iTerm\" to create window with default profile command {}", script),
+                ]));
+            }
+            launchers.push(launcher("osascript", &[
+                "-e", &format!("tell application \"Terminal\" to do script {}", script),
+                "-e", "tell application \"Terminal\" to activate",
+            ]));
+            launchers
+        }
+        // A console child would share the TUI's console, so each one gets a new window
+        Platform::Windows => {
+            let args: Vec<&str> = command.split_whitespace().collect();
+            let mut wt = vec!["new-tab"];
+            wt.extend(&args);
+            // The empty argument is `start`'s window title
+            let mut start = vec!["/C", "start", "", "powershell", "-NoExit", "-Command"];
+            start.extend(&args);
+            vec![launcher("wt", &wt), launcher("cmd", &start)]
+        }
+    }
+}
+
+/// iTerm is used over Terminal.app when running inside it or when it is installed.
+pub fn iterm_installed() -> bool {
+    std::env::var("TERM_PROGRAM").map(|t| t == "iTerm.app").unwrap_or(false)
+        || std::path::Path::new("/Applications/iTerm.app").exists()
+}
+
+/// Label for notifications: the program, or the app an `osascript` launcher drives.
+pub fn launcher_name(launcher: &Launcher) -> String {
+    let (program, args) = launcher;
+    if program == "osascript" {
+        if let Some(app) = args.get(1).and_then(|a| a.split('"').nth(1)) {
+            return app.to_string();
+        }
+    }
+    program.clone()
+}
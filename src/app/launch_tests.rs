@@ -0,0 +1,47 @@
+use crate::app::launch::{browser_command, discover_url, launcher_name, terminal_commands, Platform};
+
+#[test]
+fn test_browser_command_per_platform() {
+    let url = "https://wazuh.local/app/endpoints-summary#/agents?tab=welcome&agent=001";
+    assert_eq!(browser_command(Platform::Linux, url), ("xdg-open".to_string(), vec![url.to_string()]));
+    assert_eq!(browser_command(Platform::MacOs, url), ("open".to_string(), vec![url.to_string()]));
+    let (program, args) = browser_command(Platform::Windows, url);
+    assert_eq!(program, "rundll32");
+    assert_eq!(args.last().map(String::as_str), Some(url));
+}
+
+#[test]
+fn test_terminal_commands_per_platform() {
+    let linux = terminal_commands(Platform::Linux, "ssh root@10.0.0.5", false);
+    assert_eq!(linux[1].0, "gnome-terminal");
+    assert_eq!(linux[1].1.last().map(String::as_str), Some("ssh root@10.0.0.5; exec bash"));
+
+    let mac = terminal_commands(Platform::MacOs, "ssh root@10.0.0.5", true);
+    assert_eq!(mac.iter().map(launcher_name).collect::<Vec<_>>(), vec!["iTerm", "Terminal"]);
+    assert!(mac[0].1[1].ends_with("command \"ssh root@10.0.0.5\""));
+    assert_eq!(terminal_commands(Platform::MacOs, "ssh root@10.0.0.5", false).len(), 1);
+
+    let windows = terminal_commands(Platform::Windows, "ssh root@10.0.0.5", false);
+    assert_eq!(windows[0], ("wt".to_string(), vec!["new-tab".to_string(), "ssh".to_string(), "root@10.0.0.5".to_string()]));
+    assert_eq!(launcher_name(&windows[1]), "cmd");
+    assert!(windows[1].1.contains(&"powershell".to_string()));
+}
+
+#[test]
+fn test_discover_url_for_alert() {
+    let log = serde_json::json!({
+        "_index": "wazuh-alerts-4.x-2024.05.01",
+        "_id": "Xk3pR48BmTq1",
+        "_source": { "@timestamp": "2024-05-01T10:00:00.000Z" }
+    });
+    let url = discover_url("https://wazuh.local:55000", "wazuh-alerts-*", &log).unwrap();
+    assert!(url.starts_with("https://wazuh.local/app/data-explorer/discover#?_a="));
+    assert!(url.contains("indexPattern:'wazuh-alerts-*'"));
+    assert!(url.contains("time:(from:'2024-05-01T09:55:00.000Z',to:'2024-05-01T10:05:00.000Z')"));
+    assert!(url.contains("query:'_id:%22Xk3pR48BmTq1%22'"));
+
+    let custom = discover_url("https://wazuh.local:55000", "site-a:alerts-*", &log).unwrap();
+    assert!(custom.contains("indexPattern:'site-a:alerts-*'"));
+
+    assert_eq!(discover_url("https://wazuh.local:55000", "wazuh-alerts-*", &serde_json::json!({ "_source": {} })), None);
+}
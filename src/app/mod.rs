@@ -1,14 +1,114 @@
+pub mod actions;
+pub mod audit;
+pub mod bulk;
+pub mod bundle;
+pub mod cdb;
+pub mod certs;
+pub mod config_diff;
+pub mod conn;
+pub mod export;
+pub mod fim;
 pub mod filter;
+pub mod flood;
+pub mod geo;
+pub mod health;
+pub mod hunts;
+pub mod hygiene;
+pub mod json_tree;
+pub mod keymap;
+pub mod labels;
+pub mod launch;
+pub mod nav;
+pub mod nodes;
+pub mod pending;
+pub mod pty;
+pub mod relogin;
+pub mod remote_cmd;
+pub mod report;
+pub mod rules;
+pub mod severity;
+pub mod state;
+pub mod syscheck;
+pub mod term;
+pub mod time;
+pub mod tour;
+pub mod undo;
+pub mod wodles;
 
+#[cfg(test)]
+mod actions_tests;
+#[cfg(test)]
+mod audit_tests;
+#[cfg(test)]
+mod bulk_tests;
+#[cfg(test)]
+mod bundle_tests;
+#[cfg(test)]
+mod cdb_tests;
+#[cfg(test)]
+mod certs_tests;
+#[cfg(test)]
+mod config_diff_tests;
+#[cfg(test)]
+mod conn_tests;
+#[cfg(test)]
+mod export_tests;
+#[cfg(test)]
+mod fim_tests;
 #[cfg(test)]
 mod filter_tests;
+#[cfg(test)]
+mod flood_tests;
+#[cfg(test)]
+mod geo_tests;
+#[cfg(test)]
+mod health_tests;
+#[cfg(test)]
+mod hygiene_tests;
+#[cfg(test)]
+mod json_tree_tests;
+#[cfg(test)]
+mod keymap_tests;
+#[cfg(test)]
+mod labels_tests;
+#[cfg(test)]
+mod launch_tests;
+#[cfg(test)]
+mod nav_tests;
+#[cfg(test)]
+mod nodes_tests;
+#[cfg(test)]
+mod pending_tests;
+#[cfg(test)]
+mod relogin_tests;
+#[cfg(test)]
+mod remote_cmd_tests;
+#[cfg(test)]
+mod report_tests;
+#[cfg(test)]
+mod rules_tests;
+#[cfg(test)]
+mod severity_tests;
+#[cfg(test)]
+mod state_tests;
+#[cfg(test)]
+mod syscheck_tests;
+#[cfg(test)]
+mod term_tests;
+#[cfg(test)]
+mod time_tests;
+#[cfg(test)]
+mod tour_tests;
+#[cfg(test)]
+mod undo_tests;
+#[cfg(test)]
+mod wodles_tests;
 
 use crate::models::{WazuhAgent, WazuhGroup, WazuhHardwareItem, WazuhProcessItem, WazuhProgramItem};
 use crate::api::WazuhApi;
 use crate::app::filter::AgentFilter;
+use serde::{Deserialize, Serialize};
 use std::time::Instant;
-use std::fs::File;
-use std::io::Write;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum NotificationLevel {
@@ -23,8 +123,18 @@ pub struct Notification {
     pub message: String,
     pub level: NotificationLevel,
     pub timestamp: Instant,
+    /// Wall-clock time shown in the notification history
+    pub time: chrono::DateTime<chrono::Utc>,
+    /// How long the toast stays up
+    pub secs: u64,
 }
 
+/// Seconds a notification toast stays up unless it says otherwise.
+pub const NOTIFICATION_SECS: u64 = 5;
+
+/// Notifications kept for the history popup after their toast has expired.
+pub const NOTIFICATION_HISTORY: usize = 100;
+
 #[derive(Debug, Default, Clone)]
 pub struct ThreatStats {
     pub critical: u32,
@@ -33,48 +143,318 @@ pub struct ThreatStats {
     pub low: u32,
 }
 
+impl ThreatStats {
+    /// Counts `n` alerts of rule level `level` under its severity band.
+    pub fn add_level(&mut self, scale: &severity::SeverityScale, level: u64, n: u32) {
+        match scale.classify(level) {
+            severity::Severity::Critical => self.critical += n,
+            severity::Severity::High => self.high += n,
+            severity::Severity::Medium => self.medium += n,
+            severity::Severity::Low => self.low += n,
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.critical + self.high + self.medium + self.low
+    }
+}
+
+/// Time span of the dashboard alert histogram.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TrendRange {
+    /// Per-minute counts of the alerts behind the severity cards
+    #[default]
+    Recent,
+    Week,
+    Month,
+}
+
+impl TrendRange {
+    pub fn next(self) -> Self {
+        match self {
+            TrendRange::Recent => TrendRange::Week,
+            TrendRange::Week => TrendRange::Month,
+            TrendRange::Month => TrendRange::Recent,
+        }
+    }
+
+    /// Days covered by the per-day severity aggregation; `None` for the per-minute view.
+    pub fn days(self) -> Option<u32> {
+        match self {
+            TrendRange::Recent => None,
+            TrendRange::Week => Some(7),
+            TrendRange::Month => Some(30),
+        }
+    }
+}
+
+/// How long the last dashboard load waited on the Wazuh API and on the indexer.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LoadTiming {
+    pub api_ms: u64,
+    pub indexer_ms: u64,
+    pub total_ms: u64,
+}
+
+/// Dashboard loads slower than this are highlighted.
+pub const SLOW_LOAD_MS: u64 = 2000;
+
+/// Alert and CVE totals across every agent of one group.
+#[derive(Debug, Default, Clone)]
+pub struct GroupPosture {
+    pub agents: usize,
+    pub alerts: ThreatStats,
+    pub top_rules: Vec<report::TopRule>,
+    pub vulns: crate::models::VulnerabilitySummary,
+}
+
+/// RBAC actions granted to the authenticated API user.
+#[derive(Debug, Default, Clone)]
+pub struct Permissions {
+    pub username: String,
+    pub roles: Vec<String>,
+    pub allowed_actions: std::collections::HashSet<String>,
+    /// Listed actions without any "allow"; only consulted in black mode
+    pub denied_actions: std::collections::HashSet<String>,
+    /// The manager runs `rbac_mode: black`, where anything not denied is allowed
+    pub black_mode: bool,
+}
+
+impl Permissions {
+    /// Builds the allowed and denied sets from a `/security/users/me/policies` payload, where
+    /// each action maps resources to "allow" or "deny" next to the manager's `rbac_mode`.
+    pub fn from_policies(username: &str, roles: Vec<String>, policies: &serde_json::Value) -> Self {
+        let mut allowed_actions = std::collections::HashSet::new();
+        let mut denied_actions = std::collections::HashSet::new();
+        let black_mode = policies.get("rbac_mode").and_then(|m| m.as_str()) == Some("black");
+        if let Some(map) = policies.as_object() {
+            for (action, resources) in map.iter().filter(|(key, _)| key.as_str() != "rbac_mode") {
+                let allowed = resources.as_object()
+                    .map(|r| r.values().any(|effect| effect.as_str() == Some("allow")))
+                    .unwrap_or(false);
+                if allowed {
+                    allowed_actions.insert(action.clone());
+                } else {
+                    denied_actions.insert(action.clone());
+                }
+            }
+        }
+        Self { username: username.to_string(), roles, allowed_actions, denied_actions, black_mode }
+    }
+
+    pub fn can(&self, action: &str) -> bool {
+        if self.black_mode {
+            !self.denied_actions.contains(action)
+        } else {
+            self.allowed_actions.contains(action)
+        }
+    }
+}
+
+// RBAC action names used to gate keybindings
+pub const ACTION_AGENT_UPGRADE: &str = "agent:upgrade";
+pub const ACTION_AGENT_RESTART: &str = "agent:restart";
+pub const ACTION_GROUP_ASSIGN: &str = "agent:modify_group";
+pub const ACTION_CONFIG_PUSH: &str = "agent:reconfigure";
+pub const ACTION_AGENT_DELETE: &str = "agent:delete";
+pub const ACTION_LISTS_UPDATE: &str = "lists:update";
+pub const ACTION_RULES_UPDATE: &str = "rules:update";
+pub const ACTION_RULES_DELETE: &str = "rules:delete";
+pub const ACTION_DECODERS_UPDATE: &str = "decoders:update";
+pub const ACTION_DECODERS_DELETE: &str = "decoders:delete";
+pub const ACTION_MANAGER_RESTART: &str = "manager:restart";
+pub const ACTION_ROOTCHECK_RUN: &str = "rootcheck:run";
+pub const ACTION_SYSCHECK_RUN: &str = "syscheck:run";
+pub const ACTION_ACTIVE_RESPONSE: &str = "active-response:command";
+
+/// Quiet time after the last keystroke in the description filter before Security Events is requeried.
+pub const LIVE_SEARCH_DEBOUNCE_MS: u64 = 350;
+
+/// Items per syscollector request when paging processes and packages.
+pub const SYSCOLLECTOR_PAGE_SIZE: u32 = 500;
+
+/// Default of how far back the dashboard looks for agents that went disconnected.
+pub const DEFAULT_DISCONNECTED_WINDOW_HOURS: i64 = 24;
+
+/// Title, document reference and blank line above the fields in the log detail overlay.
+pub const LOG_DETAIL_HEADER_LINES: usize = 3;
+/// `log_format` values accepted by `PUT /logtest`, cycled in the Test Log Line popup.
+pub const LOGTEST_FORMATS: &[&str] = &[
+    "syslog", "json", "snort-full", "squid", "eventlog", "eventchannel", "audit",
+    "mysql_log", "postgresql_log", "nmapg", "iis", "command", "full_command", "djb-multilog",
+];
+
+/// Minutes either side of an alert fetched for its context timeline.
+pub const ALERT_CONTEXT_MINUTES: u32 = 5;
+/// Most alerts fetched for one context timeline.
+pub const ALERT_CONTEXT_LIMIT: u32 = 200;
+/// Most alerts fetched when pivoting on a source IP across agents.
+pub const PIVOT_LIMIT: u32 = 500;
+/// Columns moved per horizontal scroll step in the Raw log view.
+pub const RAW_SCROLL_STEP: u16 = 8;
+/// Default age before never-connected/pending agents are offered for cleanup.
+pub const DEFAULT_STALE_AGENT_DAYS: i64 = 7;
+/// Default keep-alive age at which an "active" agent is highlighted on the dashboard.
+pub const DEFAULT_STALE_KEEPALIVE_MINUTES: i64 = 5;
+/// Default cap on the alerts a full export pages through.
+pub const DEFAULT_EXPORT_MAX_ALERTS: u32 = 10_000;
+/// Alerts fetched per scroll page by a full export.
+pub const EXPORT_PAGE_SIZE: u32 = 1000;
+/// Rows in the dashboard's oldest keep-alive list.
+pub const KEEPALIVE_LIST_SIZE: usize = 10;
+/// How long alerts that arrived with a refresh keep their NEW badge.
+pub const NEW_ALERT_SECS: u64 = 10;
+/// Maximum agents returned by the per-agent vulnerability count aggregation.
+pub const AGENT_VULN_BUCKETS: u32 = 5000;
+
 pub enum DataUpdate {
     Agents(Vec<WazuhAgent>),
+    /// Agents of a `[[profiles]]` manager, or why they could not be loaded
+    ProfileAgents { profile: String, result: Result<Vec<WazuhAgent>, String> },
     Groups(Vec<WazuhGroup>),
     GroupAgents(Vec<WazuhAgent>),
-    SecurityEvents(Vec<serde_json::Value>),
+    /// A page of alerts and `hits.total`; `exact` is false when the indexer stopped counting
+    SecurityEvents { hits: Vec<serde_json::Value>, total: u64, exact: bool },
     VulnSummary(crate::models::VulnerabilitySummary),
     ThreatStats(ThreatStats),
+    /// Severity counts over every alert in the dashboard window, not just the loaded page
+    AlertTotals(ThreatStats),
     AgentHardware(WazuhHardwareItem),
-    AgentProcesses(Vec<WazuhProcessItem>),
-    AgentPrograms(Vec<WazuhProgramItem>),
+    /// One syscollector page, appended to what is loaded; `total` is the agent's full count
+    AgentProcesses { items: Vec<WazuhProcessItem>, total: u32 },
+    AgentPrograms { items: Vec<WazuhProgramItem>, total: u32 },
     AgentVulnerabilities(Vec<crate::models::WazuhVulnerabilityItem>),
     AgentLogs(Vec<serde_json::Value>),
+    AgentConnections(Vec<crate::models::NetworkConnection>),
+    HuntResults { hunt: hunts::Hunt, result: Result<Vec<hunts::HuntRow>, String> },
+    /// Alerts fetched by the full export so far, out of all that match
+    ExportProgress { fetched: usize, total: u64 },
+    /// Alerts the full export wrote and its file, or why it failed
+    ExportFinished(Result<(usize, String), String>),
     AgentConfig(serde_json::Value),
+    /// Daemons and `ossec.log` of agent 000
+    ManagerStatus(crate::models::ManagerStatus),
+    ConfigBaseline(Result<Vec<(String, String)>, String>),
+    AgentAlertHours(crate::models::HourlyAlerts),
+    AgentRootcheck { findings: Vec<crate::models::WazuhRootcheckItem>, last_scan: Option<crate::models::WazuhLastScan> },
+    /// Agents that accepted an on-demand syscheck scan
+    FimScanRequested(Vec<String>),
+    FimScanProgress { agent_id: String, last_scan: Option<crate::models::WazuhLastScan> },
     AlertHistory(Vec<(String, u64)>),
+    /// Per-day severity counts for the `Week`/`Month` histogram, oldest first
+    AlertTrend(Vec<(String, ThreatStats)>),
+    IndexerHealth(Result<crate::models::IndexerHealth, String>),
+    Certificates(Vec<certs::CertChain>),
+    DashboardTiming(LoadTiming),
+    RemoteCommandResult { id: String, result: Result<remote_cmd::CommandResult, String> },
+    /// Heartbeat ping latency in ms, or why it failed
+    Heartbeat(Result<u64, String>),
+    /// Output of the embedded SSH session; empty once it ended
+    SshOutput(Vec<u8>),
     TopAgents(Vec<(String, u64)>),
+    TopCountries(Vec<(String, u64)>),
+    TopRules(Vec<report::TopRule>),
+    CriticalAlerts(Vec<serde_json::Value>),
+    Permissions(Permissions),
+    SecurityUsers(Vec<crate::models::WazuhUser>),
+    SecurityRoles(Vec<crate::models::WazuhRole>),
+    SecurityPolicies(Vec<crate::models::WazuhPolicy>),
+    OutdatedAgents(Vec<crate::models::WazuhOutdatedAgent>),
+    AgentVulnCounts(std::collections::HashMap<String, crate::models::AgentVulnCounts>),
+    /// Alerts in the high band or above per agent id, for the health scores
+    AgentHighAlerts(std::collections::HashMap<String, usize>),
+    Audit(audit::AuditEntry),
+    /// A batch of the running bulk restart or upgrade finished
+    BulkBatch(bulk::BatchResult),
+    /// Current status of the agents with queued actions
+    PendingAgentStatus(Vec<WazuhAgent>),
+    /// A group change went through and can be undone; `message` reports it
+    GroupUndo { undo: undo::GroupUndo, message: String },
+    /// The API accepted the credentials of the Re-login popup
+    Relogin { username: String, password: String, save: bool },
+    GroupFiles { group: String, files: Vec<crate::models::WazuhGroupFile> },
+    GroupFileContent(String),
+    /// `None` when the aggregation failed
+    GroupPosture { group: String, posture: Option<GroupPosture> },
+    CdbLists(Vec<crate::models::WazuhCdbList>),
+    Logtest(crate::models::LogtestResult),
+    RuleFiles { kind: rules::RulesetKind, files: Vec<crate::models::WazuhRuleFile> },
+    /// Downloaded rule or decoder file, to be opened in the external editor
+    RuleFileContent { kind: rules::RulesetKind, filename: String, content: String },
+    /// Manager configuration check after an upload; empty when it passed
+    RuleValidation { filename: String, errors: Vec<String> },
+    AlertContext(Vec<serde_json::Value>),
+    SrcIpPivot(Vec<serde_json::Value>),
     Notification(String, NotificationLevel),
     Error(String),
     ErrorPopup { title: String, message: String },
+    /// Inspector data tagged with the load generation it belongs to
+    Inspector { generation: u64, update: Box<DataUpdate> },
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum ActiveView {
     Dashboard,
     AgentList,
     AgentInspector,
     SecurityEvents,
     GroupManagement,
+    UserManagement,
+    CdbLists,
+    Rules,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum PopupMode {
     None,
     GroupAssignment { agent_id: String },
+    AgentLabels { agent_ids: Vec<String> },
     SeverityFilter,
     SshUsername { agent_id: String, agent_ip: String },
+    SshSession,
+    RunCommand { agent_id: String },
+    ConfirmRunCommand { agent_id: String, command: String },
+    CommandOutput,
     AgentJump,
     Error { title: String, message: String },
     Help,
     CommandPalette,
+    CreateUser,
+    Relogin,
+    /// Path prompt of Export Setup or, with `import`, Import Setup
+    SetupBundle { import: bool },
+    RoleAssignment { user_id: u32 },
+    ConfirmDeleteUser { user_id: u32, username: String },
+    ProcessDetail { pid: String },
+    ConfirmAgentAction { action: actions::AgentAction, agent_ids: Vec<String> },
+    BulkProgress,
+    PendingActions,
+    /// Agent counts per cluster node
+    AgentNodes,
+    AlertContext,
+    SrcIpPivot { srcip: String },
+    Activity,
+    Notifications,
+    RequestLog,
+    IndexerHealth,
+    Certificates,
+    GroupFile { group: String, filename: String },
+    GroupMembership { agent_id: String },
+    ConfirmGroupMembership { agent_id: String, add: Vec<String>, remove: Vec<String> },
+    CdbAddEntry { filename: String },
+    ConfirmCdbChange { filename: String, key: String, value: String, remove: bool },
+    Logtest,
+    NewRuleFile,
+    ConfirmRuleUpload { kind: rules::RulesetKind, filename: String },
+    ConfirmRuleDelete { kind: rules::RulesetKind, filename: String },
+    ConfirmManagerRestart,
+    DuplicateAgents,
+    Hunts,
+    HuntResults { hunt: hunts::Hunt },
+    ExportFields,
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum SeverityFilterMode {
     Min,
     Max,
@@ -82,7 +462,8 @@ pub enum SeverityFilterMode {
     Range,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct LogFilter {
     pub mode: SeverityFilterMode,
     pub val1: u32,
@@ -91,6 +472,86 @@ pub struct LogFilter {
     pub rule_id_filter: String,
     pub description_filter: String,
     pub mitre_filter: String,
+    pub sort: LogSortColumn,
+    pub sort_order: SortOrder,
+    /// Severity chips (`critical`, `high`, `medium`, `low`); alerts in any of them match
+    pub severities: Vec<String>,
+}
+
+impl LogFilter {
+    /// The conditions that narrow the results, as shown in the Security Events summary line.
+    /// The time window and sort order are not filters in this sense.
+    pub fn active_filters(&self) -> Vec<String> {
+        let mut filters = Vec::new();
+        match self.mode {
+            SeverityFilterMode::Min if self.val1 > 0 => filters.push(format!("level ≥{}", self.val1)),
+            SeverityFilterMode::Min => {}
+            SeverityFilterMode::Max => filters.push(format!("level ≤{}", self.val1)),
+            SeverityFilterMode::Exact => filters.push(format!("level ={}", self.val1)),
+            SeverityFilterMode::Range => filters.push(format!("level {}-{}", self.val1, self.val2)),
+        }
+        if !self.severities.is_empty() {
+            filters.push(self.severities.join("+"));
+        }
+        for (label, value) in [("agent", &self.agent_filter), ("rule", &self.rule_id_filter), ("mitre", &self.mitre_filter)] {
+            if !value.is_empty() {
+                filters.push(format!("{}:{}", label, value));
+            }
+        }
+        if !self.description_filter.is_empty() {
+            filters.push(format!("\"{}\"", self.description_filter));
+        }
+        filters
+    }
+}
+
+/// Command palette names for the saved agent searches, one per Alt+1..9 slot.
+pub const SAVED_SEARCH_LABELS: [&str; 9] = [
+    "Saved Search 1", "Saved Search 2", "Saved Search 3", "Saved Search 4", "Saved Search 5",
+    "Saved Search 6", "Saved Search 7", "Saved Search 8", "Saved Search 9",
+];
+
+/// Severity chip names, most severe first, as toggled with 1-4 in Security Events.
+pub const ALERT_SEVERITIES: [&str; 4] = ["critical", "high", "medium", "low"];
+
+/// Security Events sort keys; each maps to a field of the OpenSearch sort clause.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum LogSortColumn {
+    #[default]
+    Time,
+    Level,
+    Agent,
+    Rule,
+}
+
+impl LogSortColumn {
+    pub fn next(self) -> Self {
+        match self {
+            LogSortColumn::Time => LogSortColumn::Level,
+            LogSortColumn::Level => LogSortColumn::Agent,
+            LogSortColumn::Agent => LogSortColumn::Rule,
+            LogSortColumn::Rule => LogSortColumn::Time,
+        }
+    }
+
+    pub fn field(self) -> &'static str {
+        match self {
+            LogSortColumn::Time => "@timestamp",
+            LogSortColumn::Level => "rule.level",
+            LogSortColumn::Agent => "agent.name",
+            LogSortColumn::Rule => "rule.id",
+        }
+    }
+
+    /// Table column that shows the sort indicator.
+    pub fn column(self) -> LogColumn {
+        match self {
+            LogSortColumn::Time => LogColumn::Timestamp,
+            LogSortColumn::Level => LogColumn::Level,
+            LogSortColumn::Agent => LogColumn::Agent,
+            LogSortColumn::Rule => LogColumn::RuleId,
+        }
+    }
 }
 
 impl Default for LogFilter {
@@ -103,11 +564,14 @@ impl Default for LogFilter {
             rule_id_filter: String::new(),
             description_filter: String::new(),
             mitre_filter: String::new(),
+            sort: LogSortColumn::default(),
+            sort_order: SortOrder::Desc,
+            severities: Vec::new(),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum LogColumn {
     Timestamp,
     Level,
@@ -118,6 +582,7 @@ pub enum LogColumn {
     MitreTactic,
     SrcIp,
     DstIp,
+    SrcGeo,
     User,
     Groups,
 }
@@ -134,6 +599,7 @@ impl LogColumn {
             LogColumn::MitreTactic => "Tactic",
             LogColumn::SrcIp => "Src IP",
             LogColumn::DstIp => "Dst IP",
+            LogColumn::SrcGeo => "Src Geo",
             LogColumn::User => "User",
             LogColumn::Groups => "Groups",
         }
@@ -150,6 +616,7 @@ impl LogColumn {
             LogColumn::MitreTactic,
             LogColumn::SrcIp,
             LogColumn::DstIp,
+            LogColumn::SrcGeo,
             LogColumn::User,
             LogColumn::Groups,
         ]
@@ -193,7 +660,7 @@ pub enum LogViewMode {
     Raw,
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum SortColumn {
     Id,
     Name,
@@ -201,6 +668,7 @@ pub enum SortColumn {
     Status,
     Os,
     LastKeepAlive,
+    Health,
 }
 
 impl SortColumn {
@@ -211,12 +679,62 @@ impl SortColumn {
             SortColumn::Ip => SortColumn::Status,
             SortColumn::Status => SortColumn::Os,
             SortColumn::Os => SortColumn::LastKeepAlive,
-            SortColumn::LastKeepAlive => SortColumn::Id,
+            SortColumn::LastKeepAlive => SortColumn::Health,
+            SortColumn::Health => SortColumn::Id,
         }
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ProcessSortColumn {
+    Pid,
+    Name,
+    User,
+    Memory,
+    CpuTime,
+}
+
+impl ProcessSortColumn {
+    pub fn next(&self) -> Self {
+        match self {
+            ProcessSortColumn::Pid => ProcessSortColumn::Name,
+            ProcessSortColumn::Name => ProcessSortColumn::User,
+            ProcessSortColumn::User => ProcessSortColumn::Memory,
+            ProcessSortColumn::Memory => ProcessSortColumn::CpuTime,
+            ProcessSortColumn::CpuTime => ProcessSortColumn::Pid,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum VulnSortColumn {
+    Severity,
+    Score,
+    Package,
+}
+
+impl VulnSortColumn {
+    pub fn next(&self) -> Self {
+        match self {
+            VulnSortColumn::Severity => VulnSortColumn::Score,
+            VulnSortColumn::Score => VulnSortColumn::Package,
+            VulnSortColumn::Package => VulnSortColumn::Severity,
+        }
+    }
+}
+
+/// Orders severities low to high; unknown values sort below "low".
+pub fn vuln_severity_rank(severity: &str) -> u8 {
+    match severity.to_lowercase().as_str() {
+        "critical" => 4,
+        "high" => 3,
+        "medium" => 2,
+        "low" => 1,
+        _ => 0,
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum SortOrder {
     Asc,
     Desc,
@@ -224,11 +742,14 @@ pub enum SortOrder {
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum InspectorTab {
+    Summary,
     Hardware,
     Processes,
     Programs,
     Vulnerabilities,
+    Rootcheck,
     Logs,
+    Network,
     Config,
 }
 
@@ -236,6 +757,9 @@ pub enum InspectorTab {
 pub enum ConfigStep {
     Url,
     OsUrl,
+    /// Optional, prefilled with the default pattern
+    AlertsIndex,
+    VulnIndex,
     Username,
     Password,
     Confirm,
@@ -254,6 +778,8 @@ pub struct App {
     pub config_step: ConfigStep,
     pub config_url: String,
     pub config_os_url: String,
+    pub config_alerts_index: String,
+    pub config_vuln_index: String,
     pub config_username: String,
     pub config_password: String,
     
@@ -274,38 +800,128 @@ pub struct App {
     pub table_state: ratatui::widgets::TableState,
     pub inspector_table_state: ratatui::widgets::TableState,
     pub groups_table_state: ratatui::widgets::TableState,
+    // Group detail: shared files of the group opened with Enter; → focuses the file list
+    pub group_files_group: Option<String>,
+    pub group_files: Vec<crate::models::WazuhGroupFile>,
+    pub group_file_index: usize,
+    pub group_files_focus: bool,
+    pub group_file_content: Option<String>,
+    pub group_file_scroll: u16,
+    // Inspector group membership editor: groups ticked in the popup, applied after confirmation
+    pub membership_groups: Vec<String>,
+    pub membership_index: usize,
+    // CDB lists view: → focuses the entries of the selected list
+    pub cdb_lists: Vec<crate::models::WazuhCdbList>,
+    pub cdb_list_index: usize,
+    pub cdb_entries_focus: bool,
+    pub cdb_entry_index: usize,
+    // Test Log Line popup; fields are kept between runs so a line can be tweaked and re-tested
+    pub logtest_event: String,
+    pub logtest_location: String,
+    pub logtest_format_index: usize,
+    /// 0 = event, 1 = location, 2 = format
+    pub logtest_field: usize,
+    pub logtest_result: Option<crate::models::LogtestResult>,
+    // Local rule/decoder files view; the draft is the edited text waiting for upload
+    pub ruleset_kind: rules::RulesetKind,
+    pub rule_files: Vec<crate::models::WazuhRuleFile>,
+    pub rule_file_index: usize,
+    pub rule_draft: String,
+    pub rule_draft_error: Option<String>,
     
     // Search state
     pub search_query: String,
     pub is_searching: bool,
     pub agent_filter: AgentFilter,
+    /// Agent search expressions keyed by slot 1-9
+    pub saved_agent_searches: std::collections::BTreeMap<u8, String>,
+    /// Local agent tags, see `labels.toml`
+    pub agent_labels: labels::AgentLabels,
+    pub ssh_session: Option<pty::SshSession>,
+    // Run command: the whitelist cursor and the last command sent
+    pub remote_command_index: usize,
+    pub remote_command: Option<remote_cmd::RemoteCommand>,
+    pub remote_output_scroll: u16,
+    /// API reachability from the periodic `/manager/info` ping
+    pub heartbeat: conn::Heartbeat,
     
     // Inspector Details
     pub hardware: Option<WazuhHardwareItem>,
+    /// Only loaded when inspecting agent 000
+    pub manager_status: Option<crate::models::ManagerStatus>,
     pub processes: Vec<WazuhProcessItem>,
+    /// Full counts reported by syscollector while pages are still arriving
+    pub processes_total: Option<u32>,
     pub programs: Vec<WazuhProgramItem>,
+    pub programs_total: Option<u32>,
     pub vulnerabilities: Vec<crate::models::WazuhVulnerabilityItem>,
     pub agent_logs: Vec<serde_json::Value>,
+    /// Distinct connections from the agent's network alerts, busiest first
+    pub agent_connections: Vec<crate::models::NetworkConnection>,
+    /// Outstanding findings first, then most recently seen
+    pub rootcheck: Vec<crate::models::WazuhRootcheckItem>,
+    pub rootcheck_scan: Option<crate::models::WazuhLastScan>,
+    /// Alerts per hour of the inspected agent, `None` until loaded
+    pub agent_alert_hours: Option<crate::models::HourlyAlerts>,
+    /// On-demand FIM scans by agent id, kept after finishing so the result stays visible
+    pub fim_scans: std::collections::HashMap<String, fim::FimScan>,
     pub agent_config: Option<serde_json::Value>,
     pub agent_config_component: String,
     pub available_config_components: Vec<String>,
+    /// Config tab compares against the agent's groups' `agent.conf` instead of showing JSON
+    pub config_diff_mode: bool,
+    /// `(group, agent.conf)` for each group of the inspected agent, in assignment order
+    pub config_baseline: Option<Result<Vec<(String, String)>, String>>,
     
     // Selected Log Detail
     pub selected_log: Option<serde_json::Value>,
     pub show_log_json: bool,
+    pub json_tree: json_tree::JsonTreeState,
+    pub detail_search_query: String,
+    pub detail_is_searching: bool,
+    pub detail_match_index: usize,
+    /// Alerts surrounding the open log detail on the same agent
+    pub alert_context: Vec<serde_json::Value>,
+    pub alert_context_index: usize,
+    pub alert_context_anchor: Option<String>,
+    pub alert_context_loading: bool,
+    /// Alerts sharing the open log's source IP, across all agents
+    pub pivot_events: Vec<serde_json::Value>,
+    pub pivot_index: usize,
+    pub pivot_loading: bool,
     pub log_scroll_offset: usize,
     
     // Security Events
     pub logs: Vec<serde_json::Value>,
+    /// Table rows over `logs`; `table_state` indexes these, not `logs`
+    pub alert_rows: Vec<flood::AlertRow>,
+    /// Collapse consecutive alerts of one rule and agent into a row with a count
+    pub group_floods: bool,
+    /// Alert ids of the floods listed in full
+    pub expanded_floods: std::collections::HashSet<String>,
     pub log_view_mode: LogViewMode,
+    pub raw_wrap: bool,
+    pub export_format: export::ExportFormat,
+    pub export_compression: export::ExportCompression,
+    /// Paths kept in JSON alert exports; empty exports whole alerts
+    pub export_fields: Vec<String>,
+    pub display_tz: time::DisplayTz,
+    pub severity: severity::SeverityScale,
+    pub icons: crate::ui::icons::IconSet,
+    pub relative_time: bool,
+    pub raw_scroll_x: u16,
     pub log_interval_mins: u32,
     pub log_offset: u32,
     pub log_limit: u32,
+    /// Alerts matching the filter, of which `logs` is one page
     pub log_total: u64,
+    pub log_total_exact: bool,
     
     // Dashboard Stats
     pub vuln_summary: crate::models::VulnerabilitySummary,
     pub threat_stats: ThreatStats,
+    /// Aggregated over the whole window by the indexer; `None` until that search succeeds
+    pub alert_totals: Option<ThreatStats>,
     
     pub is_loading: bool,
     pub loading_text: String,
@@ -313,7 +929,40 @@ pub struct App {
     pub error_message: Option<String>,
     pub should_quit: bool,
     pub api: Option<WazuhApi>,
+    /// APIs of the `[[profiles]]` managers, by profile name
+    pub profile_apis: Vec<(String, WazuhApi)>,
+    /// Agent list merges the agents of every profile, with a Manager column
+    pub all_profiles: bool,
+    /// Agents loaded from the profiles, kept apart so refreshing this manager's agents keeps them
+    pub profile_agents: Vec<WazuhAgent>,
+    /// Profiles whose agent list failed to load, with the error
+    pub profile_errors: Vec<(String, String)>,
     pub notifications: Vec<Notification>,
+    // Notification history popup: every toast, oldest first
+    pub notification_history: Vec<Notification>,
+    pub notification_index: usize,
+    // Help popup: search typed into it and scroll position
+    pub help_query: String,
+    pub help_scroll: u16,
+    /// Step of the onboarding tour being shown
+    pub tour_step: Option<usize>,
+    /// Bulk restart or upgrade being sent in batches, kept after it finished for the popup
+    pub bulk_op: Option<bulk::BulkOp>,
+    /// Actions waiting for disconnected agents to come back, and the panel's selected row
+    pub pending_actions: pending::PendingQueue,
+    pub pending_index: usize,
+    pub node_index: usize,
+    /// Last group change and when it was made, undone by `u` within `undo::UNDO_SECS`
+    pub group_undo: Option<(undo::GroupUndo, Instant)>,
+    pub keymap: keymap::KeyMap,
+    // Request inspector popup (hidden, palette only): selected row, newest first
+    pub request_log_index: usize,
+    // Indexer health popup (palette only): `None` while loading, selected index row
+    pub indexer_health: Option<Result<crate::models::IndexerHealth, String>>,
+    pub indexer_health_index: usize,
+    /// TLS certificate chains of the API and indexer, `None` until they are read
+    pub certificates: Option<Vec<certs::CertChain>>,
+    pub permissions: Option<Permissions>,
     
     // Filtering
     pub severity_filter: Option<String>,
@@ -324,6 +973,8 @@ pub struct App {
     pub filter_popup_tab: FilterPopupTab,
     pub visible_log_columns: Vec<LogColumn>,
     pub column_selection_index: usize,
+    pub show_query_preview: bool,
+    pub query_preview_scroll: u16,
 
     // Agent Jump
     pub jump_input: String,
@@ -335,10 +986,149 @@ pub struct App {
 
     // Multi-select
     pub selected_agents: std::collections::HashSet<String>,
+    // Security Events multi-select: whole alert documents so selections survive paging
+    pub selected_alerts: Vec<serde_json::Value>,
+    // Alert IDs acknowledged in this session (shown dimmed)
+    pub acked_alerts: std::collections::HashSet<String>,
+    // Alerts that appeared on a refresh of the same page, with when they arrived (NEW badge)
+    pub new_alerts: std::collections::HashMap<String, Instant>,
+    pub loaded_log_offset: u32,
 
     // Chart Data
     pub alert_buckets: Vec<(String, u64)>,
+    pub trend_range: TrendRange,
+    pub dashboard_timing: Option<LoadTiming>,
+    pub alert_trend: Vec<(String, ThreatStats)>,
     pub top_agents: Vec<(String, u64)>,
+    pub top_countries: Vec<(String, u64)>,
+    pub top_rules: Vec<report::TopRule>,
+    pub critical_alerts: Vec<serde_json::Value>,
+
+    // Security users & roles
+    pub security_users: Vec<crate::models::WazuhUser>,
+    pub security_roles: Vec<crate::models::WazuhRole>,
+    pub security_policies: Vec<crate::models::WazuhPolicy>,
+    pub users_table_state: ratatui::widgets::TableState,
+    pub new_user_name: String,
+    pub new_user_password: String,
+    pub new_user_field: usize, // 0 for username, 1 for password
+    pub relogin: relogin::ReloginForm,
+    pub role_selection_index: usize,
+
+    // Inspector loading: stale responses carry an older generation and are dropped
+    pub inspector_generation: u64,
+    pub inspector_task: Option<tokio::task::JoinHandle<()>>,
+    /// Description filter typed but not queried yet, see `LIVE_SEARCH_DEBOUNCE_MS`
+    pub live_search_due: Option<Instant>,
+    /// Running live query; aborted when a newer one starts
+    pub live_search_task: Option<tokio::task::JoinHandle<()>>,
+    /// Full export paging through every matching alert; started again it is cancelled
+    pub export_job: Option<tokio::task::JoinHandle<()>>,
+    /// Alerts fetched so far and how many match, while a full export runs
+    pub export_progress: Option<(usize, u64)>,
+    pub export_max_alerts: u32,
+    pub export_naming: export::ExportNaming,
+    /// Absolute path of the last file written, copied with `Y`
+    pub last_export_path: Option<String>,
+    /// Agent the inspector data (hardware, processes, logs, ...) was last loaded for
+    pub inspector_agent_id: Option<String>,
+
+    // Agent list split pane: summary of the highlighted agent, loaded once the cursor rests on it
+    pub agent_preview: bool,
+    pub preview_candidate: Option<String>,
+
+    // Group Management posture pane: per-group aggregates, `None` while loading or after a failure
+    pub group_posture: bool,
+    pub group_postures: std::collections::HashMap<String, Option<GroupPosture>>,
+    pub posture_candidate: Option<String>,
+
+    // Processes tab sorting
+    pub process_sort_column: ProcessSortColumn,
+    pub process_sort_order: SortOrder,
+
+    // Programs tab: only show packages with at least one known CVE
+    pub programs_vuln_only: bool,
+    pub vuln_sort_column: VulnSortColumn,
+    pub vuln_sort_order: SortOrder,
+    /// Severity the Vulnerabilities tab is narrowed to (lowercase), set with keys 1-4
+    pub vuln_severity_filter: Option<String>,
+    pub vuln_export_format: export::VulnExportFormat,
+
+    // Agent list: agents lagging the manager version (GET /agents/outdated)
+    pub outdated_agent_ids: std::collections::HashSet<String>,
+    pub show_outdated_only: bool,
+
+    // Maintenance: minimum age of never-connected/pending agents to clean up
+    pub stale_agent_days: i64,
+    pub stale_keepalive_minutes: i64,
+    /// Ask before upgrades, restarts and bulk scans (`[confirm] agent_actions`)
+    pub confirm_agent_actions: bool,
+
+    // Critical CVE counts per agent id, recorded as each agent is inspected
+    pub agent_critical_cves: std::collections::HashMap<String, usize>,
+
+    // Agent list severity filter (keys 1-4): CVE counts per agent id from one aggregation
+    pub agent_vuln_counts: std::collections::HashMap<String, crate::models::AgentVulnCounts>,
+    pub agent_vuln_counts_loaded: bool,
+    // Health scores: high-band alerts per agent id across the fleet, None until loaded
+    pub agent_high_alerts: Option<std::collections::HashMap<String, usize>>,
+
+    // Activity popup: recent mutating actions, oldest first (see audit.log)
+    pub audit_log: Vec<audit::AuditEntry>,
+    pub activity_index: usize,
+
+    // Dashboard recently-disconnected panel (`[maintenance] disconnected_window_hours`)
+    pub disconnected_window_hours: i64,
+    pub disconnected_index: usize,
+
+    // Duplicate agents popup: selected row across all duplicate sets
+    pub duplicate_index: usize,
+
+    // Hunts menu and the results of the hunt last run; `None` while it runs
+    pub hunt_index: usize,
+    pub hunt_rows: Option<Result<Vec<hunts::HuntRow>, String>>,
+    pub hunt_row_index: usize,
+
+    // Views left behind by transitions, most recent last; Esc/Backspace pops them
+    pub nav_stack: Vec<nav::NavEntry>,
+}
+
+/// Parses durations like `30m`, `2h` or `7d` into minutes; a bare number is minutes.
+pub fn parse_interval(input: &str) -> Result<u32, String> {
+    let input = input.trim().to_lowercase();
+    let (val_str, unit) = if input.ends_with('m') {
+        (&input[..input.len()-1], 1)
+    } else if input.ends_with('h') {
+        (&input[..input.len()-1], 60)
+    } else if input.ends_with('d') {
+        (&input[..input.len()-1], 1440)
+    } else {
+        (input.as_str(), 1) // default minutes
+    };
+
+    val_str.parse::<u32>().map(|val| val * unit).map_err(|_| "Invalid number format".to_string())
+}
+
+/// Groups to assign and to remove to go from `current` to `wanted` membership.
+pub fn group_membership_diff(current: &[String], wanted: &[String]) -> (Vec<String>, Vec<String>) {
+    let add = wanted.iter().filter(|g| !current.contains(g)).cloned().collect();
+    let remove = current.iter().filter(|g| !wanted.contains(g)).cloned().collect();
+    (add, remove)
+}
+
+/// The OpenSearch document `_id` of an alert hit.
+pub fn alert_id(log: &serde_json::Value) -> Option<&str> {
+    log.get("_id").and_then(|v| v.as_str())
+}
+
+pub fn format_interval_mins(mins: u32) -> String {
+    if mins >= 1440 && mins % 1440 == 0 {
+        format!("{}d", mins / 1440)
+    } else if mins >= 60 && mins % 60 == 0 {
+        format!("{}h", mins / 60)
+    } else {
+        format!("{}m", mins)
+    }
 }
 
 impl App {
@@ -348,13 +1138,15 @@ impl App {
         
         Self {
             active_view: ActiveView::Dashboard,
-            inspector_tab: InspectorTab::Hardware,
+            inspector_tab: InspectorTab::Summary,
             sort_column: SortColumn::Id,
             sort_order: SortOrder::Asc,
             is_config_wizard_active: false,
             config_step: ConfigStep::Url,
             config_url: String::new(),
             config_os_url: String::new(),
+            config_alerts_index: crate::models::DEFAULT_ALERTS_INDEX.to_string(),
+            config_vuln_index: crate::models::DEFAULT_VULNERABILITIES_INDEX.to_string(),
             config_username: String::new(),
             config_password: String::new(),
             show_interval_popup: false,
@@ -368,14 +1160,51 @@ impl App {
             table_state,
             inspector_table_state: ratatui::widgets::TableState::default(),
             groups_table_state: ratatui::widgets::TableState::default(),
+            group_files_group: None,
+            group_files: Vec::new(),
+            group_file_index: 0,
+            group_files_focus: false,
+            group_file_content: None,
+            group_file_scroll: 0,
+            membership_groups: Vec::new(),
+            membership_index: 0,
+            cdb_lists: Vec::new(),
+            cdb_list_index: 0,
+            cdb_entries_focus: false,
+            cdb_entry_index: 0,
+            logtest_event: String::new(),
+            logtest_location: "stdin".to_string(),
+            logtest_format_index: 0,
+            logtest_field: 0,
+            logtest_result: None,
+            ruleset_kind: rules::RulesetKind::default(),
+            rule_files: Vec::new(),
+            rule_file_index: 0,
+            rule_draft: String::new(),
+            rule_draft_error: None,
             search_query: String::new(),
             is_searching: false,
             agent_filter: AgentFilter::default(),
+            saved_agent_searches: std::collections::BTreeMap::new(),
+            agent_labels: labels::AgentLabels::default(),
+            ssh_session: None,
+            remote_command_index: 0,
+            remote_command: None,
+            remote_output_scroll: 0,
+            heartbeat: conn::Heartbeat::default(),
             hardware: None,
+            manager_status: None,
             processes: Vec::new(),
+            processes_total: None,
             programs: Vec::new(),
+            programs_total: None,
             vulnerabilities: Vec::new(),
             agent_logs: Vec::new(),
+            agent_connections: Vec::new(),
+            rootcheck: Vec::new(),
+            rootcheck_scan: None,
+            agent_alert_hours: None,
+            fim_scans: std::collections::HashMap::new(),
             agent_config: None,
             agent_config_component: "syscheck".to_string(),
             available_config_components: vec![
@@ -385,15 +1214,41 @@ impl App {
                 "agent".to_string(),
                 "auth".to_string()
             ],
+            config_diff_mode: false,
+            config_baseline: None,
             selected_log: None,
             show_log_json: false,
+            json_tree: json_tree::JsonTreeState::default(),
+            detail_search_query: String::new(),
+            detail_is_searching: false,
+            detail_match_index: 0,
+            alert_context: Vec::new(),
+            alert_context_index: 0,
+            alert_context_anchor: None,
+            alert_context_loading: false,
+            pivot_events: Vec::new(),
+            pivot_index: 0,
+            pivot_loading: false,
             log_scroll_offset: 0,
             logs: Vec::new(),
+            alert_rows: Vec::new(),
+            group_floods: true,
+            expanded_floods: std::collections::HashSet::new(),
             log_view_mode: LogViewMode::Table,
+            raw_wrap: false,
+            export_format: export::ExportFormat::default(),
+            export_compression: export::ExportCompression::default(),
+            export_fields: Vec::new(),
+            display_tz: time::DisplayTz::default(),
+            severity: severity::SeverityScale::default(),
+            icons: crate::ui::icons::IconSet::default(),
+            relative_time: false,
+            raw_scroll_x: 0,
             log_interval_mins: 15,
             log_offset: 0,
             log_limit: 50,
             log_total: 0,
+            log_total_exact: true,
             vuln_summary: crate::models::VulnerabilitySummary {
                 critical: 0,
                 high: 0,
@@ -402,13 +1257,34 @@ impl App {
                 untriaged: 0,
             },
             threat_stats: ThreatStats::default(),
+            alert_totals: None,
             is_loading: false,
             loading_text: String::from("Fetching data..."),
             spinner_index: 0,
             error_message: None,
             should_quit: false,
             api: None,
+            profile_apis: Vec::new(),
+            all_profiles: false,
+            profile_agents: Vec::new(),
+            profile_errors: Vec::new(),
             notifications: Vec::new(),
+            notification_history: Vec::new(),
+            notification_index: 0,
+            help_query: String::new(),
+            help_scroll: 0,
+            tour_step: None,
+            bulk_op: None,
+            pending_actions: pending::PendingQueue::default(),
+            pending_index: 0,
+            node_index: 0,
+            group_undo: None,
+            keymap: keymap::KeyMap::default(),
+            request_log_index: 0,
+            indexer_health: None,
+            indexer_health_index: 0,
+            certificates: None,
+            permissions: None,
             severity_filter: None,
             log_filter: LogFilter::default(),
             filter_input_1: String::new(),
@@ -422,22 +1298,146 @@ impl App {
                 LogColumn::Description,
             ],
             column_selection_index: 0,
+            show_query_preview: false,
+            query_preview_scroll: 0,
             jump_input: String::new(),
             jump_index: 0,
             command_palette_input: String::new(),
             command_palette_index: 0,
             selected_agents: std::collections::HashSet::new(),
+            selected_alerts: Vec::new(),
+            acked_alerts: std::collections::HashSet::new(),
+            new_alerts: std::collections::HashMap::new(),
+            loaded_log_offset: 0,
             alert_buckets: Vec::new(),
+            trend_range: TrendRange::default(),
+            dashboard_timing: None,
+            alert_trend: Vec::new(),
             top_agents: Vec::new(),
+            top_countries: Vec::new(),
+            top_rules: Vec::new(),
+            critical_alerts: Vec::new(),
+            security_users: Vec::new(),
+            security_roles: Vec::new(),
+            security_policies: Vec::new(),
+            users_table_state: ratatui::widgets::TableState::default(),
+            new_user_name: String::new(),
+            new_user_password: String::new(),
+            new_user_field: 0,
+            relogin: relogin::ReloginForm::new(),
+            role_selection_index: 0,
+            inspector_generation: 0,
+            inspector_task: None,
+            live_search_due: None,
+            live_search_task: None,
+            export_job: None,
+            export_progress: None,
+            export_max_alerts: DEFAULT_EXPORT_MAX_ALERTS,
+            export_naming: export::ExportNaming::default(),
+            last_export_path: None,
+            inspector_agent_id: None,
+            agent_preview: false,
+            preview_candidate: None,
+            group_posture: false,
+            group_postures: std::collections::HashMap::new(),
+            posture_candidate: None,
+            process_sort_column: ProcessSortColumn::Pid,
+            process_sort_order: SortOrder::Asc,
+            programs_vuln_only: false,
+            vuln_sort_column: VulnSortColumn::Severity,
+            vuln_sort_order: SortOrder::Desc,
+            vuln_severity_filter: None,
+            vuln_export_format: export::VulnExportFormat::default(),
+            outdated_agent_ids: std::collections::HashSet::new(),
+            show_outdated_only: false,
+            stale_agent_days: DEFAULT_STALE_AGENT_DAYS,
+            stale_keepalive_minutes: DEFAULT_STALE_KEEPALIVE_MINUTES,
+            confirm_agent_actions: true,
+            agent_critical_cves: std::collections::HashMap::new(),
+            agent_vuln_counts: std::collections::HashMap::new(),
+            agent_vuln_counts_loaded: false,
+            agent_high_alerts: None,
+            audit_log: Vec::new(),
+            activity_index: 0,
+            duplicate_index: 0,
+            hunt_index: 0,
+            hunt_rows: None,
+            hunt_row_index: 0,
+            disconnected_window_hours: DEFAULT_DISCONNECTED_WINDOW_HOURS,
+            disconnected_index: 0,
+            nav_stack: Vec::new(),
         }
     }
 
     pub fn notify(&mut self, message: &str, level: NotificationLevel) {
-        self.notifications.push(Notification {
+        self.notify_for(message, level, NOTIFICATION_SECS);
+    }
+
+    pub fn notify_for(&mut self, message: &str, level: NotificationLevel, secs: u64) {
+        if level == NotificationLevel::Error {
+            tracing::warn!(message, "error notification");
+        }
+        let notification = Notification {
             message: message.to_string(),
             level,
             timestamp: Instant::now(),
-        });
+            time: chrono::Utc::now(),
+            secs,
+        };
+        self.notification_history.push(notification.clone());
+        if self.notification_history.len() > NOTIFICATION_HISTORY {
+            self.notification_history.remove(0);
+        }
+        self.notifications.push(notification);
+    }
+
+    /// Starts following a bulk operation in the progress popup; refused while another one runs.
+    pub fn start_bulk_op(&mut self, action: actions::AgentAction, total: usize) -> bool {
+        if let Some(op) = self.bulk_op.as_ref().filter(|op| !op.is_finished()) {
+            let running = op.action.title().to_lowercase();
+            self.notify(&format!("Wait for the {} in progress to finish", running), NotificationLevel::Warning);
+            self.popup_mode = PopupMode::BulkProgress;
+            return false;
+        }
+        self.bulk_op = Some(bulk::BulkOp::new(action, total));
+        self.popup_mode = PopupMode::BulkProgress;
+        true
+    }
+
+    /// Adds a finished batch; after the last one, reports the totals.
+    pub fn record_bulk_batch(&mut self, batch: bulk::BatchResult) {
+        let Some(op) = self.bulk_op.as_mut() else { return };
+        op.batches.push(batch);
+        if !op.is_finished() {
+            return;
+        }
+        let (failed, total, verb) = (op.failed(), op.total, op.action.verb());
+        if failed == 0 {
+            self.notify(&format!("{}: all {} agents done", verb, total), NotificationLevel::Success);
+        } else {
+            self.notify(&format!("{}: {} of {} agents failed (see the progress popup)", verb, failed, total), NotificationLevel::Warning);
+        }
+    }
+
+    /// The certificate that expires first if that is within `certs::EXPIRY_WARN_DAYS`, with
+    /// its endpoint and days left.
+    pub fn expiring_certificate(&self, now: chrono::DateTime<chrono::Utc>) -> Option<(&str, i64)> {
+        self.certificates.as_ref()?.iter()
+            .filter_map(|chain| chain.soonest_expiry(now).map(|(_, days)| (chain.endpoint.as_str(), days)))
+            .filter(|(_, days)| *days < certs::EXPIRY_WARN_DAYS)
+            .min_by_key(|(_, days)| *days)
+    }
+
+    /// Keeps `undo` for `u` and reports the change it reverses, with the key.
+    pub fn offer_group_undo(&mut self, undo: undo::GroupUndo, message: &str) {
+        self.group_undo = Some((undo, Instant::now()));
+        self.notify_for(&format!("{}. Undo (u)", message), NotificationLevel::Success, undo::UNDO_SECS);
+    }
+
+    /// The group change `u` reverses, while its undo window is open.
+    pub fn take_group_undo(&mut self) -> Option<undo::GroupUndo> {
+        let (undo, at) = self.group_undo.take()?;
+        (at.elapsed().as_secs() < undo::UNDO_SECS).then_some(undo)
     }
 
     pub fn show_error(&mut self, title: &str, message: &str) {
@@ -447,47 +1447,72 @@ impl App {
         };
     }
 
+    /// Returns whether the current user may perform `action`. Until permissions are
+    /// known (or if the RBAC endpoints are unavailable) everything is allowed.
+    pub fn is_allowed(&self, action: &str) -> bool {
+        self.permissions.as_ref().map(|p| p.can(action)).unwrap_or(true)
+    }
+
+    /// Like `is_allowed`, but tells the user why the action was blocked.
+    pub fn require_permission(&mut self, action: &str, label: &str) -> bool {
+        if self.is_allowed(action) {
+            return true;
+        }
+        let user = self.permissions.as_ref().map(|p| p.username.clone()).unwrap_or_default();
+        self.notify(&format!("{} not permitted for user '{}' ({})", label, user, action), NotificationLevel::Warning);
+        false
+    }
+
     pub fn clear_old_notifications(&mut self) {
-        self.notifications.retain(|n| n.timestamp.elapsed().as_secs() < 5);
+        self.notifications.retain(|n| n.timestamp.elapsed().as_secs() < n.secs);
     }
 
     pub fn parse_and_set_interval(&mut self) -> Result<(), String> {
         let input = self.interval_input.trim().to_lowercase();
         if input.is_empty() { return Ok(()); }
 
-        let (val_str, unit) = if input.ends_with('m') {
-            (&input[..input.len()-1], 1)
-        } else if input.ends_with('h') {
-            (&input[..input.len()-1], 60)
-        } else if input.ends_with('d') {
-            (&input[..input.len()-1], 1440)
+        self.log_interval_mins = parse_interval(&input)?;
+        self.interval_input.clear();
+        self.show_interval_popup = false;
+        Ok(())
+    }
+
+    /// The filter as it would be applied from the popup, with the typed severity levels.
+    pub fn pending_log_filter(&self) -> LogFilter {
+        let mut filter = self.log_filter.clone();
+        filter.val1 = self.filter_input_1.parse().unwrap_or(0);
+        filter.val2 = self.filter_input_2.parse().unwrap_or(15);
+        filter
+    }
+
+    /// Sets the level filter to one severity band, as the filter presets and dashboard cards do.
+    pub fn apply_severity_preset(&mut self, severity: severity::Severity) {
+        let (low, high) = self.severity.bounds(severity);
+        self.log_filter.mode = if high.is_some() { SeverityFilterMode::Range } else { SeverityFilterMode::Min };
+        self.log_filter.val1 = low as u32;
+        self.log_filter.val2 = high.unwrap_or(low) as u32;
+        self.filter_input_1 = self.log_filter.val1.to_string();
+        self.filter_input_2 = self.log_filter.val2.to_string();
+    }
+
+    /// Pretty OpenSearch query that applying the filter popup would run for the current view.
+    pub fn filter_query_preview(&self) -> String {
+        let filter = self.pending_log_filter();
+        let query = if self.active_view == ActiveView::AgentInspector {
+            let agent_id = self.get_selected_agent().map(|a| a.id.clone());
+            crate::api::log_search_query(agent_id.as_deref(), self.log_interval_mins, 0, 100, Some(&filter), &self.severity)
         } else {
-            (input.as_str(), 1) // default minutes
+            crate::api::log_search_query(None, self.log_interval_mins, 0, 50, Some(&filter), &self.severity)
         };
-
-        match val_str.parse::<u32>() {
-            Ok(val) => {
-                self.log_interval_mins = val * unit;
-                self.interval_input.clear();
-                self.show_interval_popup = false;
-                Ok(())
-            }
-            Err(_) => Err("Invalid number format".to_string())
-        }
+        serde_json::to_string_pretty(&query).unwrap_or_default()
     }
 
     pub fn format_interval(&self) -> String {
-        if self.log_interval_mins >= 1440 && self.log_interval_mins % 1440 == 0 {
-            format!("{}d", self.log_interval_mins / 1440)
-        } else if self.log_interval_mins >= 60 && self.log_interval_mins % 60 == 0 {
-            format!("{}h", self.log_interval_mins / 60)
-        } else {
-            format!("{}m", self.log_interval_mins)
-        }
+        format_interval_mins(self.log_interval_mins)
     }
 
     pub fn get_spinner_char(&self) -> &str {
-        let frames = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+        let frames = self.icons.spinner();
         frames[self.spinner_index % frames.len()]
     }
 
@@ -519,12 +1544,38 @@ impl App {
             ("Search", "Start searching in the current view"),
             ("Refresh", "Refresh the current view"),
             ("Help", "Show help popup"),
+            ("Tour", "Replay the onboarding tour of the dashboard, agents and alert filter"),
             ("Quit", "Quit the application"),
             ("Dashboard", "Go to Dashboard"),
             ("Agent List", "Go to Agent List"),
             ("Security Events", "Go to Security Events"),
             ("Group Management", "Go to Group Management"),
+            ("Users & Roles", "Go to API Users & Roles"),
+            ("Re-login", "Log in as another API user for this session, e.g. for elevated access"),
+            ("Export Setup", "Write keys, display, profiles without passwords and saved filters to one TOML"),
+            ("Import Setup", "Load a setup exported on another machine into config.toml and this session"),
+            ("CDB Lists", "Browse and edit CDB lists (blocked IPs, allowed users, ...)"),
+            ("Test Log Line", "Run a raw log line through the decoders and rules (logtest)"),
+            ("Rule Files", "Edit local rule and decoder files and restart the manager"),
+            ("FIM Scan", "Run a syscheck scan now on the selected agents"),
+            ("Run Command", "Run a whitelisted diagnostic command on the agent via active response"),
+            ("Command Output", "Show the output of the last command run on an agent"),
+            ("Outdated Agents", "Show agents older than the manager"),
+            ("Cleanup Stale Agents", "Delete old never-connected/pending agents"),
+            ("Export Report", "Write a Markdown security summary of the dashboard"),
+            ("Activity Log", "Show restarts, upgrades and other changes made from this tool"),
+            ("Bulk Progress", "Batches of the last bulk restart or upgrade and what failed"),
+            ("Pending Actions", "Actions queued for disconnected agents, run when they reconnect"),
+            ("Agents by Node", "Agent counts per cluster node; Enter lists the agents of a node"),
+            ("Notifications", "Review past notifications, including expired error toasts"),
+            ("Indexer Health", "Cluster status, Wazuh index sizes and indexer disk usage"),
+            ("TLS Certificates", "Issuer, names and expiry of the manager and indexer certificates"),
+            ("Hunts", "Prebuilt hunts: SSH brute force, failed Windows logons, new sudo users, web 4xx floods"),
+            ("API Requests", "Debug: inspect the last API and OpenSearch calls that were sent"),
         ];
+        let commands = commands.into_iter()
+            .chain(self.saved_agent_searches.iter().map(|(slot, query)| (SAVED_SEARCH_LABELS[*slot as usize - 1], query.as_str())))
+            .collect::<Vec<_>>();
 
         if self.command_palette_input.is_empty() {
             return commands;
@@ -539,6 +1590,21 @@ impl App {
     }
 
     pub fn set_api(&mut self, api: WazuhApi) {
+        self.stale_agent_days = api.config.maintenance.stale_agent_days.unwrap_or(DEFAULT_STALE_AGENT_DAYS);
+        self.stale_keepalive_minutes = api.config.maintenance.stale_keepalive_minutes.unwrap_or(DEFAULT_STALE_KEEPALIVE_MINUTES);
+        self.disconnected_window_hours = api.config.maintenance.disconnected_window_hours.filter(|h| *h > 0).unwrap_or(DEFAULT_DISCONNECTED_WINDOW_HOURS);
+        self.export_max_alerts = api.config.export.max_alerts.filter(|m| *m > 0).unwrap_or(DEFAULT_EXPORT_MAX_ALERTS);
+        self.export_compression = api.config.export.compress.as_deref().and_then(export::ExportCompression::from_config).unwrap_or_default();
+        self.export_naming = export::ExportNaming { dir: api.config.export.dir.clone(), filename: api.config.export.filename.clone() };
+        self.display_tz = time::DisplayTz::from_config(&api.config);
+        self.severity = severity::SeverityScale::from_config(&api.config);
+        self.icons = crate::ui::icons::IconSet::from_config(&api.config);
+        self.keymap = keymap::KeyMap::from_config(&api.config);
+        self.relative_time = api.config.display.relative_time.unwrap_or(false);
+        self.confirm_agent_actions = api.config.confirm.agent_actions.unwrap_or(true);
+        self.profile_apis = api.config.profiles.iter()
+            .map(|p| (p.name.clone(), WazuhApi::new(p.to_config(&api.config))))
+            .collect();
         self.api = Some(api);
     }
 
@@ -546,6 +1612,11 @@ impl App {
         match self.active_view {
             ActiveView::Dashboard => {}
             ActiveView::AgentInspector => {}
+            ActiveView::GroupManagement if self.group_files_focus => {
+                if !self.group_files.is_empty() {
+                    self.group_file_index = (self.group_file_index + 1).min(self.group_files.len() - 1);
+                }
+            }
             ActiveView::GroupManagement => {
                 let len = if self.is_searching {
                     let query = self.search_query.clone();
@@ -559,6 +1630,30 @@ impl App {
                     self.groups_table_state.select(Some(next));
                 }
             }
+            ActiveView::UserManagement => {
+                let len = self.security_users.len();
+                if len > 0 {
+                    let current = self.users_table_state.selected().unwrap_or(0);
+                    self.users_table_state.select(Some((current + 1) % len));
+                }
+            }
+            ActiveView::CdbLists if self.cdb_entries_focus => {
+                let len = self.selected_cdb_list().map(|l| l.items.len()).unwrap_or(0);
+                if len > 0 {
+                    self.cdb_entry_index = (self.cdb_entry_index + 1).min(len - 1);
+                }
+            }
+            ActiveView::CdbLists => {
+                if !self.cdb_lists.is_empty() {
+                    self.cdb_list_index = (self.cdb_list_index + 1) % self.cdb_lists.len();
+                    self.cdb_entry_index = 0;
+                }
+            }
+            ActiveView::Rules => {
+                if !self.rule_files.is_empty() {
+                    self.rule_file_index = (self.rule_file_index + 1) % self.rule_files.len();
+                }
+            }
             _ => {
                 if !self.agents.is_empty() {
                     self.selected_agent_index = (self.selected_agent_index + 1) % self.agents.len();
@@ -572,6 +1667,9 @@ impl App {
         match self.active_view {
             ActiveView::Dashboard => {}
             ActiveView::AgentInspector => {}
+            ActiveView::GroupManagement if self.group_files_focus => {
+                self.group_file_index = self.group_file_index.saturating_sub(1);
+            }
             ActiveView::GroupManagement => {
                 let len = if self.is_searching {
                     let query = self.search_query.clone();
@@ -585,6 +1683,28 @@ impl App {
                     self.groups_table_state.select(Some(next));
                 }
             }
+            ActiveView::UserManagement => {
+                let len = self.security_users.len();
+                if len > 0 {
+                    let current = self.users_table_state.selected().unwrap_or(0);
+                    let next = if current > 0 { current - 1 } else { len - 1 };
+                    self.users_table_state.select(Some(next));
+                }
+            }
+            ActiveView::CdbLists if self.cdb_entries_focus => {
+                self.cdb_entry_index = self.cdb_entry_index.saturating_sub(1);
+            }
+            ActiveView::CdbLists => {
+                if !self.cdb_lists.is_empty() {
+                    self.cdb_list_index = if self.cdb_list_index > 0 { self.cdb_list_index - 1 } else { self.cdb_lists.len() - 1 };
+                    self.cdb_entry_index = 0;
+                }
+            }
+            ActiveView::Rules => {
+                if !self.rule_files.is_empty() {
+                    self.rule_file_index = if self.rule_file_index > 0 { self.rule_file_index - 1 } else { self.rule_files.len() - 1 };
+                }
+            }
             _ => {
                 if !self.agents.is_empty() {
                     if self.selected_agent_index > 0 {
@@ -610,8 +1730,10 @@ impl App {
                 let len = match self.inspector_tab {
                     InspectorTab::Processes => self.processes.len(),
                     InspectorTab::Programs => self.programs.len(),
-                    InspectorTab::Vulnerabilities => self.vulnerabilities.len(),
+                    InspectorTab::Vulnerabilities => self.filtered_vulnerabilities().len(),
+                    InspectorTab::Rootcheck => self.rootcheck.len(),
                     InspectorTab::Logs => self.agent_logs.len(),
+                    InspectorTab::Network => self.agent_connections.len(),
                     _ => 0,
                 };
                 if len > 0 {
@@ -621,9 +1743,9 @@ impl App {
                 }
             }
             ActiveView::SecurityEvents => {
-                if !self.logs.is_empty() {
+                if !self.alert_rows.is_empty() {
                     let current = self.table_state.selected().unwrap_or(0);
-                    let next = std::cmp::min(current + amount, self.logs.len() - 1);
+                    let next = std::cmp::min(current + amount, self.alert_rows.len() - 1);
                     self.table_state.select(Some(next));
                 }
             }
@@ -654,50 +1776,556 @@ impl App {
     }
 
     pub fn next_tab(&mut self) {
-        self.selected_tab_index = (self.selected_tab_index + 1) % 6;
+        self.set_inspector_tab((self.selected_tab_index + 1) % 9);
+    }
+
+    pub fn set_inspector_tab(&mut self, index: usize) {
+        self.selected_tab_index = index;
         self.inspector_tab = match self.selected_tab_index {
-            0 => InspectorTab::Hardware,
-            1 => InspectorTab::Processes,
-            2 => InspectorTab::Programs,
-            3 => InspectorTab::Vulnerabilities,
-            4 => InspectorTab::Logs,
-            5 => InspectorTab::Config,
-            _ => InspectorTab::Hardware,
+            0 => InspectorTab::Summary,
+            1 => InspectorTab::Hardware,
+            2 => InspectorTab::Processes,
+            3 => InspectorTab::Programs,
+            4 => InspectorTab::Vulnerabilities,
+            5 => InspectorTab::Rootcheck,
+            6 => InspectorTab::Logs,
+            7 => InspectorTab::Network,
+            8 => InspectorTab::Config,
+            _ => InspectorTab::Summary,
         };
         self.inspector_table_state.select(Some(0));
     }
 
+    /// Switches to `view`, remembering the current view, filters and selection for `navigate_back`.
+    pub fn navigate(&mut self, view: ActiveView) {
+        if self.active_view == view {
+            return;
+        }
+        tracing::debug!(from = ?self.active_view, to = ?view, "navigate");
+        self.nav_stack.push(nav::NavEntry::capture(self));
+        if self.nav_stack.len() > nav::NAV_HISTORY {
+            self.nav_stack.remove(0);
+        }
+        self.active_view = view;
+    }
+
+    /// Returns to the previous view with its filters and selection. With no history the
+    /// inspector still falls back to the agent list. False when there is nowhere to go.
+    pub fn navigate_back(&mut self) -> bool {
+        match self.nav_stack.pop() {
+            Some(entry) => {
+                tracing::debug!(from = ?self.active_view, to = ?entry.view, "navigate back");
+                entry.restore(self);
+                true
+            }
+            None if self.active_view == ActiveView::AgentInspector => {
+                self.active_view = ActiveView::AgentList;
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn get_selected_agent(&self) -> Option<&WazuhAgent> {
         self.agents.get(self.selected_agent_index)
     }
 
-    pub fn toggle_sort(&mut self, column: SortColumn) {
-        if self.sort_column == column {
-            self.sort_order = match self.sort_order {
-                SortOrder::Asc => SortOrder::Desc,
-                SortOrder::Desc => SortOrder::Asc,
-            };
-        } else {
-            self.sort_column = column;
-            self.sort_order = SortOrder::Asc;
+    /// Checked every tick: the agent the list preview should load, once the cursor has
+    /// stayed on it for a whole tick so scrolling through the list doesn't fire a load per row.
+    pub fn preview_agent_to_load(&mut self) -> Option<String> {
+        if !self.agent_preview || self.active_view != ActiveView::AgentList {
+            self.preview_candidate = None;
+            return None;
         }
-        self.sort_agents();
+        let agent = self.get_selected_agent().filter(|a| a.profile.is_none())?;
+        let id = agent.id.clone();
+        if self.inspector_agent_id.as_ref() == Some(&id) {
+            self.preview_candidate = None;
+            return None;
+        }
+        if self.preview_candidate.as_ref() == Some(&id) {
+            self.preview_candidate = None;
+            return Some(id);
+        }
+        self.preview_candidate = Some(id);
+        None
     }
 
-    pub fn cycle_sort(&mut self) {
-        if self.sort_order == SortOrder::Asc {
-            self.sort_order = SortOrder::Desc;
+    /// Drops the per-agent inspector data when switching to another agent, so the
+    /// previous agent's details are never shown under the new one's header.
+    pub fn reset_inspector_data(&mut self, agent_id: &str) {
+        if self.inspector_agent_id.as_deref() == Some(agent_id) {
+            return;
+        }
+        self.inspector_agent_id = Some(agent_id.to_string());
+        self.hardware = None;
+        self.manager_status = None;
+        self.processes.clear();
+        self.processes_total = None;
+        self.programs.clear();
+        self.programs_total = None;
+        self.vulnerabilities.clear();
+        self.agent_logs.clear();
+        self.agent_connections.clear();
+        self.rootcheck.clear();
+        self.rootcheck_scan = None;
+        self.agent_alert_hours = None;
+        self.agent_config = None;
+        self.config_baseline = None;
+    }
+
+    /// Agents a bulk action applies to: the selected ones, or else the highlighted agent.
+    /// Agents listed from other profiles are never targets; their IDs belong to another manager.
+    pub fn action_targets(&self) -> Vec<String> {
+        if !self.selected_agents.is_empty() {
+            let mut agent_ids: Vec<String> = self.selected_agents.iter().cloned().collect();
+            agent_ids.sort();
+            agent_ids
+        } else if let Some(agent) = self.get_selected_agent().filter(|a| a.profile.is_none()) {
+            vec![agent.id.clone()]
         } else {
-            self.sort_column = self.sort_column.next();
-            self.sort_order = SortOrder::Asc;
+            Vec::new()
         }
-        self.sort_agents();
     }
 
-    pub fn toggle_selection(&mut self) {
-        if let Some(agent) = self.get_selected_agent() {
-            let id = agent.id.clone();
-            if self.selected_agents.contains(&id) {
+    /// Opens the confirmation popup for `action` when it needs one. Returns the agents to
+    /// act on right away otherwise, without the manager for upgrades and restarts.
+    pub fn request_agent_action(&mut self, action: actions::AgentAction, agent_ids: Vec<String>) -> Option<Vec<String>> {
+        let agent_ids = match action {
+            actions::AgentAction::Upgrade => self.without_manager(agent_ids, "Upgrade"),
+            actions::AgentAction::Restart => self.without_manager(agent_ids, "Agent restart"),
+            actions::AgentAction::FimScan | actions::AgentAction::DeleteStale => agent_ids,
+        };
+        if agent_ids.is_empty() {
+            return None;
+        }
+        // Disconnected targets always ask, to offer queueing them
+        if action.needs_confirmation(agent_ids.len(), self.confirm_agent_actions) || !self.disconnected(action, &agent_ids).is_empty() {
+            self.popup_mode = PopupMode::ConfirmAgentAction { action, agent_ids };
+            return None;
+        }
+        Some(agent_ids)
+    }
+
+    /// The agents of `agent_ids` that `action` cannot reach now because they are disconnected.
+    pub fn disconnected(&self, action: actions::AgentAction, agent_ids: &[String]) -> Vec<String> {
+        if !action.needs_active_agent() {
+            return Vec::new();
+        }
+        agent_ids.iter()
+            .filter(|id| self.agents.iter().any(|a| &a.id == *id && a.profile.is_none() && a.status == "disconnected"))
+            .cloned()
+            .collect()
+    }
+
+    /// Queues `action` for the disconnected agents of `agent_ids` and returns the others, to
+    /// run now.
+    pub fn queue_disconnected(&mut self, action: actions::AgentAction, agent_ids: Vec<String>) -> Vec<String> {
+        let disconnected = self.disconnected(action, &agent_ids);
+        let mut queued = 0;
+        for id in &disconnected {
+            if let Some(agent) = self.agents.iter().find(|a| &a.id == id && a.profile.is_none()) {
+                if self.pending_actions.push(action, agent) {
+                    queued += 1;
+                }
+            }
+        }
+        if let Err(e) = self.pending_actions.save(&pending::PendingQueue::path()) {
+            self.notify(&format!("Failed to save queued actions: {}", e), NotificationLevel::Error);
+        }
+        self.notify(&format!("{} queued for {} disconnected agents; it runs when they reconnect", action.verb(), queued), NotificationLevel::Info);
+        agent_ids.into_iter().filter(|id| !disconnected.contains(id)).collect()
+    }
+
+    /// Takes the queued actions whose agent is active in `agents` (the agent list when `None`).
+    /// Restarts and upgrades stay queued while a bulk operation runs. Actions the current user
+    /// may not run are dropped, since the user can have changed since they were queued.
+    pub fn take_ready_pending_actions(&mut self, agents: Option<&[WazuhAgent]>) -> Vec<(actions::AgentAction, Vec<String>)> {
+        if self.pending_actions.is_empty() {
+            return Vec::new();
+        }
+        let bulk_busy = self.bulk_op.as_ref().is_some_and(|op| !op.is_finished());
+        let agents = agents.unwrap_or(&self.agents);
+        let ready = self.pending_actions.take_ready(agents, |action| {
+            bulk_busy && matches!(action, actions::AgentAction::Upgrade | actions::AgentAction::Restart)
+        });
+        if ready.is_empty() {
+            return ready;
+        }
+        if let Err(e) = self.pending_actions.save(&pending::PendingQueue::path()) {
+            self.notify(&format!("Failed to save queued actions: {}", e), NotificationLevel::Error);
+        }
+        let ready: Vec<_> = ready.into_iter().filter(|(action, _)| {
+            let (permission, label) = action.permission();
+            self.require_permission(permission, label)
+        }).collect();
+        for (action, agent_ids) in &ready {
+            self.notify(&format!("Agents reconnected: running the queued {} on {}", action.verb().to_lowercase(), agent_ids.join(", ")), NotificationLevel::Info);
+        }
+        self.pending_index = self.pending_index.min(self.pending_actions.actions.len().saturating_sub(1));
+        ready
+    }
+
+    /// Drops the queued action selected in the Pending actions panel.
+    pub fn remove_pending_action(&mut self) {
+        if self.pending_index >= self.pending_actions.actions.len() {
+            return;
+        }
+        let removed = self.pending_actions.actions.remove(self.pending_index);
+        self.pending_index = self.pending_index.min(self.pending_actions.actions.len().saturating_sub(1));
+        match self.pending_actions.save(&pending::PendingQueue::path()) {
+            Ok(()) => self.notify(&format!("Dropped the queued {} of agent {}", removed.action.verb().to_lowercase(), removed.agent_id), NotificationLevel::Info),
+            Err(e) => self.notify(&format!("Failed to save queued actions: {}", e), NotificationLevel::Error),
+        }
+    }
+
+    /// Drops agent 000 from the targets of an action the agent endpoints refuse for the
+    /// manager, and says so instead of letting the request fail silently.
+    pub fn without_manager(&mut self, mut agent_ids: Vec<String>, action: &str) -> Vec<String> {
+        let before = agent_ids.len();
+        agent_ids.retain(|id| id != crate::models::MANAGER_AGENT_ID);
+        if agent_ids.len() < before {
+            let skipped = if agent_ids.is_empty() { String::new() } else { ", skipped".to_string() };
+            self.notify(&format!("{} does not apply to the manager (agent 000){}", action, skipped), NotificationLevel::Warning);
+        }
+        agent_ids
+    }
+
+    pub fn set_rootcheck(&mut self, mut findings: Vec<crate::models::WazuhRootcheckItem>, last_scan: Option<crate::models::WazuhLastScan>) {
+        findings.sort_by(|a, b| b.is_outstanding().cmp(&a.is_outstanding()).then_with(|| b.date_last.cmp(&a.date_last)));
+        self.rootcheck = findings;
+        self.rootcheck_scan = last_scan;
+    }
+
+    /// Baseline settings of the current config component checked against the agent, or
+    /// `None` while either side is still loading.
+    pub fn config_drift(&self) -> Option<Result<Vec<config_diff::ConfigDrift>, String>> {
+        let files = match self.config_baseline.as_ref()? {
+            Ok(files) => files,
+            Err(e) => return Some(Err(e.clone())),
+        };
+        let config = self.agent_config.as_ref()?;
+        let agent = self.inspector_agent_id.as_ref().and_then(|id| self.agents.iter().find(|a| &a.id == id))?;
+        let os = agent.os.as_ref()
+            .map(|o| [o.name.as_deref(), o.platform.as_deref()].iter().flatten().copied().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default();
+
+        let mut baseline = Vec::new();
+        for (group, content) in files {
+            match config_diff::baseline_settings(group, content, &self.agent_config_component, &agent.name, &os) {
+                Ok(settings) => baseline.extend(settings),
+                Err(e) => return Some(Err(format!("{}/agent.conf: {}", group, e))),
+            }
+        }
+        Some(Ok(config_diff::compare(&baseline, config)))
+    }
+
+    /// Restarts the debounce after the description filter changed; only Security Events queries live.
+    pub fn schedule_live_search(&mut self) {
+        if self.active_view == ActiveView::SecurityEvents {
+            self.live_search_due = Some(Instant::now() + std::time::Duration::from_millis(LIVE_SEARCH_DEBOUNCE_MS));
+        }
+    }
+
+    /// True once typing paused long enough; the caller then runs the query.
+    pub fn live_search_ready(&mut self, now: Instant) -> bool {
+        match self.live_search_due {
+            Some(due) if now >= due => {
+                self.live_search_due = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Shown next to the description filter while a live query is pending.
+    pub fn live_search_status(&self) -> Option<&'static str> {
+        if self.live_search_due.is_some() {
+            Some("waiting for typing to pause")
+        } else if self.live_search_task.as_ref().map(|t| !t.is_finished()).unwrap_or(false) {
+            Some("searching...")
+        } else {
+            None
+        }
+    }
+
+    pub fn start_fim_scans(&mut self, agent_ids: Vec<String>) {
+        let now = chrono::Utc::now();
+        for id in agent_ids {
+            self.fim_scans.insert(id, fim::FimScan::new(now));
+        }
+    }
+
+    /// Agents whose requested scan is due for another `last_scan` poll.
+    pub fn fim_scans_to_poll(&mut self) -> Vec<String> {
+        let now = chrono::Utc::now();
+        let tick = Instant::now();
+        let mut due = Vec::new();
+        for (id, scan) in self.fim_scans.iter_mut() {
+            if scan.is_pending(now) && scan.next_poll <= tick {
+                scan.next_poll = tick + std::time::Duration::from_secs(fim::FIM_POLL_SECS);
+                due.push(id.clone());
+            }
+        }
+        due.sort();
+        due
+    }
+
+    pub fn set_fim_progress(&mut self, agent_id: &str, last_scan: Option<crate::models::WazuhLastScan>) {
+        let now = chrono::Utc::now();
+        let Some(scan) = self.fim_scans.get_mut(agent_id) else { return };
+        let was_pending = scan.is_pending(now);
+        scan.last_scan = last_scan;
+        if was_pending && matches!(scan.state(now), fim::FimScanState::Finished { .. }) {
+            let message = format!("{} on agent {}", scan.label(now), agent_id);
+            self.notify(&message, NotificationLevel::Success);
+        }
+    }
+
+    pub fn fim_scans_running(&self) -> usize {
+        let now = chrono::Utc::now();
+        self.fim_scans.values().filter(|s| s.is_pending(now)).count()
+    }
+
+    pub fn toggle_sort(&mut self, column: SortColumn) {
+        if self.sort_column == column {
+            self.sort_order = match self.sort_order {
+                SortOrder::Asc => SortOrder::Desc,
+                SortOrder::Desc => SortOrder::Asc,
+            };
+        } else {
+            self.sort_column = column;
+            self.sort_order = SortOrder::Asc;
+        }
+        self.sort_agents();
+    }
+
+    pub fn cycle_sort(&mut self) {
+        if self.sort_order == SortOrder::Asc {
+            self.sort_order = SortOrder::Desc;
+        } else {
+            self.sort_column = self.sort_column.next();
+            self.sort_order = SortOrder::Asc;
+        }
+        self.sort_agents();
+    }
+
+    /// Adds or removes a severity chip and goes back to the first page.
+    pub fn toggle_log_severity(&mut self, severity: &str) {
+        let chips = &mut self.log_filter.severities;
+        if let Some(pos) = chips.iter().position(|s| s == severity) {
+            chips.remove(pos);
+        } else {
+            chips.push(severity.to_string());
+            chips.sort_by_key(|s| ALERT_SEVERITIES.iter().position(|a| a == s));
+        }
+        self.log_offset = 0;
+        self.table_state.select(Some(0));
+    }
+
+    /// Newest/highest first, then ascending, then the next sort key. Goes back to the
+    /// first page since the order of every page changes.
+    pub fn cycle_log_sort(&mut self) {
+        if self.log_filter.sort_order == SortOrder::Desc {
+            self.log_filter.sort_order = SortOrder::Asc;
+        } else {
+            self.log_filter.sort = self.log_filter.sort.next();
+            self.log_filter.sort_order = SortOrder::Desc;
+        }
+        self.log_offset = 0;
+        self.table_state.select(Some(0));
+    }
+
+    /// Stores the current agent search in `slot`; an empty search clears the slot.
+    pub fn save_agent_search(&mut self, slot: u8) {
+        let query = self.search_query.trim().to_string();
+        if query.is_empty() {
+            self.saved_agent_searches.remove(&slot);
+            self.notify(&format!("Cleared saved search {}", slot), NotificationLevel::Info);
+        } else {
+            self.notify(&format!("Saved search {}: {}", slot, query), NotificationLevel::Success);
+            self.saved_agent_searches.insert(slot, query);
+        }
+    }
+
+    /// Lists the agents of the node highlighted in the Agents by Node popup.
+    pub fn show_node_agents(&mut self) {
+        let nodes = nodes::node_summaries(&self.agents);
+        let Some(node) = nodes.get(self.node_index) else { return };
+        let Some(query) = node.filter_query() else {
+            self.notify("Agents without a node cannot be filtered", NotificationLevel::Warning);
+            return;
+        };
+        self.popup_mode = PopupMode::None;
+        self.is_searching = false;
+        self.agent_filter = AgentFilter::parse(&query);
+        self.search_query = query;
+        self.active_view = ActiveView::AgentList;
+        self.table_state.select(Some(0));
+    }
+
+    /// Nodes whose agents are all disconnected, when the manager is a cluster.
+    pub fn dark_nodes(&self) -> Vec<String> {
+        let nodes = nodes::node_summaries(&self.agents);
+        if nodes.len() < 2 {
+            return Vec::new();
+        }
+        nodes.into_iter().filter(|n| n.is_dark()).map(|n| n.node).collect()
+    }
+
+    /// Applies the search saved in `slot` to the agent list.
+    pub fn apply_agent_search(&mut self, slot: u8) {
+        let Some(query) = self.saved_agent_searches.get(&slot).cloned() else {
+            self.notify(&format!("No search saved in slot {} (type a search, then Alt+{})", slot, slot), NotificationLevel::Warning);
+            return;
+        };
+        self.is_searching = false;
+        self.agent_filter = AgentFilter::parse(&query);
+        self.search_query = query;
+        self.active_view = ActiveView::AgentList;
+        self.table_state.select(Some(0));
+    }
+
+    /// Commands "Run command" may send: `[remote_commands] allowed`, or the defaults.
+    pub fn remote_commands_allowed(&self) -> Vec<String> {
+        self.api.as_ref()
+            .and_then(|a| a.config.remote_commands.allowed.clone())
+            .unwrap_or_else(|| remote_cmd::DEFAULT_COMMANDS.iter().map(|c| c.to_string()).collect())
+    }
+
+    /// Opens the command picker for the highlighted agent.
+    pub fn open_run_command(&mut self) {
+        if !self.require_permission(ACTION_ACTIVE_RESPONSE, "Run command") {
+            return;
+        }
+        let Some(agent) = self.get_selected_agent() else { return };
+        if agent.status != "active" {
+            self.notify(&format!("Agent {} is {}; commands need a connected agent", agent.id, agent.status), NotificationLevel::Warning);
+            return;
+        }
+        let agent_id = agent.id.clone();
+        self.remote_command_index = 0;
+        self.popup_mode = PopupMode::RunCommand { agent_id };
+    }
+
+    /// The picked command goes to a confirm step; nothing is sent yet.
+    pub fn stage_run_command(&mut self) {
+        let PopupMode::RunCommand { agent_id } = &self.popup_mode else { return };
+        let agent_id = agent_id.clone();
+        if let Some(command) = self.remote_commands_allowed().get(self.remote_command_index).cloned() {
+            self.popup_mode = PopupMode::ConfirmRunCommand { agent_id, command };
+        }
+    }
+
+    /// Records the command as sent and switches to the output popup.
+    pub fn start_remote_command(&mut self, agent_id: &str, command: &str) -> remote_cmd::RemoteCommand {
+        let run = remote_cmd::RemoteCommand::new(agent_id, command, chrono::Utc::now());
+        self.remote_command = Some(run.clone());
+        self.remote_output_scroll = 0;
+        self.popup_mode = PopupMode::CommandOutput;
+        run
+    }
+
+    /// Checked every tick: the command id to search for, when a poll is due.
+    pub fn remote_command_to_poll(&mut self) -> Option<String> {
+        let run = self.remote_command.as_mut()?;
+        if !run.is_pending(chrono::Utc::now()) || run.next_poll > Instant::now() {
+            return None;
+        }
+        run.next_poll = Instant::now() + std::time::Duration::from_secs(remote_cmd::RESULT_POLL_SECS);
+        Some(run.id.clone())
+    }
+
+    pub fn set_remote_command_result(&mut self, id: &str, result: Result<remote_cmd::CommandResult, String>) {
+        if let Some(run) = self.remote_command.as_mut().filter(|r| r.id == id) {
+            run.result = Some(result);
+        }
+    }
+
+    /// Applies a heartbeat result; true when the API came back and the data should be reloaded.
+    pub fn set_heartbeat(&mut self, result: Result<u64, String>) -> bool {
+        let error = result.as_ref().err().cloned();
+        match self.heartbeat.record(result, chrono::Utc::now(), Instant::now()) {
+            Some(conn::Transition::Lost) => {
+                tracing::warn!(error = ?error, "API unreachable");
+                self.notify(&format!("Lost connection to the Wazuh API: {}", error.unwrap_or_default()), NotificationLevel::Error);
+                false
+            }
+            Some(conn::Transition::Reconnected) => {
+                tracing::info!("API reachable again");
+                self.notify("Reconnected to the Wazuh API", NotificationLevel::Success);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// `user@ip` from the SSH username popup, once a username is typed.
+    pub fn ssh_target(&self) -> Option<String> {
+        let PopupMode::SshUsername { agent_ip, .. } = &self.popup_mode else { return None };
+        let username = self.input_buffer.trim();
+        (!username.is_empty() && !agent_ip.is_empty()).then(|| format!("{}@{}", username, agent_ip))
+    }
+
+    pub fn ssh_output(&mut self, bytes: &[u8]) {
+        let Some(session) = self.ssh_session.as_mut() else { return };
+        if bytes.is_empty() {
+            let message = session.finish();
+            self.notify(&message, NotificationLevel::Info);
+        } else {
+            session.screen.feed(bytes);
+        }
+    }
+
+    /// Ends the embedded SSH session (killing ssh if it still runs) and closes the pane.
+    pub fn close_ssh_session(&mut self) {
+        self.ssh_session = None;
+        if self.popup_mode == PopupMode::SshSession {
+            self.popup_mode = PopupMode::None;
+        }
+    }
+
+    /// Opens the tagging popup for the selected agents, or the highlighted one.
+    pub fn open_agent_labels(&mut self) {
+        let agent_ids: Vec<String> = if !self.selected_agents.is_empty() {
+            self.selected_agents.iter().cloned().collect()
+        } else if let Some(agent) = self.get_selected_agent() {
+            vec![agent.id.clone()]
+        } else {
+            return;
+        };
+        self.input_buffer.clear();
+        self.popup_mode = PopupMode::AgentLabels { agent_ids };
+    }
+
+    /// Applies the typed tag edits and writes `labels.toml`.
+    pub fn apply_agent_labels(&mut self) {
+        let PopupMode::AgentLabels { agent_ids } = &self.popup_mode else { return };
+        let agent_ids = agent_ids.clone();
+        let edits = labels::parse_edits(&self.input_buffer);
+        if edits.is_empty() {
+            self.notify("Type a tag, or -tag to remove one", NotificationLevel::Warning);
+            return;
+        }
+        let changed = self.agent_labels.apply(&agent_ids, &edits);
+        self.popup_mode = PopupMode::None;
+        match self.agent_labels.save(&labels::AgentLabels::path()) {
+            Ok(()) => self.notify(&format!("Updated tags on {} of {} agents", changed, agent_ids.len()), NotificationLevel::Success),
+            Err(e) => self.notify(&format!("Failed to save tags: {}", e), NotificationLevel::Error),
+        }
+    }
+
+    pub fn toggle_selection(&mut self) {
+        if self.active_view == ActiveView::SecurityEvents {
+            self.toggle_alert_selection();
+            return;
+        }
+        if self.refuse_profile_agent() {
+            return;
+        }
+        if let Some(agent) = self.get_selected_agent() {
+            let id = agent.id.clone();
+            if self.selected_agents.contains(&id) {
                 self.selected_agents.remove(&id);
             } else {
                 self.selected_agents.insert(id);
@@ -705,7 +2333,209 @@ impl App {
         }
     }
 
+    fn toggle_alert_selection(&mut self) {
+        let Some(log) = self.selected_alert() else { return };
+        let Some(id) = alert_id(log).map(|s| s.to_string()) else { return };
+        if let Some(pos) = self.selected_alerts.iter().position(|l| alert_id(l) == Some(id.as_str())) {
+            self.selected_alerts.remove(pos);
+        } else {
+            self.selected_alerts.push(log.clone());
+        }
+    }
+
+    pub fn is_alert_selected(&self, log: &serde_json::Value) -> bool {
+        alert_id(log).map(|id| self.selected_alerts.iter().any(|l| alert_id(l) == Some(id))).unwrap_or(false)
+    }
+
+    /// Records the alerts of `logs` missing from the current page. Only a refresh of the
+    /// same page counts: after paging, or when nothing overlaps (new filter), every
+    /// alert would otherwise look new.
+    fn mark_new_alerts(&mut self, logs: &[serde_json::Value]) {
+        self.new_alerts.retain(|_, arrived| arrived.elapsed().as_secs() < NEW_ALERT_SECS);
+        let previous: std::collections::HashSet<&str> = self.logs.iter().filter_map(alert_id).collect();
+        let same_page = self.log_offset == self.loaded_log_offset
+            && logs.iter().filter_map(alert_id).any(|id| previous.contains(id));
+        self.loaded_log_offset = self.log_offset;
+        if !same_page {
+            return;
+        }
+        let now = Instant::now();
+        for id in logs.iter().filter_map(alert_id).filter(|id| !previous.contains(id)) {
+            self.new_alerts.insert(id.to_string(), now);
+        }
+    }
+
+    pub fn is_alert_new(&self, log: &serde_json::Value) -> bool {
+        alert_id(log)
+            .and_then(|id| self.new_alerts.get(id))
+            .map(|arrived| arrived.elapsed().as_secs() < NEW_ALERT_SECS)
+            .unwrap_or(false)
+    }
+
+    pub fn is_alert_acked(&self, log: &serde_json::Value) -> bool {
+        alert_id(log).map(|id| self.acked_alerts.contains(id)).unwrap_or(false)
+    }
+
+    pub fn selected_alert_ids(&self) -> Vec<String> {
+        self.selected_alerts.iter().filter_map(alert_id).map(|s| s.to_string()).collect()
+    }
+
+    /// Marks the selected alerts as acknowledged, records it in the audit log and clears the selection.
+    pub fn acknowledge_selected_alerts(&mut self) -> usize {
+        let ids = self.selected_alert_ids();
+        if ids.is_empty() {
+            return 0;
+        }
+        self.acked_alerts.extend(ids.iter().cloned());
+        self.selected_alerts.clear();
+        let detail = format!("Acknowledged {} alerts", ids.len());
+        self.record_audit(audit::AuditEntry::new("ack alerts", &ids, true, &detail));
+        ids.len()
+    }
+
+    /// Replaces the agents after a refresh, keeping the highlighted agent selected by id,
+    /// or the row at the same position when it is gone.
+    pub fn set_agents(&mut self, mut agents: Vec<WazuhAgent>) {
+        let selected = self.get_selected_agent().map(|a| (a.profile.clone(), a.id.clone()));
+        if self.all_profiles {
+            agents.extend(self.profile_agents.iter().cloned());
+        }
+        self.agents = agents;
+        self.sort_agents();
+        let index = selected
+            .and_then(|(profile, id)| self.agents.iter().position(|a| a.profile == profile && a.id == id))
+            .unwrap_or_else(|| self.selected_agent_index.min(self.agents.len().saturating_sub(1)));
+        self.selected_agent_index = index;
+        // The table state is shared with Security Events; only move it when it shows agents
+        if self.active_view == ActiveView::AgentList {
+            self.table_state.select(Some(index));
+        }
+    }
+
+    /// This manager's agents, without those merged in from the profiles.
+    fn own_agents(&self) -> Vec<WazuhAgent> {
+        self.agents.iter().filter(|a| a.profile.is_none()).cloned().collect()
+    }
+
+    /// Replaces one profile's agents with a fresh load, or records why it failed; the
+    /// agents it had before stay listed until a load succeeds.
+    pub fn set_profile_agents(&mut self, profile: &str, result: Result<Vec<WazuhAgent>, String>) {
+        self.profile_errors.retain(|(name, _)| name != profile);
+        match result {
+            Ok(agents) => {
+                self.profile_agents.retain(|a| a.profile.as_deref() != Some(profile));
+                self.profile_agents.extend(agents.into_iter().map(|a| WazuhAgent { profile: Some(profile.to_string()), ..a }));
+            }
+            Err(e) => self.profile_errors.push((profile.to_string(), e)),
+        }
+        if self.all_profiles {
+            self.set_agents(self.own_agents());
+        }
+    }
+
+    /// Switches between this manager's agents and the merged list of all profiles.
+    pub fn toggle_all_profiles(&mut self) {
+        self.all_profiles = !self.all_profiles;
+        self.set_agents(self.own_agents());
+    }
+
+    /// Label of the manager an agent belongs to, for the Manager column.
+    pub fn agent_manager_label<'a>(&'a self, agent: &'a WazuhAgent) -> &'a str {
+        match &agent.profile {
+            Some(profile) => profile,
+            None => self.api.as_ref()
+                .map(|api| api.config.url.split("://").last().unwrap_or("").split([':', '/']).next().unwrap_or(""))
+                .unwrap_or("this manager"),
+        }
+    }
+
+    /// Agents of other profiles are listed for overview only; actions and the inspector
+    /// go through this connection's API and would hit the wrong manager.
+    pub fn refuse_profile_agent(&mut self) -> bool {
+        let Some((name, profile)) = self.get_selected_agent().and_then(|a| Some((a.name.clone(), a.profile.clone()?))) else { return false };
+        self.notify(&format!("{} is listed from profile '{}'; open that manager to act on it", name, profile), NotificationLevel::Warning);
+        true
+    }
+
+    /// Replaces the Security Events page, keeping the highlighted alert selected by `_id`,
+    /// or the row at the same position when it is no longer on the page.
+    pub fn set_logs(&mut self, logs: Vec<serde_json::Value>) {
+        self.mark_new_alerts(&logs);
+        if self.active_view != ActiveView::SecurityEvents {
+            self.logs = logs;
+            self.alert_rows = self.build_alert_rows();
+            return;
+        }
+        let current = self.table_state.selected();
+        let selected_id = self.selected_alert().and_then(alert_id).map(str::to_string);
+        self.logs = logs;
+        self.alert_rows = self.build_alert_rows();
+        let index = selected_id
+            .and_then(|id| self.logs.iter().position(|l| alert_id(l) == Some(id.as_str())))
+            .map(|log| self.row_of_log(log))
+            .unwrap_or_else(|| current.unwrap_or(0).min(self.alert_rows.len().saturating_sub(1)));
+        self.table_state.select(Some(index));
+    }
+
+    /// The alert under the cursor in Security Events.
+    pub fn selected_alert(&self) -> Option<&serde_json::Value> {
+        self.table_state.selected()
+            .and_then(|i| self.alert_rows.get(i))
+            .and_then(|row| self.logs.get(row.log))
+    }
+
+    fn build_alert_rows(&self) -> Vec<flood::AlertRow> {
+        // The raw view lists every event as the indexer returned it
+        if self.group_floods && self.log_view_mode == LogViewMode::Table {
+            flood::group_floods(&self.logs, &self.expanded_floods)
+        } else {
+            flood::ungrouped(self.logs.len())
+        }
+    }
+
+    /// Row showing `logs[log]`: its own, or the head of the collapsed flood it belongs to.
+    fn row_of_log(&self, log: usize) -> usize {
+        self.alert_rows.iter().rposition(|row| row.log <= log).unwrap_or(0)
+    }
+
+    /// Rebuilds the table rows after grouping changed, keeping the cursor on the same alert.
+    pub fn regroup_alerts(&mut self) {
+        let selected = self.table_state.selected().and_then(|i| self.alert_rows.get(i)).map(|row| row.log);
+        self.alert_rows = self.build_alert_rows();
+        if let Some(log) = selected {
+            self.table_state.select(Some(self.row_of_log(log)));
+        }
+    }
+
+    pub fn toggle_flood_grouping(&mut self) {
+        self.group_floods = !self.group_floods;
+        self.regroup_alerts();
+    }
+
+    /// Lists or collapses the flood under the cursor, which may be its head or one of its
+    /// listed alerts. Returns whether anything changed.
+    pub fn expand_selected_flood(&mut self, expand: bool) -> bool {
+        let Some(index) = self.table_state.selected().filter(|i| *i < self.alert_rows.len()) else { return false };
+        // A listed alert belongs to the nearest head above it
+        let Some(head) = self.alert_rows[..=index].iter().rev().find(|row| !row.member).copied() else { return false };
+        if head.count == 1 {
+            return false;
+        }
+        let Some(id) = self.logs.get(head.log).and_then(alert_id).map(str::to_string) else { return false };
+        let changed = if expand {
+            self.expanded_floods.insert(id)
+        } else {
+            self.expanded_floods.remove(&id)
+        };
+        if changed {
+            self.alert_rows = self.build_alert_rows();
+            self.table_state.select(Some(self.row_of_log(head.log)));
+        }
+        changed
+    }
+
     pub fn sort_agents(&mut self) {
+        let scores = if self.sort_column == SortColumn::Health { self.health_scores() } else { Default::default() };
         self.agents.sort_by(|a, b| {
             let res = match self.sort_column {
                 SortColumn::Id => a.id.cmp(&b.id),
@@ -718,6 +2548,7 @@ impl App {
                     os_a.cmp(&os_b)
                 },
                 SortColumn::LastKeepAlive => a.last_keep_alive.cmp(&b.last_keep_alive),
+                SortColumn::Health => scores.get(&a.id).cmp(&scores.get(&b.id)),
             };
             if self.sort_order == SortOrder::Desc {
                 res.reverse()
@@ -725,6 +2556,519 @@ impl App {
                 res
             }
         });
+        self.sort_agents_by_vulns();
+    }
+
+    /// Index patterns typed in the wizard; defaults and blanks are left out of the config.
+    pub fn wizard_indices(&self) -> crate::models::IndicesConfig {
+        let custom = |value: &str, default: &str| Some(value.trim()).filter(|v| !v.is_empty() && *v != default).map(String::from);
+        crate::models::IndicesConfig {
+            alerts: custom(&self.config_alerts_index, crate::models::DEFAULT_ALERTS_INDEX),
+            vulnerabilities: custom(&self.config_vuln_index, crate::models::DEFAULT_VULNERABILITIES_INDEX),
+        }
+    }
+
+    /// Applies an imported setup to this session; returns the profiles that need a password.
+    pub fn import_setup(&mut self, bundle: &bundle::SetupBundle) -> Vec<String> {
+        bundle.apply_ui(self);
+        let Some(mut api) = self.api.clone() else { return Vec::new() };
+        let missing = bundle.apply_to_config(&mut api.config);
+        self.set_api(api);
+        missing
+    }
+
+    /// Switches the session to the credentials the Re-login popup verified; the token of the
+    /// new user is already in place. Permissions are reloaded for the new user.
+    pub fn apply_relogin(&mut self, username: &str, password: &str) {
+        if let Some(api) = self.api.as_mut() {
+            api.set_credentials(username, password);
+        }
+        self.permissions = None;
+    }
+
+    /// Stamps the entry with the API user, appends it to the audit file and keeps it for the Activity popup.
+    pub fn record_audit(&mut self, mut entry: audit::AuditEntry) {
+        if let Some(api) = &self.api {
+            entry.user = api.config.username.clone();
+        }
+        if let Err(e) = audit::append(&audit::audit_path(), &entry) {
+            self.notify(&format!("Failed to write audit log: {}", e), NotificationLevel::Warning);
+        }
+        self.audit_log.push(entry);
+        if self.audit_log.len() > audit::AUDIT_HISTORY {
+            self.audit_log.remove(0);
+        }
+    }
+
+    /// Keys 1-4 in the Agent List: show only agents with CVEs of that severity, most affected first.
+    /// Pressing the same key again restores the regular sort.
+    pub fn toggle_agent_severity_filter(&mut self, severity: &str) {
+        if self.severity_filter.as_deref() == Some(severity) {
+            self.severity_filter = None;
+        } else {
+            self.severity_filter = Some(severity.to_string());
+        }
+        self.sort_agents();
+        self.selected_agent_index = 0;
+        self.table_state.select(Some(0));
+    }
+
+    pub fn set_agent_vuln_counts(&mut self, counts: std::collections::HashMap<String, crate::models::AgentVulnCounts>) {
+        for (id, c) in &counts {
+            self.agent_critical_cves.insert(id.clone(), c.critical as usize);
+        }
+        self.agent_vuln_counts = counts;
+        self.agent_vuln_counts_loaded = true;
+        if self.severity_filter.is_some() {
+            self.sort_agents();
+        }
+    }
+
+    /// Stable sort by the filtered severity's count, descending, so ties keep the current order.
+    fn sort_agents_by_vulns(&mut self) {
+        let Some(severity) = self.severity_filter.clone() else { return };
+        let counts = &self.agent_vuln_counts;
+        self.agents.sort_by_key(|a| std::cmp::Reverse(counts.get(&a.id).map(|c| c.count(&severity)).unwrap_or(0)));
+    }
+
+    /// Agents matching the active severity filter (all agents when none is set).
+    pub fn matches_severity_filter(&self, agent_id: &str) -> bool {
+        match &self.severity_filter {
+            Some(severity) => self.agent_vuln_counts.get(agent_id).map(|c| c.count(severity) > 0).unwrap_or(false),
+            None => true,
+        }
+    }
+
+    pub fn cycle_process_sort(&mut self) {
+        if self.process_sort_order == SortOrder::Asc {
+            self.process_sort_order = SortOrder::Desc;
+        } else {
+            self.process_sort_column = self.process_sort_column.next();
+            self.process_sort_order = SortOrder::Asc;
+        }
+        self.sort_processes();
+    }
+
+    pub fn append_processes(&mut self, items: Vec<WazuhProcessItem>, total: u32) {
+        self.processes.extend(items);
+        self.processes_total = Some(total);
+        self.sort_processes();
+    }
+
+    pub fn append_programs(&mut self, items: Vec<WazuhProgramItem>, total: u32) {
+        self.programs.extend(items);
+        self.programs_total = Some(total);
+    }
+
+    pub fn sort_processes(&mut self) {
+        self.processes.sort_by(|a, b| {
+            let res = match self.process_sort_column {
+                ProcessSortColumn::Pid => {
+                    let pid_a = a.pid.parse::<u64>().unwrap_or(0);
+                    let pid_b = b.pid.parse::<u64>().unwrap_or(0);
+                    pid_a.cmp(&pid_b)
+                },
+                ProcessSortColumn::Name => a.name.cmp(&b.name),
+                ProcessSortColumn::User => a.euser.cmp(&b.euser),
+                ProcessSortColumn::Memory => a.vm_size.cmp(&b.vm_size),
+                ProcessSortColumn::CpuTime => a.cpu_time().cmp(&b.cpu_time()),
+            };
+            if self.process_sort_order == SortOrder::Desc {
+                res.reverse()
+            } else {
+                res
+            }
+        });
+    }
+
+    /// Processes as shown in the Processes tab (search applied).
+    pub fn filtered_processes(&self) -> Vec<&WazuhProcessItem> {
+        if !self.is_searching {
+            return self.processes.iter().collect();
+        }
+        self.processes.iter()
+            .filter(|p| {
+                let content = format!("{} {} {} {} {}",
+                    p.pid,
+                    p.name.as_deref().unwrap_or(""),
+                    p.euser.as_deref().unwrap_or(""),
+                    p.state.as_deref().unwrap_or(""),
+                    p.cmd.as_deref().unwrap_or("")
+                );
+                crate::ui::common::filter_matches(&self.search_query, &content)
+            })
+            .collect()
+    }
+
+    pub fn get_selected_process(&self) -> Option<&WazuhProcessItem> {
+        let idx = self.inspector_table_state.selected().unwrap_or(0);
+        self.filtered_processes().get(idx).copied()
+    }
+
+    pub fn cycle_vuln_sort(&mut self) {
+        if self.vuln_sort_order == SortOrder::Asc {
+            self.vuln_sort_order = SortOrder::Desc;
+        } else {
+            self.vuln_sort_column = self.vuln_sort_column.next();
+            self.vuln_sort_order = SortOrder::Asc;
+        }
+        self.sort_vulnerabilities();
+    }
+
+    pub fn sort_vulnerabilities(&mut self) {
+        self.vulnerabilities.sort_by(|a, b| {
+            let res = match self.vuln_sort_column {
+                VulnSortColumn::Severity => vuln_severity_rank(&a.severity).cmp(&vuln_severity_rank(&b.severity)),
+                VulnSortColumn::Score => a.cvss.unwrap_or(0.0).total_cmp(&b.cvss.unwrap_or(0.0)),
+                VulnSortColumn::Package => {
+                    let name_a = a.package.as_ref().map(|p| p.name.as_str()).or(a.name.as_deref()).unwrap_or("");
+                    let name_b = b.package.as_ref().map(|p| p.name.as_str()).or(b.name.as_deref()).unwrap_or("");
+                    name_a.to_lowercase().cmp(&name_b.to_lowercase())
+                }
+            };
+            if self.vuln_sort_order == SortOrder::Desc {
+                res.reverse()
+            } else {
+                res
+            }
+        });
+    }
+
+    /// Keys 1-4 in the Vulnerabilities tab: narrow to one severity, or clear it when pressed again.
+    pub fn toggle_vuln_severity_filter(&mut self, severity: &str) {
+        if self.vuln_severity_filter.as_deref() == Some(severity) {
+            self.vuln_severity_filter = None;
+        } else {
+            self.vuln_severity_filter = Some(severity.to_string());
+        }
+        self.inspector_table_state.select(Some(0));
+    }
+
+    /// The `n` most severe CVEs of the inspected agent, highest CVSS first within a severity.
+    pub fn top_vulnerabilities(&self, n: usize) -> Vec<&crate::models::WazuhVulnerabilityItem> {
+        let mut vulns: Vec<_> = self.vulnerabilities.iter().collect();
+        vulns.sort_by(|a, b| {
+            vuln_severity_rank(&b.severity).cmp(&vuln_severity_rank(&a.severity))
+                .then_with(|| b.cvss.unwrap_or(0.0).total_cmp(&a.cvss.unwrap_or(0.0)))
+        });
+        vulns.truncate(n);
+        vulns
+    }
+
+    /// Vulnerabilities as shown in the Vulnerabilities tab (severity filter and search applied).
+    pub fn filtered_vulnerabilities(&self) -> Vec<&crate::models::WazuhVulnerabilityItem> {
+        self.vulnerabilities.iter()
+            .filter(|v| match &self.vuln_severity_filter {
+                // Anything that is not critical/high/medium is counted on the LOW card
+                Some(sev) if sev == "low" => vuln_severity_rank(&v.severity) <= 1,
+                Some(sev) => v.severity.eq_ignore_ascii_case(sev),
+                None => true,
+            })
+            .filter(|v| {
+                if !self.is_searching {
+                    return true;
+                }
+                let pkg_name = v.package.as_ref().map(|p| p.name.clone())
+                    .unwrap_or_else(|| v.name.clone().unwrap_or_default());
+                let pkg_version = v.package.as_ref().map(|p| p.version.clone())
+                    .unwrap_or_else(|| v.version.clone().unwrap_or_default());
+                let content = format!("{} {} {} {}", v.cve, v.severity, pkg_name, pkg_version);
+                crate::ui::common::filter_matches(&self.search_query, &content)
+            })
+            .collect()
+    }
+
+    /// Loaded vulnerabilities of the inspected agent, keyed by lowercase package name.
+    pub fn vulnerabilities_by_package(&self) -> std::collections::HashMap<String, Vec<&crate::models::WazuhVulnerabilityItem>> {
+        let mut map: std::collections::HashMap<String, Vec<&crate::models::WazuhVulnerabilityItem>> = std::collections::HashMap::new();
+        for v in &self.vulnerabilities {
+            let pkg_name = v.package.as_ref().map(|p| p.name.clone()).or_else(|| v.name.clone());
+            if let Some(name) = pkg_name {
+                map.entry(name.to_lowercase()).or_default().push(v);
+            }
+        }
+        map
+    }
+
+    /// Programs as shown in the Programs tab (search and vulnerable-only toggle applied).
+    pub fn filtered_programs(&self) -> Vec<&WazuhProgramItem> {
+        let vulnerable = if self.programs_vuln_only { Some(self.vulnerabilities_by_package()) } else { None };
+        self.programs.iter()
+            .filter(|p| vulnerable.as_ref().map(|m| m.contains_key(&p.name.to_lowercase())).unwrap_or(true))
+            .filter(|p| {
+                if !self.is_searching {
+                    return true;
+                }
+                let content = format!("{} {} {}", p.name, p.version, p.vendor.as_deref().unwrap_or(""));
+                crate::ui::common::filter_matches(&self.search_query, &content)
+            })
+            .collect()
+    }
+
+    /// Agent counts per operating system (`os.name`, falling back to `os.platform`), largest first.
+    pub fn os_distribution(&self) -> Vec<(String, usize)> {
+        let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        for agent in &self.agents {
+            let os = agent.os.as_ref()
+                .and_then(|o| o.name.clone().or_else(|| o.platform.clone()))
+                .unwrap_or_else(|| "Unknown".to_string());
+            *counts.entry(os).or_insert(0) += 1;
+        }
+        let mut dist: Vec<(String, usize)> = counts.into_iter().collect();
+        dist.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        dist
+    }
+
+    /// Version of the manager (agent 000), used to spot outdated agents.
+    pub fn manager_version(&self) -> Option<&str> {
+        self.agents.iter().find(|a| a.id == "000" && a.profile.is_none()).and_then(|a| a.version.as_deref())
+    }
+
+    /// Health score for every loaded agent, keyed by agent id.
+    pub fn health_scores(&self) -> std::collections::HashMap<String, u8> {
+        let mut high_alerts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+        if let Some(counts) = &self.agent_high_alerts {
+            high_alerts.extend(counts.iter().map(|(id, n)| (id.as_str(), *n)));
+        } else {
+            self.count_loaded_high_alerts(&mut high_alerts);
+        }
+
+        let manager_version = self.manager_version();
+        self.agents.iter().map(|a| {
+            let score = health::health_score(
+                a,
+                manager_version,
+                high_alerts.get(a.id.as_str()).copied().unwrap_or(0),
+                self.agent_critical_cves.get(&a.id).copied().unwrap_or(0),
+            );
+            (a.id.clone(), score)
+        }).collect()
+    }
+
+    /// True while the fleet-wide alert or CVE counts are missing, so scores only reflect the
+    /// loaded alerts and the agents inspected so far.
+    pub fn health_scores_partial(&self) -> bool {
+        self.agent_high_alerts.is_none() || !self.agent_vuln_counts_loaded
+    }
+
+    /// High-severity alerts per agent among the currently loaded security events.
+    fn count_loaded_high_alerts<'a>(&'a self, high_alerts: &mut std::collections::HashMap<&'a str, usize>) {
+        for log in &self.logs {
+            let source = log.get("_source").unwrap_or(log);
+            let level = source.get("rule").and_then(|r| r.get("level")).and_then(|l| l.as_u64()).unwrap_or(0);
+            if level >= self.severity.high {
+                if let Some(id) = source.get("agent").and_then(|a| a.get("id")).and_then(|i| i.as_str()) {
+                    *high_alerts.entry(id).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    /// Never-connected or pending agents of this manager registered more than `stale_agent_days`
+    /// ago, oldest first. Agents listed from other profiles are left out: they are deleted by ID.
+    pub fn stale_agents(&self) -> Vec<&WazuhAgent> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(self.stale_agent_days);
+        let mut agents: Vec<_> = self.agents.iter()
+            .filter(|a| a.profile.is_none())
+            .filter(|a| a.status == "never_connected" || a.status == "pending")
+            .filter_map(|a| {
+                let added = a.date_add.as_deref().and_then(crate::models::parse_timestamp)?;
+                (added < cutoff).then_some((a, added))
+            })
+            .collect();
+        agents.sort_by_key(|(_, added)| *added);
+        agents.into_iter().map(|(a, _)| a).collect()
+    }
+
+    /// Disconnected agents whose last keep-alive falls within the last `disconnected_window_hours`,
+    /// most recent first.
+    pub fn recently_disconnected(&self) -> Vec<&WazuhAgent> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::hours(self.disconnected_window_hours);
+        let mut agents: Vec<_> = self.agents.iter()
+            .filter(|a| a.status == "disconnected")
+            .filter_map(|a| a.last_keep_alive_time().filter(|t| *t >= cutoff).map(|t| (a, t)))
+            .collect();
+        agents.sort_by_key(|(_, t)| std::cmp::Reverse(*t));
+        agents.into_iter().map(|(a, _)| a).collect()
+    }
+
+    /// Agents sharing a name or IP, see `hygiene::find_duplicates`.
+    pub fn duplicate_agents(&self) -> Vec<hygiene::DuplicateSet> {
+        hygiene::find_duplicates(&self.agents)
+    }
+
+    /// Rows of the duplicate agents popup: each set's agents in turn, paired with their set.
+    /// An agent can appear twice when it shares both its name and its IP.
+    pub fn duplicate_rows(&self) -> Vec<(hygiene::DuplicateSet, &WazuhAgent)> {
+        self.duplicate_agents().into_iter()
+            .flat_map(|set| {
+                let agents: Vec<&WazuhAgent> = set.agent_ids.iter()
+                    .filter_map(|id| self.agents.iter().find(|a| a.profile.is_none() && &a.id == id))
+                    .collect();
+                agents.into_iter().map(move |a| (set.clone(), a))
+            })
+            .collect()
+    }
+
+    /// "Active" agents with the oldest keep-alive first, paired with whether they are past
+    /// `stale_keepalive_minutes`. The manager (000) never goes stale and is skipped.
+    pub fn oldest_keepalives(&self) -> Vec<(&WazuhAgent, bool)> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::minutes(self.stale_keepalive_minutes);
+        let mut agents: Vec<_> = self.agents.iter()
+            .filter(|a| a.status == "active" && a.id != "000")
+            .filter_map(|a| a.last_keep_alive_time().map(|t| (a, t)))
+            .collect();
+        agents.sort_by_key(|(_, t)| *t);
+        agents.into_iter()
+            .take(KEEPALIVE_LIST_SIZE)
+            .map(|(a, t)| (a, t < cutoff))
+            .collect()
+    }
+
+    /// Opens the log detail overlay with fresh scroll, tree and search state.
+    pub fn open_log_detail(&mut self, log: serde_json::Value) {
+        self.selected_log = Some(log);
+        self.log_scroll_offset = 0;
+        self.json_tree = json_tree::JsonTreeState::default();
+        self.detail_search_query.clear();
+        self.detail_is_searching = false;
+        self.detail_match_index = 0;
+    }
+
+    pub fn move_tree_cursor(&mut self, delta: i64) {
+        if let Some(log) = &self.selected_log {
+            let len = json_tree::visible_rows(log, &self.json_tree.collapsed).len() as i64;
+            self.json_tree.cursor = (self.json_tree.cursor as i64 + delta).clamp(0, (len - 1).max(0)) as usize;
+        }
+    }
+
+    /// Right arrow: expand the container under the cursor.
+    pub fn tree_expand(&mut self) {
+        if let Some(log) = &self.selected_log {
+            if let Some(row) = json_tree::visible_rows(log, &self.json_tree.collapsed).get(self.json_tree.cursor) {
+                if row.collapsed {
+                    let path = row.path.clone();
+                    self.json_tree.toggle(&path);
+                }
+            }
+        }
+    }
+
+    /// Left arrow: collapse the container under the cursor, or jump to its parent.
+    pub fn tree_collapse(&mut self) {
+        if let Some(log) = &self.selected_log {
+            let rows = json_tree::visible_rows(log, &self.json_tree.collapsed);
+            if let Some(row) = rows.get(self.json_tree.cursor) {
+                if row.is_container() && !row.collapsed {
+                    let path = row.path.clone();
+                    self.json_tree.toggle(&path);
+                } else if let Some(parent) = rows[..self.json_tree.cursor].iter().rposition(|r| json_tree::is_ancestor(&r.path, &row.path)) {
+                    self.json_tree.cursor = parent;
+                }
+            }
+        }
+    }
+
+    /// Stores fetched context alerts and selects the one the view was opened from.
+    pub fn set_alert_context(&mut self, events: Vec<serde_json::Value>) {
+        let anchor = self.alert_context_anchor.as_deref();
+        self.alert_context_index = events.iter()
+            .position(|e| e.get("_id").and_then(|i| i.as_str()) == anchor)
+            .unwrap_or(0);
+        self.alert_context = events;
+        self.alert_context_loading = false;
+    }
+
+    /// Pivot results grouped by agent name, busiest agent first.
+    pub fn pivot_groups(&self) -> Vec<(String, Vec<&serde_json::Value>)> {
+        let mut groups: Vec<(String, Vec<&serde_json::Value>)> = Vec::new();
+        for event in &self.pivot_events {
+            let source = event.get("_source").unwrap_or(event);
+            let agent = source.get("agent").and_then(|a| a.get("name")).and_then(|n| n.as_str()).unwrap_or("unknown");
+            match groups.iter_mut().find(|(name, _)| name == agent) {
+                Some((_, events)) => events.push(event),
+                None => groups.push((agent.to_string(), vec![event])),
+            }
+        }
+        groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(&b.0)));
+        groups
+    }
+
+    /// Event under the pivot cursor; the index counts events only, not agent headers.
+    pub fn selected_pivot_event(&self) -> Option<serde_json::Value> {
+        self.pivot_groups().into_iter().flat_map(|(_, events)| events).nth(self.pivot_index).cloned()
+    }
+
+    /// Shifts the unwrapped Raw log view left (negative) or right.
+    pub fn scroll_raw(&mut self, steps: i32) {
+        let delta = RAW_SCROLL_STEP.saturating_mul(steps.unsigned_abs() as u16);
+        self.raw_scroll_x = if steps < 0 {
+            self.raw_scroll_x.saturating_sub(delta)
+        } else {
+            self.raw_scroll_x.saturating_add(delta)
+        };
+    }
+
+    /// Ends search input in the log detail overlay and jumps to the first hit.
+    pub fn finish_detail_search(&mut self) {
+        self.detail_is_searching = false;
+        self.detail_match_index = 0;
+        self.focus_detail_match();
+    }
+
+    /// Moves to the next (or previous) search hit in the log detail overlay.
+    pub fn jump_to_detail_match(&mut self, forward: bool) {
+        let total = self.detail_match_count();
+        if total == 0 {
+            return;
+        }
+        self.detail_match_index = if forward {
+            (self.detail_match_index + 1) % total
+        } else {
+            (self.detail_match_index + total - 1) % total
+        };
+        self.focus_detail_match();
+    }
+
+    /// Line indices of search hits in the flat field view (header lines included).
+    pub fn detail_field_matches(&self) -> Vec<usize> {
+        self.selected_log
+            .as_ref()
+            .and_then(|log| log.get("_source"))
+            .and_then(|s| s.as_object())
+            .map(|obj| json_tree::flat_field_matches(obj, &self.detail_search_query))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|i| i + LOG_DETAIL_HEADER_LINES)
+            .collect()
+    }
+
+    /// Index and `_id` of the alert open in the detail overlay, to find the exact document in
+    /// Discover or Dev Tools.
+    pub fn detail_document(&self) -> Option<(&str, &str)> {
+        let log = self.selected_log.as_ref()?;
+        let index = log.get("_index").and_then(|v| v.as_str())?;
+        let id = log.get("_id").and_then(|v| v.as_str())?;
+        Some((index, id))
+    }
+
+    pub fn detail_match_count(&self) -> usize {
+        match &self.selected_log {
+            Some(log) if self.show_log_json => json_tree::search_paths(log, &self.detail_search_query).len(),
+            Some(_) => self.detail_field_matches().len(),
+            None => 0,
+        }
+    }
+
+    fn focus_detail_match(&mut self) {
+        if self.show_log_json {
+            let Some(log) = &self.selected_log else { return };
+            if let Some(path) = json_tree::search_paths(log, &self.detail_search_query).get(self.detail_match_index) {
+                self.json_tree.focus(log, path);
+            }
+        } else if let Some(&line) = self.detail_field_matches().get(self.detail_match_index) {
+            self.log_scroll_offset = line;
+        }
     }
 
     pub fn get_selected_group(&self) -> Option<&WazuhGroup> {
@@ -737,8 +3081,309 @@ impl App {
         self.groups_table_state.selected().and_then(|idx| filtered_groups.get(idx).copied())
     }
 
+    /// Group whose posture should be fetched now: posture mode is on, the selection has
+    /// rested on the group for a tick and nothing is cached or in flight for it yet.
+    pub fn group_posture_to_load(&mut self) -> Option<String> {
+        if !self.group_posture || self.active_view != ActiveView::GroupManagement {
+            self.posture_candidate = None;
+            return None;
+        }
+        let name = self.get_selected_group()?.name.clone();
+        if self.group_postures.contains_key(&name) {
+            self.posture_candidate = None;
+            return None;
+        }
+        if self.posture_candidate.as_ref() == Some(&name) {
+            self.posture_candidate = None;
+            self.group_postures.insert(name.clone(), None);
+            return Some(name);
+        }
+        self.posture_candidate = Some(name);
+        None
+    }
+
+    pub fn set_group_files(&mut self, group: String, files: Vec<crate::models::WazuhGroupFile>) {
+        self.group_files_group = Some(group);
+        self.group_files = files;
+        self.group_file_index = 0;
+    }
+
+    /// Opens the membership editor for the inspected agent, pre-ticking its current groups.
+    pub fn open_group_membership(&mut self) {
+        let Some(agent) = self.get_selected_agent() else { return };
+        let agent_id = agent.id.clone();
+        self.membership_groups = agent.group.clone().unwrap_or_default();
+        self.membership_index = 0;
+        self.popup_mode = PopupMode::GroupMembership { agent_id };
+    }
+
+    pub fn toggle_membership_group(&mut self) {
+        let Some(group) = self.groups.get(self.membership_index) else { return };
+        if let Some(pos) = self.membership_groups.iter().position(|g| g == &group.name) {
+            self.membership_groups.remove(pos);
+        } else {
+            self.membership_groups.push(group.name.clone());
+        }
+    }
+
+    /// Moves from the editor to the confirm step, or closes it when nothing changed.
+    pub fn confirm_group_membership(&mut self) {
+        let PopupMode::GroupMembership { agent_id } = &self.popup_mode else { return };
+        let agent_id = agent_id.clone();
+        let current = self.agents.iter()
+            .find(|a| a.id == agent_id && a.profile.is_none())
+            .and_then(|a| a.group.clone())
+            .unwrap_or_default();
+        let (add, remove) = group_membership_diff(&current, &self.membership_groups);
+        if add.is_empty() && remove.is_empty() {
+            self.popup_mode = PopupMode::None;
+            self.notify("Group membership unchanged", NotificationLevel::Info);
+        } else {
+            self.popup_mode = PopupMode::ConfirmGroupMembership { agent_id, add, remove };
+        }
+    }
+
+    pub fn selected_cdb_list(&self) -> Option<&crate::models::WazuhCdbList> {
+        self.cdb_lists.get(self.cdb_list_index)
+    }
+
+    /// Keeps the selected list across reloads when it still exists.
+    pub fn set_cdb_lists(&mut self, lists: Vec<crate::models::WazuhCdbList>) {
+        let selected = self.selected_cdb_list().map(|l| l.filename.clone());
+        self.cdb_lists = lists;
+        self.cdb_list_index = selected
+            .and_then(|name| self.cdb_lists.iter().position(|l| l.filename == name))
+            .unwrap_or(0);
+        let len = self.selected_cdb_list().map(|l| l.items.len()).unwrap_or(0);
+        self.cdb_entry_index = self.cdb_entry_index.min(len.saturating_sub(1));
+        if len == 0 {
+            self.cdb_entries_focus = false;
+        }
+    }
+
+    /// Opens the keybinding reference with an empty search.
+    pub fn open_help(&mut self) {
+        self.help_query.clear();
+        self.help_scroll = 0;
+        self.popup_mode = PopupMode::Help;
+    }
+
+    /// Help sections for the current view (and the open event detail), or for the search typed.
+    pub fn help_sections(&self) -> Vec<(keymap::KeyContext, Vec<(String, &'static str)>)> {
+        let mut contexts = vec![keymap::KeyContext::Global, keymap::KeyContext::of_view(&self.active_view)];
+        if self.selected_log.is_some() {
+            contexts.push(keymap::KeyContext::EventDetail);
+        }
+        self.keymap.help_sections(&contexts, &self.help_query)
+    }
+
+    pub fn scroll_help(&mut self, delta: i32) {
+        self.help_scroll = (self.help_scroll as i32 + delta).max(0) as u16;
+    }
+
+    pub fn start_tour(&mut self) {
+        self.tour_step = Some(0);
+        self.show_tour_step();
+    }
+
+    /// Moves the tour by `delta` steps; going past the last step ends it.
+    pub fn advance_tour(&mut self, delta: i32) {
+        let Some(step) = self.tour_step else { return };
+        let next = (step as i32 + delta).max(0) as usize;
+        if next >= tour::tour_steps().len() {
+            self.end_tour();
+        } else {
+            self.tour_step = Some(next);
+            self.show_tour_step();
+        }
+    }
+
+    pub fn end_tour(&mut self) {
+        self.tour_step = None;
+        self.popup_mode = PopupMode::None;
+        self.active_view = ActiveView::Dashboard;
+    }
+
+    /// Whether the config still asks for the tour, so finishing it should be saved.
+    pub fn tour_pending(&self) -> bool {
+        self.api.as_ref().map(|api| api.config.display.tour_seen == Some(false)).unwrap_or(false)
+    }
+
+    /// Switches to the view of the current step, with the filter popup open when it points there.
+    fn show_tour_step(&mut self) {
+        let Some(step) = self.tour_step.and_then(|i| tour::tour_steps().into_iter().nth(i)) else { return };
+        self.active_view = step.view;
+        self.selected_log = None;
+        if step.target == tour::TourTarget::FilterPopup {
+            self.filter_popup_tab = FilterPopupTab::Severity;
+            self.popup_mode = PopupMode::SeverityFilter;
+        } else {
+            self.popup_mode = PopupMode::None;
+        }
+    }
+
+    /// Opens the export field list for editing, prefilled with the current paths.
+    pub fn open_export_fields(&mut self) {
+        self.input_buffer = self.export_fields.join(", ");
+        self.popup_mode = PopupMode::ExportFields;
+    }
+
+    /// Sets the export field list from what was typed; an empty list exports whole alerts.
+    pub fn apply_export_fields(&mut self) {
+        match export::parse_field_paths(&self.input_buffer) {
+            Ok(fields) => {
+                self.export_fields = fields;
+                self.popup_mode = PopupMode::None;
+                if self.export_fields.is_empty() {
+                    self.notify("Exports contain whole alerts", NotificationLevel::Info);
+                } else {
+                    self.notify(&format!("JSON exports keep {} fields", self.export_fields.len()), NotificationLevel::Info);
+                }
+            }
+            Err(e) => self.notify(&e, NotificationLevel::Warning),
+        }
+    }
+
+    pub fn open_cdb_add_entry(&mut self) {
+        let Some(list) = self.selected_cdb_list() else { return };
+        let filename = list.filename.clone();
+        self.input_buffer.clear();
+        self.popup_mode = PopupMode::CdbAddEntry { filename };
+    }
+
+    /// Moves the typed `key:value` to the confirm step.
+    pub fn stage_cdb_entry(&mut self) {
+        let PopupMode::CdbAddEntry { filename } = &self.popup_mode else { return };
+        let filename = filename.clone();
+        match cdb::parse_entry(&self.input_buffer) {
+            Some((key, value)) => {
+                self.popup_mode = PopupMode::ConfirmCdbChange { filename, key, value, remove: false };
+            }
+            None => self.notify("Enter a key, optionally followed by :value", NotificationLevel::Warning),
+        }
+    }
+
+    pub fn stage_cdb_removal(&mut self) {
+        let Some(list) = self.selected_cdb_list() else { return };
+        let Some(entry) = list.items.get(self.cdb_entry_index) else { return };
+        self.popup_mode = PopupMode::ConfirmCdbChange {
+            filename: list.filename.clone(),
+            key: entry.key.clone(),
+            value: entry.value.clone(),
+            remove: true,
+        };
+    }
+
+    pub fn selected_rule_file(&self) -> Option<&crate::models::WazuhRuleFile> {
+        self.rule_files.get(self.rule_file_index)
+    }
+
+    /// Ignores a list that arrives after the view switched to the other kind.
+    pub fn set_rule_files(&mut self, kind: rules::RulesetKind, files: Vec<crate::models::WazuhRuleFile>) {
+        if kind != self.ruleset_kind {
+            return;
+        }
+        let selected = self.selected_rule_file().map(|f| f.filename.clone());
+        self.rule_files = files;
+        self.rule_file_index = selected
+            .and_then(|name| self.rule_files.iter().position(|f| f.filename == name))
+            .unwrap_or(0);
+    }
+
+    /// Takes the text saved in the editor; changed files go to the upload confirmation,
+    /// with the XML check result shown there.
+    pub fn set_rule_draft(&mut self, kind: rules::RulesetKind, filename: String, original: &str, edited: String) {
+        if edited == original {
+            self.notify(&format!("{} unchanged", filename), NotificationLevel::Info);
+            return;
+        }
+        self.rule_draft_error = rules::check_ruleset_xml(&edited).err();
+        self.rule_draft = edited;
+        self.popup_mode = PopupMode::ConfirmRuleUpload { kind, filename };
+    }
+
+    /// Switches the Rules view between rule and decoder files; the list is reloaded by the caller.
+    pub fn toggle_ruleset_kind(&mut self) {
+        self.ruleset_kind = self.ruleset_kind.toggle();
+        self.rule_files.clear();
+        self.rule_file_index = 0;
+    }
+
+    pub fn logtest_format(&self) -> &'static str {
+        LOGTEST_FORMATS[self.logtest_format_index % LOGTEST_FORMATS.len()]
+    }
+
+    pub fn cycle_logtest_format(&mut self, forward: bool) {
+        let len = LOGTEST_FORMATS.len();
+        self.logtest_format_index = if forward { (self.logtest_format_index + 1) % len } else { (self.logtest_format_index + len - 1) % len };
+    }
+
+    /// The text field the popup is typing into; the format field has none.
+    pub fn logtest_input(&mut self) -> Option<&mut String> {
+        match self.logtest_field {
+            0 => Some(&mut self.logtest_event),
+            1 => Some(&mut self.logtest_location),
+            _ => None,
+        }
+    }
+
+    pub fn get_selected_user(&self) -> Option<&crate::models::WazuhUser> {
+        self.users_table_state.selected().and_then(|idx| self.security_users.get(idx))
+    }
+
+    /// Resolves role ids to names using the loaded role list.
+    pub fn role_names(&self, role_ids: &[u32]) -> Vec<String> {
+        role_ids.iter()
+            .map(|id| self.security_roles.iter()
+                .find(|r| r.id == *id)
+                .map(|r| r.name.clone())
+                .unwrap_or_else(|| format!("#{}", id)))
+            .collect()
+    }
+
+    /// Writes the current dashboard figures as a Markdown report and returns its filename.
+    pub fn export_report(&self) -> Result<String, String> {
+        let digest = report::AlertDigest {
+            stats: self.threat_stats.clone(),
+            history: self.alert_buckets.clone(),
+            top_agents: self.top_agents.clone(),
+            top_rules: self.top_rules.clone(),
+            top_countries: self.top_countries.clone(),
+            critical: self.critical_alerts.clone(),
+            scale: self.severity,
+        };
+        let range = self.format_interval();
+        let ctx = report::ReportContext {
+            range: &range,
+            generated: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            agent_status: report::status_counts(&self.agents),
+            vulns: Some(&self.vuln_summary),
+        };
+
+        let path = self.export_path("wazuh_report_{to}", "report", "all", "md")?;
+        export::write_file(&path, &report::render_markdown(&ctx, &digest))?;
+        Ok(path.display().to_string())
+    }
+
+    /// Writes the Vulnerabilities tab as shown (severity filter, search and sort applied).
+    pub fn export_vulnerabilities(&self) -> Result<String, String> {
+        let vulns = self.filtered_vulnerabilities();
+        if vulns.is_empty() {
+            return Err("No vulnerabilities available to export".to_string());
+        }
+        let (agent_id, agent_name) = self.get_selected_agent()
+            .map(|a| (a.id.clone(), a.name.clone()))
+            .unwrap_or_default();
+
+        let path = self.export_path("wazuh_vulns_{agent}_{to}", "vulnerabilities", &agent_name, self.vuln_export_format.extension())?;
+        let content = self.vuln_export_format.render(&agent_id, &agent_name, &vulns)?;
+        export::write_file(&path, &content)?;
+        Ok(path.display().to_string())
+    }
+
     pub fn export_logs(&mut self) -> Result<String, String> {
         let logs_to_export = match self.active_view {
+            ActiveView::SecurityEvents if !self.selected_alerts.is_empty() => &self.selected_alerts,
             ActiveView::SecurityEvents => &self.logs,
             ActiveView::AgentInspector if self.inspector_tab == InspectorTab::Logs => &self.agent_logs,
             _ => return Err("No logs to export in this view".to_string()),
@@ -748,12 +3393,31 @@ impl App {
             return Err("No logs available to export".to_string());
         }
 
-        let filename = format!("wazuh_export_{}.json", chrono::Local::now().format("%Y%m%d_%H%M%S"));
-        let mut file = File::create(&filename).map_err(|e| format!("Failed to create file: {}", e))?;
-        
-        let json_content = serde_json::to_string_pretty(logs_to_export).map_err(|e| format!("JSON error: {}", e))?;
-        file.write_all(json_content.as_bytes()).map_err(|e| format!("Write error: {}", e))?;
+        let path = self.alert_export_path()?;
+        export::write_alerts(logs_to_export, self.export_format, self.export_compression, &self.export_fields, &path)?;
+        Ok(path.display().to_string())
+    }
+
+    /// Where alerts exported from the current view go; the inspector names its agent.
+    pub fn alert_export_path(&self) -> Result<std::path::PathBuf, String> {
+        let (view, agent) = match self.active_view {
+            ActiveView::AgentInspector => ("agent-logs", self.get_selected_agent().map(|a| a.name.clone()).unwrap_or_default()),
+            _ => ("security-events", "all".to_string()),
+        };
+        self.export_path("wazuh_export_{to}", view, &agent, &self.export_compression.extension(self.export_format))
+    }
+
+    /// Export file path under the configured directory and name template, covering the current
+    /// interval up to now.
+    fn export_path(&self, default: &str, view: &str, agent: &str, extension: &str) -> Result<std::path::PathBuf, String> {
+        let to = chrono::Local::now();
+        let from = to - chrono::Duration::minutes(self.log_interval_mins as i64);
+        self.export_naming.path(default, &export::ExportName { view, agent, from, to, extension })
+    }
 
-        Ok(filename)
+    /// Reports a written export and remembers its path for `Y`.
+    pub fn exported(&mut self, what: &str, path: String) {
+        self.notify(&format!("{} to {} ([Y] copy path)", what, path), NotificationLevel::Success);
+        self.last_export_path = Some(path);
     }
 }
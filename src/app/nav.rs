@@ -0,0 +1,56 @@
+use crate::app::{ActiveView, App, LogFilter};
+
+/// How many view transitions Esc/Backspace can walk back through.
+pub const NAV_HISTORY: usize = 32;
+
+/// Where the user was before a view transition: the view plus the filters and
+/// selection needed to put it back exactly as it was.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavEntry {
+    pub view: ActiveView,
+    pub log_filter: LogFilter,
+    pub log_offset: u32,
+    pub selected_row: Option<usize>,
+    pub selected_agent_index: usize,
+    pub inspector_tab_index: usize,
+}
+
+impl NavEntry {
+    pub fn capture(app: &App) -> Self {
+        Self {
+            view: app.active_view.clone(),
+            log_filter: app.log_filter.clone(),
+            log_offset: app.log_offset,
+            selected_row: app.table_state.selected(),
+            selected_agent_index: app.selected_agent_index,
+            inspector_tab_index: app.selected_tab_index,
+        }
+    }
+
+    pub fn restore(&self, app: &mut App) {
+        app.active_view = self.view.clone();
+        app.log_filter = self.log_filter.clone();
+        app.log_offset = self.log_offset;
+        app.table_state.select(self.selected_row);
+        app.selected_agent_index = self.selected_agent_index;
+        app.set_inspector_tab(self.inspector_tab_index);
+    }
+
+    /// Short name of the view for the header breadcrumb trail.
+    pub fn label(&self) -> &'static str {
+        view_label(&self.view)
+    }
+}
+
+pub fn view_label(view: &ActiveView) -> &'static str {
+    match view {
+        ActiveView::Dashboard => "Dashboard",
+        ActiveView::AgentList => "Agents",
+        ActiveView::AgentInspector => "Inspector",
+        ActiveView::SecurityEvents => "Security Events",
+        ActiveView::GroupManagement => "Groups",
+        ActiveView::UserManagement => "Users",
+        ActiveView::CdbLists => "Lists",
+        ActiveView::Rules => "Rules",
+    }
+}
@@ -0,0 +1,56 @@
+use crate::app::nav::NAV_HISTORY;
+use crate::app::{ActiveView, App, InspectorTab, SeverityFilterMode};
+
+#[test]
+fn test_navigate_back_restores_filter_and_selection() {
+    let mut app = App::new();
+    app.table_state.select(Some(4));
+
+    app.navigate(ActiveView::SecurityEvents);
+    app.log_filter.mode = SeverityFilterMode::Exact;
+    app.log_filter.val1 = 15;
+    app.table_state.select(Some(2));
+
+    app.navigate(ActiveView::AgentList);
+    app.selected_agent_index = 7;
+    app.navigate(ActiveView::AgentInspector);
+    app.next_tab();
+    app.next_tab();
+    assert_eq!(app.nav_stack.len(), 3);
+
+    assert!(app.navigate_back());
+    assert_eq!(app.active_view, ActiveView::AgentList);
+    assert_eq!(app.selected_agent_index, 7);
+    assert_eq!(app.inspector_tab, InspectorTab::Summary);
+
+    assert!(app.navigate_back());
+    assert_eq!(app.active_view, ActiveView::SecurityEvents);
+    assert_eq!(app.log_filter.val1, 15);
+    assert_eq!(app.table_state.selected(), Some(2));
+
+    assert!(app.navigate_back());
+    assert_eq!(app.active_view, ActiveView::Dashboard);
+    assert_eq!(app.log_filter.mode, SeverityFilterMode::Min);
+    assert_eq!(app.table_state.selected(), Some(4));
+    assert!(!app.navigate_back());
+}
+
+#[test]
+fn test_navigate_same_view_and_history_cap() {
+    let mut app = App::new();
+    app.navigate(ActiveView::Dashboard);
+    assert!(app.nav_stack.is_empty());
+
+    for i in 0..NAV_HISTORY + 5 {
+        app.navigate(if i % 2 == 0 { ActiveView::AgentList } else { ActiveView::Dashboard });
+    }
+    assert_eq!(app.nav_stack.len(), NAV_HISTORY);
+}
+
+#[test]
+fn test_navigate_back_from_inspector_without_history() {
+    let mut app = App::new();
+    app.active_view = ActiveView::AgentInspector;
+    assert!(app.navigate_back());
+    assert_eq!(app.active_view, ActiveView::AgentList);
+}
@@ -0,0 +1,56 @@
+use crate::models::WazuhAgent;
+
+/// Shown for agents the manager reports without a `node_name`.
+pub const UNKNOWN_NODE: &str = "(unknown)";
+
+/// Agents reporting to one cluster node, by connection state.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeSummary {
+    pub node: String,
+    pub total: usize,
+    pub active: usize,
+    pub disconnected: usize,
+    /// Pending and never connected
+    pub other: usize,
+}
+
+impl NodeSummary {
+    /// Every agent of the node is disconnected: the node itself is likely down or unreachable.
+    pub fn is_dark(&self) -> bool {
+        self.disconnected > 0 && self.disconnected == self.total
+    }
+
+    /// Share of the cluster's agents on this node, for balancing workers.
+    pub fn share(&self, cluster_total: usize) -> f64 {
+        if cluster_total == 0 { 0.0 } else { self.total as f64 / cluster_total as f64 }
+    }
+
+    /// `node:` filter for the agent list. Agents without a node cannot be filtered on.
+    pub fn filter_query(&self) -> Option<String> {
+        (self.node != UNKNOWN_NODE).then(|| format!("node:{}", self.node))
+    }
+}
+
+/// Agents of this manager per cluster node, sorted by node name. Agents of other profiles
+/// and the manager's own agent 000 are left out.
+pub fn node_summaries(agents: &[WazuhAgent]) -> Vec<NodeSummary> {
+    let mut nodes: Vec<NodeSummary> = Vec::new();
+    for agent in agents.iter().filter(|a| a.profile.is_none() && !a.is_manager()) {
+        let node = agent.node_name.as_deref().map(str::trim).filter(|n| !n.is_empty()).unwrap_or(UNKNOWN_NODE);
+        let summary = match nodes.iter().position(|s| s.node == node) {
+            Some(i) => &mut nodes[i],
+            None => {
+                nodes.push(NodeSummary { node: node.to_string(), ..Default::default() });
+                nodes.last_mut().unwrap()
+            }
+        };
+        summary.total += 1;
+        match agent.status.as_str() {
+            "active" => summary.active += 1,
+            "disconnected" => summary.disconnected += 1,
+            _ => summary.other += 1,
+        }
+    }
+    nodes.sort_by(|a, b| a.node.cmp(&b.node));
+    nodes
+}
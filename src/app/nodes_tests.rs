@@ -0,0 +1,67 @@
+use crate::app::nodes::{node_summaries, UNKNOWN_NODE};
+use crate::app::{ActiveView, App, PopupMode};
+use crate::models::WazuhAgent;
+
+fn agent(id: &str, node: Option<&str>, status: &str) -> WazuhAgent {
+    WazuhAgent {
+        id: id.to_string(),
+        name: format!("host-{}", id),
+        status: status.to_string(),
+        node_name: node.map(String::from),
+        ..Default::default()
+    }
+}
+
+fn cluster() -> Vec<WazuhAgent> {
+    vec![
+        agent("000", Some("master"), "active"),
+        agent("001", Some("worker-2"), "disconnected"),
+        agent("002", Some("master"), "active"),
+        agent("003", Some("worker-2"), "disconnected"),
+        agent("004", Some("worker-1"), "active"),
+        agent("005", Some("worker-1"), "never_connected"),
+        agent("006", None, "pending"),
+    ]
+}
+
+#[test]
+fn test_node_summaries() {
+    let nodes = node_summaries(&cluster());
+    let names: Vec<&str> = nodes.iter().map(|n| n.node.as_str()).collect();
+    assert_eq!(names, vec![UNKNOWN_NODE, "master", "worker-1", "worker-2"]);
+
+    // The manager's own agent is not counted
+    assert_eq!((nodes[1].total, nodes[1].active), (1, 1));
+    assert_eq!((nodes[2].active, nodes[2].other), (1, 1));
+    assert!(nodes[3].is_dark());
+    assert!(!nodes[2].is_dark());
+    assert_eq!(nodes[3].share(6), 2.0 / 6.0);
+    assert_eq!(nodes[3].filter_query().as_deref(), Some("node:worker-2"));
+    assert_eq!(nodes[0].filter_query(), None);
+}
+
+#[test]
+fn test_dark_nodes() {
+    let mut app = App::new();
+    app.agents = cluster();
+    assert_eq!(app.dark_nodes(), vec!["worker-2"]);
+
+    // A single node is not a cluster
+    app.agents = vec![agent("001", Some("master"), "disconnected")];
+    assert!(app.dark_nodes().is_empty());
+}
+
+#[test]
+fn test_show_node_agents() {
+    let mut app = App::new();
+    app.agents = cluster();
+    app.popup_mode = PopupMode::AgentNodes;
+    app.node_index = 2;
+    app.show_node_agents();
+
+    assert_eq!(app.popup_mode, PopupMode::None);
+    assert_eq!(app.active_view, ActiveView::AgentList);
+    assert_eq!(app.search_query, "node:worker-1");
+    let listed: Vec<&str> = app.agents.iter().filter(|a| app.agent_filter.matches(a)).map(|a| a.id.as_str()).collect();
+    assert_eq!(listed, vec!["004", "005"]);
+}
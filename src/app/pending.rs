@@ -0,0 +1,86 @@
+use super::actions::AgentAction;
+use crate::models::WazuhAgent;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// An action held back until its disconnected agent is active again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingAction {
+    pub action: AgentAction,
+    pub agent_id: String,
+    #[serde(default)]
+    pub agent_name: String,
+    /// RFC 3339, when it was queued
+    pub queued_at: String,
+}
+
+/// Actions queued for disconnected agents, kept in `pending_actions.toml` next to `config.toml`
+/// so they survive a restart of the TUI. They run once a refresh or the heartbeat sees the agent active.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PendingQueue {
+    pub actions: Vec<PendingAction>,
+}
+
+impl PendingQueue {
+    pub fn path() -> PathBuf {
+        crate::config::ConfigManager::get_config_path().with_file_name("pending_actions.toml")
+    }
+
+    /// A missing or unreadable file means nothing is queued.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Queues `action` for the agent; false when the same action already waits for it.
+    pub fn push(&mut self, action: AgentAction, agent: &WazuhAgent) -> bool {
+        if self.actions.iter().any(|p| p.action == action && p.agent_id == agent.id) {
+            return false;
+        }
+        self.actions.push(PendingAction {
+            action,
+            agent_id: agent.id.clone(),
+            agent_name: agent.name.clone(),
+            queued_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        });
+        true
+    }
+
+    /// Agents with something queued, for the status check.
+    pub fn agent_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.actions.iter().map(|p| p.agent_id.clone()).collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    /// Removes the actions whose agent is active in `agents` and returns them grouped by
+    /// action, in queue order. Actions `hold` refuses stay queued for the next check.
+    pub fn take_ready(&mut self, agents: &[WazuhAgent], hold: impl Fn(AgentAction) -> bool) -> Vec<(AgentAction, Vec<String>)> {
+        let active = |id: &str| agents.iter().any(|a| a.id == id && a.profile.is_none() && a.status == "active");
+        let mut ready: Vec<(AgentAction, Vec<String>)> = Vec::new();
+        self.actions.retain(|p| {
+            if hold(p.action) || !active(&p.agent_id) {
+                return true;
+            }
+            match ready.iter_mut().find(|(action, _)| *action == p.action) {
+                Some((_, ids)) => ids.push(p.agent_id.clone()),
+                None => ready.push((p.action, vec![p.agent_id.clone()])),
+            }
+            false
+        });
+        ready
+    }
+}
@@ -0,0 +1,58 @@
+use crate::app::actions::AgentAction;
+use crate::app::pending::PendingQueue;
+use crate::app::{App, PopupMode};
+use crate::models::WazuhAgent;
+
+fn agent(id: &str, status: &str) -> WazuhAgent {
+    WazuhAgent {
+        id: id.to_string(),
+        name: format!("host-{}", id),
+        status: status.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_pending_queue() {
+    let mut queue = PendingQueue::default();
+    assert!(queue.push(AgentAction::Restart, &agent("002", "disconnected")));
+    assert!(queue.push(AgentAction::Upgrade, &agent("002", "disconnected")));
+    assert!(queue.push(AgentAction::Restart, &agent("001", "disconnected")));
+    assert!(!queue.push(AgentAction::Restart, &agent("001", "disconnected")));
+    assert_eq!(queue.agent_ids(), vec!["001".to_string(), "002".to_string()]);
+
+    // Nothing runs while the agents are still away
+    assert!(queue.take_ready(&[agent("001", "disconnected"), agent("002", "disconnected")], |_| false).is_empty());
+
+    let agents = [agent("001", "active"), agent("002", "active")];
+    let ready = queue.take_ready(&agents, |action| action == AgentAction::Upgrade);
+    assert_eq!(ready, vec![(AgentAction::Restart, vec!["002".to_string(), "001".to_string()])]);
+    // The held upgrade waits for the next check
+    assert_eq!(queue.actions.len(), 1);
+    assert_eq!(queue.take_ready(&agents, |_| false), vec![(AgentAction::Upgrade, vec!["002".to_string()])]);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_pending_queue_roundtrip() {
+    let mut queue = PendingQueue::default();
+    queue.push(AgentAction::FimScan, &agent("003", "disconnected"));
+    let parsed: PendingQueue = toml::from_str(&toml::to_string_pretty(&queue).unwrap()).unwrap();
+    assert_eq!(parsed, queue);
+}
+
+#[test]
+fn test_disconnected_targets_ask() {
+    let mut app = App::new();
+    app.agents = vec![agent("001", "active"), agent("002", "disconnected")];
+    app.confirm_agent_actions = false;
+
+    assert_eq!(app.disconnected(AgentAction::Restart, &["001".to_string(), "002".to_string()]), vec!["002".to_string()]);
+    assert!(app.disconnected(AgentAction::DeleteStale, &["002".to_string()]).is_empty());
+
+    // Confirmations are off, but the popup still offers to queue the disconnected agent
+    assert_eq!(app.request_agent_action(AgentAction::FimScan, vec!["002".to_string()]), None);
+    assert!(matches!(app.popup_mode, PopupMode::ConfirmAgentAction { action: AgentAction::FimScan, .. }));
+    app.popup_mode = PopupMode::None;
+    assert_eq!(app.request_agent_action(AgentAction::FimScan, vec!["001".to_string()]), Some(vec!["001".to_string()]));
+}
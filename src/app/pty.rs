@@ -0,0 +1,113 @@
+//! SSH inside the TUI: `ssh` runs on a pseudo-terminal and its output is rendered by
+//! `term::TermScreen`. Works wherever the TUI runs, including over a remote SSH session
+//! where no GUI terminal can be opened.
+
+use crate::app::term::TermScreen;
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use std::io::{Read, Write};
+
+/// An `ssh` child on a pty. Output arrives as `DataUpdate::SshOutput` from a reader thread.
+pub struct SshSession {
+    /// `user@host` shown in the pane title
+    pub target: String,
+    pub screen: TermScreen,
+    pub exited: bool,
+    writer: Box<dyn Write + Send>,
+    master: Box<dyn MasterPty + Send>,
+    child: Box<dyn Child + Send + Sync>,
+}
+
+fn pty_size(rows: u16, cols: u16) -> PtySize {
+    PtySize { rows, cols, pixel_width: 0, pixel_height: 0 }
+}
+
+impl std::fmt::Debug for SshSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SshSession").field("target", &self.target).field("exited", &self.exited).finish()
+    }
+}
+
+/// Prefer the embedded pane when the TUI itself runs over SSH or without a display,
+/// where an external terminal window can't be shown.
+pub fn prefer_embedded<F: Fn(&str) -> Option<String>>(env: F) -> bool {
+    let remote = env("SSH_CONNECTION").is_some() || env("SSH_TTY").is_some();
+    let headless = cfg!(target_os = "linux") && env("DISPLAY").is_none() && env("WAYLAND_DISPLAY").is_none();
+    remote || headless
+}
+
+impl SshSession {
+    /// Starts `ssh -t <target>` on a new pty sized `rows` x `cols`. `on_output` runs on the
+    /// reader thread for every chunk; it gets an empty chunk once the session ends.
+    pub fn spawn<F>(target: &str, rows: u16, cols: u16, on_output: F) -> anyhow::Result<Self>
+    where
+        F: Fn(Vec<u8>) + Send + 'static,
+    {
+        let pair = native_pty_system().openpty(pty_size(rows, cols))?;
+        let mut command = CommandBuilder::new("ssh");
+        // `--` keeps a target starting with `-` from being read as an ssh option
+        command.args(["-t", "--", target]);
+        command.env("TERM", "xterm");
+        if let Ok(dir) = std::env::current_dir() {
+            command.cwd(dir);
+        }
+        let child = pair.slave.spawn_command(command)?;
+        // Only the child may hold the slave, or the reader never sees the pty close
+        drop(pair.slave);
+
+        let mut reader = pair.master.try_clone_reader()?;
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            loop {
+                match reader.read(&mut buf) {
+                    // EIO once the child closed the pty
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => on_output(buf[..n].to_vec()),
+                }
+            }
+            on_output(Vec::new());
+        });
+
+        Ok(Self {
+            target: target.to_string(),
+            screen: TermScreen::new(rows as usize, cols as usize),
+            exited: false,
+            writer: pair.master.take_writer()?,
+            master: pair.master,
+            child,
+        })
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        if !self.exited {
+            let _ = self.writer.write_all(bytes);
+        }
+    }
+
+    /// Resizes the screen and tells the remote side; a no-op when the size is unchanged.
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        if (rows as usize, cols as usize) == self.screen.size() {
+            return;
+        }
+        self.screen.resize(rows as usize, cols as usize);
+        let _ = self.master.resize(pty_size(rows, cols));
+    }
+
+    /// Exit status text once the child is gone.
+    pub fn finish(&mut self) -> String {
+        self.exited = true;
+        match self.child.wait() {
+            Ok(status) if status.success() => "Connection closed".to_string(),
+            Ok(status) => format!("ssh exited with code {}", status.exit_code()),
+            Err(e) => format!("ssh: {}", e),
+        }
+    }
+}
+
+impl Drop for SshSession {
+    fn drop(&mut self) {
+        if !self.exited {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
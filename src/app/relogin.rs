@@ -0,0 +1,43 @@
+/// Fields of the Re-login popup, in Tab order.
+pub const RELOGIN_FIELDS: usize = 3;
+
+/// Credentials typed in the Re-login popup, for switching the session to another API user
+/// (e.g. temporary elevated access) without editing `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReloginForm {
+    pub username: String,
+    pub password: String,
+    /// 0 username, 1 password, 2 "save to config.toml"
+    pub field: usize,
+    /// Write the credentials to `config.toml` once the login works; off so nothing reaches disk by default
+    pub save: bool,
+}
+
+impl ReloginForm {
+    /// Empty form, focused on the username.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The text field with focus; `None` on the save checkbox.
+    pub fn input(&mut self) -> Option<&mut String> {
+        match self.field {
+            0 => Some(&mut self.username),
+            1 => Some(&mut self.password),
+            _ => None,
+        }
+    }
+
+    pub fn next_field(&mut self) {
+        self.field = (self.field + 1) % RELOGIN_FIELDS;
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !self.username.trim().is_empty() && !self.password.is_empty()
+    }
+
+    /// The credentials to log in with, clearing the password from the form.
+    pub fn take_credentials(&mut self) -> (String, String) {
+        (self.username.trim().to_string(), std::mem::take(&mut self.password))
+    }
+}
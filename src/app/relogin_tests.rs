@@ -0,0 +1,47 @@
+use crate::app::relogin::ReloginForm;
+use crate::app::App;
+
+#[test]
+fn test_relogin_form_fields() {
+    let mut form = ReloginForm::new();
+    form.input().unwrap().push_str("admin");
+    form.next_field();
+    form.input().unwrap().push_str("S3cret!");
+    form.next_field();
+    // The save checkbox takes no text
+    assert_eq!(form.input(), None);
+    form.next_field();
+    assert_eq!(form.field, 0);
+    assert!(!form.save);
+}
+
+#[test]
+fn test_relogin_form_complete() {
+    let mut form = ReloginForm::new();
+    assert!(!form.is_complete());
+    form.username = "  ".to_string();
+    form.password = "pass".to_string();
+    assert!(!form.is_complete());
+    form.username = " admin ".to_string();
+    assert!(form.is_complete());
+
+    assert_eq!(form.take_credentials(), ("admin".to_string(), "pass".to_string()));
+    // The password does not linger in the form
+    assert!(form.password.is_empty());
+    assert!(!form.is_complete());
+}
+
+#[test]
+fn test_apply_relogin() {
+    let mut app = App::new();
+    app.set_api(crate::api::WazuhApi::new(crate::models::Config {
+        url: "https://wazuh:55000".to_string(),
+        username: "viewer".to_string(),
+        password: "viewer-pass".to_string(),
+        ..Default::default()
+    }));
+    app.apply_relogin("admin", "admin-pass");
+    let api = app.api.as_ref().unwrap();
+    assert_eq!((api.config.username.as_str(), api.config.password.as_str()), ("admin", "admin-pass"));
+    assert!(app.permissions.is_none());
+}
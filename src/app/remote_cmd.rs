@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use std::time::{Duration, Instant};
+
+/// Active-response script installed on the agents, see `contrib/active-response/wazuh-tui-exec`.
+pub const AR_SCRIPT: &str = "wazuh-tui-exec";
+/// Seconds between alert searches while waiting for the output.
+pub const RESULT_POLL_SECS: u64 = 3;
+/// The output travels agent log -> logcollector -> manager rule -> indexer; give up after this.
+pub const RESULT_TIMEOUT_SECS: i64 = 120;
+/// Offered when `[remote_commands] allowed` is not configured. The script on the agent
+/// keeps its own copy of the list and refuses anything else.
+pub const DEFAULT_COMMANDS: [&str; 5] = [
+    "systemctl status wazuh-agent",
+    "uptime",
+    "df -h",
+    "free -m",
+    "tail -n 50 /var/ossec/logs/ossec.log",
+];
+
+/// What the script reported back through the `data.wazuh_tui_exec` alert fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandResult {
+    pub exit_code: i64,
+    pub output: String,
+}
+
+/// `<agent>-<millis>`: unique per run and safe to pass as a script argument.
+pub fn request_id(agent_id: &str, now: DateTime<Utc>) -> String {
+    format!("{}-{}", agent_id, now.timestamp_millis())
+}
+
+/// Reads the result out of an alert `_source`. The JSON decoder may store numbers as strings.
+pub fn parse_result(source: &serde_json::Value) -> Option<CommandResult> {
+    let exec = source.get("data")?.get("wazuh_tui_exec")?;
+    let exit_code = match exec.get("exit_code") {
+        Some(serde_json::Value::Number(n)) => n.as_i64()?,
+        Some(serde_json::Value::String(s)) => s.parse().ok()?,
+        _ => return None,
+    };
+    let output = exec.get("output").and_then(|o| o.as_str()).unwrap_or_default().to_string();
+    Some(CommandResult { exit_code, output })
+}
+
+/// One whitelisted command sent to an agent, tracked until its output shows up in the alerts.
+#[derive(Debug, Clone)]
+pub struct RemoteCommand {
+    pub id: String,
+    pub agent_id: String,
+    pub command: String,
+    pub requested_at: DateTime<Utc>,
+    pub next_poll: Instant,
+    pub result: Option<Result<CommandResult, String>>,
+}
+
+impl RemoteCommand {
+    pub fn new(agent_id: &str, command: &str, now: DateTime<Utc>) -> Self {
+        Self {
+            id: request_id(agent_id, now),
+            agent_id: agent_id.to_string(),
+            command: command.to_string(),
+            requested_at: now,
+            next_poll: Instant::now() + Duration::from_secs(RESULT_POLL_SECS),
+            result: None,
+        }
+    }
+
+    pub fn is_pending(&self, now: DateTime<Utc>) -> bool {
+        self.result.is_none() && (now - self.requested_at).num_seconds() <= RESULT_TIMEOUT_SECS
+    }
+
+    /// Popup status line.
+    pub fn status(&self, now: DateTime<Utc>) -> String {
+        match &self.result {
+            Some(Ok(r)) if r.exit_code == 0 => "Finished".to_string(),
+            Some(Ok(r)) => format!("Exited with code {}", r.exit_code),
+            Some(Err(e)) => e.clone(),
+            None if self.is_pending(now) => format!("Waiting for output... {}s", (now - self.requested_at).num_seconds().max(0)),
+            None => format!("No output after {}s; check that the script and its log collector are set up on the agent", RESULT_TIMEOUT_SECS),
+        }
+    }
+}
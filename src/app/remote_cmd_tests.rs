@@ -0,0 +1,32 @@
+use crate::app::remote_cmd::{self, CommandResult, RemoteCommand};
+use chrono::{Duration, TimeZone, Utc};
+
+#[test]
+fn test_parse_result() {
+    let source = serde_json::json!({
+        "data": { "wazuh_tui_exec": { "id": "003-1714557600000", "exit_code": "3", "output": "inactive (dead)\n" } }
+    });
+    assert_eq!(remote_cmd::parse_result(&source), Some(CommandResult { exit_code: 3, output: "inactive (dead)\n".to_string() }));
+
+    let source = serde_json::json!({ "data": { "wazuh_tui_exec": { "exit_code": 0 } } });
+    assert_eq!(remote_cmd::parse_result(&source).map(|r| r.exit_code), Some(0));
+    assert_eq!(remote_cmd::parse_result(&serde_json::json!({ "data": { "wazuh_tui_exec": { "exit_code": "x" } } })), None);
+    assert_eq!(remote_cmd::parse_result(&serde_json::json!({ "rule": { "id": "100850" } })), None);
+}
+
+#[test]
+fn test_remote_command_status() {
+    let requested = Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap();
+    let mut run = RemoteCommand::new("003", "uptime", requested);
+    assert_eq!(run.id, "003-1714557600000");
+    assert!(run.is_pending(requested + Duration::seconds(30)));
+    assert_eq!(run.status(requested + Duration::seconds(30)), "Waiting for output... 30s");
+
+    let late = requested + Duration::seconds(remote_cmd::RESULT_TIMEOUT_SECS + 1);
+    assert!(!run.is_pending(late));
+    assert!(run.status(late).starts_with("No output after"));
+
+    run.result = Some(Ok(CommandResult { exit_code: 1, output: String::new() }));
+    assert!(!run.is_pending(requested));
+    assert_eq!(run.status(requested), "Exited with code 1");
+}
@@ -0,0 +1,205 @@
+use serde_json::Value;
+use crate::app::ThreatStats;
+use crate::app::severity::{Severity, SeverityScale};
+use crate::app::time::DisplayTz;
+use crate::models::{VulnerabilitySummary, WazuhAgent};
+
+pub const REPORT_ALERT_LIMIT: usize = 20;
+const TOP_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopRule {
+    pub id: String,
+    pub description: String,
+    pub count: u64,
+}
+
+/// Dashboard figures derived from one batch of alert hits.
+#[derive(Debug, Default, Clone)]
+pub struct AlertDigest {
+    pub stats: ThreatStats,
+    pub history: Vec<(String, u64)>,
+    pub top_agents: Vec<(String, u64)>,
+    pub top_rules: Vec<TopRule>,
+    pub top_countries: Vec<(String, u64)>,
+    /// Alerts from the high band up, highest level first, capped at `REPORT_ALERT_LIMIT`
+    pub critical: Vec<Value>,
+    /// Bands the stats were counted in
+    pub scale: SeverityScale,
+}
+
+impl AlertDigest {
+    /// Histogram buckets are per minute in `tz`.
+    pub fn from_hits(hits: &[Value], tz: DisplayTz, scale: SeverityScale) -> Self {
+        let mut stats = ThreatStats::default();
+        let mut buckets = std::collections::BTreeMap::new();
+        let mut agent_counts: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
+        let mut rule_counts: std::collections::HashMap<String, (String, u64)> = std::collections::HashMap::new();
+        let mut critical = Vec::new();
+
+        for hit in hits {
+            let Some(source) = hit.get("_source") else { continue };
+            let rule = source.get("rule");
+            if let Some(level) = rule.and_then(|r| r.get("level")).and_then(|l| l.as_u64()) {
+                stats.add_level(&scale, level, 1);
+                if level >= scale.high {
+                    critical.push(hit.clone());
+                }
+            }
+            if let Some(agent_name) = source.get("agent").and_then(|a| a.get("name")).and_then(|n| n.as_str()) {
+                *agent_counts.entry(agent_name.to_string()).or_insert(0) += 1;
+            }
+            if let Some(id) = rule.and_then(|r| r.get("id")).and_then(|v| v.as_str()) {
+                let description = rule.and_then(|r| r.get("description")).and_then(|v| v.as_str()).unwrap_or("");
+                rule_counts.entry(id.to_string()).or_insert_with(|| (description.to_string(), 0)).1 += 1;
+            }
+            if let Some(t) = source.get("@timestamp").and_then(|t| t.as_str()).and_then(crate::models::parse_timestamp) {
+                // Sort by full minute so windows spanning midnight stay in order, label as HH:MM
+                let local = tz.convert(t);
+                *buckets.entry((local.format("%Y-%m-%d %H:%M").to_string(), local.format("%H:%M").to_string())).or_insert(0u64) += 1;
+            }
+        }
+
+        let mut top_agents: Vec<(String, u64)> = agent_counts.into_iter().collect();
+        top_agents.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_agents.truncate(TOP_LIMIT);
+
+        let mut top_rules: Vec<TopRule> = rule_counts.into_iter()
+            .map(|(id, (description, count))| TopRule { id, description, count })
+            .collect();
+        top_rules.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.id.cmp(&b.id)));
+        top_rules.truncate(TOP_LIMIT);
+
+        critical.sort_by_key(|h| std::cmp::Reverse(level_of(h)));
+        critical.truncate(REPORT_ALERT_LIMIT);
+
+        AlertDigest {
+            stats,
+            history: buckets.into_iter().map(|((_, label), count)| (label, count)).collect(),
+            top_agents,
+            top_rules,
+            top_countries: crate::app::geo::top_countries(hits, TOP_LIMIT),
+            critical,
+            scale,
+        }
+    }
+}
+
+fn level_of(hit: &Value) -> u64 {
+    hit.get("_source").unwrap_or(hit).get("rule").and_then(|r| r.get("level")).and_then(|l| l.as_u64()).unwrap_or(0)
+}
+
+/// Everything in a report that does not come from the alert hits themselves.
+pub struct ReportContext<'a> {
+    pub range: &'a str,
+    pub generated: String,
+    /// Agent counts per connection status
+    pub agent_status: Vec<(String, usize)>,
+    /// Omitted from the report when not loaded
+    pub vulns: Option<&'a VulnerabilitySummary>,
+}
+
+/// Agent counts per status, most common first.
+pub fn status_counts(agents: &[WazuhAgent]) -> Vec<(String, usize)> {
+    let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for agent in agents {
+        *counts.entry(agent.status.clone()).or_insert(0) += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Human-readable list of alert counts above the given limits; empty when within bounds.
+pub fn threshold_breaches(stats: &ThreatStats, max_critical: Option<u32>, max_high: Option<u32>) -> Vec<String> {
+    let mut breaches = Vec::new();
+    if let Some(max) = max_critical.filter(|m| stats.critical > *m) {
+        breaches.push(format!("critical alerts {} > {}", stats.critical, max));
+    }
+    if let Some(max) = max_high.filter(|m| stats.high > *m) {
+        breaches.push(format!("high alerts {} > {}", stats.high, max));
+    }
+    breaches
+}
+
+fn md_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Weekly-summary style Markdown report.
+pub fn render_markdown(ctx: &ReportContext, digest: &AlertDigest) -> String {
+    let mut out = String::new();
+    out.push_str("# Wazuh Security Report\n\n");
+    out.push_str(&format!("- **Time range:** last {}\n", ctx.range));
+    out.push_str(&format!("- **Generated:** {}\n\n", ctx.generated));
+
+    out.push_str("## Alert Summary\n\n| Severity | Alerts |\n|----------|-------:|\n");
+    let band = |severity: Severity| format!("{} ({})", severity.label(), digest.scale.range_label(severity));
+    out.push_str(&format!("| {} | {} |\n", band(Severity::Critical), digest.stats.critical));
+    out.push_str(&format!("| {} | {} |\n", band(Severity::High), digest.stats.high));
+    out.push_str(&format!("| {} | {} |\n", band(Severity::Medium), digest.stats.medium));
+    out.push_str(&format!("| {} | {} |\n\n", band(Severity::Low), digest.stats.low));
+
+    if let Some(vulns) = ctx.vulns {
+        out.push_str("## Vulnerabilities\n\n| Severity | Count |\n|----------|------:|\n");
+        out.push_str(&format!("| Critical | {} |\n| High | {} |\n| Medium | {} |\n| Low | {} |\n\n",
+            vulns.critical, vulns.high, vulns.medium, vulns.low));
+    }
+
+    if !ctx.agent_status.is_empty() {
+        out.push_str("## Agents\n\n| Status | Agents |\n|--------|-------:|\n");
+        for (status, count) in &ctx.agent_status {
+            out.push_str(&format!("| {} | {} |\n", md_cell(status), count));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("## Top Agents by Alerts\n\n");
+    if digest.top_agents.is_empty() {
+        out.push_str("_No alerts in this range._\n\n");
+    } else {
+        out.push_str("| # | Agent | Alerts |\n|---|-------|-------:|\n");
+        for (i, (name, count)) in digest.top_agents.iter().enumerate() {
+            out.push_str(&format!("| {} | {} | {} |\n", i + 1, md_cell(name), count));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("## Top Rules\n\n");
+    if digest.top_rules.is_empty() {
+        out.push_str("_No alerts in this range._\n\n");
+    } else {
+        out.push_str("| Rule | Description | Alerts |\n|------|-------------|-------:|\n");
+        for rule in &digest.top_rules {
+            out.push_str(&format!("| {} | {} | {} |\n", md_cell(&rule.id), md_cell(&rule.description), rule.count));
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!("## Critical Alerts (level ≥ {})\n\n", digest.scale.high));
+    if digest.critical.is_empty() {
+        out.push_str("_None._\n");
+    } else {
+        out.push_str("| Time | Level | Agent | Rule | Description |\n|------|------:|-------|------|-------------|\n");
+        for hit in &digest.critical {
+            let source = hit.get("_source").unwrap_or(hit);
+            let text = |path: &[&str]| {
+                let mut v = source;
+                for key in path {
+                    match v.get(key) {
+                        Some(next) => v = next,
+                        None => return "-".to_string(),
+                    }
+                }
+                v.as_str().unwrap_or("-").to_string()
+            };
+            out.push_str(&format!("| {} | {} | {} | {} | {} |\n",
+                md_cell(&text(&["@timestamp"])),
+                level_of(hit),
+                md_cell(&text(&["agent", "name"])),
+                md_cell(&text(&["rule", "id"])),
+                md_cell(&text(&["rule", "description"]))));
+        }
+    }
+    out
+}
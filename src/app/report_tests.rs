@@ -0,0 +1,72 @@
+use crate::app::report::{render_markdown, threshold_breaches, AlertDigest, ReportContext};
+use crate::models::VulnerabilitySummary;
+use crate::app::severity::SeverityScale;
+use crate::app::time::DisplayTz;
+use serde_json::json;
+
+fn hit(agent: &str, rule: &str, level: u64, minute: &str) -> serde_json::Value {
+    json!({ "_source": {
+        "@timestamp": format!("2024-05-01T10:{}:00.000Z", minute),
+        "agent": { "name": agent },
+        "rule": { "id": rule, "level": level, "description": format!("rule {} | desc", rule) }
+    }})
+}
+
+fn sample() -> Vec<serde_json::Value> {
+    vec![
+        hit("web-01", "5710", 5, "01"),
+        hit("web-01", "5710", 5, "01"),
+        hit("db-01", "100002", 15, "02"),
+        hit("web-01", "31103", 12, "03"),
+    ]
+}
+
+#[test]
+fn test_digest_from_hits() {
+    let digest = AlertDigest::from_hits(&sample(), DisplayTz::Utc, SeverityScale::default());
+    assert_eq!((digest.stats.critical, digest.stats.high, digest.stats.low), (1, 1, 2));
+    assert_eq!(digest.top_agents[0], ("web-01".to_string(), 3));
+    assert_eq!(digest.top_rules[0].id, "5710");
+    assert_eq!(digest.top_rules[0].count, 2);
+    assert_eq!(digest.history.len(), 3);
+
+    // Only alerts at the report threshold, highest level first
+    assert_eq!(digest.critical.len(), 2);
+    assert_eq!(digest.critical[0]["_source"]["rule"]["level"], 15);
+    assert!(digest.critical.iter().all(|h| h["_source"]["rule"]["level"].as_u64().unwrap() >= digest.scale.high));
+}
+
+#[test]
+fn test_render_markdown() {
+    let digest = AlertDigest::from_hits(&sample(), DisplayTz::Utc, SeverityScale::default());
+    let vulns = VulnerabilitySummary { critical: 3, high: 0, medium: 0, low: 0, untriaged: 0 };
+    let ctx = ReportContext {
+        range: "24h",
+        generated: "2024-05-01 12:00".to_string(),
+        agent_status: vec![("active".to_string(), 2)],
+        vulns: Some(&vulns),
+    };
+    let md = render_markdown(&ctx, &digest);
+    assert!(md.starts_with("# Wazuh Security Report"));
+    assert!(md.contains("- **Time range:** last 24h"));
+    assert!(md.contains("| Critical | 3 |"));
+    assert!(md.contains("| 1 | web-01 | 3 |"));
+    // Pipes inside cells are escaped so the table stays intact
+    assert!(md.contains("rule 5710 \\| desc"));
+}
+
+#[test]
+fn test_threshold_breaches() {
+    let digest = AlertDigest::from_hits(&sample(), DisplayTz::Utc, SeverityScale::default());
+    assert!(threshold_breaches(&digest.stats, None, None).is_empty());
+    assert!(threshold_breaches(&digest.stats, Some(1), Some(1)).is_empty());
+    assert_eq!(threshold_breaches(&digest.stats, Some(0), Some(1)), vec!["critical alerts 1 > 0".to_string()]);
+}
+
+#[test]
+fn test_histogram_buckets_use_display_timezone() {
+    let tz = DisplayTz::parse("+01:00").unwrap();
+    let digest = AlertDigest::from_hits(&sample(), tz, SeverityScale::default());
+    let labels: Vec<&str> = digest.history.iter().map(|(l, _)| l.as_str()).collect();
+    assert_eq!(labels, vec!["11:01", "11:02", "11:03"]);
+}
@@ -0,0 +1,159 @@
+//! Helpers for editing local rule and decoder files (`etc/rules`, `etc/decoders`) in an external editor.
+
+/// Which half of the local ruleset the Rules view is showing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RulesetKind {
+    #[default]
+    Rules,
+    Decoders,
+}
+
+impl RulesetKind {
+    /// API path segment, as in `/rules/files` and `/decoders/files`.
+    pub fn endpoint(self) -> &'static str {
+        match self {
+            RulesetKind::Rules => "rules",
+            RulesetKind::Decoders => "decoders",
+        }
+    }
+
+    pub fn dirname(self) -> &'static str {
+        match self {
+            RulesetKind::Rules => "etc/rules",
+            RulesetKind::Decoders => "etc/decoders",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RulesetKind::Rules => "Rule",
+            RulesetKind::Decoders => "Decoder",
+        }
+    }
+
+    pub fn template(self) -> &'static str {
+        match self {
+            RulesetKind::Rules => RULE_FILE_TEMPLATE,
+            RulesetKind::Decoders => DECODER_FILE_TEMPLATE,
+        }
+    }
+
+    pub fn update_action(self) -> &'static str {
+        match self {
+            RulesetKind::Rules => crate::app::ACTION_RULES_UPDATE,
+            RulesetKind::Decoders => crate::app::ACTION_DECODERS_UPDATE,
+        }
+    }
+
+    pub fn delete_action(self) -> &'static str {
+        match self {
+            RulesetKind::Rules => crate::app::ACTION_RULES_DELETE,
+            RulesetKind::Decoders => crate::app::ACTION_DECODERS_DELETE,
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            RulesetKind::Rules => RulesetKind::Decoders,
+            RulesetKind::Decoders => RulesetKind::Rules,
+        }
+    }
+}
+
+/// Starting content for a rule file created from the TUI; local rule IDs start at 100000.
+pub const RULE_FILE_TEMPLATE: &str = r#"<group name="local,">
+  <rule id="100100" level="5">
+    <if_sid></if_sid>
+    <description>Describe what this rule detects</description>
+  </rule>
+</group>
+"#;
+
+/// Starting content for a decoder file: a parent matching the program and a child extracting fields.
+pub const DECODER_FILE_TEMPLATE: &str = r#"<decoder name="local_custom">
+  <program_name>^custom-app$</program_name>
+</decoder>
+
+<decoder name="local_custom_fields">
+  <parent>local_custom</parent>
+  <regex>user=(\S+) src=(\S+)</regex>
+  <order>srcuser, srcip</order>
+</decoder>
+"#;
+
+/// Program and arguments from `$VISUAL` or `$EDITOR` (e.g. `code --wait`), `vi` when unset.
+pub fn editor_command() -> (String, Vec<String>) {
+    let raw = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_default();
+    let mut parts = raw.split_whitespace().map(|s| s.to_string());
+    match parts.next() {
+        Some(program) => (program, parts.collect()),
+        None => ("vi".to_string(), Vec::new()),
+    }
+}
+
+/// Appends `.xml` unless the name already has it; rejects paths.
+pub fn ruleset_filename(input: &str) -> Option<String> {
+    let name = input.trim();
+    if name.is_empty() || name.contains('/') || name.contains("..") {
+        return None;
+    }
+    Some(if name.ends_with(".xml") { name.to_string() } else { format!("{}.xml", name) })
+}
+
+/// Checks that tags are balanced, which catches most hand-editing mistakes before the
+/// manager refuses the file. Rule and decoder files have several root elements, so
+/// this does not require a single root.
+pub fn check_ruleset_xml(content: &str) -> Result<(), String> {
+    let mut stack: Vec<(String, usize)> = Vec::new();
+    let mut rest = content;
+    let mut line = 1;
+
+    while let Some(start) = rest.find('<') {
+        line += rest[..start].matches('\n').count();
+        rest = &rest[start..];
+
+        let (end_marker, skip_only) = if rest.starts_with("<!--") {
+            ("-->", true)
+        } else if rest.starts_with("<![CDATA[") {
+            ("]]>", true)
+        } else if rest.starts_with("<?") {
+            ("?>", true)
+        } else {
+            (">", false)
+        };
+        let Some(end) = rest.find(end_marker) else {
+            return Err(format!("line {}: unterminated tag", line));
+        };
+        let tag = &rest[1..end];
+        let consumed = end + end_marker.len();
+
+        if !skip_only {
+            if let Some(name) = tag.strip_prefix('/') {
+                let name = name.trim();
+                match stack.pop() {
+                    Some((open, _)) if open == name => {}
+                    Some((open, open_line)) => {
+                        return Err(format!("line {}: </{}> closes <{}> opened on line {}", line, name, open, open_line));
+                    }
+                    None => return Err(format!("line {}: </{}> without opening tag", line, name)),
+                }
+            } else if !tag.ends_with('/') {
+                let name = tag.split_whitespace().next().unwrap_or("");
+                if name.is_empty() {
+                    return Err(format!("line {}: empty tag", line));
+                }
+                stack.push((name.to_string(), line));
+            }
+        }
+
+        line += rest[..consumed].matches('\n').count();
+        rest = &rest[consumed..];
+    }
+
+    match stack.pop() {
+        Some((open, open_line)) => Err(format!("line {}: <{}> is never closed", open_line, open)),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,53 @@
+use crate::app::rules::{check_ruleset_xml, ruleset_filename, RulesetKind, DECODER_FILE_TEMPLATE, RULE_FILE_TEMPLATE};
+
+#[test]
+fn test_check_rule_xml_accepts_valid_files() {
+    assert!(check_ruleset_xml(RULE_FILE_TEMPLATE).is_ok());
+    assert!(check_ruleset_xml(DECODER_FILE_TEMPLATE).is_ok());
+
+    let content = r#"<!-- Local rules -->
+<group name="syslog,sshd,">
+  <rule id="100001" level="10" frequency="8" timeframe="120">
+    <if_matched_sid>5710</if_matched_sid>
+    <same_source_ip />
+    <description>sshd: brute force from <![CDATA[<unknown>]]></description>
+  </rule>
+</group>
+<group name="web,">
+  <rule id="100002" level="3">
+    <match>GET /health</match>
+    <description>Health check</description>
+  </rule>
+</group>
+"#;
+    assert_eq!(check_ruleset_xml(content), Ok(()));
+}
+
+#[test]
+fn test_check_rule_xml_reports_line() {
+    let mismatched = "<group name=\"local,\">\n  <rule id=\"100100\" level=\"5\">\n    <description>x</descripton>\n  </rule>\n</group>\n";
+    assert_eq!(check_ruleset_xml(mismatched), Err("line 3: </descripton> closes <description> opened on line 3".to_string()));
+
+    let unclosed = "<group name=\"local,\">\n  <rule id=\"1\" level=\"5\">\n  </rule>\n";
+    assert_eq!(check_ruleset_xml(unclosed), Err("line 1: <group> is never closed".to_string()));
+
+    assert!(check_ruleset_xml("<group>\n</rule>").is_err());
+    assert!(check_ruleset_xml("<group name=\"x\"").is_err());
+}
+
+#[test]
+fn test_ruleset_filename() {
+    assert_eq!(ruleset_filename(" web_rules "), Some("web_rules.xml".to_string()));
+    assert_eq!(ruleset_filename("local_rules.xml"), Some("local_rules.xml".to_string()));
+    assert_eq!(ruleset_filename(""), None);
+    assert_eq!(ruleset_filename("../ossec.conf"), None);
+}
+
+#[test]
+fn test_ruleset_kind() {
+    assert_eq!(RulesetKind::default(), RulesetKind::Rules);
+    assert_eq!(RulesetKind::Rules.toggle(), RulesetKind::Decoders);
+    assert_eq!(RulesetKind::Decoders.endpoint(), "decoders");
+    assert_eq!(RulesetKind::Decoders.dirname(), "etc/decoders");
+    assert_eq!(RulesetKind::Decoders.update_action(), "decoders:update");
+}
@@ -0,0 +1,103 @@
+/// Alert severity band of a rule level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl Severity {
+    /// Name used by the severity chips and aggregation keys.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Critical => "critical",
+            Severity::High => "high",
+            Severity::Medium => "medium",
+            Severity::Low => "low",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Critical => "Critical",
+            Severity::High => "High",
+            Severity::Medium => "Medium",
+            Severity::Low => "Low",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "critical" => Some(Severity::Critical),
+            "high" => Some(Severity::High),
+            "medium" => Some(Severity::Medium),
+            "low" => Some(Severity::Low),
+            _ => None,
+        }
+    }
+}
+
+/// Lowest rule level of each alert severity band; levels below `medium` are low. The stats,
+/// colors, filter presets, chips and dashboard cards all go by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeverityScale {
+    pub critical: u64,
+    pub high: u64,
+    pub medium: u64,
+}
+
+impl Default for SeverityScale {
+    fn default() -> Self {
+        SeverityScale { critical: 15, high: 12, medium: 7 }
+    }
+}
+
+impl SeverityScale {
+    /// The `[severity]` section over the defaults; a scale that is not strictly increasing from
+    /// medium to critical is ignored.
+    pub fn from_config(config: &crate::models::Config) -> Self {
+        let default = Self::default();
+        let scale = SeverityScale {
+            critical: config.severity.critical.unwrap_or(default.critical),
+            high: config.severity.high.unwrap_or(default.high),
+            medium: config.severity.medium.unwrap_or(default.medium),
+        };
+        if 0 < scale.medium && scale.medium < scale.high && scale.high < scale.critical {
+            scale
+        } else {
+            default
+        }
+    }
+
+    pub fn classify(&self, level: u64) -> Severity {
+        if level >= self.critical {
+            Severity::Critical
+        } else if level >= self.high {
+            Severity::High
+        } else if level >= self.medium {
+            Severity::Medium
+        } else {
+            Severity::Low
+        }
+    }
+
+    /// Inclusive rule level bounds of a band; critical has no upper bound.
+    pub fn bounds(&self, severity: Severity) -> (u64, Option<u64>) {
+        match severity {
+            Severity::Critical => (self.critical, None),
+            Severity::High => (self.high, Some(self.critical - 1)),
+            Severity::Medium => (self.medium, Some(self.high - 1)),
+            Severity::Low => (0, Some(self.medium - 1)),
+        }
+    }
+
+    /// Level range as shown next to a band, e.g. `15+` or `12-14`.
+    pub fn range_label(&self, severity: Severity) -> String {
+        match self.bounds(severity) {
+            (low, None) => format!("{}+", low),
+            (low, Some(high)) if low == high => low.to_string(),
+            (low, Some(high)) => format!("{}-{}", low, high),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::app::severity::{Severity, SeverityScale};
+use crate::app::ThreatStats;
+
+#[test]
+fn test_severity_scale_from_config() {
+    let mut config: crate::models::Config = toml::from_str(r#"
+        url = "https://hq:55000"
+        username = "wazuh"
+        password = "secret"
+
+        [severity]
+        critical = 13
+        high = 10
+    "#).unwrap();
+    let scale = SeverityScale::from_config(&config);
+    assert_eq!(scale, SeverityScale { critical: 13, high: 10, medium: 7 });
+    assert_eq!(scale.classify(13), Severity::Critical);
+    assert_eq!(scale.classify(12), Severity::High);
+    assert_eq!(scale.classify(9), Severity::Medium);
+    assert_eq!(scale.classify(6), Severity::Low);
+    assert_eq!(scale.bounds(Severity::High), (10, Some(12)));
+    assert_eq!(scale.range_label(Severity::Critical), "13+");
+    assert_eq!(scale.range_label(Severity::Low), "0-6");
+
+    let mut stats = ThreatStats::default();
+    for level in [3, 8, 11, 14] {
+        stats.add_level(&scale, level, 1);
+    }
+    assert_eq!((stats.critical, stats.high, stats.medium, stats.low), (1, 1, 1, 1));
+
+    // Bands out of order fall back to the stock scale
+    config.severity.medium = Some(12);
+    assert_eq!(SeverityScale::from_config(&config), SeverityScale::default());
+}
+
+#[test]
+fn test_severity_preset_follows_scale() {
+    let mut app = crate::app::App::new();
+    app.severity = SeverityScale { critical: 13, high: 10, medium: 5 };
+    app.apply_severity_preset(Severity::High);
+    assert_eq!(app.log_filter.mode, crate::app::SeverityFilterMode::Range);
+    assert_eq!((app.log_filter.val1, app.log_filter.val2), (10, 12));
+    assert_eq!(app.filter_input_2, "12");
+
+    app.apply_severity_preset(Severity::Critical);
+    assert_eq!(app.log_filter.mode, crate::app::SeverityFilterMode::Min);
+    assert_eq!(app.log_filter.val1, 13);
+}
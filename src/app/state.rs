@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use crate::app::{ActiveView, App, LogColumn, LogFilter, SortColumn, SortOrder};
+use crate::app::filter::AgentFilter;
+
+/// UI state remembered between sessions in `state.toml`, next to `config.toml`.
+/// Every field is optional so older or hand-edited files still load.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    pub active_view: Option<ActiveView>,
+    pub sort_column: Option<SortColumn>,
+    pub sort_order: Option<SortOrder>,
+    pub log_columns: Option<Vec<LogColumn>>,
+    pub log_interval_mins: Option<u32>,
+    pub agent_query: Option<String>,
+    pub agent_preview: Option<bool>,
+    /// Paths kept in JSON alert exports
+    pub export_fields: Option<Vec<String>>,
+    // Tables last: TOML needs plain values before them
+    pub log_filter: Option<LogFilter>,
+    /// Saved agent searches keyed by slot ("1" to "9"), recalled with Alt+1..9
+    pub saved_agent_searches: Option<std::collections::BTreeMap<String, String>>,
+}
+
+impl UiState {
+    pub fn path() -> PathBuf {
+        crate::config::ConfigManager::get_config_path().with_file_name("state.toml")
+    }
+
+    /// Missing or unreadable state is not an error, the defaults apply.
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        std::fs::write(Self::path(), toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn capture(app: &App) -> Self {
+        // The inspector needs a selected agent, so it reopens on the list instead
+        let view = match &app.active_view {
+            ActiveView::AgentInspector => ActiveView::AgentList,
+            v => v.clone(),
+        };
+        Self {
+            active_view: Some(view),
+            sort_column: Some(app.sort_column.clone()),
+            sort_order: Some(app.sort_order.clone()),
+            log_columns: Some(app.visible_log_columns.clone()),
+            log_interval_mins: Some(app.log_interval_mins),
+            agent_query: Some(app.agent_filter.raw_query.clone()).filter(|q| !q.is_empty()),
+            agent_preview: Some(app.agent_preview),
+            export_fields: Some(app.export_fields.clone()).filter(|f| !f.is_empty()),
+            log_filter: Some(app.log_filter.clone()),
+            saved_agent_searches: Some(app.saved_agent_searches.iter().map(|(slot, q)| (slot.to_string(), q.clone())).collect())
+                .filter(|m: &std::collections::BTreeMap<_, _>| !m.is_empty()),
+        }
+    }
+
+    pub fn apply(&self, app: &mut App) {
+        if let Some(view) = &self.active_view {
+            app.active_view = if *view == ActiveView::AgentInspector { ActiveView::AgentList } else { view.clone() };
+        }
+        if let Some(col) = &self.sort_column {
+            app.sort_column = col.clone();
+        }
+        if let Some(order) = &self.sort_order {
+            app.sort_order = order.clone();
+        }
+        if let Some(cols) = self.log_columns.as_ref().filter(|c| !c.is_empty()) {
+            app.visible_log_columns = cols.clone();
+        }
+        if let Some(mins) = self.log_interval_mins.filter(|m| *m > 0) {
+            app.log_interval_mins = mins;
+        }
+        if let Some(query) = &self.agent_query {
+            app.search_query = query.clone();
+            app.agent_filter = AgentFilter::parse(query);
+        }
+        if let Some(preview) = self.agent_preview {
+            app.agent_preview = preview;
+        }
+        if let Some(fields) = &self.export_fields {
+            app.export_fields = fields.clone();
+        }
+        if let Some(filter) = &self.log_filter {
+            app.log_filter = filter.clone();
+        }
+        for (slot, query) in self.saved_agent_searches.iter().flatten() {
+            if let Some(slot) = slot.parse::<u8>().ok().filter(|s| (1..=9).contains(s)) {
+                app.saved_agent_searches.insert(slot, query.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::app::state::UiState;
+use crate::app::{ActiveView, App, LogColumn, SeverityFilterMode, SortColumn, SortOrder};
+
+#[test]
+fn test_ui_state_round_trip() {
+    let mut app = App::new();
+    app.active_view = ActiveView::SecurityEvents;
+    app.sort_column = SortColumn::Name;
+    app.sort_order = SortOrder::Desc;
+    app.visible_log_columns = vec![LogColumn::Timestamp, LogColumn::SrcIp];
+    app.log_interval_mins = 240;
+    app.agent_filter = crate::app::filter::AgentFilter::parse("st:active");
+    app.agent_preview = true;
+    app.log_filter.mode = SeverityFilterMode::Range;
+    app.log_filter.val1 = 12;
+    app.log_filter.rule_id_filter = "5710".to_string();
+    app.log_filter.sort = crate::app::LogSortColumn::Level;
+    app.saved_agent_searches.insert(3, "st:disconnected os:windows".to_string());
+
+    let saved = toml::to_string_pretty(&UiState::capture(&app)).unwrap();
+    let state: UiState = toml::from_str(&saved).unwrap();
+
+    let mut restored = App::new();
+    state.apply(&mut restored);
+    assert_eq!(restored.active_view, ActiveView::SecurityEvents);
+    assert_eq!(restored.sort_column, SortColumn::Name);
+    assert_eq!(restored.sort_order, SortOrder::Desc);
+    assert_eq!(restored.visible_log_columns, vec![LogColumn::Timestamp, LogColumn::SrcIp]);
+    assert_eq!(restored.log_interval_mins, 240);
+    assert_eq!(restored.agent_filter.raw_query, "st:active");
+    assert!(restored.agent_preview);
+    assert_eq!(restored.log_filter, app.log_filter);
+    assert_eq!(restored.saved_agent_searches, app.saved_agent_searches);
+}
+
+#[test]
+fn test_ui_state_partial_file() {
+    let state: UiState = toml::from_str("active_view = \"AgentInspector\"\nlog_interval_mins = 0\n").unwrap();
+    let mut app = App::new();
+    let default_interval = app.log_interval_mins;
+    state.apply(&mut app);
+    assert_eq!(app.active_view, ActiveView::AgentList);
+    assert_eq!(app.log_interval_mins, default_interval);
+}
+
+#[test]
+fn test_saved_agent_searches() {
+    let mut app = App::new();
+    app.search_query = " st:disconnected os:windows ".to_string();
+    app.save_agent_search(2);
+    assert!(app.get_command_palette_matches().contains(&("Saved Search 2", "st:disconnected os:windows")));
+
+    app.active_view = ActiveView::Dashboard;
+    app.search_query.clear();
+    app.apply_agent_search(2);
+    assert_eq!(app.active_view, ActiveView::AgentList);
+    assert_eq!(app.agent_filter.raw_query, "st:disconnected os:windows");
+
+    // Saving an empty search clears the slot
+    app.search_query.clear();
+    app.save_agent_search(2);
+    assert!(app.saved_agent_searches.is_empty());
+
+    // Unknown slots in a hand-edited file are ignored
+    let state: UiState = toml::from_str("[saved_agent_searches]\n1 = \"g:web\"\n12 = \"st:active\"\n").unwrap();
+    state.apply(&mut app);
+    assert_eq!(app.saved_agent_searches.len(), 1);
+}
+
+#[test]
+fn test_live_search_waits_for_typing_pause() {
+    let mut app = App::new();
+    app.schedule_live_search();
+    assert!(app.live_search_due.is_none(), "only Security Events searches live");
+
+    app.active_view = ActiveView::SecurityEvents;
+    app.schedule_live_search();
+    let first = app.live_search_due.unwrap();
+    assert!(!app.live_search_ready(std::time::Instant::now()));
+    assert_eq!(app.live_search_status(), Some("waiting for typing to pause"));
+
+    // Another keystroke pushes the query back
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    app.schedule_live_search();
+    let due = app.live_search_due.unwrap();
+    assert!(due >= first + std::time::Duration::from_millis(5));
+
+    let later = due + std::time::Duration::from_millis(1);
+    assert!(app.live_search_ready(later));
+    assert!(!app.live_search_ready(later), "fires once per pause");
+    assert_eq!(app.live_search_status(), None);
+}
+
+#[test]
+fn test_active_log_filters() {
+    let mut filter = crate::app::LogFilter::default();
+    assert!(filter.active_filters().is_empty(), "min level 0 filters nothing");
+
+    filter.mode = SeverityFilterMode::Range;
+    filter.val1 = 7;
+    filter.val2 = 12;
+    filter.severities = vec!["critical".to_string(), "high".to_string()];
+    filter.rule_id_filter = "5710".to_string();
+    filter.description_filter = "ssh".to_string();
+    assert_eq!(filter.active_filters(), vec!["level 7-12", "critical+high", "rule:5710", "\"ssh\""]);
+}
+
+#[test]
+fn test_agent_actions_skip_the_manager() {
+    let mut app = App::new();
+    let ids = app.without_manager(vec!["001".to_string(), "000".to_string()], "Upgrade");
+    assert_eq!(ids, vec!["001"]);
+    assert!(app.notifications.last().map(|n| n.message.contains("skipped")).unwrap_or(false));
+
+    assert!(app.without_manager(vec!["000".to_string()], "Upgrade").is_empty());
+    let count = app.notifications.len();
+    app.without_manager(vec!["002".to_string()], "Upgrade");
+    assert_eq!(app.notifications.len(), count, "nothing to say without the manager");
+}
+
+#[test]
+fn test_all_profiles_merges_agent_lists() {
+    let agent = |id: &str, name: &str| crate::models::WazuhAgent {
+        id: id.to_string(),
+        name: name.to_string(),
+        ip: None,
+        status: "active".to_string(),
+        version: None,
+        node_name: None,
+        group: None,
+        date_add: None,
+        last_keep_alive: None,
+        os: None,
+        manager: None,
+        profile: None,
+    };
+    let mut app = App::new();
+    app.set_agents(vec![agent("001", "hq-web")]);
+    app.set_profile_agents("branch", Ok(vec![agent("001", "branch-web"), agent("002", "branch-db")]));
+    assert_eq!(app.agents.len(), 1, "profiles stay hidden until the mode is on");
+
+    app.toggle_all_profiles();
+    assert_eq!(app.agents.len(), 3);
+    assert_eq!(app.agents.iter().filter(|a| a.profile.as_deref() == Some("branch")).count(), 2);
+
+    // Refreshing this manager keeps the other sites, a failed site keeps its last list
+    app.set_agents(vec![agent("001", "hq-web"), agent("003", "hq-mail")]);
+    app.set_profile_agents("branch", Err("timed out".to_string()));
+    assert_eq!(app.agents.len(), 4);
+    assert_eq!(app.profile_errors, vec![("branch".to_string(), "timed out".to_string())]);
+
+    app.selected_agent_index = app.agents.iter().position(|a| a.name == "branch-db").unwrap();
+    assert!(app.refuse_profile_agent());
+    app.toggle_all_profiles();
+    assert!(app.agents.iter().all(|a| a.profile.is_none()));
+    assert!(!app.refuse_profile_agent());
+}
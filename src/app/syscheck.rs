@@ -0,0 +1,141 @@
+use serde_json::Value;
+
+/// Checks enabled by `check_all`; a directory with all of them is shown as such.
+const CHECK_ALL: [&str; 9] = [
+    "check_md5sum", "check_sha1sum", "check_sha256sum", "check_perm", "check_size",
+    "check_owner", "check_group", "check_mtime", "check_inode",
+];
+
+/// How changes under a directory are picked up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MonitorMode {
+    Scheduled,
+    Realtime,
+    Whodata,
+}
+
+impl MonitorMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            MonitorMode::Scheduled => "scheduled",
+            MonitorMode::Realtime => "realtime",
+            MonitorMode::Whodata => "whodata",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitoredDir {
+    pub path: String,
+    pub mode: MonitorMode,
+    pub report_changes: bool,
+    /// Options other than the mode and `report_changes`, with the file checks folded
+    /// into `check_all` when complete
+    pub options: Vec<String>,
+    pub recursion_level: Option<u64>,
+}
+
+/// The syscheck component config in the shape the Config tab shows it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyscheckSummary {
+    pub enabled: bool,
+    /// Seconds between scheduled scans
+    pub frequency: Option<u64>,
+    pub scan_time: Option<String>,
+    pub scan_on_start: Option<bool>,
+    /// `skip_nfs`, `skip_dev`, ... that are on, without the prefix
+    pub skipped: Vec<String>,
+    pub directories: Vec<MonitoredDir>,
+    /// Plain paths and `sregex` patterns, the latter prefixed with `regex:`
+    pub ignore: Vec<String>,
+    pub nodiff: Vec<String>,
+    pub registry: Vec<String>,
+}
+
+/// Reads the `syscheck` section returned by `WazuhApi::get_agent_config`. `None` when the
+/// value does not look like one (e.g. an error payload), so the caller can show raw JSON.
+pub fn parse_syscheck(config: &Value) -> Option<SyscheckSummary> {
+    let config = config.get("syscheck").unwrap_or(config).as_object()?;
+    if !config.contains_key("directories") && !config.contains_key("frequency") && !config.contains_key("disabled") {
+        return None;
+    }
+
+    let directories = config.get("directories").and_then(|d| d.as_array()).map(|dirs| {
+        dirs.iter().filter_map(|d| {
+            let path = d.get("dir").and_then(|p| p.as_str())?.to_string();
+            let opts: Vec<&str> = d.get("opts").and_then(|o| o.as_array())
+                .map(|o| o.iter().filter_map(|v| v.as_str()).collect())
+                .unwrap_or_default();
+            let mode = if opts.contains(&"whodata") {
+                MonitorMode::Whodata
+            } else if opts.contains(&"realtime") {
+                MonitorMode::Realtime
+            } else {
+                MonitorMode::Scheduled
+            };
+            let all_checks = CHECK_ALL.iter().all(|c| opts.contains(c));
+            let mut options: Vec<String> = opts.iter()
+                .filter(|o| !matches!(**o, "realtime" | "whodata" | "report_changes"))
+                .filter(|o| !(all_checks && CHECK_ALL.contains(o)))
+                .map(|o| o.to_string())
+                .collect();
+            if all_checks {
+                options.insert(0, "check_all".to_string());
+            }
+            Some(MonitoredDir {
+                path,
+                mode,
+                report_changes: opts.contains(&"report_changes"),
+                options,
+                recursion_level: d.get("recursion_level").and_then(|r| r.as_u64()),
+            })
+        }).collect()
+    }).unwrap_or_default();
+
+    let mut ignore = strings(config.get("ignore"));
+    ignore.extend(strings(config.get("ignore_sregex")).into_iter().map(|r| format!("regex:{}", r)));
+    // Windows agents list registry keys as `{ entry, arch, opts }`
+    let registry = config.get("windows_registry").and_then(|r| r.as_array())
+        .map(|keys| keys.iter().filter_map(|k| k.get("entry").and_then(|e| e.as_str())).map(String::from).collect())
+        .unwrap_or_default();
+
+    Some(SyscheckSummary {
+        enabled: !config.get("disabled").map(is_yes).unwrap_or(false),
+        frequency: config.get("frequency").and_then(|f| f.as_u64().or_else(|| f.as_str()?.parse().ok())),
+        scan_time: config.get("scan_time").and_then(|t| t.as_str()).map(String::from),
+        scan_on_start: config.get("scan_on_start").map(is_yes),
+        skipped: config.iter()
+            .filter(|(k, v)| k.starts_with("skip_") && is_yes(v))
+            .map(|(k, _)| k.trim_start_matches("skip_").to_string())
+            .collect(),
+        directories,
+        ignore,
+        nodiff: strings(config.get("nodiff")),
+        registry,
+    })
+}
+
+fn strings(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(items)) => items.iter().filter_map(|i| i.as_str()).map(String::from).collect(),
+        Some(Value::String(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    }
+}
+
+fn is_yes(value: &Value) -> bool {
+    match value {
+        Value::Bool(b) => *b,
+        Value::String(s) => matches!(s.to_lowercase().as_str(), "yes" | "true" | "1"),
+        _ => false,
+    }
+}
+
+/// `43200` -> `12h`, the way `<frequency>` is usually written.
+pub fn format_frequency(secs: u64) -> String {
+    if secs > 0 && secs.is_multiple_of(60) {
+        crate::app::format_interval_mins((secs / 60) as u32)
+    } else {
+        format!("{}s", secs)
+    }
+}
@@ -0,0 +1,46 @@
+use crate::app::syscheck::{format_frequency, parse_syscheck, MonitorMode};
+
+#[test]
+fn test_parse_syscheck_directories() {
+    let config = serde_json::json!({ "syscheck": {
+        "disabled": "no",
+        "frequency": 43200,
+        "scan_on_start": "yes",
+        "skip_nfs": "yes",
+        "skip_dev": "no",
+        "directories": [
+            { "dir": "/etc", "recursion_level": 256, "opts": [
+                "check_md5sum", "check_sha1sum", "check_sha256sum", "check_perm", "check_size",
+                "check_owner", "check_group", "check_mtime", "check_inode", "realtime", "report_changes"
+            ] },
+            { "dir": "/bin", "opts": ["check_sha256sum", "whodata", "follow_symbolic_link"] },
+            { "dir": "/boot", "opts": ["check_size"] }
+        ],
+        "ignore": ["/etc/mtab"],
+        "ignore_sregex": [".log$|.swp$"],
+        "nodiff": ["/etc/ssl/private.key"]
+    } });
+
+    let summary = parse_syscheck(&config).unwrap();
+    assert!(summary.enabled);
+    assert_eq!(summary.frequency.map(format_frequency).as_deref(), Some("12h"));
+    assert_eq!(summary.scan_on_start, Some(true));
+    assert_eq!(summary.skipped, vec!["nfs"]);
+
+    let etc = &summary.directories[0];
+    assert_eq!((etc.mode, etc.report_changes, etc.recursion_level), (MonitorMode::Realtime, true, Some(256)));
+    assert_eq!(etc.options, vec!["check_all"]);
+    assert_eq!(summary.directories[1].mode, MonitorMode::Whodata);
+    assert_eq!(summary.directories[1].options, vec!["check_sha256sum", "follow_symbolic_link"]);
+    assert_eq!(summary.directories[2].mode, MonitorMode::Scheduled);
+
+    assert_eq!(summary.ignore, vec!["/etc/mtab", "regex:.log$|.swp$"]);
+    assert_eq!(summary.nodiff, vec!["/etc/ssl/private.key"]);
+}
+
+#[test]
+fn test_parse_syscheck_unexpected_shape() {
+    assert!(parse_syscheck(&serde_json::json!({ "error": 1 })).is_none());
+    assert!(!parse_syscheck(&serde_json::json!({ "disabled": "yes" })).unwrap().enabled);
+    assert_eq!(format_frequency(90), "90s");
+}
@@ -0,0 +1,152 @@
+//! Screen model and key encoding for the embedded SSH pane. Escape sequences are parsed by
+//! the `vt100` crate; this module turns its cells into what the pane draws.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TermColor {
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CellStyle {
+    pub fg: Option<TermColor>,
+    pub bg: Option<TermColor>,
+    pub bold: bool,
+    pub underline: bool,
+    pub reverse: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TermCell {
+    pub ch: char,
+    pub style: CellStyle,
+}
+
+impl Default for TermCell {
+    fn default() -> Self {
+        Self { ch: ' ', style: CellStyle::default() }
+    }
+}
+
+/// Screen of the embedded SSH pane, parsed by `vt100`. There is no scrollback; lines scrolled
+/// off the top are gone.
+pub struct TermScreen {
+    parser: vt100::Parser,
+}
+
+impl std::fmt::Debug for TermScreen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TermScreen").field("size", &self.size()).field("cursor", &self.cursor()).finish()
+    }
+}
+
+fn term_color(color: vt100::Color) -> Option<TermColor> {
+    match color {
+        vt100::Color::Default => None,
+        vt100::Color::Idx(n) => Some(TermColor::Indexed(n)),
+        vt100::Color::Rgb(r, g, b) => Some(TermColor::Rgb(r, g, b)),
+    }
+}
+
+impl TermScreen {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self { parser: vt100::Parser::new(clamp_size(rows), clamp_size(cols), 0) }
+    }
+
+    /// Rows and columns.
+    pub fn size(&self) -> (usize, usize) {
+        let (rows, cols) = self.parser.screen().size();
+        (rows as usize, cols as usize)
+    }
+
+    /// Row and column, zero-based.
+    pub fn cursor(&self) -> (usize, usize) {
+        let (row, col) = self.parser.screen().cursor_position();
+        (row as usize, col as usize)
+    }
+
+    pub fn cursor_visible(&self) -> bool {
+        !self.parser.screen().hide_cursor()
+    }
+
+    /// Keeps the top-left content; the scroll region resets to the whole screen.
+    pub fn resize(&mut self, rows: usize, cols: usize) {
+        let (rows, cols) = (clamp_size(rows), clamp_size(cols));
+        let (row, col) = self.parser.screen().cursor_position();
+        // Keep the cursor line visible when shrinking: scroll the lines above it off first
+        if row >= rows {
+            let drop = row + 1 - rows;
+            self.parser.process(format!("\x1b[r\x1b[{}S\x1b[{};{}H", drop, row + 1 - drop, col + 1).as_bytes());
+        }
+        self.parser.screen_mut().set_size(rows, cols);
+    }
+
+    pub fn cell(&self, row: usize, col: usize) -> TermCell {
+        let Some(cell) = self.parser.screen().cell(row as u16, col as u16) else { return TermCell::default() };
+        TermCell {
+            ch: cell.contents().chars().next().unwrap_or(' '),
+            style: CellStyle {
+                fg: term_color(cell.fgcolor()),
+                bg: term_color(cell.bgcolor()),
+                bold: cell.bold(),
+                underline: cell.underline(),
+                reverse: cell.inverse(),
+            },
+        }
+    }
+
+    /// The row's characters with trailing blanks removed.
+    pub fn row_text(&self, row: usize) -> String {
+        let (_, cols) = self.parser.screen().size();
+        self.parser.screen().rows(0, cols).nth(row).map(|r| r.trim_end().to_string()).unwrap_or_default()
+    }
+
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.parser.process(bytes);
+    }
+}
+
+fn clamp_size(n: usize) -> u16 {
+    n.clamp(1, u16::MAX as usize) as u16
+}
+
+/// Bytes a terminal would send for the key, or None for keys with no encoding.
+pub fn key_bytes(key: &KeyEvent) -> Option<Vec<u8>> {
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    let mut bytes = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => match c.to_ascii_lowercase() {
+            c @ 'a'..='z' => vec![c as u8 - b'a' + 1],
+            ' ' | '@' | '2' => vec![0],
+            '[' | '3' => vec![0x1b],
+            '\\' | '4' => vec![0x1c],
+            '^' | '6' => vec![0x1e],
+            '_' | '7' | '/' => vec![0x1f],
+            _ => return None,
+        },
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::F(n @ 1..=4) => vec![0x1b, b'O', b'P' + n - 1],
+        KeyCode::F(n @ 5..=12) => format!("\x1b[{}~", [15, 17, 18, 19, 20, 21, 23, 24][n as usize - 5]).into_bytes(),
+        _ => return None,
+    };
+    if alt {
+        bytes.insert(0, 0x1b);
+    }
+    Some(bytes)
+}
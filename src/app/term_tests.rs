@@ -0,0 +1,80 @@
+use crate::app::pty::prefer_embedded;
+use crate::app::term::{key_bytes, TermColor, TermScreen};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[test]
+fn test_term_screen_text_and_wrap() {
+    let mut screen = TermScreen::new(3, 10);
+    screen.feed(b"user@web:~$ ls\r\n");
+    assert_eq!(screen.row_text(0), "user@web:~");
+    assert_eq!(screen.row_text(1), "$ ls");
+    assert_eq!(screen.cursor(), (2, 0));
+
+    // Scrolls once the bottom row is full
+    screen.feed("a\tb\r\nlast ✓".as_bytes());
+    assert_eq!(screen.row_text(0), "$ ls");
+    assert_eq!(screen.row_text(1), "a       b");
+    assert_eq!(screen.row_text(2), "last ✓");
+}
+
+#[test]
+fn test_term_screen_escape_sequences() {
+    let mut screen = TermScreen::new(4, 20);
+    screen.feed(b"\x1b]0;title\x07line one\r\nline two\x1b[1;6H\x1b[K\x1b[2;1H\x1b[1;31mX\x1b[0m");
+    assert_eq!(screen.row_text(0), "line");
+    assert_eq!(screen.row_text(1), "Xine two");
+    let cell = screen.cell(1, 0);
+    assert_eq!(cell.style.fg, Some(TermColor::Indexed(1)));
+    assert!(cell.style.bold);
+    assert_eq!(screen.cell(1, 1).style.fg, None);
+
+    screen.feed(b"\x1b[38;5;208mY\x1b[48;2;1;2;3mZ");
+    assert_eq!(screen.cell(1, 1).style.fg, Some(TermColor::Indexed(208)));
+    assert_eq!(screen.cell(1, 2).style.bg, Some(TermColor::Rgb(1, 2, 3)));
+
+    // Scroll region: a line feed on its last row only scrolls rows 2-3
+    screen.feed(b"\x1b[2J\x1b[Htop\x1b[2;3r\x1b[2;1Ha\r\nb\r\nc\x1b[?25l");
+    assert_eq!(screen.row_text(0), "top");
+    assert_eq!(screen.row_text(1), "b");
+    assert_eq!(screen.row_text(2), "c");
+    assert!(!screen.cursor_visible());
+}
+
+#[test]
+fn test_term_screen_resize_keeps_cursor_line() {
+    let mut screen = TermScreen::new(4, 10);
+    screen.feed(b"1\r\n2\r\n3\r\n4");
+    screen.resize(2, 5);
+    assert_eq!(screen.row_text(0), "3");
+    assert_eq!(screen.row_text(1), "4");
+    assert_eq!(screen.cursor(), (1, 1));
+}
+
+#[test]
+fn test_term_screen_restores_cursor_saved_before_shrinking() {
+    let mut screen = TermScreen::new(10, 20);
+    screen.feed(b"\x1b[9;15H\x1b7");
+    screen.resize(4, 8);
+    screen.feed(b"\x1b8x");
+    assert_eq!(screen.size(), (4, 8));
+    assert_eq!(screen.row_text(3), "       x");
+}
+
+#[test]
+fn test_key_bytes() {
+    let key = |code, modifiers| key_bytes(&KeyEvent::new(code, modifiers));
+    assert_eq!(key(KeyCode::Char('c'), KeyModifiers::CONTROL), Some(vec![3]));
+    assert_eq!(key(KeyCode::Char('é'), KeyModifiers::NONE), Some("é".as_bytes().to_vec()));
+    assert_eq!(key(KeyCode::Char('b'), KeyModifiers::ALT), Some(b"\x1bb".to_vec()));
+    assert_eq!(key(KeyCode::Up, KeyModifiers::NONE), Some(b"\x1b[A".to_vec()));
+    assert_eq!(key(KeyCode::F(5), KeyModifiers::NONE), Some(b"\x1b[15~".to_vec()));
+    assert_eq!(key(KeyCode::Enter, KeyModifiers::NONE), Some(b"\r".to_vec()));
+    assert_eq!(key(KeyCode::CapsLock, KeyModifiers::NONE), None);
+}
+
+#[test]
+fn test_prefer_embedded_ssh() {
+    let env = |vars: &'static [(&'static str, &'static str)]| move |k: &str| vars.iter().find(|(n, _)| *n == k).map(|(_, v)| v.to_string());
+    assert!(prefer_embedded(env(&[("SSH_CONNECTION", "10.0.0.1 5000 10.0.0.2 22"), ("DISPLAY", ":0")])));
+    assert!(!prefer_embedded(env(&[("DISPLAY", ":0")])));
+}
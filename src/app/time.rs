@@ -0,0 +1,72 @@
+use chrono::{DateTime, FixedOffset, Local, Offset, Utc};
+
+/// Timezone alert timestamps are displayed in.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DisplayTz {
+    #[default]
+    Local,
+    Utc,
+    Fixed(FixedOffset),
+}
+
+impl DisplayTz {
+    /// Accepts `local`, `utc` or a fixed offset such as `+02:00` or `-0530`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        match s.to_lowercase().as_str() {
+            "local" => return Some(DisplayTz::Local),
+            "utc" | "z" => return Some(DisplayTz::Utc),
+            _ => {}
+        }
+        let (sign, rest) = match s.chars().next()? {
+            '+' => (1, &s[1..]),
+            '-' => (-1, &s[1..]),
+            _ => return None,
+        };
+        let digits: String = rest.chars().filter(|c| *c != ':').collect();
+        if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let hours: i32 = digits[..2].parse().ok()?;
+        let minutes: i32 = digits[2..].parse().ok()?;
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(DisplayTz::Fixed)
+    }
+
+    /// `[display] timezone` from the config, falling back to local time when unset or invalid.
+    pub fn from_config(config: &crate::models::Config) -> Self {
+        config.display.timezone.as_deref().and_then(Self::parse).unwrap_or_default()
+    }
+
+    pub fn convert(&self, t: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            DisplayTz::Local => {
+                let local = t.with_timezone(&Local);
+                local.with_timezone(&local.offset().fix())
+            }
+            DisplayTz::Utc => t.with_timezone(&t.offset().fix()),
+            DisplayTz::Fixed(offset) => t.with_timezone(offset),
+        }
+    }
+
+    /// Short label for headers, e.g. `UTC` or `UTC+02:00`.
+    pub fn label(&self) -> String {
+        let offset = match self {
+            DisplayTz::Utc => return "UTC".to_string(),
+            DisplayTz::Local => Local::now().offset().fix(),
+            DisplayTz::Fixed(offset) => *offset,
+        };
+        if offset.local_minus_utc() == 0 {
+            "UTC".to_string()
+        } else {
+            format!("UTC{}", offset)
+        }
+    }
+
+    /// Formats an alert timestamp with `fmt`, or returns it unchanged if it cannot be parsed.
+    pub fn format(&self, ts: &str, fmt: &str) -> String {
+        match crate::models::parse_timestamp(ts) {
+            Some(t) => self.convert(t).format(fmt).to_string(),
+            None => ts.to_string(),
+        }
+    }
+}
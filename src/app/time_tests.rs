@@ -0,0 +1,22 @@
+use crate::app::time::DisplayTz;
+use chrono::FixedOffset;
+
+#[test]
+fn test_parse_display_tz() {
+    assert_eq!(DisplayTz::parse("UTC"), Some(DisplayTz::Utc));
+    assert_eq!(DisplayTz::parse("local"), Some(DisplayTz::Local));
+    assert_eq!(DisplayTz::parse("+02:00"), Some(DisplayTz::Fixed(FixedOffset::east_opt(7200).unwrap())));
+    assert_eq!(DisplayTz::parse("-0530"), Some(DisplayTz::Fixed(FixedOffset::east_opt(-19800).unwrap())));
+    assert_eq!(DisplayTz::parse("Europe/Berlin"), None);
+    assert_eq!(DisplayTz::parse("+2"), None);
+}
+
+#[test]
+fn test_format_converts_timezone() {
+    let tz = DisplayTz::parse("+02:00").unwrap();
+    assert_eq!(tz.format("2024-05-01T23:30:00.000Z", "%Y-%m-%d %H:%M"), "2024-05-02 01:30");
+    // Wazuh's `timestamp` field uses an offset without a colon
+    assert_eq!(tz.format("2024-05-01T23:30:00.000+0000", "%H:%M"), "01:30");
+    assert_eq!(DisplayTz::Utc.format("not a time", "%H:%M"), "not a time");
+    assert_eq!(tz.label(), "UTC+02:00");
+}
@@ -0,0 +1,56 @@
+use super::ActiveView;
+
+/// Part of the screen a tour step points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TourTarget {
+    Header,
+    Body,
+    Footer,
+    /// The advanced filter popup
+    FilterPopup,
+}
+
+/// One hint of the first-run tour, shown over `view`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TourStep {
+    pub view: ActiveView,
+    pub target: TourTarget,
+    pub title: &'static str,
+    pub text: &'static str,
+}
+
+/// Steps of the first-run tour, in order.
+pub fn tour_steps() -> Vec<TourStep> {
+    vec![
+        TourStep {
+            view: ActiveView::Dashboard,
+            target: TourTarget::Header,
+            title: "Welcome",
+            text: "The tabs are the views of the TUI; Tab moves to the next one. The header also shows where you came from and the timezone of every timestamp.",
+        },
+        TourStep {
+            view: ActiveView::Dashboard,
+            target: TourTarget::Body,
+            title: "Dashboard",
+            text: "Agent health and the alerts of the time interval (i to change it). 1-4 open the Critical, High, Medium or Low alerts; J jumps to an agent by name.",
+        },
+        TourStep {
+            view: ActiveView::AgentList,
+            target: TourTarget::Body,
+            title: "Agents",
+            text: "Enter inspects an agent, Space selects several for restart (R), upgrade (U) or group assignment (G). / searches, e.g. st:active os:linux.",
+        },
+        TourStep {
+            view: ActiveView::SecurityEvents,
+            target: TourTarget::FilterPopup,
+            title: "Alert filter",
+            text: "f opens this filter over the alerts. Tab switches between severity, agent, rule, text and the visible columns; Enter applies it.",
+        },
+        TourStep {
+            view: ActiveView::SecurityEvents,
+            target: TourTarget::Footer,
+            title: "Keys",
+            text: "The footer lists the keys of the current view. ? shows all of them with a search, and Ctrl+P opens the command palette, where \"Tour\" replays this tour.",
+        },
+    ]
+}
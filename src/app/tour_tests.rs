@@ -0,0 +1,44 @@
+use crate::app::tour::{tour_steps, TourTarget};
+use crate::app::{ActiveView, App, PopupMode};
+
+#[test]
+fn test_tour_walks_the_steps() {
+    let mut app = App::new();
+    app.start_tour();
+    assert_eq!(app.tour_step, Some(0));
+    assert_eq!(app.active_view, ActiveView::Dashboard);
+
+    let filter_step = tour_steps().iter().position(|s| s.target == TourTarget::FilterPopup).unwrap();
+    app.advance_tour(filter_step as i32);
+    assert_eq!(app.active_view, ActiveView::SecurityEvents);
+    assert_eq!(app.popup_mode, PopupMode::SeverityFilter);
+
+    // Back closes the popup again, and never goes before the first step
+    app.advance_tour(-1);
+    assert_eq!(app.popup_mode, PopupMode::None);
+    app.advance_tour(-10);
+    assert_eq!(app.tour_step, Some(0));
+
+    app.advance_tour(tour_steps().len() as i32);
+    assert_eq!(app.tour_step, None);
+    assert_eq!(app.active_view, ActiveView::Dashboard);
+}
+
+#[test]
+fn test_tour_pending_follows_config() {
+    let mut app = App::new();
+    assert!(!app.tour_pending());
+
+    let mut config = crate::models::Config {
+        url: "https://hq:55000".to_string(),
+        username: "wazuh".to_string(),
+        password: "secret".to_string(),
+        ..Default::default()
+    };
+    app.set_api(crate::api::WazuhApi::new(config.clone()));
+    assert!(!app.tour_pending());
+
+    config.display.tour_seen = Some(false);
+    app.set_api(crate::api::WazuhApi::new(config));
+    assert!(app.tour_pending());
+}
@@ -0,0 +1,46 @@
+use crate::models::WazuhAgent;
+
+/// Seconds a group change can be undone with `u`.
+pub const UNDO_SECS: u64 = 10;
+
+/// Group memberships a change added and removed, reversed by `u`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupUndo {
+    /// (group, agents) that were assigned and are removed again
+    pub added: Vec<(String, Vec<String>)>,
+    /// (group, agents) that were removed and are assigned again
+    pub removed: Vec<(String, Vec<String>)>,
+}
+
+impl GroupUndo {
+    /// Snapshot before assigning `agent_ids` to `group`; agents already in it stay there on
+    /// undo. `None` when the assignment changes nothing.
+    pub fn assignment(agents: &[WazuhAgent], group: &str, agent_ids: &[String]) -> Option<Self> {
+        let in_group = |id: &String| agents.iter()
+            .any(|a| &a.id == id && a.group.as_ref().is_some_and(|groups| groups.iter().any(|g| g == group)));
+        let new: Vec<String> = agent_ids.iter().filter(|id| !in_group(id)).cloned().collect();
+        if new.is_empty() {
+            return None;
+        }
+        Some(GroupUndo { added: vec![(group.to_string(), new)], removed: Vec::new() })
+    }
+
+    /// Snapshot of the membership editor's changes to one agent.
+    pub fn membership(agent_id: &str, add: &[String], remove: &[String]) -> Option<Self> {
+        if add.is_empty() && remove.is_empty() {
+            return None;
+        }
+        let one = |groups: &[String]| groups.iter().map(|g| (g.clone(), vec![agent_id.to_string()])).collect();
+        Some(GroupUndo { added: one(add), removed: one(remove) })
+    }
+
+    /// What undoing does, e.g. "remove 3 agents from web, add 1 agent to db".
+    pub fn summary(&self) -> String {
+        let agents = |n: usize| if n == 1 { "1 agent".to_string() } else { format!("{} agents", n) };
+        self.added.iter()
+            .map(|(group, ids)| format!("remove {} from {}", agents(ids.len()), group))
+            .chain(self.removed.iter().map(|(group, ids)| format!("add {} to {}", agents(ids.len()), group)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
@@ -0,0 +1,55 @@
+use crate::app::undo::GroupUndo;
+use crate::app::App;
+use crate::models::WazuhAgent;
+
+fn agent(id: &str, groups: &[&str]) -> WazuhAgent {
+    WazuhAgent {
+        id: id.to_string(),
+        name: format!("host-{}", id),
+        status: "active".to_string(),
+        group: Some(groups.iter().map(|g| g.to_string()).collect()),
+        ..Default::default()
+    }
+}
+
+fn ids(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_assignment_snapshot() {
+    let agents = vec![agent("001", &["default"]), agent("002", &["default", "web"]), agent("003", &["default"])];
+    // 002 was already in web, so undo leaves it there
+    let undo = GroupUndo::assignment(&agents, "web", &ids(&["001", "002", "003"])).unwrap();
+    assert_eq!(undo.added, vec![("web".to_string(), ids(&["001", "003"]))]);
+    assert!(undo.removed.is_empty());
+    assert_eq!(undo.summary(), "remove 2 agents from web");
+
+    assert_eq!(GroupUndo::assignment(&agents, "web", &ids(&["002"])), None);
+}
+
+#[test]
+fn test_membership_snapshot() {
+    let undo = GroupUndo::membership("001", &ids(&["web"]), &ids(&["db"])).unwrap();
+    assert_eq!(undo.added, vec![("web".to_string(), ids(&["001"]))]);
+    assert_eq!(undo.removed, vec![("db".to_string(), ids(&["001"]))]);
+    assert_eq!(undo.summary(), "remove 1 agent from web, add 1 agent to db");
+    assert_eq!(GroupUndo::membership("001", &[], &[]), None);
+}
+
+#[test]
+fn test_undo_window() {
+    let mut app = App::new();
+    let undo = GroupUndo::membership("001", &ids(&["web"]), &[]).unwrap();
+    app.offer_group_undo(undo.clone(), "Groups of agent 001 updated");
+    assert_eq!(app.notifications.last().unwrap().message, "Groups of agent 001 updated. Undo (u)");
+    assert_eq!(app.take_group_undo(), Some(undo.clone()));
+    // Undone once only
+    assert_eq!(app.take_group_undo(), None);
+
+    let expired = std::time::Instant::now().checked_sub(std::time::Duration::from_secs(11));
+    if let Some(at) = expired {
+        app.group_undo = Some((undo, at));
+        assert_eq!(app.take_group_undo(), None);
+    }
+}
@@ -0,0 +1,59 @@
+use serde_json::Value;
+
+/// One entry of an agent's `wmodules` configuration (docker-listener, osquery,
+/// syscollector, sca, command, ...).
+#[derive(Debug, Clone, PartialEq)]
+pub struct WodleStatus {
+    pub name: String,
+    pub enabled: bool,
+    /// Settings other than the on/off switch, by key
+    pub params: Vec<(String, String)>,
+}
+
+/// Lists the modules of a `wmodules` config. Wazuh returns an array of single-key
+/// objects; modules switch off with either `disabled: yes` or `enabled: no`, and are
+/// on when neither is set.
+pub fn parse_wmodules(config: &Value) -> Vec<WodleStatus> {
+    let entries = config.get("wmodules").unwrap_or(config);
+    let Some(entries) = entries.as_array() else { return Vec::new() };
+
+    entries.iter()
+        .filter_map(|entry| entry.as_object())
+        .flat_map(|obj| obj.iter())
+        .map(|(name, settings)| {
+            let mut enabled = true;
+            let mut params = Vec::new();
+            if let Some(settings) = settings.as_object() {
+                for (key, value) in settings {
+                    match key.as_str() {
+                        "disabled" => enabled = !is_yes(value),
+                        "enabled" => enabled = is_yes(value),
+                        _ => params.push((key.clone(), param_value(value))),
+                    }
+                }
+            }
+            WodleStatus { name: name.clone(), enabled, params }
+        })
+        .collect()
+}
+
+fn is_yes(value: &Value) -> bool {
+    match value {
+        Value::Bool(b) => *b,
+        Value::String(s) => matches!(s.to_lowercase().as_str(), "yes" | "true" | "1"),
+        _ => false,
+    }
+}
+
+/// Scalars as-is, nested blocks summarised so the table stays one line per module.
+fn param_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) if items.iter().all(|i| !i.is_object() && !i.is_array()) => {
+            items.iter().map(param_value).collect::<Vec<_>>().join(", ")
+        }
+        Value::Array(items) => format!("[{} items]", items.len()),
+        Value::Object(obj) => format!("{{{} keys}}", obj.len()),
+        other => other.to_string(),
+    }
+}
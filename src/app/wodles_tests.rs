@@ -0,0 +1,30 @@
+use crate::app::wodles::parse_wmodules;
+
+#[test]
+fn test_parse_wmodules_states_and_params() {
+    let config = serde_json::json!({ "wmodules": [
+        { "syscollector": { "disabled": "no", "interval": 3600, "scan-on-start": "yes" } },
+        { "sca": { "enabled": "yes", "policies": ["cis_ubuntu22-04.yml"] } },
+        { "docker-listener": { "disabled": "yes", "interval": "10m" } },
+        { "osquery": { "disabled": "no", "run_daemon": "yes", "packs": [{ "name": "it" }, { "name": "hw" }] } },
+        { "command": { "tag": "netstat", "command": "netstat -tulpn" } }
+    ] });
+
+    let modules = parse_wmodules(&config);
+    let states: Vec<_> = modules.iter().map(|m| (m.name.as_str(), m.enabled)).collect();
+    assert_eq!(states, vec![
+        ("syscollector", true), ("sca", true), ("docker-listener", false), ("osquery", true), ("command", true),
+    ]);
+    assert_eq!(modules[0].params, vec![
+        ("interval".to_string(), "3600".to_string()),
+        ("scan-on-start".to_string(), "yes".to_string()),
+    ]);
+    assert_eq!(modules[1].params[0].1, "cis_ubuntu22-04.yml");
+    assert_eq!(modules[3].params[0], ("packs".to_string(), "[2 items]".to_string()));
+}
+
+#[test]
+fn test_parse_wmodules_unexpected_shape() {
+    assert!(parse_wmodules(&serde_json::json!({ "error": 1 })).is_empty());
+    assert!(!parse_wmodules(&serde_json::json!([{ "sca": { "enabled": "no" } }]))[0].enabled);
+}
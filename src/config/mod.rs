@@ -1,11 +1,72 @@
-use std::path::PathBuf;
-use anyhow::Result;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, anyhow};
 use directories::ProjectDirs;
 use std::fs;
 use crate::models::Config;
 
 pub struct ConfigManager;
 
+/// Command line flags that take precedence over the TOML file and the environment.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CliArgs {
+    pub config_path: Option<PathBuf>,
+    pub url: Option<String>,
+    pub user: Option<String>,
+    /// Headless mode: write a report to this path and exit
+    pub snapshot: Option<PathBuf>,
+    /// Snapshot time window such as `30m`, `24h` or `7d`
+    pub window: Option<String>,
+    pub max_critical: Option<u32>,
+    pub max_high: Option<u32>,
+    /// Write debug logs to the data dir (see `crate::logging`)
+    pub debug: bool,
+}
+
+impl CliArgs {
+    /// Parses `--config <path>`, `--url <url>`, `--user <name>` and the snapshot flags
+    /// `--snapshot <path>`, `--window <dur>`, `--max-critical <n>`, `--max-high <n>`
+    /// (all also accepted as `--flag=value`), plus the `--debug` switch.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut cli = CliArgs::default();
+        let mut iter = args.into_iter();
+
+        while let Some(arg) = iter.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+                _ => (arg.clone(), None),
+            };
+
+            if flag == "--debug" && inline_value.is_none() {
+                cli.debug = true;
+                continue;
+            }
+
+            if !matches!(flag.as_str(), "--config" | "-c" | "--url" | "--user" | "--username" | "--snapshot" | "--window" | "--max-critical" | "--max-high") {
+                return Err(anyhow!("Unknown argument: {}", arg));
+            }
+
+            let value = match inline_value {
+                Some(v) => v,
+                None => iter.next().ok_or_else(|| anyhow!("Missing value for {}", flag))?,
+            };
+
+            match flag.as_str() {
+                "--config" | "-c" => cli.config_path = Some(PathBuf::from(value)),
+                "--url" => cli.url = Some(value),
+                "--snapshot" => cli.snapshot = Some(PathBuf::from(value)),
+                "--window" => cli.window = Some(value),
+                "--max-critical" | "--max-high" => {
+                    let n = value.parse::<u32>().map_err(|_| anyhow!("{} expects a number, got '{}'", flag, value))?;
+                    if flag == "--max-critical" { cli.max_critical = Some(n) } else { cli.max_high = Some(n) }
+                }
+                _ => cli.user = Some(value),
+            }
+        }
+
+        Ok(cli)
+    }
+}
+
 impl ConfigManager {
     pub fn get_config_path() -> PathBuf {
         let proj_dirs = ProjectDirs::from("com", "wazuh", "wazuh-tui")
@@ -17,17 +78,77 @@ impl ConfigManager {
         config_dir.join("config.toml")
     }
 
-    pub fn load() -> Result<Config> {
-        let path = Self::get_config_path();
+    pub fn load_from(path: &Path) -> Result<Config> {
         let content = fs::read_to_string(path)?;
         let config: Config = toml::from_str(&content)?;
         Ok(config)
     }
 
-    pub fn save(config: &Config) -> Result<()> {
-        let path = Self::get_config_path();
+    /// The file `resolve` reads: `--config` when given, the default location otherwise.
+    pub fn config_path(cli: &CliArgs) -> PathBuf {
+        cli.config_path.clone().unwrap_or_else(Self::get_config_path)
+    }
+
+    /// Resolves the effective configuration: TOML file (default or `--config`),
+    /// then `WAZUH_TUI_*` environment variables, then `--url`/`--user` flags.
+    /// Works without any file as long as URL, username and password are provided.
+    pub fn resolve(cli: &CliArgs) -> Result<Config> {
+        let path = Self::config_path(cli);
+        let file_config = Self::load_from(&path);
+
+        let mut config = match file_config {
+            Ok(c) => c,
+            Err(e) => {
+                if cli.config_path.is_some() {
+                    return Err(anyhow!("Failed to read config {}: {}", path.display(), e));
+                }
+                Config::default()
+            }
+        };
+
+        Self::apply_overrides(&mut config, |key| std::env::var(key).ok(), cli);
+
+        if config.url.is_empty() || config.username.is_empty() || config.password.is_empty() {
+            return Err(anyhow!("Configuration not found"));
+        }
+        Ok(config)
+    }
+
+    pub fn apply_overrides<F: Fn(&str) -> Option<String>>(config: &mut Config, env: F, cli: &CliArgs) {
+        if let Some(v) = env("WAZUH_TUI_URL") { config.url = v; }
+        if let Some(v) = env("WAZUH_TUI_USERNAME") { config.username = v; }
+        if let Some(v) = env("WAZUH_TUI_PASSWORD") { config.password = v; }
+        if let Some(v) = env("WAZUH_TUI_OS_URL") { config.os_url = Some(v); }
+        if let Some(v) = env("WAZUH_TUI_AUTH_CONTEXT") { config.auth_context = Some(v); }
+
+        if let Some(v) = &cli.url { config.url = v.clone(); }
+        if let Some(v) = &cli.user { config.username = v.clone(); }
+
+        // OpenSearch shares the Wazuh credentials unless configured separately
+        if config.os_url.is_some() {
+            if config.os_username.is_none() { config.os_username = Some(config.username.clone()); }
+            if config.os_password.is_none() { config.os_password = Some(config.password.clone()); }
+        }
+    }
+
+    /// Changes the config file at `path` in place, leaving environment and flag overrides out
+    /// of it. A missing file (a setup from environment variables alone) is created.
+    pub fn update<F: FnOnce(&mut Config)>(path: &Path, change: F) -> Result<()> {
+        let mut config = match Self::load_from(path) {
+            Ok(config) => config,
+            Err(e) if e.downcast_ref::<std::io::Error>().is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound) => Config::default(),
+            Err(e) => return Err(e),
+        };
+        change(&mut config);
+        Self::save(path, &config)
+    }
+
+    pub fn save(path: &Path, config: &Config) -> Result<()> {
         let content = toml::to_string_pretty(config)?;
         fs::write(path, content)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,194 @@
+use crate::config::{CliArgs, ConfigManager};
+use crate::models::Config;
+use crate::ui::icons::IconSet;
+use std::path::PathBuf;
+
+fn empty_config() -> Config {
+    Config {
+        url: "https://file:55000".to_string(),
+        username: "file-user".to_string(),
+        password: "file-pass".to_string(),
+        os_url: None,
+        os_username: None,
+        os_password: None,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_cli_args_parse() {
+    let args = vec!["--config", "/tmp/wazuh.toml", "--url=https://cli:55000", "--user", "admin"]
+        .into_iter().map(String::from);
+    let cli = CliArgs::parse(args).unwrap();
+
+    assert_eq!(cli.config_path, Some(PathBuf::from("/tmp/wazuh.toml")));
+    assert_eq!(cli.url.as_deref(), Some("https://cli:55000"));
+    assert_eq!(cli.user.as_deref(), Some("admin"));
+}
+
+#[test]
+fn test_cli_args_snapshot_flags() {
+    let args = vec!["--snapshot", "/tmp/report.md", "--window=7d", "--max-critical", "0", "--max-high=25"]
+        .into_iter().map(String::from);
+    let cli = CliArgs::parse(args).unwrap();
+
+    assert_eq!(cli.snapshot, Some(PathBuf::from("/tmp/report.md")));
+    assert_eq!(cli.window.as_deref(), Some("7d"));
+    assert_eq!(cli.max_critical, Some(0));
+    assert_eq!(cli.max_high, Some(25));
+    assert!(!cli.debug);
+}
+
+#[test]
+fn test_cli_args_debug_switch() {
+    let cli = CliArgs::parse(vec!["--debug", "--user", "admin"].into_iter().map(String::from)).unwrap();
+    assert!(cli.debug);
+    assert_eq!(cli.user.as_deref(), Some("admin"));
+    assert!(CliArgs::parse(vec!["--debug=yes".to_string()]).is_err());
+}
+
+#[test]
+fn test_cli_args_errors() {
+    assert!(CliArgs::parse(vec!["--max-high".to_string(), "many".to_string()]).is_err());
+    assert!(CliArgs::parse(vec!["--url".to_string()]).is_err());
+    assert!(CliArgs::parse(vec!["--bogus".to_string(), "x".to_string()]).is_err());
+}
+
+#[test]
+fn test_overrides_precedence() {
+    let mut config = empty_config();
+    let env = |key: &str| match key {
+        "WAZUH_TUI_URL" => Some("https://env:55000".to_string()),
+        "WAZUH_TUI_PASSWORD" => Some("env-pass".to_string()),
+        "WAZUH_TUI_OS_URL" => Some("https://env:9200".to_string()),
+        "WAZUH_TUI_AUTH_CONTEXT" => Some(r#"{"name":"jdoe"}"#.to_string()),
+        _ => None,
+    };
+    let cli = CliArgs { url: Some("https://cli:55000".to_string()), ..Default::default() };
+
+    ConfigManager::apply_overrides(&mut config, env, &cli);
+
+    // Flags beat environment, environment beats file
+    assert_eq!(config.url, "https://cli:55000");
+    assert_eq!(config.username, "file-user");
+    assert_eq!(config.password, "env-pass");
+    assert_eq!(config.os_url.as_deref(), Some("https://env:9200"));
+    assert_eq!(config.os_password.as_deref(), Some("env-pass"));
+    assert_eq!(config.auth_context.as_deref(), Some(r#"{"name":"jdoe"}"#));
+}
+
+#[test]
+fn test_profiles_section() {
+    let config: Config = toml::from_str(r#"
+        url = "https://hq:55000"
+        username = "wazuh"
+        password = "secret"
+
+        [network]
+        api_timeout_secs = 20
+
+        [[profiles]]
+        name = "branch-office"
+        url = "https://branch:55000"
+        username = "viewer"
+        password = "viewer-pass"
+    "#).unwrap();
+    assert_eq!(config.profiles.len(), 1);
+    let branch = config.profiles[0].to_config(&config);
+    assert_eq!((branch.url.as_str(), branch.username.as_str()), ("https://branch:55000", "viewer"));
+    assert_eq!(branch.network.api_timeout_secs, Some(20));
+
+    // Configs without profiles are written back without the section
+    assert!(!toml::to_string(&empty_config()).unwrap().contains("profiles"));
+}
+
+#[test]
+fn test_indices_section() {
+    let config: Config = toml::from_str(r#"
+        url = "https://wazuh:55000"
+        username = "wazuh"
+        password = "secret"
+
+        [indices]
+        alerts = "site-a:wazuh-alerts-*"
+    "#).unwrap();
+    assert_eq!(config.indices.alerts(), "site-a:wazuh-alerts-*");
+    assert_eq!(config.indices.vulnerabilities(), "wazuh-states-vulnerabilities*");
+    assert_eq!(config.indices.health(), "site-a:wazuh-alerts-*,wazuh-states-vulnerabilities*");
+
+    // Without the section the built-in patterns apply
+    let defaults = empty_config();
+    assert_eq!(defaults.indices.alerts(), "wazuh-alerts-*");
+    assert_eq!(defaults.indices.health(), "wazuh-alerts-*,wazuh-states-*");
+}
+
+#[test]
+fn test_wizard_indices() {
+    let mut app = crate::app::App::new();
+    // Prefilled defaults are not written to the config
+    let indices = app.wizard_indices();
+    assert!(indices.alerts.is_none() && indices.vulnerabilities.is_none());
+
+    app.config_alerts_index = " custom-alerts-* ".to_string();
+    app.config_vuln_index.clear();
+    let indices = app.wizard_indices();
+    assert_eq!(indices.alerts.as_deref(), Some("custom-alerts-*"));
+    assert!(indices.vulnerabilities.is_none());
+}
+
+#[test]
+fn test_export_section() {
+    let config: Config = toml::from_str(r#"
+        url = "https://hq:55000"
+        username = "wazuh"
+        password = "secret"
+
+        [export]
+        max_alerts = 50000
+        dir = "~/wazuh-exports"
+        filename = "{view}_{agent}_{to}"
+        compress = "gzip"
+    "#).unwrap();
+    assert_eq!(config.export.max_alerts, Some(50000));
+    assert_eq!(config.export.dir.as_deref(), Some("~/wazuh-exports"));
+    assert_eq!(config.export.filename.as_deref(), Some("{view}_{agent}_{to}"));
+    assert_eq!(config.export.compress.as_deref(), Some("gzip"));
+    assert_eq!(empty_config().export.max_alerts, None);
+}
+
+#[test]
+fn test_display_icons() {
+    let config: Config = toml::from_str(r#"
+        url = "https://hq:55000"
+        username = "wazuh"
+        password = "secret"
+
+        [display]
+        icons = "ascii"
+    "#).unwrap();
+    assert_eq!(IconSet::from_config(&config), IconSet::Ascii);
+    assert_eq!(IconSet::from_config(&empty_config()), IconSet::Nerd);
+}
+
+#[test]
+fn test_update_writes_the_given_path() {
+    let dir = std::env::temp_dir().join(format!("wazuh-tui-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("custom.toml");
+    let _ = std::fs::remove_file(&path);
+
+    // An environment-only setup has no file yet
+    ConfigManager::update(&path, |config| config.display.tour_seen = Some(true)).unwrap();
+    assert_eq!(ConfigManager::load_from(&path).unwrap().display.tour_seen, Some(true));
+
+    ConfigManager::save(&path, &empty_config()).unwrap();
+    ConfigManager::update(&path, |config| config.username = "relogin".to_string()).unwrap();
+    let config = ConfigManager::load_from(&path).unwrap();
+    assert_eq!(config.username, "relogin");
+    assert_eq!(config.url, "https://file:55000");
+
+    let cli = CliArgs { config_path: Some(path.clone()), ..Default::default() };
+    assert_eq!(ConfigManager::config_path(&cli), path);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,43 @@
+//! Debug logging to daily-rotated files. Off unless `--debug` is passed or `RUST_LOG`
+//! is set, so normal runs never write anything.
+
+use directories::ProjectDirs;
+use std::path::PathBuf;
+use tracing_subscriber::EnvFilter;
+
+/// Rotated files kept before the oldest is deleted.
+pub const LOG_FILES_KEPT: usize = 7;
+
+/// `logs/` under the platform data dir (e.g. `~/.local/share/wazuh-tui/logs`).
+pub fn log_dir() -> PathBuf {
+    ProjectDirs::from("com", "wazuh", "wazuh-tui")
+        .or_else(|| ProjectDirs::from("", "", "wazuh-tui"))
+        .map(|dirs| dirs.data_dir().join("logs"))
+        .unwrap_or_else(|| PathBuf::from("logs"))
+}
+
+/// Starts the file logger. `RUST_LOG` picks the filter when set, `--debug` alone logs
+/// this crate at debug level. The returned guard flushes the writer when dropped.
+pub fn init(debug: bool) -> anyhow::Result<Option<tracing_appender::non_blocking::WorkerGuard>> {
+    let filter = match std::env::var("RUST_LOG") {
+        Ok(directives) if !directives.is_empty() => EnvFilter::new(directives),
+        _ if debug => EnvFilter::new("wazuh_rust_tui=debug"),
+        _ => return Ok(None),
+    };
+
+    let appender = tracing_appender::rolling::Builder::new()
+        .rotation(tracing_appender::rolling::Rotation::DAILY)
+        .filename_prefix("wazuh-tui")
+        .filename_suffix("log")
+        .max_log_files(LOG_FILES_KEPT)
+        .build(log_dir())?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(false)
+        .init();
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "logging started");
+    Ok(Some(guard))
+}
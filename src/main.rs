@@ -3,9 +3,13 @@ pub mod api;
 pub mod config;
 pub mod app;
 pub mod ui;
+pub mod terminal;
+pub mod logging;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 
 use crate::app::{App, ActiveView};
-use crate::config::ConfigManager;
+use crate::config::{CliArgs, ConfigManager};
 use crate::api::WazuhApi;
 use anyhow::Result;
 use crossterm::{
@@ -18,25 +22,1007 @@ use std::io;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
+/// Loads API users, roles and policies for the Users & Roles view.
+async fn fetch_security_data(api: &WazuhApi, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    match api.list_users().await {
+        Ok(res) => { let _ = tx.send(crate::app::DataUpdate::SecurityUsers(res.data.affected_items)).await; }
+        Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load users: {}", e))).await; }
+    }
+    if let Ok(res) = api.list_roles().await {
+        let _ = tx.send(crate::app::DataUpdate::SecurityRoles(res.data.affected_items)).await;
+    }
+    if let Ok(res) = api.list_policies().await {
+        let _ = tx.send(crate::app::DataUpdate::SecurityPolicies(res.data.affected_items)).await;
+    }
+}
+
+async fn fetch_cdb_lists(api: &WazuhApi, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    match api.get_cdb_lists().await {
+        Ok(res) => { let _ = tx.send(crate::app::DataUpdate::CdbLists(res.data.affected_items)).await; }
+        Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load CDB lists: {}", e))).await; }
+    }
+}
+
+async fn fetch_rule_files(api: &WazuhApi, tx: &mpsc::Sender<crate::app::DataUpdate>, kind: crate::app::rules::RulesetKind) {
+    match api.get_ruleset_files(kind).await {
+        Ok(res) => { let _ = tx.send(crate::app::DataUpdate::RuleFiles { kind, files: res.data.affected_items }).await; }
+        Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load {} files: {}", kind.endpoint(), e))).await; }
+    }
+}
+
+/// Opens an SSH session to `target` in the full-screen pane instead of a terminal window.
+fn open_embedded_ssh(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, target: &str) {
+    // The pane's border takes one cell on each side; the draw resizes it to the exact area
+    let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    let tx = tx.clone();
+    let on_output = move |bytes| { let _ = tx.blocking_send(crate::app::DataUpdate::SshOutput(bytes)); };
+    match crate::app::pty::SshSession::spawn(target, rows.saturating_sub(2).max(1), cols.saturating_sub(2).max(1), on_output) {
+        Ok(session) => {
+            app.ssh_session = Some(session);
+            app.popup_mode = crate::app::PopupMode::SshSession;
+        }
+        Err(e) => app.notify(&format!("Failed to start ssh: {}", e), crate::app::NotificationLevel::Error),
+    }
+}
+
+/// Keys go to the embedded SSH session. Ctrl+] closes the pane, as does any key after ssh exited.
+fn handle_ssh_key(app: &mut App, key: &crossterm::event::KeyEvent) {
+    let ctrl = key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL);
+    // Terminals report Ctrl+] as either `]` or `5` with Ctrl
+    let close_key = ctrl && matches!(key.code, KeyCode::Char(']') | KeyCode::Char('5'));
+    if close_key || app.ssh_session.as_ref().map(|s| s.exited).unwrap_or(true) {
+        app.close_ssh_session();
+    } else if let (Some(session), Some(bytes)) = (app.ssh_session.as_mut(), crate::app::term::key_bytes(key)) {
+        session.write(&bytes);
+    }
+}
+
+/// Suspends the TUI, opens `content` in the user's editor and returns the saved text.
+fn edit_externally(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, filename: &str, content: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("wazuh-tui-{}-{}", std::process::id(), filename));
+    std::fs::write(&path, content)?;
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    let (program, args) = crate::app::rules::editor_command();
+    let status = std::process::Command::new(&program).args(&args).arg(&path).status();
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    terminal.clear()?;
+
+    let status = status.map_err(|e| anyhow::anyhow!("Failed to start {}: {}", program, e))?;
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    if !status.success() {
+        return Err(anyhow::anyhow!("{} exited with {}", program, status));
+    }
+    Ok(edited?)
+}
+
+/// Refreshes agents, groups, CVE counts, the alert digest, the alert totals and the health score inputs. The
+/// Wazuh API calls run concurrently with the `_msearch` requests to the indexer, and are timed.
+async fn load_dashboard(api: &WazuhApi, tx: &mpsc::Sender<crate::app::DataUpdate>, minutes: u32, limit: u32, tz: crate::app::time::DisplayTz) {
+    let started = Instant::now();
+    let wazuh = async {
+        let (agents, groups) = tokio::join!(api.list_agents(None, 0, 500), api.get_groups());
+        (agents, groups, started.elapsed())
+    };
+    let indexer = async { (api.get_dashboard_searches(minutes, limit).await, started.elapsed()) };
+    let health = api.get_health_inputs(minutes, crate::app::AGENT_VULN_BUCKETS);
+    let totals = api.get_alert_severity_counts(minutes);
+    let ((agents, groups, api_time), (searches, indexer_time), health, totals) = tokio::join!(wazuh, indexer, health, totals);
+
+    if let Ok(agents_res) = agents {
+        let _ = tx.send(crate::app::DataUpdate::Agents(agents_res.data.affected_items)).await;
+    }
+    if let Ok(groups_res) = groups {
+        let _ = tx.send(crate::app::DataUpdate::Groups(groups_res.data.affected_items)).await;
+    }
+    match searches {
+        Ok((alerts, vulns)) => {
+            if let Ok(summary) = vulns {
+                let _ = tx.send(crate::app::DataUpdate::VulnSummary(summary)).await;
+            }
+            if let Some(hits) = alerts.as_ref().ok().and_then(|r| r.pointer("/hits/hits")).and_then(|h| h.as_array()) {
+                send_alert_digest(tx, hits, tz, api.severity()).await;
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "dashboard search failed"),
+    }
+    match health {
+        Ok((alerts, vulns)) => {
+            if let Ok(counts) = alerts {
+                let _ = tx.send(crate::app::DataUpdate::AgentHighAlerts(counts)).await;
+            }
+            if let Ok(counts) = vulns {
+                let _ = tx.send(crate::app::DataUpdate::AgentVulnCounts(counts)).await;
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "health score search failed"),
+    }
+    match totals {
+        Ok(stats) => {
+            let _ = tx.send(crate::app::DataUpdate::AlertTotals(stats)).await;
+        }
+        Err(e) => tracing::warn!(error = %e, "alert total search failed"),
+    }
+
+    let timing = crate::app::LoadTiming {
+        api_ms: api_time.as_millis() as u64,
+        indexer_ms: indexer_time.as_millis() as u64,
+        total_ms: started.elapsed().as_millis() as u64,
+    };
+    tracing::info!(api_ms = timing.api_ms, indexer_ms = timing.indexer_ms, total_ms = timing.total_ms, "dashboard loaded");
+    let _ = tx.send(crate::app::DataUpdate::DashboardTiming(timing)).await;
+}
+
+/// Sends the dashboard figures derived from a batch of alert hits.
+async fn send_alert_digest(tx: &mpsc::Sender<crate::app::DataUpdate>, hits: &[serde_json::Value], tz: crate::app::time::DisplayTz, scale: crate::app::severity::SeverityScale) {
+    let digest = crate::app::report::AlertDigest::from_hits(hits, tz, scale);
+    let _ = tx.send(crate::app::DataUpdate::ThreatStats(digest.stats)).await;
+    let _ = tx.send(crate::app::DataUpdate::AlertHistory(digest.history)).await;
+    let _ = tx.send(crate::app::DataUpdate::TopAgents(digest.top_agents)).await;
+    let _ = tx.send(crate::app::DataUpdate::TopRules(digest.top_rules)).await;
+    let _ = tx.send(crate::app::DataUpdate::TopCountries(digest.top_countries)).await;
+    let _ = tx.send(crate::app::DataUpdate::CriticalAlerts(digest.critical)).await;
+}
+
+/// Reads the API and indexer certificates; `open` shows them in the certificate popup.
+fn spawn_certificates(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, open: bool) {
+    if open {
+        app.certificates = None;
+        app.popup_mode = crate::app::PopupMode::Certificates;
+    }
+    let Some(api) = app.api.clone() else { return };
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let chains = api.certificate_chains().await;
+        let _ = tx.send(crate::app::DataUpdate::Certificates(chains)).await;
+    });
+}
+
+/// Opens the indexer health popup and loads cluster, index and disk figures.
+fn spawn_indexer_health(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    app.indexer_health = None;
+    app.indexer_health_index = 0;
+    app.popup_mode = crate::app::PopupMode::IndexerHealth;
+    let Some(api) = app.api.clone() else { return };
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let health = api.get_indexer_health().await.map_err(|e| e.to_string());
+        let _ = tx.send(crate::app::DataUpdate::IndexerHealth(health)).await;
+    });
+}
+
+/// Loads the per-day severity histogram when the dashboard shows a multi-day trend.
+fn spawn_alert_trend(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let (Some(api), Some(days)) = (app.api.clone(), app.trend_range.days()) else { return };
+    // Day boundaries follow the display timezone
+    let time_zone = app.display_tz.convert(chrono::Utc::now()).offset().to_string();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        match api.get_alert_trend(days, &time_zone).await {
+            Ok(trend) => { let _ = tx.send(crate::app::DataUpdate::AlertTrend(trend)).await; }
+            Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load alert trend: {}", e))).await; }
+        }
+    });
+}
+
+/// Aggregates alerts and CVEs over the agents of `group` for the posture pane.
+fn spawn_group_posture(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>, group: String) {
+    let Some(api) = app.api.clone() else { return };
+    let interval = app.log_interval_mins;
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = match api.list_agents(Some(&group), 0, 500).await {
+            Ok(res) => {
+                let ids: Vec<String> = res.data.affected_items.into_iter().map(|a| a.id).collect();
+                api.get_group_posture(&ids, interval).await
+            }
+            Err(e) => Err(e),
+        };
+        let posture = match result {
+            Ok(posture) => Some(posture),
+            Err(e) => {
+                let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load posture of {}: {}", group, e))).await;
+                None
+            }
+        };
+        let _ = tx.send(crate::app::DataUpdate::GroupPosture { group, posture }).await;
+    });
+}
+
+/// Loads every Agent Inspector tab for `agent_id` concurrently. Each call bumps the
+/// inspector generation and aborts the previous load, so responses for an agent the
+/// user already moved away from are discarded instead of overwriting the new one.
+fn spawn_inspector_load(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, agent_id: String, errors_as_popup: bool) {
+    let Some(api) = app.api.clone() else { return };
+    if let Some(task) = app.inspector_task.take() {
+        task.abort();
+    }
+    app.reset_inspector_data(&agent_id);
+    app.inspector_generation += 1;
+    let generation = app.inspector_generation;
+    tracing::debug!(agent_id, generation, "inspector load");
+    if app.config_diff_mode {
+        spawn_config_baseline(app, tx);
+    }
+    let interval = app.log_interval_mins;
+    let config_component = app.agent_config_component.clone();
+    let tx = tx.clone();
+
+    app.inspector_task = Some(tokio::spawn(async move {
+        let send = |update: crate::app::DataUpdate| {
+            let tx = tx.clone();
+            async move {
+                let _ = tx.send(crate::app::DataUpdate::Inspector { generation, update: Box::new(update) }).await;
+            }
+        };
+        let error = |title: &str, message: String| {
+            if errors_as_popup {
+                crate::app::DataUpdate::ErrorPopup { title: title.to_string(), message }
+            } else {
+                crate::app::DataUpdate::Error(message)
+            }
+        };
+
+        tokio::join!(
+            async {
+                if let Ok(hw_res) = api.get_hardware_info(&agent_id).await {
+                    if let Some(hw) = hw_res.data.affected_items.into_iter().next() {
+                        send(crate::app::DataUpdate::AgentHardware(hw)).await;
+                    }
+                }
+            },
+            async {
+                // Pages are shown as they arrive; a failed page keeps what was loaded
+                let mut offset = 0;
+                while let Ok(res) = api.get_processes(&agent_id, offset, crate::app::SYSCOLLECTOR_PAGE_SIZE).await {
+                    let (count, total) = (res.data.affected_items.len() as u32, res.data.total_affected_items);
+                    send(crate::app::DataUpdate::AgentProcesses { items: res.data.affected_items, total }).await;
+                    offset += count;
+                    if count == 0 || offset >= total {
+                        break;
+                    }
+                }
+            },
+            async {
+                let mut offset = 0;
+                while let Ok(res) = api.get_programs(&agent_id, offset, crate::app::SYSCOLLECTOR_PAGE_SIZE).await {
+                    let (count, total) = (res.data.affected_items.len() as u32, res.data.total_affected_items);
+                    send(crate::app::DataUpdate::AgentPrograms { items: res.data.affected_items, total }).await;
+                    offset += count;
+                    if count == 0 || offset >= total {
+                        break;
+                    }
+                }
+            },
+            async {
+                match api.get_vulnerabilities(&agent_id).await {
+                    Ok(vuln_res) => send(crate::app::DataUpdate::AgentVulnerabilities(vuln_res.data.affected_items)).await,
+                    Err(e) => send(error("Vulnerabilities Error", format!("Failed to load vulnerabilities: {}", e))).await,
+                }
+            },
+            async {
+                if let Ok(logs_res) = api.get_logs(Some(&agent_id), interval, 0, 100, None).await {
+                    if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
+                        send(crate::app::DataUpdate::AgentLogs(hits.clone())).await;
+                    }
+                }
+            },
+            async {
+                if let Ok(hours) = api.get_agent_alert_hours(&agent_id).await {
+                    send(crate::app::DataUpdate::AgentAlertHours(hours)).await;
+                }
+            },
+            async {
+                if let Ok(connections) = api.get_agent_connections(&agent_id, interval).await {
+                    send(crate::app::DataUpdate::AgentConnections(connections)).await;
+                }
+            },
+            async {
+                if let Ok(res) = api.get_rootcheck(&agent_id).await {
+                    let last_scan = api.get_rootcheck_last_scan(&agent_id).await.ok()
+                        .and_then(|r| r.data.affected_items.into_iter().next());
+                    send(crate::app::DataUpdate::AgentRootcheck { findings: res.data.affected_items, last_scan }).await;
+                }
+            },
+            async {
+                if agent_id == crate::models::MANAGER_AGENT_ID {
+                    let (daemons, logs) = tokio::join!(api.get_manager_status(), api.get_manager_logs(20));
+                    match daemons {
+                        Ok(daemons) => send(crate::app::DataUpdate::ManagerStatus(crate::models::ManagerStatus {
+                            daemons,
+                            logs: logs.unwrap_or_default(),
+                        })).await,
+                        Err(e) => send(error("Manager Status Error", format!("Failed to load manager status: {}", e))).await,
+                    }
+                }
+            },
+            async {
+                match api.get_agent_config(&agent_id, &config_component).await {
+                    Ok(config_res) => send(crate::app::DataUpdate::AgentConfig(config_res)).await,
+                    Err(e) => send(error("Config Error", format!("Failed to load config: {}", e))).await,
+                }
+            },
+        );
+    }));
+}
+
+/// Reports the outcome of a mutating action as a notification and records it in the audit log.
+async fn send_audited(tx: &mpsc::Sender<crate::app::DataUpdate>, action: &str, targets: &[String], result: Result<String, String>) {
+    let (success, message) = match result {
+        Ok(m) => (true, m),
+        Err(m) => (false, m),
+    };
+    let level = if success { crate::app::NotificationLevel::Success } else { crate::app::NotificationLevel::Error };
+    let _ = tx.send(crate::app::DataUpdate::Audit(crate::app::audit::AuditEntry::new(action, targets, success, &message))).await;
+    // Per-item failures and remediation of an API error (`api::error::describe`) need a popup
+    match message.split_once('\n') {
+        Some((title, details)) if !success => {
+            let _ = tx.send(crate::app::DataUpdate::ErrorPopup { title: title.to_string(), message: details.to_string() }).await;
+        }
+        _ => {
+            let _ = tx.send(crate::app::DataUpdate::Notification(message, level)).await;
+        }
+    }
+}
+
+/// Reloads the current Security Events page with the active filter and offset.
+fn spawn_log_page(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let Some(api) = app.api.clone() else { return };
+    let tx = tx.clone();
+    let interval = app.log_interval_mins;
+    let offset = app.log_offset;
+    let limit = app.log_limit;
+    let filter = Some(app.log_filter.clone());
+    tokio::spawn(async move {
+        if let Ok(res) = api.get_logs(None, interval, offset, limit, filter.as_ref()).await {
+            if let Some(update) = security_events_update(&res) {
+                let _ = tx.send(update).await;
+            }
+        }
+    });
+}
+
+/// The Security Events update for an indexer search response: its hits and `hits.total`.
+fn security_events_update(res: &serde_json::Value) -> Option<crate::app::DataUpdate> {
+    let hits = res.get("hits")?;
+    let total = hits.get("total");
+    let count = total.and_then(|t| t.get("value").or(Some(t))).and_then(|v| v.as_u64());
+    let hits = hits.get("hits")?.as_array()?.clone();
+    Some(crate::app::DataUpdate::SecurityEvents {
+        total: count.unwrap_or(hits.len() as u64),
+        exact: total.and_then(|t| t.get("relation")).and_then(|r| r.as_str()) != Some("gte"),
+        hits,
+    })
+}
+
+/// Pages through every alert matching the Security Events filter (or the inspected agent's
+/// alerts) with a scroll, up to `export_max_alerts`, and writes them in the export format.
+/// Started while a job runs, it cancels that job instead.
+fn spawn_full_export(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    if let Some(job) = app.export_job.take() {
+        job.abort();
+        app.export_progress = None;
+        app.notify("Export cancelled", crate::app::NotificationLevel::Warning);
+        return;
+    }
+    let Some(api) = app.api.clone() else { return };
+    let (agent_id, filter) = if app.active_view == ActiveView::AgentInspector {
+        (app.inspector_agent_id.clone(), None)
+    } else {
+        (None, Some(app.log_filter.clone()))
+    };
+    let query = crate::api::export_query(agent_id.as_deref(), app.log_interval_mins, filter.as_ref(), crate::app::EXPORT_PAGE_SIZE, &app.severity);
+    let path = match app.alert_export_path() {
+        Ok(path) => path,
+        Err(e) => {
+            app.notify(&e, crate::app::NotificationLevel::Error);
+            return;
+        }
+    };
+    let (format, compression, fields, max) = (app.export_format, app.export_compression, app.export_fields.clone(), app.export_max_alerts as usize);
+    app.export_progress = Some((0, 0));
+    app.notify(&format!("Exporting up to {} alerts...", max), crate::app::NotificationLevel::Info);
+
+    let tx = tx.clone();
+    app.export_job = Some(tokio::spawn(async move {
+        // Each page goes straight to the file; dropping the writer (cancel, error) removes it
+        let mut writer = match crate::app::export::AlertWriter::create(&path, format, compression, &fields) {
+            Ok(writer) => writer,
+            Err(e) => {
+                let _ = tx.send(crate::app::DataUpdate::ExportFinished(Err(e))).await;
+                return;
+            }
+        };
+        let mut scroll_id: Option<String> = None;
+        let mut page = api.start_alert_scroll(&query).await;
+        let result = loop {
+            let body = match page {
+                Ok(body) => body,
+                Err(e) => break Err(format!("Export failed after {} alerts: {}", writer.written(), e)),
+            };
+            let mut hits = body.pointer("/hits/hits").and_then(|h| h.as_array()).cloned().unwrap_or_default();
+            let total = body.pointer("/hits/total/value").or_else(|| body.pointer("/hits/total")).and_then(|t| t.as_u64()).unwrap_or(0);
+            scroll_id = body.get("_scroll_id").and_then(|s| s.as_str()).map(String::from).or(scroll_id);
+            let last_page = hits.is_empty();
+            hits.truncate(max - writer.written());
+            if let Err(e) = writer.write(&hits) {
+                break Err(e);
+            }
+            let _ = tx.send(crate::app::DataUpdate::ExportProgress { fetched: writer.written(), total }).await;
+            if last_page || writer.written() >= max {
+                break Ok(());
+            }
+            let Some(id) = scroll_id.as_deref() else { break Ok(()) };
+            page = api.scroll_alerts(id).await;
+        };
+        if let Some(id) = scroll_id {
+            let _ = api.clear_alert_scroll(&id).await;
+        }
+        let result = result.and_then(|_| {
+            if writer.written() == 0 {
+                return Err("No alerts match the filter".to_string());
+            }
+            writer.finish().map(|count| (count, path.display().to_string()))
+        });
+        let _ = tx.send(crate::app::DataUpdate::ExportFinished(result)).await;
+    }));
+}
+
+/// Opens `url` with the desktop's default browser.
+fn open_in_browser(app: &mut App, url: &str) {
+    let (program, args) = crate::app::launch::browser_command(crate::app::launch::Platform::current(), url);
+    if let Err(e) = std::process::Command::new(&program)
+        .args(&args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    {
+        app.notify(&format!("Failed to open browser with {}: {}", program, e), crate::app::NotificationLevel::Error);
+    }
+}
+
+/// Requeries Security Events from the first page as the description filter is typed; a
+/// query still running for an older input is cancelled.
+fn spawn_live_search(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let Some(api) = app.api.clone() else { return };
+    if let Some(task) = app.live_search_task.take() {
+        task.abort();
+    }
+    app.log_offset = 0;
+    let tx = tx.clone();
+    let (interval, limit, filter) = (app.log_interval_mins, app.log_limit, app.pending_log_filter());
+    tracing::debug!(text = %filter.description_filter, "live search");
+    app.live_search_task = Some(tokio::spawn(async move {
+        if let Ok(res) = api.get_logs(None, interval, 0, limit, Some(&filter)).await {
+            if let Some(update) = security_events_update(&res) {
+                let _ = tx.send(update).await;
+            }
+        }
+    }));
+}
+
+/// Walks back one view transition and reloads what the restored view shows.
+fn go_back(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    if !app.navigate_back() {
+        return;
+    }
+    match app.active_view {
+        ActiveView::SecurityEvents => spawn_log_page(app, tx),
+        ActiveView::AgentInspector => {
+            let agent_id = app.get_selected_agent().map(|a| a.id.clone());
+            if let Some(agent_id) = agent_id.filter(|id| app.inspector_agent_id.as_ref() != Some(id)) {
+                spawn_inspector_load(app, tx, agent_id, false);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Loads the agent list of every configured profile at once; each arrives on its own, so a
+/// slow or unreachable site does not hold up the others.
+fn spawn_profile_agents(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    for (profile, api) in &app.profile_apis {
+        let (profile, api, tx) = (profile.clone(), api.clone(), tx.clone());
+        tokio::spawn(async move {
+            let result = api.list_agents(None, 0, 500).await
+                .map(|res| res.data.affected_items)
+                .map_err(|e| e.to_string());
+            let _ = tx.send(crate::app::DataUpdate::ProfileAgents { profile, result }).await;
+        });
+    }
+}
+
+/// Runs `action` on the agents, after the confirmation popup unless it is not needed.
+fn request_agent_action(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, action: crate::app::actions::AgentAction, agent_ids: Vec<String>) {
+    if let Some(agent_ids) = app.request_agent_action(action, agent_ids) {
+        run_agent_action(app, tx, action, agent_ids);
+    }
+}
+
+/// Runs the queued actions whose agents are active again.
+fn run_pending_actions(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, agents: Option<&[crate::models::WazuhAgent]>) {
+    for (action, agent_ids) in app.take_ready_pending_actions(agents) {
+        run_agent_action(app, tx, action, agent_ids);
+    }
+}
+
+/// Checks whether the agents with queued actions have reconnected.
+fn spawn_pending_status(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let Some(api) = app.api.clone() else { return };
+    let agent_ids = app.pending_actions.agent_ids();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let ids: Vec<&str> = agent_ids.iter().map(|s| s.as_str()).collect();
+        if let Ok(res) = api.get_agents_status(&ids).await {
+            let _ = tx.send(crate::app::DataUpdate::PendingAgentStatus(res.data.affected_items)).await;
+        }
+    });
+}
+
+fn run_agent_action(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, action: crate::app::actions::AgentAction, agent_ids: Vec<String>) {
+    use crate::app::actions::AgentAction;
+    match action {
+        AgentAction::Upgrade | AgentAction::Restart => spawn_bulk(app, tx, action, agent_ids),
+        AgentAction::FimScan => spawn_fim_scan(app, tx, agent_ids),
+        AgentAction::DeleteStale => spawn_delete_agents(app, tx, agent_ids),
+    }
+}
+
+/// Sends a restart or upgrade in batches of `bulk::BATCH_SIZE` agents, one request after
+/// the other, reporting each batch to the progress popup and the audit log.
+fn spawn_bulk(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, action: crate::app::actions::AgentAction, agent_ids: Vec<String>) {
+    use crate::app::actions::AgentAction;
+    use crate::app::bulk::BatchResult;
+    let Some(api) = app.api.clone() else { return };
+    if !app.start_bulk_op(action, agent_ids.len()) {
+        return;
+    }
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        for batch in crate::app::bulk::batches(&agent_ids) {
+            let ids: Vec<&str> = batch.iter().map(|s| s.as_str()).collect();
+            let (audit_action, res) = match action {
+                AgentAction::Upgrade => ("upgrade", api.upgrade_agents(&ids).await),
+                _ => ("restart", api.restart_agents(&ids).await),
+            };
+            let result = match res {
+                Ok(response) => BatchResult::from_response(batch, &response),
+                Err(e) => match e.downcast_ref::<crate::api::ApiError>() {
+                    // Every agent of the batch refused, each with its own reason
+                    Some(error) if !error.failed_items.is_empty() => BatchResult {
+                        failed: error.failed_items.iter()
+                            .flat_map(|item| item.ids.iter().map(|id| (id.clone(), item.message.clone())))
+                            .collect(),
+                        agent_ids: batch,
+                        error: None,
+                    },
+                    _ => BatchResult::from_error(batch, e.to_string()),
+                },
+            };
+            let (success, detail) = match &result.error {
+                Some(e) => (false, format!("{} failed: {}", action.verb(), e)),
+                None if result.failed.is_empty() => (true, format!("{} sent to {} agents", action.verb(), result.agent_ids.len())),
+                None => (false, format!("{} refused for {} of {} agents", action.verb(), result.failed.len(), result.agent_ids.len())),
+            };
+            let entry = crate::app::audit::AuditEntry::new(audit_action, &result.agent_ids, success, &detail);
+            let _ = tx.send(crate::app::DataUpdate::Audit(entry)).await;
+            let _ = tx.send(crate::app::DataUpdate::BulkBatch(result)).await;
+        }
+    });
+}
+
+/// Loads a shared setup into the config file at `config_path` and the running session.
+fn import_setup(app: &mut App, path: &std::path::Path, config_path: &std::path::Path) {
+    let bundle = match crate::app::bundle::SetupBundle::load(path) {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            app.notify(&format!("Reading {} failed: {}", path.display(), e), crate::app::NotificationLevel::Error);
+            return;
+        }
+    };
+    if let Err(e) = ConfigManager::update(config_path, |config| { bundle.apply_to_config(config); }) {
+        app.notify(&format!("Saving the imported setup to {} failed: {}", config_path.display(), e), crate::app::NotificationLevel::Warning);
+    }
+    let missing = app.import_setup(&bundle);
+    if missing.is_empty() {
+        app.notify(&format!("Setup imported from {}", path.display()), crate::app::NotificationLevel::Success);
+    } else {
+        app.notify(&format!("Setup imported; add passwords for {} in {}", missing.join(", "), config_path.display()), crate::app::NotificationLevel::Warning);
+    }
+}
+
+/// Logs in with the Re-login popup's credentials; the session switches over only if the API accepts them.
+fn spawn_relogin(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let Some(api) = app.api.clone() else { return };
+    let (username, password) = app.relogin.take_credentials();
+    let save = app.relogin.save;
+    app.popup_mode = crate::app::PopupMode::None;
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let mut candidate = api.clone();
+        candidate.set_credentials(&username, &password);
+        let res = match candidate.authenticate().await {
+            Ok(_) => {
+                let _ = tx.send(crate::app::DataUpdate::Relogin { username: username.clone(), password, save }).await;
+                let stored = if save { ", saved to config.toml" } else { " for this session" };
+                Ok(format!("Logged in as {}{}", username, stored))
+            }
+            Err(e) => Err(format!("Login as {} failed: {}", username, crate::api::error::describe(&e))),
+        };
+        send_audited(&tx, "re-login", &[username], res).await;
+    });
+}
+
+/// Reverses a group change: removes the agents it assigned and assigns back those it removed.
+fn spawn_group_undo(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, undo: crate::app::undo::GroupUndo) {
+    let Some(api) = app.api.clone() else { return };
+    let tx = tx.clone();
+    app.notify(&format!("Undoing: {}...", undo.summary()), crate::app::NotificationLevel::Info);
+    tokio::spawn(async move {
+        for (group, agent_ids) in &undo.added {
+            let ids: Vec<&str> = agent_ids.iter().map(|s| s.as_str()).collect();
+            let res = api.remove_agents_from_group(group, &ids).await
+                .map(|_| format!("{} agents removed from {} again", ids.len(), group))
+                .map_err(|e| format!("Undo in {} failed: {}", group, crate::api::error::describe(&e)));
+            send_audited(&tx, &format!("undo group assign ({})", group), agent_ids, res).await;
+        }
+        for (group, agent_ids) in &undo.removed {
+            let ids: Vec<&str> = agent_ids.iter().map(|s| s.as_str()).collect();
+            let res = api.assign_agents_to_group(group, &ids).await
+                .map(|_| format!("{} agents added to {} again", ids.len(), group))
+                .map_err(|e| format!("Undo in {} failed: {}", group, crate::api::error::describe(&e)));
+            send_audited(&tx, &format!("undo group remove ({})", group), agent_ids, res).await;
+        }
+        if let Ok(res) = api.list_agents(None, 0, 500).await {
+            let _ = tx.send(crate::app::DataUpdate::Agents(res.data.affected_items)).await;
+        }
+    });
+}
+
+/// Deletes the agents (stale cleanup) and reloads the agent list.
+fn spawn_delete_agents(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, agent_ids: Vec<String>) {
+    let Some(api) = app.api.clone() else { return };
+    let tx = tx.clone();
+    let count = agent_ids.len();
+    app.notify(&format!("Deleting {} stale agents...", count), crate::app::NotificationLevel::Info);
+    tokio::spawn(async move {
+        let ids: Vec<&str> = agent_ids.iter().map(|s| s.as_str()).collect();
+        let res = api.delete_agents(&ids).await
+            .map(|_| format!("Deleted {} stale agents", count))
+            .map_err(|e| format!("Cleanup failed: {}", crate::api::error::describe(&e)));
+        send_audited(&tx, "delete agents", &agent_ids, res).await;
+        if let Ok(res) = api.list_agents(None, 0, 500).await {
+            let _ = tx.send(crate::app::DataUpdate::Agents(res.data.affected_items)).await;
+        }
+    });
+}
+
+/// Starts an immediate syscheck scan on the agents and tracks it until each agent
+/// reports the scan finished.
+fn spawn_fim_scan(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, agent_ids: Vec<String>) {
+    let Some(api) = app.api.clone() else { return };
+    let tx = tx.clone();
+    let count = agent_ids.len();
+    app.notify(&format!("Requesting FIM scan on {} agents...", count), crate::app::NotificationLevel::Info);
+    tokio::spawn(async move {
+        let ids: Vec<&str> = agent_ids.iter().map(|s| s.as_str()).collect();
+        let res = match api.run_syscheck(&ids).await {
+            Ok(_) => {
+                let _ = tx.send(crate::app::DataUpdate::FimScanRequested(agent_ids.clone())).await;
+                Ok(format!("FIM scan started on {} agents", count))
+            }
+            Err(e) => Err(format!("FIM scan failed: {}", crate::api::error::describe(&e))),
+        };
+        send_audited(&tx, "fim scan", &agent_ids, res).await;
+    });
+}
+
+/// Sends a whitelisted command to the agent through the active-response script; the
+/// output is picked up from the alerts by `spawn_remote_command_poll`.
+fn spawn_run_command(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, agent_id: String, command: String) {
+    if !app.require_permission(crate::app::ACTION_ACTIVE_RESPONSE, "Run command") {
+        return;
+    }
+    let Some(api) = app.api.clone() else { return };
+    let run = app.start_remote_command(&agent_id, &command);
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let arguments = vec![run.id.clone(), command.clone()];
+        let res = match api.run_active_response(&[agent_id.as_str()], crate::app::remote_cmd::AR_SCRIPT, &arguments).await {
+            Ok(_) => Ok(format!("Sent '{}' to agent {}", command, agent_id)),
+            Err(e) => {
+                let message = format!("Run command failed: {}", e);
+                let _ = tx.send(crate::app::DataUpdate::RemoteCommandResult { id: run.id, result: Err(message.clone()) }).await;
+                Err(message)
+            }
+        };
+        send_audited(&tx, "run command", &[agent_id], res).await;
+    });
+}
+
+fn spawn_remote_command_poll(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>, id: String) {
+    let Some(api) = app.api.clone() else { return };
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        // Nothing yet, or a failed search, is retried on the next poll
+        if let Ok(Some(source)) = api.get_remote_command_result(&id).await {
+            let result = crate::app::remote_cmd::parse_result(&source).ok_or_else(|| "The agent reported an unreadable result".to_string());
+            let _ = tx.send(crate::app::DataUpdate::RemoteCommandResult { id, result }).await;
+        }
+    });
+}
+
+/// Pings the API; after an outage the ping re-authenticates and a successful one reloads the dashboard data.
+fn spawn_heartbeat(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let Some(api) = app.api.clone() else { return };
+    let reconnect = app.heartbeat.needs_reconnect();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = api.ping(reconnect).await.map_err(|e| e.to_string());
+        let _ = tx.send(crate::app::DataUpdate::Heartbeat(result)).await;
+    });
+}
+
+fn spawn_fim_poll(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>, agent_id: String) {
+    let Some(api) = app.api.clone() else { return };
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        // A failed poll is retried on the next interval
+        if let Ok(res) = api.get_syscheck_last_scan(&agent_id).await {
+            let last_scan = res.data.affected_items.into_iter().next();
+            let _ = tx.send(crate::app::DataUpdate::FimScanProgress { agent_id, last_scan }).await;
+        }
+    });
+}
+
+/// Loads the shared `agent.conf` of each group of the inspected agent for the Config tab diff.
+fn spawn_config_baseline(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let Some(api) = app.api.clone() else { return };
+    let Some(agent) = app.inspector_agent_id.as_ref().and_then(|id| app.agents.iter().find(|a| &a.id == id)) else { return };
+    let groups = agent.group.clone().unwrap_or_default();
+    let generation = app.inspector_generation;
+    app.config_baseline = None;
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let mut files = Vec::new();
+        for group in groups {
+            match api.get_group_file(&group, "agent.conf").await {
+                Ok(content) => files.push((group, content)),
+                Err(e) => {
+                    let update = crate::app::DataUpdate::ConfigBaseline(Err(format!("Failed to load {}/agent.conf: {}", group, e)));
+                    let _ = tx.send(crate::app::DataUpdate::Inspector { generation, update: Box::new(update) }).await;
+                    return;
+                }
+            }
+        }
+        let update = crate::app::DataUpdate::ConfigBaseline(Ok(files));
+        let _ = tx.send(crate::app::DataUpdate::Inspector { generation, update: Box::new(update) }).await;
+    });
+}
+
+/// Opens the highlighted group file in a popup and loads its content.
+fn spawn_group_file(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let (Some(group), Some(file)) = (app.group_files_group.clone(), app.group_files.get(app.group_file_index)) else { return };
+    let Some(api) = app.api.clone() else { return };
+    let filename = file.filename.clone();
+    app.group_file_content = None;
+    app.group_file_scroll = 0;
+    app.popup_mode = crate::app::PopupMode::GroupFile { group: group.clone(), filename: filename.clone() };
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        match api.get_group_file(&group, &filename).await {
+            Ok(content) => { let _ = tx.send(crate::app::DataUpdate::GroupFileContent(content)).await; },
+            Err(e) => { let _ = tx.send(crate::app::DataUpdate::GroupFileContent(format!("Failed to load {}: {}", filename, e))).await; },
+        }
+    });
+}
+
+/// Refreshes the set of agents whose version lags the manager.
+fn spawn_outdated_fetch(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let Some(api) = app.api.clone() else { return };
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        match api.get_outdated_agents().await {
+            Ok(res) => { let _ = tx.send(crate::app::DataUpdate::OutdatedAgents(res.data.affected_items)).await; },
+            Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load outdated agents: {}", e))).await; },
+        }
+    });
+}
+
+/// Loads CVE counts per agent for the Agent List severity filter.
+fn spawn_agent_vuln_counts(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let Some(api) = app.api.clone() else { return };
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        match api.get_agent_vuln_counts(crate::app::AGENT_VULN_BUCKETS).await {
+            Ok(counts) => { let _ = tx.send(crate::app::DataUpdate::AgentVulnCounts(counts)).await; },
+            Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load vulnerability counts: {}", e))).await; },
+        }
+    });
+}
+
+fn spawn_alert_context(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let Some(log) = &app.selected_log else { return };
+    let source = log.get("_source").unwrap_or(log);
+    let agent_id = source.get("agent").and_then(|a| a.get("id")).and_then(|v| v.as_str()).map(|s| s.to_string());
+    let timestamp = source.get("@timestamp").or_else(|| source.get("timestamp")).and_then(|v| v.as_str()).map(|s| s.to_string());
+    let (Some(agent_id), Some(timestamp)) = (agent_id, timestamp) else {
+        app.notify("Alert has no agent or timestamp", crate::app::NotificationLevel::Warning);
+        return;
+    };
+    let Some(api) = app.api.clone() else { return };
+
+    app.alert_context_anchor = log.get("_id").and_then(|v| v.as_str()).map(|s| s.to_string());
+    app.alert_context.clear();
+    app.alert_context_loading = true;
+    app.popup_mode = crate::app::PopupMode::AlertContext;
+
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        match api.get_alert_context(&agent_id, &timestamp, crate::app::ALERT_CONTEXT_MINUTES, crate::app::ALERT_CONTEXT_LIMIT).await {
+            Ok(res) => {
+                let hits = res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()).cloned().unwrap_or_default();
+                let _ = tx.send(crate::app::DataUpdate::AlertContext(hits)).await;
+            }
+            Err(e) => {
+                let _ = tx.send(crate::app::DataUpdate::AlertContext(Vec::new())).await;
+                let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load alert context: {}", e))).await;
+            }
+        }
+    });
+}
+
+fn spawn_srcip_pivot(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>) {
+    let srcip = app.selected_log.as_ref()
+        .and_then(|l| l.get("_source").unwrap_or(l).get("data"))
+        .and_then(|d| d.get("srcip"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+    let Some(srcip) = srcip else {
+        app.notify("Alert has no data.srcip to pivot on", crate::app::NotificationLevel::Warning);
+        return;
+    };
+    spawn_srcip_pivot_for(app, tx, srcip);
+}
+
+/// Opens the source IP pivot popup and loads the alerts from `srcip` in the current interval.
+fn spawn_srcip_pivot_for(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, srcip: String) {
+    let Some(api) = app.api.clone() else { return };
+
+    app.pivot_events.clear();
+    app.pivot_index = 0;
+    app.pivot_loading = true;
+    app.popup_mode = crate::app::PopupMode::SrcIpPivot { srcip: srcip.clone() };
+
+    let minutes = app.log_interval_mins;
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        match api.get_alerts_by_srcip(&srcip, minutes, crate::app::PIVOT_LIMIT).await {
+            Ok(res) => {
+                let hits = res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()).cloned().unwrap_or_default();
+                let _ = tx.send(crate::app::DataUpdate::SrcIpPivot(hits)).await;
+            }
+            Err(e) => {
+                let _ = tx.send(crate::app::DataUpdate::SrcIpPivot(Vec::new())).await;
+                let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to pivot on source IP: {}", e))).await;
+            }
+        }
+    });
+}
+
+/// Runs `hunt` over the current interval and shows its results table.
+fn spawn_hunt(app: &mut App, tx: &mpsc::Sender<crate::app::DataUpdate>, hunt: crate::app::hunts::Hunt) {
+    let Some(api) = app.api.clone() else { return };
+    app.hunt_rows = None;
+    app.hunt_row_index = 0;
+    app.popup_mode = crate::app::PopupMode::HuntResults { hunt };
+
+    let minutes = app.log_interval_mins;
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = api.get_hunt(hunt, minutes).await.map_err(|e| e.to_string());
+        let _ = tx.send(crate::app::DataUpdate::HuntResults { hunt, result }).await;
+    });
+}
+
+/// Starts the Prometheus exporter when `[metrics] listen` is configured.
+#[cfg(feature = "metrics")]
+fn start_metrics(app: &App, tx: &mpsc::Sender<crate::app::DataUpdate>) -> Option<crate::metrics::SharedMetrics> {
+    let addr = app.api.as_ref()?.config.metrics.listen.clone()?;
+    let shared = crate::metrics::SharedMetrics::default();
+    let server_state = shared.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        if let Err(e) = crate::metrics::serve(&addr, server_state).await {
+            let _ = tx.send(crate::app::DataUpdate::Error(format!("Metrics exporter on {} stopped: {}", addr, e))).await;
+        }
+    });
+    Some(shared)
+}
+
+/// Headless `--snapshot` run: writes a Markdown report for the window and returns the
+/// process exit code (0 within thresholds, 2 when a `--max-*` threshold is exceeded).
+async fn run_snapshot(cli: &CliArgs, path: &std::path::Path) -> Result<i32> {
+    let config = ConfigManager::resolve(cli)?;
+    let api = WazuhApi::new(config);
+    let window = cli.window.as_deref().unwrap_or("24h");
+    let minutes = crate::app::parse_interval(window).map_err(|e| anyhow::anyhow!("--window {}: {}", window, e))?;
+
+    let agents = api.list_agents(None, 0, 500).await.map(|r| r.data.affected_items).unwrap_or_default();
+    let (logs, vulns) = api.get_dashboard_searches(minutes, 1000).await?;
+    let hits = logs?.pointer("/hits/hits").and_then(|h| h.as_array()).cloned().unwrap_or_default();
+    let tz = crate::app::time::DisplayTz::from_config(&api.config);
+    let mut digest = crate::app::report::AlertDigest::from_hits(&hits, tz, api.severity());
+    // The hits are capped at 1000; the severity totals and thresholds count every alert
+    digest.stats = api.get_alert_severity_counts(minutes).await?;
+    // The report still goes out when the vulnerability index is unreachable
+    let vulns = vulns.ok();
+
+    let range = crate::app::format_interval_mins(minutes);
+    let ctx = crate::app::report::ReportContext {
+        range: &range,
+        generated: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        agent_status: crate::app::report::status_counts(&agents),
+        vulns: vulns.as_ref(),
+    };
+    std::fs::write(path, crate::app::report::render_markdown(&ctx, &digest))?;
+    println!(
+        "Snapshot written to {} (last {}: {} critical, {} high)",
+        path.display(), range, digest.stats.critical, digest.stats.high
+    );
+
+    let breaches = crate::app::report::threshold_breaches(&digest.stats, cli.max_critical, cli.max_high);
+    for breach in &breaches {
+        eprintln!("Threshold exceeded: {}", breach);
+    }
+    Ok(if breaches.is_empty() { 0 } else { 2 })
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Terminal setup
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
+    // Parse CLI flags before touching the terminal so usage errors print normally
+    let cli = CliArgs::parse(std::env::args().skip(1))?;
+    let _log_guard = match crate::logging::init(cli.debug) {
+        Ok(guard) => guard,
+        Err(e) => {
+            eprintln!("Failed to start debug logging: {}", e);
+            None
+        }
+    };
+
+    if let Some(path) = cli.snapshot.clone() {
+        let code = run_snapshot(&cli, &path).await?;
+        std::process::exit(code);
+    }
+
+    // Terminal setup; the guard restores it on every exit path, the hook on panics
+    crate::terminal::install_panic_hook();
+    let _terminal_guard = crate::terminal::TerminalGuard::enter()?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
     // App state
     let mut app = App::new();
     let (tx, mut rx) = mpsc::channel(100);
     
-    // Try to load config and init API
-    match ConfigManager::load() {
+    // Try to load config (file, env and flags) and init API
+    let config_path = ConfigManager::config_path(&cli);
+    match ConfigManager::resolve(&cli) {
         Ok(config) => {
             let api = WazuhApi::new(config);
             app.set_api(api.clone());
             app.active_view = ActiveView::Dashboard;
+            crate::app::state::UiState::load().apply(&mut app);
+            if app.tour_pending() {
+                app.start_tour();
+            }
+            app.agent_labels = crate::app::labels::AgentLabels::load(&crate::app::labels::AgentLabels::path());
+            app.pending_actions = crate::app::pending::PendingQueue::load(&crate::app::pending::PendingQueue::path());
+            app.audit_log = crate::app::audit::load_recent(&crate::app::audit::audit_path(), crate::app::audit::AUDIT_HISTORY);
         }
         Err(_) => {
             app.is_config_wizard_active = true;
@@ -47,84 +1033,213 @@ async fn main() -> Result<()> {
     let tick_rate = Duration::from_millis(250);
     let mut last_tick = Instant::now();
 
+    #[cfg(feature = "metrics")]
+    let metrics_state = start_metrics(&app, &tx);
+
     // Initial data load
     if let Some(api) = app.api.clone() {
         app.set_loading("Fetching initial dashboard data...");
         let tx = tx.clone();
+        let tz = app.display_tz;
+        // A restored Security Events view needs its own page of alerts
+        let restored_events = (app.active_view == ActiveView::SecurityEvents).then(|| (app.log_interval_mins, app.log_filter.clone()));
+        let restored_lists = app.active_view == ActiveView::CdbLists;
+        let restored_rules = (app.active_view == ActiveView::Rules).then_some(app.ruleset_kind);
         tokio::spawn(async move {
-            // Initial agent load
-            if let Ok(agents_res) = api.list_agents(None, 0, 500).await {
-                let _ = tx.send(crate::app::DataUpdate::Agents(agents_res.data.affected_items)).await;
+            // Permissions first so restricted actions are gated before the user can trigger them
+            if let Ok(perms) = api.get_permissions().await {
+                let _ = tx.send(crate::app::DataUpdate::Permissions(perms)).await;
             }
 
-            // Initial logs load for stats (default 24h for dashboard)
-            if let Ok(logs_res) = api.get_logs(None, 1440, 0, 1000, None).await {
-                if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                    let mut stats = crate::app::ThreatStats::default();
-                    let mut buckets = std::collections::BTreeMap::new();
-                    let mut agent_counts = std::collections::HashMap::new();
-
-                    for hit in hits {
-                        if let Some(source) = hit.get("_source") {
-                            if let Some(level) = source.get("rule").and_then(|r| r.get("level")).and_then(|l| l.as_u64()) {
-                                match level {
-                                    15..=u64::MAX => stats.critical += 1,
-                                    12..=14 => stats.high += 1,
-                                    7..=11 => stats.medium += 1,
-                                    _ => stats.low += 1,
-                                }
-                            }
-                            if let Some(agent_name) = source.get("agent").and_then(|a| a.get("name")).and_then(|n| n.as_str()) {
-                                *agent_counts.entry(agent_name.to_string()).or_insert(0u64) += 1;
-                            }
-                            if let Some(ts) = source.get("@timestamp").and_then(|t| t.as_str()) {
-                                if ts.len() >= 16 {
-                                    let minute = &ts[11..16];
-                                    *buckets.entry(minute.to_string()).or_insert(0u64) += 1;
-                                }
-                            }
-                        }
+            // Initial stats cover the last 24h
+            load_dashboard(&api, &tx, 1440, 1000, tz).await;
+
+            if let Some((interval, filter)) = restored_events {
+                if let Ok(res) = api.get_logs(None, interval, 0, 50, Some(&filter)).await {
+                    if let Some(update) = security_events_update(&res) {
+                        let _ = tx.send(update).await;
                     }
-                    let _ = tx.send(crate::app::DataUpdate::ThreatStats(stats)).await;
-                    let hist: Vec<(String, u64)> = buckets.into_iter().collect();
-                    let _ = tx.send(crate::app::DataUpdate::AlertHistory(hist)).await;
-                    let mut top: Vec<(String, u64)> = agent_counts.into_iter().collect();
-                    top.sort_by(|a, b| b.1.cmp(&a.1));
-                    top.truncate(5);
-                    let _ = tx.send(crate::app::DataUpdate::TopAgents(top)).await;
                 }
             }
+            if restored_lists {
+                fetch_cdb_lists(&api, &tx).await;
+            }
+            if let Some(kind) = restored_rules {
+                fetch_rule_files(&api, &tx, kind).await;
+            }
         });
         app.stop_loading();
     }
+    // Read once at startup for the expiry warning in the status bar
+    spawn_certificates(&mut app, &tx, false);
 
     loop {
         // Handle async updates
         while let Ok(update) = rx.try_recv() {
+            // Drop inspector responses from a load that has since been superseded
+            let update = match update {
+                crate::app::DataUpdate::Inspector { generation, update } => {
+                    if generation != app.inspector_generation {
+                        tracing::debug!(generation, current = app.inspector_generation, "dropped stale inspector update");
+                        continue;
+                    }
+                    *update
+                }
+                other => other,
+            };
             match update {
                 crate::app::DataUpdate::Agents(agents) => {
-                    app.agents = agents;
-                    app.sort_agents();
+                    app.set_agents(agents);
+                    run_pending_actions(&mut app, &tx, None);
                 }
+                crate::app::DataUpdate::PendingAgentStatus(agents) => run_pending_actions(&mut app, &tx, Some(&agents)),
+                crate::app::DataUpdate::ProfileAgents { profile, result } => app.set_profile_agents(&profile, result),
                 crate::app::DataUpdate::Groups(groups) => app.groups = groups,
-                crate::app::DataUpdate::GroupAgents(agents) => {
-                     app.agents = agents;
-                     app.sort_agents();
+                crate::app::DataUpdate::GroupAgents(agents) => app.set_agents(agents),
+                crate::app::DataUpdate::SecurityEvents { hits, total, exact } => {
+                    app.log_total = total;
+                    app.log_total_exact = exact;
+                    app.set_logs(hits);
                 }
-                crate::app::DataUpdate::SecurityEvents(logs) => app.logs = logs,
                 crate::app::DataUpdate::VulnSummary(summary) => app.vuln_summary = summary,
                 crate::app::DataUpdate::ThreatStats(stats) => app.threat_stats = stats,
+                crate::app::DataUpdate::AlertTotals(stats) => app.alert_totals = Some(stats),
                 crate::app::DataUpdate::AgentHardware(hw) => app.hardware = Some(hw),
-                crate::app::DataUpdate::AgentProcesses(procs) => app.processes = procs,
-                crate::app::DataUpdate::AgentPrograms(progs) => app.programs = progs,
-                crate::app::DataUpdate::AgentVulnerabilities(vulns) => app.vulnerabilities = vulns,
+                crate::app::DataUpdate::AgentProcesses { items, total } => app.append_processes(items, total),
+                crate::app::DataUpdate::AgentPrograms { items, total } => app.append_programs(items, total),
+                crate::app::DataUpdate::AgentVulnerabilities(vulns) => {
+                    let critical = vulns.iter().filter(|v| v.severity.eq_ignore_ascii_case("critical")).count();
+                    if let Some(id) = app.inspector_agent_id.clone() {
+                        app.agent_critical_cves.insert(id, critical);
+                    }
+                    app.vulnerabilities = vulns;
+                    app.sort_vulnerabilities();
+                }
                 crate::app::DataUpdate::AgentLogs(logs) => app.agent_logs = logs,
+                crate::app::DataUpdate::AgentConnections(connections) => app.agent_connections = connections,
+                crate::app::DataUpdate::AgentAlertHours(hours) => app.agent_alert_hours = Some(hours),
                 crate::app::DataUpdate::AgentConfig(config) => app.agent_config = Some(config),
+                crate::app::DataUpdate::ManagerStatus(status) => app.manager_status = Some(status),
+                crate::app::DataUpdate::ConfigBaseline(baseline) => app.config_baseline = Some(baseline),
+                crate::app::DataUpdate::AgentRootcheck { findings, last_scan } => app.set_rootcheck(findings, last_scan),
+                crate::app::DataUpdate::FimScanRequested(agent_ids) => app.start_fim_scans(agent_ids),
+                crate::app::DataUpdate::FimScanProgress { agent_id, last_scan } => app.set_fim_progress(&agent_id, last_scan),
                 crate::app::DataUpdate::AlertHistory(hist) => app.alert_buckets = hist,
+                crate::app::DataUpdate::AlertTrend(trend) => app.alert_trend = trend,
+                crate::app::DataUpdate::IndexerHealth(health) => app.indexer_health = Some(health),
+                crate::app::DataUpdate::Certificates(chains) => app.certificates = Some(chains),
+                crate::app::DataUpdate::DashboardTiming(timing) => app.dashboard_timing = Some(timing),
                 crate::app::DataUpdate::TopAgents(top) => app.top_agents = top,
+                crate::app::DataUpdate::TopCountries(top) => app.top_countries = top,
+                crate::app::DataUpdate::TopRules(top) => app.top_rules = top,
+                crate::app::DataUpdate::CriticalAlerts(alerts) => app.critical_alerts = alerts,
+                crate::app::DataUpdate::Permissions(perms) => app.permissions = Some(perms),
+                crate::app::DataUpdate::SecurityUsers(users) => {
+                    app.security_users = users;
+                    if app.users_table_state.selected().is_none() && !app.security_users.is_empty() {
+                        app.users_table_state.select(Some(0));
+                    }
+                }
+                crate::app::DataUpdate::SecurityRoles(roles) => app.security_roles = roles,
+                crate::app::DataUpdate::SecurityPolicies(policies) => app.security_policies = policies,
+                crate::app::DataUpdate::OutdatedAgents(agents) => {
+                    app.outdated_agent_ids = agents.into_iter().map(|a| a.id).collect();
+                }
+                crate::app::DataUpdate::AgentVulnCounts(counts) => app.set_agent_vuln_counts(counts),
+                crate::app::DataUpdate::AgentHighAlerts(counts) => app.agent_high_alerts = Some(counts),
+                crate::app::DataUpdate::Audit(entry) => app.record_audit(entry),
+                crate::app::DataUpdate::BulkBatch(batch) => app.record_bulk_batch(batch),
+                crate::app::DataUpdate::GroupUndo { undo, message } => app.offer_group_undo(undo, &message),
+                crate::app::DataUpdate::GroupFiles { group, files } => app.set_group_files(group, files),
+                crate::app::DataUpdate::GroupPosture { group, posture } => { app.group_postures.insert(group, posture); }
+                crate::app::DataUpdate::GroupFileContent(content) => app.group_file_content = Some(content),
+                crate::app::DataUpdate::CdbLists(lists) => app.set_cdb_lists(lists),
+                crate::app::DataUpdate::Logtest(result) => app.logtest_result = Some(result),
+                crate::app::DataUpdate::RuleFiles { kind, files } => app.set_rule_files(kind, files),
+                crate::app::DataUpdate::RuleFileContent { kind, filename, content } => {
+                    match edit_externally(&mut terminal, &filename, &content) {
+                        Ok(edited) => app.set_rule_draft(kind, filename, &content, edited),
+                        Err(e) => app.notify(&format!("Editing {} failed: {}", filename, e), crate::app::NotificationLevel::Error),
+                    }
+                }
+                crate::app::DataUpdate::RuleValidation { filename, errors } => {
+                    if errors.is_empty() {
+                        app.notify(&format!("{} uploaded, configuration check passed", filename), crate::app::NotificationLevel::Success);
+                        app.popup_mode = crate::app::PopupMode::ConfirmManagerRestart;
+                    } else {
+                        app.show_error(&format!("{} uploaded but the manager rejects it", filename), &errors.join("\n"));
+                    }
+                }
+                crate::app::DataUpdate::AlertContext(events) => app.set_alert_context(events),
+                crate::app::DataUpdate::ExportProgress { fetched, total } => {
+                    if app.export_job.is_some() {
+                        app.export_progress = Some((fetched, total));
+                    }
+                }
+                crate::app::DataUpdate::ExportFinished(result) => {
+                    app.export_job = None;
+                    app.export_progress = None;
+                    match result {
+                        Ok((count, path)) => app.exported(&format!("Exported {} alerts", count), path),
+                        Err(e) => app.notify(&e, crate::app::NotificationLevel::Error),
+                    }
+                }
+                crate::app::DataUpdate::HuntResults { hunt, result } => {
+                    // Dropped if the user closed the table or started another hunt
+                    if app.popup_mode == (crate::app::PopupMode::HuntResults { hunt }) {
+                        app.hunt_rows = Some(result);
+                    }
+                }
+                crate::app::DataUpdate::SrcIpPivot(events) => {
+                    app.pivot_events = events;
+                    app.pivot_loading = false;
+                }
                 crate::app::DataUpdate::Notification(msg, level) => app.notify(&msg, level),
-                crate::app::DataUpdate::Error(msg) => app.error_message = Some(msg),
-                crate::app::DataUpdate::ErrorPopup { title, message } => app.show_error(&title, &message),
+                crate::app::DataUpdate::Error(msg) => {
+                    tracing::warn!(message = %msg, "error");
+                    app.error_message = Some(msg);
+                }
+                crate::app::DataUpdate::ErrorPopup { title, message } => {
+                    tracing::warn!(%title, %message, "error popup");
+                    app.show_error(&title, &message);
+                }
+                crate::app::DataUpdate::RemoteCommandResult { id, result } => app.set_remote_command_result(&id, result),
+                crate::app::DataUpdate::Heartbeat(result) => {
+                    if result.is_ok() && !app.pending_actions.is_empty() {
+                        spawn_pending_status(&app, &tx);
+                    }
+                    if app.set_heartbeat(result) {
+                        if let Some(api) = app.api.clone() {
+                            let (tx, interval, tz) = (tx.clone(), app.log_interval_mins, app.display_tz);
+                            tokio::spawn(async move {
+                                load_dashboard(&api, &tx, interval, 100, tz).await;
+                            });
+                        }
+                    }
+                }
+                crate::app::DataUpdate::Relogin { username, password, save } => {
+                    app.apply_relogin(&username, &password);
+                    if save {
+                        let saved = ConfigManager::update(&config_path, |config| {
+                            config.username = username.clone();
+                            config.password = password.clone();
+                        });
+                        if let Err(e) = saved {
+                            app.notify(&format!("Logged in as {}, but saving to {} failed: {}", username, config_path.display(), e), crate::app::NotificationLevel::Warning);
+                        }
+                    }
+                    if let Some(api) = app.api.clone() {
+                        let (tx, interval, tz) = (tx.clone(), app.log_interval_mins, app.display_tz);
+                        tokio::spawn(async move {
+                            if let Ok(perms) = api.get_permissions().await {
+                                let _ = tx.send(crate::app::DataUpdate::Permissions(perms)).await;
+                            }
+                            load_dashboard(&api, &tx, interval, 100, tz).await;
+                        });
+                    }
+                }
+                crate::app::DataUpdate::SshOutput(bytes) => app.ssh_output(&bytes),
+                crate::app::DataUpdate::Inspector { .. } => {}
             }
         }
 
@@ -135,6 +1250,38 @@ async fn main() -> Result<()> {
 
             if event::poll(Duration::from_millis(50))? {
                 if let Event::Key(key) = event::read()? {
+                    if app.popup_mode == crate::app::PopupMode::SshSession {
+                        handle_ssh_key(&mut app, &key);
+                        continue;
+                    }
+
+                    if app.tour_step.is_some() {
+                        match key.code {
+                            KeyCode::Enter | KeyCode::Right | KeyCode::Char(' ') | KeyCode::Char('n') => app.advance_tour(1),
+                            KeyCode::Left | KeyCode::Backspace | KeyCode::Char('p') => app.advance_tour(-1),
+                            KeyCode::Esc | KeyCode::Char('q') => app.end_tour(),
+                            _ => {}
+                        }
+                        if app.tour_step.is_none() && app.tour_pending() {
+                            match ConfigManager::update(&config_path, |config| config.display.tour_seen = Some(true)) {
+                                Ok(()) => {
+                                    if let Some(api) = app.api.as_mut() {
+                                        api.config.display.tour_seen = Some(true);
+                                    }
+                                }
+                                Err(e) => app.notify(&format!("Could not save the tour as seen: {}", e), crate::app::NotificationLevel::Warning),
+                            }
+                        }
+                        continue;
+                    }
+
+                    // `[keys]` aliases apply in the views, not while typing or in popups
+                    let key = if !app.is_config_wizard_active && !app.show_interval_popup && !app.is_searching
+                        && !app.detail_is_searching && app.popup_mode == crate::app::PopupMode::None {
+                        app.keymap.translate(key)
+                    } else {
+                        key
+                    };
                     
                     // Handle input for text fields
                     if app.is_config_wizard_active {
@@ -143,6 +1290,8 @@ async fn main() -> Result<()> {
                                 match app.config_step {
                                     crate::app::ConfigStep::Url => app.config_url.push(c),
                                     crate::app::ConfigStep::OsUrl => app.config_os_url.push(c),
+                                    crate::app::ConfigStep::AlertsIndex => app.config_alerts_index.push(c),
+                                    crate::app::ConfigStep::VulnIndex => app.config_vuln_index.push(c),
                                     crate::app::ConfigStep::Username => app.config_username.push(c),
                                     crate::app::ConfigStep::Password => app.config_password.push(c),
                                     _ => {}
@@ -156,38 +1305,26 @@ async fn main() -> Result<()> {
                         }
                     } else if matches!(app.popup_mode, crate::app::PopupMode::SeverityFilter) {
                         // Advanced filter popup input handling
-                        if let KeyCode::Char(c) = key.code {
+                        let ctrl = key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL);
+                        if ctrl && key.code == KeyCode::Char('q') {
+                            app.show_query_preview = !app.show_query_preview;
+                            app.query_preview_scroll = 0;
+                        } else if ctrl && key.code == KeyCode::Char('y') {
+                            use std::io::Write;
+                            let mut out = io::stdout();
+                            let _ = write!(out, "{}", crate::ui::common::osc52_sequence(&app.filter_query_preview())).and_then(|_| out.flush());
+                            app.notify("Query copied to clipboard", crate::app::NotificationLevel::Success);
+                        } else if let KeyCode::Char(c) = key.code {
                             // Handle special keys first
                             match c {
-                                '1' if app.filter_popup_tab == crate::app::FilterPopupTab::Severity => {
-                                    // Critical preset: level >= 15
-                                    app.log_filter.mode = crate::app::SeverityFilterMode::Min;
-                                    app.log_filter.val1 = 15;
-                                    app.filter_input_1 = "15".to_string();
-                                }
-                                '2' if app.filter_popup_tab == crate::app::FilterPopupTab::Severity => {
-                                    // High preset: 12-14
-                                    app.log_filter.mode = crate::app::SeverityFilterMode::Range;
-                                    app.log_filter.val1 = 12;
-                                    app.log_filter.val2 = 14;
-                                    app.filter_input_1 = "12".to_string();
-                                    app.filter_input_2 = "14".to_string();
-                                }
-                                '3' if app.filter_popup_tab == crate::app::FilterPopupTab::Severity => {
-                                    // Medium preset: 7-11
-                                    app.log_filter.mode = crate::app::SeverityFilterMode::Range;
-                                    app.log_filter.val1 = 7;
-                                    app.log_filter.val2 = 11;
-                                    app.filter_input_1 = "7".to_string();
-                                    app.filter_input_2 = "11".to_string();
-                                }
-                                '4' if app.filter_popup_tab == crate::app::FilterPopupTab::Severity => {
-                                    // Low preset: 0-6
-                                    app.log_filter.mode = crate::app::SeverityFilterMode::Range;
-                                    app.log_filter.val1 = 0;
-                                    app.log_filter.val2 = 6;
-                                    app.filter_input_1 = "0".to_string();
-                                    app.filter_input_2 = "6".to_string();
+                                '1'..='4' if app.filter_popup_tab == crate::app::FilterPopupTab::Severity => {
+                                    let severity = match c {
+                                        '1' => crate::app::severity::Severity::Critical,
+                                        '2' => crate::app::severity::Severity::High,
+                                        '3' => crate::app::severity::Severity::Medium,
+                                        _ => crate::app::severity::Severity::Low,
+                                    };
+                                    app.apply_severity_preset(severity);
                                 }
                                 'a' if app.filter_popup_tab == crate::app::FilterPopupTab::Severity => {
                                     // All levels: 0-15
@@ -248,6 +1385,7 @@ async fn main() -> Result<()> {
                                         }
                                         crate::app::FilterPopupTab::Text => {
                                             app.log_filter.description_filter.push(c);
+                                            app.schedule_live_search();
                                         }
                                         crate::app::FilterPopupTab::Columns => {
                                             // No text input in columns tab (handled by Space above)
@@ -256,11 +1394,65 @@ async fn main() -> Result<()> {
                                 }
                             }
                         }
-                    } else if matches!(app.popup_mode, crate::app::PopupMode::SshUsername { .. }) {
+                    } else if matches!(app.popup_mode, crate::app::PopupMode::SshUsername { .. }) && key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('e') {
+                            if let Some(target) = app.ssh_target() {
+                                app.popup_mode = crate::app::PopupMode::None;
+                                open_embedded_ssh(&mut app, &tx, &target);
+                            }
+                        }
+                    } else if matches!(app.popup_mode, crate::app::PopupMode::SshUsername { .. } | crate::app::PopupMode::CdbAddEntry { .. } | crate::app::PopupMode::NewRuleFile | crate::app::PopupMode::AgentLabels { .. } | crate::app::PopupMode::ExportFields | crate::app::PopupMode::SetupBundle { .. }) {
                         if let KeyCode::Char(c) = key.code {
                             app.input_buffer.push(c);
                         }
-                    } else if app.is_searching {
+                    } else if let crate::app::PopupMode::ProcessDetail { pid } = &app.popup_mode {
+                        if key.code == KeyCode::Char('y') {
+                            let platform = app.get_selected_agent().and_then(|a| a.os.as_ref()).and_then(|o| o.platform.clone());
+                            if let Some(cmd) = app.processes.iter().find(|p| &p.pid == pid).map(|p| p.kill_command(platform.as_deref())) {
+                                use std::io::Write;
+                                let mut out = io::stdout();
+                                let _ = write!(out, "{}", crate::ui::common::osc52_sequence(&cmd)).and_then(|_| out.flush());
+                                app.notify(&format!("Copied: {}", cmd), crate::app::NotificationLevel::Success);
+                            }
+                        }
+                    } else if let crate::app::PopupMode::ConfirmRuleUpload { kind, filename } = &app.popup_mode {
+                        if key.code == KeyCode::Char('e') {
+                            let (kind, filename) = (*kind, filename.clone());
+                            let draft = app.rule_draft.clone();
+                            match edit_externally(&mut terminal, &filename, &draft) {
+                                Ok(edited) => app.set_rule_draft(kind, filename, &draft, edited),
+                                Err(e) => app.notify(&format!("Editing {} failed: {}", filename, e), crate::app::NotificationLevel::Error),
+                            }
+                        }
+                    } else if matches!(app.popup_mode, crate::app::PopupMode::Logtest) {
+                        if let (KeyCode::Char(c), Some(input)) = (key.code, app.logtest_input()) {
+                            input.push(c);
+                        }
+                    } else if matches!(app.popup_mode, crate::app::PopupMode::GroupMembership { .. }) {
+                        if key.code == KeyCode::Char(' ') {
+                            app.toggle_membership_group();
+                        }
+                    } else if matches!(app.popup_mode, crate::app::PopupMode::CreateUser) {
+                        if let KeyCode::Char(c) = key.code {
+                            if app.new_user_field == 0 {
+                                app.new_user_name.push(c);
+                            } else {
+                                app.new_user_password.push(c);
+                            }
+                        }
+                    } else if matches!(app.popup_mode, crate::app::PopupMode::Relogin) {
+                        if let KeyCode::Char(c) = key.code {
+                            match app.relogin.input() {
+                                Some(input) => input.push(c),
+                                None if c == ' ' => app.relogin.save = !app.relogin.save,
+                                None => {}
+                            }
+                        }
+                    } else if app.detail_is_searching {
+                        if let KeyCode::Char(c) = key.code {
+                            app.detail_search_query.push(c);
+                        }
+                    } else if app.is_searching && !key.modifiers.contains(crossterm::event::KeyModifiers::ALT) {
                          if let KeyCode::Char(c) = key.code {
                             app.search_query.push(c);
                             app.agent_filter = crate::app::filter::AgentFilter::parse(&app.search_query);
@@ -275,18 +1467,72 @@ async fn main() -> Result<()> {
                             app.jump_input.push(c);
                             app.jump_index = 0; // Reset selection on input
                         }
+                    } else if let crate::app::PopupMode::ConfirmAgentAction { action, agent_ids } = &app.popup_mode {
+                        // `Q` runs it on the connected agents and queues it for the disconnected ones
+                        if key.code == KeyCode::Char('Q') && !app.disconnected(*action, agent_ids).is_empty() {
+                            let (action, agent_ids) = (*action, agent_ids.clone());
+                            app.popup_mode = crate::app::PopupMode::None;
+                            let agent_ids = app.queue_disconnected(action, agent_ids);
+                            if !agent_ids.is_empty() {
+                                run_agent_action(&mut app, &tx, action, agent_ids);
+                            }
+                        }
+                    } else if matches!(app.popup_mode, crate::app::PopupMode::PendingActions) {
+                        if key.code == KeyCode::Char('d') {
+                            app.remove_pending_action();
+                        }
+                    } else if matches!(app.popup_mode, crate::app::PopupMode::Help) {
+                        // Typing searches the help; `?` still closes it until something is typed
+                        if let KeyCode::Char(c) = key.code {
+                            if c == '?' && app.help_query.is_empty() {
+                                app.popup_mode = crate::app::PopupMode::None;
+                            } else {
+                                app.help_query.push(c);
+                                app.help_scroll = 0;
+                            }
+                        }
                     } 
                     
                     // Main key handling
                     match key.code {
+                        KeyCode::Char(c @ '1'..='9') if key.modifiers.contains(crossterm::event::KeyModifiers::ALT)
+                            && !app.is_config_wizard_active && !app.detail_is_searching && !app.show_interval_popup
+                            && app.popup_mode == crate::app::PopupMode::None => {
+                            // Alt+digit saves the search being typed, otherwise recalls the slot
+                            let slot = c as u8 - b'0';
+                            if app.is_searching && app.active_view == ActiveView::AgentList {
+                                app.save_agent_search(slot);
+                                if let Err(e) = crate::app::state::UiState::capture(&app).save() {
+                                    app.notify(&format!("Could not save search: {}", e), crate::app::NotificationLevel::Error);
+                                }
+                            } else if !app.is_searching {
+                                app.apply_agent_search(slot);
+                            }
+                        }
                          KeyCode::Char('p') if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) => {
                             // Command Palette Trigger
                             app.popup_mode = crate::app::PopupMode::CommandPalette;
                             app.command_palette_input.clear();
                             app.command_palette_index = 0;
                         }
-                        KeyCode::Char(c) if !app.is_config_wizard_active && !app.is_searching && !app.show_interval_popup && app.popup_mode == crate::app::PopupMode::None => {
-                            if c == 'k' {
+                        KeyCode::Char(c) if !app.is_config_wizard_active && !app.is_searching && !app.detail_is_searching && !app.show_interval_popup && app.popup_mode == crate::app::PopupMode::None => {
+                            if app.active_view == ActiveView::AgentList && " GURFXhot".contains(c) && app.refuse_profile_agent() {
+                                // Row from another profile, user already notified
+                            } else if c == 'u' && app.selected_log.is_none() && app.group_undo.is_some() {
+                                match app.take_group_undo() {
+                                    Some(undo) => spawn_group_undo(&mut app, &tx, undo),
+                                    None => app.notify("Nothing to undo", crate::app::NotificationLevel::Info),
+                                }
+                            } else if c == 'C' && app.active_view == ActiveView::AgentList {
+                                if app.profile_apis.is_empty() {
+                                    app.notify("No [[profiles]] in config.toml to list agents from", crate::app::NotificationLevel::Info);
+                                } else {
+                                    app.toggle_all_profiles();
+                                    if app.all_profiles {
+                                        spawn_profile_agents(&app, &tx);
+                                    }
+                                }
+                            } else if c == 'k' {
                                 if app.active_view == ActiveView::AgentInspector {
                                     app.scroll_up(1);
                                 } else if app.selected_log.is_some() {
@@ -302,8 +1548,35 @@ async fn main() -> Result<()> {
                                 } else if app.active_view != ActiveView::Dashboard {
                                     app.next_item();
                                 }
+                            } else if c == 'c' && app.selected_log.is_some() {
+                                spawn_alert_context(&mut app, &tx);
+                            } else if c == 'p' && app.selected_log.is_some() {
+                                spawn_srcip_pivot(&mut app, &tx);
+                            } else if c == 'o' && app.selected_log.is_some() {
+                                let url = app.api.as_ref().zip(app.selected_log.as_ref())
+                                    .and_then(|(api, log)| crate::app::launch::discover_url(&api.config.url, api.config.indices.alerts(), log));
+                                match url {
+                                    Some(url) => open_in_browser(&mut app, &url),
+                                    None => app.notify("This alert has no document ID or timestamp to link to", crate::app::NotificationLevel::Warning),
+                                }
+                            } else if (c == 'y' || c == 'u') && app.selected_log.is_some() {
+                                // `y` the document ID, `u` a Dev Tools request fetching it
+                                if let Some((index, id)) = app.detail_document() {
+                                    let text = if c == 'y' { id.to_string() } else { format!("GET {}/_doc/{}", index, id) };
+                                    use std::io::Write;
+                                    let mut out = io::stdout();
+                                    let _ = write!(out, "{}", crate::ui::common::osc52_sequence(&text)).and_then(|_| out.flush());
+                                    app.notify(&format!("Copied: {}", text), crate::app::NotificationLevel::Success);
+                                } else {
+                                    app.notify("This alert has no indexer document ID", crate::app::NotificationLevel::Warning);
+                                }
+                            } else if (c == 'n' || c == 'N') && app.selected_log.is_some() {
+                                app.jump_to_detail_match(c == 'n');
                             } else if c == ' ' {
                                 app.toggle_selection();
+                            } else if c == '/' && app.selected_log.is_some() {
+                                app.detail_is_searching = true;
+                                app.detail_search_query.clear();
                             } else if c == '/' {
                                 app.is_searching = true;
                                 app.search_query.clear();
@@ -311,7 +1584,13 @@ async fn main() -> Result<()> {
                                 app.show_interval_popup = true;
                                 app.interval_input = format!("{}m", app.log_interval_mins);
                             } else if c == 'G' {
-                                if let Some(agent) = app.get_selected_agent() {
+                                if !app.require_permission(crate::app::ACTION_GROUP_ASSIGN, "Group assignment") {
+                                    // Blocked, user already notified
+                                } else if app.selected_agents.len() <= 1 && app.get_selected_agent().map(|a| a.is_manager()).unwrap_or(false) {
+                                    app.notify("Groups do not apply to the manager (agent 000)", crate::app::NotificationLevel::Warning);
+                                } else if app.active_view == ActiveView::AgentInspector {
+                                    app.open_group_membership();
+                                } else if let Some(agent) = app.get_selected_agent() {
                                     let agent_id = agent.id.clone();
                                     // If multiple selected, pass special "MULTI" id or handle logic
                                     let target_id = if app.selected_agents.len() > 1 { "MULTI".to_string() } else { agent_id };
@@ -323,10 +1602,27 @@ async fn main() -> Result<()> {
                                         crate::app::LogViewMode::Table => crate::app::LogViewMode::Raw,
                                         crate::app::LogViewMode::Raw => crate::app::LogViewMode::Table,
                                     };
+                                    app.regroup_alerts();
+                                } else if app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Programs {
+                                    app.programs_vuln_only = !app.programs_vuln_only;
+                                    app.inspector_table_state.select(Some(0));
+                                } else if app.active_view == ActiveView::Rules {
+                                    app.toggle_ruleset_kind();
+                                    if let Some(api) = app.api.clone() {
+                                        let tx = tx.clone();
+                                        let kind = app.ruleset_kind;
+                                        tokio::spawn(async move {
+                                            fetch_rule_files(&api, &tx, kind).await;
+                                        });
+                                    }
                                 }
                             } else if c == 'e' {
                                 if app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Config {
-                                    if let (Some(api), Some(agent), Some(config)) = (&app.api, app.get_selected_agent(), &app.agent_config) {
+                                    if !app.require_permission(crate::app::ACTION_CONFIG_PUSH, "Config push") {
+                                        // Blocked, user already notified
+                                    } else if app.get_selected_agent().map(|a| a.is_manager()).unwrap_or(false) {
+                                        app.notify("The manager's configuration is ossec.conf on the manager; config push only applies to agents", crate::app::NotificationLevel::Warning);
+                                    } else if let (Some(api), Some(agent), Some(config)) = (&app.api, app.get_selected_agent(), &app.agent_config) {
                                         let api = api.clone();
                                         let agent_id = agent.id.clone();
                                         let component = app.agent_config_component.clone();
@@ -335,19 +1631,53 @@ async fn main() -> Result<()> {
                                         
                                         app.notify(&format!("Pushing config update to {}...", agent_id), crate::app::NotificationLevel::Info);
                                         tokio::spawn(async move {
-                                            match api.update_agent_config(&agent_id, &component, config).await {
-                                                Ok(_) => { let _ = tx.send(crate::app::DataUpdate::Notification("Configuration updated successfully".to_string(), crate::app::NotificationLevel::Success)).await; },
-                                                Err(e) => { let _ = tx.send(crate::app::DataUpdate::Notification(format!("Update failed: {}", e), crate::app::NotificationLevel::Error)).await; },
-                                            }
+                                            let res = api.update_agent_config(&agent_id, &component, config).await
+                                                .map(|_| format!("Configuration ({}) updated successfully", component))
+                                                .map_err(|e| format!("Update failed: {}", crate::api::error::describe(&e)));
+                                            send_audited(&tx, "config push", &[agent_id], res).await;
                                         });
                                     }
+                                } else if app.active_view == ActiveView::Dashboard {
+                                    match app.export_report() {
+                                        Ok(path) => app.exported("Report written", path),
+                                        Err(e) => app.notify(&e, crate::app::NotificationLevel::Error),
+                                    }
+                                } else if app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Vulnerabilities {
+                                    match app.export_vulnerabilities() {
+                                        Ok(path) => app.exported("Vulnerabilities exported", path),
+                                        Err(e) => app.notify(&e, crate::app::NotificationLevel::Error),
+                                    }
                                 } else if app.active_view == ActiveView::SecurityEvents || (app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Logs) {
                                     // Handle log export here
                                     match app.export_logs() {
-                                        Ok(filename) => app.notify(&format!("Logs exported to {}", filename), crate::app::NotificationLevel::Success),
+                                        Ok(path) => app.exported("Logs exported", path),
                                         Err(e) => app.notify(&e, crate::app::NotificationLevel::Error),
                                     }
                                 }
+                            } else if c == 'W' && (app.active_view == ActiveView::SecurityEvents || (app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Logs)) {
+                                spawn_full_export(&mut app, &tx);
+                            } else if c == 'x' && (app.active_view == ActiveView::SecurityEvents || (app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Logs)) {
+                                app.open_export_fields();
+                            } else if c == 'E' && (app.active_view == ActiveView::SecurityEvents || (app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Logs)) {
+                                app.export_format = app.export_format.next();
+                                app.notify(&format!("Export format: {}", app.export_format.label()), crate::app::NotificationLevel::Info);
+                            } else if c == 'Z' && (app.active_view == ActiveView::SecurityEvents || (app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Logs)) {
+                                app.export_compression = app.export_compression.next();
+                                app.notify(&format!("Export compression: {}", app.export_compression.label()), crate::app::NotificationLevel::Info);
+                            } else if c == 'E' && app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Vulnerabilities {
+                                app.vuln_export_format = app.vuln_export_format.next();
+                                app.notify(&format!("Export format: {}", app.vuln_export_format.label()), crate::app::NotificationLevel::Info);
+                            } else if (c == 'h' || c == 'l') && app.active_view == ActiveView::SecurityEvents && app.log_view_mode == crate::app::LogViewMode::Raw {
+                                if !app.raw_wrap {
+                                    app.scroll_raw(if c == 'h' { -1 } else { 1 });
+                                }
+                            } else if c == 'g' && app.active_view == ActiveView::SecurityEvents && app.log_view_mode == crate::app::LogViewMode::Table && app.selected_log.is_none() {
+                                app.toggle_flood_grouping();
+                                let state = if app.group_floods { "grouped" } else { "listed one by one" };
+                                app.notify(&format!("Repeated alerts {}", state), crate::app::NotificationLevel::Info);
+                            } else if c == 'w' && app.active_view == ActiveView::SecurityEvents && app.log_view_mode == crate::app::LogViewMode::Raw {
+                                app.raw_wrap = !app.raw_wrap;
+                                app.raw_scroll_x = 0;
                             } else if c == 'h' {
                                 if let Some(agent) = app.get_selected_agent() {
                                     let agent_id = agent.id.clone();
@@ -366,13 +1696,7 @@ async fn main() -> Result<()> {
                                     if let Ok(u) = reqwest::Url::parse(&api.config.url) {
                                         let browser_url = format!("{}://{}/app/endpoints-summary#/agents?tab=welcome&agent={}&tabView=panels", 
                                             u.scheme(), u.host_str().unwrap_or(""), agent.id);
-                                        
-                                        let _ = std::process::Command::new("xdg-open")
-                                            .arg(browser_url)
-                                            .stdin(std::process::Stdio::null())
-                                            .stdout(std::process::Stdio::null())
-                                            .stderr(std::process::Stdio::null())
-                                            .spawn();
+                                        open_in_browser(&mut app, &browser_url);
                                     }
                                 }
                             } else if c == '+' {
@@ -380,65 +1704,131 @@ async fn main() -> Result<()> {
                             } else if c == '-' {
                                 app.log_interval_mins = app.log_interval_mins.saturating_sub(15).max(5);
                             } else if c == 'U' {
-                                if let Some(api) = app.api.as_ref() {
-                                    let api = api.clone();
-                                    let tx = tx.clone();
-                                    
-                                    let agent_ids: Vec<String> = if !app.selected_agents.is_empty() {
-                                        app.selected_agents.iter().cloned().collect()
-                                    } else if let Some(agent) = app.get_selected_agent() {
-                                        vec![agent.id.clone()]
-                                    } else {
-                                        Vec::new()
-                                    };
-
-                                    if !agent_ids.is_empty() {
-                                        let count = agent_ids.len();
-                                        app.notify(&format!("Starting upgrade for {} agents...", count), crate::app::NotificationLevel::Info);
+                                if !app.require_permission(crate::app::ACTION_AGENT_UPGRADE, "Upgrade") {
+                                    // Blocked, user already notified
+                                } else {
+                                    let agent_ids = app.action_targets();
+                                    request_agent_action(&mut app, &tx, crate::app::actions::AgentAction::Upgrade, agent_ids);
+                                }
+                            } else if c == 'H' && app.active_view == ActiveView::Dashboard {
+                                app.trend_range = app.trend_range.next();
+                                app.alert_trend.clear();
+                                spawn_alert_trend(&app, &tx);
+                            } else if c == 'P' && app.active_view == ActiveView::AgentList {
+                                app.agent_preview = !app.agent_preview;
+                            } else if c == 'P' && app.active_view == ActiveView::GroupManagement {
+                                app.group_posture = !app.group_posture;
+                            } else if c == 'O' && app.active_view == ActiveView::AgentList {
+                                app.show_outdated_only = !app.show_outdated_only;
+                                app.table_state.select(Some(0));
+                                if app.show_outdated_only {
+                                    spawn_outdated_fetch(&app, &tx);
+                                }
+                            } else if c == 'A' && app.active_view == ActiveView::AgentList && app.show_outdated_only {
+                                // Select every outdated agent and upgrade them in one go
+                                if !app.require_permission(crate::app::ACTION_AGENT_UPGRADE, "Upgrade") {
+                                    // Blocked, user already notified
+                                } else if app.outdated_agent_ids.is_empty() {
+                                    app.notify("No outdated agents", crate::app::NotificationLevel::Info);
+                                } else {
+                                    app.selected_agents = app.outdated_agent_ids.clone();
+                                    let agent_ids = app.action_targets();
+                                    request_agent_action(&mut app, &tx, crate::app::actions::AgentAction::Upgrade, agent_ids);
+                                }
+                            } else if c == 'R' && app.active_view == ActiveView::Rules {
+                                if app.require_permission(crate::app::ACTION_MANAGER_RESTART, "Manager restart") {
+                                    app.popup_mode = crate::app::PopupMode::ConfirmManagerRestart;
+                                }
+                            } else if c == 'S' && app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Rootcheck {
+                                if app.require_permission(crate::app::ACTION_ROOTCHECK_RUN, "Rootcheck scan") {
+                                    if let (Some(api), Some(agent)) = (app.api.clone(), app.get_selected_agent()) {
+                                        let agent_id = agent.id.clone();
+                                        let tx = tx.clone();
+                                        app.notify(&format!("Requesting rootcheck scan on agent {}...", agent_id), crate::app::NotificationLevel::Info);
                                         tokio::spawn(async move {
-                                            let ids: Vec<&str> = agent_ids.iter().map(|s| s.as_str()).collect();
-                                            match api.upgrade_agents(&ids).await {
-                                                Ok(_) => { let _ = tx.send(crate::app::DataUpdate::Notification(format!("Upgrade started for {} agents", count), crate::app::NotificationLevel::Success)).await; },
-                                                Err(e) => { let _ = tx.send(crate::app::DataUpdate::Notification(format!("Upgrade failed: {}", e), crate::app::NotificationLevel::Error)).await; },
-                                            }
+                                            let res = api.run_rootcheck(&[agent_id.as_str()]).await
+                                                .map(|_| format!("Rootcheck scan started on agent {}; press [r] later to reload", agent_id))
+                                                .map_err(|e| format!("Rootcheck scan failed: {}", crate::api::error::describe(&e)));
+                                            send_audited(&tx, "rootcheck scan", &[agent_id], res).await;
                                         });
                                     }
                                 }
+                            } else if c == 'B' {
+                                app.hunt_index = 0;
+                                app.popup_mode = crate::app::PopupMode::Hunts;
+                            } else if c == 'Y' {
+                                if let Some(path) = app.last_export_path.clone() {
+                                    use std::io::Write;
+                                    let mut out = io::stdout();
+                                    let _ = write!(out, "{}", crate::ui::common::osc52_sequence(&path)).and_then(|_| out.flush());
+                                    app.notify(&format!("Copied: {}", path), crate::app::NotificationLevel::Success);
+                                } else {
+                                    app.notify("Nothing exported yet", crate::app::NotificationLevel::Info);
+                                }
+                            } else if c == 'd' && app.active_view == ActiveView::Dashboard {
+                                app.duplicate_index = 0;
+                                app.popup_mode = crate::app::PopupMode::DuplicateAgents;
+                            } else if c == 'd' && app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Config {
+                                app.config_diff_mode = !app.config_diff_mode;
+                                if app.config_diff_mode && app.config_baseline.is_none() {
+                                    spawn_config_baseline(&mut app, &tx);
+                                }
+                            } else if c == 'F' && matches!(app.active_view, ActiveView::AgentList | ActiveView::AgentInspector) {
+                                if app.require_permission(crate::app::ACTION_SYSCHECK_RUN, "FIM scan") {
+                                    let agent_ids = app.action_targets();
+                                    request_agent_action(&mut app, &tx, crate::app::actions::AgentAction::FimScan, agent_ids);
+                                }
                             } else if c == 'R' {
-                                if let Some(api) = app.api.as_ref() {
-                                    let api = api.clone();
-                                    let tx = tx.clone();
-                                    
-                                    let agent_ids: Vec<String> = if !app.selected_agents.is_empty() {
-                                        app.selected_agents.iter().cloned().collect()
-                                    } else if let Some(agent) = app.get_selected_agent() {
-                                        vec![agent.id.clone()]
-                                    } else {
-                                        Vec::new()
-                                    };
-
-                                    if !agent_ids.is_empty() {
-                                        let count = agent_ids.len();
-                                        app.notify(&format!("Restarting {} agents...", count), crate::app::NotificationLevel::Info);
-                                        tokio::spawn(async move {
-                                            let ids: Vec<&str> = agent_ids.iter().map(|s| s.as_str()).collect();
-                                            match api.restart_agents(&ids).await {
-                                                Ok(_) => { let _ = tx.send(crate::app::DataUpdate::Notification(format!("Restart signal sent to {} agents", count), crate::app::NotificationLevel::Success)).await; },
-                                                Err(e) => { let _ = tx.send(crate::app::DataUpdate::Notification(format!("Restart failed: {}", e), crate::app::NotificationLevel::Error)).await; },
-                                            }
-                                        });
+                                let only_manager = app.selected_agents.is_empty() && app.get_selected_agent().map(|a| a.is_manager()).unwrap_or(false);
+                                if only_manager {
+                                    // Agent 000 restarts through the manager endpoint, behind its own confirmation
+                                    if app.require_permission(crate::app::ACTION_MANAGER_RESTART, "Manager restart") {
+                                        app.popup_mode = crate::app::PopupMode::ConfirmManagerRestart;
                                     }
+                                } else if !app.require_permission(crate::app::ACTION_AGENT_RESTART, "Restart") {
+                                    // Blocked, user already notified
+                                } else {
+                                    let agent_ids = app.action_targets();
+                                    request_agent_action(&mut app, &tx, crate::app::actions::AgentAction::Restart, agent_ids);
                                 }
                             } else if c == 's' {
                                 if app.active_view == ActiveView::AgentList {
                                     app.cycle_sort();
+                                } else if app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Processes {
+                                    app.cycle_process_sort();
+                                } else if app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Vulnerabilities {
+                                    app.cycle_vuln_sort();
+                                } else if app.active_view == ActiveView::SecurityEvents && app.selected_log.is_none() {
+                                    app.cycle_log_sort();
+                                    spawn_log_page(&app, &tx);
+                                }
+                            } else if ('1'..='4').contains(&c) && app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Vulnerabilities {
+                                let severity = match c {
+                                    '1' => "critical",
+                                    '2' => "high",
+                                    '3' => "medium",
+                                    _ => "low",
+                                };
+                                app.toggle_vuln_severity_filter(severity);
+                            } else if ('1'..='4').contains(&c) && app.active_view == ActiveView::SecurityEvents && app.selected_log.is_none() {
+                                let index = c as usize - '1' as usize;
+                                app.toggle_log_severity(crate::app::ALERT_SEVERITIES[index]);
+                                spawn_log_page(&app, &tx);
+                            } else if ('1'..='4').contains(&c) && app.active_view == ActiveView::AgentList {
+                                let severity = match c {
+                                    '1' => "critical",
+                                    '2' => "high",
+                                    '3' => "medium",
+                                    _ => "low",
+                                };
+                                app.toggle_agent_severity_filter(severity);
+                                if app.severity_filter.is_some() {
+                                    spawn_agent_vuln_counts(&app, &tx);
                                 }
-                        } else if c == '1' {
+                            } else if c == '1' {
                                 if app.active_view == ActiveView::Dashboard {
-                                    app.log_filter.mode = crate::app::SeverityFilterMode::Min;
-                                    app.log_filter.val1 = 15;
-                                    app.log_filter.val2 = 15;
-                                    app.active_view = ActiveView::SecurityEvents;
+                                    app.navigate(ActiveView::SecurityEvents);
+                                    app.apply_severity_preset(crate::app::severity::Severity::Critical);
                                     
                                     // Trigger data load with new filter
                                     if let Some(api) = app.api.clone() {
@@ -448,8 +1838,8 @@ async fn main() -> Result<()> {
                                         let filter = Some(app.log_filter.clone());
                                         tokio::spawn(async move {
                                             if let Ok(res) = api.get_logs(None, interval, 0, 50, filter.as_ref()).await {
-                                                if let Some(hits) = res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                    let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
+                                                if let Some(update) = security_events_update(&res) {
+                                                    let _ = tx.send(update).await;
                                                 }
                                             }
                                         });
@@ -458,10 +1848,8 @@ async fn main() -> Result<()> {
                                 }
                             } else if c == '2' {
                                 if app.active_view == ActiveView::Dashboard {
-                                    app.log_filter.mode = crate::app::SeverityFilterMode::Range;
-                                    app.log_filter.val1 = 12;
-                                    app.log_filter.val2 = 14;
-                                    app.active_view = ActiveView::SecurityEvents;
+                                    app.navigate(ActiveView::SecurityEvents);
+                                    app.apply_severity_preset(crate::app::severity::Severity::High);
                                     
                                     if let Some(api) = app.api.clone() {
                                         app.set_loading("Fetching high severity alerts...");
@@ -470,8 +1858,8 @@ async fn main() -> Result<()> {
                                         let filter = Some(app.log_filter.clone());
                                         tokio::spawn(async move {
                                             if let Ok(res) = api.get_logs(None, interval, 0, 50, filter.as_ref()).await {
-                                                if let Some(hits) = res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                    let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
+                                                if let Some(update) = security_events_update(&res) {
+                                                    let _ = tx.send(update).await;
                                                 }
                                             }
                                         });
@@ -480,10 +1868,8 @@ async fn main() -> Result<()> {
                                 }
                             } else if c == '3' {
                                 if app.active_view == ActiveView::Dashboard {
-                                    app.log_filter.mode = crate::app::SeverityFilterMode::Range;
-                                    app.log_filter.val1 = 7;
-                                    app.log_filter.val2 = 11;
-                                    app.active_view = ActiveView::SecurityEvents;
+                                    app.navigate(ActiveView::SecurityEvents);
+                                    app.apply_severity_preset(crate::app::severity::Severity::Medium);
                                     
                                     if let Some(api) = app.api.clone() {
                                         app.set_loading("Fetching medium severity alerts...");
@@ -492,8 +1878,8 @@ async fn main() -> Result<()> {
                                         let filter = Some(app.log_filter.clone());
                                         tokio::spawn(async move {
                                             if let Ok(res) = api.get_logs(None, interval, 0, 50, filter.as_ref()).await {
-                                                if let Some(hits) = res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                    let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
+                                                if let Some(update) = security_events_update(&res) {
+                                                    let _ = tx.send(update).await;
                                                 }
                                             }
                                         });
@@ -502,10 +1888,8 @@ async fn main() -> Result<()> {
                                 }
                             } else if c == '4' {
                                 if app.active_view == ActiveView::Dashboard {
-                                    app.log_filter.mode = crate::app::SeverityFilterMode::Range;
-                                    app.log_filter.val1 = 0;
-                                    app.log_filter.val2 = 6;
-                                    app.active_view = ActiveView::SecurityEvents;
+                                    app.navigate(ActiveView::SecurityEvents);
+                                    app.apply_severity_preset(crate::app::severity::Severity::Low);
                                     
                                     if let Some(api) = app.api.clone() {
                                         app.set_loading("Fetching low severity alerts...");
@@ -514,8 +1898,8 @@ async fn main() -> Result<()> {
                                         let filter = Some(app.log_filter.clone());
                                         tokio::spawn(async move {
                                             if let Ok(res) = api.get_logs(None, interval, 0, 50, filter.as_ref()).await {
-                                                if let Some(hits) = res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                    let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
+                                                if let Some(update) = security_events_update(&res) {
+                                                    let _ = tx.send(update).await;
                                                 }
                                             }
                                         });
@@ -523,127 +1907,121 @@ async fn main() -> Result<()> {
                                     }
                                 }
                             } else if c == 'r' {
+                                if app.active_view == ActiveView::GroupManagement {
+                                    // Posture is refetched for the selected group on the next ticks
+                                    app.group_postures.clear();
+                                }
                                 if let Some(api) = app.api.clone() {
                                     app.set_loading("Refreshing...");
                                     let tx = tx.clone();
                                     let active_view = app.active_view.clone();
+                                    let ruleset_kind = app.ruleset_kind;
                                     let agent_id = app.get_selected_agent().map(|a| a.id.clone());
                                     let interval = app.log_interval_mins;
-                                    let config_component = app.agent_config_component.clone();
-                                    
+                                    let filter = app.log_filter.clone();
+                                    let tz = app.display_tz;
+                                    if let (ActiveView::AgentInspector, Some(id)) = (&active_view, agent_id) {
+                                        spawn_inspector_load(&mut app, &tx, id, true);
+                                    }
+                                    if active_view == ActiveView::Dashboard {
+                                        spawn_alert_trend(&app, &tx);
+                                    }
+                                    if active_view == ActiveView::AgentList && app.all_profiles {
+                                        spawn_profile_agents(&app, &tx);
+                                    }
+
                                     tokio::spawn(async move {
                                         match active_view {
                                             ActiveView::Dashboard | ActiveView::AgentList | ActiveView::GroupManagement => {
-                                                if let Ok(agents_res) = api.list_agents(None, 0, 500).await {
-                                                    let _ = tx.send(crate::app::DataUpdate::Agents(agents_res.data.affected_items)).await;
-                                                }
-                                                if let Ok(groups_res) = api.get_groups().await {
-                                                    let _ = tx.send(crate::app::DataUpdate::Groups(groups_res.data.affected_items)).await;
-                                                }
-
-                                            // Fetch logs for dashboard threat summary
-                                            if let Ok(logs_res) = api.get_logs(None, interval, 0, 100, None).await {
-                                                if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                    let mut stats = crate::app::ThreatStats::default();
-                                                    let mut buckets = std::collections::BTreeMap::new();
-                                                    let mut agent_counts = std::collections::HashMap::new();
-
-                                                    for hit in hits {
-                                                        if let Some(source) = hit.get("_source") {
-                                                            if let Some(level) = source.get("rule").and_then(|r| r.get("level")).and_then(|l| l.as_u64()) {
-                                                                match level {
-                                                                    15..=u64::MAX => stats.critical += 1,
-                                                                    12..=14 => stats.high += 1,
-                                                                    7..=11 => stats.medium += 1,
-                                                                    _ => stats.low += 1,
-                                                                }
-                                                            }
-                                                            
-                                                            if let Some(agent_name) = source.get("agent").and_then(|a| a.get("name")).and_then(|n| n.as_str()) {
-                                                                *agent_counts.entry(agent_name.to_string()).or_insert(0u64) += 1;
-                                                            }
-
-                                                            if let Some(ts) = source.get("@timestamp").and_then(|t| t.as_str()) {
-                                                                // Group by minute: 2023-10-27T10:15:30.000Z -> 10:15
-                                                                if ts.len() >= 16 {
-                                                                    let minute = &ts[11..16];
-                                                                    *buckets.entry(minute.to_string()).or_insert(0u64) += 1;
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                    let _ = tx.send(crate::app::DataUpdate::ThreatStats(stats)).await;
-                                                    let hist: Vec<(String, u64)> = buckets.into_iter().collect();
-                                                    let _ = tx.send(crate::app::DataUpdate::AlertHistory(hist)).await;
-
-                                                    let mut top: Vec<(String, u64)> = agent_counts.into_iter().collect();
-                                                    top.sort_by(|a, b| b.1.cmp(&a.1));
-                                                    top.truncate(5);
-                                                    let _ = tx.send(crate::app::DataUpdate::TopAgents(top)).await;
-                                                }
-                                            }
-                                        }
-                                        ActiveView::AgentInspector => {
-                                             if let Some(id) = agent_id {
-                                                if let Ok(hw_res) = api.get_hardware_info(&id).await {
-                                                    if let Some(hw) = hw_res.data.affected_items.into_iter().next() {
-                                                        let _ = tx.send(crate::app::DataUpdate::AgentHardware(hw)).await;
-                                                    }
-                                                }
-                                                if let Ok(proc_res) = api.get_processes(&id).await {
-                                                    let _ = tx.send(crate::app::DataUpdate::AgentProcesses(proc_res.data.affected_items)).await;
-                                                }
-                                            if let Ok(prog_res) = api.get_programs(&id).await {
-                                                let _ = tx.send(crate::app::DataUpdate::AgentPrograms(prog_res.data.affected_items)).await;
+                                                load_dashboard(&api, &tx, interval, 100, tz).await;
                                             }
-                                            match api.get_vulnerabilities(&id).await {
-                                                Ok(vuln_res) => {
-                                                    let _ = tx.send(crate::app::DataUpdate::AgentVulnerabilities(vuln_res.data.affected_items)).await;
-                                                }
-                                                Err(e) => {
-                                                    let _ = tx.send(crate::app::DataUpdate::ErrorPopup { 
-                                                        title: "Vulnerabilities Error".to_string(), 
-                                                        message: format!("Failed to load vulnerabilities: {}", e) 
-                                                    }).await;
-                                                }
-                                            }
-                                            if let Ok(logs_res) = api.get_logs(Some(&id), interval, 0, 100, None).await {
-                                                    if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                        let _ = tx.send(crate::app::DataUpdate::AgentLogs(hits.clone())).await;
-                                                    }
-                                                }
-                                                match api.get_agent_config(&id, &config_component).await {
-                                                    Ok(config_res) => {
-                                                        let _ = tx.send(crate::app::DataUpdate::AgentConfig(config_res)).await;
-                                                    }
-                                                    Err(e) => {
-                                                        let _ = tx.send(crate::app::DataUpdate::ErrorPopup { 
-                                                            title: "Config Error".to_string(), 
-                                                            message: format!("Failed to load config: {}", e) 
-                                                        }).await;
-                                                    }
-                                                }
-                                            }
-                                        }
+                                        // Loaded by spawn_inspector_load so a newer load can supersede it
+                                        ActiveView::AgentInspector => {}
                                         ActiveView::SecurityEvents => {
-                                            if let Ok(logs_res) = api.get_logs(None, interval, 0, 50, None).await {
-                                                if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                    let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
+                                            if let Ok(logs_res) = api.get_logs(None, interval, 0, 50, Some(&filter)).await {
+                                                if let Some(update) = security_events_update(&logs_res) {
+                                                    let _ = tx.send(update).await;
                                                 }
                                             }
                                         }
+                                        ActiveView::UserManagement => {
+                                            fetch_security_data(&api, &tx).await;
+                                        }
+                                        ActiveView::CdbLists => {
+                                            fetch_cdb_lists(&api, &tx).await;
+                                        }
+                                        ActiveView::Rules => {
+                                            fetch_rule_files(&api, &tx, ruleset_kind).await;
+                                        }
                                     }
                                     let _ = tx.send(crate::app::DataUpdate::Notification("Data refreshed".to_string(), crate::app::NotificationLevel::Success)).await;
                                 });
                                 app.stop_loading();
                             }
-                        } else if c == '?' {
-                            // Toggle help popup
-                            if app.popup_mode == crate::app::PopupMode::Help {
-                                app.popup_mode = crate::app::PopupMode::None;
+                        } else if c == 'n' && app.active_view == ActiveView::UserManagement {
+                            app.new_user_name.clear();
+                            app.new_user_password.clear();
+                            app.new_user_field = 0;
+                            app.popup_mode = crate::app::PopupMode::CreateUser;
+                        } else if c == 'a' && app.active_view == ActiveView::SecurityEvents && app.selected_log.is_none() {
+                            match app.acknowledge_selected_alerts() {
+                                0 => app.notify("Select alerts with Space first", crate::app::NotificationLevel::Info),
+                                n => app.notify(&format!("Acknowledged {} alerts", n), crate::app::NotificationLevel::Success),
+                            }
+                        } else if c == 'y' && app.active_view == ActiveView::SecurityEvents && app.selected_log.is_none() {
+                            let ids = app.selected_alert_ids();
+                            if ids.is_empty() {
+                                app.notify("Select alerts with Space first", crate::app::NotificationLevel::Info);
                             } else {
-                                app.popup_mode = crate::app::PopupMode::Help;
+                                use std::io::Write;
+                                let mut out = io::stdout();
+                                let _ = write!(out, "{}", crate::ui::common::osc52_sequence(&ids.join("\n"))).and_then(|_| out.flush());
+                                app.notify(&format!("Copied {} alert IDs", ids.len()), crate::app::NotificationLevel::Success);
+                            }
+                        } else if c == 'a' && app.active_view == ActiveView::UserManagement {
+                            if let Some(user) = app.get_selected_user() {
+                                app.popup_mode = crate::app::PopupMode::RoleAssignment { user_id: user.id };
+                                app.role_selection_index = 0;
+                            }
+                        } else if c == 'D' && app.active_view == ActiveView::UserManagement {
+                            if let Some(user) = app.get_selected_user() {
+                                app.popup_mode = crate::app::PopupMode::ConfirmDeleteUser { user_id: user.id, username: user.username.clone() };
+                            }
+                        } else if c == 'n' && app.active_view == ActiveView::Rules {
+                            if app.require_permission(app.ruleset_kind.update_action(), "Ruleset editing") {
+                                app.input_buffer.clear();
+                                app.popup_mode = crate::app::PopupMode::NewRuleFile;
                             }
+                        } else if c == 'D' && app.active_view == ActiveView::Rules {
+                            if let Some(file) = app.selected_rule_file() {
+                                let (kind, filename) = (app.ruleset_kind, file.filename.clone());
+                                if app.require_permission(kind.delete_action(), "Ruleset file deletion") {
+                                    app.popup_mode = crate::app::PopupMode::ConfirmRuleDelete { kind, filename };
+                                }
+                            }
+                        } else if c == 'n' && app.active_view == ActiveView::CdbLists {
+                            if app.require_permission(crate::app::ACTION_LISTS_UPDATE, "CDB list editing") {
+                                app.open_cdb_add_entry();
+                            }
+                        } else if c == 'D' && app.active_view == ActiveView::CdbLists && app.cdb_entries_focus {
+                            if app.require_permission(crate::app::ACTION_LISTS_UPDATE, "CDB list editing") {
+                                app.stage_cdb_removal();
+                            }
+                        } else if c == 'X' && matches!(app.active_view, ActiveView::AgentList | ActiveView::AgentInspector) {
+                            app.open_run_command();
+                        } else if c == 't' && app.active_view == ActiveView::AgentList {
+                            app.open_agent_labels();
+                        } else if c == 'L' {
+                            app.activity_index = 0;
+                            app.popup_mode = crate::app::PopupMode::Activity;
+                        } else if c == 'M' {
+                            app.notification_index = 0;
+                            app.popup_mode = crate::app::PopupMode::Notifications;
+                        } else if c == 'T' {
+                            app.relative_time = !app.relative_time;
+                            app.notify(if app.relative_time { "Relative timestamps" } else { "Absolute timestamps" }, crate::app::NotificationLevel::Info);
+                        } else if c == '?' {
+                            app.open_help();
                         } else if c == 'J' && app.active_view == ActiveView::Dashboard {
                             // Quick jump to agent from dashboard (moved from 'j' to 'J')
                             app.popup_mode = crate::app::PopupMode::AgentJump;
@@ -651,7 +2029,7 @@ async fn main() -> Result<()> {
                             app.jump_index = 0;
                         } else if c == 'q' {
                             if app.active_view == ActiveView::AgentInspector {
-                                app.active_view = ActiveView::AgentList;
+                                go_back(&mut app, &tx);
                             } else {
                                 app.should_quit = true;
                             }
@@ -662,6 +2040,9 @@ async fn main() -> Result<()> {
                             app.popup_mode = crate::app::PopupMode::None;
                         } else if matches!(app.popup_mode, crate::app::PopupMode::CommandPalette) {
                             app.popup_mode = crate::app::PopupMode::None;
+                        } else if app.detail_is_searching {
+                            app.detail_is_searching = false;
+                            app.detail_search_query.clear();
                         } else if app.is_searching {
                             app.is_searching = false;
                         } else if app.popup_mode != crate::app::PopupMode::None {
@@ -671,64 +2052,72 @@ async fn main() -> Result<()> {
                         } else if app.selected_log.is_some() {
                             app.selected_log = None;
                             app.log_scroll_offset = 0;
+                        } else if !app.selected_alerts.is_empty() && app.active_view == ActiveView::SecurityEvents {
+                            app.selected_alerts.clear();
                         } else if app.severity_filter.is_some() {
                             app.severity_filter = None;
-                        } else if app.active_view == ActiveView::AgentInspector {
-                            app.active_view = ActiveView::AgentList;
+                            app.sort_agents();
+                        } else if app.vuln_severity_filter.is_some() && app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Vulnerabilities {
+                            app.vuln_severity_filter = None;
+                        } else {
+                            go_back(&mut app, &tx);
                         }
                     }
                     KeyCode::PageUp => {
-                        if app.active_view == ActiveView::SecurityEvents {
+                        if matches!(app.popup_mode, crate::app::PopupMode::Help) {
+                            app.scroll_help(-10);
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::SeverityFilter) {
+                            app.query_preview_scroll = app.query_preview_scroll.saturating_sub(10);
+                        } else if app.active_view == ActiveView::SecurityEvents {
                             app.log_offset = app.log_offset.saturating_sub(app.log_limit);
-                            let api = app.api.as_ref().unwrap().clone();
-                            let tx = tx.clone();
-                            let interval = app.log_interval_mins;
-                            let offset = app.log_offset;
-                            let limit = app.log_limit;
-                            let filter = Some(app.log_filter.clone());
-                            tokio::spawn(async move {
-                                if let Ok(res) = api.get_logs(None, interval, offset, limit, filter.as_ref()).await {
-                                    if let Some(hits) = res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                        let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
-                                    }
-                                }
-                            });
+                            spawn_log_page(&app, &tx);
                         } else {
                             app.scroll_up(15);
                         }
                     }
                     KeyCode::PageDown => {
-                        if app.active_view == ActiveView::SecurityEvents {
+                        if matches!(app.popup_mode, crate::app::PopupMode::Help) {
+                            app.scroll_help(10);
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::SeverityFilter) {
+                            app.query_preview_scroll = app.query_preview_scroll.saturating_add(10);
+                        } else if app.active_view == ActiveView::SecurityEvents {
                             app.log_offset += app.log_limit;
-                            let api = app.api.as_ref().unwrap().clone();
-                            let tx = tx.clone();
-                            let interval = app.log_interval_mins;
-                            let offset = app.log_offset;
-                            let limit = app.log_limit;
-                            let filter = Some(app.log_filter.clone());
-                            tokio::spawn(async move {
-                                if let Ok(res) = api.get_logs(None, interval, offset, limit, filter.as_ref()).await {
-                                    if let Some(hits) = res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                        let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
-                                    }
-                                }
-                            });
+                            spawn_log_page(&app, &tx);
                         } else {
                             app.scroll_down(15);
                         }
                     }
                     KeyCode::Backspace => {
-                        if matches!(app.popup_mode, crate::app::PopupMode::AgentJump) {
+                        if matches!(app.popup_mode, crate::app::PopupMode::Help) {
+                            app.help_query.pop();
+                            app.help_scroll = 0;
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::AgentJump) {
                             app.jump_input.pop();
                             app.jump_index = 0;
                         } else if matches!(app.popup_mode, crate::app::PopupMode::CommandPalette) {
                             app.command_palette_input.pop();
                             app.command_palette_index = 0;
+                        } else if app.detail_is_searching {
+                            app.detail_search_query.pop();
                         } else if app.is_searching {
                             app.search_query.pop();
                             app.agent_filter = crate::app::filter::AgentFilter::parse(&app.search_query);
-                        } else if matches!(app.popup_mode, crate::app::PopupMode::SshUsername { .. }) {
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::SshUsername { .. } | crate::app::PopupMode::CdbAddEntry { .. } | crate::app::PopupMode::NewRuleFile | crate::app::PopupMode::AgentLabels { .. } | crate::app::PopupMode::ExportFields | crate::app::PopupMode::SetupBundle { .. }) {
                             app.input_buffer.pop();
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::Logtest) {
+                            if let Some(input) = app.logtest_input() {
+                                input.pop();
+                            }
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::CreateUser) {
+                            if app.new_user_field == 0 {
+                                app.new_user_name.pop();
+                            } else {
+                                app.new_user_password.pop();
+                            }
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::Relogin) {
+                            if let Some(input) = app.relogin.input() {
+                                input.pop();
+                            }
                         } else if matches!(app.popup_mode, crate::app::PopupMode::SeverityFilter) {
                             match app.filter_popup_tab {
                                 crate::app::FilterPopupTab::Severity => {
@@ -750,6 +2139,7 @@ async fn main() -> Result<()> {
                                 }
                                 crate::app::FilterPopupTab::Text => {
                                     app.log_filter.description_filter.pop();
+                                    app.schedule_live_search();
                                 }
                                 crate::app::FilterPopupTab::Columns => {
                                     // No backspace in columns tab
@@ -761,10 +2151,14 @@ async fn main() -> Result<()> {
                             match app.config_step {
                                 crate::app::ConfigStep::Url => { app.config_url.pop(); }
                                 crate::app::ConfigStep::OsUrl => { app.config_os_url.pop(); }
+                                crate::app::ConfigStep::AlertsIndex => { app.config_alerts_index.pop(); }
+                                crate::app::ConfigStep::VulnIndex => { app.config_vuln_index.pop(); }
                                 crate::app::ConfigStep::Username => { app.config_username.pop(); }
                                 crate::app::ConfigStep::Password => { app.config_password.pop(); }
                                 crate::app::ConfigStep::Confirm => { app.config_step = crate::app::ConfigStep::Password; }
                             }
+                        } else if app.popup_mode == crate::app::PopupMode::None && app.selected_log.is_none() {
+                            go_back(&mut app, &tx);
                         }
                     }
                     KeyCode::Tab => {
@@ -772,31 +2166,43 @@ async fn main() -> Result<()> {
                             // Tab switches between filter popup tabs
                             app.filter_popup_tab = app.filter_popup_tab.next();
                             app.filter_active_input = 0; // Reset input focus when switching tabs
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::CreateUser) {
+                            app.new_user_field = (app.new_user_field + 1) % 2;
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::Relogin) {
+                            app.relogin.next_field();
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::Logtest) {
+                            app.logtest_field = (app.logtest_field + 1) % 3;
                         } else if app.active_view == ActiveView::AgentInspector {
                             app.next_tab();
                         } else {
-                            app.active_view = match app.active_view {
+                            let next_view = match app.active_view {
                                 ActiveView::Dashboard => ActiveView::AgentList,
                                 ActiveView::AgentList => ActiveView::SecurityEvents,
                                 ActiveView::SecurityEvents => ActiveView::GroupManagement,
-                                ActiveView::GroupManagement => ActiveView::Dashboard,
+                                ActiveView::GroupManagement => ActiveView::UserManagement,
+                                ActiveView::UserManagement => ActiveView::CdbLists,
+                                ActiveView::CdbLists => ActiveView::Rules,
+                                ActiveView::Rules => ActiveView::Dashboard,
                                 ActiveView::AgentInspector => ActiveView::AgentList,
                             };
+                            app.navigate(next_view);
                             
                             if let Some(api) = app.api.clone() {
                                 app.set_loading("Fetching data...");
                                 app.error_message = None;
                                 let tx = tx.clone();
                                 let interval = app.log_interval_mins;
+                                let filter = app.log_filter.clone();
                                 let active_view = app.active_view.clone();
+                                let ruleset_kind = app.ruleset_kind;
                                 
                                 tokio::spawn(async move {
                                     match active_view {
                                         ActiveView::SecurityEvents => {
-                                            match api.get_logs(None, interval, 0, 50, None).await {
+                                            match api.get_logs(None, interval, 0, 50, Some(&filter)).await {
                                                 Ok(logs_res) => {
-                                                    if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                        let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
+                                                    if let Some(update) = security_events_update(&logs_res) {
+                                                        let _ = tx.send(update).await;
                                                     }
                                                 }
                                                 Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load logs: {}", e))).await; }
@@ -812,6 +2218,15 @@ async fn main() -> Result<()> {
                                                 Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load groups: {}", e))).await; }
                                             }
                                         }
+                                        ActiveView::UserManagement => {
+                                            fetch_security_data(&api, &tx).await;
+                                        }
+                                        ActiveView::CdbLists => {
+                                            fetch_cdb_lists(&api, &tx).await;
+                                        }
+                                        ActiveView::Rules => {
+                                            fetch_rule_files(&api, &tx, ruleset_kind).await;
+                                        }
                                         _ => {}
                                     }
                                 });
@@ -848,109 +2263,38 @@ async fn main() -> Result<()> {
                                                       app.set_loading("Refreshing...");
                                                       let tx = tx.clone();
                                                       let active_view = app.active_view.clone();
+                                                      let ruleset_kind = app.ruleset_kind;
                                                       let agent_id = app.get_selected_agent().map(|a| a.id.clone());
                                                       let interval = app.log_interval_mins;
-                                                      let config_component = app.agent_config_component.clone();
-                                                      
+                                                      let filter = app.log_filter.clone();
+                                                      let tz = app.display_tz;
+                                                      if let (ActiveView::AgentInspector, Some(id)) = (&active_view, agent_id) {
+                                                          spawn_inspector_load(&mut app, &tx, id, true);
+                                                      }
+
                                                       tokio::spawn(async move {
                                                           match active_view {
                                                                   ActiveView::Dashboard | ActiveView::AgentList | ActiveView::GroupManagement => {
-                                                                      if let Ok(agents_res) = api.list_agents(None, 0, 500).await {
-                                                                          let _ = tx.send(crate::app::DataUpdate::Agents(agents_res.data.affected_items)).await;
-                                                                      }
-                                                                      if let Ok(groups_res) = api.get_groups().await {
-                                                                          let _ = tx.send(crate::app::DataUpdate::Groups(groups_res.data.affected_items)).await;
-                                                                      }
-
-                                                                  if let Ok(logs_res) = api.get_logs(None, interval, 0, 100, None).await {
-                                                                      if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                                          let mut stats = crate::app::ThreatStats::default();
-                                                                          let mut buckets = std::collections::BTreeMap::new();
-                                                                          let mut agent_counts = std::collections::HashMap::new();
-
-                                                                          for hit in hits {
-                                                                              if let Some(source) = hit.get("_source") {
-                                                                                  if let Some(level) = source.get("rule").and_then(|r| r.get("level")).and_then(|l| l.as_u64()) {
-                                                                                      match level {
-                                                                                          15..=u64::MAX => stats.critical += 1,
-                                                                                          12..=14 => stats.high += 1,
-                                                                                          7..=11 => stats.medium += 1,
-                                                                                          _ => stats.low += 1,
-                                                                                      }
-                                                                                  }
-                                                                                  
-                                                                                  if let Some(agent_name) = source.get("agent").and_then(|a| a.get("name")).and_then(|n| n.as_str()) {
-                                                                                      *agent_counts.entry(agent_name.to_string()).or_insert(0u64) += 1;
-                                                                                  }
-
-                                                                                  if let Some(ts) = source.get("@timestamp").and_then(|t| t.as_str()) {
-                                                                                      if ts.len() >= 16 {
-                                                                                          let minute = &ts[11..16];
-                                                                                          *buckets.entry(minute.to_string()).or_insert(0u64) += 1;
-                                                                                      }
-                                                                                  }
-                                                                              }
-                                                                          }
-                                                                          let _ = tx.send(crate::app::DataUpdate::ThreatStats(stats)).await;
-                                                                          let hist: Vec<(String, u64)> = buckets.into_iter().collect();
-                                                                          let _ = tx.send(crate::app::DataUpdate::AlertHistory(hist)).await;
-
-                                                                          let mut top: Vec<(String, u64)> = agent_counts.into_iter().collect();
-                                                                          top.sort_by(|a, b| b.1.cmp(&a.1));
-                                                                          top.truncate(5);
-                                                                          let _ = tx.send(crate::app::DataUpdate::TopAgents(top)).await;
-                                                                      }
-                                                                  }
-                                                              }
-                                                              ActiveView::AgentInspector => {
-                                                                   if let Some(id) = agent_id {
-                                                                      if let Ok(hw_res) = api.get_hardware_info(&id).await {
-                                                                          if let Some(hw) = hw_res.data.affected_items.into_iter().next() {
-                                                                              let _ = tx.send(crate::app::DataUpdate::AgentHardware(hw)).await;
-                                                                          }
-                                                                      }
-                                                                      if let Ok(proc_res) = api.get_processes(&id).await {
-                                                                          let _ = tx.send(crate::app::DataUpdate::AgentProcesses(proc_res.data.affected_items)).await;
-                                                                      }
-                                                                  if let Ok(prog_res) = api.get_programs(&id).await {
-                                                                      let _ = tx.send(crate::app::DataUpdate::AgentPrograms(prog_res.data.affected_items)).await;
+                                                                      load_dashboard(&api, &tx, interval, 100, tz).await;
                                                                   }
-                                                                  match api.get_vulnerabilities(&id).await {
-                                                                      Ok(vuln_res) => {
-                                                                          let _ = tx.send(crate::app::DataUpdate::AgentVulnerabilities(vuln_res.data.affected_items)).await;
-                                                                      }
-                                                                      Err(e) => {
-                                                                          let _ = tx.send(crate::app::DataUpdate::ErrorPopup { 
-                                                                              title: "Vulnerabilities Error".to_string(), 
-                                                                              message: format!("Failed to load vulnerabilities: {}", e) 
-                                                                          }).await;
-                                                                      }
-                                                                  }
-                                                                  if let Ok(logs_res) = api.get_logs(Some(&id), interval, 0, 100, None).await {
-                                                                          if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                                              let _ = tx.send(crate::app::DataUpdate::AgentLogs(hits.clone())).await;
-                                                                          }
-                                                                      }
-                                                                      match api.get_agent_config(&id, &config_component).await {
-                                                                          Ok(config_res) => {
-                                                                              let _ = tx.send(crate::app::DataUpdate::AgentConfig(config_res)).await;
-                                                                          }
-                                                                          Err(e) => {
-                                                                              let _ = tx.send(crate::app::DataUpdate::ErrorPopup { 
-                                                                                  title: "Config Error".to_string(), 
-                                                                                  message: format!("Failed to load config: {}", e) 
-                                                                              }).await;
-                                                                          }
-                                                                      }
-                                                                  }
-                                                              }
+                                                              // Loaded by spawn_inspector_load so a newer load can supersede it
+                                                              ActiveView::AgentInspector => {}
                                                               ActiveView::SecurityEvents => {
-                                                                  if let Ok(logs_res) = api.get_logs(None, interval, 0, 50, None).await {
-                                                                      if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                                          let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
+                                                                  if let Ok(logs_res) = api.get_logs(None, interval, 0, 50, Some(&filter)).await {
+                                                                      if let Some(update) = security_events_update(&logs_res) {
+                                                                          let _ = tx.send(update).await;
                                                                       }
                                                                   }
                                                               }
+                                                              ActiveView::UserManagement => {
+                                                                  fetch_security_data(&api, &tx).await;
+                                                              }
+                                                              ActiveView::CdbLists => {
+                                                                  fetch_cdb_lists(&api, &tx).await;
+                                                              }
+                                                              ActiveView::Rules => {
+                                                                  fetch_rule_files(&api, &tx, ruleset_kind).await;
+                                                              }
                                                           }
                                                           let _ = tx.send(crate::app::DataUpdate::Notification("Data refreshed".to_string(), crate::app::NotificationLevel::Success)).await;
                                                       });
@@ -958,77 +2302,197 @@ async fn main() -> Result<()> {
                                                   }
                                               },
                                               "Help" => {
-                                                  app.popup_mode = crate::app::PopupMode::Help;
+                                                  app.open_help();
+                                              },
+                                              "Tour" => {
+                                                  app.start_tour();
                                               },
                                               "Quit" => {
                                                   app.should_quit = true;
                                               },
                                               "Dashboard" => {
-                                                  app.active_view = ActiveView::Dashboard;
+                                                  app.navigate(ActiveView::Dashboard);
                                                   app.popup_mode = crate::app::PopupMode::None;
                                               },
                                               "Agent List" => {
-                                                  app.active_view = ActiveView::AgentList;
+                                                  app.navigate(ActiveView::AgentList);
                                                   app.popup_mode = crate::app::PopupMode::None;
                                               },
                                               "Security Events" => {
-                                                  app.active_view = ActiveView::SecurityEvents;
+                                                  app.navigate(ActiveView::SecurityEvents);
                                                   app.popup_mode = crate::app::PopupMode::None;
                                               },
                                               "Group Management" => {
-                                                  app.active_view = ActiveView::GroupManagement;
+                                                  app.navigate(ActiveView::GroupManagement);
+                                                  app.popup_mode = crate::app::PopupMode::None;
+                                              },
+                                              "FIM Scan" => {
+                                                  app.popup_mode = crate::app::PopupMode::None;
+                                                  if app.require_permission(crate::app::ACTION_SYSCHECK_RUN, "FIM scan") {
+                                                      let agent_ids = app.action_targets();
+                                                      request_agent_action(&mut app, &tx, crate::app::actions::AgentAction::FimScan, agent_ids);
+                                                  }
+                                              },
+                                              "Outdated Agents" => {
+                                                  app.navigate(ActiveView::AgentList);
+                                                  app.popup_mode = crate::app::PopupMode::None;
+                                                  app.show_outdated_only = true;
+                                                  app.table_state.select(Some(0));
+                                                  spawn_outdated_fetch(&app, &tx);
+                                              },
+                                              "Cleanup Stale Agents" => {
+                                                  if !app.require_permission(crate::app::ACTION_AGENT_DELETE, "Agent deletion") {
+                                                      app.popup_mode = crate::app::PopupMode::None;
+                                                  } else {
+                                                      let agent_ids: Vec<String> = app.stale_agents().iter().map(|a| a.id.clone()).collect();
+                                                      if agent_ids.is_empty() {
+                                                          app.popup_mode = crate::app::PopupMode::None;
+                                                          app.notify(&format!("No never-connected or pending agents older than {} days", app.stale_agent_days), crate::app::NotificationLevel::Info);
+                                                      } else {
+                                                          app.popup_mode = crate::app::PopupMode::None;
+                                                          request_agent_action(&mut app, &tx, crate::app::actions::AgentAction::DeleteStale, agent_ids);
+                                                      }
+                                                  }
+                                              },
+                                              "Export Setup" | "Import Setup" => {
+                                                  let import = *name == "Import Setup";
+                                                  let path = crate::app::bundle::default_path(app.export_naming.dir.as_deref());
+                                                  app.input_buffer = path.display().to_string();
+                                                  app.popup_mode = crate::app::PopupMode::SetupBundle { import };
+                                              },
+                                              "Re-login" => {
+                                                  app.relogin = crate::app::relogin::ReloginForm::new();
+                                                  app.popup_mode = crate::app::PopupMode::Relogin;
+                                              },
+                                              "Agents by Node" => {
+                                                  app.node_index = 0;
+                                                  app.popup_mode = crate::app::PopupMode::AgentNodes;
+                                              },
+                                              "Pending Actions" => {
+                                                  app.pending_index = 0;
+                                                  app.popup_mode = crate::app::PopupMode::PendingActions;
+                                              },
+                                              "Bulk Progress" => {
+                                                  if app.bulk_op.is_some() {
+                                                      app.popup_mode = crate::app::PopupMode::BulkProgress;
+                                                  } else {
+                                                      app.popup_mode = crate::app::PopupMode::None;
+                                                      app.notify("No bulk restart or upgrade yet", crate::app::NotificationLevel::Info);
+                                                  }
+                                              },
+                                              "Activity Log" => {
+                                                  app.activity_index = 0;
+                                                  app.popup_mode = crate::app::PopupMode::Activity;
+                                              },
+                                              "Notifications" => {
+                                                  app.notification_index = 0;
+                                                  app.popup_mode = crate::app::PopupMode::Notifications;
+                                              },
+                                              "Indexer Health" => spawn_indexer_health(&mut app, &tx),
+                                              "TLS Certificates" => spawn_certificates(&mut app, &tx, true),
+                                              "Hunts" => {
+                                                  app.hunt_index = 0;
+                                                  app.popup_mode = crate::app::PopupMode::Hunts;
+                                              },
+                                              "Run Command" => {
+                                                  app.popup_mode = crate::app::PopupMode::None;
+                                                  app.open_run_command();
+                                              },
+                                              "Command Output" => {
+                                                  app.popup_mode = if app.remote_command.is_some() { crate::app::PopupMode::CommandOutput } else { crate::app::PopupMode::None };
+                                              },
+                                              saved if saved.starts_with("Saved Search ") => {
+                                                  let slot = saved.trim_start_matches("Saved Search ").parse().unwrap_or(0);
+                                                  app.popup_mode = crate::app::PopupMode::None;
+                                                  app.apply_agent_search(slot);
+                                              },
+                                              "API Requests" => {
+                                                  app.request_log_index = 0;
+                                                  app.popup_mode = crate::app::PopupMode::RequestLog;
+                                              },
+                                              "Export Report" => {
+                                                  app.popup_mode = crate::app::PopupMode::None;
+                                                  match app.export_report() {
+                                                      Ok(path) => app.exported("Report written", path),
+                                                      Err(e) => app.notify(&e, crate::app::NotificationLevel::Error),
+                                                  }
+                                              },
+                                              "Test Log Line" => {
+                                                  app.logtest_field = 0;
+                                                  app.popup_mode = crate::app::PopupMode::Logtest;
+                                              },
+                                              "Rule Files" => {
+                                                  app.navigate(ActiveView::Rules);
+                                                  app.popup_mode = crate::app::PopupMode::None;
+                                                  if let Some(api) = app.api.clone() {
+                                                      let tx = tx.clone();
+                                                      let kind = app.ruleset_kind;
+                                                      tokio::spawn(async move {
+                                                          fetch_rule_files(&api, &tx, kind).await;
+                                                      });
+                                                  }
+                                              },
+                                              "CDB Lists" => {
+                                                  app.navigate(ActiveView::CdbLists);
+                                                  app.popup_mode = crate::app::PopupMode::None;
+                                                  if let Some(api) = app.api.clone() {
+                                                      let tx = tx.clone();
+                                                      tokio::spawn(async move {
+                                                          fetch_cdb_lists(&api, &tx).await;
+                                                      });
+                                                  }
+                                              },
+                                              "Users & Roles" => {
+                                                  app.navigate(ActiveView::UserManagement);
                                                   app.popup_mode = crate::app::PopupMode::None;
+                                                  if let Some(api) = app.api.clone() {
+                                                      let tx = tx.clone();
+                                                      tokio::spawn(async move {
+                                                          fetch_security_data(&api, &tx).await;
+                                                      });
+                                                  }
                                               },
                                               _ => {}
                                           }
                                       }
                                   }
+                                  crate::app::PopupMode::AgentNodes => app.show_node_agents(),
+                                  crate::app::PopupMode::Hunts => {
+                                    let hunt = crate::app::hunts::HUNTS[app.hunt_index.min(crate::app::hunts::HUNTS.len() - 1)];
+                                    spawn_hunt(&mut app, &tx, hunt);
+                                }
+                                  crate::app::PopupMode::HuntResults { hunt } => {
+                                    let hunt = *hunt;
+                                    let key = app.hunt_rows.as_ref().and_then(|r| r.as_ref().ok())
+                                        .and_then(|rows| rows.get(app.hunt_row_index))
+                                        .map(|row| row.key.clone());
+                                    if let Some(key) = key.filter(|_| hunt.keyed_by_srcip()) {
+                                        spawn_srcip_pivot_for(&mut app, &tx, key);
+                                    }
+                                }
+                                  crate::app::PopupMode::DuplicateAgents => {
+                                    let agent_id = app.duplicate_rows().get(app.duplicate_index).map(|(_, a)| a.id.clone());
+                                    if let Some(agent_id) = agent_id {
+                                        if let Some(pos) = app.agents.iter().position(|a| a.id == agent_id && a.profile.is_none()) {
+                                            app.popup_mode = crate::app::PopupMode::None;
+                                            app.selected_agent_index = pos;
+                                            app.navigate(ActiveView::AgentInspector);
+                                            app.error_message = None;
+                                            spawn_inspector_load(&mut app, &tx, agent_id, false);
+                                        }
+                                    }
+                                }
                                   crate::app::PopupMode::AgentJump => {
                                     let matches = app.get_jump_matches();
                                     if let Some(agent) = matches.get(app.jump_index) {
                                         let agent_id = agent.id.clone();
                                         if let Some(pos) = app.agents.iter().position(|a| a.id == agent_id) {
                                             app.selected_agent_index = pos;
-                                            app.active_view = ActiveView::AgentInspector;
+                                            app.navigate(ActiveView::AgentInspector);
                                             
                                             // Trigger data load for the inspector
                                             app.set_loading("Loading agent details...");
-                                            if let Some(api) = &app.api {
-                                                let api = api.clone();
-                                                let tx = tx.clone();
-                                                let interval = app.log_interval_mins;
-                                                let config_component = app.agent_config_component.clone();
-                                                
-                                                tokio::spawn(async move {
-                                                    if let Ok(hw_res) = api.get_hardware_info(&agent_id).await {
-                                                        if let Some(hw) = hw_res.data.affected_items.into_iter().next() {
-                                                            let _ = tx.send(crate::app::DataUpdate::AgentHardware(hw)).await;
-                                                        }
-                                                    }
-                                                    if let Ok(proc_res) = api.get_processes(&agent_id).await {
-                                                        let _ = tx.send(crate::app::DataUpdate::AgentProcesses(proc_res.data.affected_items)).await;
-                                                    }
-                                                    if let Ok(prog_res) = api.get_programs(&agent_id).await {
-                                                        let _ = tx.send(crate::app::DataUpdate::AgentPrograms(prog_res.data.affected_items)).await;
-                                                    }
-                                                    if let Ok(vuln_res) = api.get_vulnerabilities(&agent_id).await {
-                                                        let _ = tx.send(crate::app::DataUpdate::AgentVulnerabilities(vuln_res.data.affected_items)).await;
-                                                    }
-                                                    if let Ok(logs_res) = api.get_logs(Some(&agent_id), interval, 0, 100, None).await {
-                                                        if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                            let _ = tx.send(crate::app::DataUpdate::AgentLogs(hits.clone())).await;
-                                                        }
-                                                    }
-                                                    match api.get_agent_config(&agent_id, &config_component).await {
-                                                        Ok(config_res) => {
-                                                            let _ = tx.send(crate::app::DataUpdate::AgentConfig(config_res)).await;
-                                                        }
-                                                        Err(e) => {
-                                                            let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load config: {}", e))).await;
-                                                        }
-                                                    }
-                                                });
-                                            }
+                                            spawn_inspector_load(&mut app, &tx, agent_id, false);
                                             app.stop_loading();
                                         }
                                     }
@@ -1045,12 +2509,24 @@ async fn main() -> Result<()> {
                                         } else {
                                             vec![agent_id.clone()]
                                         };
+                                        let agent_ids = app.without_manager(agent_ids, "Group assignment");
+                                        let undo = crate::app::undo::GroupUndo::assignment(&app.agents, &group_id, &agent_ids);
 
                                         tokio::spawn(async move {
                                             let ids: Vec<&str> = agent_ids.iter().map(|s| s.as_str()).collect();
-                                            match api.assign_agents_to_group(&group_id, &ids).await {
-                                                Ok(_) => { let _ = tx.send(crate::app::DataUpdate::Notification(format!("{} agents assigned to {}", ids.len(), group_id), crate::app::NotificationLevel::Success)).await; },
-                                                Err(e) => { let _ = tx.send(crate::app::DataUpdate::Notification(format!("Assignment failed: {}", e), crate::app::NotificationLevel::Error)).await; },
+                                            let res = api.assign_agents_to_group(&group_id, &ids).await
+                                                .map(|_| format!("{} agents assigned to {}", ids.len(), group_id))
+                                                .map_err(|e| format!("Assignment failed: {}", crate::api::error::describe(&e)));
+                                            match (res, undo) {
+                                                (Ok(message), Some(undo)) => {
+                                                    let entry = crate::app::audit::AuditEntry::new(&format!("group assign ({})", group_id), &agent_ids, true, &message);
+                                                    let _ = tx.send(crate::app::DataUpdate::Audit(entry)).await;
+                                                    let _ = tx.send(crate::app::DataUpdate::GroupUndo { undo, message }).await;
+                                                }
+                                                (res, _) => send_audited(&tx, &format!("group assign ({})", group_id), &agent_ids, res).await,
+                                            }
+                                            if let Ok(res) = api.list_agents(None, 0, 500).await {
+                                                let _ = tx.send(crate::app::DataUpdate::Agents(res.data.affected_items)).await;
                                             }
                                         });
                                     }
@@ -1059,8 +2535,9 @@ async fn main() -> Result<()> {
                                 }
 
                                 crate::app::PopupMode::SeverityFilter => {
-                                    app.log_filter.val1 = app.filter_input_1.parse().unwrap_or(0);
-                                    app.log_filter.val2 = app.filter_input_2.parse().unwrap_or(15);
+                                    app.log_filter = app.pending_log_filter();
+                                    // Applying queries right away; a pending live search would only repeat it
+                                    app.live_search_due = None;
                                     
                                     if let Some(api) = app.api.clone() {
                                         app.set_loading("Refreshing with filters...");
@@ -1074,8 +2551,8 @@ async fn main() -> Result<()> {
                                             match active_view {
                                                 ActiveView::SecurityEvents => {
                                                     if let Ok(res) = api.get_logs(None, interval, 0, 50, filter.as_ref()).await {
-                                                        if let Some(hits) = res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                            let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
+                                                        if let Some(update) = security_events_update(&res) {
+                                                            let _ = tx.send(update).await;
                                                         }
                                                     }
                                                 }
@@ -1095,28 +2572,23 @@ async fn main() -> Result<()> {
                                     }
                                     app.popup_mode = crate::app::PopupMode::None;
                                 }
-                                crate::app::PopupMode::SshUsername { agent_id: _, agent_ip } => {
-                                    let username = app.input_buffer.clone();
-                                    if !username.is_empty() && !agent_ip.is_empty() {
-                                        let ssh_cmd = format!("ssh {}@{}", username, agent_ip);
-                                        // Try common terminal emulators
-                                        let terminals = [
-                                            ("xdg-terminal", vec![ssh_cmd.clone()]),
-                                            ("gnome-terminal", vec!["--".to_string(), "bash".to_string(), "-c".to_string(), format!("{}; exec bash", ssh_cmd)]),
-                                            ("konsole", vec!["-e".to_string(), ssh_cmd.clone()]),
-                                            ("wezterm", vec!["start".to_string(), "--".to_string(), "bash".to_string(), "-c".to_string(), format!("{}; exec bash", ssh_cmd)]),
-                                            ("alacritty", vec!["-e".to_string(), "bash".to_string(), "-c".to_string(), format!("{}; exec bash", ssh_cmd)]),
-                                            ("kitty", vec!["bash".to_string(), "-c".to_string(), format!("{}; exec bash", ssh_cmd)]),
-                                            ("foot", vec!["bash".to_string(), "-c".to_string(), format!("{}; exec bash", ssh_cmd)]),
-                                            ("xterm", vec!["-e".to_string(), ssh_cmd.clone()]),
-                                        ];
-                                        
+                                crate::app::PopupMode::SshUsername { .. } => {
+                                    let target = app.ssh_target();
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                    if let Some(target) = target.as_deref().filter(|_| crate::app::pty::prefer_embedded(|k| std::env::var(k).ok())) {
+                                        open_embedded_ssh(&mut app, &tx, target);
+                                    } else if let Some(target) = target {
+                                        let ssh_cmd = format!("ssh {}", target);
+                                        let platform = crate::app::launch::Platform::current();
+                                        let terminals = crate::app::launch::terminal_commands(platform, &ssh_cmd, platform == crate::app::launch::Platform::MacOs && crate::app::launch::iterm_installed());
+
                                         let mut spawned = false;
                                         let mut last_error = String::new();
 
-                                        for (t, args) in terminals {
-                                            match std::process::Command::new(t)
-                                                .args(&args)
+                                        for terminal in terminals {
+                                            let (program, args) = &terminal;
+                                            match std::process::Command::new(program)
+                                                .args(args)
                                                 .stdin(std::process::Stdio::null())
                                                 .stdout(std::process::Stdio::null())
                                                 .stderr(std::process::Stdio::null())
@@ -1124,7 +2596,7 @@ async fn main() -> Result<()> {
                                             {
                                                 Ok(_) => {
                                                     spawned = true;
-                                                    app.notify(&format!("SSH session started in {}", t), crate::app::NotificationLevel::Success);
+                                                    app.notify(&format!("SSH session started in {}", crate::app::launch::launcher_name(&terminal)), crate::app::NotificationLevel::Success);
                                                     break;
                                                 }
                                                 Err(e) => {
@@ -1133,15 +2605,292 @@ async fn main() -> Result<()> {
                                             }
                                         }
                                         if !spawned {
-                                            app.notify(&format!("Failed to launch SSH: {}", last_error), crate::app::NotificationLevel::Error);
+                                            tracing::warn!(error = %last_error, "no terminal emulator could be started");
+                                            app.notify("No terminal window could be opened, using the embedded SSH pane", crate::app::NotificationLevel::Warning);
+                                            open_embedded_ssh(&mut app, &tx, &target);
                                         }
                                     }
-                                    app.popup_mode = crate::app::PopupMode::None;
                                 }
                                 crate::app::PopupMode::Error { .. } => {
                                     // Just close the error popup
                                     app.popup_mode = crate::app::PopupMode::None;
                                 }
+                                crate::app::PopupMode::CreateUser => {
+                                    if app.new_user_field == 0 {
+                                        app.new_user_field = 1;
+                                    } else if let Some(api) = app.api.clone() {
+                                        let tx = tx.clone();
+                                        let username = app.new_user_name.clone();
+                                        let password = app.new_user_password.clone();
+                                        app.new_user_password.clear();
+                                        tokio::spawn(async move {
+                                            let res = api.create_user(&username, &password).await
+                                                .map(|_| format!("User {} created", username))
+                                                .map_err(|e| format!("Create user failed: {}", crate::api::error::describe(&e)));
+                                            send_audited(&tx, "create user", &[username], res).await;
+                                            fetch_security_data(&api, &tx).await;
+                                        });
+                                        app.popup_mode = crate::app::PopupMode::None;
+                                    }
+                                }
+                                crate::app::PopupMode::Relogin => {
+                                    if app.relogin.field == 0 {
+                                        app.relogin.next_field();
+                                    } else if app.relogin.is_complete() {
+                                        spawn_relogin(&mut app, &tx);
+                                    }
+                                }
+                                crate::app::PopupMode::RoleAssignment { user_id } => {
+                                    let user_id = *user_id;
+                                    let assigned = app.security_users.iter().find(|u| u.id == user_id).map(|u| u.roles.clone()).unwrap_or_default();
+                                    if let (Some(api), Some(role)) = (app.api.clone(), app.security_roles.get(app.role_selection_index)) {
+                                        let tx = tx.clone();
+                                        let role_id = role.id;
+                                        let role_name = role.name.clone();
+                                        let remove = assigned.contains(&role_id);
+                                        tokio::spawn(async move {
+                                            let res = if remove {
+                                                api.remove_roles_from_user(user_id, &[role_id]).await
+                                            } else {
+                                                api.assign_roles_to_user(user_id, &[role_id]).await
+                                            };
+                                            let verb = if remove { "removed" } else { "assigned" };
+                                            let res = res
+                                                .map(|_| format!("Role {} {}", role_name, verb))
+                                                .map_err(|e| format!("Role update failed: {}", crate::api::error::describe(&e)));
+                                            let action = if remove { "remove role" } else { "assign role" };
+                                            send_audited(&tx, action, &[format!("user {}", user_id), role_name], res).await;
+                                            fetch_security_data(&api, &tx).await;
+                                        });
+                                    }
+                                }
+                                crate::app::PopupMode::ConfirmDeleteUser { user_id, username } => {
+                                    if let Some(api) = app.api.clone() {
+                                        let tx = tx.clone();
+                                        let user_id = *user_id;
+                                        let username = username.clone();
+                                        tokio::spawn(async move {
+                                            let res = api.delete_user(user_id).await
+                                                .map(|_| format!("User {} deleted", username))
+                                                .map_err(|e| format!("Delete user failed: {}", crate::api::error::describe(&e)));
+                                            send_audited(&tx, "delete user", &[username], res).await;
+                                            fetch_security_data(&api, &tx).await;
+                                        });
+                                    }
+                                    app.users_table_state.select(Some(0));
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                }
+                                crate::app::PopupMode::SrcIpPivot { .. } => {
+                                    if let Some(event) = app.selected_pivot_event() {
+                                        app.open_log_detail(event);
+                                    }
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                }
+                                crate::app::PopupMode::AlertContext => {
+                                    if let Some(event) = app.alert_context.get(app.alert_context_index).cloned() {
+                                        app.open_log_detail(event);
+                                    }
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                }
+                                crate::app::PopupMode::ProcessDetail { .. } => {
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                }
+                                crate::app::PopupMode::GroupMembership { .. } => {
+                                    app.confirm_group_membership();
+                                }
+                                crate::app::PopupMode::CdbAddEntry { .. } => {
+                                    app.stage_cdb_entry();
+                                }
+                                crate::app::PopupMode::AgentLabels { .. } => {
+                                    app.apply_agent_labels();
+                                }
+                                crate::app::PopupMode::ExportFields => {
+                                    app.apply_export_fields();
+                                    if app.popup_mode == crate::app::PopupMode::None {
+                                        if let Err(e) = crate::app::state::UiState::capture(&app).save() {
+                                            app.notify(&format!("Could not save export fields: {}", e), crate::app::NotificationLevel::Error);
+                                        }
+                                    }
+                                }
+                                crate::app::PopupMode::RunCommand { .. } => {
+                                    app.stage_run_command();
+                                }
+                                crate::app::PopupMode::ConfirmRunCommand { agent_id, command } => {
+                                    let (agent_id, command) = (agent_id.clone(), command.clone());
+                                    spawn_run_command(&mut app, &tx, agent_id, command);
+                                }
+                                crate::app::PopupMode::CommandOutput => {
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                }
+                                crate::app::PopupMode::SetupBundle { import } => {
+                                    let import = *import;
+                                    let path = std::path::PathBuf::from(app.input_buffer.trim());
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                    if import {
+                                        import_setup(&mut app, &path, &config_path);
+                                    } else {
+                                        match crate::app::bundle::SetupBundle::capture(&app).save(&path) {
+                                            Ok(()) => app.notify(&format!("Setup exported to {}", path.display()), crate::app::NotificationLevel::Success),
+                                            Err(e) => app.notify(&format!("Exporting the setup failed: {}", e), crate::app::NotificationLevel::Error),
+                                        }
+                                    }
+                                }
+                                crate::app::PopupMode::NewRuleFile => {
+                                    match crate::app::rules::ruleset_filename(&app.input_buffer) {
+                                        Some(filename) if app.rule_files.iter().any(|f| f.filename == filename) => {
+                                            app.notify(&format!("{} already exists, edit it from the list", filename), crate::app::NotificationLevel::Warning);
+                                        }
+                                        Some(filename) => {
+                                            app.popup_mode = crate::app::PopupMode::None;
+                                            let kind = app.ruleset_kind;
+                                            match edit_externally(&mut terminal, &filename, kind.template()) {
+                                                Ok(edited) => app.set_rule_draft(kind, filename, kind.template(), edited),
+                                                Err(e) => app.notify(&format!("Editing {} failed: {}", filename, e), crate::app::NotificationLevel::Error),
+                                            }
+                                        }
+                                        None => app.notify("Enter a file name without directories", crate::app::NotificationLevel::Warning),
+                                    }
+                                }
+                                crate::app::PopupMode::ConfirmRuleUpload { kind, filename } => {
+                                    if let Some(api) = app.api.clone().filter(|_| app.rule_draft_error.is_none()) {
+                                        let tx = tx.clone();
+                                        let (kind, filename) = (*kind, filename.clone());
+                                        let content = app.rule_draft.clone();
+                                        app.popup_mode = crate::app::PopupMode::None;
+                                        app.notify(&format!("Uploading {}...", filename), crate::app::NotificationLevel::Info);
+                                        tokio::spawn(async move {
+                                            let res = api.put_ruleset_file(kind, &filename, content).await
+                                                .map(|_| format!("{} uploaded", filename))
+                                                .map_err(|e| format!("Uploading {} failed: {}", filename, crate::api::error::describe(&e)));
+                                            let uploaded = res.is_ok();
+                                            send_audited(&tx, &format!("{} file upload", kind.label().to_lowercase()), std::slice::from_ref(&filename), res).await;
+                                            if uploaded {
+                                                match api.validate_manager_config().await {
+                                                    Ok(errors) => { let _ = tx.send(crate::app::DataUpdate::RuleValidation { filename, errors }).await; },
+                                                    Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Configuration check failed: {}", e))).await; },
+                                                }
+                                            }
+                                            fetch_rule_files(&api, &tx, kind).await;
+                                        });
+                                    }
+                                }
+                                crate::app::PopupMode::ConfirmRuleDelete { kind, filename } => {
+                                    if let Some(api) = app.api.clone() {
+                                        let tx = tx.clone();
+                                        let (kind, filename) = (*kind, filename.clone());
+                                        tokio::spawn(async move {
+                                            let res = api.delete_ruleset_file(kind, &filename).await
+                                                .map(|_| format!("{} deleted", filename))
+                                                .map_err(|e| format!("Deleting {} failed: {}", filename, crate::api::error::describe(&e)));
+                                            send_audited(&tx, &format!("{} file delete", kind.label().to_lowercase()), &[filename], res).await;
+                                            fetch_rule_files(&api, &tx, kind).await;
+                                        });
+                                    }
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                }
+                                crate::app::PopupMode::ConfirmManagerRestart => {
+                                    if let Some(api) = app.api.clone() {
+                                        let tx = tx.clone();
+                                        tokio::spawn(async move {
+                                            let res = api.restart_manager().await
+                                                .map(|_| "Manager restart requested".to_string())
+                                                .map_err(|e| format!("Manager restart failed: {}", crate::api::error::describe(&e)));
+                                            send_audited(&tx, "manager restart", &[], res).await;
+                                        });
+                                    }
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                }
+                                crate::app::PopupMode::Logtest => {
+                                    if let (Some(api), false) = (app.api.clone(), app.logtest_event.trim().is_empty()) {
+                                        let tx = tx.clone();
+                                        let event = app.logtest_event.clone();
+                                        let format = app.logtest_format();
+                                        let location = app.logtest_location.clone();
+                                        let token = app.logtest_result.as_ref().and_then(|r| r.token.clone());
+                                        app.logtest_result = None;
+                                        tokio::spawn(async move {
+                                            match api.logtest(&event, format, &location, token.as_deref()).await {
+                                                Ok(result) => { let _ = tx.send(crate::app::DataUpdate::Logtest(result)).await; },
+                                                Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Logtest failed: {}", e))).await; },
+                                            }
+                                        });
+                                    }
+                                }
+                                crate::app::PopupMode::ConfirmCdbChange { filename, key, value, remove } => {
+                                    if let Some(api) = app.api.clone() {
+                                        let tx = tx.clone();
+                                        let (filename, key, value, remove) = (filename.clone(), key.clone(), value.clone(), *remove);
+                                        tokio::spawn(async move {
+                                            // Edit the current file, not our copy, so concurrent edits are kept
+                                            let res = match api.get_cdb_list_file(&filename).await {
+                                                Ok(content) => {
+                                                    let updated = if remove {
+                                                        crate::app::cdb::remove_entry(&content, &key)
+                                                            .ok_or_else(|| format!("{} is no longer in {}", key, filename))
+                                                    } else {
+                                                        crate::app::cdb::append_entry(&content, &key, &value)
+                                                            .ok_or_else(|| format!("{} is already in {}", key, filename))
+                                                    };
+                                                    let done = if remove { format!("Removed {} from {}", key, filename) } else { format!("Added {} to {}", key, filename) };
+                                                    match updated {
+                                                        Ok(updated) => api.put_cdb_list_file(&filename, updated).await
+                                                            .map(|_| done)
+                                                            .map_err(|e| format!("Updating {} failed: {}", filename, crate::api::error::describe(&e))),
+                                                        Err(e) => Err(e),
+                                                    }
+                                                }
+                                                Err(e) => Err(format!("Failed to read {}: {}", filename, e)),
+                                            };
+                                            let action = if remove { "cdb remove" } else { "cdb add" };
+                                            send_audited(&tx, action, &[format!("{}:{}", filename, key)], res).await;
+                                            fetch_cdb_lists(&api, &tx).await;
+                                        });
+                                    }
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                }
+                                crate::app::PopupMode::ConfirmGroupMembership { agent_id, add, remove } => {
+                                    if let Some(api) = app.api.clone() {
+                                        let tx = tx.clone();
+                                        let (agent_id, add, remove) = (agent_id.clone(), add.clone(), remove.clone());
+                                        app.notify(&format!("Updating groups of agent {}...", agent_id), crate::app::NotificationLevel::Info);
+                                        tokio::spawn(async move {
+                                            let targets = vec![agent_id.clone()];
+                                            // Only the changes that went through are undone
+                                            let (mut added, mut removed) = (Vec::new(), Vec::new());
+                                            for group in &add {
+                                                let res = api.assign_agents_to_group(group, &[agent_id.as_str()]).await
+                                                    .map(|_| format!("Agent {} added to {}", agent_id, group))
+                                                    .map_err(|e| format!("Adding to {} failed: {}", group, crate::api::error::describe(&e)));
+                                                if res.is_ok() {
+                                                    added.push(group.clone());
+                                                }
+                                                send_audited(&tx, &format!("group assign ({})", group), &targets, res).await;
+                                            }
+                                            for group in &remove {
+                                                let res = api.remove_agents_from_group(group, &[agent_id.as_str()]).await
+                                                    .map(|_| format!("Agent {} removed from {}", agent_id, group))
+                                                    .map_err(|e| format!("Removing from {} failed: {}", group, crate::api::error::describe(&e)));
+                                                if res.is_ok() {
+                                                    removed.push(group.clone());
+                                                }
+                                                send_audited(&tx, &format!("group remove ({})", group), &targets, res).await;
+                                            }
+                                            if let Some(undo) = crate::app::undo::GroupUndo::membership(&agent_id, &added, &removed) {
+                                                let message = format!("Groups of agent {} updated", agent_id);
+                                                let _ = tx.send(crate::app::DataUpdate::GroupUndo { undo, message }).await;
+                                            }
+                                            if let Ok(res) = api.list_agents(None, 0, 500).await {
+                                                let _ = tx.send(crate::app::DataUpdate::Agents(res.data.affected_items)).await;
+                                            }
+                                        });
+                                    }
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                }
+                                crate::app::PopupMode::ConfirmAgentAction { action, agent_ids } => {
+                                    let (action, agent_ids) = (*action, agent_ids.clone());
+                                    app.popup_mode = crate::app::PopupMode::None;
+                                    run_agent_action(&mut app, &tx, action, agent_ids);
+                                }
                                 _ => {}
                             }
                         } else if app.show_interval_popup {
@@ -1154,13 +2903,14 @@ async fn main() -> Result<()> {
                                     let active_view = app.active_view.clone();
                                     let agent_id = app.get_selected_agent().map(|a| a.id.clone());
                                     let interval = app.log_interval_mins;
+                                    let filter = app.log_filter.clone();
                                     
                                     tokio::spawn(async move {
                                         match active_view {
                                             ActiveView::SecurityEvents => {
-                                                if let Ok(res) = api.get_logs(None, interval, app.log_offset, app.log_limit, None).await {
-                                                    if let Some(hits) = res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                        let _ = tx.send(crate::app::DataUpdate::SecurityEvents(hits.clone())).await;
+                                                if let Ok(res) = api.get_logs(None, interval, app.log_offset, app.log_limit, Some(&filter)).await {
+                                                    if let Some(update) = security_events_update(&res) {
+                                                        let _ = tx.send(update).await;
                                                     }
                                                 }
                                             }
@@ -1179,6 +2929,9 @@ async fn main() -> Result<()> {
                                                             let _ = tx.send(crate::app::DataUpdate::AgentLogs(hits.clone())).await;
                                                         }
                                                     }
+                                                    if let Ok(connections) = api.get_agent_connections(&id, interval).await {
+                                                        let _ = tx.send(crate::app::DataUpdate::AgentConnections(connections)).await;
+                                                    }
                                                 }
                                             }
                                             _ => {}
@@ -1199,7 +2952,9 @@ async fn main() -> Result<()> {
                                         app.config_step = crate::app::ConfigStep::OsUrl;
                                     }
                                 }
-                                crate::app::ConfigStep::OsUrl => { app.config_step = crate::app::ConfigStep::Username; }
+                                crate::app::ConfigStep::OsUrl => { app.config_step = crate::app::ConfigStep::AlertsIndex; }
+                                crate::app::ConfigStep::AlertsIndex => { app.config_step = crate::app::ConfigStep::VulnIndex; }
+                                crate::app::ConfigStep::VulnIndex => { app.config_step = crate::app::ConfigStep::Username; }
                                 crate::app::ConfigStep::Username => { app.config_step = crate::app::ConfigStep::Password; }
                                 crate::app::ConfigStep::Password => { app.config_step = crate::app::ConfigStep::Confirm; }
                                 crate::app::ConfigStep::Confirm => {
@@ -1210,8 +2965,11 @@ async fn main() -> Result<()> {
                                         os_url: Some(app.config_os_url.clone()),
                                         os_username: Some(app.config_username.clone()),
                                         os_password: Some(app.config_username.clone()),
+                                        indices: app.wizard_indices(),
+                                        display: crate::models::DisplayConfig { tour_seen: Some(false), ..Default::default() },
+                                        ..Default::default()
                                     };
-                                    if let Ok(_) = ConfigManager::save(&config) {
+                                    if let Ok(_) = ConfigManager::save(&config_path, &config) {
                                         let api = WazuhApi::new(config);
                                         app.set_api(api);
                                         app.is_config_wizard_active = false;
@@ -1224,9 +2982,26 @@ async fn main() -> Result<()> {
                                             }
                                         }
                                         app.stop_loading();
+                                        app.start_tour();
                                     }
                                 }
                             }
+                        } else if app.active_view == ActiveView::Rules {
+                            let filename = app.selected_rule_file().map(|f| f.filename.clone());
+                            if let (Some(filename), Some(api)) = (filename, app.api.clone()) {
+                                let kind = app.ruleset_kind;
+                                if app.require_permission(kind.update_action(), "Ruleset editing") {
+                                    let tx = tx.clone();
+                                    tokio::spawn(async move {
+                                        match api.get_ruleset_file(kind, &filename).await {
+                                            Ok(content) => { let _ = tx.send(crate::app::DataUpdate::RuleFileContent { kind, filename, content }).await; },
+                                            Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load {}: {}", filename, e))).await; },
+                                        }
+                                    });
+                                }
+                            }
+                        } else if app.active_view == ActiveView::GroupManagement && app.group_files_focus {
+                             spawn_group_file(&mut app, &tx);
                         } else if app.active_view == ActiveView::GroupManagement {
                              if let Some(group) = app.get_selected_group() {
                                  let api = app.api.as_ref().unwrap().clone();
@@ -1236,55 +3011,32 @@ async fn main() -> Result<()> {
                                      if let Ok(res) = api.list_agents(Some(&group_name), 0, 500).await {
                                          let _ = tx.send(crate::app::DataUpdate::Agents(res.data.affected_items)).await;
                                      }
+                                     match api.get_group_files(&group_name).await {
+                                         Ok(res) => { let _ = tx.send(crate::app::DataUpdate::GroupFiles { group: group_name, files: res.data.affected_items }).await; },
+                                         Err(e) => { let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load group files: {}", e))).await; },
+                                     }
                                  });
                              }
+                        } else if app.active_view == ActiveView::Dashboard {
+                            // Inspect the highlighted recently-disconnected agent
+                            let agent_id = app.recently_disconnected().get(app.disconnected_index).map(|a| a.id.clone());
+                            if let Some(agent_id) = agent_id {
+                                if let Some(pos) = app.agents.iter().position(|a| a.id == agent_id) {
+                                    app.selected_agent_index = pos;
+                                    app.navigate(ActiveView::AgentInspector);
+                                    app.error_message = None;
+                                    spawn_inspector_load(&mut app, &tx, agent_id, false);
+                                }
+                            }
                         } else if app.active_view == ActiveView::AgentList {
-                            if let Some(agent) = app.get_selected_agent() {
+                            if app.refuse_profile_agent() {
+                                // Row from another profile, user already notified
+                            } else if let Some(agent) = app.get_selected_agent() {
                                 let agent_id = agent.id.clone();
-                                app.active_view = ActiveView::AgentInspector;
+                                app.navigate(ActiveView::AgentInspector);
                                 app.set_loading("Loading agent details...");
                                 app.error_message = None;
-                                if let Some(api) = &app.api {
-                                    let api = api.clone();
-                                    let tx = tx.clone();
-                                    let interval = app.log_interval_mins;
-                                    let config_component = app.agent_config_component.clone();
-                                    
-                                    tokio::spawn(async move {
-                                        if let Ok(hw_res) = api.get_hardware_info(&agent_id).await {
-                                            if let Some(hw) = hw_res.data.affected_items.into_iter().next() {
-                                                let _ = tx.send(crate::app::DataUpdate::AgentHardware(hw)).await;
-                                            }
-                                        }
-                                        if let Ok(proc_res) = api.get_processes(&agent_id).await {
-                                            let _ = tx.send(crate::app::DataUpdate::AgentProcesses(proc_res.data.affected_items)).await;
-                                        }
-                                        if let Ok(prog_res) = api.get_programs(&agent_id).await {
-                                            let _ = tx.send(crate::app::DataUpdate::AgentPrograms(prog_res.data.affected_items)).await;
-                                        }
-                                        match api.get_vulnerabilities(&agent_id).await {
-                                            Ok(vuln_res) => {
-                                                let _ = tx.send(crate::app::DataUpdate::AgentVulnerabilities(vuln_res.data.affected_items)).await;
-                                            }
-                                            Err(e) => {
-                                                let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load vulnerabilities: {}", e))).await;
-                                            }
-                                        }
-                                        if let Ok(logs_res) = api.get_logs(Some(&agent_id), interval, 0, 100, None).await {
-                                            if let Some(hits) = logs_res.get("hits").and_then(|h| h.get("hits")).and_then(|h| h.as_array()) {
-                                                let _ = tx.send(crate::app::DataUpdate::AgentLogs(hits.clone())).await;
-                                            }
-                                        }
-                                        match api.get_agent_config(&agent_id, &config_component).await {
-                                            Ok(config_res) => {
-                                                let _ = tx.send(crate::app::DataUpdate::AgentConfig(config_res)).await;
-                                            }
-                                            Err(e) => {
-                                                let _ = tx.send(crate::app::DataUpdate::Error(format!("Failed to load config: {}", e))).await;
-                                            }
-                                        }
-                                    });
-                                }
+                                spawn_inspector_load(&mut app, &tx, agent_id, false);
                                 app.stop_loading();
                             }
                         } else if app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Config {
@@ -1310,8 +3062,14 @@ async fn main() -> Result<()> {
                                     }
                                 });
                             }
+                        } else if app.active_view == ActiveView::AgentInspector && app.inspector_tab == crate::app::InspectorTab::Processes {
+                            if let Some(process) = app.get_selected_process() {
+                                app.popup_mode = crate::app::PopupMode::ProcessDetail { pid: process.pid.clone() };
+                            }
                         } else if app.active_view == ActiveView::AgentInspector || app.active_view == ActiveView::SecurityEvents {
-                            if app.selected_log.is_some() {
+                            if app.detail_is_searching {
+                                app.finish_detail_search();
+                            } else if app.selected_log.is_some() {
                                 app.show_log_json = !app.show_log_json;
                             } else {
                                 let log = match app.active_view {
@@ -1319,22 +3077,68 @@ async fn main() -> Result<()> {
                                         let idx = app.inspector_table_state.selected().unwrap_or(0);
                                         app.agent_logs.get(idx).cloned()
                                     }
-                                    ActiveView::SecurityEvents => {
-                                        let idx = app.table_state.selected().unwrap_or(0);
-                                        app.logs.get(idx).cloned()
-                                    }
+                                    ActiveView::SecurityEvents => app.selected_alert().cloned(),
                                     _ => None,
                                 };
 
                                 if let Some(l) = log {
-                                    app.selected_log = Some(l);
-                                    app.log_scroll_offset = 0; // Reset scroll for new log
+                                    app.open_log_detail(l);
                                 }
                             }
                         }
                     }
                     KeyCode::Down => {
-                         if app.selected_log.is_some() {
+                         if matches!(app.popup_mode, crate::app::PopupMode::Help) {
+                             app.scroll_help(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::SrcIpPivot { .. }) {
+                             if !app.pivot_events.is_empty() {
+                                 app.pivot_index = (app.pivot_index + 1).min(app.pivot_events.len() - 1);
+                             }
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::GroupFile { .. }) {
+                             app.group_file_scroll = app.group_file_scroll.saturating_add(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::GroupMembership { .. }) {
+                             if !app.groups.is_empty() {
+                                 app.membership_index = (app.membership_index + 1).min(app.groups.len() - 1);
+                             }
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::Hunts) {
+                             app.hunt_index = (app.hunt_index + 1).min(crate::app::hunts::HUNTS.len() - 1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::HuntResults { .. }) {
+                             let count = app.hunt_rows.as_ref().and_then(|r| r.as_ref().ok()).map(|rows| rows.len()).unwrap_or(0);
+                             app.hunt_row_index = (app.hunt_row_index + 1).min(count.saturating_sub(1));
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::DuplicateAgents) {
+                             let count = app.duplicate_rows().len();
+                             app.duplicate_index = (app.duplicate_index + 1).min(count.saturating_sub(1));
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::PendingActions) {
+                             app.pending_index = (app.pending_index + 1).min(app.pending_actions.actions.len().saturating_sub(1));
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::AgentNodes) {
+                             let count = crate::app::nodes::node_summaries(&app.agents).len();
+                             app.node_index = (app.node_index + 1).min(count.saturating_sub(1));
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::Activity) {
+                             if !app.audit_log.is_empty() {
+                                 app.activity_index = (app.activity_index + 1).min(app.audit_log.len() - 1);
+                             }
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::Notifications) {
+                             if !app.notification_history.is_empty() {
+                                 app.notification_index = (app.notification_index + 1).min(app.notification_history.len() - 1);
+                             }
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::RequestLog) {
+                             let count = app.api.as_ref().map(|a| a.request_log().len()).unwrap_or(0);
+                             app.request_log_index = (app.request_log_index + 1).min(count.saturating_sub(1));
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::IndexerHealth) {
+                             let count = app.indexer_health.as_ref().and_then(|h| h.as_ref().ok()).map(|h| h.indices.len()).unwrap_or(0);
+                             app.indexer_health_index = (app.indexer_health_index + 1).min(count.saturating_sub(1));
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::RunCommand { .. }) {
+                             let count = app.remote_commands_allowed().len();
+                             app.remote_command_index = (app.remote_command_index + 1).min(count.saturating_sub(1));
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::CommandOutput) {
+                             app.remote_output_scroll = app.remote_output_scroll.saturating_add(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::AlertContext) {
+                             if !app.alert_context.is_empty() {
+                                 app.alert_context_index = (app.alert_context_index + 1).min(app.alert_context.len() - 1);
+                             }
+                         } else if app.selected_log.is_some() && app.show_log_json {
+                             app.move_tree_cursor(1);
+                         } else if app.selected_log.is_some() {
                              // Scroll down in log detail view
                              app.log_scroll_offset = app.log_scroll_offset.saturating_add(1);
                          } else if matches!(app.popup_mode, crate::app::PopupMode::SeverityFilter) {
@@ -1362,6 +3166,11 @@ async fn main() -> Result<()> {
                                  }
                                  _ => {}
                              }
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::RoleAssignment { .. }) {
+                              let len = app.security_roles.len();
+                              if len > 0 {
+                                  app.role_selection_index = (app.role_selection_index + 1) % len;
+                              }
                          } else if matches!(app.popup_mode, crate::app::PopupMode::CommandPalette) {
                               let matches_len = app.get_command_palette_matches().len();
                               if matches_len > 0 {
@@ -1375,13 +3184,50 @@ async fn main() -> Result<()> {
                          } else if app.active_view == ActiveView::AgentInspector {
                               app.scroll_down(1);
                          } else if app.active_view == ActiveView::Dashboard {
-                              // No table selection in dashboard
+                              let len = app.recently_disconnected().len();
+                              if len > 0 {
+                                  app.disconnected_index = (app.disconnected_index + 1) % len;
+                              }
                          } else {
                               app.next_item();
                          }
                     }
                     KeyCode::Up => {
-                         if app.selected_log.is_some() {
+                         if matches!(app.popup_mode, crate::app::PopupMode::Help) {
+                             app.scroll_help(-1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::SrcIpPivot { .. }) {
+                             app.pivot_index = app.pivot_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::GroupFile { .. }) {
+                             app.group_file_scroll = app.group_file_scroll.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::GroupMembership { .. }) {
+                             app.membership_index = app.membership_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::Hunts) {
+                             app.hunt_index = app.hunt_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::HuntResults { .. }) {
+                             app.hunt_row_index = app.hunt_row_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::DuplicateAgents) {
+                             app.duplicate_index = app.duplicate_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::PendingActions) {
+                             app.pending_index = app.pending_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::AgentNodes) {
+                             app.node_index = app.node_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::Activity) {
+                             app.activity_index = app.activity_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::Notifications) {
+                             app.notification_index = app.notification_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::RequestLog) {
+                             app.request_log_index = app.request_log_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::IndexerHealth) {
+                             app.indexer_health_index = app.indexer_health_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::RunCommand { .. }) {
+                             app.remote_command_index = app.remote_command_index.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::CommandOutput) {
+                             app.remote_output_scroll = app.remote_output_scroll.saturating_sub(1);
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::AlertContext) {
+                             app.alert_context_index = app.alert_context_index.saturating_sub(1);
+                         } else if app.selected_log.is_some() && app.show_log_json {
+                             app.move_tree_cursor(-1);
+                         } else if app.selected_log.is_some() {
                              // Scroll up in log detail view
                              app.log_scroll_offset = app.log_scroll_offset.saturating_sub(1);
                          } else if matches!(app.popup_mode, crate::app::PopupMode::SeverityFilter) {
@@ -1417,6 +3263,11 @@ async fn main() -> Result<()> {
                                  }
                                  _ => {}
                              }
+                         } else if matches!(app.popup_mode, crate::app::PopupMode::RoleAssignment { .. }) {
+                              let len = app.security_roles.len();
+                              if len > 0 {
+                                  app.role_selection_index = if app.role_selection_index == 0 { len - 1 } else { app.role_selection_index - 1 };
+                              }
                          } else if matches!(app.popup_mode, crate::app::PopupMode::CommandPalette) {
                               let matches_len = app.get_command_palette_matches().len();
                               if matches_len > 0 {
@@ -1438,13 +3289,28 @@ async fn main() -> Result<()> {
                          } else if app.active_view == ActiveView::AgentInspector {
                               app.scroll_up(1);
                          } else if app.active_view == ActiveView::Dashboard {
-                              // No table selection in dashboard
+                              let len = app.recently_disconnected().len();
+                              if len > 0 {
+                                  app.disconnected_index = (app.disconnected_index + len - 1) % len;
+                              }
                          } else {
                               app.previous_item();
                          }
                     }
                     KeyCode::Left => {
-                        if matches!(app.popup_mode, crate::app::PopupMode::SeverityFilter) {
+                        if app.selected_log.is_some() && app.show_log_json {
+                            app.tree_collapse();
+                        } else if app.selected_log.is_none() && app.active_view == ActiveView::SecurityEvents && app.log_view_mode == crate::app::LogViewMode::Raw && !app.raw_wrap {
+                            app.scroll_raw(-1);
+                        } else if app.selected_log.is_none() && app.active_view == ActiveView::SecurityEvents && app.popup_mode == crate::app::PopupMode::None {
+                            app.expand_selected_flood(false);
+                        } else if app.active_view == ActiveView::GroupManagement && app.popup_mode == crate::app::PopupMode::None {
+                            app.group_files_focus = false;
+                        } else if app.active_view == ActiveView::CdbLists && app.popup_mode == crate::app::PopupMode::None {
+                            app.cdb_entries_focus = false;
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::Logtest) && app.logtest_field == 2 {
+                            app.cycle_logtest_format(false);
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::SeverityFilter) {
                             match app.filter_popup_tab {
                                 crate::app::FilterPopupTab::Severity => {
                                     if app.log_filter.mode == crate::app::SeverityFilterMode::Range {
@@ -1459,7 +3325,20 @@ async fn main() -> Result<()> {
                         }
                     }
                     KeyCode::Right => {
-                        if matches!(app.popup_mode, crate::app::PopupMode::SeverityFilter) {
+                        if app.selected_log.is_some() && app.show_log_json {
+                            app.tree_expand();
+                        } else if app.selected_log.is_none() && app.active_view == ActiveView::SecurityEvents && app.log_view_mode == crate::app::LogViewMode::Raw && !app.raw_wrap {
+                            app.scroll_raw(1);
+                        } else if app.selected_log.is_none() && app.active_view == ActiveView::SecurityEvents && app.popup_mode == crate::app::PopupMode::None {
+                            app.expand_selected_flood(true);
+                        } else if app.active_view == ActiveView::GroupManagement && app.popup_mode == crate::app::PopupMode::None {
+                            // Only the files of the group opened with Enter are listed
+                            app.group_files_focus = !app.group_files.is_empty();
+                        } else if app.active_view == ActiveView::CdbLists && app.popup_mode == crate::app::PopupMode::None {
+                            app.cdb_entries_focus = app.selected_cdb_list().map(|l| !l.items.is_empty()).unwrap_or(false);
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::Logtest) && app.logtest_field == 2 {
+                            app.cycle_logtest_format(true);
+                        } else if matches!(app.popup_mode, crate::app::PopupMode::SeverityFilter) {
                             match app.filter_popup_tab {
                                 crate::app::FilterPopupTab::Severity => {
                                     if app.log_filter.mode == crate::app::SeverityFilterMode::Range {
@@ -1478,11 +3357,34 @@ async fn main() -> Result<()> {
             }
         }
 
+        if app.live_search_ready(Instant::now()) {
+            spawn_live_search(&mut app, &tx);
+        }
+
         if last_tick.elapsed() >= tick_rate {
             app.clear_old_notifications();
+            if let Some(agent_id) = app.preview_agent_to_load() {
+                spawn_inspector_load(&mut app, &tx, agent_id, false);
+            }
+            if let Some(group) = app.group_posture_to_load() {
+                spawn_group_posture(&app, &tx, group);
+            }
+            for agent_id in app.fim_scans_to_poll() {
+                spawn_fim_poll(&app, &tx, agent_id);
+            }
+            if let Some(id) = app.remote_command_to_poll() {
+                spawn_remote_command_poll(&app, &tx, id);
+            }
+            if app.api.is_some() && app.heartbeat.due(Instant::now()) {
+                spawn_heartbeat(&app, &tx);
+            }
             if app.is_loading {
                 app.spinner_index = app.spinner_index.wrapping_add(1);
             }
+            #[cfg(feature = "metrics")]
+            if let Some(Ok(mut snapshot)) = metrics_state.as_ref().map(|m| m.lock()) {
+                *snapshot = crate::metrics::MetricsSnapshot::from_app(&app);
+            }
             last_tick = Instant::now();
         }
 
@@ -1491,13 +3393,11 @@ async fn main() -> Result<()> {
         }
     }
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen
-    )?;
-    terminal.show_cursor()?;
+    if app.api.is_some() {
+        if let Err(e) = crate::app::state::UiState::capture(&app).save() {
+            eprintln!("Failed to save UI state: {}", e);
+        }
+    }
 
     Ok(())
 }
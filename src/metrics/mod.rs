@@ -0,0 +1,87 @@
+use crate::api::LatencyStats;
+use crate::app::{App, ThreatStats};
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Values exported on each scrape, copied from the app state every tick.
+#[derive(Debug, Default, Clone)]
+pub struct MetricsSnapshot {
+    pub agents_by_status: Vec<(String, usize)>,
+    /// Indexer totals for the window; the alert series are left out until they arrive
+    pub alerts: Option<ThreatStats>,
+    pub window_mins: u32,
+    pub api_latency: LatencyStats,
+}
+
+pub type SharedMetrics = Arc<Mutex<MetricsSnapshot>>;
+
+impl MetricsSnapshot {
+    pub fn from_app(app: &App) -> Self {
+        MetricsSnapshot {
+            agents_by_status: crate::app::report::status_counts(&app.agents),
+            alerts: app.alert_totals.clone(),
+            window_mins: app.log_interval_mins,
+            api_latency: app.api.as_ref().map(|a| a.latency()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Prometheus text exposition format (version 0.0.4).
+pub fn render(s: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+
+    out.push_str("# HELP wazuh_tui_agents Registered agents by connection status.\n# TYPE wazuh_tui_agents gauge\n");
+    for (status, count) in &s.agents_by_status {
+        out.push_str(&format!("wazuh_tui_agents{{status=\"{}\"}} {}\n", status, count));
+    }
+
+    if let Some(alerts) = &s.alerts {
+        let severities = [
+            ("critical", alerts.critical),
+            ("high", alerts.high),
+            ("medium", alerts.medium),
+            ("low", alerts.low),
+        ];
+        out.push_str("# HELP wazuh_tui_alerts Alerts in the dashboard window by severity.\n# TYPE wazuh_tui_alerts gauge\n");
+        for (severity, count) in severities {
+            out.push_str(&format!("wazuh_tui_alerts{{severity=\"{}\"}} {}\n", severity, count));
+        }
+        out.push_str("# HELP wazuh_tui_alert_rate_per_minute Alerts per minute over the dashboard window by severity.\n# TYPE wazuh_tui_alert_rate_per_minute gauge\n");
+        let window = s.window_mins.max(1) as f64;
+        for (severity, count) in severities {
+            out.push_str(&format!("wazuh_tui_alert_rate_per_minute{{severity=\"{}\"}} {}\n", severity, count as f64 / window));
+        }
+    }
+    out.push_str("# HELP wazuh_tui_alert_window_minutes Length of the dashboard window.\n# TYPE wazuh_tui_alert_window_minutes gauge\n");
+    out.push_str(&format!("wazuh_tui_alert_window_minutes {}\n", s.window_mins));
+
+    out.push_str("# HELP wazuh_tui_api_request_duration_seconds Round-trip time of Wazuh API and indexer requests.\n# TYPE wazuh_tui_api_request_duration_seconds summary\n");
+    out.push_str(&format!("wazuh_tui_api_request_duration_seconds_sum {}\n", s.api_latency.total_secs));
+    out.push_str(&format!("wazuh_tui_api_request_duration_seconds_count {}\n", s.api_latency.count));
+    out.push_str("# HELP wazuh_tui_api_last_request_duration_seconds Round-trip time of the most recent request.\n# TYPE wazuh_tui_api_last_request_duration_seconds gauge\n");
+    out.push_str(&format!("wazuh_tui_api_last_request_duration_seconds {}\n", s.api_latency.last_secs));
+    out
+}
+
+/// Serves the current snapshot to every connection, whatever the request path.
+pub async fn serve(addr: &str, shared: SharedMetrics) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    loop {
+        let (mut socket, _) = listener.accept().await?;
+        let body = render(&shared.lock().map(|s| s.clone()).unwrap_or_default());
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,27 @@
+use crate::api::LatencyStats;
+use crate::app::ThreatStats;
+use crate::metrics::{render, MetricsSnapshot};
+
+#[test]
+fn test_render_prometheus_text() {
+    let snapshot = MetricsSnapshot {
+        agents_by_status: vec![("active".to_string(), 3), ("disconnected".to_string(), 1)],
+        alerts: Some(ThreatStats { critical: 2, high: 10, medium: 0, low: 30 }),
+        window_mins: 10,
+        api_latency: LatencyStats { count: 4, total_secs: 1.5, last_secs: 0.25 },
+    };
+    let text = render(&snapshot);
+
+    assert!(text.contains("wazuh_tui_agents{status=\"active\"} 3\n"));
+    assert!(text.contains("wazuh_tui_alerts{severity=\"critical\"} 2\n"));
+    assert!(text.contains("wazuh_tui_alert_rate_per_minute{severity=\"high\"} 1\n"));
+    assert!(text.contains("wazuh_tui_api_request_duration_seconds_count 4\n"));
+    assert!(text.contains("# TYPE wazuh_tui_api_request_duration_seconds summary\n"));
+}
+
+#[test]
+fn test_alert_series_wait_for_indexer_totals() {
+    let text = render(&MetricsSnapshot { window_mins: 10, ..Default::default() });
+    assert!(!text.contains("wazuh_tui_alerts{"));
+    assert!(text.contains("wazuh_tui_alert_window_minutes 10\n"));
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Config {
     pub url: String,
     pub username: String,
@@ -8,6 +8,169 @@ pub struct Config {
     pub os_url: Option<String>,
     pub os_username: Option<String>,
     pub os_password: Option<String>,
+    /// JSON authorization context for `run_as` logins, matched by the manager's role rules
+    /// (e.g. LDAP or SSO groups); the API user needs `allow_run_as`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_context: Option<String>,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub indices: IndicesConfig,
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub remote_commands: RemoteCommandsConfig,
+    #[serde(default)]
+    pub export: ExportConfig,
+    #[serde(default)]
+    pub severity: SeverityConfig,
+    #[serde(default)]
+    pub confirm: ConfirmConfig,
+    /// Extra keys standing in for built-in ones, e.g. `"F5" = "r"`
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub keys: std::collections::HashMap<String, String>,
+    /// Other managers listed next to this one in the all-profiles agent list
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<ProfileConfig>,
+}
+
+/// An independent Wazuh manager (another site), queried read-only for its agents.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProfileConfig {
+    /// Shown in the Manager column
+    pub name: String,
+    pub url: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl ProfileConfig {
+    /// API settings for this manager; network limits are shared with the main config.
+    pub fn to_config(&self, base: &Config) -> Config {
+        Config {
+            url: self.url.clone(),
+            username: self.username.clone(),
+            password: self.password.clone(),
+            network: base.network.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+pub const DEFAULT_ALERTS_INDEX: &str = "wazuh-alerts-*";
+pub const DEFAULT_VULNERABILITIES_INDEX: &str = "wazuh-states-vulnerabilities*";
+
+/// OpenSearch index patterns, for custom index names or cross-cluster search prefixes
+/// such as `site-a:wazuh-alerts-*`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IndicesConfig {
+    /// Default `wazuh-alerts-*`
+    pub alerts: Option<String>,
+    /// Default `wazuh-states-vulnerabilities*`
+    pub vulnerabilities: Option<String>,
+}
+
+impl IndicesConfig {
+    pub fn alerts(&self) -> &str {
+        self.alerts.as_deref().map(str::trim).filter(|i| !i.is_empty()).unwrap_or(DEFAULT_ALERTS_INDEX)
+    }
+
+    pub fn vulnerabilities(&self) -> &str {
+        self.vulnerabilities.as_deref().map(str::trim).filter(|i| !i.is_empty()).unwrap_or(DEFAULT_VULNERABILITIES_INDEX)
+    }
+
+    /// Patterns of the Indexer Health popup: all Wazuh alert and state indices by default,
+    /// otherwise the configured ones.
+    pub fn health(&self) -> String {
+        if self.alerts.is_none() && self.vulnerabilities.is_none() {
+            return "wazuh-alerts-*,wazuh-states-*".to_string();
+        }
+        format!("{},{}", self.alerts(), self.vulnerabilities())
+    }
+}
+
+/// Timeouts and retry policy for API and indexer requests.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NetworkConfig {
+    pub api_timeout_secs: Option<u64>,
+    pub indexer_timeout_secs: Option<u64>,
+    pub max_retries: Option<u32>,
+    pub retry_base_delay_ms: Option<u64>,
+    /// Requests sent at the same time; further ones wait their turn
+    pub max_concurrent_requests: Option<usize>,
+    /// Overrides keyed by path fragment, e.g. `"/syscollector" = 30`
+    #[serde(default)]
+    pub endpoint_timeouts: std::collections::HashMap<String, u64>,
+}
+
+/// Housekeeping thresholds for the maintenance commands.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MaintenanceConfig {
+    /// Minimum age in days before a never-connected or pending agent is offered for cleanup
+    pub stale_agent_days: Option<i64>,
+    /// Minutes without a keep-alive before an "active" agent is flagged on the dashboard
+    pub stale_keepalive_minutes: Option<i64>,
+    /// Hours back the dashboard's recently disconnected panel looks
+    pub disconnected_window_hours: Option<i64>,
+}
+
+/// How timestamps are presented.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DisplayConfig {
+    /// `local` (default), `utc` or a fixed offset like `+02:00`
+    pub timezone: Option<String>,
+    /// Start with relative times ("2m ago") in the alert and agent tables
+    pub relative_time: Option<bool>,
+    /// `nerd` (default), `ascii` for terminals without a Nerd Font, or `none`
+    pub icons: Option<String>,
+    /// Written as false by the setup wizard; the onboarding tour shows at startup until it is true
+    pub tour_seen: Option<bool>,
+}
+
+/// Which operations ask for confirmation first.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ConfirmConfig {
+    /// Upgrades, restarts and FIM scans on several agents (default true); deletions always ask
+    pub agent_actions: Option<bool>,
+}
+
+/// Lowest rule level of the critical, high and medium alert bands (defaults 15, 12 and 7).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SeverityConfig {
+    pub critical: Option<u64>,
+    pub high: Option<u64>,
+    pub medium: Option<u64>,
+}
+
+/// Where exports are written and limits for exporting every alert that matches the filter.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ExportConfig {
+    /// Most alerts a full export writes (default 10000)
+    pub max_alerts: Option<u32>,
+    /// Directory for export files (default: the working directory); `~` is the home directory
+    pub dir: Option<String>,
+    /// File name template with `{view}`, `{agent}`, `{from}` and `{to}`; the extension is appended
+    pub filename: Option<String>,
+    /// `gzip` or `zip` to compress alert exports (default none; `Z` switches it at runtime)
+    pub compress: Option<String>,
+}
+
+/// Commands offered by "Run command" (active response, see `contrib/active-response`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RemoteCommandsConfig {
+    /// Exact command lines; must match the list in the agent-side script
+    pub allowed: Option<Vec<String>>,
+}
+
+/// Prometheus exporter settings (only used when built with the `metrics` feature).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MetricsConfig {
+    /// Address to serve metrics on, e.g. `127.0.0.1:9185`; disabled when unset
+    pub listen: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,7 +191,7 @@ pub struct WazuhOS {
     pub arch: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct WazuhAgent {
     pub id: String,
     pub name: String,
@@ -43,6 +206,91 @@ pub struct WazuhAgent {
     pub last_keep_alive: Option<String>,
     pub os: Option<WazuhOS>,
     pub manager: Option<String>,
+    /// Profile the agent was loaded from in the all-profiles list; `None` for this connection's manager
+    #[serde(skip)]
+    pub profile: Option<String>,
+}
+
+/// Agent id of the manager itself; it is listed with the agents but has its own endpoints.
+pub const MANAGER_AGENT_ID: &str = "000";
+
+impl WazuhAgent {
+    /// Agent 000: it cannot be restarted, upgraded, deleted or grouped through the agent endpoints.
+    pub fn is_manager(&self) -> bool {
+        self.id == MANAGER_AGENT_ID
+    }
+
+    pub fn last_keep_alive_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.last_keep_alive.as_deref().and_then(parse_timestamp)
+    }
+}
+
+/// Parses Wazuh timestamps, which come either as RFC3339 or without an offset (UTC).
+pub fn parse_timestamp(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .or_else(|_| {
+            // Wazuh's own `timestamp` field: 2024-05-01T10:00:00.000+0000
+            chrono::DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z").map(|dt| dt.with_timezone(&chrono::Utc))
+        })
+        .or_else(|_| {
+            chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
+                .map(|ndt| chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(ndt, chrono::Utc))
+        })
+        .ok()
+}
+
+/// A line of the manager's `ossec.log`, from `GET /manager/logs`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ManagerLogEntry {
+    pub timestamp: String,
+    pub tag: String,
+    pub level: String,
+    pub description: String,
+}
+
+/// What the inspector shows for agent 000 in place of agent-side status.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ManagerStatus {
+    /// Daemon name and state (`running`, `stopped`), as `GET /manager/status` reports them
+    pub daemons: Vec<(String, String)>,
+    /// Newest first
+    pub logs: Vec<ManagerLogEntry>,
+}
+
+/// Hours of alert history in the inspector summary sparkline.
+pub const ALERT_HOURS: u32 = 24;
+
+/// An agent's alert counts per hour, oldest first; quiet hours are included with 0.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HourlyAlerts {
+    pub hours: Vec<(chrono::DateTime<chrono::Utc>, u64)>,
+}
+
+impl HourlyAlerts {
+    pub fn counts(&self) -> Vec<u64> {
+        self.hours.iter().map(|(_, n)| *n).collect()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.hours.iter().map(|(_, n)| n).sum()
+    }
+
+    /// The busiest hour; the latest one on a tie.
+    pub fn peak(&self) -> Option<(chrono::DateTime<chrono::Utc>, u64)> {
+        self.hours.iter().copied().filter(|(_, n)| *n > 0).max_by_key(|(t, n)| (*n, *t))
+    }
+}
+
+/// A distinct source → destination:port seen in an agent's alerts (`data.srcip`,
+/// `data.dstip`, `data.dstport`); any of the three may be absent from the alerts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkConnection {
+    pub srcip: Option<String>,
+    pub dstip: Option<String>,
+    pub dstport: Option<String>,
+    pub count: u64,
+    pub last_seen: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -56,12 +304,97 @@ pub struct WazuhAgentsResponse {
     pub data: WazuhAgentsData,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WazuhOutdatedAgent {
+    pub id: String,
+    pub name: String,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhOutdatedAgentsData {
+    pub affected_items: Vec<WazuhOutdatedAgent>,
+    pub total_affected_items: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhOutdatedAgentsResponse {
+    pub data: WazuhOutdatedAgentsData,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WazuhGroup {
     pub name: String,
     pub count: Option<u32>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WazuhGroupFile {
+    pub filename: String,
+    pub hash: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhGroupFilesData {
+    pub affected_items: Vec<WazuhGroupFile>,
+    pub total_affected_items: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhGroupFilesResponse {
+    pub data: WazuhGroupFilesData,
+}
+
+/// A CDB list file (`etc/lists/...`) with the entries the manager parsed from it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WazuhCdbList {
+    pub filename: String,
+    #[serde(default)]
+    pub relative_dirname: String,
+    #[serde(default)]
+    pub items: Vec<WazuhCdbEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WazuhCdbEntry {
+    pub key: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhCdbListsData {
+    pub affected_items: Vec<WazuhCdbList>,
+    pub total_affected_items: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhCdbListsResponse {
+    pub data: WazuhCdbListsData,
+}
+
+/// A rule or decoder file, from `/rules/files` or `/decoders/files`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WazuhRuleFile {
+    pub filename: String,
+    #[serde(default)]
+    pub relative_dirname: String,
+    /// `enabled` or `disabled` in ossec.conf
+    #[serde(default)]
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhRuleFilesData {
+    pub affected_items: Vec<WazuhRuleFile>,
+    pub total_affected_items: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhRuleFilesResponse {
+    pub data: WazuhRuleFilesData,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WazuhGroupsData {
     pub affected_items: Vec<WazuhGroup>,
@@ -120,6 +453,30 @@ pub struct WazuhProcessItem {
     pub pid: String,
     pub state: Option<String>,
     pub agent_id: String,
+    pub ppid: Option<u64>,
+    pub euser: Option<String>,
+    pub vm_size: Option<u64>, // KiB
+    pub utime: Option<u64>,   // clock ticks
+    pub stime: Option<u64>,   // clock ticks
+}
+
+impl WazuhProcessItem {
+    /// Total CPU time (user + system) in clock ticks, if syscollector reported either.
+    pub fn cpu_time(&self) -> Option<u64> {
+        match (self.utime, self.stime) {
+            (None, None) => None,
+            (u, s) => Some(u.unwrap_or(0) + s.unwrap_or(0)),
+        }
+    }
+
+    /// Command an operator would run on the endpoint to terminate this process.
+    pub fn kill_command(&self, platform: Option<&str>) -> String {
+        if platform.map(|p| p.eq_ignore_ascii_case("windows")).unwrap_or(false) {
+            format!("taskkill /PID {} /F", self.pid)
+        } else {
+            format!("kill -9 {}", self.pid)
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -153,6 +510,96 @@ pub struct WazuhProgramsResponse {
     pub data: WazuhProgramsData,
 }
 
+/// Outcome of running one event through `PUT /logtest`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogtestResult {
+    /// Session token; reusing it keeps state for multi-event rules
+    pub token: Option<String>,
+    pub alert: bool,
+    pub decoder: Option<String>,
+    pub parent_decoder: Option<String>,
+    pub program_name: Option<String>,
+    pub rule_id: Option<String>,
+    pub rule_level: Option<u64>,
+    pub rule_description: Option<String>,
+    /// Fields extracted by the decoder as `name: value`
+    pub fields: Vec<(String, String)>,
+    pub messages: Vec<String>,
+}
+
+/// `GET _cluster/health` of the indexer.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IndexerClusterHealth {
+    pub cluster_name: String,
+    /// green, yellow or red
+    pub status: String,
+    #[serde(default)]
+    pub number_of_nodes: u32,
+    #[serde(default)]
+    pub active_shards: u32,
+    #[serde(default)]
+    pub unassigned_shards: u32,
+}
+
+/// One row of `_cat/indices?format=json&bytes=b`; `_cat` returns numbers as strings.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IndexerIndex {
+    pub index: String,
+    pub health: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "docs.count")]
+    pub docs_count: Option<String>,
+    #[serde(rename = "store.size")]
+    pub store_size: Option<String>,
+}
+
+/// One node of `_cat/allocation?format=json&bytes=b`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IndexerAllocation {
+    pub node: String,
+    #[serde(rename = "disk.percent")]
+    pub disk_percent: Option<String>,
+    #[serde(rename = "disk.used")]
+    pub disk_used: Option<String>,
+    #[serde(rename = "disk.avail")]
+    pub disk_avail: Option<String>,
+}
+
+impl IndexerAllocation {
+    pub fn disk_percent(&self) -> Option<u32> {
+        self.disk_percent.as_deref().and_then(|p| p.parse().ok())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IndexerHealth {
+    pub cluster: IndexerClusterHealth,
+    /// wazuh-alerts-* and wazuh-states-* indices, newest name first
+    pub indices: Vec<IndexerIndex>,
+    pub disks: Vec<IndexerAllocation>,
+}
+
+/// CVE counts of one agent by severity, from the vulnerability states index.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AgentVulnCounts {
+    pub critical: u32,
+    pub high: u32,
+    pub medium: u32,
+    pub low: u32,
+}
+
+impl AgentVulnCounts {
+    pub fn count(&self, severity: &str) -> u32 {
+        match severity.to_lowercase().as_str() {
+            "critical" => self.critical,
+            "high" => self.high,
+            "medium" => self.medium,
+            "low" => self.low,
+            _ => 0,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AgentSummary {
     pub total: u32,
@@ -180,9 +627,77 @@ pub struct WazuhVulnerabilityItem {
     pub name: Option<String>,
     #[serde(default)]
     pub version: Option<String>,
+    /// CVSS base score, when the indexer provides one
+    #[serde(default)]
+    pub cvss: Option<f64>,
 }
 
+/// One rootcheck (policy monitoring) finding from `GET /rootcheck/{agent_id}`.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WazuhRootcheckItem {
+    pub log: String,
+    /// `outstanding` or `solved`
+    pub status: Option<String>,
+    pub date_first: Option<String>,
+    pub date_last: Option<String>,
+    pub pci_dss: Option<String>,
+    pub cis: Option<String>,
+}
+
+impl WazuhRootcheckItem {
+    /// Rough kind of finding, from the wording rootcheck uses in its messages.
+    pub fn category(&self) -> &'static str {
+        let log = self.log.to_lowercase();
+        if log.contains("trojan") || log.contains("rootkit") {
+            "Trojan"
+        } else if log.contains("process") {
+            "Process"
+        } else if log.contains("port") {
+            "Port"
+        } else if log.contains("file") || log.contains("dir") {
+            "File"
+        } else if log.contains("system audit") || self.cis.is_some() {
+            "Policy"
+        } else {
+            "Event"
+        }
+    }
+
+    pub fn is_outstanding(&self) -> bool {
+        self.status.as_deref().map(|s| s != "solved").unwrap_or(true)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhRootcheckData {
+    pub affected_items: Vec<WazuhRootcheckItem>,
+    pub total_affected_items: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhRootcheckResponse {
+    pub data: WazuhRootcheckData,
+}
+
+/// `GET /rootcheck/{agent_id}/last_scan` and `GET /syscheck/{agent_id}/last_scan`; `end` is unset
+/// while a scan is running.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WazuhLastScan {
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhLastScanData {
+    pub affected_items: Vec<WazuhLastScan>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhLastScanResponse {
+    pub data: WazuhLastScanData,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct VulnerabilitySummary {
     pub critical: u32,
     pub high: u32,
@@ -276,3 +791,66 @@ pub struct OSVulnerabilityHits {
 pub struct OSVulnerabilityResponse {
     pub hits: OSVulnerabilityHits,
 }
+
+// Wazuh RBAC (security) structures
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WazuhUser {
+    pub id: u32,
+    pub username: String,
+    #[serde(default)]
+    pub allow_run_as: bool,
+    #[serde(default)]
+    pub roles: Vec<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhUsersData {
+    pub affected_items: Vec<WazuhUser>,
+    pub total_affected_items: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhUsersResponse {
+    pub data: WazuhUsersData,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WazuhRole {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub policies: Vec<u32>,
+    #[serde(default)]
+    pub users: Vec<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhRolesData {
+    pub affected_items: Vec<WazuhRole>,
+    pub total_affected_items: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhRolesResponse {
+    pub data: WazuhRolesData,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WazuhPolicy {
+    pub id: u32,
+    pub name: String,
+    pub policy: Option<serde_json::Value>,
+    #[serde(default)]
+    pub roles: Vec<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhPoliciesData {
+    pub affected_items: Vec<WazuhPolicy>,
+    pub total_affected_items: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WazuhPoliciesResponse {
+    pub data: WazuhPoliciesData,
+}
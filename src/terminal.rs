@@ -0,0 +1,70 @@
+//! Terminal setup and teardown. The guard and the panic hook both restore the
+//! terminal, so neither an error return nor a crash leaves the shell in raw mode.
+
+use crossterm::{
+    cursor::Show,
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io;
+use std::path::PathBuf;
+
+/// Raw mode and the alternate screen for as long as it lives.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn enter() -> io::Result<Self> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// Best effort: leaves the alternate screen and raw mode even if either step fails.
+pub fn restore() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+}
+
+/// `crash-<timestamp>.log` next to `config.toml`.
+pub fn crash_report_path() -> PathBuf {
+    let name = format!("crash-{}.log", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+    crate::config::ConfigManager::get_config_path().with_file_name(name)
+}
+
+/// Installs a panic hook that writes a crash report. A panic on the UI thread also
+/// restores the terminal before the message is printed; panics inside background
+/// tasks are caught by tokio, so the TUI keeps running and only the report is written.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let thread = std::thread::current();
+        let thread_name = thread.name().unwrap_or("unnamed");
+        let message = info.payload().downcast_ref::<&str>().map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        let location = info.location().map(|l| l.to_string()).unwrap_or_else(|| "unknown location".to_string());
+        let backtrace = std::backtrace::Backtrace::force_capture();
+
+        let report = format!(
+            "wazuh-rust-tui {} crashed at {}\nthread: {}\nlocation: {}\nmessage: {}\n\nbacktrace:\n{}\n",
+            env!("CARGO_PKG_VERSION"), chrono::Local::now().to_rfc3339(), thread_name, location, message, backtrace
+        );
+        let path = crash_report_path();
+        let written = std::fs::write(&path, report).is_ok();
+
+        if thread_name == "main" {
+            restore();
+            default_hook(info);
+            if written {
+                eprintln!("Crash report written to {}", path.display());
+            }
+        }
+    }));
+}
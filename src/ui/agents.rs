@@ -2,36 +2,71 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     widgets::{Block, Borders, Paragraph, Row, Table, Cell, Tabs},
+    text::{Line, Span},
     Frame,
 };
-use crate::app::{App, SortColumn, SortOrder, InspectorTab};
+use crate::app::{App, SortColumn, SortOrder, InspectorTab, ProcessSortColumn, VulnSortColumn};
 use crate::ui::theme::*;
-use crate::ui::common::{filter_matches, format_last_keep_alive, centered_rect};
+use crate::ui::icons::{Icon, IconSet};
+use crate::ui::common::{filter_matches, format_table_time, format_kib, centered_rect, table_window, is_narrow, fit_columns, pick_columns, paging_label};
 use crate::ui::json::{colorize_json};
 
+/// Minimum widths of the agent list columns: ID, name, IP, status, OS, keep-alive, health, tags.
+const AGENT_COLUMN_WIDTHS: [u16; 9] = [8, 20, 16, 15, 30, 18, 10, 16, 16];
+/// Which agent list columns survive on narrow terminals first (lower is kept longer).
+const AGENT_COLUMN_PRIORITY: [u8; 9] = [1, 0, 4, 2, 6, 5, 3, 7, 8];
+/// The Manager column, only shown in the all-profiles list.
+const MANAGER_COLUMN: usize = 8;
+
+fn health_color(score: u8) -> Color {
+    match score {
+        80..=100 => GREEN,
+        50..=79 => YELLOW,
+        _ => RED,
+    }
+}
+
 pub fn draw_agent_list(f: &mut Frame, app: &mut App, area: Rect) {
+    let area = if app.agent_preview {
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(area);
+        draw_agent_preview(f, app, panes[1]);
+        panes[0]
+    } else {
+        area
+    };
+
     let mut filtered_agents: Vec<_> = if !app.agent_filter.raw_query.is_empty() {
         app.agents.iter()
-            .filter(|a| app.agent_filter.matches(a))
+            .filter(|a| app.agent_filter.matches_with_tags(a, app.agent_labels.tags(&a.id)))
             .collect()
     } else {
         app.agents.iter().collect()
     };
 
-    if let Some(_severity) = &app.severity_filter {
-        // Note: In a real app we'd need the agent vulnerability info here.
-        // For now we just filter based on a hypothetical property or keep all if not available.
-        // But let's show we are filtering.
-        filtered_agents.retain(|_a| {
-            // Ideally we check if agent has vulnerabilities of this severity
-            true 
-        });
+    if app.show_outdated_only {
+        filtered_agents.retain(|a| app.outdated_agent_ids.contains(&a.id));
     }
 
+    if app.severity_filter.is_some() {
+        filtered_agents.retain(|a| app.matches_severity_filter(&a.id));
+    }
+
+    let column_count = if app.all_profiles { AGENT_COLUMN_WIDTHS.len() } else { MANAGER_COLUMN };
+    let columns: Vec<usize> = if is_narrow(area) {
+        // Borders and the highlight symbol take four cells
+        fit_columns(&AGENT_COLUMN_WIDTHS[..column_count], &AGENT_COLUMN_PRIORITY[..column_count], area.width.saturating_sub(4))
+    } else {
+        (0..column_count).collect()
+    };
+
     let get_header = |name: &str, col: SortColumn| {
         let mut s = name.to_string();
         if app.sort_column == col {
-            s.push_str(if app.sort_order == SortOrder::Asc { " 󰁞" } else { " 󰁆" });
+            s.push(' ');
+            s.push_str(app.icons.sort_arrow(app.sort_order == SortOrder::Asc));
         }
         Cell::from(s).style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD)) // One Dark Blue
     };
@@ -43,17 +78,23 @@ pub fn draw_agent_list(f: &mut Frame, app: &mut App, area: Rect) {
         get_header(" STATUS ", SortColumn::Status),
         get_header(" OPERATING SYSTEM ", SortColumn::Os),
         get_header(" LAST KEEP ALIVE ", SortColumn::LastKeepAlive),
+        // `~` while the fleet-wide alert and CVE counts are missing
+        get_header(if app.health_scores_partial() { " HEALTH~ " } else { " HEALTH " }, SortColumn::Health),
+        Cell::from(" TAGS ").style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
+        Cell::from(" MANAGER ").style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
     ];
     
-    let header = Row::new(header_cells)
+    let header = Row::new(pick_columns(header_cells, &columns))
         .style(Style::default().bg(BG)) // One Dark Background
         .height(1);
 
-    let rows = filtered_agents.iter().map(|a| {
+    let health = app.health_scores();
+    let window = table_window(filtered_agents.len(), app.table_state.selected(), area);
+    let rows = filtered_agents[window.start..window.end].iter().map(|a| {
         let (status_icon, base_color) = match a.status.as_str() {
-            "active" => ("󰄬 ", GREEN),      // One Dark Green
-            "disconnected" => ("󰅖 ", RED), // One Dark Red
-            _ => ("󰒲 ", FG),             // One Dark Gray
+            "active" => (app.icons.pad(Icon::Check), GREEN),      // One Dark Green
+            "disconnected" => (app.icons.pad(Icon::Cross), RED), // One Dark Red
+            _ => (app.icons.pad(Icon::Pending), FG),             // One Dark Gray
         };
 
         let os_info = match &a.os {
@@ -70,44 +111,289 @@ pub fn draw_agent_list(f: &mut Frame, app: &mut App, area: Rect) {
         };
 
         let is_selected = app.selected_agents.contains(&a.id);
-        let selection_prefix = if is_selected { "󰄬 " } else { "  " };
+        let selection_prefix = if is_selected { app.icons.pad(Icon::Selected) } else { "  " };
 
-        Row::new(vec![
+        Row::new(pick_columns(vec![
             Cell::from(format!("{} {}", selection_prefix, a.id)),
-            Cell::from(a.name.clone()),
+            if a.is_manager() {
+                Cell::from(Line::from(vec![
+                    Span::raw(format!("{} ", a.name)),
+                    Span::styled(" manager ", Style::default().fg(BG).bg(PURPLE)),
+                ]))
+            } else {
+                Cell::from(a.name.clone())
+            },
             Cell::from(a.ip.clone().unwrap_or_else(|| "N/A".to_string())),
             Cell::from(format!("{}{}", status_icon, a.status)),
             Cell::from(os_info),
-            Cell::from(format_last_keep_alive(&a.last_keep_alive)),
-        ]).style(Style::default().fg(base_color)).height(1)
+            Cell::from(a.last_keep_alive.as_deref().map(|t| format_table_time(app, t)).unwrap_or_else(|| "Never".to_string())),
+            health.get(&a.id).map(|h| Cell::from(format!("{:>3}", h)).style(Style::default().fg(health_color(*h)))).unwrap_or_else(|| Cell::from("")),
+            Cell::from(app.agent_labels.tags(&a.id).join(", ")).style(Style::default().fg(CYAN)),
+            Cell::from(app.agent_manager_label(a).to_string()).style(Style::default().fg(if a.profile.is_some() { PURPLE } else { FG })),
+        ], &columns)).style(Style::default().fg(base_color)).height(1)
     });
 
-    let table = Table::new(rows, [
+    let widths = pick_columns(vec![
             Constraint::Length(8),
             Constraint::Min(20),
             Constraint::Length(16),
             Constraint::Length(15),
             Constraint::Min(30),
             Constraint::Length(18),
-        ])
+            Constraint::Length(10),
+            Constraint::Min(16),
+            Constraint::Length(16),
+        ], &columns);
+    let table = Table::new(rows, widths)
         .header(header)
         .block(Block::default()
             .borders(Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(Style::default().fg(DARK_GRAY)) // Subtle border
-            .title(format!(" 󰒋 Agents List ({}){} ", 
+            .title(format!(" {}Agents List ({}){}{}{} ",
+                app.icons.pad(Icon::Agent),
                 filtered_agents.len(),
-                app.severity_filter.as_ref().map(|s| format!(" | Filter: {} ", s.to_uppercase())).unwrap_or_default()
+                app.severity_filter.as_ref().map(|s| format!(" | Filter: {} CVEs ", s.to_uppercase())).unwrap_or_default(),
+                if app.show_outdated_only { format!(" | {}Outdated only", app.icons.pad(Icon::Outdated)) } else { String::new() },
+                profiles_label(app),
             )))
         .highlight_style(Style::default()
             .bg(SELECTION_BG) // Selection background (One Dark)
             .add_modifier(Modifier::BOLD))
-        .highlight_symbol("󰁔 ");
+        .highlight_symbol(app.icons.pad(Icon::Pointer));
 
-    let mut state = app.table_state.clone();
+    let mut state = window.state;
     f.render_stateful_widget(table, area, &mut state);
 }
 
+/// Title suffix of the all-profiles list; profiles that failed to load are named.
+fn profiles_label(app: &App) -> String {
+    if !app.all_profiles {
+        return String::new();
+    }
+    let sites = app.profile_apis.len() + 1;
+    if app.profile_errors.is_empty() {
+        format!(" | {}{} managers", app.icons.pad(Icon::Sites), sites)
+    } else {
+        let failed: Vec<&str> = app.profile_errors.iter().map(|(name, _)| name.as_str()).collect();
+        format!(" | {}{} managers, unreachable: {}", app.icons.pad(Icon::Sites), sites, failed.join(", "))
+    }
+}
+
+/// Right pane of the split agent list: the Summary tab of the highlighted agent.
+fn draw_agent_preview(f: &mut Frame, app: &App, area: Rect) {
+    let Some(agent) = app.get_selected_agent() else { return };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    let health = app.health_scores().get(&agent.id).copied().unwrap_or(0);
+    let approx = if app.health_scores_partial() { "~" } else { "" };
+    let header = Paragraph::new(Line::from(vec![
+        Span::raw(format!(" {} ({}) | {} ", agent.name, agent.id, agent.status)),
+        Span::styled(format!(" {}Health {}{} ", app.icons.pad(Icon::Health), approx, health), Style::default().fg(BG).bg(health_color(health)).add_modifier(Modifier::BOLD)),
+    ])).block(Block::default()
+        .borders(Borders::ALL)
+        .title(" Preview ")
+        .border_style(Style::default().fg(DARK_GRAY)));
+    f.render_widget(header, chunks[0]);
+
+    if let Some(profile) = &agent.profile {
+        f.render_widget(Paragraph::new(format!(" Listed from profile '{}'; no preview for other managers", profile))
+            .style(Style::default().fg(DARK_GRAY))
+            .block(Block::default().borders(Borders::ALL).border_type(ratatui::widgets::BorderType::Rounded).border_style(Style::default().fg(DARK_GRAY))), chunks[1]);
+    } else if app.inspector_agent_id.as_deref() == Some(agent.id.as_str()) {
+        draw_inspector_summary(f, app, agent, chunks[1]);
+    } else {
+        f.render_widget(Paragraph::new(" Loading preview...")
+            .style(Style::default().fg(DARK_GRAY))
+            .block(Block::default().borders(Borders::ALL).border_type(ratatui::widgets::BorderType::Rounded).border_style(Style::default().fg(DARK_GRAY))), chunks[1]);
+    }
+}
+
+/// Sparkline of the agent's alerts per hour over the last day, to see at a glance whether the
+/// host just got noisy.
+fn draw_alert_hours(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY));
+    let Some(hours) = &app.agent_alert_hours else {
+        let p = Paragraph::new(Span::styled(" Loading alert history...", Style::default().fg(DARK_GRAY)))
+            .block(block.title(format!(" Alerts per Hour ({}h) ", crate::models::ALERT_HOURS)));
+        f.render_widget(p, area);
+        return;
+    };
+    let peak = hours.peak()
+        .map(|(t, n)| format!(", peak {} at {}", n, app.display_tz.convert(t).format("%H:00")))
+        .unwrap_or_default();
+    let title = format!(" Alerts per Hour ({}h): {} total{} ", crate::models::ALERT_HOURS, hours.total(), peak);
+    let counts = hours.counts();
+    let sparkline = ratatui::widgets::Sparkline::default()
+        .block(block.title(title))
+        .data(&counts)
+        .style(Style::default().fg(if hours.total() > 0 { ORANGE } else { DARK_GRAY }));
+    f.render_widget(sparkline, area);
+}
+
+/// One-screen triage view: agent facts, resources, the latest alerts and the worst CVEs.
+fn draw_inspector_summary(f: &mut Frame, app: &App, agent: &crate::models::WazuhAgent, area: Rect) {
+    let block = |title: &str| Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .title(format!(" {} ", title))
+        .border_style(Style::default().fg(DARK_GRAY));
+    let field = |label: &str, value: String| Line::from(vec![
+        Span::styled(format!(" {:<12}", label), Style::default().fg(BLUE)),
+        Span::styled(value, Style::default().fg(FG)),
+    ]);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(8), // Metadata + resources
+            Constraint::Length(4), // Alerts per hour
+            Constraint::Length(7), // Last alerts
+            Constraint::Min(5),    // Top CVEs
+        ])
+        .split(area);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(rows[0]);
+
+    let os = agent.os.as_ref().map(|o| format!("{} {}",
+        o.name.as_deref().unwrap_or(""),
+        o.version.as_deref().unwrap_or("")).trim().to_string())
+        .unwrap_or_default();
+    let keepalive = agent.last_keep_alive.as_deref().map(|ts| format_table_time(app, ts)).unwrap_or_else(|| "-".to_string());
+    let registered = agent.date_add.as_deref().map(|ts| format_table_time(app, ts)).unwrap_or_else(|| "-".to_string());
+    let metadata = vec![
+        field("IP", agent.ip.clone().unwrap_or_else(|| "-".to_string())),
+        field("OS", os),
+        field("Version", agent.version.clone().unwrap_or_else(|| "-".to_string())),
+        field("Node", agent.node_name.clone().unwrap_or_else(|| "-".to_string())),
+        field("Registered", registered),
+        field("Keep-alive", keepalive),
+    ];
+    f.render_widget(Paragraph::new(metadata).block(block("Agent")), top[0]);
+
+    let mut resources = match &app.hardware {
+        Some(hw) => vec![
+            field("CPU", format!("{} ({} cores)", hw.cpu.name, hw.cpu.cores)),
+            field("RAM", format!("{} used of {} ({}%)",
+                format_kib(hw.ram.total.saturating_sub(hw.ram.free)),
+                format_kib(hw.ram.total),
+                hw.ram.usage)),
+        ],
+        None => vec![Line::from(Span::styled(" Loading hardware info...", Style::default().fg(DARK_GRAY)))],
+    };
+    let groups = agent.group.as_ref().filter(|g| !g.is_empty()).map(|g| g.join(", ")).unwrap_or_else(|| "none".to_string());
+    resources.push(Line::from(""));
+    resources.push(field("Groups", groups));
+    if let Some(scan) = app.fim_scans.get(&agent.id) {
+        resources.push(field("FIM", scan.label(chrono::Utc::now())));
+    }
+    f.render_widget(Paragraph::new(resources)
+        .block(block("Resources & Groups"))
+        .wrap(ratatui::widgets::Wrap { trim: false }), top[1]);
+
+    draw_alert_hours(f, app, rows[1]);
+
+    let alert_rows = app.agent_logs.iter().take(5).map(|log| {
+        let source = log.get("_source");
+        let level = source.and_then(|s| s.pointer("/rule/level")).and_then(|v| v.as_u64()).unwrap_or(0);
+        let description = source.and_then(|s| s.pointer("/rule/description")).and_then(|v| v.as_str()).unwrap_or("No description");
+        let timestamp = source.and_then(|s| s.get("@timestamp")).and_then(|v| v.as_str()).unwrap_or("Unknown");
+        let color = match level {
+            12..=16 => VULN_CRITICAL,
+            8..=11 => VULN_HIGH,
+            4..=7 => VULN_MEDIUM,
+            _ => FG,
+        };
+        Row::new(vec![
+            Cell::from(format_table_time(app, timestamp)),
+            Cell::from(level.to_string()),
+            Cell::from(description.to_string()),
+        ]).style(Style::default().fg(color))
+    });
+    let alerts = Table::new(alert_rows, [
+        Constraint::Length(20),
+        Constraint::Length(5),
+        Constraint::Min(30),
+    ]).header(Row::new(vec!["Timestamp", "Lvl", "Description"]).style(Style::default().fg(BLUE)))
+      .block(block(&format!("Last Alerts ({} in {})", app.agent_logs.len(), crate::app::format_interval_mins(app.log_interval_mins))));
+    f.render_widget(alerts, rows[2]);
+
+    if agent.is_manager() {
+        draw_manager_status(f, app, rows[3]);
+        return;
+    }
+
+    let vuln_rows = app.top_vulnerabilities(3).into_iter().map(|v| {
+        let color = match v.severity.to_lowercase().as_str() {
+            "critical" => VULN_CRITICAL,
+            "high" => VULN_HIGH,
+            "medium" => VULN_MEDIUM,
+            _ => FG,
+        };
+        let pkg = v.package.as_ref().map(|p| format!("{} {}", p.name, p.version))
+            .unwrap_or_else(|| format!("{} {}", v.name.as_deref().unwrap_or("N/A"), v.version.as_deref().unwrap_or("")));
+        Row::new(vec![
+            Cell::from(v.cve.clone()),
+            Cell::from(if v.severity.is_empty() { "N/A".to_string() } else { v.severity.clone() }),
+            Cell::from(v.cvss.map(|s| format!("{:.1}", s)).unwrap_or_else(|| "-".to_string())),
+            Cell::from(pkg),
+        ]).style(Style::default().fg(color))
+    });
+    let vulns = Table::new(vuln_rows, [
+        Constraint::Length(18),
+        Constraint::Length(10),
+        Constraint::Length(6),
+        Constraint::Min(30),
+    ]).header(Row::new(vec!["CVE", "Severity", "CVSS", "Package"]).style(Style::default().fg(BLUE)))
+      .block(block(&format!("Top CVEs ({} total)", app.vulnerabilities.len())));
+    f.render_widget(vulns, rows[3]);
+}
+
+/// Agent 000 in place of the CVE list: its daemons and the newest `ossec.log` lines.
+fn draw_manager_status(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .title(" Manager │ ossec.log ")
+        .border_style(Style::default().fg(DARK_GRAY));
+    let Some(status) = &app.manager_status else {
+        f.render_widget(Paragraph::new(Span::styled(" Loading manager status...", Style::default().fg(DARK_GRAY))).block(block), area);
+        return;
+    };
+
+    let mut daemons = vec![Span::styled(" Daemons  ", Style::default().fg(BLUE))];
+    for (name, state) in &status.daemons {
+        let color = match state.as_str() {
+            "running" => GREEN,
+            "stopped" | "failed" => RED,
+            _ => DARK_GRAY,
+        };
+        daemons.push(Span::styled(format!("● {} ", name), Style::default().fg(color)));
+    }
+    let mut lines = vec![Line::from(daemons), Line::from("")];
+    lines.extend(status.logs.iter().map(|entry| {
+        let color = match entry.level.as_str() {
+            "error" | "critical" => RED,
+            "warning" => YELLOW,
+            _ => FG,
+        };
+        Line::from(vec![
+            Span::styled(format!(" {} ", format_table_time(app, &entry.timestamp)), Style::default().fg(DARK_GRAY)),
+            Span::styled(format!("{}: ", entry.tag), Style::default().fg(CYAN)),
+            Span::styled(entry.description.clone(), Style::default().fg(color)),
+        ])
+    }));
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 pub fn draw_agent_inspector(f: &mut Frame, app: &mut App, area: Rect) {
     let agent = match app.get_selected_agent() {
         Some(a) => a,
@@ -117,23 +403,46 @@ pub fn draw_agent_inspector(f: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Agent Header
+            Constraint::Length(4), // Agent Header
             Constraint::Length(3), // Tabs
             Constraint::Min(0),    // Details
         ])
         .split(area);
 
-    let header = Paragraph::new(format!(" Inspecting: {} ({}) | OS: {} | Status: {}", 
-        agent.name, agent.id, 
-        agent.os.as_ref().and_then(|o| o.name.clone()).unwrap_or_default(),
-        agent.status
-    )).block(Block::default()
+    let health = app.health_scores().get(&agent.id).copied().unwrap_or(0);
+    let approx = if app.health_scores_partial() { "~" } else { "" };
+    let groups = agent.group.as_ref().filter(|g| !g.is_empty()).map(|g| g.join(", ")).unwrap_or_else(|| "none".to_string());
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::raw(format!(" Inspecting: {} ({}) | OS: {} | Status: {} ",
+                agent.name, agent.id,
+                agent.os.as_ref().and_then(|o| o.name.clone()).unwrap_or_default(),
+                agent.status
+            )),
+            Span::styled(format!(" {}Health {}{} ", app.icons.pad(Icon::Health), approx, health), Style::default().fg(BG).bg(health_color(health)).add_modifier(Modifier::BOLD)),
+        ]),
+        if agent.is_manager() {
+            let running = app.manager_status.as_ref()
+                .map(|s| format!("{}/{} daemons running", s.daemons.iter().filter(|(_, state)| state == "running").count(), s.daemons.len()))
+                .unwrap_or_else(|| "status loading".to_string());
+            Line::from(vec![
+                Span::styled(" manager ", Style::default().fg(BG).bg(PURPLE)),
+                Span::styled(format!(" {} │ restart with [R], agent-only actions are disabled", running), Style::default().fg(DARK_GRAY)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled(" Groups: ", Style::default().fg(BLUE)),
+                Span::styled(groups, Style::default().fg(FG)),
+                Span::styled("  [G] Edit", Style::default().fg(DARK_GRAY)),
+            ])
+        },
+    ]).block(Block::default()
         .borders(Borders::ALL)
         .title(" Agent Info ")
         .border_style(Style::default().fg(DARK_GRAY)));
     f.render_widget(header, chunks[0]);
 
-    let titles = vec![" Hardware ", " Processes ", " Programs ", " Vulnerabilities ", " Events/Logs ", " Config "];
+    let titles = vec![" Summary ", " Hardware ", " Processes ", " Programs ", " Vulnerabilities ", " Rootcheck ", " Events/Logs ", " Network ", " Config "];
     let tabs = Tabs::new(titles)
         .select(app.selected_tab_index)
         .block(Block::default().borders(Borders::ALL).title(" Categories ").border_style(Style::default().fg(DARK_GRAY)))
@@ -142,6 +451,7 @@ pub fn draw_agent_inspector(f: &mut Frame, app: &mut App, area: Rect) {
     f.render_widget(tabs, chunks[1]);
 
     match app.inspector_tab {
+        InspectorTab::Summary => draw_inspector_summary(f, app, agent, chunks[2]),
         InspectorTab::Hardware => {
             if let Some(hw) = &app.hardware {
                 let text = format!(
@@ -157,72 +467,100 @@ pub fn draw_agent_inspector(f: &mut Frame, app: &mut App, area: Rect) {
             }
         },
         InspectorTab::Processes => {
-            let filtered_processes: Vec<_> = if app.is_searching {
-                app.processes.iter()
-                    .filter(|p| {
-                        let content = format!("{} {} {} {}", 
-                            p.pid, 
-                            p.name.as_ref().unwrap_or(&String::new()), 
-                            p.state.as_ref().unwrap_or(&String::new()), 
-                            p.cmd.as_ref().unwrap_or(&String::new())
-                        );
-                        filter_matches(&app.search_query, &content)
-                    })
-                    .collect()
-            } else {
-                app.processes.iter().collect()
+            let filtered_processes = app.filtered_processes();
+
+            let sort_header = |name: &str, col: Option<ProcessSortColumn>| {
+                let mut s = name.to_string();
+                if col == Some(app.process_sort_column) {
+                    s.push(' ');
+                    s.push_str(app.icons.sort_arrow(app.process_sort_order == SortOrder::Asc));
+                }
+                Cell::from(s)
             };
+            let header = Row::new(vec![
+                sort_header("PID", Some(ProcessSortColumn::Pid)),
+                sort_header("PPID", None),
+                sort_header("Name", Some(ProcessSortColumn::Name)),
+                sort_header("User", Some(ProcessSortColumn::User)),
+                sort_header("State", None),
+                sort_header("Memory", Some(ProcessSortColumn::Memory)),
+                sort_header("CPU", Some(ProcessSortColumn::CpuTime)),
+                sort_header("Command", None),
+            ]).style(Style::default().fg(BLUE));
 
-            let rows = filtered_processes.iter().map(|p| {
+            let window = table_window(filtered_processes.len(), app.inspector_table_state.selected(), chunks[2]);
+            let rows = filtered_processes[window.start..window.end].iter().map(|p| {
                 Row::new(vec![
                     Cell::from(p.pid.clone()),
+                    Cell::from(p.ppid.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string())),
                     Cell::from(p.name.clone().unwrap_or_else(|| "N/A".to_string())),
+                    Cell::from(p.euser.clone().unwrap_or_else(|| "N/A".to_string())),
                     Cell::from(p.state.clone().unwrap_or_else(|| "N/A".to_string())),
+                    Cell::from(p.vm_size.map(format_kib).unwrap_or_else(|| "-".to_string())),
+                    Cell::from(p.cpu_time().map(|t| t.to_string()).unwrap_or_else(|| "-".to_string())),
                     Cell::from(p.cmd.clone().unwrap_or_else(|| "N/A".to_string())),
                 ]).style(Style::default().fg(FG))
             });
             let table = Table::new(rows, [
+                Constraint::Length(8),
                 Constraint::Length(8),
                 Constraint::Length(20),
+                Constraint::Length(12),
+                Constraint::Length(8),
+                Constraint::Length(10),
                 Constraint::Length(10),
                 Constraint::Min(30),
-            ]).header(Row::new(vec!["PID", "Name", "State", "Command"]).style(Style::default().fg(BLUE)))
-              .block(Block::default().borders(Borders::ALL).title(" Processes ").border_style(Style::default().fg(DARK_GRAY)))
+            ]).header(header)
+              .block(Block::default().borders(Borders::ALL).title(format!(" Processes ({}){} ", filtered_processes.len(), paging_label(app.icons, app.processes.len(), app.processes_total))).border_style(Style::default().fg(DARK_GRAY)))
               .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD));
-            let mut state = app.inspector_table_state.clone();
+            let mut state = window.state;
             f.render_stateful_widget(table, chunks[2], &mut state);
         },
         InspectorTab::Programs => {
-             let filtered_programs: Vec<_> = if app.is_searching {
-                app.programs.iter()
-                    .filter(|p| {
-                        let content = format!("{} {} {}", 
-                            p.name, 
-                            p.version, 
-                            p.vendor.as_ref().unwrap_or(&String::new())
-                        );
-                        filter_matches(&app.search_query, &content)
-                    })
-                    .collect()
-            } else {
-                app.programs.iter().collect()
-            };
+            let vulns_by_package = app.vulnerabilities_by_package();
+            let filtered_programs = app.filtered_programs();
+
+            let window = table_window(filtered_programs.len(), app.inspector_table_state.selected(), chunks[2]);
+            let rows = filtered_programs[window.start..window.end].iter().map(|p| {
+                let vulns = vulns_by_package.get(&p.name.to_lowercase());
+                let count = vulns.map(|v| v.len()).unwrap_or(0);
+                // Colour the package by its worst CVE
+                let color = vulns.map(|v| {
+                    let sev = |s: &str| match s.to_lowercase().as_str() {
+                        "critical" => 3,
+                        "high" => 2,
+                        "medium" => 1,
+                        _ => 0,
+                    };
+                    match v.iter().map(|i| sev(&i.severity)).max().unwrap_or(0) {
+                        3 => VULN_CRITICAL,
+                        2 => VULN_HIGH,
+                        1 => VULN_MEDIUM,
+                        _ => VULN_LOW,
+                    }
+                }).unwrap_or(FG);
 
-            let rows = filtered_programs.iter().map(|p| {
                 Row::new(vec![
                     Cell::from(p.name.clone()),
                     Cell::from(p.version.clone()),
                     Cell::from(p.vendor.clone().unwrap_or_else(|| "N/A".to_string())),
-                ]).style(Style::default().fg(FG))
+                    Cell::from(if count > 0 { format!("{}{}", app.icons.pad(Icon::Shield), count) } else { "-".to_string() }),
+                ]).style(Style::default().fg(color))
             });
+            let title = format!(" Installed Programs ({}){}{} ",
+                filtered_programs.len(),
+                if app.programs_vuln_only { " | Vulnerable only" } else { "" },
+                paging_label(app.icons, app.programs.len(), app.programs_total)
+            );
             let table = Table::new(rows, [
                 Constraint::Min(30),
                 Constraint::Length(25),
                 Constraint::Length(25),
-            ]).header(Row::new(vec!["Name", "Version", "Vendor"]).style(Style::default().fg(BLUE)))
-              .block(Block::default().borders(Borders::ALL).border_type(ratatui::widgets::BorderType::Rounded).title(" Installed Programs ").border_style(Style::default().fg(DARK_GRAY)))
+                Constraint::Length(8),
+            ]).header(Row::new(vec!["Name", "Version", "Vendor", "CVEs"]).style(Style::default().fg(BLUE)))
+              .block(Block::default().borders(Borders::ALL).border_type(ratatui::widgets::BorderType::Rounded).title(title).border_style(Style::default().fg(DARK_GRAY)))
               .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD));
-            let mut state = app.inspector_table_state.clone();
+            let mut state = window.state;
             f.render_stateful_widget(table, chunks[2], &mut state);
         },
         InspectorTab::Vulnerabilities => {
@@ -267,11 +605,14 @@ pub fn draw_agent_inspector(f: &mut Frame, app: &mut App, area: Rect) {
                     ])
                     .split(vuln_layout[0]);
 
-                let draw_severity_card = |f: &mut Frame, title: &str, count: u64, color: Color, area: Rect| {
+                let active_filter = app.vuln_severity_filter.as_deref();
+                let draw_severity_card = |f: &mut Frame, key: char, title: &str, count: u64, color: Color, area: Rect| {
+                    // The card matching the 1-4 filter gets a thick border
+                    let active = active_filter == Some(title.to_lowercase().as_str());
                     let block = Block::default()
                         .borders(Borders::ALL)
-                        .border_type(ratatui::widgets::BorderType::Rounded)
-                        .title(format!(" {} ", title))
+                        .border_type(if active { ratatui::widgets::BorderType::Thick } else { ratatui::widgets::BorderType::Rounded })
+                        .title(format!(" [{}] {} ", key, title))
                         .border_style(Style::default().fg(color));
                     
                     let text = Paragraph::new(count.to_string())
@@ -282,33 +623,14 @@ pub fn draw_agent_inspector(f: &mut Frame, app: &mut App, area: Rect) {
                     f.render_widget(text, area);
                 };
 
-                draw_severity_card(f, "CRITICAL", crit as u64, VULN_CRITICAL, summary_chunks[0]);
-                draw_severity_card(f, "HIGH", high as u64, VULN_HIGH, summary_chunks[1]);
-                draw_severity_card(f, "MEDIUM", med as u64, VULN_MEDIUM, summary_chunks[2]);
-                draw_severity_card(f, "LOW", low as u64, VULN_LOW, summary_chunks[3]);
+                draw_severity_card(f, '1', "CRITICAL", crit as u64, VULN_CRITICAL, summary_chunks[0]);
+                draw_severity_card(f, '2', "HIGH", high as u64, VULN_HIGH, summary_chunks[1]);
+                draw_severity_card(f, '3', "MEDIUM", med as u64, VULN_MEDIUM, summary_chunks[2]);
+                draw_severity_card(f, '4', "LOW", low as u64, VULN_LOW, summary_chunks[3]);
 
 
                 // --- LIST SECTION ---
-                let filtered_vulns: Vec<_> = if app.is_searching {
-                    app.vulnerabilities.iter()
-                        .filter(|v| {
-                            let pkg_name = v.package.as_ref().map(|p| p.name.clone())
-                                .unwrap_or_else(|| v.name.clone().unwrap_or_default());
-                            let pkg_version = v.package.as_ref().map(|p| p.version.clone())
-                                .unwrap_or_else(|| v.version.clone().unwrap_or_default());
-
-                            let content = format!("{} {} {} {}", 
-                                v.cve, 
-                                v.severity, 
-                                pkg_name, 
-                                pkg_version
-                            );
-                            filter_matches(&app.search_query, &content)
-                        })
-                        .collect()
-                } else {
-                    app.vulnerabilities.iter().collect()
-                };
+                let filtered_vulns = app.filtered_vulnerabilities();
 
                 let rows = filtered_vulns.iter().map(|v| {
                     let color = match v.severity.to_lowercase().as_str() {
@@ -328,22 +650,45 @@ pub fn draw_agent_inspector(f: &mut Frame, app: &mut App, area: Rect) {
                     Row::new(vec![
                         Cell::from(v.cve.clone()),
                         Cell::from(severity_display),
+                        Cell::from(v.cvss.map(|s| format!("{:.1}", s)).unwrap_or_else(|| "-".to_string())),
                         Cell::from(pkg_name),
                         Cell::from(pkg_version),
                     ]).style(Style::default().fg(color))
                 });
+                let sort_header = |name: &str, col: Option<VulnSortColumn>| {
+                    let mut s = name.to_string();
+                    if col == Some(app.vuln_sort_column) {
+                        s.push(' ');
+                        s.push_str(app.icons.sort_arrow(app.vuln_sort_order == SortOrder::Asc));
+                    }
+                    Cell::from(s)
+                };
+                let header = Row::new(vec![
+                    sort_header("CVE", None),
+                    sort_header("Severity", Some(VulnSortColumn::Severity)),
+                    sort_header("CVSS", Some(VulnSortColumn::Score)),
+                    sort_header("Package", Some(VulnSortColumn::Package)),
+                    sort_header("Version", None),
+                ]).style(Style::default().fg(BLUE));
+                let title = format!(" Details ({}/{}){} ",
+                    filtered_vulns.len(),
+                    app.vulnerabilities.len(),
+                    app.vuln_severity_filter.as_ref().map(|s| format!(" | {} only", s.to_uppercase())).unwrap_or_default()
+                );
                 let table = Table::new(rows, [
                     Constraint::Length(15),
                     Constraint::Length(12),
+                    Constraint::Length(8),
                     Constraint::Min(30),
                     Constraint::Length(20),
-                ]).header(Row::new(vec!["CVE", "Severity", "Package", "Version"]).style(Style::default().fg(BLUE)))
-                  .block(Block::default().borders(Borders::ALL).border_type(ratatui::widgets::BorderType::Rounded).title(" Details ").border_style(Style::default().fg(DARK_GRAY)))
+                ]).header(header)
+                  .block(Block::default().borders(Borders::ALL).border_type(ratatui::widgets::BorderType::Rounded).title(title).border_style(Style::default().fg(DARK_GRAY)))
                   .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD));
                 let mut state = app.inspector_table_state.clone();
                 f.render_stateful_widget(table, vuln_layout[1], &mut state);
             }
         },
+        InspectorTab::Rootcheck => draw_rootcheck(f, app, chunks[2]),
         InspectorTab::Logs => {
             let filtered_logs: Vec<_> = if app.is_searching {
                 app.agent_logs.iter()
@@ -360,15 +705,15 @@ pub fn draw_agent_inspector(f: &mut Frame, app: &mut App, area: Rect) {
                 let description = rule.get("description").and_then(|v| v.as_str()).unwrap_or("No description");
                 let timestamp = source.get("@timestamp").and_then(|v| v.as_str()).unwrap_or("Unknown");
 
-        let (_icon, color) = match level {
-            12..=16 => ("󰅚 ", VULN_CRITICAL),
-            8..=11 => ("󰀦 ", VULN_HIGH),
-            4..=7 => ("󱈸 ", VULN_MEDIUM),
-            _ => ("󰋼 ", FG),
+        let color = match level {
+            12..=16 => VULN_CRITICAL,
+            8..=11 => VULN_HIGH,
+            4..=7 => VULN_MEDIUM,
+            _ => FG,
         };
 
         Row::new(vec![
-            Cell::from(timestamp.to_string()),
+            Cell::from(format_table_time(app, timestamp)),
             Cell::from(level.to_string()),
             Cell::from(description.to_string()),
         ]).style(Style::default().fg(color))
@@ -384,19 +729,36 @@ pub fn draw_agent_inspector(f: &mut Frame, app: &mut App, area: Rect) {
             let mut state = app.inspector_table_state.clone();
             f.render_stateful_widget(table, chunks[2], &mut state);
         },
+        InspectorTab::Network => draw_network_connections(f, app, chunks[2]),
+        InspectorTab::Config if app.config_diff_mode => draw_config_drift(f, app, chunks[2]),
         InspectorTab::Config => {
             let block = Block::default()
                 .borders(Borders::ALL)
                 .border_type(ratatui::widgets::BorderType::Rounded)
-                .title(format!(" Component: {} (Press Enter to cycle) ", app.agent_config_component))
+                .title(format!(" Component: {} (Press Enter to cycle, d to diff against group) ", app.agent_config_component))
                 .border_style(Style::default().fg(DARK_GRAY));
             
             if let Some(config) = &app.agent_config {
+                // Modules get a table; anything that does not parse falls back to JSON
+                let modules = if app.agent_config_component == "wmodules" {
+                    crate::app::wodles::parse_wmodules(config)
+                } else {
+                    Vec::new()
+                };
+                let syscheck = if app.agent_config_component == "syscheck" {
+                    crate::app::syscheck::parse_syscheck(config)
+                } else {
+                    None
+                };
                 if config.is_null() || (config.is_object() && config.as_object().map(|o| o.is_empty()).unwrap_or(false)) {
                     f.render_widget(Paragraph::new(format!(" No configuration found for component: {}\n\nPress Enter to cycle to another component.", app.agent_config_component))
                         .block(block)
                         .wrap(ratatui::widgets::Wrap { trim: false })
                         .style(Style::default().fg(FG)), chunks[2]);
+                } else if !modules.is_empty() {
+                    draw_wodle_table(f, app.icons, &modules, &app.agent_config_component, chunks[2]);
+                } else if let Some(syscheck) = syscheck {
+                    draw_syscheck_summary(f, app.icons, &syscheck, chunks[2]);
                 } else {
                     // Use colorized JSON for config display
                     let lines = colorize_json(config);
@@ -420,3 +782,274 @@ pub fn draw_agent_inspector(f: &mut Frame, app: &mut App, area: Rect) {
         crate::ui::logs::draw_log_detail(f, app, &log, area);
     }
 }
+
+/// Rootcheck findings with the last scan window in the title.
+fn draw_rootcheck(f: &mut Frame, app: &App, area: Rect) {
+    let scan = match &app.rootcheck_scan {
+        Some(crate::models::WazuhLastScan { start: Some(start), end: Some(end) }) =>
+            format!("last scan {} – {}", format_table_time(app, start), format_table_time(app, end)),
+        Some(crate::models::WazuhLastScan { start: Some(start), end: None }) =>
+            format!("scan running since {}", format_table_time(app, start)),
+        _ => "never scanned".to_string(),
+    };
+    let outstanding = app.rootcheck.iter().filter(|r| r.is_outstanding()).count();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(format!(" Rootcheck: {} outstanding, {} solved | {} ", outstanding, app.rootcheck.len() - outstanding, scan));
+
+    if app.rootcheck.is_empty() {
+        f.render_widget(Paragraph::new("\n  No rootcheck findings for this agent.\n  Press [S] to run a scan.")
+            .style(Style::default().fg(DARK_GRAY))
+            .block(block), area);
+        return;
+    }
+
+    let rows = app.rootcheck.iter()
+        .filter(|r| !app.is_searching || filter_matches(&app.search_query, &r.log))
+        .map(|r| {
+            let (status, color) = if r.is_outstanding() { ("outstanding", ORANGE) } else { ("solved", GREEN) };
+            let category_color = match r.category() {
+                "Trojan" => VULN_CRITICAL,
+                "Process" | "Port" => VULN_HIGH,
+                "File" => VULN_MEDIUM,
+                _ => FG,
+            };
+            let compliance = [r.cis.as_deref().map(|c| format!("CIS {}", c)), r.pci_dss.as_deref().map(|p| format!("PCI {}", p))]
+                .into_iter().flatten().collect::<Vec<_>>().join(" ");
+            Row::new(vec![
+                Cell::from(r.category()).style(Style::default().fg(category_color).add_modifier(Modifier::BOLD)),
+                Cell::from(status).style(Style::default().fg(color)),
+                Cell::from(r.date_last.as_deref().map(|t| format_table_time(app, t)).unwrap_or_else(|| "-".to_string())),
+                Cell::from(r.log.clone()),
+                Cell::from(compliance).style(Style::default().fg(DARK_GRAY)),
+            ]).style(Style::default().fg(if r.is_outstanding() { FG } else { DARK_GRAY }))
+        });
+
+    let table = Table::new(rows, [
+        Constraint::Length(8),
+        Constraint::Length(12),
+        Constraint::Length(20),
+        Constraint::Min(40),
+        Constraint::Length(16),
+    ])
+    .header(Row::new(vec!["Kind", "Status", "Last Seen", "Finding", "Compliance"]).style(Style::default().fg(BLUE)))
+    .block(block)
+    .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD));
+    let mut state = app.inspector_table_state.clone();
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+/// Who the agent has been talking to, going by the addresses in its alerts.
+fn draw_network_connections(f: &mut Frame, app: &App, area: Rect) {
+    let total: u64 = app.agent_connections.iter().map(|c| c.count).sum();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(format!(" Connections: {} distinct from {} alerts | last {} ", app.agent_connections.len(), total, app.format_interval()));
+
+    if app.agent_connections.is_empty() {
+        f.render_widget(Paragraph::new("\n  No alerts with data.srcip or data.dstip for this agent in the interval.")
+            .style(Style::default().fg(DARK_GRAY))
+            .block(block), area);
+        return;
+    }
+
+    let cell = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+    let rows = app.agent_connections.iter()
+        .filter(|c| !app.is_searching || filter_matches(&app.search_query, &format!("{} {} {}", cell(&c.srcip), cell(&c.dstip), cell(&c.dstport))))
+        .map(|c| {
+            Row::new(vec![
+                Cell::from(cell(&c.srcip)),
+                Cell::from(cell(&c.dstip)),
+                Cell::from(cell(&c.dstport)).style(Style::default().fg(CYAN)),
+                Cell::from(c.count.to_string()).style(Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
+                Cell::from(c.last_seen.as_deref().map(|t| format_table_time(app, t)).unwrap_or_else(|| "-".to_string())).style(Style::default().fg(DARK_GRAY)),
+            ]).style(Style::default().fg(FG))
+        });
+
+    let table = Table::new(rows, [
+        Constraint::Min(20),
+        Constraint::Min(20),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(20),
+    ])
+    .header(Row::new(vec!["Source", "Destination", "Port", "Alerts", "Last Seen"]).style(Style::default().fg(BLUE)))
+    .block(block)
+    .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD));
+    let mut state = app.inspector_table_state.clone();
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+/// Settings from the agent's groups' `agent.conf` and whether the agent runs with them.
+fn draw_config_drift(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY));
+    let drift = match app.config_drift() {
+        None => {
+            f.render_widget(Paragraph::new("Loading group baseline...").block(block.title(" Group Baseline ")).style(Style::default().fg(GRAY)), area);
+            return;
+        }
+        Some(Err(e)) => {
+            f.render_widget(Paragraph::new(e).block(block.title(" Group Baseline ")).wrap(ratatui::widgets::Wrap { trim: false }).style(Style::default().fg(RED)), area);
+            return;
+        }
+        Some(Ok(drift)) => drift,
+    };
+    if drift.is_empty() {
+        let msg = format!(" The agent's groups set nothing for {}; it runs on local settings only.\n\n [d] Back to config  [Enter] Next component ", app.agent_config_component);
+        f.render_widget(Paragraph::new(msg).block(block.title(" Group Baseline ")).wrap(ratatui::widgets::Wrap { trim: false }).style(Style::default().fg(FG)), area);
+        return;
+    }
+
+    let deviations = drift.iter().filter(|d| d.drift != crate::app::config_diff::Drift::Match).count();
+    let rows = drift.iter().map(|d| {
+        let (state, agent_value, color) = match &d.drift {
+            crate::app::config_diff::Drift::Match => (format!("{}same", app.icons.pad(Icon::Check)), String::new(), GREEN),
+            crate::app::config_diff::Drift::Differs(actual) => (format!("{}differs", app.icons.pad(Icon::Warning)), actual.clone(), YELLOW),
+            crate::app::config_diff::Drift::Missing => (format!("{}missing", app.icons.pad(Icon::Cross)), "-".to_string(), RED),
+        };
+        let value_style = Style::default().fg(if color == GREEN { FG } else { color });
+        Row::new(vec![
+            Cell::from(d.setting.group.clone()).style(Style::default().fg(DARK_GRAY)),
+            Cell::from(d.setting.key.clone()).style(Style::default().fg(CYAN)),
+            Cell::from(state).style(Style::default().fg(color)),
+            Cell::from(d.setting.expected.clone()).style(Style::default().fg(FG)),
+            Cell::from(agent_value).style(value_style),
+        ])
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Length(14),
+        Constraint::Length(28),
+        Constraint::Length(11),
+        Constraint::Percentage(30),
+        Constraint::Min(20),
+    ])
+    .header(Row::new(vec!["Group", "Setting", "State", "agent.conf", "Agent"]).style(Style::default().fg(BLUE)))
+    .block(block
+        .border_style(Style::default().fg(if deviations > 0 { YELLOW } else { DARK_GRAY }))
+        .title(format!(" Component: {} vs group — {} of {} settings deviate ([d] Back) ", app.agent_config_component, deviations, drift.len())));
+    f.render_widget(table, area);
+}
+
+/// Syscheck settings as a header, a table of monitored directories and the ignore lists.
+fn draw_syscheck_summary(f: &mut Frame, icons: IconSet, syscheck: &crate::app::syscheck::SyscheckSummary, area: Rect) {
+    use crate::app::syscheck::{format_frequency, MonitorMode};
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(" Component: syscheck (Press Enter to cycle, d to diff against group) ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let mut excluded = Vec::new();
+    if !syscheck.ignore.is_empty() {
+        excluded.push(("Ignored", syscheck.ignore.join(", ")));
+    }
+    if !syscheck.nodiff.is_empty() {
+        excluded.push(("No diff", syscheck.nodiff.join(", ")));
+    }
+    if !syscheck.registry.is_empty() {
+        excluded.push(("Registry", syscheck.registry.join(", ")));
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(3), Constraint::Length(excluded.len() as u16 * 2)])
+        .split(inner);
+
+    let (state, color) = if syscheck.enabled {
+        (format!("{}enabled", icons.pad(Icon::Check)), GREEN)
+    } else {
+        (format!("{}disabled", icons.pad(Icon::Cross)), RED)
+    };
+    let mut header = vec![
+        Span::styled(format!(" {} ", state), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        Span::styled(" │ every ", Style::default().fg(DARK_GRAY)),
+        Span::styled(syscheck.frequency.map(format_frequency).unwrap_or_else(|| "-".to_string()), Style::default().fg(CYAN)),
+    ];
+    if let Some(at) = &syscheck.scan_time {
+        header.push(Span::styled(format!(" at {}", at), Style::default().fg(CYAN)));
+    }
+    if let Some(on_start) = syscheck.scan_on_start {
+        header.push(Span::styled(" │ scan on start: ", Style::default().fg(DARK_GRAY)));
+        header.push(Span::styled(if on_start { "yes" } else { "no" }, Style::default().fg(FG)));
+    }
+    if !syscheck.skipped.is_empty() {
+        header.push(Span::styled(" │ skips: ", Style::default().fg(DARK_GRAY)));
+        header.push(Span::styled(syscheck.skipped.join(", "), Style::default().fg(FG)));
+    }
+    f.render_widget(Paragraph::new(Line::from(header)), chunks[0]);
+
+    let rows = syscheck.directories.iter().map(|d| {
+        let mode_color = match d.mode {
+            MonitorMode::Whodata => PURPLE,
+            MonitorMode::Realtime => GREEN,
+            MonitorMode::Scheduled => GRAY,
+        };
+        Row::new(vec![
+            Cell::from(d.path.clone()).style(Style::default().fg(CYAN)),
+            Cell::from(d.mode.label()).style(Style::default().fg(mode_color)),
+            Cell::from(if d.report_changes { icons.get_or(Icon::Check, "yes") } else { "" }).style(Style::default().fg(YELLOW)),
+            Cell::from(d.recursion_level.map(|r| r.to_string()).unwrap_or_default()).style(Style::default().fg(GRAY)),
+            Cell::from(d.options.join(", ")).style(Style::default().fg(FG)),
+        ])
+    });
+    let table = Table::new(rows, [
+        Constraint::Percentage(35),
+        Constraint::Length(10),
+        Constraint::Length(8),
+        Constraint::Length(6),
+        Constraint::Min(20),
+    ])
+    .header(Row::new(vec!["Directory", "Mode", "Changes", "Depth", "Options"]).style(Style::default().fg(BLUE)))
+    .block(Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(format!(" {} monitored directories ", syscheck.directories.len())));
+    f.render_widget(table, chunks[1]);
+
+    let lines: Vec<Line> = excluded.into_iter().map(|(label, items)| Line::from(vec![
+        Span::styled(format!(" {:<9}", label), Style::default().fg(DARK_GRAY)),
+        Span::styled(items, Style::default().fg(FG)),
+    ])).collect();
+    f.render_widget(Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false }), chunks[2]);
+}
+
+/// `wmodules` as one row per module instead of raw JSON.
+fn draw_wodle_table(f: &mut Frame, icons: IconSet, modules: &[crate::app::wodles::WodleStatus], component: &str, area: Rect) {
+    let enabled = modules.iter().filter(|m| m.enabled).count();
+    let rows = modules.iter().map(|m| {
+        let (state, color) = if m.enabled {
+            (format!("{}enabled", icons.pad(Icon::Check)), GREEN)
+        } else {
+            (format!("{}disabled", icons.pad(Icon::Cross)), DARK_GRAY)
+        };
+        let params = m.params.iter().map(|(k, v)| format!("{}: {}", k, v)).collect::<Vec<_>>().join("  ·  ");
+        Row::new(vec![
+            Cell::from(m.name.clone()).style(Style::default().fg(if m.enabled { CYAN } else { DARK_GRAY }).add_modifier(Modifier::BOLD)),
+            Cell::from(state).style(Style::default().fg(color)),
+            Cell::from(params).style(Style::default().fg(if m.enabled { FG } else { DARK_GRAY })),
+        ])
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Length(24),
+        Constraint::Length(11),
+        Constraint::Min(30),
+    ])
+    .header(Row::new(vec!["Module", "State", "Parameters"]).style(Style::default().fg(BLUE)))
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(format!(" Component: {} — {}/{} enabled (Press Enter to cycle) ", component, enabled, modules.len())));
+    f.render_widget(table, area);
+}
@@ -1,6 +1,8 @@
 use chrono;
 use regex::RegexBuilder;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::widgets::TableState;
+use crate::ui::icons::{Icon, IconSet};
 
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -22,6 +24,67 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+/// The slice of a table that is actually on screen, plus the state to render it with.
+pub struct TableWindow {
+    pub start: usize,
+    pub end: usize,
+    pub state: TableState,
+}
+
+/// Computes which rows of a bordered table with a one-line header are visible, so
+/// large tables only build `Row`s for the viewport. Scrolling keeps the selection on
+/// the last visible line, matching how ratatui scrolls a fresh `TableState`.
+pub fn table_window(total: usize, selected: Option<usize>, area: Rect) -> TableWindow {
+    let height = (area.height.saturating_sub(3) as usize).max(1);
+    let selected = selected.filter(|_| total > 0).map(|s| s.min(total - 1));
+    let start = match selected {
+        Some(s) if s >= height => s + 1 - height,
+        _ => 0,
+    };
+    let end = (start + height).min(total);
+
+    let mut state = TableState::default();
+    state.select(selected.map(|s| s - start));
+    TableWindow { start, end, state }
+}
+
+/// Terminals narrower than this get the compact layouts: fewer table columns,
+/// stacked dashboard panels and a one-line footer.
+pub const NARROW_WIDTH: u16 = 100;
+
+pub fn is_narrow(area: Rect) -> bool {
+    area.width < NARROW_WIDTH
+}
+
+/// Indices (in display order) of the table columns that fit in `available` cells.
+/// Columns are taken by `priorities` (lower is more important), each needing its
+/// minimum width plus one cell of spacing; the most important column always stays.
+pub fn fit_columns(min_widths: &[u16], priorities: &[u8], available: u16) -> Vec<usize> {
+    let mut by_priority: Vec<usize> = (0..min_widths.len()).collect();
+    by_priority.sort_by_key(|&i| priorities[i]);
+
+    let mut used = 0u16;
+    let mut kept = Vec::new();
+    for i in by_priority {
+        let needed = min_widths[i] + if kept.is_empty() { 0 } else { 1 };
+        if !kept.is_empty() && used + needed > available {
+            continue;
+        }
+        used += needed;
+        kept.push(i);
+    }
+    kept.sort_unstable();
+    kept
+}
+
+/// Keeps the cells (or widths) of the columns returned by `fit_columns`.
+pub fn pick_columns<T>(items: Vec<T>, columns: &[usize]) -> Vec<T> {
+    items.into_iter().enumerate()
+        .filter(|(i, _)| columns.contains(i))
+        .map(|(_, item)| item)
+        .collect()
+}
+
 pub fn filter_matches(query: &str, content: &str) -> bool {
     if let Ok(re) = RegexBuilder::new(query).case_insensitive(true).build() {
         re.is_match(content)
@@ -30,35 +93,121 @@ pub fn filter_matches(query: &str, content: &str) -> bool {
     }
 }
 
+/// Which segment of a stacked bar fills each row, bottom row first. The bar is
+/// scaled so `max` fills `height` rows; `None` rows are empty.
+pub fn stacked_bar_cells(segments: &[u64], height: u16, max: u64) -> Vec<Option<usize>> {
+    let total: u64 = segments.iter().sum();
+    if max == 0 || total == 0 {
+        return vec![None; height as usize];
+    }
+    // Any non-zero bar gets at least one row
+    let filled = ((total * height as u64).div_ceil(max)).clamp(1, height as u64);
+    (0..height as u64).map(|row| {
+        if row >= filled {
+            return None;
+        }
+        // Value at the middle of this row, on the bar's own scale
+        let at = (2 * row + 1) * total / (2 * filled);
+        let mut cumulative = 0;
+        segments.iter().position(|v| {
+            cumulative += v;
+            at < cumulative
+        })
+    }).collect()
+}
+
+/// Table title suffix while more syscollector pages are on their way.
+pub fn paging_label(icons: IconSet, loaded: usize, total: Option<u32>) -> String {
+    match total {
+        Some(total) if total as usize > loaded => format!(" │ {}loading {}/{}", icons.pad(Icon::Loading), loaded, total),
+        _ => String::new(),
+    }
+}
+
+/// Line under the Security Events table: which slice of the matching alerts is on screen and
+/// the filters narrowing them, so an empty page reads as "filtered out" rather than "no data".
+/// `exact` is false when the indexer stopped counting and `total` is a lower bound.
+pub fn events_summary(offset: u32, shown: usize, total: u64, exact: bool, interval: &str, filters: &[String]) -> String {
+    let filtered = if filters.is_empty() { String::new() } else { format!(" (filtered: {})", filters.join(", ")) };
+    if total == 0 {
+        return if filters.is_empty() {
+            format!("No alerts in the last {}", interval)
+        } else {
+            format!("No alerts match in the last {}{} │ [f] changes the filter", interval, filtered)
+        };
+    }
+    let plus = if exact { "" } else { "+" };
+    if shown == 0 {
+        return format!("Past the last page: {}{} alerts{}", total, plus, filtered);
+    }
+    format!("{}–{} of {}{} alerts shown{}", offset as u64 + 1, offset as u64 + shown as u64, total, plus, filtered)
+}
+
+/// Formats a size reported in KiB (syscollector's unit) as KB/MB/GB.
+pub fn format_kib(kib: u64) -> String {
+    if kib >= 1024 * 1024 {
+        format!("{:.1} GB", kib as f64 / (1024.0 * 1024.0))
+    } else if kib >= 1024 {
+        format!("{:.1} MB", kib as f64 / 1024.0)
+    } else {
+        format!("{} KB", kib)
+    }
+}
+
+/// Base64 encoding for OSC 52 clipboard sequences.
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
+
+/// Builds the OSC 52 escape sequence that asks the terminal to put `text` on the clipboard.
+/// Works over SSH and without any clipboard library, as long as the terminal supports it.
+pub fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
 pub fn format_last_keep_alive(last_keep_alive: &Option<String>) -> String {
-    if let Some(time_str) = last_keep_alive {
-        // Try RFC3339 first (standard ISO8601)
-        let dt = chrono::DateTime::parse_from_rfc3339(time_str)
-            .map(|dt| dt.with_timezone(&chrono::Utc))
-            .or_else(|_| {
-                // Try format without offset if it fails (common in some Wazuh versions)
-                chrono::NaiveDateTime::parse_from_str(time_str, "%Y-%m-%dT%H:%M:%S")
-                    .map(|ndt| chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(ndt, chrono::Utc))
-            });
-
-        if let Ok(dt) = dt {
-            let now = chrono::Utc::now();
-            let duration = now.signed_duration_since(dt);
-            
-            if duration.num_seconds() < 0 {
-                return "Just now".to_string();
-            }
-            if duration.num_seconds() < 60 {
-                return format!("{}s ago", duration.num_seconds());
-            }
-            if duration.num_minutes() < 60 {
-                return format!("{}m ago", duration.num_minutes());
-            }
-            if duration.num_hours() < 24 {
-                return format!("{}h ago", duration.num_hours());
-            }
-            return format!("{}d ago", duration.num_days());
+    last_keep_alive.as_deref()
+        .and_then(crate::models::parse_timestamp)
+        .map(format_relative)
+        .unwrap_or_else(|| "Never".to_string())
+}
+
+/// "12s ago", "5m ago", "3h ago", "2d ago" relative to now.
+pub fn format_relative(dt: chrono::DateTime<chrono::Utc>) -> String {
+    let duration = chrono::Utc::now().signed_duration_since(dt);
+
+    if duration.num_seconds() < 0 {
+        return "Just now".to_string();
+    }
+    if duration.num_seconds() < 60 {
+        return format!("{}s ago", duration.num_seconds());
+    }
+    if duration.num_minutes() < 60 {
+        return format!("{}m ago", duration.num_minutes());
+    }
+    if duration.num_hours() < 24 {
+        return format!("{}h ago", duration.num_hours());
+    }
+    format!("{}d ago", duration.num_days())
+}
+
+/// An alert or keep-alive timestamp as shown in tables: relative when the toggle
+/// is on, otherwise full date and time in the display timezone.
+pub fn format_table_time(app: &crate::app::App, ts: &str) -> String {
+    if app.relative_time {
+        if let Some(dt) = crate::models::parse_timestamp(ts) {
+            return format_relative(dt);
         }
     }
-    "Never".to_string()
+    app.display_tz.format(ts, "%Y-%m-%d %H:%M:%S")
 }
@@ -1,19 +1,26 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect, Alignment},
     style::{Modifier, Style},
-    widgets::{Block, Borders, Paragraph, Row, Table, Cell},
+    widgets::{block::Title, Block, Borders, Paragraph, Row, Table, Cell},
     text::{Line, Span},
     Frame,
 };
 use crate::app::App;
+use crate::app::severity::Severity;
 use crate::ui::theme::*;
+use crate::ui::icons::Icon;
+use crate::ui::common::{format_last_keep_alive, table_window, is_narrow, stacked_bar_cells};
 
 pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
+    // Narrow terminals stack the side-by-side panels
+    let narrow = is_narrow(area);
+    let stack = if narrow { Direction::Vertical } else { Direction::Horizontal };
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5),  // Agent Summary
-            Constraint::Length(7),  // Threat Summary
+            Constraint::Length(if narrow { 11 } else { 5 }),  // Agent Summary
+            Constraint::Length(if narrow { 14 } else { 9 }),  // Threat Summary + trend
             Constraint::Min(0),     // Bottom content
         ])
         .margin(1)
@@ -32,7 +39,7 @@ pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
         .borders(Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded)
         .border_style(Style::default().fg(DARK_GRAY))
-        .title(Span::styled(" 󰒋 AGENTS ", Style::default().fg(BLUE).add_modifier(Modifier::BOLD)));
+        .title(Span::styled(format!(" {}AGENTS ", app.icons.pad(Icon::Agent)), Style::default().fg(BLUE).add_modifier(Modifier::BOLD)));
 
     let health_pct = if total > 0 { (active * 100) / total } else { 0 };
     let health_bar_width = 20;
@@ -53,7 +60,7 @@ pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
             Span::styled("  Pending: ", Style::default().fg(FG)),
             Span::styled(format!("{}", pending), Style::default().fg(YELLOW)),
         ]),
-        Line::from(""),
+        duplicates_line(app),
         Line::from(vec![
             Span::styled("  Health: ", Style::default().fg(FG)),
             Span::styled("▓".repeat(filled), Style::default().fg(bar_color)),
@@ -62,22 +69,48 @@ pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
         ]),
     ];
 
-    let agent_para = Paragraph::new(agent_content).block(agent_block);
-    f.render_widget(agent_para, chunks[0]);
+    let top_row = Layout::default()
+        .direction(stack)
+        .constraints(if narrow {
+            [Constraint::Length(6), Constraint::Length(5)]
+        } else {
+            [Constraint::Percentage(60), Constraint::Percentage(40)]
+        })
+        .split(chunks[0]);
+
+    let agent_para = Paragraph::new(agent_content)
+        .block(agent_block)
+        .wrap(ratatui::widgets::Wrap { trim: false });
+    f.render_widget(agent_para, top_row[0]);
+
+    draw_vuln_summary(f, app, top_row[1]);
 
     // ─────────────────────────────────────────────────────────────────────────
     // THREAT SUMMARY SECTION
     // ─────────────────────────────────────────────────────────────────────────
     let interval_text = format!("Last {}", app.format_interval());
 
-    let threat_block = Block::default()
+    let mut threat_block = Block::default()
         .borders(Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded)
         .border_style(Style::default().fg(DARK_GRAY))
         .title(Span::styled(
-            format!(" 󱖙 SECURITY ALERTS ({}) ", interval_text),
+            format!(" {}SECURITY ALERTS ({}) ", app.icons.pad(Icon::Alerts), interval_text),
             Style::default().fg(PURPLE).add_modifier(Modifier::BOLD)
         ));
+    // Load time per backend, so a slow manager or indexer is easy to spot
+    if let Some(timing) = app.dashboard_timing {
+        let slow = timing.total_ms >= crate::app::SLOW_LOAD_MS;
+        threat_block = threat_block.title(Title::from(Span::styled(
+            format!(" {}API {}ms · indexer {}ms ", app.icons.pad(Icon::Timer), timing.api_ms, timing.indexer_ms),
+            Style::default().fg(if slow { YELLOW } else { DARK_GRAY }),
+        )).alignment(Alignment::Right));
+    }
+
+    let threat_row = Layout::default()
+        .direction(stack)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(chunks[1]);
 
     let threat_layout = Layout::default()
         .direction(Direction::Horizontal)
@@ -88,41 +121,51 @@ pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
             Constraint::Percentage(25),
         ])
         .margin(1)
-        .split(chunks[1]);
+        .split(threat_row[0]);
 
-    f.render_widget(threat_block, chunks[1]);
+    f.render_widget(threat_block, threat_row[0]);
+    draw_alert_trend(f, app, threat_row[1]);
 
-    // Severity cards - minimal style
-    let create_severity_card = |label: &'static str, count: u32, color: ratatui::style::Color, key: char| {
+    // Severity cards - minimal style, with the level range of each band
+    let create_severity_card = |severity: Severity, count: u32, color: ratatui::style::Color, key: char| {
         let lines = vec![
             Line::from(Span::styled(
                 format!("{}", count),
                 Style::default().fg(color).add_modifier(Modifier::BOLD)
             )),
-            Line::from(Span::styled(label, Style::default().fg(FG))),
+            Line::from(Span::styled(severity.label(), Style::default().fg(FG))),
             Line::from(Span::styled(
-                format!("[{}]", key),
+                format!("[{}] {}", key, app.severity.range_label(severity)),
                 Style::default().fg(DARK_GRAY)
             )),
         ];
         Paragraph::new(lines).alignment(Alignment::Center)
     };
 
-    f.render_widget(create_severity_card("Critical", app.threat_stats.critical, VULN_CRITICAL, '1'), threat_layout[0]);
-    f.render_widget(create_severity_card("High", app.threat_stats.high, VULN_HIGH, '2'), threat_layout[1]);
-    f.render_widget(create_severity_card("Medium", app.threat_stats.medium, VULN_MEDIUM, '3'), threat_layout[2]);
-    f.render_widget(create_severity_card("Low", app.threat_stats.low, VULN_LOW, '4'), threat_layout[3]);
+    f.render_widget(create_severity_card(Severity::Critical, app.threat_stats.critical, VULN_CRITICAL, '1'), threat_layout[0]);
+    f.render_widget(create_severity_card(Severity::High, app.threat_stats.high, VULN_HIGH, '2'), threat_layout[1]);
+    f.render_widget(create_severity_card(Severity::Medium, app.threat_stats.medium, VULN_MEDIUM, '3'), threat_layout[2]);
+    f.render_widget(create_severity_card(Severity::Low, app.threat_stats.low, VULN_LOW, '4'), threat_layout[3]);
 
     // ─────────────────────────────────────────────────────────────────────────
-    // BOTTOM SECTION - Top Attacked Agents / Quick Actions
+    // BOTTOM SECTION - Top Attacked Agents / OS Distribution / Keep-alives & Quick Actions
     // ─────────────────────────────────────────────────────────────────────────
     let bottom_layout = Layout::default()
-        .direction(Direction::Horizontal)
+        .direction(stack)
+        .constraints([
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+        ])
+        .split(chunks[2]);
+
+    let left_column = Layout::default()
+        .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(50),
             Constraint::Percentage(50),
         ])
-        .split(chunks[2]);
+        .split(bottom_layout[0]);
 
     // Top Attacked Agents Table
     if !app.top_agents.is_empty() {
@@ -130,7 +173,7 @@ pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
             .borders(Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(Style::default().fg(DARK_GRAY))
-            .title(Span::styled(" 󰀦 TOP ATTACKED AGENTS ", Style::default().fg(RED)));
+            .title(Span::styled(format!(" {}TOP ATTACKED AGENTS ", app.icons.pad(Icon::Warning)), Style::default().fg(RED)));
 
         let rows = app.top_agents.iter().enumerate().map(|(i, (name, count))| {
             let rank_style = match i {
@@ -157,19 +200,19 @@ pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
         )
         .block(top_block);
 
-        f.render_widget(table, bottom_layout[0]);
+        f.render_widget(table, left_column[0]);
     } else {
         let empty_block = Block::default()
             .borders(Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(Style::default().fg(DARK_GRAY))
-            .title(Span::styled(" 󰀦 TOP ATTACKED AGENTS ", Style::default().fg(RED)));
+            .title(Span::styled(format!(" {}TOP ATTACKED AGENTS ", app.icons.pad(Icon::Warning)), Style::default().fg(RED)));
 
         let empty_msg = Paragraph::new("\n\n  No alert data available.\n  Press [r] to refresh.")
             .style(Style::default().fg(DARK_GRAY))
             .block(empty_block);
 
-        f.render_widget(empty_msg, bottom_layout[0]);
+        f.render_widget(empty_msg, left_column[0]);
     }
 
     // Quick Actions / Help Panel
@@ -177,7 +220,7 @@ pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
         .borders(Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded)
         .border_style(Style::default().fg(DARK_GRAY))
-        .title(Span::styled(" 󰋗 QUICK ACTIONS ", Style::default().fg(CYAN)));
+        .title(Span::styled(format!(" {}QUICK ACTIONS ", app.icons.pad(Icon::Actions)), Style::default().fg(CYAN)));
 
     let help_content = vec![
         Line::from(""),
@@ -193,6 +236,10 @@ pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
             Span::styled("  [1-4]     ", Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
             Span::styled("View alerts by severity", Style::default().fg(FG)),
         ]),
+        Line::from(vec![
+            Span::styled("  [↑/↓ ⏎]   ", Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
+            Span::styled("Inspect disconnected agent", Style::default().fg(FG)),
+        ]),
         Line::from(vec![
             Span::styled("  [r]       ", Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
             Span::styled("Refresh data", Style::default().fg(FG)),
@@ -201,6 +248,18 @@ pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
             Span::styled("  [i]       ", Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
             Span::styled("Set time interval", Style::default().fg(FG)),
         ]),
+        Line::from(vec![
+            Span::styled("  [H]       ", Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
+            Span::styled("Alert trend range", Style::default().fg(FG)),
+        ]),
+        Line::from(vec![
+            Span::styled("  [d]       ", Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
+            Span::styled("Duplicate agents", Style::default().fg(FG)),
+        ]),
+        Line::from(vec![
+            Span::styled("  [e]       ", Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
+            Span::styled("Export Markdown report", Style::default().fg(FG)),
+        ]),
         Line::from(vec![
             Span::styled("  [Ctrl+P]  ", Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
             Span::styled("Command palette", Style::default().fg(FG)),
@@ -211,6 +270,338 @@ pub fn draw_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
         ]),
     ];
 
+    let right_column = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ])
+        .split(bottom_layout[2]);
+
     let help_para = Paragraph::new(help_content).block(help_block);
-    f.render_widget(help_para, bottom_layout[1]);
+    f.render_widget(help_para, right_column[1]);
+    draw_oldest_keepalives(f, app, right_column[0]);
+
+    let middle_column = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ])
+        .split(bottom_layout[1]);
+
+    draw_os_distribution(f, app, middle_column[0]);
+    draw_top_countries(f, app, middle_column[1]);
+    draw_recently_disconnected(f, app, left_column[1]);
+}
+
+/// Fleet hygiene warning in the agents box: registrations sharing a name or an IP, most often
+/// left behind by re-imaged hosts. Blank when there are none.
+fn duplicates_line(app: &App) -> Line<'static> {
+    let sets = app.duplicate_agents();
+    if sets.is_empty() {
+        return Line::from("");
+    }
+    let names = sets.iter().filter(|s| s.kind == crate::app::hygiene::DuplicateKind::Name).count();
+    let ips = sets.len() - names;
+    let mut parts = Vec::new();
+    if names > 0 {
+        parts.push(format!("{} duplicate name{}", names, if names == 1 { "" } else { "s" }));
+    }
+    if ips > 0 {
+        parts.push(format!("{} shared IP{}", ips, if ips == 1 { "" } else { "s" }));
+    }
+    Line::from(vec![
+        Span::styled(format!("  ⚠ {}", parts.join(", ")), Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
+        Span::styled("  [d] details", Style::default().fg(DARK_GRAY)),
+    ])
+}
+
+/// Alert histogram: per-minute totals of the loaded alerts, or per-day counts stacked
+/// by severity (critical at the bottom) for the 7 and 30 day ranges.
+fn draw_alert_trend(f: &mut Frame, app: &App, area: Rect) {
+    let span = match app.trend_range.days() {
+        Some(days) => format!("{} days", days),
+        None => app.format_interval(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(Span::styled(format!(" {}ALERT TREND ({}) [H] ", app.icons.pad(Icon::Trend), span), Style::default().fg(PURPLE).add_modifier(Modifier::BOLD)));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let colors = [VULN_CRITICAL, VULN_HIGH, VULN_MEDIUM, VULN_LOW];
+    let columns: Vec<(String, Vec<u64>)> = match app.trend_range.days() {
+        Some(_) => app.alert_trend.iter().map(|(day, s)| {
+            // yyyy-MM-dd -> MM-dd
+            (day.get(5..).unwrap_or(day).to_string(), vec![s.critical as u64, s.high as u64, s.medium as u64, s.low as u64])
+        }).collect(),
+        None => app.alert_buckets.iter().map(|(label, n)| (label.clone(), vec![*n])).collect(),
+    };
+    if columns.is_empty() || inner.height < 2 || inner.width < 4 {
+        let text = if app.trend_range.days().is_some() { "  Loading alert trend..." } else { "  No alert data available." };
+        f.render_widget(Paragraph::new(text).style(Style::default().fg(DARK_GRAY)), inner);
+        return;
+    }
+
+    // Newest columns win when there are more than fit; one cell gap between wider bars
+    let bar_width = (inner.width as usize / columns.len()).clamp(1, 4);
+    let step = if bar_width > 1 { bar_width } else { 1 };
+    let visible = (inner.width as usize / step).min(columns.len());
+    let columns = &columns[columns.len() - visible..];
+    let max = columns.iter().map(|(_, v)| v.iter().sum::<u64>()).max().unwrap_or(0);
+    let bar_height = inner.height - 1;
+
+    let buf = f.buffer_mut();
+    for (i, (_, values)) in columns.iter().enumerate() {
+        let x = inner.x + (i * step) as u16;
+        for (row, segment) in stacked_bar_cells(values, bar_height, max).into_iter().enumerate() {
+            let Some(segment) = segment else { break };
+            let color = if values.len() == 1 { PURPLE } else { colors[segment] };
+            let y = inner.y + bar_height - 1 - row as u16;
+            for dx in 0..step.saturating_sub(1).max(1) as u16 {
+                buf.get_mut(x + dx, y).set_char('█').set_style(Style::default().fg(color));
+            }
+        }
+    }
+
+    // Axis: first and last column labels plus the peak count
+    let first = &columns[0].0;
+    let last = &columns[columns.len() - 1].0;
+    let peak = format!("max {}", max);
+    let gap = (inner.width as usize).saturating_sub(first.len() + last.len() + peak.len()) / 2;
+    let axis = Line::from(vec![
+        Span::styled(first.clone(), Style::default().fg(DARK_GRAY)),
+        Span::raw(" ".repeat(gap)),
+        Span::styled(peak, Style::default().fg(FG)),
+        Span::raw(" ".repeat(gap)),
+        Span::styled(if visible > 1 { last.clone() } else { String::new() }, Style::default().fg(DARK_GRAY)),
+    ]);
+    f.render_widget(Paragraph::new(axis), Rect { y: inner.y + bar_height, height: 1, ..inner });
+}
+
+/// Fleet-wide CVE counts by severity with a proportional bar.
+fn draw_vuln_summary(f: &mut Frame, app: &App, area: Rect) {
+    let v = &app.vuln_summary;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(Span::styled(format!(" {}VULNERABILITIES ", app.icons.pad(Icon::Shield)), Style::default().fg(RED).add_modifier(Modifier::BOLD)));
+
+    let parts = [
+        (v.critical, VULN_CRITICAL),
+        (v.high, VULN_HIGH),
+        (v.medium, VULN_MEDIUM),
+        (v.low, VULN_LOW),
+        (v.untriaged, DARK_GRAY),
+    ];
+    let total: u32 = parts.iter().map(|(n, _)| n).sum();
+
+    let mut content = vec![
+        Line::from(vec![
+            Span::styled("  Crit: ", Style::default().fg(FG)),
+            Span::styled(format!("{:<6}", v.critical), Style::default().fg(VULN_CRITICAL).add_modifier(Modifier::BOLD)),
+            Span::styled("High: ", Style::default().fg(FG)),
+            Span::styled(format!("{:<6}", v.high), Style::default().fg(VULN_HIGH).add_modifier(Modifier::BOLD)),
+            Span::styled("Med: ", Style::default().fg(FG)),
+            Span::styled(format!("{:<6}", v.medium), Style::default().fg(VULN_MEDIUM)),
+            Span::styled("Low: ", Style::default().fg(FG)),
+            Span::styled(format!("{}", v.low), Style::default().fg(VULN_LOW)),
+        ]),
+        Line::from(""),
+    ];
+
+    if total == 0 {
+        content.push(Line::from(Span::styled("  No vulnerability data", Style::default().fg(DARK_GRAY))));
+    } else {
+        let bar_width = area.width.saturating_sub(16) as u32;
+        let mut bar = vec![Span::raw("  ")];
+        for (n, color) in parts {
+            // Round to the nearest cell, but keep every non-zero severity visible
+            let cells = ((n * bar_width * 2 + total) / (2 * total)).max(1) as usize;
+            if n > 0 {
+                bar.push(Span::styled("▓".repeat(cells), Style::default().fg(color)));
+            }
+        }
+        bar.push(Span::styled(format!(" {} total", total), Style::default().fg(FG).add_modifier(Modifier::BOLD)));
+        content.push(Line::from(bar));
+    }
+
+    f.render_widget(Paragraph::new(content).block(block), area);
+}
+
+fn draw_recently_disconnected(f: &mut Frame, app: &App, area: Rect) {
+    let agents = app.recently_disconnected();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(Span::styled(
+            format!(" {}DISCONNECTED (last {}h) ", app.icons.pad(Icon::Cross), app.disconnected_window_hours),
+            Style::default().fg(RED),
+        ));
+
+    if agents.is_empty() {
+        let empty_msg = Paragraph::new("\n  No agents disconnected recently.")
+            .style(Style::default().fg(DARK_GRAY))
+            .block(block);
+        f.render_widget(empty_msg, area);
+        return;
+    }
+
+    let window = table_window(agents.len(), Some(app.disconnected_index), area);
+    let rows = agents[window.start..window.end].iter().map(|a| {
+        Row::new(vec![
+            Cell::from(a.id.clone()).style(Style::default().fg(DARK_GRAY)),
+            Cell::from(a.name.clone()).style(Style::default().fg(FG)),
+            Cell::from(format_last_keep_alive(&a.last_keep_alive)).style(Style::default().fg(RED)),
+        ])
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Length(6),
+        Constraint::Min(15),
+        Constraint::Length(10),
+    ])
+    .header(
+        Row::new(vec!["ID", "Agent", "Lost"])
+            .style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD))
+    )
+    .block(block)
+    .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+    .highlight_symbol(app.icons.pad(Icon::Pointer));
+
+    let mut state = window.state;
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+/// Active agents with the oldest keep-alive; those past the threshold are red.
+fn draw_oldest_keepalives(f: &mut Frame, app: &App, area: Rect) {
+    let agents = app.oldest_keepalives();
+    let stale = agents.iter().filter(|(_, stale)| *stale).count();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(Span::styled(
+            format!(" {}OLDEST KEEP-ALIVE ({} over {}m) ", app.icons.pad(Icon::Hourglass), stale, app.stale_keepalive_minutes),
+            Style::default().fg(YELLOW),
+        ));
+
+    if agents.is_empty() {
+        let empty_msg = Paragraph::new("\n  No active agents.")
+            .style(Style::default().fg(DARK_GRAY))
+            .block(block);
+        f.render_widget(empty_msg, area);
+        return;
+    }
+
+    let rows = agents.iter().map(|(a, stale)| {
+        let age_style = if *stale {
+            Style::default().fg(RED).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(GREEN)
+        };
+        Row::new(vec![
+            Cell::from(a.id.clone()).style(Style::default().fg(DARK_GRAY)),
+            Cell::from(a.name.clone()).style(Style::default().fg(if *stale { RED } else { FG })),
+            Cell::from(format_last_keep_alive(&a.last_keep_alive)).style(age_style),
+        ])
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Length(6),
+        Constraint::Min(15),
+        Constraint::Length(10),
+    ])
+    .header(
+        Row::new(vec!["ID", "Agent", "Seen"])
+            .style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD))
+    )
+    .block(block);
+
+    f.render_widget(table, area);
+}
+
+fn draw_top_countries(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(Span::styled(format!(" {}TOP SOURCE COUNTRIES ", app.icons.pad(Icon::Globe)), Style::default().fg(ORANGE)));
+
+    if app.top_countries.is_empty() {
+        let empty_msg = Paragraph::new("\n  No GeoIP data in recent alerts.")
+            .style(Style::default().fg(DARK_GRAY))
+            .block(block);
+        f.render_widget(empty_msg, area);
+        return;
+    }
+
+    let rows = app.top_countries.iter().enumerate().map(|(i, (country, count))| {
+        Row::new(vec![
+            Cell::from(format!(" {}.", i + 1)).style(Style::default().fg(DARK_GRAY)),
+            Cell::from(country.clone()).style(Style::default().fg(FG)),
+            Cell::from(format!("{}", count)).style(Style::default().fg(ORANGE).add_modifier(Modifier::BOLD)),
+        ])
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Length(4),
+        Constraint::Min(12),
+        Constraint::Length(8),
+    ])
+    .header(
+        Row::new(vec![" #", "Country", "Alerts"])
+            .style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD))
+    )
+    .block(block);
+
+    f.render_widget(table, area);
+}
+
+fn draw_os_distribution(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(Span::styled(format!(" {}OS DISTRIBUTION ", app.icons.pad(Icon::Os)), Style::default().fg(GREEN)));
+
+    let mut dist = app.os_distribution();
+    if dist.is_empty() {
+        let empty_msg = Paragraph::new("\n\n  No agents loaded.")
+            .style(Style::default().fg(DARK_GRAY))
+            .block(block);
+        f.render_widget(empty_msg, area);
+        return;
+    }
+
+    // Fold the tail into "Other" so the widget never overflows its box
+    let max_rows = area.height.saturating_sub(2).max(1) as usize;
+    if dist.len() > max_rows {
+        let other: usize = dist[max_rows - 1..].iter().map(|(_, c)| c).sum();
+        dist.truncate(max_rows - 1);
+        dist.push(("Other".to_string(), other));
+    }
+
+    let label_width = 16;
+    let count_width = 6;
+    let bar_max = (area.width as usize).saturating_sub(label_width + count_width + 4).max(1);
+    let max_count = dist.iter().map(|(_, c)| *c).max().unwrap_or(1).max(1);
+    let palette = [BLUE, GREEN, YELLOW, PURPLE, CYAN, ORANGE];
+
+    let lines: Vec<Line> = dist.iter().enumerate().map(|(i, (name, count))| {
+        let label: String = name.chars().take(label_width - 1).collect();
+        let bar_len = ((count * bar_max) / max_count).max(1);
+        Line::from(vec![
+            Span::styled(format!(" {:<width$}", label, width = label_width), Style::default().fg(FG)),
+            Span::styled("▇".repeat(bar_len), Style::default().fg(palette[i % palette.len()])),
+            Span::styled(format!(" {}", count), Style::default().fg(FG).add_modifier(Modifier::BOLD)),
+        ])
+    }).collect();
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
 }
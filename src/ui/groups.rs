@@ -2,10 +2,12 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     widgets::{Block, Borders, Paragraph, Row, Table, Cell},
+    text::{Line, Span},
     Frame,
 };
-use crate::app::App;
+use crate::app::{App, GroupPosture};
 use crate::ui::theme::*;
+use crate::ui::icons::Icon;
 use crate::ui::common::filter_matches;
 
 pub fn draw_group_management(f: &mut Frame, app: &mut App, area: Rect) {
@@ -26,18 +28,30 @@ pub fn draw_group_management(f: &mut Frame, app: &mut App, area: Rect) {
         app.groups.iter().collect()
     };
 
+    // Posture mode adds the critical/high alert counts of every group loaded so far
     let rows = filtered_groups.iter().map(|g| {
-        Row::new(vec![
+        let mut cells = vec![
             Cell::from(g.name.clone()),
             Cell::from(g.count.map(|c| c.to_string()).unwrap_or_else(|| "0".to_string())),
-        ]).style(Style::default().fg(FG))
+        ];
+        if app.group_posture {
+            cells.push(match app.group_postures.get(&g.name) {
+                Some(Some(p)) => Cell::from(format!("{}/{}", p.alerts.critical, p.alerts.high))
+                    .style(Style::default().fg(if p.alerts.critical > 0 { VULN_CRITICAL } else if p.alerts.high > 0 { VULN_HIGH } else { GREEN })),
+                Some(None) => Cell::from("…").style(Style::default().fg(DARK_GRAY)),
+                None => Cell::from("-").style(Style::default().fg(DARK_GRAY)),
+            });
+        }
+        Row::new(cells).style(Style::default().fg(FG))
     });
 
-    let table = Table::new(rows, [
-        Constraint::Min(20),
-        Constraint::Length(10),
-    ])
-    .header(Row::new(vec!["Group Name", "Agents"]).style(Style::default().fg(BLUE)))
+    let (widths, header) = if app.group_posture {
+        (vec![Constraint::Min(16), Constraint::Length(7), Constraint::Length(9)], vec!["Group Name", "Agents", "Crit/High"])
+    } else {
+        (vec![Constraint::Min(20), Constraint::Length(10)], vec!["Group Name", "Agents"])
+    };
+    let table = Table::new(rows, widths)
+    .header(Row::new(header).style(Style::default().fg(BLUE)))
     .block(Block::default()
         .borders(Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded)
@@ -46,7 +60,7 @@ pub fn draw_group_management(f: &mut Frame, app: &mut App, area: Rect) {
     .highlight_style(Style::default()
         .bg(SELECTION_BG)
         .add_modifier(Modifier::BOLD))
-    .highlight_symbol("󰁔 ");
+    .highlight_symbol(app.icons.pad(Icon::Pointer));
 
     let mut state = app.groups_table_state.clone();
     f.render_stateful_widget(table, chunks[0], &mut state);
@@ -56,15 +70,36 @@ pub fn draw_group_management(f: &mut Frame, app: &mut App, area: Rect) {
         .and_then(|idx| filtered_groups.get(idx));
 
     if let Some(group) = selected_group {
+        // Shared files once the group has been opened with Enter
+        let files_loaded = app.group_files_group.as_deref() == Some(group.name.as_str());
+        let members_area = if app.group_posture {
+            let split = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(10), Constraint::Min(0)])
+                .split(chunks[1]);
+            draw_group_posture(f, app, &group.name, split[0]);
+            split[1]
+        } else {
+            chunks[1]
+        };
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(if files_loaded {
+                [Constraint::Percentage(60), Constraint::Percentage(40)]
+            } else {
+                [Constraint::Percentage(100), Constraint::Length(0)]
+            })
+            .split(members_area);
+
         let group_agents: Vec<_> = app.agents.iter()
             .filter(|a| a.group.as_ref().map(|g| g.contains(&group.name)).unwrap_or(false))
             .collect();
 
         let agent_rows = group_agents.iter().map(|a| {
             let (icon, color) = match a.status.as_str() {
-                "active" => ("󰄬 ", GREEN),
-                "disconnected" => ("󰅖 ", RED),
-                _ => ("󰒲 ", FG),
+                "active" => (app.icons.pad(Icon::Check), GREEN),
+                "disconnected" => (app.icons.pad(Icon::Cross), RED),
+                _ => (app.icons.pad(Icon::Pending), FG),
             };
             Row::new(vec![
                 Cell::from(a.id.clone()),
@@ -87,7 +122,11 @@ pub fn draw_group_management(f: &mut Frame, app: &mut App, area: Rect) {
             .border_style(Style::default().fg(DARK_GRAY))
             .title(format!(" Agents in Group: {} ", group.name)));
         
-        f.render_widget(agent_table, chunks[1]);
+        f.render_widget(agent_table, right[0]);
+
+        if files_loaded {
+            draw_group_files(f, app, right[1]);
+        }
     } else {
         let placeholder = Paragraph::new("\n\n Select a group from the list to view its members. ")
             .alignment(ratatui::layout::Alignment::Center)
@@ -100,3 +139,103 @@ pub fn draw_group_management(f: &mut Frame, app: &mut App, area: Rect) {
         f.render_widget(placeholder, chunks[1]);
     }
 }
+
+/// Alert and CVE totals for the group next to its most frequent rules.
+fn draw_group_posture(f: &mut Frame, app: &App, group: &str, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(PURPLE))
+        .title(Span::styled(
+            format!(" {}Posture: {} (last {}) [P] Hide ", app.icons.pad(Icon::Shield), group, app.format_interval()),
+            Style::default().fg(PURPLE).add_modifier(Modifier::BOLD),
+        ));
+
+    let Some(Some(posture)) = app.group_postures.get(group) else {
+        let text = if app.group_postures.contains_key(group) { "\n  Loading posture… [r] Retry" } else { "\n  Loading posture…" };
+        f.render_widget(Paragraph::new(text).style(Style::default().fg(DARK_GRAY)).block(block), area);
+        return;
+    };
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(36), Constraint::Min(0)])
+        .split(inner);
+
+    f.render_widget(Paragraph::new(posture_lines(posture)), cols[0]);
+
+    let rows = posture.top_rules.iter().map(|r| {
+        Row::new(vec![
+            Cell::from(r.id.clone()).style(Style::default().fg(CYAN)),
+            Cell::from(r.description.clone()).style(Style::default().fg(FG)),
+            Cell::from(r.count.to_string()).style(Style::default().fg(ORANGE).add_modifier(Modifier::BOLD)),
+        ])
+    });
+    let rules = Table::new(rows, [
+        Constraint::Length(8),
+        Constraint::Min(20),
+        Constraint::Length(7),
+    ])
+    .header(Row::new(vec!["Rule", "Top rules", "Alerts"]).style(Style::default().fg(BLUE)));
+    f.render_widget(rules, cols[1]);
+}
+
+fn posture_lines(posture: &GroupPosture) -> Vec<Line<'static>> {
+    let count = |label: &'static str, n: u32, color| vec![
+        Span::styled(format!("{:<9}", label), Style::default().fg(FG)),
+        Span::styled(format!("{:>6}", n), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+    ];
+    let row = |left: Vec<Span<'static>>, right: Vec<Span<'static>>| {
+        let mut spans = vec![Span::raw(" ")];
+        spans.extend(left);
+        spans.push(Span::raw("   "));
+        spans.extend(right);
+        Line::from(spans)
+    };
+    let (a, v) = (&posture.alerts, &posture.vulns);
+    vec![
+        Line::from(vec![
+            Span::styled(format!(" {:<18}", "Alerts"), Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
+            Span::styled("CVEs", Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
+        ]),
+        row(count("Critical", a.critical, VULN_CRITICAL), count("Critical", v.critical, VULN_CRITICAL)),
+        row(count("High", a.high, VULN_HIGH), count("High", v.high, VULN_HIGH)),
+        row(count("Medium", a.medium, VULN_MEDIUM), count("Medium", v.medium, VULN_MEDIUM)),
+        row(count("Low", a.low, VULN_LOW), count("Low", v.low, VULN_LOW)),
+        Line::from(Span::styled(format!(" across {} agents", posture.agents), Style::default().fg(DARK_GRAY))),
+    ]
+}
+
+fn draw_group_files(f: &mut Frame, app: &App, area: Rect) {
+    let border = if app.group_files_focus { BLUE } else { DARK_GRAY };
+    let rows = app.group_files.iter().map(|file| {
+        Row::new(vec![
+            Cell::from(file.filename.clone()),
+            Cell::from(file.hash.clone().unwrap_or_default()),
+        ]).style(Style::default().fg(FG))
+    });
+
+    let hint = if app.group_files_focus { "[Enter] View  [←] Back" } else { "[→] Focus" };
+    let table = Table::new(rows, [
+        Constraint::Min(24),
+        Constraint::Length(34),
+    ])
+    .header(Row::new(vec!["File", "MD5"]).style(Style::default().fg(BLUE)))
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(border))
+        .title(format!(" Group Files ({}) {} ", app.group_files.len(), hint)))
+    .highlight_style(Style::default()
+        .bg(SELECTION_BG)
+        .add_modifier(Modifier::BOLD))
+    .highlight_symbol(app.icons.pad(Icon::Pointer));
+
+    let mut state = ratatui::widgets::TableState::default();
+    if app.group_files_focus {
+        state.select(Some(app.group_file_index));
+    }
+    f.render_stateful_widget(table, area, &mut state);
+}
@@ -0,0 +1,166 @@
+/// Glyphs the views draw their icons with, from `[display] icons`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IconSet {
+    /// Nerd Font glyphs
+    #[default]
+    Nerd,
+    /// Plain ASCII markers for terminals and fonts without the glyphs
+    Ascii,
+    /// No icons; only the selection pointer and sort arrows stay, as ASCII
+    None,
+}
+
+/// Every icon the views draw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    // Markers the tables need to stay usable: kept as ASCII without icons
+    Pointer,
+    SortAsc,
+    SortDesc,
+    Selected,
+    // States and levels: ASCII stand-ins, dropped without icons
+    Check,
+    Cross,
+    Pending,
+    New,
+    Info,
+    Warning,
+    Error,
+    Invalid,
+    Critical,
+    High,
+    Medium,
+    Low,
+    // Decoration next to titles and labels: Nerd Font only
+    App,
+    Dashboard,
+    Agent,
+    Alerts,
+    Group,
+    User,
+    File,
+    Rule,
+    Clock,
+    Search,
+    Filter,
+    Loading,
+    Export,
+    Fim,
+    Outdated,
+    Sites,
+    Health,
+    Shield,
+    Timer,
+    Actions,
+    Trend,
+    Globe,
+    Os,
+    Hourglass,
+    Disk,
+}
+
+impl Icon {
+    /// Nerd Font glyph and ASCII stand-in, each with the space that separates it from its label.
+    fn glyphs(&self) -> (&'static str, &'static str) {
+        match self {
+            Icon::Pointer => ("󰁔 ", "> "),
+            Icon::SortAsc => ("󰁞 ", "^ "),
+            Icon::SortDesc => ("󰁆 ", "v "),
+            Icon::Selected => ("󰄬 ", "* "),
+            Icon::Check => ("󰄬 ", "+ "),
+            Icon::Cross => ("󰅖 ", "x "),
+            Icon::Pending => ("󰒲 ", "~ "),
+            Icon::New => ("󰎔 ", "+ "),
+            Icon::Info => ("󰋼 ", "i "),
+            Icon::Warning => ("󰀦 ", "! "),
+            Icon::Error => ("󰅚 ", "x "),
+            Icon::Invalid => ("󰅙 ", "x "),
+            Icon::Critical => ("󰅚 ", "C "),
+            Icon::High => ("󰀦 ", "H "),
+            Icon::Medium => ("󱈸 ", "M "),
+            Icon::Low => ("󰋼 ", "L "),
+            Icon::App => ("󰆍 ", ""),
+            Icon::Dashboard => ("󱂬 ", ""),
+            Icon::Agent => ("󰒋 ", ""),
+            Icon::Alerts => ("󱖙 ", ""),
+            Icon::Group => ("󰒲 ", ""),
+            Icon::User => ("󰀉 ", ""),
+            Icon::File => ("󰈙 ", ""),
+            Icon::Rule => ("󰘦 ", ""),
+            Icon::Clock => ("󰥔 ", ""),
+            Icon::Search => ("󰍉 ", ""),
+            Icon::Filter => ("󰈲 ", ""),
+            Icon::Loading => ("󰑓 ", ""),
+            Icon::Export => ("󰈝 ", ""),
+            Icon::Fim => ("󰈞 ", ""),
+            Icon::Outdated => ("󰚰 ", ""),
+            Icon::Sites => ("󰒍 ", ""),
+            Icon::Health => ("󰓙 ", ""),
+            Icon::Shield => ("󰒃 ", ""),
+            Icon::Timer => ("󱎫 ", ""),
+            Icon::Actions => ("󰋗 ", ""),
+            Icon::Trend => ("󰄨 ", ""),
+            Icon::Globe => ("󰇧 ", ""),
+            Icon::Os => ("󰌽 ", ""),
+            Icon::Hourglass => ("󰔟 ", ""),
+            Icon::Disk => ("󰋊 ", ""),
+        }
+    }
+
+    fn structural(&self) -> bool {
+        matches!(self, Icon::Pointer | Icon::SortAsc | Icon::SortDesc | Icon::Selected)
+    }
+}
+
+impl IconSet {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "nerd" => Some(IconSet::Nerd),
+            "ascii" => Some(IconSet::Ascii),
+            "none" => Some(IconSet::None),
+            _ => None,
+        }
+    }
+
+    /// `[display] icons` from the config, falling back to Nerd Font glyphs when unset or invalid.
+    pub fn from_config(config: &crate::models::Config) -> Self {
+        config.display.icons.as_deref().and_then(Self::parse).unwrap_or_default()
+    }
+
+    /// The icon followed by a space, or nothing when the set has no icon for it.
+    pub fn pad(&self, icon: Icon) -> &'static str {
+        let (nerd, ascii) = icon.glyphs();
+        match self {
+            IconSet::Nerd => nerd,
+            IconSet::Ascii => ascii,
+            IconSet::None if icon.structural() => ascii,
+            IconSet::None => "",
+        }
+    }
+
+    /// The icon alone, e.g. for a marker column.
+    pub fn get(&self, icon: Icon) -> &'static str {
+        self.pad(icon).trim_end()
+    }
+
+    /// Arrow after the heading of the sorted column.
+    pub fn sort_arrow(&self, ascending: bool) -> &'static str {
+        self.get(if ascending { Icon::SortAsc } else { Icon::SortDesc })
+    }
+
+    /// Frames of the loading spinner; braille dots need more than a plain console font.
+    pub fn spinner(&self) -> &'static [&'static str] {
+        match self {
+            IconSet::Nerd => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+            IconSet::Ascii | IconSet::None => &["|", "/", "-", "\\"],
+        }
+    }
+
+    /// The icon alone, or `text` when the set has no icon for it.
+    pub fn get_or(&self, icon: Icon, text: &'static str) -> &'static str {
+        match self.get(icon) {
+            "" => text,
+            glyph => glyph,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Paragraph, Row, Table, Cell, TableState},
+    Frame,
+};
+use crate::app::App;
+use crate::ui::theme::*;
+use crate::ui::icons::Icon;
+
+pub fn draw_cdb_lists(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(30), // List files
+            Constraint::Percentage(70), // Entries of the selected list
+        ])
+        .split(area);
+
+    let rows = app.cdb_lists.iter().map(|l| {
+        Row::new(vec![
+            Cell::from(format!("{}{}", app.icons.pad(Icon::File), l.filename)),
+            Cell::from(l.items.len().to_string()),
+        ]).style(Style::default().fg(FG))
+    });
+
+    let lists_border = if app.cdb_entries_focus { DARK_GRAY } else { BLUE };
+    let table = Table::new(rows, [
+        Constraint::Min(16),
+        Constraint::Length(7),
+    ])
+    .header(Row::new(vec!["List", "Entries"]).style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD)))
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(lists_border))
+        .title(format!(" CDB Lists ({}) ", app.cdb_lists.len())))
+    .highlight_style(Style::default()
+        .bg(SELECTION_BG)
+        .add_modifier(Modifier::BOLD))
+    .highlight_symbol(app.icons.pad(Icon::Pointer));
+
+    let mut state = TableState::default();
+    if !app.cdb_lists.is_empty() {
+        state.select(Some(app.cdb_list_index));
+    }
+    f.render_stateful_widget(table, chunks[0], &mut state);
+
+    let entries_border = if app.cdb_entries_focus { BLUE } else { DARK_GRAY };
+    let Some(list) = app.selected_cdb_list() else {
+        let placeholder = Paragraph::new("\n\n No CDB lists loaded. Press [r] to refresh. ")
+            .alignment(ratatui::layout::Alignment::Center)
+            .style(Style::default().fg(GRAY))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(Style::default().fg(DARK_GRAY))
+                .title(" Entries "));
+        f.render_widget(placeholder, chunks[1]);
+        return;
+    };
+
+    let rows = list.items.iter().map(|e| {
+        Row::new(vec![
+            Cell::from(e.key.clone()),
+            Cell::from(e.value.clone()),
+        ]).style(Style::default().fg(FG))
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Percentage(50),
+        Constraint::Percentage(50),
+    ])
+    .header(Row::new(vec!["Key", "Value"]).style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD)))
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(entries_border))
+        .title(format!(" {}/{} ({}) ", list.relative_dirname, list.filename, list.items.len())))
+    .highlight_style(Style::default()
+        .bg(SELECTION_BG)
+        .add_modifier(Modifier::BOLD))
+    .highlight_symbol(app.icons.pad(Icon::Pointer));
+
+    let mut state = TableState::default();
+    if app.cdb_entries_focus {
+        state.select(Some(app.cdb_entry_index));
+    }
+    f.render_stateful_widget(table, chunks[1], &mut state);
+}
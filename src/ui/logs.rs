@@ -7,7 +7,9 @@ use ratatui::{
 };
 use crate::app::App;
 use crate::ui::theme::*;
-use crate::ui::json::{colorize_json, colorize_flat_json};
+use crate::ui::icons::Icon;
+use crate::app::json_tree::{self, TreeRow};
+use crate::ui::json::colorize_flat_json;
 
 pub fn draw_log_detail(f: &mut Frame, app: &mut App, log: &serde_json::Value, area: Rect) {
     f.render_widget(Clear, area);
@@ -15,7 +17,7 @@ pub fn draw_log_detail(f: &mut Frame, app: &mut App, log: &serde_json::Value, ar
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded)
-        .title(" 󱖙 Event Detail ")
+        .title(format!(" {}Event Detail ", app.icons.pad(Icon::Alerts)))
         .title_alignment(ratatui::layout::Alignment::Center)
         .border_style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD));
 
@@ -23,21 +25,59 @@ pub fn draw_log_detail(f: &mut Frame, app: &mut App, log: &serde_json::Value, ar
     let inner_area = block.inner(area);
     f.render_widget(block, area);
 
-    let lines: Vec<Line> = if app.show_log_json {
-        // Raw JSON with syntax highlighting
-        colorize_json(log)
-    } else {
+    if app.show_log_json {
+        draw_json_tree(f, app, log, inner_area);
+        let help = Paragraph::new(" [Enter] Field View │ [←/→] Fold │ [/] Search │ [n/N] Next/Prev │ [c] Context │ [p] Src IP │ [Esc] Close ")
+            .alignment(ratatui::layout::Alignment::Center)
+            .style(Style::default().fg(BLUE).bg(STATUS_BAR_BG));
+        let help_area = Rect::new(area.x, area.y + area.height - 1, area.width, 1);
+        f.render_widget(help, help_area);
+        return;
+    }
+
+    let lines: Vec<Line> = {
         // Flattened JSON with colored keys/values
-        let mut result = vec![
-            Line::from(vec![
-                Span::styled(" --- LOG FIELDS ---", Style::default().fg(BLUE).add_modifier(Modifier::BOLD))
-            ]),
-            Line::from(""),
+        let mut title = vec![
+            Span::styled(" --- LOG FIELDS ---", Style::default().fg(BLUE).add_modifier(Modifier::BOLD))
         ];
+        let source = log.get("_source").unwrap_or(log);
+        if let Some(ts) = source.get("@timestamp").and_then(|v| v.as_str()) {
+            title.push(Span::styled(
+                format!("  {}{} {}", app.icons.pad(Icon::Clock), app.display_tz.format(ts, "%Y-%m-%d %H:%M:%S"), app.display_tz.label()),
+                Style::default().fg(GRAY),
+            ));
+        }
+        if let Some(geo) = crate::app::geo::geo_label(source) {
+            let ip = source.get("data").and_then(|d| d.get("srcip")).and_then(|v| v.as_str()).unwrap_or("source");
+            title.push(Span::styled(format!("  {}{} ({})", app.icons.pad(Icon::Globe), ip, geo), Style::default().fg(CYAN)));
+        }
+        title.extend(detail_search_spans(app));
+        let document = match app.detail_document() {
+            Some((index, id)) => Line::from(vec![
+                Span::styled(" _index ", Style::default().fg(GRAY)),
+                Span::styled(index.to_string(), Style::default().fg(FG)),
+                Span::styled("  _id ", Style::default().fg(GRAY)),
+                Span::styled(id.to_string(), Style::default().fg(FG)),
+                Span::styled("  [y] Copy ID [u] Copy GET [o] Discover", Style::default().fg(GRAY)),
+            ]),
+            None => Line::from(""),
+        };
+        let mut result = vec![Line::from(title), document, Line::from("")];
         
         if let Some(obj) = log.get("_source").and_then(|s| s.as_object()) {
             result.extend(colorize_flat_json(obj, ""));
         }
+
+        let matches = app.detail_field_matches();
+        for (n, &idx) in matches.iter().enumerate() {
+            if let Some(line) = result.get_mut(idx) {
+                line.style = if n == app.detail_match_index {
+                    Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().bg(DARK_GRAY)
+                };
+            }
+        }
         result
     };
 
@@ -49,9 +89,103 @@ pub fn draw_log_detail(f: &mut Frame, app: &mut App, log: &serde_json::Value, ar
     f.render_widget(p, inner_area);
 
     // Mini help at bottom
-    let help = Paragraph::new(" [Enter] Toggle Raw JSON │ [Esc] Close Detail │ [↑/↓] Scroll ")
+    let help = Paragraph::new(" [Enter] Toggle Raw JSON │ [/] Search │ [n/N] Next/Prev │ [c] Context │ [p] Src IP │ [Esc] Close Detail │ [↑/↓] Scroll ")
         .alignment(ratatui::layout::Alignment::Center)
         .style(Style::default().fg(BLUE).bg(STATUS_BAR_BG));
     let help_area = Rect::new(area.x, area.y + area.height - 1, area.width, 1);
     f.render_widget(help, help_area);
 }
+
+/// Search prompt while typing, or the match counter once a query is set.
+fn detail_search_spans(app: &App) -> Vec<Span<'static>> {
+    if app.detail_is_searching {
+        vec![
+            Span::styled("  / ", Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{}█", app.detail_search_query), Style::default().fg(FG)),
+        ]
+    } else if !app.detail_search_query.is_empty() {
+        let total = app.detail_match_count();
+        let current = if total == 0 { 0 } else { app.detail_match_index.min(total - 1) + 1 };
+        vec![Span::styled(
+            format!("  [{}/{}] \"{}\"", current, total, app.detail_search_query),
+            Style::default().fg(YELLOW),
+        )]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Collapsible tree view of the raw event, with a path/search line on top.
+fn draw_json_tree(f: &mut Frame, app: &mut App, log: &serde_json::Value, area: Rect) {
+    let rows = json_tree::visible_rows(log, &app.json_tree.collapsed);
+    if app.json_tree.cursor >= rows.len() {
+        app.json_tree.cursor = rows.len().saturating_sub(1);
+    }
+    let cursor = app.json_tree.cursor;
+
+    let path = rows.get(cursor).map(|r| r.path.clone()).unwrap_or_default();
+    let mut header = vec![Span::styled(format!(" {}", path), Style::default().fg(CYAN))];
+    header.extend(detail_search_spans(app));
+    let header = Line::from(header);
+    f.render_widget(Paragraph::new(header), Rect::new(area.x, area.y, area.width, 1));
+
+    let body = Rect::new(area.x, area.y + 1, area.width, area.height.saturating_sub(2));
+    let height = body.height as usize;
+    let start = if cursor >= height { cursor + 1 - height } else { 0 };
+    let query = app.detail_search_query.to_lowercase();
+
+    let lines: Vec<Line> = rows.iter().enumerate().skip(start).take(height)
+        .map(|(i, row)| {
+            let mut line = tree_row_line(row, &query);
+            if i == cursor {
+                line.style = Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD);
+            }
+            line
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), body);
+}
+
+fn tree_row_line<'a>(row: &TreeRow, query: &str) -> Line<'a> {
+    let mut spans = vec![Span::raw("  ".repeat(row.depth + 1))];
+    let marker = match (row.is_container(), row.collapsed) {
+        (true, true) => "▸ ",
+        (true, false) => "▾ ",
+        _ => "  ",
+    };
+    spans.push(Span::styled(marker, Style::default().fg(GRAY)));
+
+    let hit = |text: &str| !query.is_empty() && text.to_lowercase().contains(query);
+    if let Some(key) = &row.key {
+        let mut style = Style::default().fg(JSON_KEY);
+        if hit(key) {
+            style = style.fg(BG).bg(YELLOW);
+        }
+        spans.push(Span::styled(key.clone(), style));
+        spans.push(Span::styled(": ", Style::default().fg(JSON_COLON)));
+    }
+
+    match row.value {
+        serde_json::Value::Object(map) => {
+            spans.push(Span::styled(format!("{{{}}}", map.len()), Style::default().fg(JSON_BRACKET)));
+        }
+        serde_json::Value::Array(arr) => {
+            spans.push(Span::styled(format!("[{}]", arr.len()), Style::default().fg(JSON_BRACKET)));
+        }
+        value => {
+            let color = match value {
+                serde_json::Value::String(_) => JSON_STRING,
+                serde_json::Value::Number(_) => JSON_NUMBER,
+                serde_json::Value::Bool(_) => JSON_BOOL,
+                _ => JSON_NULL,
+            };
+            let text = json_tree::scalar_text(value);
+            let mut style = Style::default().fg(color);
+            if hit(&text) {
+                style = style.fg(BG).bg(YELLOW);
+            }
+            spans.push(Span::styled(text, style));
+        }
+    }
+    Line::from(spans)
+}
@@ -5,8 +5,15 @@ pub mod security;
 pub mod groups;
 pub mod popups;
 pub mod common;
+pub mod icons;
 pub mod json;
 pub mod logs;
+pub mod users;
+pub mod lists;
+pub mod rules;
+
+#[cfg(test)]
+mod tests;
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -17,10 +24,15 @@ use ratatui::{
 };
 use crate::app::{App, ActiveView, InspectorTab, ConfigStep};
 use crate::ui::theme::*;
+use crate::ui::icons::Icon;
+use crate::ui::common::is_narrow;
 use crate::ui::dashboard::draw_dashboard;
 use crate::ui::agents::{draw_agent_list, draw_agent_inspector};
 use crate::ui::security::draw_security_events;
 use crate::ui::groups::draw_group_management;
+use crate::ui::users::draw_user_management;
+use crate::ui::lists::draw_cdb_lists;
+use crate::ui::rules::draw_rule_files;
 use crate::ui::logs::draw_log_detail;
 use crate::ui::popups::{draw_popup, draw_interval_popup};
 
@@ -39,17 +51,23 @@ pub fn draw(f: &mut Frame, app: &mut App) {
     draw_notifications(f, app);
 
     // --- HEADER ---
-    let titles = vec![
-        " 󱂬 Dashboard ", 
-        " 󰒋 Agents ", 
-        " 󱖙 Security Events ", 
-        " 󰒲 Groups ",
-    ];
+    let titles: Vec<String> = [
+        (Icon::Dashboard, "Dashboard"),
+        (Icon::Agent, "Agents"),
+        (Icon::Alerts, "Security Events"),
+        (Icon::Group, "Groups"),
+        (Icon::User, "Users"),
+        (Icon::File, "Lists"),
+        (Icon::Rule, "Rules"),
+    ].iter().map(|(icon, name)| format!(" {}{} ", app.icons.pad(*icon), name)).collect();
     let active_tab = match app.active_view {
         ActiveView::Dashboard => 0,
         ActiveView::AgentList | ActiveView::AgentInspector => 1,
         ActiveView::SecurityEvents => 2,
         ActiveView::GroupManagement => 3,
+        ActiveView::UserManagement => 4,
+        ActiveView::CdbLists => 5,
+        ActiveView::Rules => 6,
     };
 
     let (id_count, active_count) = match app.active_view {
@@ -85,18 +103,52 @@ pub fn draw(f: &mut Frame, app: &mut App) {
                 "Groups".to_string()
             }
         },
+        ActiveView::UserManagement => {
+            if let Some(user) = app.get_selected_user() {
+                format!("Users > {}", user.username)
+            } else {
+                "Users".to_string()
+            }
+        },
+        ActiveView::CdbLists => {
+            if let Some(list) = app.selected_cdb_list() {
+                format!("Lists > {}", list.filename)
+            } else {
+                "Lists".to_string()
+            }
+        },
+        ActiveView::Rules => {
+            let section = if app.ruleset_kind == crate::app::rules::RulesetKind::Rules { "Rules" } else { "Decoders" };
+            if let Some(file) = app.selected_rule_file() {
+                format!("{} > {}", section, file.filename)
+            } else {
+                section.to_string()
+            }
+        },
     };
 
+    // Prefix the views Esc/Backspace walks back through (last three)
+    let trail_start = app.nav_stack.len().saturating_sub(3);
+    let mut trail: Vec<&str> = app.nav_stack[trail_start..].iter().map(|e| e.label()).collect();
+    if trail_start > 0 {
+        trail.insert(0, "…");
+    }
+    trail.push(&breadcrumb);
+    let breadcrumb = trail.join(" » ");
+
     let header_block = Block::default()
         .borders(Borders::BOTTOM)
         .border_style(Style::default().fg(DARK_GRAY))
-        .title(format!(" 󰆍 WAZUH TUI v0.1.0 │ {} │ View: {}/{} Active ", 
+        .title(format!(" {}WAZUH TUI v0.1.0 │ {} │ View: {}/{} Active │ {}{} ",
+            app.icons.pad(Icon::App),
             breadcrumb,
-            active_count, id_count
+            active_count, id_count,
+            app.icons.pad(Icon::Clock),
+            app.display_tz.label()
         ));
 
     let tabs = Tabs::new(titles)
-        .block(header_block)
+        .block(header_block.title(connection_title(app)))
         .select(active_tab)
         .style(Style::default().fg(FG))
         .highlight_style(
@@ -116,13 +168,15 @@ pub fn draw(f: &mut Frame, app: &mut App) {
             } else {
                 Style::default().fg(DARK_GRAY)
             })
-            .title(format!(" 󰍉 FILTERING {} ", match app.active_view {
+            .title(format!(" {}FILTERING {} ", app.icons.pad(Icon::Search), match app.active_view {
                 ActiveView::AgentList => "AGENTS",
                 ActiveView::SecurityEvents => "SECURITY EVENTS",
                 ActiveView::AgentInspector => match app.inspector_tab {
                     InspectorTab::Processes => "PROCESSES",
                     InspectorTab::Programs => "PROGRAMS",
+                    InspectorTab::Rootcheck => "ROOTCHECK",
                     InspectorTab::Logs => "LOGS",
+                    InspectorTab::Network => "CONNECTIONS",
                     _ => "DETAILS",
                 },
                 _ => "CONTENT",
@@ -164,6 +218,9 @@ pub fn draw(f: &mut Frame, app: &mut App) {
             ActiveView::AgentInspector => draw_agent_inspector(f, app, content_area),
             ActiveView::SecurityEvents => draw_security_events(f, app, content_area),
             ActiveView::GroupManagement => draw_group_management(f, app, content_area),
+            ActiveView::UserManagement => draw_user_management(f, app, content_area),
+            ActiveView::CdbLists => draw_cdb_lists(f, app, content_area),
+            ActiveView::Rules => draw_rule_files(f, app, content_area),
         }
     }
 
@@ -188,33 +245,133 @@ pub fn draw(f: &mut Frame, app: &mut App) {
         Span::styled(" [r] Refresh ", Style::default().fg(BLUE)),
     ];
 
+    // Actions the current user lacks RBAC permission for are greyed out
+    let gated = |action: &str| if app.is_allowed(action) { Style::default().fg(YELLOW) } else { Style::default().fg(DARK_GRAY) };
+
     if app.active_view == ActiveView::AgentList {
         footer_spans.push(Span::styled(" [Space] Select ", Style::default().fg(YELLOW)));
         footer_spans.push(Span::styled(" [s] Sort ", Style::default().fg(YELLOW)));
-        footer_spans.push(Span::styled(" [U] Upgrade ", Style::default().fg(YELLOW)));
-        footer_spans.push(Span::styled(" [R] Restart ", Style::default().fg(YELLOW)));
+        footer_spans.push(Span::styled(" [U] Upgrade ", gated(crate::app::ACTION_AGENT_UPGRADE)));
+        footer_spans.push(Span::styled(" [1-4] CVE Severity ", if app.severity_filter.is_some() { Style::default().fg(RED).add_modifier(Modifier::BOLD) } else { Style::default().fg(YELLOW) }));
+        footer_spans.push(Span::styled(" [O] Outdated ", if app.show_outdated_only { Style::default().fg(ORANGE).add_modifier(Modifier::BOLD) } else { Style::default().fg(YELLOW) }));
+        if app.show_outdated_only {
+            footer_spans.push(Span::styled(" [A] Upgrade All ", gated(crate::app::ACTION_AGENT_UPGRADE)));
+        }
+        footer_spans.push(Span::styled(" [R] Restart ", gated(crate::app::ACTION_AGENT_RESTART)));
+        footer_spans.push(Span::styled(" [t] Tag ", Style::default().fg(YELLOW)));
+        if app.is_searching || !app.saved_agent_searches.is_empty() {
+            footer_spans.push(Span::styled(if app.is_searching { " [Alt+1-9] Save Search " } else { " [Alt+1-9] Saved Search " }, Style::default().fg(YELLOW)));
+        }
+        footer_spans.push(Span::styled(" [P] Preview ", if app.agent_preview { Style::default().fg(BLUE).add_modifier(Modifier::BOLD) } else { Style::default().fg(YELLOW) }));
         footer_spans.push(Span::styled(" [Enter] Inspect ", Style::default().fg(GREEN)));
     }
 
     if app.active_view == ActiveView::GroupManagement {
-        footer_spans.push(Span::styled(" [Enter] View Agents ", Style::default().fg(GREEN)));
+        if app.group_files_focus {
+            footer_spans.push(Span::styled(" [Enter] View File ", Style::default().fg(GREEN)));
+            footer_spans.push(Span::styled(" [←] Groups ", Style::default().fg(CYAN)));
+        } else {
+            footer_spans.push(Span::styled(" [Enter] View Agents & Files ", Style::default().fg(GREEN)));
+            footer_spans.push(Span::styled(" [→] Files ", Style::default().fg(CYAN)));
+        }
+        footer_spans.push(Span::styled(" [P] Posture ", if app.group_posture { Style::default().fg(BLUE).add_modifier(Modifier::BOLD) } else { Style::default().fg(YELLOW) }));
         footer_spans.push(Span::styled(" [/] Search ", Style::default().fg(YELLOW)));
     }
 
+    if app.active_view == ActiveView::CdbLists {
+        footer_spans.push(Span::styled(" [n] Add Entry ", gated(crate::app::ACTION_LISTS_UPDATE)));
+        if app.cdb_entries_focus {
+            footer_spans.push(Span::styled(" [D] Remove Entry ", gated(crate::app::ACTION_LISTS_UPDATE)));
+            footer_spans.push(Span::styled(" [←] Lists ", Style::default().fg(CYAN)));
+        } else {
+            footer_spans.push(Span::styled(" [→] Entries ", Style::default().fg(CYAN)));
+        }
+    }
+
+    if app.active_view == ActiveView::Rules {
+        footer_spans.push(Span::styled(" [Enter] Edit ", gated(app.ruleset_kind.update_action())));
+        footer_spans.push(Span::styled(" [n] New File ", gated(app.ruleset_kind.update_action())));
+        footer_spans.push(Span::styled(" [D] Delete ", gated(app.ruleset_kind.delete_action())));
+        footer_spans.push(Span::styled(" [v] Rules/Decoders ", Style::default().fg(CYAN)));
+        footer_spans.push(Span::styled(" [R] Restart Manager ", gated(crate::app::ACTION_MANAGER_RESTART)));
+    }
+
+    if app.active_view == ActiveView::UserManagement {
+        footer_spans.push(Span::styled(" [n] New User ", Style::default().fg(GREEN)));
+        footer_spans.push(Span::styled(" [a] Assign Role ", Style::default().fg(YELLOW)));
+        footer_spans.push(Span::styled(" [D] Delete User ", Style::default().fg(RED)));
+    }
+
     if app.active_view == ActiveView::SecurityEvents || (app.active_view == ActiveView::AgentInspector && app.inspector_tab == InspectorTab::Logs) {
         footer_spans.push(Span::styled(" [f] Filter ", Style::default().fg(PURPLE)));
         if app.active_view == ActiveView::SecurityEvents {
              footer_spans.push(Span::styled(" [v] Toggle View ", Style::default().fg(YELLOW)));
+             footer_spans.push(Span::styled(" [s] Sort ", Style::default().fg(YELLOW)));
+             footer_spans.push(Span::styled(" [1-4] Severity ", if app.log_filter.severities.is_empty() { Style::default().fg(YELLOW) } else { Style::default().fg(RED).add_modifier(Modifier::BOLD) }));
+             if app.log_view_mode == crate::app::LogViewMode::Raw {
+                 footer_spans.push(Span::styled(" [w] Wrap ", Style::default().fg(YELLOW)));
+                 if !app.raw_wrap {
+                     footer_spans.push(Span::styled(" [h/l] Scroll ", Style::default().fg(YELLOW)));
+                 }
+             } else {
+                 footer_spans.push(Span::styled(if app.group_floods { " [g] Ungroup " } else { " [g] Group " }, Style::default().fg(YELLOW)));
+             }
+             footer_spans.push(Span::styled(" [Space] Select ", Style::default().fg(YELLOW)));
+             if !app.selected_alerts.is_empty() {
+                 footer_spans.push(Span::styled(" [a] Ack ", Style::default().fg(GREEN)));
+                 footer_spans.push(Span::styled(" [y] Copy IDs ", Style::default().fg(GREEN)));
+             }
         }
-        footer_spans.push(Span::styled(" [e] Export JSON ", Style::default().fg(PURPLE)));
+        let export_scope = if app.active_view == ActiveView::SecurityEvents && !app.selected_alerts.is_empty() { "Selected " } else { "" };
+        let field_count = if app.export_format == crate::app::export::ExportFormat::Json && !app.export_fields.is_empty() {
+            format!(" ({} fields)", app.export_fields.len())
+        } else {
+            String::new()
+        };
+        let compression = match app.export_compression {
+            crate::app::export::ExportCompression::None => String::new(),
+            c => format!("+{}", c.label()),
+        };
+        footer_spans.push(Span::styled(format!(" [e] Export {}{}{}{} ", export_scope, app.export_format.label(), compression, field_count), Style::default().fg(PURPLE)));
+        footer_spans.push(Span::styled(" [E] Format ", Style::default().fg(PURPLE)));
+        footer_spans.push(Span::styled(" [Z] Compress ", Style::default().fg(PURPLE)));
+        footer_spans.push(Span::styled(" [x] Fields ", Style::default().fg(PURPLE)));
+        if app.export_job.is_none() {
+            footer_spans.push(Span::styled(" [W] Export All ", Style::default().fg(PURPLE)));
+        }
+    }
+
+    if app.active_view == ActiveView::AgentInspector && app.inspector_tab == InspectorTab::Processes {
+        footer_spans.push(Span::styled(" [s] Sort ", Style::default().fg(YELLOW)));
+        footer_spans.push(Span::styled(" [Enter] Details ", Style::default().fg(GREEN)));
+    }
+
+    if app.active_view == ActiveView::AgentInspector && app.inspector_tab == InspectorTab::Vulnerabilities {
+        footer_spans.push(Span::styled(" [s] Sort ", Style::default().fg(YELLOW)));
+        let style = if app.vuln_severity_filter.is_some() { Style::default().fg(RED).add_modifier(Modifier::BOLD) } else { Style::default().fg(YELLOW) };
+        footer_spans.push(Span::styled(" [1-4] Severity ", style));
+        footer_spans.push(Span::styled(format!(" [e] Export {} ", app.vuln_export_format.label()), Style::default().fg(PURPLE)));
+        footer_spans.push(Span::styled(" [E] Format ", Style::default().fg(PURPLE)));
+    }
+
+    if app.active_view == ActiveView::AgentInspector && app.inspector_tab == InspectorTab::Programs {
+        let style = if app.programs_vuln_only { Style::default().fg(RED).add_modifier(Modifier::BOLD) } else { Style::default().fg(YELLOW) };
+        footer_spans.push(Span::styled(" [v] Vulnerable Only ", style));
+    }
+
+    if app.active_view == ActiveView::AgentInspector && app.inspector_tab == InspectorTab::Rootcheck {
+        footer_spans.push(Span::styled(" [S] Run Scan ", gated(crate::app::ACTION_ROOTCHECK_RUN)));
     }
 
     if app.active_view == ActiveView::AgentInspector && app.inspector_tab == InspectorTab::Config {
-         footer_spans.push(Span::styled(" [e] Edit Config ", Style::default().fg(YELLOW)));
+         footer_spans.push(Span::styled(" [e] Edit Config ", gated(crate::app::ACTION_CONFIG_PUSH)));
+         footer_spans.push(Span::styled(if app.config_diff_mode { " [d] Show Config " } else { " [d] Group Diff " }, Style::default().fg(YELLOW)));
     }
 
     if app.active_view == ActiveView::AgentList || app.active_view == ActiveView::AgentInspector {
-        footer_spans.push(Span::styled(" [G] Group ", Style::default().fg(YELLOW)));
+        footer_spans.push(Span::styled(" [G] Group ", gated(crate::app::ACTION_GROUP_ASSIGN)));
+        footer_spans.push(Span::styled(" [F] FIM Scan ", gated(crate::app::ACTION_SYSCHECK_RUN)));
+        footer_spans.push(Span::styled(" [X] Run Cmd ", gated(crate::app::ACTION_ACTIVE_RESPONSE)));
         footer_spans.push(Span::styled(" [h] SSH ", Style::default().fg(YELLOW)));
         footer_spans.push(Span::styled(" [o] Browser ", Style::default().fg(YELLOW)));
     }
@@ -223,11 +380,45 @@ pub fn draw(f: &mut Frame, app: &mut App) {
     footer_spans.push(Span::styled(" [+/-] Quick Adj ", Style::default().fg(GREEN)));
 
     if app.is_searching {
-        footer_spans.push(Span::styled(format!(" 󰍉 Filtering: {} ", app.search_query), Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)));
+        footer_spans.push(Span::styled(format!(" {}Filtering: {} ", app.icons.pad(Icon::Search), app.search_query), Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)));
+    }
+
+    // Status indicators survive the compact footer on narrow terminals
+    let mut status_spans = Vec::new();
+    if let Some(retry) = app.api.as_ref().and_then(|a| a.retry_status()) {
+        status_spans.push(Span::styled(format!(" {}{} ", app.icons.pad(Icon::Loading), retry), Style::default().fg(ORANGE).add_modifier(Modifier::BOLD)));
+    }
+
+    if let Some((fetched, total)) = app.export_progress {
+        let total = if total > 0 { total.min(app.export_max_alerts as u64).to_string() } else { "?".to_string() };
+        status_spans.push(Span::styled(format!(" {}Export: {}/{} [W] Cancel ", app.icons.pad(Icon::Export), fetched, total), Style::default().fg(PURPLE).add_modifier(Modifier::BOLD)));
+    }
+
+    if let Some(op) = app.bulk_op.as_ref().filter(|op| !op.is_finished()) {
+        status_spans.push(Span::styled(format!(" {}{}: {}/{} ", app.icons.pad(Icon::Loading), op.action.verb(), op.processed(), op.total), Style::default().fg(BLUE).add_modifier(Modifier::BOLD)));
+    }
+
+    if !app.pending_actions.is_empty() {
+        status_spans.push(Span::styled(format!(" {}Queued: {} ", app.icons.pad(Icon::Hourglass), app.pending_actions.actions.len()), Style::default().fg(ORANGE).add_modifier(Modifier::BOLD)));
+    }
+
+    if let Some((endpoint, days)) = app.expiring_certificate(chrono::Utc::now()) {
+        let text = if days < 0 { format!("{} cert expired", endpoint) } else { format!("{} cert expires in {}d", endpoint, days) };
+        status_spans.push(Span::styled(format!(" {}{} ", app.icons.pad(Icon::Warning), text), Style::default().fg(if days < 0 { RED } else { YELLOW }).add_modifier(Modifier::BOLD)));
+    }
+
+    let dark_nodes = app.dark_nodes();
+    if !dark_nodes.is_empty() {
+        status_spans.push(Span::styled(format!(" {}All agents down on {} ", app.icons.pad(Icon::Warning), dark_nodes.join(", ")), Style::default().fg(RED).add_modifier(Modifier::BOLD)));
+    }
+
+    let fim_running = app.fim_scans_running();
+    if fim_running > 0 {
+        status_spans.push(Span::styled(format!(" {}FIM: {} running ", app.icons.pad(Icon::Fim), fim_running), Style::default().fg(CYAN).add_modifier(Modifier::BOLD)));
     }
 
     if app.is_loading {
-        footer_spans.push(Span::styled(format!(" {} {} ", app.get_spinner_char(), app.loading_text), Style::default().fg(BLUE).add_modifier(Modifier::BOLD)));
+        status_spans.push(Span::styled(format!(" {} {} ", app.get_spinner_char(), app.loading_text), Style::default().fg(BLUE).add_modifier(Modifier::BOLD)));
     }
 
     if app.active_view == ActiveView::Dashboard {
@@ -236,18 +427,96 @@ pub fn draw(f: &mut Frame, app: &mut App) {
     }
 
     if let Some(err) = &app.error_message {
-        footer_spans.push(Span::styled(format!(" 󰅚 {} ", err), Style::default().fg(RED).add_modifier(Modifier::BOLD)));
+        status_spans.push(Span::styled(format!(" {}{} ", app.icons.pad(Icon::Error), err), Style::default().fg(RED).add_modifier(Modifier::BOLD)));
     }
 
+    let footer_spans = if is_narrow(f.size()) {
+        let mut compact = vec![
+            Span::styled(" [?] Help ", Style::default().fg(PURPLE)),
+            Span::styled(" [Ctrl+P] Commands ", Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
+        ];
+        compact.extend(status_spans);
+        compact
+    } else {
+        footer_spans.extend(status_spans);
+        footer_spans
+    };
+
     let status_bar = Paragraph::new(ratatui::text::Line::from(footer_spans))
         .style(Style::default().bg(STATUS_BAR_BG))
         .block(Block::default().borders(Borders::TOP).border_style(Style::default().fg(DARK_GRAY)));
     f.render_widget(status_bar, main_layout[3]);
 
+    // --- ONBOARDING TOUR ---
+    if let Some(step) = app.tour_step {
+        draw_tour(f, step, &main_layout);
+    }
+
     // --- NOTIFICATION TOASTS (Rendered last to be on top) ---
     draw_notifications(f, app);
 }
 
+/// Frames the part of the screen the tour step is about and shows its hint next to it.
+fn draw_tour(f: &mut Frame, step: usize, main_layout: &[Rect]) {
+    use crate::app::tour::{tour_steps, TourTarget};
+    let steps = tour_steps();
+    let Some(current) = steps.get(step) else { return };
+    let size = f.size();
+    let target = match current.target {
+        TourTarget::Header => main_layout[0],
+        TourTarget::Body => main_layout[2],
+        TourTarget::Footer => main_layout[3],
+        // Same area as the advanced filter popup
+        TourTarget::FilterPopup => crate::ui::common::centered_rect(70, 80, size),
+    };
+    f.render_widget(Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Double)
+        .border_style(Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)), target);
+
+    let width = 64.min(size.width.saturating_sub(4));
+    // Room for the wrapped text, with a spare line for words pushed to the next one
+    let text_lines = current.text.chars().count() as u16 / width.saturating_sub(2).max(1) + 2;
+    let height = (text_lines + 4).min(size.height);
+    let y = match current.target {
+        TourTarget::Header => target.bottom(),
+        TourTarget::Footer => target.y.saturating_sub(height),
+        TourTarget::Body | TourTarget::FilterPopup => target.bottom().saturating_sub(height + 1),
+    };
+    let area = Rect::new(size.width.saturating_sub(width) / 2, y, width, height);
+
+    let hint = Paragraph::new(vec![
+        ratatui::text::Line::from(current.text),
+        ratatui::text::Line::from(""),
+        ratatui::text::Line::from(Span::styled("[Enter/→] Next  [←] Back  [Esc] Skip tour", Style::default().fg(DARK_GRAY))),
+    ])
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .style(Style::default().fg(FG))
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(YELLOW))
+            .title(Span::styled(format!(" Tour {}/{}: {} ", step + 1, steps.len(), current.title), Style::default().fg(YELLOW).add_modifier(Modifier::BOLD))));
+    f.render_widget(Clear, area);
+    f.render_widget(hint, area);
+}
+
+/// Connection dot for the right end of the header, from the API heartbeat.
+fn connection_title(app: &App) -> ratatui::widgets::block::Title<'static> {
+    use crate::app::conn::ConnectionState;
+    let color = match app.heartbeat.state {
+        ConnectionState::Up => GREEN,
+        ConnectionState::Degraded => YELLOW,
+        ConnectionState::Down => RED,
+        ConnectionState::Unknown => DARK_GRAY,
+    };
+    let line = ratatui::text::Line::from(vec![
+        Span::styled(" ● ", Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("{} ", app.heartbeat.label(chrono::Utc::now())), Style::default().fg(color)),
+    ]);
+    ratatui::widgets::block::Title::from(line).alignment(ratatui::layout::Alignment::Right)
+}
+
 fn draw_notifications(f: &mut Frame, app: &mut App) {
     if app.notifications.is_empty() {
         return;
@@ -260,10 +529,10 @@ fn draw_notifications(f: &mut Frame, app: &mut App) {
     let notifications = app.notifications.clone();
     for (i, notification) in notifications.iter().enumerate() {
         let (icon, color) = match notification.level {
-            crate::app::NotificationLevel::Info => ("󰋼 ", BLUE),
-            crate::app::NotificationLevel::Success => ("󰄬 ", GREEN),
-            crate::app::NotificationLevel::Warning => ("󰀦 ", YELLOW),
-            crate::app::NotificationLevel::Error => ("󰅚 ", RED),
+            crate::app::NotificationLevel::Info => (Icon::Info, BLUE),
+            crate::app::NotificationLevel::Success => (Icon::Check, GREEN),
+            crate::app::NotificationLevel::Warning => (Icon::Warning, YELLOW),
+            crate::app::NotificationLevel::Error => (Icon::Error, RED),
         };
 
         let notification_area = Rect::new(
@@ -277,7 +546,7 @@ fn draw_notifications(f: &mut Frame, app: &mut App) {
             .borders(Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(Style::default().fg(color))
-            .title(format!(" {} Notification ", icon));
+            .title(format!(" {}Notification ", app.icons.pad(icon)));
 
         let p = Paragraph::new(notification.message.as_str())
             .block(block)
@@ -309,8 +578,10 @@ fn draw_config_wizard(f: &mut Frame, app: &mut App, area: Rect) {
     let fields = vec![
         ("1. Wazuh API URL", &app.config_url, app.config_step == ConfigStep::Url),
         ("2. OpenSearch URL", &app.config_os_url, app.config_step == ConfigStep::OsUrl),
-        ("3. Username", &app.config_username, app.config_step == ConfigStep::Username),
-        ("4. Password", &pass_mask, app.config_step == ConfigStep::Password),
+        ("3. Alerts index pattern (optional)", &app.config_alerts_index, app.config_step == ConfigStep::AlertsIndex),
+        ("4. Vulnerabilities index pattern (optional)", &app.config_vuln_index, app.config_step == ConfigStep::VulnIndex),
+        ("5. Username", &app.config_username, app.config_step == ConfigStep::Username),
+        ("6. Password", &pass_mask, app.config_step == ConfigStep::Password),
     ];
 
     let input_chunks = Layout::default()
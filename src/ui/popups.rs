@@ -1,13 +1,18 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect, Alignment},
     style::{Modifier, Style},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap, Tabs},
+    widgets::{Block, Borders, Cell, Clear, Gauge, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap, Tabs},
     text::{Span, Line},
     Frame,
 };
 use crate::app::{App, PopupMode, SeverityFilterMode, FilterPopupTab, LogColumn};
+use crate::app::time::DisplayTz;
+use crate::app::severity::{Severity, SeverityScale};
+use crate::app::actions::{status_counts, AgentAction};
+use crate::app::keymap::KeyContext;
 use crate::ui::theme::*;
-use crate::ui::common::centered_rect;
+use crate::ui::icons::Icon;
+use crate::ui::common::{centered_rect, format_kib, format_last_keep_alive, format_table_time};
 
 fn draw_popup_shell<'a>(f: &mut Frame, title: &str, percent_x: u16, percent_y: u16, border_style: Style) -> (Rect, Block<'a>) {
     let area = centered_rect(percent_x, percent_y, f.size());
@@ -20,22 +25,101 @@ fn draw_popup_shell<'a>(f: &mut Frame, title: &str, percent_x: u16, percent_y: u
     (area, block)
 }
 
-fn get_severity_style(level: u64) -> Style {
-    let color = match level {
-        15..=u64::MAX => VULN_CRITICAL,
-        12..=14 => VULN_HIGH,
-        7..=11 => VULN_MEDIUM,
-        _ => VULN_LOW,
+/// Color of an OpenSearch green/yellow/red health status.
+fn health_color(status: &str) -> ratatui::style::Color {
+    match status {
+        "green" => GREEN,
+        "yellow" => YELLOW,
+        "red" => RED,
+        _ => GRAY,
+    }
+}
+
+fn agent_status_color(status: &str) -> ratatui::style::Color {
+    match status {
+        "active" => GREEN,
+        "disconnected" => RED,
+        "pending" => YELLOW,
+        _ => DARK_GRAY,
+    }
+}
+
+fn get_severity_style(scale: &SeverityScale, level: u64) -> Style {
+    let color = match scale.classify(level) {
+        Severity::Critical => VULN_CRITICAL,
+        Severity::High => VULN_HIGH,
+        Severity::Medium => VULN_MEDIUM,
+        Severity::Low => VULN_LOW,
     };
     Style::default().fg(color)
 }
 
-fn get_severity_label(level: u64) -> &'static str {
-    match level {
-        15..=u64::MAX => "Critical",
-        12..=14 => "High",
-        7..=11 => "Medium",
-        _ => "Low",
+/// Time of day, level, rule id and description of an alert hit.
+fn alert_summary(event: &serde_json::Value, tz: DisplayTz) -> (String, u64, String, String) {
+    let source = event.get("_source").unwrap_or(event);
+    let timestamp = source.get("@timestamp").or_else(|| source.get("timestamp")).and_then(|v| v.as_str()).unwrap_or("");
+    let time = tz.format(timestamp, "%H:%M:%S");
+    let rule = source.get("rule");
+    let level = rule.and_then(|r| r.get("level")).and_then(|l| l.as_u64()).unwrap_or(0);
+    let rule_id = rule.and_then(|r| r.get("id")).and_then(|v| v.as_str()).unwrap_or("-").to_string();
+    let description = rule.and_then(|r| r.get("description")).and_then(|v| v.as_str()).unwrap_or("").to_string();
+    (time, level, rule_id, description)
+}
+
+fn get_severity_label(scale: &SeverityScale, level: u64) -> &'static str {
+    scale.classify(level).label()
+}
+
+fn term_color(color: crate::app::term::TermColor) -> ratatui::style::Color {
+    match color {
+        crate::app::term::TermColor::Indexed(n) => ratatui::style::Color::Indexed(n),
+        crate::app::term::TermColor::Rgb(r, g, b) => ratatui::style::Color::Rgb(r, g, b),
+    }
+}
+
+/// Full-screen pane for the embedded SSH session; the pty follows the pane size.
+fn draw_ssh_session(f: &mut Frame, app: &mut App) {
+    let Some(session) = app.ssh_session.as_mut() else { return };
+    let (title, color) = if session.exited {
+        (format!("SSH {} (closed) [any key] Close", session.target), DARK_GRAY)
+    } else {
+        (format!("SSH {} [Ctrl+]] Close", session.target), GREEN)
+    };
+    let (area, block) = draw_popup_shell(f, &title, 100, 100, Style::default().fg(color));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if inner.width == 0 || inner.height == 0 {
+        return;
+    }
+    session.resize(inner.height, inner.width);
+
+    let screen = &session.screen;
+    let buf = f.buffer_mut();
+    for row in 0..inner.height {
+        for col in 0..inner.width {
+            let cell = screen.cell(row as usize, col as usize);
+            let mut style = Style::default();
+            if let Some(fg) = cell.style.fg {
+                style = style.fg(term_color(fg));
+            }
+            if let Some(bg) = cell.style.bg {
+                style = style.bg(term_color(bg));
+            }
+            if cell.style.bold {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            if cell.style.underline {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            if cell.style.reverse {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            buf.get_mut(inner.x + col, inner.y + row).set_char(cell.ch).set_style(style);
+        }
+    }
+    if screen.cursor_visible() && !session.exited {
+        let (row, col) = screen.cursor();
+        f.set_cursor(inner.x + col as u16, inner.y + row as u16);
     }
 }
 
@@ -46,14 +130,14 @@ pub fn draw_popup(f: &mut Frame, app: &mut App) {
             
             let list_items: Vec<_> = app.groups.iter().map(|g| {
                 ListItem::new(Line::from(vec![
-                    Span::styled(format!(" 󰒲 {} ", g.name), Style::default().fg(FG)),
+                    Span::styled(format!(" {}{} ", app.icons.pad(Icon::Group), g.name), Style::default().fg(FG)),
                 ]))
             }).collect();
             
             let list = List::new(list_items)
                 .block(block)
                 .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
-                .highlight_symbol("󰁔 ");
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
             
             let mut state = ListState::default();
             state.select(app.groups_table_state.selected());
@@ -65,12 +149,13 @@ pub fn draw_popup(f: &mut Frame, app: &mut App) {
         PopupMode::SshUsername { agent_id, agent_ip } => {
             let (area, block) = draw_popup_shell(f, &format!("SSH to {} ({})", agent_id, agent_ip), 40, 20, Style::default().fg(YELLOW));
             
-            let p = Paragraph::new(format!(" Enter SSH Username:\n\n {}█\n\n [Enter] Launch SSH  [Esc] Cancel ", app.input_buffer))
+            let p = Paragraph::new(format!(" Enter SSH Username:\n\n {}█\n\n [Enter] Launch SSH  [^E] In this window  [Esc] Cancel ", app.input_buffer))
                 .block(block)
                 .alignment(Alignment::Center)
                 .style(Style::default().fg(FG));
             f.render_widget(p, area);
         },
+        PopupMode::SshSession => draw_ssh_session(f, app),
         PopupMode::AgentJump => {
             let (area, block) = draw_popup_shell(f, "Quick Agent Jump (Autocomplete)", 50, 40, Style::default().fg(YELLOW).add_modifier(Modifier::BOLD));
             f.render_widget(block, area);
@@ -84,7 +169,7 @@ pub fn draw_popup(f: &mut Frame, app: &mut App) {
                 .margin(1)
                 .split(area);
 
-            let input = Paragraph::new(format!(" 󰍉 Query: {}█ ", app.jump_input))
+            let input = Paragraph::new(format!(" {}Query: {}█ ", app.icons.pad(Icon::Search), app.jump_input))
                 .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(DARK_GRAY)));
             f.render_widget(input, chunks[0]);
 
@@ -110,7 +195,7 @@ pub fn draw_popup(f: &mut Frame, app: &mut App) {
             let list = List::new(items)
                 .block(Block::default().borders(Borders::NONE))
                 .highlight_style(Style::default().bg(SELECTION_BG))
-                .highlight_symbol("󰁔 ");
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
             f.render_widget(list, chunks[1]);
         },
         PopupMode::CommandPalette => {
@@ -145,21 +230,1193 @@ pub fn draw_popup(f: &mut Frame, app: &mut App) {
 
             let list = List::new(items)
                 .block(Block::default().borders(Borders::NONE))
-                .highlight_symbol("󰁔 ");
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
             f.render_widget(list, chunks[1]);
         },
         PopupMode::Error { title, message } => {
             let (area, block) = draw_popup_shell(f, title, 60, 40, Style::default().fg(RED).add_modifier(Modifier::BOLD));
-            
-            let p = Paragraph::new(format!("\n{}\n\n\n [Enter/Esc] Close ", message))
+
+            if !message.contains('\n') {
+                let p = Paragraph::new(format!("\n{}\n\n\n [Enter/Esc] Close ", message))
+                    .block(block)
+                    .alignment(Alignment::Center)
+                    .style(Style::default().fg(FG))
+                    .wrap(Wrap { trim: true });
+                f.render_widget(p, area);
+                return;
+            }
+
+            // Several lines: the failed items and remediation of an API error, or a list of problems
+            let mut lines = vec![Line::from("")];
+            for line in message.lines() {
+                lines.push(match line.strip_prefix("Remediation: ") {
+                    Some(remediation) => Line::from(vec![
+                        Span::styled(" Remediation: ", Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
+                        Span::styled(remediation.to_string(), Style::default().fg(YELLOW)),
+                    ]),
+                    None => Line::from(Span::styled(format!(" {}", line), Style::default().fg(FG))),
+                });
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(" [Enter/Esc] Close ", Style::default().fg(PURPLE))).alignment(Alignment::Center));
+            f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+        },
+        PopupMode::Help => {
+            draw_help_popup(f, app);
+        },
+        PopupMode::CreateUser => {
+            let (area, block) = draw_popup_shell(f, "Create API User", 45, 30, Style::default().fg(GREEN));
+            let pass_mask = "*".repeat(app.new_user_password.len());
+            let cursor = |field: usize| if app.new_user_field == field { "█" } else { "" };
+
+            let lines = vec![
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("  Username: ", Style::default().fg(FG)),
+                    Span::styled(app.new_user_name.clone(), Style::default().fg(GREEN).add_modifier(Modifier::BOLD)),
+                    Span::styled(cursor(0), Style::default().fg(YELLOW)),
+                ]),
+                Line::from(vec![
+                    Span::styled("  Password: ", Style::default().fg(FG)),
+                    Span::styled(pass_mask, Style::default().fg(GREEN).add_modifier(Modifier::BOLD)),
+                    Span::styled(cursor(1), Style::default().fg(YELLOW)),
+                ]),
+                Line::from(""),
+                Line::from(Span::styled("  Passwords need 8+ chars with upper, lower, digit and symbol", Style::default().fg(DARK_GRAY))),
+                Line::from(""),
+                Line::from(Span::styled("  [Tab] Switch field  [Enter] Create  [Esc] Cancel", Style::default().fg(DARK_GRAY))),
+            ];
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        },
+        PopupMode::Relogin => {
+            let (area, block) = draw_popup_shell(f, "Re-login", 50, 35, Style::default().fg(YELLOW));
+            let form = &app.relogin;
+            let pass_mask = "*".repeat(form.password.chars().count());
+            let cursor = |field: usize| if form.field == field { "█" } else { "" };
+            let current = app.api.as_ref().map(|a| a.config.username.clone()).unwrap_or_default();
+
+            let lines = vec![
+                Line::from(Span::styled(format!("  Logged in as {}", current), Style::default().fg(GRAY))),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("  Username: ", Style::default().fg(FG)),
+                    Span::styled(form.username.clone(), Style::default().fg(GREEN).add_modifier(Modifier::BOLD)),
+                    Span::styled(cursor(0), Style::default().fg(YELLOW)),
+                ]),
+                Line::from(vec![
+                    Span::styled("  Password: ", Style::default().fg(FG)),
+                    Span::styled(pass_mask, Style::default().fg(GREEN).add_modifier(Modifier::BOLD)),
+                    Span::styled(cursor(1), Style::default().fg(YELLOW)),
+                ]),
+                Line::from(vec![
+                    Span::styled(format!("  [{}] ", if form.save { "x" } else { " " }), Style::default().fg(if form.field == 2 { YELLOW } else { FG })),
+                    Span::styled("Save to config.toml", Style::default().fg(if form.field == 2 { YELLOW } else { FG })),
+                ]),
+                Line::from(""),
+                Line::from(Span::styled("  Unsaved credentials last until the TUI exits", Style::default().fg(DARK_GRAY))),
+                Line::from(""),
+                Line::from(Span::styled("  [Tab] Switch field  [Space] Toggle save  [Enter] Log in  [Esc] Cancel", Style::default().fg(DARK_GRAY))),
+            ];
+            f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+        },
+        PopupMode::RoleAssignment { user_id } => {
+            let username = app.security_users.iter()
+                .find(|u| u.id == *user_id)
+                .map(|u| u.username.clone())
+                .unwrap_or_default();
+            let assigned: Vec<u32> = app.security_users.iter()
+                .find(|u| u.id == *user_id)
+                .map(|u| u.roles.clone())
+                .unwrap_or_default();
+            let (area, block) = draw_popup_shell(f, &format!("Roles for {} ([Enter] toggle)", username), 45, 50, Style::default().fg(BLUE));
+
+            let items: Vec<_> = app.security_roles.iter().map(|r| {
+                let checkbox = if assigned.contains(&r.id) { "[✓]" } else { "[ ]" };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {} ", checkbox), Style::default().fg(GREEN)),
+                    Span::styled(r.name.clone(), Style::default().fg(FG)),
+                ]))
+            }).collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+
+            let mut state = ListState::default();
+            state.select(Some(app.role_selection_index));
+            f.render_stateful_widget(list, area, &mut state);
+        },
+        PopupMode::ConfirmDeleteUser { username, .. } => {
+            let (area, block) = draw_popup_shell(f, "Delete API User", 40, 20, Style::default().fg(RED).add_modifier(Modifier::BOLD));
+            let p = Paragraph::new(format!("\n Delete user '{}'?\n\n [Enter] Delete  [Esc] Cancel ", username))
                 .block(block)
                 .alignment(Alignment::Center)
+                .style(Style::default().fg(FG));
+            f.render_widget(p, area);
+        },
+        PopupMode::ProcessDetail { pid } => {
+            let Some(process) = app.processes.iter().find(|p| &p.pid == pid) else { return };
+            let platform = app.get_selected_agent().and_then(|a| a.os.as_ref()).and_then(|o| o.platform.clone());
+            let (area, block) = draw_popup_shell(f, &format!("Process {}", pid), 60, 50, Style::default().fg(BLUE));
+
+            let field = |label: &str, value: String| Line::from(vec![
+                Span::styled(format!("  {:<10}", label), Style::default().fg(CYAN)),
+                Span::styled(value, Style::default().fg(FG)),
+            ]);
+            let or_na = |v: Option<String>| v.unwrap_or_else(|| "N/A".to_string());
+
+            let lines = vec![
+                Line::from(""),
+                field("Name", or_na(process.name.clone())),
+                field("PID", process.pid.clone()),
+                field("PPID", or_na(process.ppid.map(|v| v.to_string()))),
+                field("User", or_na(process.euser.clone())),
+                field("State", or_na(process.state.clone())),
+                field("Memory", or_na(process.vm_size.map(crate::ui::common::format_kib))),
+                field("CPU user", or_na(process.utime.map(|t| format!("{} ticks", t)))),
+                field("CPU sys", or_na(process.stime.map(|t| format!("{} ticks", t)))),
+                field("Command", or_na(process.cmd.clone())),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("  Kill:     ", Style::default().fg(RED)),
+                    Span::styled(process.kill_command(platform.as_deref()), Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
+                ]),
+                Line::from(""),
+                Line::from(Span::styled("  [y] Copy kill command  [Esc] Close", Style::default().fg(DARK_GRAY))),
+            ];
+            f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+        },
+        PopupMode::AlertContext => {
+            let agent = app.selected_log.as_ref()
+                .and_then(|l| l.get("_source").unwrap_or(l).get("agent"))
+                .and_then(|a| a.get("name"))
+                .and_then(|n| n.as_str())
+                .unwrap_or("agent")
+                .to_string();
+            let title = format!("Alert Context: {} (±{}m)", agent, crate::app::ALERT_CONTEXT_MINUTES);
+            let (area, block) = draw_popup_shell(f, &title, 80, 70, Style::default().fg(CYAN));
+
+            if app.alert_context_loading || app.alert_context.is_empty() {
+                let msg = if app.alert_context_loading { "Loading surrounding events..." } else { "No events found in this window" };
+                let p = Paragraph::new(msg).block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                f.render_widget(p, area);
+                return;
+            }
+
+            let anchor = app.alert_context_anchor.as_deref();
+            let items: Vec<ListItem> = app.alert_context.iter().map(|event| {
+                let (time, level, rule_id, description) = alert_summary(event, app.display_tz);
+                let is_anchor = anchor.is_some() && event.get("_id").and_then(|i| i.as_str()) == anchor;
+
+                let marker = if is_anchor { Span::styled("● ", Style::default().fg(YELLOW)) } else { Span::styled("│ ", Style::default().fg(DARK_GRAY)) };
+                let mut desc_style = Style::default().fg(FG);
+                if is_anchor {
+                    desc_style = desc_style.add_modifier(Modifier::BOLD);
+                }
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {} ", time), Style::default().fg(GRAY)),
+                    marker,
+                    Span::styled(format!("{:>3} ", level), get_severity_style(&app.severity, level)),
+                    Span::styled(format!("{:<8}", rule_id), Style::default().fg(DARK_GRAY)),
+                    Span::styled(description, desc_style),
+                ]))
+            }).collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select(Some(app.alert_context_index));
+            f.render_stateful_widget(list, area, &mut state);
+
+            let help = Paragraph::new(" [↑/↓] Navigate │ [Enter] Open │ [Esc] Back ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(CYAN));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::SrcIpPivot { srcip } => {
+            let title = format!("Source IP {} across agents (last {})", srcip, app.format_interval());
+            let (area, block) = draw_popup_shell(f, &title, 80, 70, Style::default().fg(PURPLE));
+
+            if app.pivot_loading || app.pivot_events.is_empty() {
+                let msg = if app.pivot_loading { "Searching all agents..." } else { "No alerts from this source IP in the interval" };
+                let p = Paragraph::new(msg).block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                f.render_widget(p, area);
+                return;
+            }
+
+            // Agent headers are not selectable, so map the event index to a list row
+            let mut items = Vec::new();
+            let mut selected_row = 0;
+            let mut event_idx = 0;
+            for (agent, events) in app.pivot_groups() {
+                items.push(ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {} ", agent), Style::default().fg(PURPLE).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("({} alerts)", events.len()), Style::default().fg(GRAY)),
+                ])));
+                for event in events {
+                    if event_idx == app.pivot_index {
+                        selected_row = items.len();
+                    }
+                    event_idx += 1;
+                    let (time, level, rule_id, description) = alert_summary(event, app.display_tz);
+                    items.push(ListItem::new(Line::from(vec![
+                        Span::styled(format!("   {} ", time), Style::default().fg(GRAY)),
+                        Span::styled(format!("{:>3} ", level), get_severity_style(&app.severity, level)),
+                        Span::styled(format!("{:<8}", rule_id), Style::default().fg(DARK_GRAY)),
+                        Span::styled(description, Style::default().fg(FG)),
+                    ])));
+                }
+            }
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select(Some(selected_row));
+            f.render_stateful_widget(list, area, &mut state);
+
+            let help = Paragraph::new(" [↑/↓] Navigate │ [Enter] Open │ [Esc] Back ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::GroupFile { group, filename } => {
+            let (area, block) = draw_popup_shell(f, &format!("{}/{}", group, filename), 85, 80, Style::default().fg(BLUE));
+            let content = app.group_file_content.as_deref().unwrap_or("Loading...");
+            let p = Paragraph::new(content.to_string())
+                .block(block)
                 .style(Style::default().fg(FG))
-                .wrap(Wrap { trim: true });
+                .scroll((app.group_file_scroll, 0));
             f.render_widget(p, area);
+
+            let help = Paragraph::new(" [↑/↓] Scroll │ [Esc] Close ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
         },
-        PopupMode::Help => {
-            draw_help_popup(f, app);
+        PopupMode::Activity => {
+            let (area, block) = draw_popup_shell(f, "Activity Log", 85, 70, Style::default().fg(CYAN));
+
+            if app.audit_log.is_empty() {
+                let p = Paragraph::new("No actions recorded yet").block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                f.render_widget(p, area);
+                return;
+            }
+
+            // Newest first
+            let items: Vec<ListItem> = app.audit_log.iter().rev().map(|entry| {
+                let (mark, color) = if entry.success { ("✓", GREEN) } else { ("✗", RED) };
+                let mut targets = entry.targets.iter().take(5).cloned().collect::<Vec<_>>().join(",");
+                if entry.targets.len() > 5 {
+                    targets.push_str(&format!(" +{}", entry.targets.len() - 5));
+                }
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {} ", app.display_tz.format(&entry.timestamp, "%Y-%m-%d %H:%M:%S")), Style::default().fg(GRAY)),
+                    Span::styled(format!("{} ", mark), Style::default().fg(color)),
+                    Span::styled(format!("{:<12}", entry.user), Style::default().fg(DARK_GRAY)),
+                    Span::styled(format!("{:<18}", entry.action), Style::default().fg(CYAN)),
+                    Span::styled(format!("{:<24} ", targets), Style::default().fg(FG)),
+                    Span::styled(entry.detail.clone(), Style::default().fg(color)),
+                ]))
+            }).collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select(Some(app.activity_index));
+            f.render_stateful_widget(list, area, &mut state);
+
+            let help = Paragraph::new(format!(" [↑/↓] Navigate │ [Esc] Close │ {} ", crate::app::audit::audit_path().display()))
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::BulkProgress => {
+            let Some(op) = app.bulk_op.as_ref() else { return };
+            let color = if op.failed() > 0 { ORANGE } else if op.is_finished() { GREEN } else { BLUE };
+            let (area, block) = draw_popup_shell(f, op.action.title(), 60, 50, Style::default().fg(color).add_modifier(Modifier::BOLD));
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(2), Constraint::Length(1), Constraint::Length(2), Constraint::Min(0), Constraint::Length(1)])
+                .split(inner);
+
+            let state = if op.is_finished() { "done".to_string() } else { format!("{} sending...", app.get_spinner_char()) };
+            let summary = Line::from(vec![
+                Span::styled(format!(" Batch {}/{}  ", op.batches.len(), op.batch_count), Style::default().fg(FG)),
+                Span::styled(format!("{} ok", op.processed() - op.failed()), Style::default().fg(GREEN)),
+                Span::styled("  •  ", Style::default().fg(DARK_GRAY)),
+                Span::styled(format!("{} failed", op.failed()), Style::default().fg(if op.failed() > 0 { RED } else { DARK_GRAY })),
+                Span::styled(format!("  •  {}", state), Style::default().fg(DARK_GRAY)),
+            ]);
+            f.render_widget(Paragraph::new(vec![Line::from(""), summary]), chunks[0]);
+
+            let gauge = Gauge::default()
+                .gauge_style(Style::default().fg(color).bg(SELECTION_BG))
+                .ratio(op.ratio())
+                .label(format!("{}/{} agents", op.processed(), op.total));
+            f.render_widget(gauge, Rect::new(chunks[1].x + 1, chunks[1].y, chunks[1].width.saturating_sub(2), 1));
+
+            let failures = op.failures();
+            let heading = if failures.is_empty() { " No failures" } else { " Failures:" };
+            f.render_widget(Paragraph::new(vec![Line::from(""), Line::from(Span::styled(heading, Style::default().fg(DARK_GRAY)))]), chunks[2]);
+            let lines: Vec<Line> = failures.iter()
+                .map(|l| Line::from(Span::styled(format!("  {}", l), Style::default().fg(RED))))
+                .collect();
+            f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[3]);
+
+            let hint = if op.is_finished() { " [Esc] Close " } else { " [Esc] Close (keeps running, see the footer) " };
+            f.render_widget(Paragraph::new(hint).alignment(Alignment::Center).style(Style::default().fg(PURPLE)), chunks[4]);
+        },
+        PopupMode::PendingActions => {
+            let (area, block) = draw_popup_shell(f, "Pending Actions", 70, 50, Style::default().fg(ORANGE));
+
+            if app.pending_actions.is_empty() {
+                let p = Paragraph::new("No actions queued for disconnected agents").block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                f.render_widget(p, area);
+                return;
+            }
+
+            let items: Vec<ListItem> = app.pending_actions.actions.iter().map(|p| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {} ", app.display_tz.format(&p.queued_at, "%Y-%m-%d %H:%M")), Style::default().fg(GRAY)),
+                    Span::styled(format!("{:<10}", p.action.verb()), Style::default().fg(CYAN)),
+                    Span::styled(format!("{:<6}", p.agent_id), Style::default().fg(DARK_GRAY)),
+                    Span::styled(p.agent_name.clone(), Style::default().fg(FG)),
+                ]))
+            }).collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select(Some(app.pending_index));
+            f.render_stateful_widget(list, area, &mut state);
+
+            let help = Paragraph::new(" Runs when the agent is active again │ [↑/↓] Navigate │ [d] Drop │ [Esc] Close ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::AgentNodes => {
+            let (area, block) = draw_popup_shell(f, "Agents by Node", 70, 50, Style::default().fg(BLUE));
+            let nodes = crate::app::nodes::node_summaries(&app.agents);
+            if nodes.is_empty() {
+                let p = Paragraph::new("No agents loaded").block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                f.render_widget(p, area);
+                return;
+            }
+
+            let cluster_total: usize = nodes.iter().map(|n| n.total).sum();
+            let bar_width = 20;
+            let items: Vec<ListItem> = nodes.iter().map(|node| {
+                let filled = (node.share(cluster_total) * bar_width as f64).round() as usize;
+                let name_color = if node.is_dark() { RED } else { FG };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {:<24}", node.node), Style::default().fg(name_color).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("{:<width$}", "█".repeat(filled), width = bar_width), Style::default().fg(BLUE)),
+                    Span::styled(format!(" {:>5} ({:>3.0}%) ", node.total, node.share(cluster_total) * 100.0), Style::default().fg(FG)),
+                    Span::styled(format!("{} active ", node.active), Style::default().fg(GREEN)),
+                    Span::styled(format!("{} disconnected ", node.disconnected), Style::default().fg(if node.disconnected > 0 { RED } else { GRAY })),
+                    Span::styled(if node.other > 0 { format!("{} other", node.other) } else { String::new() }, Style::default().fg(GRAY)),
+                ]))
+            }).collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select(Some(app.node_index.min(nodes.len() - 1)));
+            f.render_stateful_widget(list, area, &mut state);
+
+            let help = Paragraph::new(format!(" {} agents on {} nodes │ [↑/↓] Navigate │ [Enter] List agents │ [Esc] Close ", cluster_total, nodes.len()))
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::Hunts => {
+            let (area, block) = draw_popup_shell(f, &format!("Hunts (last {})", app.format_interval()), 60, 40, Style::default().fg(ORANGE));
+            let items: Vec<ListItem> = crate::app::hunts::HUNTS.iter().map(|hunt| {
+                ListItem::new(vec![
+                    Line::from(Span::styled(format!(" {}", hunt.label()), Style::default().fg(FG).add_modifier(Modifier::BOLD))),
+                    Line::from(Span::styled(format!("   {}", hunt.description()), Style::default().fg(GRAY))),
+                ])
+            }).collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().bg(SELECTION_BG))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select(Some(app.hunt_index));
+            f.render_stateful_widget(list, area, &mut state);
+
+            let help = Paragraph::new(" [↑/↓] Navigate │ [Enter] Run │ [Esc] Close ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::HuntResults { hunt } => {
+            let title = format!("{} (last {})", hunt.label(), app.format_interval());
+            let (area, block) = draw_popup_shell(f, &title, 90, 75, Style::default().fg(ORANGE));
+            let rows = match &app.hunt_rows {
+                None => {
+                    let p = Paragraph::new("Running hunt...").block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                    f.render_widget(p, area);
+                    return;
+                }
+                Some(Err(e)) => {
+                    let p = Paragraph::new(format!("Hunt failed: {}", e)).block(block).wrap(Wrap { trim: false }).style(Style::default().fg(RED));
+                    f.render_widget(p, area);
+                    return;
+                }
+                Some(Ok(rows)) if rows.is_empty() => {
+                    let p = Paragraph::new(format!("Nothing found: {}", hunt.description().to_lowercase()))
+                        .block(block).alignment(Alignment::Center).style(Style::default().fg(GREEN));
+                    f.render_widget(p, area);
+                    return;
+                }
+                Some(Ok(rows)) => rows,
+            };
+
+            let table_rows = rows.iter().map(|row| {
+                let mut agents = row.agents.join(", ");
+                if row.agents.len() == 5 {
+                    agents.push_str(", …");
+                }
+                Row::new(vec![
+                    Cell::from(row.key.clone()).style(Style::default().fg(FG).add_modifier(Modifier::BOLD)),
+                    Cell::from(row.count.to_string()).style(Style::default().fg(ORANGE)),
+                    Cell::from(agents).style(Style::default().fg(CYAN)),
+                    Cell::from(row.details.join(", ")).style(Style::default().fg(GRAY)),
+                    Cell::from(row.last_seen.as_deref().map(|t| format_table_time(app, t)).unwrap_or_else(|| "-".to_string())).style(Style::default().fg(DARK_GRAY)),
+                ])
+            });
+            let table = Table::new(table_rows, [
+                Constraint::Length(28),
+                Constraint::Length(8),
+                Constraint::Percentage(30),
+                Constraint::Min(20),
+                Constraint::Length(20),
+            ])
+            .header(Row::new(vec![hunt.key_field().1, "Alerts", "Agents", hunt.detail_field().1, "Last Seen"]).style(Style::default().fg(BLUE)))
+            .block(block)
+            .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD));
+            let mut state = TableState::default();
+            state.select(Some(app.hunt_row_index.min(rows.len() - 1)));
+            f.render_stateful_widget(table, area, &mut state);
+
+            let help = if hunt.keyed_by_srcip() {
+                " [↑/↓] Navigate │ [Enter] Pivot on source IP │ [Esc] Close "
+            } else {
+                " [↑/↓] Navigate │ [Esc] Close "
+            };
+            let help = Paragraph::new(help)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::DuplicateAgents => {
+            let (area, block) = draw_popup_shell(f, "Duplicate Agents", 80, 70, Style::default().fg(YELLOW));
+            let rows = app.duplicate_rows();
+
+            if rows.is_empty() {
+                let p = Paragraph::new("No duplicate names or shared IPs").block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                f.render_widget(p, area);
+                return;
+            }
+
+            // The set is named on its first row only
+            let items: Vec<ListItem> = rows.iter().enumerate().map(|(i, (set, agent))| {
+                let first = i == 0 || rows[i - 1].0 != *set;
+                let label = if first {
+                    format!(" {} {} ×{}", set.kind.label(), set.value, set.agent_ids.len())
+                } else {
+                    String::new()
+                };
+                let status_color = match agent.status.as_str() {
+                    "active" => GREEN,
+                    "disconnected" => RED,
+                    _ => DARK_GRAY,
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<32}", label), Style::default().fg(YELLOW)),
+                    Span::styled(format!("{:<6}", agent.id), Style::default().fg(CYAN)),
+                    Span::styled(format!("{:<24}", agent.name), Style::default().fg(FG)),
+                    Span::styled(format!("{:<16}", agent.ip.as_deref().unwrap_or("-")), Style::default().fg(GRAY)),
+                    Span::styled(format!("{:<18}", agent.status), Style::default().fg(status_color)),
+                    Span::styled(format_last_keep_alive(&agent.last_keep_alive), Style::default().fg(DARK_GRAY)),
+                ]))
+            }).collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select(Some(app.duplicate_index.min(rows.len() - 1)));
+            f.render_stateful_widget(list, area, &mut state);
+
+            let help = Paragraph::new(" [↑/↓] Navigate │ [Enter] Inspect │ [Esc] Close ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::Notifications => {
+            let (area, block) = draw_popup_shell(f, "Notifications", 80, 70, Style::default().fg(BLUE));
+
+            if app.notification_history.is_empty() {
+                let p = Paragraph::new("No notifications yet").block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                f.render_widget(p, area);
+                return;
+            }
+
+            // Newest first
+            let items: Vec<ListItem> = app.notification_history.iter().rev().map(|n| {
+                let (icon, color) = match n.level {
+                    crate::app::NotificationLevel::Info => (Icon::Info, BLUE),
+                    crate::app::NotificationLevel::Success => (Icon::Check, GREEN),
+                    crate::app::NotificationLevel::Warning => (Icon::Warning, YELLOW),
+                    crate::app::NotificationLevel::Error => (Icon::Error, RED),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {} ", app.display_tz.format(&n.time.to_rfc3339(), "%H:%M:%S")), Style::default().fg(GRAY)),
+                    Span::styled(app.icons.pad(icon), Style::default().fg(color)),
+                    Span::styled(n.message.clone(), Style::default().fg(if n.level == crate::app::NotificationLevel::Error { RED } else { FG })),
+                ]))
+            }).collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select(Some(app.notification_index));
+            f.render_stateful_widget(list, area, &mut state);
+
+            let help = Paragraph::new(format!(" [↑/↓] Navigate │ [Esc] Close │ last {} kept ", crate::app::NOTIFICATION_HISTORY))
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::RequestLog => {
+            let (area, block) = draw_popup_shell(f, "API Requests", 90, 80, Style::default().fg(PURPLE));
+            let log = app.api.as_ref().map(|a| a.request_log()).unwrap_or_default();
+
+            if log.is_empty() {
+                let p = Paragraph::new("No requests recorded yet").block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                f.render_widget(p, area);
+                return;
+            }
+
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(45), Constraint::Min(3), Constraint::Length(1)])
+                .split(inner);
+
+            let items: Vec<ListItem> = log.iter().map(|r| {
+                let (status, color) = match r.status {
+                    Some(code) if code < 300 => (code.to_string(), GREEN),
+                    Some(code) if code < 500 => (code.to_string(), YELLOW),
+                    Some(code) => (code.to_string(), RED),
+                    None => ("ERR".to_string(), RED),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {} ", app.display_tz.format(&r.time.to_rfc3339(), "%H:%M:%S")), Style::default().fg(GRAY)),
+                    Span::styled(format!("{:<6} ", r.method), Style::default().fg(CYAN)),
+                    Span::styled(format!("{:<4} ", status), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("{:>6}ms ", r.elapsed_ms), Style::default().fg(GRAY)),
+                    Span::styled(r.url.clone(), Style::default().fg(FG)),
+                ]))
+            }).collect();
+
+            let selected = app.request_log_index.min(log.len() - 1);
+            let list = List::new(items)
+                .block(Block::default().borders(Borders::BOTTOM).border_style(Style::default().fg(GRAY)))
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select(Some(selected));
+            f.render_stateful_widget(list, chunks[0], &mut state);
+
+            let record = &log[selected];
+            let mut lines = vec![
+                Line::from(vec![
+                    Span::styled(format!("{} ", record.method), Style::default().fg(CYAN).add_modifier(Modifier::BOLD)),
+                    Span::styled(record.url.clone(), Style::default().fg(FG)),
+                ]),
+                Line::from(Span::styled(
+                    format!("Status: {}  │  {} ms  │  attempt {}",
+                        record.status.map(|s| s.to_string()).unwrap_or_else(|| "no response".to_string()),
+                        record.elapsed_ms, record.attempt + 1),
+                    Style::default().fg(GRAY),
+                )),
+            ];
+            if let Some(error) = &record.error {
+                lines.push(Line::from(Span::styled(error.clone(), Style::default().fg(RED))));
+            }
+            lines.push(Line::from(""));
+            match &record.body {
+                Some(body) => lines.extend(body.lines().map(|l| Line::from(Span::styled(l.to_string(), Style::default().fg(FG))))),
+                None => lines.push(Line::from(Span::styled("(no request body)", Style::default().fg(GRAY)))),
+            }
+            f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[1]);
+
+            let help = Paragraph::new(format!(" [↑/↓] Navigate │ [Esc] Close │ last {} kept ", crate::api::REQUEST_LOG_SIZE))
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, chunks[2]);
+        },
+        PopupMode::IndexerHealth => {
+            let (area, block) = draw_popup_shell(f, "Indexer Health", 80, 75, Style::default().fg(CYAN));
+            let health = match &app.indexer_health {
+                None => {
+                    let p = Paragraph::new("Loading indexer health...").block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                    f.render_widget(p, area);
+                    return;
+                }
+                Some(Err(e)) => {
+                    let p = Paragraph::new(format!("Failed to query the indexer: {}", e)).block(block).wrap(Wrap { trim: false }).style(Style::default().fg(RED));
+                    f.render_widget(p, area);
+                    return;
+                }
+                Some(Ok(health)) => health,
+            };
+
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+            let header_height = 3 + health.disks.len() as u16;
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(header_height), Constraint::Min(3), Constraint::Length(1)])
+                .split(inner);
+
+            let cluster = &health.cluster;
+            let mut lines = vec![
+                Line::from(vec![
+                    Span::styled(format!(" {} ", cluster.cluster_name), Style::default().fg(FG).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!(" {} ", cluster.status.to_uppercase()), Style::default().fg(health_color(&cluster.status)).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("  {} nodes │ {} active shards │ ", cluster.number_of_nodes, cluster.active_shards), Style::default().fg(GRAY)),
+                    Span::styled(format!("{} unassigned", cluster.unassigned_shards), Style::default().fg(if cluster.unassigned_shards > 0 { YELLOW } else { GRAY })),
+                ]),
+                Line::from(""),
+            ];
+            for disk in &health.disks {
+                let pct = disk.disk_percent().unwrap_or(0);
+                // OpenSearch watermarks: 85% stops new shards, 90% moves shards away, 95% makes indices read-only
+                let (color, note) = match pct {
+                    95.. => (RED, "flood stage: indices read-only"),
+                    90..=94 => (ORANGE, "high watermark"),
+                    85..=89 => (YELLOW, "low watermark"),
+                    _ => (GREEN, ""),
+                };
+                let bytes = |v: &Option<String>| v.as_deref().and_then(|b| b.parse::<u64>().ok()).map(|b| format_kib(b / 1024)).unwrap_or_else(|| "-".to_string());
+                lines.push(Line::from(vec![
+                    Span::styled(format!(" {}{:<20}", app.icons.pad(Icon::Disk), disk.node), Style::default().fg(FG)),
+                    Span::styled(format!("{:>3}% ", pct), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("{} used, {} free ", bytes(&disk.disk_used), bytes(&disk.disk_avail)), Style::default().fg(GRAY)),
+                    Span::styled(note, Style::default().fg(color)),
+                ]));
+            }
+            lines.push(Line::from(""));
+            f.render_widget(Paragraph::new(lines), chunks[0]);
+
+            let size = |i: &crate::models::IndexerIndex| i.store_size.as_deref().and_then(|b| b.parse::<u64>().ok()).unwrap_or(0);
+            let items: Vec<ListItem> = health.indices.iter().map(|index| {
+                let status = index.health.as_deref().unwrap_or("");
+                let closed = index.status.as_deref() == Some("close");
+                ListItem::new(Line::from(vec![
+                    Span::styled("● ", Style::default().fg(if closed { GRAY } else { health_color(status) })),
+                    Span::styled(format!("{:<44}", index.index), Style::default().fg(if closed { GRAY } else { FG })),
+                    Span::styled(format!("{:>12} docs ", index.docs_count.as_deref().unwrap_or("-")), Style::default().fg(GRAY)),
+                    Span::styled(format!("{:>10}", format_kib(size(index) / 1024)), Style::default().fg(CYAN)),
+                ]))
+            }).collect();
+            let total: u64 = health.indices.iter().map(size).sum();
+            let list = List::new(items)
+                .block(Block::default()
+                    .borders(Borders::TOP)
+                    .border_style(Style::default().fg(GRAY))
+                    .title(format!(" {} indices, {} ", health.indices.len(), format_kib(total / 1024))))
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select((!health.indices.is_empty()).then_some(app.indexer_health_index));
+            f.render_stateful_widget(list, chunks[1], &mut state);
+
+            let help = Paragraph::new(" [↑/↓] Navigate │ [Esc] Close ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, chunks[2]);
+        },
+        PopupMode::Certificates => {
+            let (area, block) = draw_popup_shell(f, "TLS Certificates", 75, 70, Style::default().fg(CYAN));
+            let Some(chains) = &app.certificates else {
+                let p = Paragraph::new("Reading certificates...").block(block).alignment(Alignment::Center).style(Style::default().fg(GRAY));
+                f.render_widget(p, area);
+                return;
+            };
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(3), Constraint::Length(1)])
+                .split(inner);
+
+            let now = chrono::Utc::now();
+            let field = |name: &str, value: String, color: ratatui::style::Color| Line::from(vec![
+                Span::styled(format!("   {:<10}", name), Style::default().fg(GRAY)),
+                Span::styled(value, Style::default().fg(color)),
+            ]);
+            let mut lines = Vec::new();
+            for chain in chains {
+                lines.push(Line::from(vec![
+                    Span::styled(format!(" {} ", chain.endpoint), Style::default().fg(FG).add_modifier(Modifier::BOLD)),
+                    Span::styled(chain.url.clone(), Style::default().fg(GRAY)),
+                ]));
+                match &chain.result {
+                    Err(e) => lines.push(Line::from(Span::styled(format!("   {}", e), Style::default().fg(RED)))),
+                    Ok(certs) => for (i, cert) in certs.iter().enumerate() {
+                        let days = cert.days_left(now);
+                        let (color, note) = match days {
+                            ..=-1 => (RED, "expired".to_string()),
+                            d if d < crate::app::certs::EXPIRY_WARN_DAYS => (YELLOW, format!("{} days left", d)),
+                            d => (GREEN, format!("{} days left", d)),
+                        };
+                        let role = if i == 0 { "Leaf" } else { "Chain" };
+                        let self_signed = if cert.is_self_signed() { " (self-signed)" } else { "" };
+                        lines.push(Line::from(Span::styled(format!("  {} {}{}", role, i + 1, self_signed), Style::default().fg(CYAN))));
+                        lines.push(field("Subject", cert.subject.clone(), FG));
+                        lines.push(field("Issuer", cert.issuer.clone(), FG));
+                        if !cert.san.is_empty() {
+                            lines.push(field("SAN", cert.san.join(", "), FG));
+                        }
+                        lines.push(field("Valid", format!("{} → {}", cert.not_before.format("%Y-%m-%d"), cert.not_after.format("%Y-%m-%d")), FG));
+                        lines.push(field("Expiry", note, color));
+                    },
+                }
+                lines.push(Line::from(""));
+            }
+            f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+            let help = Paragraph::new(" [Esc] Close ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, chunks[1]);
+        },
+        PopupMode::GroupMembership { agent_id } => {
+            let (area, block) = draw_popup_shell(f, &format!("Groups of Agent {}", agent_id), 45, 60, Style::default().fg(BLUE));
+            let current = app.agents.iter()
+                .find(|a| &a.id == agent_id)
+                .and_then(|a| a.group.clone())
+                .unwrap_or_default();
+
+            let list_items: Vec<_> = app.groups.iter().map(|g| {
+                let ticked = app.membership_groups.contains(&g.name);
+                let member = current.contains(&g.name);
+                // Pending changes stand out until confirmed
+                let style = match (ticked, member) {
+                    (true, false) => Style::default().fg(GREEN),
+                    (false, true) => Style::default().fg(RED),
+                    _ => Style::default().fg(FG),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(if ticked { " [✓] " } else { " [ ] " }, style),
+                    Span::styled(g.name.clone(), style),
+                ]))
+            }).collect();
+
+            let list = List::new(list_items)
+                .block(block)
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+
+            let mut state = ListState::default();
+            state.select(Some(app.membership_index));
+            f.render_stateful_widget(list, area, &mut state);
+
+            let help = Paragraph::new(" [Space] Toggle │ [Enter] Review │ [Esc] Cancel ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::ConfirmGroupMembership { agent_id, add, remove } => {
+            let (area, block) = draw_popup_shell(f, "Apply Group Changes", 50, 40, Style::default().fg(YELLOW).add_modifier(Modifier::BOLD));
+            let name = app.agents.iter().find(|a| &a.id == agent_id).map(|a| a.name.clone()).unwrap_or_default();
+
+            let mut lines = vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("  Agent {} ({}):", name, agent_id), Style::default().fg(FG))),
+                Line::from(""),
+            ];
+            for g in add {
+                lines.push(Line::from(Span::styled(format!("  + {}", g), Style::default().fg(GREEN))));
+            }
+            for g in remove {
+                lines.push(Line::from(Span::styled(format!("  - {}", g), Style::default().fg(RED))));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("  [Enter] Apply  [Esc] Cancel", Style::default().fg(YELLOW))));
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        },
+        PopupMode::Logtest => {
+            let (area, block) = draw_popup_shell(f, "Test Log Line (logtest)", 75, 70, Style::default().fg(BLUE));
+            let cursor = |field: usize| if app.logtest_field == field { "█" } else { "" };
+            let label = |field: usize, text: &'static str| Span::styled(text, if app.logtest_field == field {
+                Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(FG)
+            });
+
+            let mut lines = vec![
+                Line::from(""),
+                Line::from(vec![
+                    label(0, "  Event:    "),
+                    Span::styled(app.logtest_event.clone(), Style::default().fg(GREEN)),
+                    Span::styled(cursor(0), Style::default().fg(YELLOW)),
+                ]),
+                Line::from(vec![
+                    label(1, "  Location: "),
+                    Span::styled(app.logtest_location.clone(), Style::default().fg(GREEN)),
+                    Span::styled(cursor(1), Style::default().fg(YELLOW)),
+                ]),
+                Line::from(vec![
+                    label(2, "  Format:   "),
+                    Span::styled(format!("◀ {} ▶", app.logtest_format()), Style::default().fg(GREEN)),
+                ]),
+                Line::from(""),
+            ];
+
+            match &app.logtest_result {
+                None => lines.push(Line::from(Span::styled("  Press Enter to run the event through the manager's decoders and rules", Style::default().fg(DARK_GRAY)))),
+                Some(res) => {
+                    let decoder = match (&res.decoder, &res.parent_decoder) {
+                        (Some(name), Some(parent)) if parent != name => format!("{} (parent {})", name, parent),
+                        (Some(name), _) => name.clone(),
+                        (None, _) => "no decoder matched".to_string(),
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled("  Decoder:  ", Style::default().fg(BLUE)),
+                        Span::styled(decoder, Style::default().fg(FG)),
+                    ]));
+                    if let Some(program) = &res.program_name {
+                        lines.push(Line::from(vec![
+                            Span::styled("  Program:  ", Style::default().fg(BLUE)),
+                            Span::styled(program.clone(), Style::default().fg(FG)),
+                        ]));
+                    }
+                    match (&res.rule_id, res.rule_level) {
+                        (Some(id), level) => {
+                            let level = level.unwrap_or(0);
+                            lines.push(Line::from(vec![
+                                Span::styled("  Rule:     ", Style::default().fg(BLUE)),
+                                Span::styled(format!("{} ", id), Style::default().fg(FG).add_modifier(Modifier::BOLD)),
+                                Span::styled(format!("level {} ({})", level, get_severity_label(&app.severity, level)), get_severity_style(&app.severity, level)),
+                            ]));
+                            lines.push(Line::from(Span::styled(format!("            {}", res.rule_description.clone().unwrap_or_default()), Style::default().fg(FG))));
+                        }
+                        (None, _) => lines.push(Line::from(vec![
+                            Span::styled("  Rule:     ", Style::default().fg(BLUE)),
+                            Span::styled("no rule matched", Style::default().fg(DARK_GRAY)),
+                        ])),
+                    }
+                    lines.push(Line::from(vec![
+                        Span::styled("  Alert:    ", Style::default().fg(BLUE)),
+                        if res.alert {
+                            Span::styled("yes", Style::default().fg(RED).add_modifier(Modifier::BOLD))
+                        } else {
+                            Span::styled("no", Style::default().fg(DARK_GRAY))
+                        },
+                    ]));
+                    if !res.fields.is_empty() {
+                        lines.push(Line::from(""));
+                        lines.push(Line::from(Span::styled("  Decoded fields", Style::default().fg(BLUE))));
+                        for (name, value) in &res.fields {
+                            lines.push(Line::from(vec![
+                                Span::styled(format!("    {:<16}", name), Style::default().fg(DARK_GRAY)),
+                                Span::styled(value.clone(), Style::default().fg(FG)),
+                            ]));
+                        }
+                    }
+                    for msg in &res.messages {
+                        lines.push(Line::from(Span::styled(format!("  {}", msg), Style::default().fg(YELLOW))));
+                    }
+                }
+            }
+
+            f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+
+            let help = Paragraph::new(" [Tab] Field │ [←/→] Format │ [Enter] Test │ [Esc] Close ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, Rect::new(area.x + 1, area.y + area.height - 1, area.width.saturating_sub(2), 1));
+        },
+        PopupMode::SetupBundle { import } => {
+            let title = if *import { "Import Setup" } else { "Export Setup" };
+            let (area, block) = draw_popup_shell(f, title, 60, 25, Style::default().fg(GREEN));
+            let action = if *import { "Import" } else { "Export" };
+
+            let p = Paragraph::new(format!(" Setup file (keys, display, profiles without passwords, filters):\n\n {}█\n\n [Enter] {}  [Esc] Cancel ", app.input_buffer, action))
+                .block(block)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false })
+                .style(Style::default().fg(FG));
+            f.render_widget(p, area);
+        },
+        PopupMode::NewRuleFile => {
+            let kind = app.ruleset_kind;
+            let (area, block) = draw_popup_shell(f, &format!("New {} File", kind.label()), 45, 20, Style::default().fg(GREEN));
+
+            let p = Paragraph::new(format!(" File name ({}, .xml added):\n\n {}█\n\n [Enter] Edit  [Esc] Cancel ", kind.dirname(), app.input_buffer))
+                .block(block)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(FG));
+            f.render_widget(p, area);
+        },
+        PopupMode::ConfirmRuleUpload { kind, filename } => {
+            let color = if app.rule_draft_error.is_some() { RED } else { YELLOW };
+            let (area, block) = draw_popup_shell(f, &format!("Upload {}", filename), 55, 30, Style::default().fg(color).add_modifier(Modifier::BOLD));
+
+            let mut lines = vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("  {} lines edited in {}/{}", app.rule_draft.lines().count(), kind.dirname(), filename), Style::default().fg(FG))),
+                Line::from(""),
+            ];
+            match &app.rule_draft_error {
+                Some(err) => {
+                    lines.push(Line::from(Span::styled(format!("  {}{}", app.icons.pad(Icon::Invalid), err), Style::default().fg(RED))));
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled("  [e] Edit again  [Esc] Discard", Style::default().fg(RED))));
+                }
+                None => {
+                    lines.push(Line::from(Span::styled(format!("  {}XML structure looks valid; the manager checks it after upload", app.icons.pad(Icon::Check)), Style::default().fg(GREEN))));
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled("  [Enter] Upload  [e] Edit again  [Esc] Discard", Style::default().fg(YELLOW))));
+                }
+            }
+            f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+        },
+        PopupMode::ConfirmRuleDelete { kind, filename } => {
+            let (area, block) = draw_popup_shell(f, &format!("Delete {} File", kind.label()), 45, 20, Style::default().fg(RED).add_modifier(Modifier::BOLD));
+            let lines = vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("  Delete {}/{}?", kind.dirname(), filename), Style::default().fg(FG))),
+                Line::from(Span::styled("  It stays loaded until the manager restarts.", Style::default().fg(DARK_GRAY))),
+                Line::from(""),
+                Line::from(Span::styled("  [Enter] Delete  [Esc] Cancel", Style::default().fg(RED))),
+            ];
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        },
+        PopupMode::ConfirmManagerRestart => {
+            let (area, block) = draw_popup_shell(f, "Restart Manager", 45, 20, Style::default().fg(YELLOW).add_modifier(Modifier::BOLD));
+            let lines = vec![
+                Line::from(""),
+                Line::from(Span::styled("  Restart the Wazuh manager to load the ruleset?", Style::default().fg(FG))),
+                Line::from(Span::styled("  Agents reconnect automatically; events are queued meanwhile.", Style::default().fg(DARK_GRAY))),
+                Line::from(""),
+                Line::from(Span::styled("  [Enter] Restart  [Esc] Later", Style::default().fg(YELLOW))),
+            ];
+            f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+        },
+        PopupMode::ExportFields => {
+            let (area, block) = draw_popup_shell(f, "Export Fields", 60, 30, Style::default().fg(PURPLE));
+            let lines = vec![
+                Line::from(""),
+                Line::from(Span::styled("  JSON paths to keep, separated by commas or spaces:", Style::default().fg(GRAY))),
+                Line::from(Span::styled("  @timestamp, agent.name, rule.id, data.srcip, rule.groups[0]", Style::default().fg(DARK_GRAY))),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("  Fields: ", Style::default().fg(BLUE)),
+                    Span::styled(format!("{}█", app.input_buffer), Style::default().fg(FG)),
+                ]),
+                Line::from(""),
+                Line::from(Span::styled("  Empty exports whole alerts. CEF, LEEF and Syslog keep their own fields.", Style::default().fg(DARK_GRAY))),
+                Line::from(""),
+                Line::from(Span::styled("  [Enter] Save  [Esc] Cancel", Style::default().fg(YELLOW))),
+            ];
+            f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+        },
+        PopupMode::CdbAddEntry { filename } => {
+            let (area, block) = draw_popup_shell(f, &format!("Add to {}", filename), 50, 20, Style::default().fg(BLUE));
+
+            let p = Paragraph::new(format!(" Entry (key or key:value):\n\n {}█\n\n [Enter] Review  [Esc] Cancel ", app.input_buffer))
+                .block(block)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(FG));
+            f.render_widget(p, area);
+        },
+        PopupMode::AgentLabels { agent_ids } => {
+            let (area, block) = draw_popup_shell(f, &format!("Tag {} Agents", agent_ids.len()), 55, 30, Style::default().fg(BLUE));
+            // Tags the targets share are shown as current; others only as suggestions
+            let current: Vec<&String> = app.agent_labels.tags(&agent_ids[0]).iter()
+                .filter(|t| agent_ids.iter().all(|id| app.agent_labels.tags(id).contains(t)))
+                .collect();
+            let known = app.agent_labels.all_tags();
+            let lines = vec![
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("  Current: ", Style::default().fg(DARK_GRAY)),
+                    Span::styled(if current.is_empty() { "-".to_string() } else { current.iter().map(|t| t.as_str()).collect::<Vec<_>>().join(", ") }, Style::default().fg(CYAN)),
+                ]),
+                Line::from(vec![
+                    Span::styled("  In use:  ", Style::default().fg(DARK_GRAY)),
+                    Span::styled(if known.is_empty() { "-".to_string() } else { known.join(", ") }, Style::default().fg(FG)),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("  Tags: ", Style::default().fg(BLUE)),
+                    Span::styled(format!("{}█", app.input_buffer), Style::default().fg(FG)),
+                ]),
+                Line::from(""),
+                Line::from(Span::styled("  Comma-separated; -tag removes. Stored locally only.", Style::default().fg(DARK_GRAY))),
+                Line::from(Span::styled("  [Enter] Apply  [Esc] Cancel", Style::default().fg(YELLOW))),
+            ];
+            f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+        },
+        PopupMode::ConfirmCdbChange { filename, key, value, remove } => {
+            let (title, color, sign) = if *remove { ("Remove CDB Entry", RED, "-") } else { ("Add CDB Entry", GREEN, "+") };
+            let (area, block) = draw_popup_shell(f, title, 50, 25, Style::default().fg(color).add_modifier(Modifier::BOLD));
+
+            let lines = vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("  List: {}", filename), Style::default().fg(FG))),
+                Line::from(""),
+                Line::from(Span::styled(format!("  {} {}", sign, crate::app::cdb::format_entry(key, value)), Style::default().fg(color))),
+                Line::from(""),
+                Line::from(Span::styled("  [Enter] Apply  [Esc] Cancel", Style::default().fg(color))),
+            ];
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        },
+        PopupMode::ConfirmAgentAction { action, agent_ids } => {
+            let color = if action.is_destructive() { RED } else { YELLOW };
+            let (area, block) = draw_popup_shell(f, action.title(), 60, 60, Style::default().fg(color).add_modifier(Modifier::BOLD));
+            let agents: Vec<_> = agent_ids.iter().filter_map(|id| app.agents.iter().find(|a| &a.id == id)).collect();
+
+            let intro = match action {
+                AgentAction::DeleteStale => format!("Delete {} agents registered more than {} days ago:", agent_ids.len(), app.stale_agent_days),
+                _ => format!("{} {} agents:", action.verb(), agent_ids.len()),
+            };
+            let mut counts: Vec<Span> = vec![Span::raw("  ")];
+            for (i, (status, n)) in status_counts(&agents).into_iter().enumerate() {
+                if i > 0 {
+                    counts.push(Span::styled("  •  ", Style::default().fg(DARK_GRAY)));
+                }
+                counts.push(Span::styled(format!("{} {}", n, status.replace('_', " ")), Style::default().fg(agent_status_color(&status))));
+            }
+            if agents.len() < agent_ids.len() {
+                counts.push(Span::styled(format!("  ({} not loaded)", agent_ids.len() - agents.len()), Style::default().fg(DARK_GRAY)));
+            }
+            let mut lines = vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("  {}", intro), Style::default().fg(FG))),
+                Line::from(counts),
+                Line::from(""),
+            ];
+
+            // Leave room for the header lines and the key hints
+            let max_listed = area.height.saturating_sub(9) as usize;
+            for a in agents.iter().take(max_listed) {
+                let detail = match action {
+                    AgentAction::DeleteStale => a.date_add.clone(),
+                    _ => a.version.clone(),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:<6}", a.id), Style::default().fg(DARK_GRAY)),
+                    Span::styled(format!("{:<28}", a.name), Style::default().fg(FG)),
+                    Span::styled(format!("{:<16}", a.status), Style::default().fg(agent_status_color(&a.status))),
+                    Span::styled(detail.unwrap_or_default(), Style::default().fg(DARK_GRAY)),
+                ]));
+            }
+            if agents.len() > max_listed {
+                lines.push(Line::from(Span::styled(format!("  … and {} more", agents.len() - max_listed), Style::default().fg(DARK_GRAY))));
+            }
+
+            lines.push(Line::from(""));
+            let disconnected = app.disconnected(*action, agent_ids).len();
+            if disconnected > 0 {
+                lines.push(Line::from(Span::styled(format!("  {} disconnected: [Q] Run on the others, queue it for these until they reconnect", disconnected), Style::default().fg(ORANGE))));
+            }
+            lines.push(Line::from(Span::styled(format!("  [Enter] {}  [Esc] Cancel", action.verb()), Style::default().fg(color))));
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        },
+        PopupMode::RunCommand { agent_id } => {
+            let (area, block) = draw_popup_shell(f, &format!("Run Command on Agent {}", agent_id), 50, 40, Style::default().fg(YELLOW));
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(inner);
+
+            let items: Vec<ListItem> = app.remote_commands_allowed().into_iter().map(|c| {
+                ListItem::new(Line::from(Span::styled(format!(" $ {}", c), Style::default().fg(FG))))
+            }).collect();
+            let list = List::new(items)
+                .highlight_style(Style::default().bg(SELECTION_BG).add_modifier(Modifier::BOLD))
+                .highlight_symbol(app.icons.pad(Icon::Pointer));
+            let mut state = ListState::default();
+            state.select(Some(app.remote_command_index));
+            f.render_stateful_widget(list, chunks[0], &mut state);
+
+            let help = Paragraph::new(" [↑/↓] Navigate │ [Enter] Review │ [Esc] Cancel ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, chunks[1]);
+        },
+        PopupMode::ConfirmRunCommand { agent_id, command } => {
+            let (area, block) = draw_popup_shell(f, "Run Command", 50, 25, Style::default().fg(YELLOW).add_modifier(Modifier::BOLD));
+            let lines = vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("  Run on agent {}:", agent_id), Style::default().fg(FG))),
+                Line::from(""),
+                Line::from(Span::styled(format!("  $ {}", command), Style::default().fg(CYAN).add_modifier(Modifier::BOLD))),
+                Line::from(""),
+                Line::from(Span::styled(format!("  Sent through the {} active-response script.", crate::app::remote_cmd::AR_SCRIPT), Style::default().fg(DARK_GRAY))),
+                Line::from(""),
+                Line::from(Span::styled("  [Enter] Run  [Esc] Cancel", Style::default().fg(YELLOW))),
+            ];
+            f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+        },
+        PopupMode::CommandOutput => {
+            let Some(run) = &app.remote_command else { return };
+            let now = chrono::Utc::now();
+            let color = match &run.result {
+                Some(Ok(r)) if r.exit_code == 0 => GREEN,
+                Some(_) => RED,
+                None if run.is_pending(now) => CYAN,
+                None => ORANGE,
+            };
+            let (area, block) = draw_popup_shell(f, &format!("Agent {}: {}", run.agent_id, run.command), 80, 75, Style::default().fg(color));
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(2), Constraint::Min(0), Constraint::Length(1)])
+                .split(inner);
+
+            let status = Line::from(Span::styled(format!(" {}", run.status(now)), Style::default().fg(color).add_modifier(Modifier::BOLD)));
+            f.render_widget(Paragraph::new(status).wrap(Wrap { trim: false }), chunks[0]);
+
+            if let Some(Ok(result)) = &run.result {
+                let lines: Vec<Line> = result.output.lines().map(|l| Line::from(Span::styled(l.to_string(), Style::default().fg(FG)))).collect();
+                f.render_widget(Paragraph::new(lines).scroll((app.remote_output_scroll, 0)), chunks[1]);
+            }
+
+            let help = Paragraph::new(" [↑/↓] Scroll │ [Enter/Esc] Close ")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(PURPLE));
+            f.render_widget(help, chunks[2]);
         },
         _ => {}
     }
@@ -177,195 +1434,57 @@ pub fn draw_interval_popup(f: &mut Frame, app: &mut App) {
     }
 }
 
-fn draw_help_popup(f: &mut Frame, app: &App) {
+fn draw_help_popup(f: &mut Frame, app: &mut App) {
     let (area, block) = draw_popup_shell(f, "Keyboard Shortcuts", 70, 80, Style::default().fg(BLUE).add_modifier(Modifier::BOLD));
-    
-    // Build help content based on current view
-    let mut lines: Vec<Line> = vec![
-        Line::from(vec![
-            Span::styled("  GLOBAL KEYS", Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("  ?       ", Style::default().fg(CYAN)),
-            Span::styled("Toggle this help", Style::default().fg(FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  q       ", Style::default().fg(CYAN)),
-            Span::styled("Quit / Go back", Style::default().fg(FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  Tab     ", Style::default().fg(CYAN)),
-            Span::styled("Switch view", Style::default().fg(FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  r       ", Style::default().fg(CYAN)),
-            Span::styled("Refresh data", Style::default().fg(FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  /       ", Style::default().fg(CYAN)),
-            Span::styled("Start search/filter", Style::default().fg(FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("          ", Style::default().fg(CYAN)),
-            Span::styled("Syntax: ", Style::default().fg(DARK_GRAY)),
-            Span::styled("n:name st:active ip:10 os:linux sev:high", Style::default().fg(BLUE)),
-        ]),
-        Line::from(vec![
-            Span::styled("  Esc     ", Style::default().fg(CYAN)),
-            Span::styled("Cancel / Close popup", Style::default().fg(FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  i       ", Style::default().fg(CYAN)),
-            Span::styled("Set time interval", Style::default().fg(FG)),
-        ]),
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let search = if app.help_query.is_empty() {
+        Line::from(Span::styled("  Type to search the keys of every view", Style::default().fg(DARK_GRAY)))
+    } else {
         Line::from(vec![
-            Span::styled("  +/-     ", Style::default().fg(CYAN)),
-            Span::styled("Adjust interval (+/- 15m)", Style::default().fg(FG)),
-        ]),
-        Line::from(""),
-    ];
-    
-    // Add view-specific help
-    match app.active_view {
-        crate::app::ActiveView::Dashboard => {
-            lines.push(Line::from(vec![
-                Span::styled("  DASHBOARD", Style::default().fg(GREEN).add_modifier(Modifier::BOLD)),
-            ]));
-            lines.push(Line::from(""));
-            lines.push(Line::from(vec![
-                Span::styled("  j       ", Style::default().fg(CYAN)),
-                Span::styled("Quick jump to agent", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  1-4     ", Style::default().fg(CYAN)),
-                Span::styled("Filter by severity (1=Critical, 4=Low)", Style::default().fg(FG)),
-            ]));
-        }
-        crate::app::ActiveView::AgentList => {
-            lines.push(Line::from(vec![
-                Span::styled("  AGENTS LIST", Style::default().fg(GREEN).add_modifier(Modifier::BOLD)),
-            ]));
-            lines.push(Line::from(""));
-            lines.push(Line::from(vec![
-                Span::styled("  Enter   ", Style::default().fg(CYAN)),
-                Span::styled("Inspect selected agent", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  Space   ", Style::default().fg(CYAN)),
-                Span::styled("Toggle selection (multi-select)", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  s       ", Style::default().fg(CYAN)),
-                Span::styled("Cycle sort column/order", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  U       ", Style::default().fg(CYAN)),
-                Span::styled("Upgrade selected agents", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  R       ", Style::default().fg(CYAN)),
-                Span::styled("Restart selected agents", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  G       ", Style::default().fg(CYAN)),
-                Span::styled("Assign to group", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  h       ", Style::default().fg(CYAN)),
-                Span::styled("SSH to agent", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  o       ", Style::default().fg(CYAN)),
-                Span::styled("Open in browser", Style::default().fg(FG)),
-            ]));
-        }
-        crate::app::ActiveView::AgentInspector => {
-            lines.push(Line::from(vec![
-                Span::styled("  AGENT INSPECTOR", Style::default().fg(GREEN).add_modifier(Modifier::BOLD)),
-            ]));
-            lines.push(Line::from(""));
-            lines.push(Line::from(vec![
-                Span::styled("  Tab     ", Style::default().fg(CYAN)),
-                Span::styled("Switch category tab", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  Enter   ", Style::default().fg(CYAN)),
-                Span::styled("View log detail / Cycle config", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  f       ", Style::default().fg(CYAN)),
-                Span::styled("Filter logs by severity", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  e       ", Style::default().fg(CYAN)),
-                Span::styled("Export logs to JSON", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  G       ", Style::default().fg(CYAN)),
-                Span::styled("Assign to group", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  h       ", Style::default().fg(CYAN)),
-                Span::styled("SSH to agent", Style::default().fg(FG)),
-            ]));
-        }
-        crate::app::ActiveView::SecurityEvents => {
-            lines.push(Line::from(vec![
-                Span::styled("  SECURITY EVENTS", Style::default().fg(GREEN).add_modifier(Modifier::BOLD)),
-            ]));
-            lines.push(Line::from(""));
-            lines.push(Line::from(vec![
-                Span::styled("  Enter   ", Style::default().fg(CYAN)),
-                Span::styled("View event detail", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  f       ", Style::default().fg(CYAN)),
-                Span::styled("Filter by severity", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  e       ", Style::default().fg(CYAN)),
-                Span::styled("Export to JSON", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  PgUp    ", Style::default().fg(CYAN)),
-                Span::styled("Previous page", Style::default().fg(FG)),
-            ]));
-            lines.push(Line::from(vec![
-                Span::styled("  PgDn    ", Style::default().fg(CYAN)),
-                Span::styled("Next page", Style::default().fg(FG)),
-            ]));
-        }
-        crate::app::ActiveView::GroupManagement => {
-            lines.push(Line::from(vec![
-                Span::styled("  GROUPS (Read-Only)", Style::default().fg(GREEN).add_modifier(Modifier::BOLD)),
-            ]));
-            lines.push(Line::from(""));
-            lines.push(Line::from(vec![
-                Span::styled("  Enter   ", Style::default().fg(CYAN)),
-                Span::styled("View agents in group", Style::default().fg(FG)),
-            ]));
+            Span::styled("  Search: ", Style::default().fg(FG)),
+            Span::styled(format!("{}█", app.help_query), Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
+        ])
+    };
+    f.render_widget(Paragraph::new(search), chunks[0]);
+
+    // Generated from the keymap: global keys and the current view's, or every match of the search
+    let sections = app.help_sections();
+    let width = sections.iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(keys, _)| keys.chars().count()))
+        .max()
+        .unwrap_or(0)
+        .max(7);
+    let mut lines: Vec<Line> = Vec::new();
+    for (context, rows) in sections {
+        let color = if context == KeyContext::Global { YELLOW } else { GREEN };
+        lines.push(Line::from(Span::styled(format!("  {}", context.title()), Style::default().fg(color).add_modifier(Modifier::BOLD))));
+        lines.push(Line::from(""));
+        for (keys, action) in rows {
             lines.push(Line::from(vec![
-                Span::styled("  ↑/↓     ", Style::default().fg(CYAN)),
-                Span::styled("Navigate groups", Style::default().fg(FG)),
+                Span::styled(format!("  {:<width$} ", keys, width = width), Style::default().fg(CYAN)),
+                Span::styled(action, Style::default().fg(FG)),
             ]));
         }
+        lines.push(Line::from(""));
     }
-    
-    lines.push(Line::from(""));
-    lines.push(Line::from(vec![
-        Span::styled("  Press ", Style::default().fg(DARK_GRAY)),
-        Span::styled("?", Style::default().fg(YELLOW)),
-        Span::styled(" or ", Style::default().fg(DARK_GRAY)),
-        Span::styled("Esc", Style::default().fg(YELLOW)),
-        Span::styled(" to close", Style::default().fg(DARK_GRAY)),
-    ]));
-    
-    let p = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false });
-    
-    f.render_widget(p, area);
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(format!("  No keys match '{}'", app.help_query), Style::default().fg(DARK_GRAY))));
+    }
+
+    let max_scroll = lines.len().saturating_sub(chunks[1].height as usize) as u16;
+    app.help_scroll = app.help_scroll.min(max_scroll);
+    f.render_widget(Paragraph::new(lines).scroll((app.help_scroll, 0)), chunks[1]);
+
+    let help = Paragraph::new(" [type] Search │ [↑/↓/PgUp/PgDn] Scroll │ [Esc] Close ")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(PURPLE));
+    f.render_widget(help, chunks[2]);
 }
 
 fn draw_advanced_filter_popup(f: &mut Frame, app: &mut App) {
@@ -376,7 +1495,7 @@ fn draw_advanced_filter_popup(f: &mut Frame, app: &mut App) {
         .borders(Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded)
         .border_style(Style::default().fg(PURPLE).add_modifier(Modifier::BOLD))
-        .title(" 󰈲 Advanced Event Filter ");
+        .title(format!(" {}Advanced Event Filter ", app.icons.pad(Icon::Filter)));
     f.render_widget(block, area);
     
     let inner = Layout::default()
@@ -407,13 +1526,23 @@ fn draw_advanced_filter_popup(f: &mut Frame, app: &mut App) {
         .divider("│");
     f.render_widget(tabs, inner[0]);
     
-    // Content based on active tab
+    // Content based on active tab, with the generated query below when expanded
+    let content = if app.show_query_preview {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(inner[1]);
+        draw_query_preview(f, app, split[1]);
+        split[0]
+    } else {
+        inner[1]
+    };
     match app.filter_popup_tab {
-        FilterPopupTab::Severity => draw_severity_tab(f, app, inner[1]),
-        FilterPopupTab::Agent => draw_agent_filter_tab(f, app, inner[1]),
-        FilterPopupTab::Rule => draw_rule_filter_tab(f, app, inner[1]),
-        FilterPopupTab::Text => draw_text_filter_tab(f, app, inner[1]),
-        FilterPopupTab::Columns => draw_columns_tab(f, app, inner[1]),
+        FilterPopupTab::Severity => draw_severity_tab(f, app, content),
+        FilterPopupTab::Agent => draw_agent_filter_tab(f, app, content),
+        FilterPopupTab::Rule => draw_rule_filter_tab(f, app, content),
+        FilterPopupTab::Text => draw_text_filter_tab(f, app, content),
+        FilterPopupTab::Columns => draw_columns_tab(f, app, content),
     }
     
     // Footer
@@ -425,7 +1554,9 @@ fn draw_advanced_filter_popup(f: &mut Frame, app: &mut App) {
         Span::styled(" [Esc] ", Style::default().fg(RED).add_modifier(Modifier::BOLD)),
         Span::styled("Cancel  ", Style::default().fg(FG)),
         Span::styled(" [c] ", Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
-        Span::styled("Clear All", Style::default().fg(FG)),
+        Span::styled("Clear All  ", Style::default().fg(FG)),
+        Span::styled(" [^Q] ", Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
+        Span::styled(if app.show_query_preview { "Hide Query" } else { "Show Query" }, Style::default().fg(FG)),
     ];
     let footer = Paragraph::new(Line::from(footer_text))
         .alignment(Alignment::Center)
@@ -433,6 +1564,20 @@ fn draw_advanced_filter_popup(f: &mut Frame, app: &mut App) {
     f.render_widget(footer, inner[2]);
 }
 
+fn draw_query_preview(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(Span::styled(" OpenSearch query [^Y] Copy [PgUp/PgDn] Scroll ", Style::default().fg(BLUE)));
+    let lines: Vec<Line> = app.filter_query_preview().lines()
+        .map(|l| Line::from(Span::styled(l.to_string(), Style::default().fg(FG))))
+        .collect();
+    // Clamp so scrolling past the end keeps the last lines in view
+    let max_scroll = (lines.len() as u16).saturating_sub(area.height.saturating_sub(1));
+    let scroll = app.query_preview_scroll.min(max_scroll);
+    f.render_widget(Paragraph::new(lines).block(block).scroll((scroll, 0)), area);
+}
+
 fn draw_severity_tab(f: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -455,13 +1600,13 @@ fn draw_severity_tab(f: &mut Frame, app: &mut App, area: Rect) {
     let presets_content = vec![
         Line::from(vec![
             Span::styled("  [1] ", Style::default().fg(VULN_CRITICAL).add_modifier(Modifier::BOLD)),
-            Span::styled("Critical (15+)    ", Style::default().fg(FG)),
+            Span::styled(format!("Critical ({})    ", app.severity.range_label(Severity::Critical)), Style::default().fg(FG)),
             Span::styled("  [2] ", Style::default().fg(VULN_HIGH).add_modifier(Modifier::BOLD)),
-            Span::styled("High (12-14)    ", Style::default().fg(FG)),
+            Span::styled(format!("High ({})    ", app.severity.range_label(Severity::High)), Style::default().fg(FG)),
             Span::styled("  [3] ", Style::default().fg(VULN_MEDIUM).add_modifier(Modifier::BOLD)),
-            Span::styled("Medium (7-11)    ", Style::default().fg(FG)),
+            Span::styled(format!("Medium ({})    ", app.severity.range_label(Severity::Medium)), Style::default().fg(FG)),
             Span::styled("  [4] ", Style::default().fg(VULN_LOW).add_modifier(Modifier::BOLD)),
-            Span::styled("Low (0-6)", Style::default().fg(FG)),
+            Span::styled(format!("Low ({})", app.severity.range_label(Severity::Low)), Style::default().fg(FG)),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -503,8 +1648,8 @@ fn draw_severity_tab(f: &mut Frame, app: &mut App, area: Rect) {
     
     let v1 = app.filter_input_1.parse::<u64>().unwrap_or(0);
     let v2 = app.filter_input_2.parse::<u64>().unwrap_or(15);
-    let style1 = get_severity_style(v1);
-    let style2 = get_severity_style(v2);
+    let style1 = get_severity_style(&app.severity, v1);
+    let style2 = get_severity_style(&app.severity, v2);
     
     let mut input_lines = vec![];
     
@@ -513,11 +1658,11 @@ fn draw_severity_tab(f: &mut Frame, app: &mut App, area: Rect) {
             Span::styled("  Min: ", Style::default().fg(FG)),
             Span::styled(format!("{}", app.filter_input_1), style1.add_modifier(Modifier::BOLD)),
             if app.filter_active_input == 0 { Span::styled("█", Style::default().fg(YELLOW)) } else { Span::raw("") },
-            Span::styled(format!(" ({})", get_severity_label(v1)), Style::default().fg(DARK_GRAY)),
+            Span::styled(format!(" ({})", get_severity_label(&app.severity, v1)), Style::default().fg(DARK_GRAY)),
             Span::styled("     Max: ", Style::default().fg(FG)),
             Span::styled(format!("{}", app.filter_input_2), style2.add_modifier(Modifier::BOLD)),
             if app.filter_active_input == 1 { Span::styled("█", Style::default().fg(YELLOW)) } else { Span::raw("") },
-            Span::styled(format!(" ({})", get_severity_label(v2)), Style::default().fg(DARK_GRAY)),
+            Span::styled(format!(" ({})", get_severity_label(&app.severity, v2)), Style::default().fg(DARK_GRAY)),
         ]));
         input_lines.push(Line::from(""));
         input_lines.push(Line::from(vec![
@@ -531,7 +1676,7 @@ fn draw_severity_tab(f: &mut Frame, app: &mut App, area: Rect) {
             Span::styled("  Level: ", Style::default().fg(FG)),
             Span::styled(format!("{}", app.filter_input_1), style1.add_modifier(Modifier::BOLD)),
             Span::styled("█", Style::default().fg(YELLOW)),
-            Span::styled(format!("  ({})", get_severity_label(v1)), Style::default().fg(DARK_GRAY)),
+            Span::styled(format!("  ({})", get_severity_label(&app.severity, v1)), Style::default().fg(DARK_GRAY)),
         ]));
         input_lines.push(Line::from(""));
         input_lines.push(Line::from(vec![
@@ -592,7 +1737,7 @@ fn draw_agent_filter_tab(f: &mut Frame, app: &mut App, area: Rect) {
             .title(Span::styled(format!(" Matching Agents ({}) ", matches.len()), Style::default().fg(CYAN)));
         
         let items: Vec<ListItem> = matches.iter()
-            .map(|name| ListItem::new(format!("  󰒋 {}", name)))
+            .map(|name| ListItem::new(format!("  {}{}", app.icons.pad(Icon::Agent), name)))
             .collect();
         
         let list = List::new(items)
@@ -673,7 +1818,7 @@ fn draw_text_filter_tab(f: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5),
+            Constraint::Length(6),
             Constraint::Min(0),
         ])
         .margin(1)
@@ -695,6 +1840,11 @@ fn draw_text_filter_tab(f: &mut Frame, app: &mut App, area: Rect) {
         Line::from(vec![
             Span::styled("  Full-text search in alert descriptions (case-insensitive)", Style::default().fg(DARK_GRAY)),
         ]),
+        match app.live_search_status() {
+            Some(status) => Line::from(Span::styled(format!("  {}{}", app.icons.pad(Icon::Loading), status), Style::default().fg(CYAN))),
+            None if app.active_view == crate::app::ActiveView::SecurityEvents => Line::from(Span::styled("  Events update as you type", Style::default().fg(DARK_GRAY))),
+            None => Line::from(""),
+        },
     ];
     
     let para = Paragraph::new(content).block(block);
@@ -0,0 +1,42 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Row, Table, Cell, TableState},
+    Frame,
+};
+use crate::app::App;
+use crate::ui::theme::*;
+use crate::ui::icons::Icon;
+
+pub fn draw_rule_files(f: &mut Frame, app: &mut App, area: Rect) {
+    let rows = app.rule_files.iter().map(|r| {
+        let status_style = if r.status == "enabled" { Style::default().fg(GREEN) } else { Style::default().fg(DARK_GRAY) };
+        Row::new(vec![
+            Cell::from(format!("{}{}", app.icons.pad(Icon::Rule), r.filename)),
+            Cell::from(r.relative_dirname.clone()),
+            Cell::from(r.status.clone()).style(status_style),
+        ]).style(Style::default().fg(FG))
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Min(30),
+        Constraint::Length(20),
+        Constraint::Length(10),
+    ])
+    .header(Row::new(vec!["File", "Directory", "Status"]).style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD)))
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(format!(" Local {} Files ({}) │ [v] {} ", app.ruleset_kind.label(), app.rule_files.len(), app.ruleset_kind.toggle().label())))
+    .highlight_style(Style::default()
+        .bg(SELECTION_BG)
+        .add_modifier(Modifier::BOLD))
+    .highlight_symbol(app.icons.pad(Icon::Pointer));
+
+    let mut state = TableState::default();
+    if !app.rule_files.is_empty() {
+        state.select(Some(app.rule_file_index));
+    }
+    f.render_stateful_widget(table, area, &mut state);
+}
@@ -1,32 +1,35 @@
 use ratatui::{
-    layout::{Constraint, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     widgets::{Block, Borders, Paragraph, Row, Table, Cell},
     text::{Line, Span},
     Frame,
 };
-use crate::app::{App, LogViewMode, LogColumn};
+use crate::app::{App, LogViewMode, LogColumn, SortOrder};
+use crate::app::severity::{Severity, SeverityScale};
 use crate::ui::theme::*;
+use crate::ui::icons::Icon;
+use crate::ui::common::{events_summary, format_table_time, table_window, is_narrow, fit_columns};
 
-fn get_severity_info(level: u64) -> (&'static str, ratatui::style::Color) {
-    match level {
-        15..=u64::MAX => ("󰅚 ", VULN_CRITICAL),
-        12..=14 => ("󰀦 ", VULN_HIGH),
-        7..=11 => ("󱈸 ", VULN_MEDIUM),
-        _ => ("󰋼 ", FG),
+fn get_severity_info(scale: &SeverityScale, level: u64) -> (Icon, ratatui::style::Color) {
+    match scale.classify(level) {
+        Severity::Critical => (Icon::Critical, VULN_CRITICAL),
+        Severity::High => (Icon::High, VULN_HIGH),
+        Severity::Medium => (Icon::Medium, VULN_MEDIUM),
+        Severity::Low => (Icon::Low, FG),
     }
 }
 
-fn extract_field(source: &serde_json::Value, column: &LogColumn) -> String {
+fn extract_field(source: &serde_json::Value, column: &LogColumn, app: &App) -> String {
     match column {
         LogColumn::Timestamp => {
             let ts = source.get("@timestamp").and_then(|v| v.as_str()).unwrap_or("Unknown");
-            ts.split('.').next().unwrap_or(ts).replace('T', " ")
+            format_table_time(app, ts)
         }
         LogColumn::Level => {
             let level = source.get("rule").and_then(|r| r.get("level")).and_then(|l| l.as_u64()).unwrap_or(0);
-            let (icon, _) = get_severity_info(level);
-            format!("{}{:02}", icon, level)
+            let (icon, _) = get_severity_info(&app.severity, level);
+            format!("{}{:02}", app.icons.pad(icon), level)
         }
         LogColumn::Agent => {
             source.get("agent")
@@ -85,6 +88,7 @@ fn extract_field(source: &serde_json::Value, column: &LogColumn) -> String {
                 .unwrap_or("-")
                 .to_string()
         }
+        LogColumn::SrcGeo => crate::app::geo::geo_label(source).unwrap_or_else(|| "-".to_string()),
         LogColumn::User => {
             source.get("data")
                 .and_then(|d| d.get("srcuser"))
@@ -119,51 +123,154 @@ fn get_column_width(column: &LogColumn) -> Constraint {
         LogColumn::MitreTactic => Constraint::Length(18),
         LogColumn::SrcIp => Constraint::Length(16),
         LogColumn::DstIp => Constraint::Length(16),
+        LogColumn::SrcGeo => Constraint::Length(22),
         LogColumn::User => Constraint::Length(12),
         LogColumn::Groups => Constraint::Length(20),
     }
 }
 
+/// Order in which columns are dropped on narrow terminals, last to go first.
+fn column_priority(column: &LogColumn) -> u8 {
+    match column {
+        LogColumn::Description => 0,
+        LogColumn::Level => 1,
+        LogColumn::Timestamp => 2,
+        LogColumn::Agent => 3,
+        LogColumn::RuleId => 4,
+        LogColumn::SrcIp => 5,
+        LogColumn::User => 6,
+        LogColumn::MitreId => 7,
+        LogColumn::DstIp => 8,
+        LogColumn::MitreTactic => 9,
+        LogColumn::SrcGeo => 10,
+        LogColumn::Groups => 11,
+    }
+}
+
+/// The visible columns that fit in `area`; all of them unless the terminal is narrow.
+fn fitted_columns(app: &App, area: Rect) -> Vec<LogColumn> {
+    if !is_narrow(area) {
+        return app.visible_log_columns.clone();
+    }
+    let min_widths: Vec<u16> = app.visible_log_columns.iter()
+        .map(|col| match get_column_width(col) {
+            Constraint::Length(n) | Constraint::Min(n) => n,
+            _ => 0,
+        })
+        .collect();
+    let priorities: Vec<u8> = app.visible_log_columns.iter().map(column_priority).collect();
+    // Borders, highlight symbol and the select marker column take seven cells
+    fit_columns(&min_widths, &priorities, area.width.saturating_sub(7))
+        .into_iter()
+        .map(|i| app.visible_log_columns[i])
+        .collect()
+}
+
 pub fn draw_security_events(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+    let filters = app.log_filter.active_filters();
+    let summary = events_summary(app.log_offset, app.logs.len(), app.log_total, app.log_total_exact, &app.format_interval(), &filters);
+    // An empty page caused by the filter should catch the eye
+    let color = if app.log_total == 0 && !filters.is_empty() { YELLOW } else { DARK_GRAY };
+    f.render_widget(Paragraph::new(Span::styled(format!(" {}", summary), Style::default().fg(color))), chunks[1]);
+
     if app.log_view_mode == LogViewMode::Raw {
-        draw_raw_view(f, app, area);
-        return;
+        draw_raw_view(f, app, chunks[0]);
+    } else {
+        draw_events_table(f, app, chunks[0]);
     }
+}
+
+fn draw_events_table(f: &mut Frame, app: &mut App, area: Rect) {
+    let columns = fitted_columns(app, area);
 
     // Build dynamic header based on visible columns
-    let header_cells: Vec<Cell> = app.visible_log_columns.iter()
-        .map(|col| Cell::from(format!(" {} ", col.label()))
-            .style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD)))
+    // Leading narrow column holds the multi-select marker
+    let header_cells: Vec<Cell> = std::iter::once(Cell::from(""))
+        .chain(columns.iter()
+            .map(|col| {
+                let mut label = format!(" {} ", col.label());
+                if *col == app.log_filter.sort.column() {
+                    label.push_str(app.icons.sort_arrow(app.log_filter.sort_order == SortOrder::Asc));
+                }
+                Cell::from(label).style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD))
+            }))
         .collect();
     
     let header = Row::new(header_cells)
         .style(Style::default().bg(BG))
         .height(1);
 
-    // Build rows with only visible columns
-    let rows = app.logs.iter().map(|log| {
+    // Build rows with only visible columns, and only for the rows on screen
+    let window = table_window(app.alert_rows.len(), app.table_state.selected(), area);
+    let rows = app.alert_rows[window.start..window.end].iter().map(|row| {
+        let log = &app.logs[row.log];
         let source = log.get("_source").unwrap_or(log);
         let level = source.get("rule")
             .and_then(|r| r.get("level"))
             .and_then(|l| l.as_u64())
             .unwrap_or(0);
-        let (_, color) = get_severity_info(level);
+        let (_, color) = get_severity_info(&app.severity, level);
+        let color = if app.is_alert_acked(log) { DARK_GRAY } else { color };
+        let is_new = app.is_alert_new(log);
+        let marker = if app.is_alert_selected(log) {
+            Cell::from(app.icons.get(Icon::Selected)).style(Style::default().fg(GREEN))
+        } else if is_new {
+            Cell::from(app.icons.get_or(Icon::New, "+")).style(Style::default().fg(CYAN).add_modifier(Modifier::BOLD))
+        } else {
+            Cell::from("")
+        };
 
-        let cells: Vec<Cell> = app.visible_log_columns.iter()
-            .map(|col| Cell::from(extract_field(source, col)))
+        let cells: Vec<Cell> = std::iter::once(marker)
+            .chain(columns.iter()
+                .map(|col| {
+                    let text = extract_field(source, col, app);
+                    match col {
+                        LogColumn::Description if row.member => Cell::from(format!("  └ {}", text)),
+                        LogColumn::Description if row.count > 1 => {
+                            let open = if app.expanded_floods.contains(crate::app::alert_id(log).unwrap_or_default()) { "▾" } else { "▸" };
+                            Cell::from(Line::from(vec![
+                                Span::styled(format!("{} ×{} ", open, row.count), Style::default().fg(ORANGE).add_modifier(Modifier::BOLD)),
+                                Span::raw(text),
+                            ]))
+                        }
+                        _ => Cell::from(text),
+                    }
+                }))
             .collect();
 
-        Row::new(cells).style(Style::default().fg(color)).height(1)
+        let style = Style::default().fg(color);
+        Row::new(cells).style(if is_new { style.add_modifier(Modifier::BOLD) } else { style }).height(1)
     });
 
     // Build column widths
-    let widths: Vec<Constraint> = app.visible_log_columns.iter()
-        .map(|col| get_column_width(col))
+    let widths: Vec<Constraint> = std::iter::once(Constraint::Length(2))
+        .chain(columns.iter().map(get_column_width))
         .collect();
 
     // Build title with filter status
     let filter_status = build_filter_status(app);
-    let title = format!(" 󱖙 Security Events {} ", filter_status);
+    let selected = if app.selected_alerts.is_empty() { String::new() } else { format!("| {} selected ", app.selected_alerts.len()) };
+    let new_count = app.logs.iter().filter(|l| app.is_alert_new(l)).count();
+    let new = if new_count == 0 { String::new() } else { format!("| {}{} new ", app.icons.pad(Icon::New), new_count) };
+    let collapsed: usize = app.alert_rows.iter().filter(|r| r.count > 1).map(|r| r.count - 1).sum::<usize>()
+        .saturating_sub(app.alert_rows.iter().filter(|r| r.member).count());
+    let grouped = if collapsed == 0 { String::new() } else { format!("| {} repeats folded ", collapsed) };
+    let title = format!(" {}Security Events {} {}{}{}", app.icons.pad(Icon::Alerts), filter_status, selected, new, grouped);
+    let mut title = vec![Span::styled(title, Style::default().fg(PURPLE))];
+    for severity in &app.log_filter.severities {
+        let color = match severity.as_str() {
+            "critical" => VULN_CRITICAL,
+            "high" => VULN_HIGH,
+            "medium" => VULN_MEDIUM,
+            _ => VULN_LOW,
+        };
+        title.push(Span::styled(format!(" {} ", severity.to_uppercase()), Style::default().fg(BG).bg(color).add_modifier(Modifier::BOLD)));
+        title.push(Span::raw(" "));
+    }
 
     let table = Table::new(rows, widths)
         .header(header)
@@ -171,13 +278,13 @@ pub fn draw_security_events(f: &mut Frame, app: &mut App, area: Rect) {
             .borders(Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(Style::default().fg(DARK_GRAY))
-            .title(Span::styled(title, Style::default().fg(PURPLE))))
+            .title(Line::from(title)))
         .highlight_style(Style::default()
             .bg(SELECTION_BG)
             .add_modifier(Modifier::BOLD))
-        .highlight_symbol("󰁔 ");
+        .highlight_symbol(app.icons.pad(Icon::Pointer));
 
-    let mut state = app.table_state.clone();
+    let mut state = window.state;
     f.render_stateful_widget(table, area, &mut state);
 }
 
@@ -224,19 +331,43 @@ fn build_filter_status(app: &App) -> String {
 }
 
 fn draw_raw_view(f: &mut Frame, app: &mut App, area: Rect) {
-    let logs_text: Vec<Line> = app.logs.iter().map(|log| {
-        let formatted = serde_json::to_string(log).unwrap_or_default();
-        Line::from(Span::raw(formatted))
+    // One event per line, starting so the selected event stays on screen
+    let selected = app.table_state.selected().unwrap_or(0);
+    let height = area.height.saturating_sub(2) as usize;
+    let start = if app.raw_wrap {
+        // Wrapped events take a variable number of rows, so pin the selection to the top
+        selected
+    } else if selected >= height {
+        selected + 1 - height
+    } else {
+        0
+    };
+
+    let logs_text: Vec<Line> = app.alert_rows.iter().enumerate().skip(start).map(|(i, row)| {
+        let formatted = serde_json::to_string(&app.logs[row.log]).unwrap_or_default();
+        let mut line = Line::from(Span::raw(formatted));
+        if i == selected {
+            line.style = Style::default().bg(SELECTION_BG);
+        }
+        line
     }).collect();
 
-    let p = Paragraph::new(logs_text)
+    let title = if app.raw_wrap {
+        " Security Alerts (Raw JSON) │ Wrap ".to_string()
+    } else {
+        format!(" Security Alerts (Raw JSON) │ Col {} ", app.raw_scroll_x + 1)
+    };
+    let mut p = Paragraph::new(logs_text)
         .block(Block::default()
             .borders(Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(Style::default().fg(DARK_GRAY))
-            .title(" Security Alerts (Raw JSON) "))
-        .wrap(ratatui::widgets::Wrap { trim: true })
-        .scroll((app.log_offset as u16, 0));
+            .title(title));
+    p = if app.raw_wrap {
+        p.wrap(ratatui::widgets::Wrap { trim: true })
+    } else {
+        p.scroll((0, app.raw_scroll_x))
+    };
         
     f.render_widget(p, area);
 }
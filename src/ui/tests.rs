@@ -0,0 +1,111 @@
+use crate::ui::common::{events_summary, fit_columns, format_kib, osc52_sequence, paging_label, pick_columns, stacked_bar_cells, table_window};
+use crate::ui::icons::{Icon, IconSet};
+use ratatui::layout::Rect;
+
+#[test]
+fn test_table_window_scrolls_with_selection() {
+    // 13 lines high: 2 border lines + 1 header leaves 10 visible rows
+    let area = Rect::new(0, 0, 80, 13);
+
+    let w = table_window(10_000, Some(3), area);
+    assert_eq!((w.start, w.end, w.state.selected()), (0, 10, Some(3)));
+
+    let w = table_window(10_000, Some(5_000), area);
+    assert_eq!((w.start, w.end, w.state.selected()), (4_991, 5_001, Some(9)));
+
+    let w = table_window(10_000, Some(9_999), area);
+    assert_eq!((w.start, w.end, w.state.selected()), (9_990, 10_000, Some(9)));
+}
+
+#[test]
+fn test_table_window_small_and_empty() {
+    let area = Rect::new(0, 0, 80, 13);
+
+    let w = table_window(4, Some(2), area);
+    assert_eq!((w.start, w.end, w.state.selected()), (0, 4, Some(2)));
+
+    // Selection past the end (e.g. after a filter shrank the list) is clamped
+    let w = table_window(4, Some(50), area);
+    assert_eq!((w.start, w.end, w.state.selected()), (0, 4, Some(3)));
+
+    let w = table_window(0, Some(0), area);
+    assert_eq!((w.start, w.end, w.state.selected()), (0, 0, None));
+}
+
+#[test]
+fn test_format_kib() {
+    assert_eq!(format_kib(512), "512 KB");
+    assert_eq!(format_kib(56_320), "55.0 MB");
+    assert_eq!(format_kib(3 * 1024 * 1024), "3.0 GB");
+}
+
+#[test]
+fn test_paging_label() {
+    assert_eq!(paging_label(IconSet::Nerd, 500, Some(3200)), " │ 󰑓 loading 500/3200");
+    assert_eq!(paging_label(IconSet::Ascii, 500, Some(3200)), " │ loading 500/3200");
+    assert_eq!(paging_label(IconSet::Nerd, 3200, Some(3200)), "");
+    assert_eq!(paging_label(IconSet::Nerd, 0, None), "");
+}
+
+#[test]
+fn test_icon_sets() {
+    assert_eq!(IconSet::parse("ASCII"), Some(IconSet::Ascii));
+    assert_eq!(IconSet::parse("none"), Some(IconSet::None));
+    assert_eq!(IconSet::parse("emoji"), None);
+
+    assert_eq!(IconSet::Nerd.pad(Icon::Check), "󰄬 ");
+    assert_eq!(IconSet::Ascii.pad(Icon::Check), "+ ");
+    assert_eq!(IconSet::None.pad(Icon::Check), "");
+    assert_eq!(IconSet::Ascii.pad(Icon::Agent), "");
+
+    // Without icons the tables keep their pointer and sort arrows
+    assert_eq!(IconSet::None.pad(Icon::Pointer), "> ");
+    assert_eq!(IconSet::None.sort_arrow(false), "v");
+    assert_eq!(IconSet::Nerd.get(Icon::New), "󰎔");
+    assert_eq!(IconSet::None.get_or(Icon::Check, "yes"), "yes");
+    assert_eq!(IconSet::Ascii.get_or(Icon::Check, "yes"), "+");
+}
+
+#[test]
+fn test_osc52_sequence() {
+    assert_eq!(osc52_sequence("kill -9 812"), "\x1b]52;c;a2lsbCAtOSA4MTI=\x07");
+    assert_eq!(osc52_sequence("ab"), "\x1b]52;c;YWI=\x07");
+}
+
+#[test]
+fn test_fit_columns_drops_low_priority_first() {
+    let widths = [8, 20, 16, 15, 30];
+    let priorities = [1, 0, 3, 2, 4];
+
+    // Everything fits: 89 cells of columns plus 4 of spacing
+    assert_eq!(fit_columns(&widths, &priorities, 93), vec![0, 1, 2, 3, 4]);
+    // Name, ID and status fit (45 cells); IP would need 17 more
+    assert_eq!(fit_columns(&widths, &priorities, 60), vec![0, 1, 3]);
+    // The most important column stays even when nothing fits
+    assert_eq!(fit_columns(&widths, &priorities, 5), vec![1]);
+
+    assert_eq!(pick_columns(vec!["id", "name", "ip", "status", "os"], &[0, 1, 3]), vec!["id", "name", "status"]);
+}
+
+#[test]
+fn test_stacked_bar_cells() {
+    // Half of max fills half the rows, split between the segments in order
+    assert_eq!(stacked_bar_cells(&[2, 0, 2], 4, 8), vec![Some(0), Some(2), None, None]);
+    assert_eq!(stacked_bar_cells(&[1, 3], 4, 4), vec![Some(0), Some(1), Some(1), Some(1)]);
+    // Tiny counts stay visible, empty bars stay empty
+    assert_eq!(stacked_bar_cells(&[1], 3, 1000), vec![Some(0), None, None]);
+    assert_eq!(stacked_bar_cells(&[0, 0], 2, 10), vec![None, None]);
+}
+
+#[test]
+fn test_events_summary() {
+    let filters = vec!["level ≥7".to_string(), "agent:web".to_string()];
+    assert_eq!(events_summary(50, 50, 1234, true, "24h", &filters), "51–100 of 1234 alerts shown (filtered: level ≥7, agent:web)");
+    assert_eq!(events_summary(0, 50, 10000, false, "24h", &[]), "1–50 of 10000+ alerts shown");
+    assert_eq!(events_summary(0, 0, 0, true, "1h", &[]), "No alerts in the last 1h");
+    assert_eq!(
+        events_summary(0, 0, 0, true, "1h", &filters),
+        "No alerts match in the last 1h (filtered: level ≥7, agent:web) │ [f] changes the filter"
+    );
+    assert_eq!(events_summary(100, 0, 80, true, "1h", &[]), "Past the last page: 80 alerts");
+}
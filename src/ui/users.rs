@@ -0,0 +1,110 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Paragraph, Row, Table, Cell},
+    text::{Line, Span},
+    Frame,
+};
+use crate::app::App;
+use crate::ui::theme::*;
+use crate::ui::icons::Icon;
+
+pub fn draw_user_management(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(55), // Users
+            Constraint::Percentage(45), // Roles / Policies of selected user
+        ])
+        .split(area);
+
+    let rows = app.security_users.iter().map(|u| {
+        let run_as = if u.allow_run_as { format!("{}yes", app.icons.pad(Icon::Check)) } else { "no".to_string() };
+        Row::new(vec![
+            Cell::from(u.id.to_string()),
+            Cell::from(u.username.clone()),
+            Cell::from(app.role_names(&u.roles).join(", ")),
+            Cell::from(run_as),
+        ]).style(Style::default().fg(FG))
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Length(5),
+        Constraint::Length(20),
+        Constraint::Min(20),
+        Constraint::Length(8),
+    ])
+    .header(Row::new(vec!["ID", "Username", "Roles", "Run-as"]).style(Style::default().fg(BLUE).add_modifier(Modifier::BOLD)))
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(format!(" {}API Users ({}) ", app.icons.pad(Icon::User), app.security_users.len())))
+    .highlight_style(Style::default()
+        .bg(SELECTION_BG)
+        .add_modifier(Modifier::BOLD))
+    .highlight_symbol(app.icons.pad(Icon::Pointer));
+
+    let mut state = app.users_table_state.clone();
+    f.render_stateful_widget(table, chunks[0], &mut state);
+
+    // Right side: roles and policies granted to the selected user
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(DARK_GRAY))
+        .title(" Roles & Policies ");
+
+    let Some(user) = app.get_selected_user() else {
+        let placeholder = Paragraph::new("\n\n Select a user to view its roles. ")
+            .alignment(ratatui::layout::Alignment::Center)
+            .style(Style::default().fg(GRAY))
+            .block(block);
+        f.render_widget(placeholder, chunks[1]);
+        return;
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" User: ", Style::default().fg(FG)),
+            Span::styled(user.username.clone(), Style::default().fg(BLUE).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+    ];
+
+    if user.roles.is_empty() {
+        lines.push(Line::from(Span::styled(" No roles assigned", Style::default().fg(DARK_GRAY))));
+    }
+
+    for role_id in &user.roles {
+        let role = app.security_roles.iter().find(|r| r.id == *role_id);
+        let role_name = role.map(|r| r.name.clone()).unwrap_or_else(|| format!("#{}", role_id));
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {}", app.icons.pad(Icon::Shield)), Style::default().fg(YELLOW)),
+            Span::styled(role_name, Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
+        ]));
+
+        for policy_id in role.map(|r| r.policies.clone()).unwrap_or_default() {
+            let policy_name = app.security_policies.iter()
+                .find(|p| p.id == policy_id)
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| format!("#{}", policy_id));
+            lines.push(Line::from(vec![
+                Span::styled("     • ", Style::default().fg(DARK_GRAY)),
+                Span::styled(policy_name, Style::default().fg(FG)),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(" [n] ", Style::default().fg(CYAN)),
+        Span::styled("New user  ", Style::default().fg(DARK_GRAY)),
+        Span::styled("[a] ", Style::default().fg(CYAN)),
+        Span::styled("Assign role  ", Style::default().fg(DARK_GRAY)),
+        Span::styled("[D] ", Style::default().fg(CYAN)),
+        Span::styled("Delete user", Style::default().fg(DARK_GRAY)),
+    ]));
+
+    f.render_widget(Paragraph::new(lines).block(block), chunks[1]);
+}